@@ -0,0 +1,590 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::solana_program::ed25519_program;
+use crate::errors::EventError;
+
+/// Size of the Ed25519Program instruction header for one signature
+/// (num_signatures: u8, padding: u8, then seven u16 offsets)
+const HEADER_SIZE: usize = 16;
+
+const PUBLIC_KEY_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 64;
+
+/// Offsets of the public key, signature and message when they are
+/// stored inline in the Ed25519Program instruction (the layout produced
+/// by the standard client helpers)
+#[cfg(test)]
+const PUBLIC_KEY_OFFSET: usize = HEADER_SIZE;
+#[cfg(test)]
+const SIGNATURE_OFFSET: usize = PUBLIC_KEY_OFFSET + PUBLIC_KEY_SIZE;
+#[cfg(test)]
+const MESSAGE_OFFSET: usize = SIGNATURE_OFFSET + SIGNATURE_SIZE;
+
+/// Instruction index meaning "this instruction" in the offsets header
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Check-in message formats, selected per event by `Event::checkin_message_version`
+///
+/// v1: nonce_hash (32 bytes) || nonce_value (u64 LE), trailing bytes ignored
+/// v2: CHECKIN_DOMAIN_V2 || ticket (32 bytes) || nonce_hash || nonce_value, exact
+/// v3: CHECKIN_DOMAIN_V3 || ticket || event (32 bytes) || nonce_hash ||
+///     nonce_value || expiry_slot (u64 LE), exact
+pub const CHECKIN_MESSAGE_V1: u8 = 1;
+pub const CHECKIN_MESSAGE_V2: u8 = 2;
+pub const CHECKIN_MESSAGE_V3: u8 = 3;
+pub const LATEST_CHECKIN_MESSAGE_VERSION: u8 = CHECKIN_MESSAGE_V3;
+
+/// Domain separators prefixed to v2 and v3 check-in messages
+pub const CHECKIN_DOMAIN_V2: &[u8] = b"mythra:checkin:v2";
+pub const CHECKIN_DOMAIN_V3: &[u8] = b"mythra:checkin:v3";
+
+/// Furthest ahead a v3 message's expiry slot may be (about a minute, like
+/// a recent blockhash), so a signed check-in can't be stockpiled for later
+pub const MAX_CHECKIN_MESSAGE_SLOTS: u64 = 150;
+
+/// Load the message signed by `expected_signer` in the preceding instruction
+///
+/// The Ed25519Program instruction must immediately precede the current one
+/// and carry exactly one signature with its key, signature and message
+/// inline. The runtime has already verified the signature by the time this
+/// instruction runs; this checks it was made by the expected key and
+/// returns the signed message for the caller to compare.
+pub fn load_signed_message(
+    instructions_sysvar: &AccountInfo,
+    expected_signer: &Pubkey,
+) -> Result<Vec<u8>> {
+    // Load the current instruction index
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    
+    // Ed25519 instruction should be immediately before this instruction
+    if current_index == 0 {
+        return Err(EventError::Ed25519InstructionMissing.into());
+    }
+    
+    let ed25519_ix = load_instruction_at_checked(current_index - 1, instructions_sysvar)?;
+    
+    // Verify it's the Ed25519Program
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID,
+        EventError::Ed25519InstructionMissing
+    );
+    
+    let message = parse_signed_message(&ed25519_ix.data, (current_index - 1) as u16, expected_signer)?;
+    
+    msg!("Ed25519 signature verified successfully");
+    msg!("Signer: {}", expected_signer);
+    
+    Ok(message.to_vec())
+}
+
+/// Offsets header of a single Ed25519Program signature
+///
+/// Mirrors the runtime's `Ed25519SignatureOffsets`: each region is an
+/// offset into the data of the instruction at the matching index, where
+/// `u16::MAX` means the Ed25519Program instruction itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    /// Parse the header of Ed25519Program instruction data, requiring
+    /// exactly one signature
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= HEADER_SIZE && data[0] == 1,
+            EventError::InvalidSignature
+        );
+        
+        let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+        
+        // Offsets start after num_signatures and padding
+        Ok(Self {
+            signature_offset: read_u16(2),
+            signature_instruction_index: read_u16(4),
+            public_key_offset: read_u16(6),
+            public_key_instruction_index: read_u16(8),
+            message_data_offset: read_u16(10),
+            message_data_size: read_u16(12),
+            message_instruction_index: read_u16(14),
+        })
+    }
+    
+    /// Whether every region lives in the Ed25519Program instruction itself
+    pub fn is_self_contained(&self, own_index: u16) -> bool {
+        let is_own = |index: u16| index == CURRENT_INSTRUCTION || index == own_index;
+        
+        is_own(self.signature_instruction_index)
+            && is_own(self.public_key_instruction_index)
+            && is_own(self.message_instruction_index)
+    }
+}
+
+/// Bounds-checked slice of `len` bytes at `offset`
+fn region(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    let end = start.checked_add(len).ok_or(EventError::InvalidSignature)?;
+    
+    data.get(start..end).ok_or(EventError::InvalidSignature.into())
+}
+
+/// Extract the signed message from Ed25519Program instruction data
+///
+/// `own_index` is the position of the Ed25519Program instruction in the
+/// transaction. Rejects anything but a single signature whose key,
+/// signature and message all live in that instruction, so the offsets
+/// can't point the runtime at a different key or message than the one
+/// checked here. Regions are read from the declared offsets.
+pub fn parse_signed_message<'a>(
+    data: &'a [u8],
+    own_index: u16,
+    expected_signer: &Pubkey,
+) -> Result<&'a [u8]> {
+    let offsets = Ed25519SignatureOffsets::parse(data)?;
+    
+    require!(
+        offsets.is_self_contained(own_index),
+        EventError::InvalidSignature
+    );
+    
+    // Every declared region must be in bounds
+    region(data, offsets.signature_offset, SIGNATURE_SIZE)?;
+    let public_key = region(data, offsets.public_key_offset, PUBLIC_KEY_SIZE)?;
+    let message = region(data, offsets.message_data_offset, offsets.message_data_size as usize)?;
+    
+    // Verify public key matches expected signer
+    let pubkey = Pubkey::try_from(public_key)
+        .map_err(|_| EventError::InvalidSignature)?;
+    require!(
+        pubkey == *expected_signer,
+        EventError::InvalidSignature
+    );
+    
+    Ok(message)
+}
+
+/// What a check-in message is checked against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckinContext<'a> {
+    pub ticket: &'a Pubkey,
+    pub event: &'a Pubkey,
+    pub nonce_hash: &'a [u8; 32],
+    pub nonce_value: u64,
+    pub current_slot: u64,
+}
+
+/// Components of a v3 check-in message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckinMessageV3 {
+    pub ticket: Pubkey,
+    pub event: Pubkey,
+    pub nonce_hash: [u8; 32],
+    pub nonce_value: u64,
+    pub expiry_slot: u64,
+}
+
+impl CheckinMessageV3 {
+    pub const LEN: usize = CHECKIN_DOMAIN_V3.len() + 32 + 32 + 32 + 8 + 8;
+    
+    /// Split a signed message into its components
+    ///
+    /// Only the layout is checked here (exact length and domain); the
+    /// components are compared by `verify`.
+    pub fn parse(message: &[u8]) -> Result<Self> {
+        require!(
+            message.len() == Self::LEN && message.starts_with(CHECKIN_DOMAIN_V3),
+            EventError::MalformedCheckinMessage
+        );
+        
+        let body = &message[CHECKIN_DOMAIN_V3.len()..];
+        let read_u64 = |offset: usize| u64::from_le_bytes(body[offset..offset + 8].try_into().unwrap());
+        
+        Ok(Self {
+            ticket: Pubkey::try_from(&body[0..32]).unwrap(),
+            event: Pubkey::try_from(&body[32..64]).unwrap(),
+            nonce_hash: body[64..96].try_into().unwrap(),
+            nonce_value: read_u64(96),
+            expiry_slot: read_u64(104),
+        })
+    }
+    
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::LEN);
+        message.extend_from_slice(CHECKIN_DOMAIN_V3);
+        message.extend_from_slice(self.ticket.as_ref());
+        message.extend_from_slice(self.event.as_ref());
+        message.extend_from_slice(&self.nonce_hash);
+        message.extend_from_slice(&self.nonce_value.to_le_bytes());
+        message.extend_from_slice(&self.expiry_slot.to_le_bytes());
+        message
+    }
+    
+    /// Check each component, failing with an error naming the first one
+    /// that doesn't match
+    pub fn verify(&self, context: &CheckinContext) -> Result<()> {
+        require_keys_eq!(
+            self.ticket,
+            *context.ticket,
+            EventError::CheckinMessageTicketMismatch
+        );
+        require_keys_eq!(
+            self.event,
+            *context.event,
+            EventError::CheckinMessageEventMismatch
+        );
+        require!(
+            self.nonce_hash == *context.nonce_hash && self.nonce_value == context.nonce_value,
+            EventError::CheckinMessageNonceMismatch
+        );
+        require!(
+            context.current_slot <= self.expiry_slot,
+            EventError::CheckinMessageExpired
+        );
+        require!(
+            self.expiry_slot - context.current_slot <= MAX_CHECKIN_MESSAGE_SLOTS,
+            EventError::CheckinMessageExpiryTooFar
+        );
+        
+        Ok(())
+    }
+}
+
+/// Build the check-in message a ticket owner signs for the given version
+///
+/// `expiry_slot` is only part of v3 messages.
+pub fn checkin_message(
+    version: u8,
+    context: &CheckinContext,
+    expiry_slot: u64,
+) -> Result<Vec<u8>> {
+    let mut message = Vec::with_capacity(CheckinMessageV3::LEN);
+    
+    match version {
+        CHECKIN_MESSAGE_V1 => {}
+        CHECKIN_MESSAGE_V2 => {
+            message.extend_from_slice(CHECKIN_DOMAIN_V2);
+            message.extend_from_slice(context.ticket.as_ref());
+        }
+        CHECKIN_MESSAGE_V3 => {
+            return Ok(CheckinMessageV3 {
+                ticket: *context.ticket,
+                event: *context.event,
+                nonce_hash: *context.nonce_hash,
+                nonce_value: context.nonce_value,
+                expiry_slot,
+            }.to_bytes());
+        }
+        _ => return Err(EventError::InvalidCheckinMessageVersion.into()),
+    }
+    
+    message.extend_from_slice(context.nonce_hash);
+    message.extend_from_slice(&context.nonce_value.to_le_bytes());
+    
+    Ok(message)
+}
+
+/// Check a signed check-in message is in the format required by `version`
+pub fn verify_checkin_message(
+    version: u8,
+    message: &[u8],
+    context: &CheckinContext,
+) -> Result<()> {
+    if version == CHECKIN_MESSAGE_V3 {
+        return CheckinMessageV3::parse(message)?.verify(context);
+    }
+    
+    let expected = checkin_message(version, context, 0)?;
+    
+    let matches = match version {
+        // Legacy clients may append data after the nonce value
+        CHECKIN_MESSAGE_V1 => message.starts_with(&expected),
+        _ => message == expected.as_slice(),
+    };
+    require!(
+        matches,
+        EventError::InvalidSignature
+    );
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Build Ed25519Program instruction data the way the client helpers do
+    fn ed25519_data(signer: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let mut data = vec![1u8, 0];
+        for value in [
+            SIGNATURE_OFFSET as u16,
+            CURRENT_INSTRUCTION,
+            PUBLIC_KEY_OFFSET as u16,
+            CURRENT_INSTRUCTION,
+            MESSAGE_OFFSET as u16,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0u8; 64]); // signature (checked by the runtime)
+        data.extend_from_slice(message);
+        data
+    }
+    
+    #[test]
+    fn test_parse_signed_message() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_data(&signer, b"hello");
+        
+        assert_eq!(parse_signed_message(&data, 0, &signer).unwrap(), b"hello");
+    }
+    
+    #[test]
+    fn test_wrong_signer_rejected() {
+        let data = ed25519_data(&Pubkey::new_unique(), b"hello");
+        
+        assert_eq!(
+            parse_signed_message(&data, 0, &Pubkey::new_unique()).unwrap_err(),
+            EventError::InvalidSignature.into()
+        );
+    }
+    
+    #[test]
+    fn test_out_of_line_offsets_rejected() {
+        let signer = Pubkey::new_unique();
+        
+        // Public key taken from another instruction
+        let mut data = ed25519_data(&signer, b"hello");
+        data[8..10].copy_from_slice(&5u16.to_le_bytes());
+        assert!(parse_signed_message(&data, 0, &signer).is_err());
+        
+        // Message size running past the end of the data
+        let mut data = ed25519_data(&signer, b"hello");
+        data[12..14].copy_from_slice(&100u16.to_le_bytes());
+        assert!(parse_signed_message(&data, 0, &signer).is_err());
+        
+        // Multiple signatures
+        let mut data = ed25519_data(&signer, b"hello");
+        data[0] = 2;
+        assert!(parse_signed_message(&data, 0, &signer).is_err());
+        
+        // Signature or message taken from another instruction
+        let mut data = ed25519_data(&signer, b"hello");
+        data[4..6].copy_from_slice(&3u16.to_le_bytes());
+        assert!(parse_signed_message(&data, 0, &signer).is_err());
+        let mut data = ed25519_data(&signer, b"hello");
+        data[14..16].copy_from_slice(&3u16.to_le_bytes());
+        assert!(parse_signed_message(&data, 0, &signer).is_err());
+    }
+    
+    #[test]
+    fn test_own_instruction_index_accepted() {
+        let signer = Pubkey::new_unique();
+        let mut data = ed25519_data(&signer, b"hello");
+        for index_field in [4, 8, 14] {
+            data[index_field..index_field + 2].copy_from_slice(&2u16.to_le_bytes());
+        }
+        
+        // Explicit index of the ed25519 instruction itself is the same as u16::MAX
+        assert_eq!(parse_signed_message(&data, 2, &signer).unwrap(), b"hello");
+        assert!(parse_signed_message(&data, 1, &signer).is_err());
+    }
+    
+    #[test]
+    fn test_reads_declared_offsets() {
+        let signer = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+        
+        // Header points the key at the second key slot rather than offset 16
+        let mut data = ed25519_data(&attacker, b"hello");
+        let moved_key_offset = data.len() as u16;
+        data.extend_from_slice(signer.as_ref());
+        data[6..8].copy_from_slice(&moved_key_offset.to_le_bytes());
+        
+        // The key the runtime verified is the one checked, not the bytes at 16
+        assert_eq!(parse_signed_message(&data, 0, &signer).unwrap(), b"hello");
+        assert!(parse_signed_message(&data, 0, &attacker).is_err());
+    }
+    
+    #[test]
+    fn test_malformed_data_rejected() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_data(&signer, b"hello");
+        
+        // Truncated header and empty data
+        assert!(parse_signed_message(&[], 0, &signer).is_err());
+        assert!(parse_signed_message(&data[..HEADER_SIZE - 1], 0, &signer).is_err());
+        
+        // Header intact but the regions it declares are cut off
+        assert!(parse_signed_message(&data[..SIGNATURE_OFFSET], 0, &signer).is_err());
+        assert!(parse_signed_message(&data[..MESSAGE_OFFSET + 2], 0, &signer).is_err());
+        
+        // Offsets past the end of the data
+        for offset_field in [2, 6, 10] {
+            let mut data = ed25519_data(&signer, b"hello");
+            data[offset_field..offset_field + 2].copy_from_slice(&u16::MAX.to_le_bytes());
+            assert!(parse_signed_message(&data, 0, &signer).is_err());
+        }
+        
+        // Zero signatures
+        let mut data = ed25519_data(&signer, b"hello");
+        data[0] = 0;
+        assert!(parse_signed_message(&data, 0, &signer).is_err());
+    }
+    
+    fn context<'a>(ticket: &'a Pubkey, event: &'a Pubkey, nonce_hash: &'a [u8; 32]) -> CheckinContext<'a> {
+        CheckinContext {
+            ticket,
+            event,
+            nonce_hash,
+            nonce_value: 42,
+            current_slot: 1_000,
+        }
+    }
+    
+    #[test]
+    fn test_checkin_message_versions() {
+        let (ticket, event) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let context = context(&ticket, &event, &[1; 32]);
+        let other_value = CheckinContext { nonce_value: 43, ..context };
+        
+        for version in [CHECKIN_MESSAGE_V1, CHECKIN_MESSAGE_V2, CHECKIN_MESSAGE_V3] {
+            let message = checkin_message(version, &context, 1_010).unwrap();
+            assert!(verify_checkin_message(version, &message, &context).is_ok());
+            assert!(verify_checkin_message(version, &message, &other_value).is_err());
+        }
+        
+        // Unknown versions are rejected outright
+        assert_eq!(
+            checkin_message(0, &context, 0).unwrap_err(),
+            EventError::InvalidCheckinMessageVersion.into()
+        );
+        assert!(checkin_message(LATEST_CHECKIN_MESSAGE_VERSION + 1, &context, 0).is_err());
+    }
+    
+    #[test]
+    fn test_v1_payload_rejected_for_v2_event() {
+        let (ticket, event) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let context = context(&ticket, &event, &[1; 32]);
+        let v1 = checkin_message(CHECKIN_MESSAGE_V1, &context, 0).unwrap();
+        
+        assert_eq!(
+            verify_checkin_message(CHECKIN_MESSAGE_V2, &v1, &context).unwrap_err(),
+            EventError::InvalidSignature.into()
+        );
+        
+        // And the other way round
+        let v2 = checkin_message(CHECKIN_MESSAGE_V2, &context, 0).unwrap();
+        assert!(verify_checkin_message(CHECKIN_MESSAGE_V1, &v2, &context).is_err());
+    }
+    
+    #[test]
+    fn test_v2_payload_bound_to_ticket() {
+        let (ticket, event) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let message = checkin_message(CHECKIN_MESSAGE_V2, &context(&ticket, &event, &[1; 32]), 0).unwrap();
+        
+        let other_ticket = Pubkey::new_unique();
+        assert!(verify_checkin_message(CHECKIN_MESSAGE_V2, &message, &context(&other_ticket, &event, &[1; 32])).is_err());
+    }
+    
+    #[test]
+    fn test_v3_components_reported() {
+        let (ticket, event) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let context = context(&ticket, &event, &[1; 32]);
+        let message = checkin_message(CHECKIN_MESSAGE_V3, &context, 1_010).unwrap();
+        let verify = |context: &CheckinContext| verify_checkin_message(CHECKIN_MESSAGE_V3, &message, context);
+        
+        let other = Pubkey::new_unique();
+        assert_eq!(
+            verify(&CheckinContext { ticket: &other, ..context }).unwrap_err(),
+            EventError::CheckinMessageTicketMismatch.into()
+        );
+        assert_eq!(
+            verify(&CheckinContext { event: &other, ..context }).unwrap_err(),
+            EventError::CheckinMessageEventMismatch.into()
+        );
+        assert_eq!(
+            verify(&CheckinContext { nonce_hash: &[2; 32], ..context }).unwrap_err(),
+            EventError::CheckinMessageNonceMismatch.into()
+        );
+        assert_eq!(
+            verify(&CheckinContext { nonce_value: 43, ..context }).unwrap_err(),
+            EventError::CheckinMessageNonceMismatch.into()
+        );
+    }
+    
+    #[test]
+    fn test_v3_expiry_slot() {
+        let (ticket, event) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let context = context(&ticket, &event, &[1; 32]);
+        let at_slot = |expiry_slot: u64| {
+            let message = checkin_message(CHECKIN_MESSAGE_V3, &context, expiry_slot).unwrap();
+            verify_checkin_message(CHECKIN_MESSAGE_V3, &message, &context)
+        };
+        
+        // Valid through the expiry slot itself
+        assert!(at_slot(1_000).is_ok());
+        assert!(at_slot(1_000 + MAX_CHECKIN_MESSAGE_SLOTS).is_ok());
+        assert_eq!(at_slot(999).unwrap_err(), EventError::CheckinMessageExpired.into());
+        
+        // Messages can't be signed far ahead of time
+        assert_eq!(
+            at_slot(1_001 + MAX_CHECKIN_MESSAGE_SLOTS).unwrap_err(),
+            EventError::CheckinMessageExpiryTooFar.into()
+        );
+        assert_eq!(at_slot(u64::MAX).unwrap_err(), EventError::CheckinMessageExpiryTooFar.into());
+    }
+    
+    #[test]
+    fn test_v3_truncated_and_reordered_rejected() {
+        let (ticket, event) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let context = context(&ticket, &event, &[1; 32]);
+        let message = checkin_message(CHECKIN_MESSAGE_V3, &context, 1_010).unwrap();
+        assert_eq!(CheckinMessageV3::parse(&message).unwrap().expiry_slot, 1_010);
+        
+        // Truncated anywhere, or with bytes appended
+        for len in [0, CHECKIN_DOMAIN_V3.len(), CheckinMessageV3::LEN - 8, CheckinMessageV3::LEN - 1] {
+            assert_eq!(
+                CheckinMessageV3::parse(&message[..len]).unwrap_err(),
+                EventError::MalformedCheckinMessage.into()
+            );
+        }
+        let mut extended = message.clone();
+        extended.push(0);
+        assert!(CheckinMessageV3::parse(&extended).is_err());
+        
+        // Wrong domain: a v2 message of the same length is not a v3 one
+        let mut wrong_domain = message.clone();
+        wrong_domain[..CHECKIN_DOMAIN_V2.len()].copy_from_slice(CHECKIN_DOMAIN_V2);
+        assert!(CheckinMessageV3::parse(&wrong_domain).is_err());
+        
+        // Ticket and event swapped: parses, but the components don't match
+        let domain = CHECKIN_DOMAIN_V3.len();
+        let mut swapped = message.clone();
+        swapped[domain..domain + 32].copy_from_slice(event.as_ref());
+        swapped[domain + 32..domain + 64].copy_from_slice(ticket.as_ref());
+        assert_eq!(
+            verify_checkin_message(CHECKIN_MESSAGE_V3, &swapped, &context).unwrap_err(),
+            EventError::CheckinMessageTicketMismatch.into()
+        );
+        
+        // Nonce value and expiry slot swapped
+        let tail = CheckinMessageV3::LEN - 16;
+        let mut swapped = message.clone();
+        swapped[tail..tail + 8].copy_from_slice(&1_010u64.to_le_bytes());
+        swapped[tail + 8..].copy_from_slice(&42u64.to_le_bytes());
+        assert_eq!(
+            verify_checkin_message(CHECKIN_MESSAGE_V3, &swapped, &context).unwrap_err(),
+            EventError::CheckinMessageNonceMismatch.into()
+        );
+    }
+}
@@ -206,4 +206,423 @@ pub enum EventError {
     
     #[msg("Invalid event")]
     InvalidEvent,
+    
+    // Cancellation errors
+    #[msg("Event has already been canceled")]
+    EventAlreadyCanceled,
+    
+    #[msg("Event has not been canceled")]
+    EventNotCanceled,
+    
+    #[msg("Event is not in refund shortfall mode")]
+    ShortfallModeNotActive,
+    
+    #[msg("Refund claim registration window has closed")]
+    RefundClaimWindowClosed,
+    
+    #[msg("Refund claim registration window is still open")]
+    RefundClaimWindowOpen,
+    
+    #[msg("Refund claims have not been settled yet")]
+    RefundClaimsNotSettled,
+    
+    #[msg("Refund claims have already been settled")]
+    RefundClaimsAlreadySettled,
+    
+    #[msg("Refund claim has already been paid")]
+    RefundClaimAlreadyPaid,
+    
+    // Platform config errors
+    #[msg("Only the platform admin can perform this action")]
+    UnauthorizedPlatformAdmin,
+    
+    #[msg("Profit split must sum to 10000 basis points")]
+    InvalidProfitSplit,
+    
+    #[msg("Platform share is below the platform minimum")]
+    PlatformShareTooLow,
+    
+    #[msg("Backer share is below the platform minimum")]
+    BackerShareTooLow,
+    
+    #[msg("Purchase would exceed the per-wallet limit for this tier")]
+    PurchaseLimitExceeded,
+    
+    #[msg("Treasury must be a wallet, not an escrow PDA or program account")]
+    InvalidTreasuryAccount,
+    
+    #[msg("Budget must have between 1 and 8 milestones")]
+    InvalidMilestoneCount,
+    
+    // Attendance snapshot errors
+    #[msg("Ticket has not been checked in")]
+    TicketNotCheckedIn,
+    
+    #[msg("Attendance page index does not match the current page")]
+    InvalidAttendancePage,
+    
+    #[msg("Only the organizer or platform admin can trigger distribution")]
+    UnauthorizedDistributionTrigger,
+    
+    #[msg("Refund amount exceeds the price paid for the ticket")]
+    RefundExceedsPricePaid,
+    
+    // Ticket index errors
+    #[msg("Ticket index account is required for this event")]
+    TicketIndexRequired,
+    
+    #[msg("Ticket index is full")]
+    TicketIndexFull,
+    
+    #[msg("Event is sold out")]
+    EventSoldOut,
+    
+    #[msg("Program is paused")]
+    ProgramPaused,
+    
+    #[msg("Platform treasury does not match the platform config")]
+    InvalidPlatformTreasury,
+    
+    #[msg("Cannot shorten an event after tickets have been sold")]
+    CannotShortenLiveEvent,
+    
+    // Tier sale window errors
+    #[msg("Sale window must start before it ends and close by event start")]
+    InvalidSaleWindow,
+    
+    #[msg("Ticket sales for this tier have not started")]
+    SaleNotStarted,
+    
+    #[msg("Ticket sales for this tier have ended")]
+    SaleEnded,
+    
+    #[msg("Tier cannot be changed after tickets have been sold")]
+    TierHasSales,
+    
+    // Order errors
+    #[msg("Order ID exceeds maximum length")]
+    OrderIdTooLong,
+    
+    #[msg("Order has already been fulfilled")]
+    OrderAlreadyFulfilled,
+    
+    #[msg("Only the buyer, or the event authority after the timeout, can cancel this order")]
+    UnauthorizedOrderCancel,
+    
+    #[msg("Order does not match the provided accounts")]
+    InvalidOrder,
+    
+    #[msg("Campaign has not reached its goal or deadline yet")]
+    CampaignNotReadyToFinalize,
+    
+    // Batch purchase errors
+    #[msg("Batch must contain between 1 and 5 tickets")]
+    InvalidBatchSize,
+    
+    #[msg("Remaining accounts do not match the batch layout")]
+    InvalidBatchAccounts,
+    
+    // Voting reminder errors
+    #[msg("Voting reminder can only be emitted in the last 24 hours of voting")]
+    ReminderWindowNotOpen,
+    
+    #[msg("Voting reminder has already been emitted")]
+    ReminderAlreadyEmitted,
+    
+    #[msg("Event has ended")]
+    EventEnded,
+    
+    #[msg("Tickets cannot be sold for a canceled event")]
+    EventCanceledNoSales,
+    
+    #[msg("Nonce expiry must be between 1 second and the maximum nonce lifetime")]
+    InvalidNonceExpiry,
+    
+    #[msg("Only the ticket owner or event authority can register a check-in nonce")]
+    UnauthorizedNonceRegistration,
+    
+    #[msg("Nonce does not belong to this ticket or payer")]
+    InvalidNonce,
+    
+    #[msg("Nonce has not expired yet")]
+    NonceNotExpired,
+    
+    #[msg("Revision would drop or reduce a milestone that already released funds")]
+    CannotReviseReleasedMilestone,
+    
+    #[msg("Unsupported check-in message version or version not increasing")]
+    InvalidCheckinMessageVersion,
+    
+    #[msg("Ticket has not been checked in")]
+    TicketNotUsed,
+    
+    #[msg("Event account is too small for its serialized fields")]
+    EventAccountTooSmall,
+    
+    #[msg("Ticket tier does not match the ticket")]
+    InvalidTier,
+    
+    #[msg("Max uses must be at least 1")]
+    InvalidMaxUses,
+    
+    #[msg("Ticket sales are halted for this event")]
+    SalesHalted,
+    
+    #[msg("Ticket sales are not halted")]
+    SalesNotHalted,
+    
+    #[msg("Retired supply must be between 1 and the tier's unsold seats")]
+    InvalidRetireAmount,
+    
+    #[msg("Campaign escrow still holds funds owed to contributors")]
+    CampaignEscrowNotCloseable,
+    
+    #[msg("Only campaign backers can buy tickets during the presale")]
+    BackerPresaleOnly,
+    
+    #[msg("Campaign still has unsettled refunds or claims")]
+    CampaignNotSettled,
+    
+    #[msg("Contribution still has an unclaimed refund or profit share")]
+    ContributionNotSettled,
+    
+    #[msg("Milestone releases are closed this long after the event ended")]
+    MilestoneReleaseWindowClosed,
+    
+    #[msg("Budget is still pending or needed for milestone releases")]
+    BudgetNotCloseable,
+    
+    #[msg("Budget voting has not been finalized")]
+    BudgetNotFinalized,
+    
+    #[msg("Locale must be a unique lowercase two-letter ISO 639-1 code")]
+    InvalidLocale,
+    
+    #[msg("Too many metadata locales")]
+    TooManyLocales,
+    
+    #[msg("Campaign deadline has already been extended")]
+    DeadlineAlreadyExtended,
+    
+    #[msg("New deadline must be later than the current deadline")]
+    DeadlineNotExtended,
+    
+    #[msg("Refunds have already been opened for this campaign")]
+    RefundsAlreadyOpen,
+    
+    #[msg("Contribution was already refunded under a different refund mode")]
+    RefundModeMismatch,
+    
+    #[msg("Ticket is pre-registered stock and has not been sold")]
+    TicketInactive,
+    
+    #[msg("Ticket has already been activated")]
+    TicketAlreadyActive,
+    
+    #[msg("Promo window must be non-empty and end in the future")]
+    InvalidPromoWindow,
+    
+    #[msg("A promo is currently running on this tier")]
+    PromoAlreadyRunning,
+    
+    #[msg("Campaigns can only be denominated in lamports")]
+    UnsupportedDenomination,
+    
+    #[msg("Distribution grace period after the event end has not passed")]
+    DistributionGracePeriodActive,
+    
+    #[msg("Budget was rejected too recently to be revised")]
+    RevisionCooldownActive,
+    
+    #[msg("Revised budget has the same total and milestones as the rejected one")]
+    IdenticalBudgetRevision,
+    
+    #[msg("Campaign has no loss recovery pool")]
+    NoLossRecovery,
+    
+    #[msg("Loss recovery share has already been claimed")]
+    LossAlreadyRecovered,
+    
+    #[msg("Backer claim deadline has passed")]
+    ClaimDeadlinePassed,
+    
+    #[msg("Backer claim deadline has not passed yet")]
+    ClaimDeadlineNotPassed,
+    
+    #[msg("Unclaimed backer funds have already been swept")]
+    BackerPoolAlreadySwept,
+    
+    #[msg("Sweep recipient does not match the unclaimed profit policy")]
+    InvalidSweepRecipient,
+    
+    #[msg("Milestone is under dispute")]
+    MilestoneDisputed,
+    
+    #[msg("Milestone was voided by an upheld dispute")]
+    MilestoneVoided,
+    
+    #[msg("Milestone has already been disputed")]
+    MilestoneAlreadyDisputed,
+    
+    #[msg("Dispute is not open")]
+    DisputeNotOpen,
+    
+    #[msg("Start timestamp must be in the future")]
+    StartTimestampInPast,
+    
+    #[msg("Event start can only be moved later")]
+    CannotMoveStartEarlier,
+    
+    #[msg("Ticket is not eligible for a postponement refund")]
+    PostponementRefundNotAvailable,
+    
+    #[msg("Wallet is blocked from this event")]
+    WalletBlocked,
+    
+    #[msg("Blocklist entry does not match the event and wallet")]
+    InvalidBlocklistEntry,
+    
+    #[msg("No authority transfer has been proposed for this event")]
+    NoPendingAuthority,
+    
+    #[msg("Signer is not the proposed event authority")]
+    NotPendingAuthority,
+    
+    #[msg("Delegate permissions must be a non-empty set of known flags")]
+    InvalidDelegatePermissions,
+    
+    #[msg("No platform fees to settle")]
+    NoPlatformFeesToSettle,
+    
+    #[msg("Platform fees can only be settled once the event's refund window has closed")]
+    PlatformFeesNotSettleable,
+    
+    #[msg("Ticket tier is closed")]
+    TierClosed,
+    
+    #[msg("Free tiers require a per-wallet limit")]
+    FreeTierRequiresWalletLimit,
+    
+    #[msg("Merkle proof does not match the tier allowlist")]
+    MerkleProofInvalid,
+    
+    #[msg("Resale price exceeds the tier's maximum")]
+    ResalePriceExceedsCap,
+    
+    #[msg("Tickets from this tier are soulbound and cannot be transferred")]
+    TicketNotTransferable,
+    
+    #[msg("Soulbound tickets need a mint whose freeze authority is the program's ticket freeze PDA")]
+    SoulboundMintNotFreezable,
+    
+    #[msg("Tickets can only be upgraded to a higher-priced tier of the same kind")]
+    InvalidTierUpgrade,
+    
+    #[msg("Seat numbers start at 1 and only apply to seated tiers")]
+    InvalidSeatNumber,
+    
+    #[msg("The seat assignment account for this ticket's seat is required")]
+    SeatAssignmentRequired,
+    
+    #[msg("This ticket has already been given as a gift")]
+    GiftLimitReached,
+    
+    #[msg("Claim deadline must be in the future and at most 30 days away")]
+    InvalidClaimExpiry,
+    
+    #[msg("Claim secret does not match this pending transfer")]
+    InvalidClaimPreimage,
+    
+    #[msg("Pending transfer has expired and can no longer be claimed")]
+    ClaimExpired,
+    
+    #[msg("Pending transfer can only be canceled once its claim deadline has passed")]
+    ClaimNotExpired,
+    
+    #[msg("Metadata account, metadata authority and Token Metadata program are required to create ticket metadata")]
+    MetadataAccountsRequired,
+    
+    #[msg("Metadata account is not the Token Metadata PDA for this mint")]
+    InvalidMetadataAccount,
+    
+    #[msg("Check-in message does not have the layout required by the event's message version")]
+    MalformedCheckinMessage,
+    
+    #[msg("Check-in message was signed for a different ticket")]
+    CheckinMessageTicketMismatch,
+    
+    #[msg("Check-in message was signed for a different event")]
+    CheckinMessageEventMismatch,
+    
+    #[msg("Check-in message nonce hash or value does not match")]
+    CheckinMessageNonceMismatch,
+    
+    #[msg("Check-in message expiry slot has passed")]
+    CheckinMessageExpired,
+    
+    #[msg("Check-in message expiry slot is too far in the future")]
+    CheckinMessageExpiryTooFar,
+    
+    #[msg("Batch check-in must contain between 1 and 8 tickets")]
+    InvalidCheckinBatchSize,
+    
+    #[msg("Check-in delegate must differ from the ticket owner")]
+    InvalidCheckinDelegate,
+    
+    #[msg("Refund policy steps must be at most 4, with decreasing cutoffs and refund percentages of at most 100%")]
+    InvalidRefundPolicy,
+    
+    #[msg("Refund policy can't change once tickets have been sold")]
+    RefundPolicyLocked,
+    
+    #[msg("No refund is available under the event's refund policy")]
+    RefundNotAvailable,
+    
+    #[msg("Refund fee must be at most 10000 basis points")]
+    InvalidRefundFee,
+    
+    #[msg("Cancellation refund batch must contain between 1 and 6 tickets")]
+    InvalidRefundBatchSize,
+    
+    #[msg("Refunds for an underfunded cancellation are paid through refund claims")]
+    ShortfallRefundsByClaim,
+    
+    #[msg("The ticket NFT is not delegated to the program; the buyer must sign the refund")]
+    RefundNeedsBuyerSignature,
+    
+    #[msg("Only the ticket owner, or the event authority after the close grace period, can close this ticket")]
+    UnauthorizedTicketClose,
+    
+    #[msg("Contribution carries no voting power at the budget snapshot")]
+    NoVotingPowerAtSnapshot,
+    
+    #[msg("Budget doesn't use per-milestone release votes")]
+    MilestoneVoteNotRequired,
+    
+    #[msg("Milestone release vote has already been opened")]
+    MilestoneVoteAlreadyOpened,
+    
+    #[msg("Milestone release vote is not open")]
+    MilestoneVoteNotOpen,
+    
+    #[msg("Milestone release hasn't been approved by backers")]
+    MilestoneNotApproved,
+    
+    #[msg("Voting period must be positive")]
+    InvalidVotingPeriod,
+    
+    #[msg("Budget turnout floor can't exceed 100%")]
+    InvalidMinTurnout,
+    
+    #[msg("Ticket of a canceled event must be refunded before it is swept")]
+    TicketNotRefunded,
+    
+    #[msg("Only the program upgrade authority can initialize the platform config")]
+    UnauthorizedConfigInit,
+    
+    #[msg("Budget has been replaced by a revision or was never submitted for this campaign")]
+    BudgetNotActive,
+    
+    #[msg("Refund of withdrawn revenue requires the event's platform fee vault")]
+    PlatformFeeVaultRequired,
 }
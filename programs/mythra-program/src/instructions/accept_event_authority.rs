@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::Event;
+
+#[derive(Accounts)]
+pub struct AcceptEventAuthority<'info> {
+    /// Event being handed off
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+    
+    /// Proposed authority accepting the handoff
+    pub new_authority: Signer<'info>,
+}
+
+/// Accept a proposed event authority transfer
+///
+/// Must be signed by the wallet named in `propose_authority_transfer`.
+/// Every `has_one = authority` check follows the new authority from here on.
+pub fn handler(ctx: Context<AcceptEventAuthority>) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    
+    let previous_authority = event.accept_authority(&ctx.accounts.new_authority.key())?;
+    
+    // Emit EventAuthorityTransferred event
+    emit!(EventAuthorityTransferred {
+        event_pubkey: event.key(),
+        previous_authority,
+        new_authority: event.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Event {} authority transferred", event.key());
+    msg!("From {} to {}", previous_authority, event.authority);
+    
+    Ok(())
+}
+
+#[event]
+pub struct EventAuthorityTransferred {
+    pub event_pubkey: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::TokenAccount;
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Ticket, BuyerRecord, TicketIndex, PlatformConfig, BlocklistEntry};
+
+#[derive(Accounts)]
+#[instruction(buyer: Pubkey)]
+pub struct ActivateTicket<'info> {
+    /// Pre-registered ticket being sold
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.event == event.key() @ EventError::InvalidEvent,
+        constraint = ticket.tier == tier.key() @ EventError::InvalidTier
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedTierCreation
+    )]
+    pub event: Account<'info, Event>,
+    
+    #[account(mut)]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Buyer's token account, already holding the ticket's NFT
+    #[account(
+        constraint = buyer_token_account.mint == ticket.mint @ EventError::InvalidMintOwner,
+        constraint = buyer_token_account.owner == buyer @ EventError::InvalidMintOwner,
+        constraint = buyer_token_account.amount == 1 @ EventError::TicketNotOwned
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Per-wallet purchase counter for this tier
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = BuyerRecord::LEN,
+        seeds = [b"buyer", tier.key().as_ref(), buyer.as_ref()],
+        bump
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    /// Buyer's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = TicketIndex::LEN,
+        seeds = [b"ticket_index", event.key().as_ref(), buyer.as_ref()],
+        bump
+    )]
+    pub ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Escrow account to receive ticket payment
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Blocklist entry PDA for the buyer (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), buyer.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
+    /// Event authority running the point of sale
+    pub authority: Signer<'info>,
+    
+    /// Pays the ticket price and any rent (the buyer, or the box office
+    /// after taking payment off-chain)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Sell a pre-registered ticket at the point of sale
+///
+/// Takes the tier price (or its live promo price) into the ticket escrow,
+/// counts the ticket against tier and event supply, and assigns it to
+/// `buyer`, who must already hold the NFT. Sale rules match purchase_ticket.
+pub fn handler(ctx: Context<ActivateTicket>, buyer: Pubkey) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let tier = &mut ctx.accounts.tier;
+    let event = &mut ctx.accounts.event;
+    let buyer_record = &mut ctx.accounts.buyer_record;
+    let clock = Clock::get()?;
+    
+    // VALIDATION: Platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // VALIDATION: Buyer must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &event.key(),
+        &buyer,
+    )?;
+    
+    // VALIDATION: Event must not be canceled, started or ended
+    event.require_sales_open(clock.unix_timestamp)?;
+    
+    // VALIDATION: Tier sale window must be open
+    tier.require_sale_open(clock.unix_timestamp)?;
+    
+    // VALIDATION: Check tier has available supply
+    require!(tier.is_available(), EventError::ExceedsTotalSupply);
+    
+    // VALIDATION: Check event-wide capacity across all tiers
+    require!(event.has_capacity(), EventError::EventSoldOut);
+    
+    // VALIDATION: Check per-wallet limit
+    require!(
+        buyer_record.can_purchase(1, tier.max_per_wallet),
+        EventError::PurchaseLimitExceeded
+    );
+    
+    // Assign the ticket at the current price (fails if it was already sold)
+    let (payment_amount, promo_applied) = tier.price_at(clock.unix_timestamp);
+    ticket.activate(buyer, payment_amount, clock.unix_timestamp)?;
+    ticket.promo_applied = promo_applied;
+    
+    // Transfer payment from payer to escrow
+    
+    if payment_amount > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.ticket_escrow.to_account_info(),
+            },
+        );
+        
+        transfer(transfer_ctx, payment_amount)?;
+        
+        msg!("Payment transferred: {} lamports", payment_amount);
+    }
+    
+    // Count the ticket against supply now that it's sold
+    tier.current_supply = tier.current_supply
+        .checked_add(1)
+        .ok_or(EventError::ExceedsTotalSupply)?;
+    
+    event.tickets_sold = event.tickets_sold
+        .checked_add(1)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    event.ticket_revenue = event.ticket_revenue
+        .checked_add(payment_amount)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    // Count this sale against the buyer's wallet limit
+    buyer_record.tier = tier.key();
+    buyer_record.buyer = buyer;
+    buyer_record.purchased_count = buyer_record.purchased_count
+        .checked_add(1)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    buyer_record.bump = ctx.bumps.buyer_record;
+    
+    // Append to the buyer's ticket index if the event maintains one
+    if event.maintain_ticket_index {
+        let ticket_index = ctx.accounts.ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        ticket_index.init_if_new(
+            event.key(),
+            buyer,
+            ctx.bumps.ticket_index.ok_or(EventError::TicketIndexRequired)?,
+        );
+        ticket_index.add(ticket.key());
+    }
+    
+    // Emit TicketActivated event
+    emit!(TicketActivated {
+        ticket_pubkey: ticket.key(),
+        event_pubkey: event.key(),
+        tier_pubkey: tier.key(),
+        mint_pubkey: ticket.mint,
+        buyer,
+        price_paid: payment_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Ticket activated: {}", ticket.key());
+    msg!("Buyer: {}", buyer);
+    msg!("Tier supply: {}/{}", tier.current_supply, tier.max_supply);
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketActivated {
+    pub ticket_pubkey: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub mint_pubkey: Pubkey,
+    pub buyer: Pubkey,
+    pub price_paid: u64,
+    pub timestamp: i64,
+}
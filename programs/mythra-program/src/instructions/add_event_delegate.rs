@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, EventDelegate};
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct AddEventDelegate<'info> {
+    /// Event the delegate will act for
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Delegate registry entry to be created
+    /// PDA: ["delegate", event.key(), delegate]
+    #[account(
+        init,
+        payer = authority,
+        space = EventDelegate::SPACE,
+        seeds = [b"delegate", event.key().as_ref(), delegate.as_ref()],
+        bump
+    )]
+    pub event_delegate: Account<'info, EventDelegate>,
+    
+    /// Event authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddEventDelegate>, delegate: Pubkey, permissions: u8) -> Result<()> {
+    EventDelegate::validate_permissions(permissions)?;
+    
+    let event_delegate = &mut ctx.accounts.event_delegate;
+    let clock = Clock::get()?;
+    
+    event_delegate.event = ctx.accounts.event.key();
+    event_delegate.delegate = delegate;
+    event_delegate.permissions = permissions;
+    event_delegate.added_at = clock.unix_timestamp;
+    event_delegate.bump = ctx.bumps.event_delegate;
+    
+    // Emit EventDelegateAdded event
+    emit!(EventDelegateAdded {
+        event_pubkey: event_delegate.event,
+        delegate,
+        permissions,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Delegate {} added for event {} (permissions {:#06b})", delegate, event_delegate.event, permissions);
+    
+    Ok(())
+}
+
+#[event]
+pub struct EventDelegateAdded {
+    pub event_pubkey: Pubkey,
+    pub delegate: Pubkey,
+    pub permissions: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, GateOperator};
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey)]
+pub struct AddGateOperator<'info> {
+    /// Event the operator will scan for
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Gate operator registry entry to be created
+    /// PDA: ["gate_operator", event.key(), operator]
+    #[account(
+        init,
+        payer = authority,
+        space = GateOperator::SPACE,
+        seeds = [b"gate_operator", event.key().as_ref(), operator.as_ref()],
+        bump
+    )]
+    pub gate_operator: Account<'info, GateOperator>,
+    
+    /// Event authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddGateOperator>, operator: Pubkey) -> Result<()> {
+    let gate_operator = &mut ctx.accounts.gate_operator;
+    let clock = Clock::get()?;
+    
+    gate_operator.event = ctx.accounts.event.key();
+    gate_operator.operator = operator;
+    gate_operator.added_at = clock.unix_timestamp;
+    gate_operator.bump = ctx.bumps.gate_operator;
+    
+    // Emit GateOperatorAdded event
+    emit!(GateOperatorAdded {
+        event_pubkey: gate_operator.event,
+        operator,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Gate operator {} added for event {}", operator, gate_operator.event);
+    
+    Ok(())
+}
+
+#[event]
+pub struct GateOperatorAdded {
+    pub event_pubkey: Pubkey,
+    pub operator: Pubkey,
+    pub timestamp: i64,
+}
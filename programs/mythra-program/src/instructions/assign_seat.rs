@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Ticket, SeatAssignment};
+
+#[derive(Accounts)]
+#[instruction(seat_number: u32)]
+pub struct AssignSeat<'info> {
+    /// Ticket being seated
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.tier == tier.key() @ EventError::InvalidTier
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Seated tier the ticket belongs to
+    #[account(
+        mut,
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Parent event (authority must match)
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Assignment for the new seat (fails if the seat is taken)
+    /// PDA: ["seat", tier, seat_number]
+    #[account(
+        init,
+        payer = authority,
+        space = SeatAssignment::SPACE,
+        seeds = [b"seat", tier.key().as_ref(), &seat_number.to_le_bytes()],
+        bump
+    )]
+    pub seat_assignment: Account<'info, SeatAssignment>,
+    
+    /// Assignment for the ticket's current seat, freed by the move
+    /// (required when the ticket already has a seat)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"seat", tier.key().as_ref(), &ticket.seat_number.to_le_bytes()],
+        bump = previous_seat.bump,
+        constraint = previous_seat.ticket == ticket.key() @ EventError::SeatAssignmentRequired
+    )]
+    pub previous_seat: Option<Account<'info, SeatAssignment>>,
+    
+    /// Event authority (pays for the new assignment, receives the old one's rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Assign a ticket's seat, or move it to another one
+///
+/// Covers tickets sold without a seat (batch, order and register-mint
+/// sales on seated tiers) and seat changes. Each seat has one assignment
+/// PDA, so a seat can't be given out twice; moving a ticket frees its old
+/// seat for someone else.
+pub fn handler(ctx: Context<AssignSeat>, seat_number: u32) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let tier = &mut ctx.accounts.tier;
+    
+    // Validation: refunded tickets don't need a seat
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    // Validation: a seated ticket's current assignment must be released
+    require!(
+        ticket.seat_number == 0 || ctx.accounts.previous_seat.is_some(),
+        EventError::SeatAssignmentRequired
+    );
+    
+    // Validation: seated tier and a real seat number
+    tier.claim_seat(seat_number)?;
+    
+    let seat_assignment = &mut ctx.accounts.seat_assignment;
+    seat_assignment.tier = tier.key();
+    seat_assignment.seat_number = seat_number;
+    seat_assignment.ticket = ticket.key();
+    seat_assignment.bump = ctx.bumps.seat_assignment;
+    
+    let previous_seat = ticket.seat_number;
+    ticket.seat_number = seat_number;
+    
+    emit!(SeatAssigned {
+        ticket: ticket.key(),
+        tier: tier.key(),
+        previous_seat,
+        seat_number,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Seat assigned: {} -> {}", ticket.key(), seat_number);
+    if previous_seat > 0 {
+        msg!("Freed seat: {}", previous_seat);
+    }
+    
+    Ok(())
+}
+
+#[event]
+pub struct SeatAssigned {
+    pub ticket: Pubkey,
+    pub tier: Pubkey,
+    pub previous_seat: u32,
+    pub seat_number: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::metadata::{self, TokenMetadata, CreateTicketMetadata};
+use crate::state::{Event, TicketTier, Ticket};
+
+#[derive(Accounts)]
+pub struct AttachMetadata<'info> {
+    /// Ticket whose NFT gets metadata
+    #[account(
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event account (must match ticket.event)
+    #[account(
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Tier account (supplies the URI and royalty)
+    #[account(
+        constraint = tier.key() == ticket.tier @ EventError::InvalidTier
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// NFT mint account
+    /// CHECK: Must be the ticket's mint; Token Metadata checks the mint authority
+    #[account(
+        address = ticket.mint @ EventError::InvalidMintOwner
+    )]
+    pub mint: AccountInfo<'info>,
+    
+    /// Token Metadata account for the mint (fails if it already exists)
+    /// CHECK: Address checked against the metadata PDA; created by the CPI
+    #[account(
+        mut,
+        address = metadata::metadata_address(&mint.key()) @ EventError::InvalidMetadataAccount
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    /// Per-event PDA that owns ticket metadata
+    /// CHECK: Address checked by seeds; only signs the metadata CPI
+    #[account(
+        seeds = [metadata::METADATA_AUTHORITY_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub metadata_authority: UncheckedAccount<'info>,
+    
+    /// Mint authority of the NFT (pays for the metadata account)
+    #[account(mut)]
+    pub mint_authority: Signer<'info>,
+    
+    pub token_metadata_program: Program<'info, TokenMetadata>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Create Token Metadata for a ticket minted outside `purchase_ticket`
+///
+/// For `register_mint` tickets, or purchases made without metadata. The
+/// name, URI, creators and royalty are derived from the ticket's event and
+/// tier exactly as `purchase_ticket` does, so only the mint authority's
+/// signature is needed.
+pub fn handler(ctx: Context<AttachMetadata>) -> Result<()> {
+    let ticket = &ctx.accounts.ticket;
+    let event_key = ctx.accounts.event.key();
+    
+    // Validation: refunded tickets' NFTs are burned
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    metadata::create_ticket_metadata(
+        CreateTicketMetadata {
+            metadata: &ctx.accounts.metadata,
+            mint: &ctx.accounts.mint,
+            mint_authority: &ctx.accounts.mint_authority,
+            payer: &ctx.accounts.mint_authority,
+            metadata_authority: &ctx.accounts.metadata_authority,
+            system_program: &ctx.accounts.system_program,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+        },
+        metadata::ticket_data(
+            &event_key,
+            &ctx.accounts.event,
+            &ctx.accounts.tier,
+            ticket.seat_number,
+            &ctx.accounts.metadata_authority.key(),
+        ),
+        &event_key,
+        ctx.bumps.metadata_authority,
+    )?;
+    
+    emit!(TicketMetadataAttached {
+        ticket: ticket.key(),
+        mint: ticket.mint,
+        metadata: ctx.accounts.metadata.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Ticket metadata created: {}", ctx.accounts.metadata.key());
+    msg!("Ticket: {}", ticket.key());
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketMetadataAttached {
+    pub ticket: Pubkey,
+    pub mint: Pubkey,
+    pub metadata: Pubkey,
+    pub timestamp: i64,
+}
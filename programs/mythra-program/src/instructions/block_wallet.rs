@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, BlocklistEntry};
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BlockWallet<'info> {
+    /// Event the wallet is blocked from
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Blocklist entry to be created
+    /// PDA: ["blocklist", event.key(), wallet]
+    #[account(
+        init,
+        payer = authority,
+        space = BlocklistEntry::SPACE,
+        seeds = [b"blocklist", event.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: Account<'info, BlocklistEntry>,
+    
+    /// Event authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<BlockWallet>, wallet: Pubkey) -> Result<()> {
+    let blocklist_entry = &mut ctx.accounts.blocklist_entry;
+    let clock = Clock::get()?;
+    
+    blocklist_entry.event = ctx.accounts.event.key();
+    blocklist_entry.wallet = wallet;
+    blocklist_entry.blocked_at = clock.unix_timestamp;
+    blocklist_entry.bump = ctx.bumps.blocklist_entry;
+    
+    // Emit WalletBlocked event
+    emit!(WalletBlocked {
+        event_pubkey: blocklist_entry.event,
+        wallet,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Wallet {} blocked from event {}", wallet, blocklist_entry.event);
+    
+    Ok(())
+}
+
+#[event]
+pub struct WalletBlocked {
+    pub event_pubkey: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
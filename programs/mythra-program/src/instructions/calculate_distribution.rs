@@ -1,18 +1,30 @@
 use anchor_lang::prelude::*;
-use crate::state::{Campaign, CampaignStatus, Event};
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Campaign, CampaignStatus, Event, PlatformConfig, PlatformStats};
 use crate::errors::EventError;
 
 /// Calculate profit distribution after event ends
 /// 
-/// This instruction calculates the profit (revenue - expenses) and splits it:
-/// - 60% to backer pool (proportional to contributions)
-/// - 35% to organizer pool
-/// - 5% to platform pool
+/// This instruction calculates the profit (revenue - expenses) and splits it
+/// by the shares agreed at campaign creation (60/35/5 by default):
+/// - backer pool (proportional to contributions, plus rounding dust)
+/// - organizer pool
+/// - platform pool
 /// 
-/// If there's a loss (expenses > revenue), no distribution occurs.
+/// Ticket revenue is first swept from the event's ticket escrow into the
+/// campaign escrow, which is where profit claims are paid from. Revenue the
+/// organizer already withdrew and payments of pending orders stay out of
+/// the sweep, which is recorded as withdrawn.
+///
+/// Only the organizer or platform admin may trigger it, and not before the
+/// platform's grace period after the event end has passed.
+///
+/// If there's a loss (expenses > revenue), no profit is distributed; the
+/// escrow that is left is snapshotted into `recovery_pool` for backers to
+/// claim through `claim_loss_recovery`.
 pub fn handler(ctx: Context<CalculateDistribution>) -> Result<()> {
     let campaign = &mut ctx.accounts.campaign;
-    let event = &ctx.accounts.event;
+    let event = &mut ctx.accounts.event;
     let clock = Clock::get()?;
     
     // Validation: Campaign must be funded
@@ -27,88 +39,118 @@ pub fn handler(ctx: Context<CalculateDistribution>) -> Result<()> {
         EventError::EventNotEnded
     );
     
+    // Validation: Grace period after the event end must have passed, so
+    // revenue sweeps and milestone releases can settle first
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_distribution_grace_passed(
+        platform_config.as_ref(),
+        event.end_ts,
+        clock.unix_timestamp,
+    )?;
+    
     // Validation: Distribution not already calculated
     require!(
         !campaign.distribution_complete,
         EventError::DistributionAlreadyComplete
     );
     
-    // Update revenue from event ticket sales
-    campaign.total_revenue = event.ticket_revenue;
+    // Validation: Only the organizer or platform admin picks the snapshot time,
+    // unless they have been unresponsive past the distribution timeout
+    let timed_out = PlatformConfig::distribution_timed_out(
+        platform_config.as_ref(),
+        event.end_ts,
+        clock.unix_timestamp,
+    );
+    require!(
+        timed_out ||
+            campaign.can_trigger_distribution(&ctx.accounts.authority.key(), platform_config.as_ref()),
+        EventError::UnauthorizedDistributionTrigger
+    );
     
-    // Calculate profit or loss
-    let revenue = campaign.total_revenue;
-    let expenses = campaign.total_expenses;
+    if timed_out {
+        msg!("Distribution timeout passed, permissionless trigger by {}", ctx.accounts.authority.key());
+    }
     
-    msg!("Revenue: {} lamports", revenue);
-    msg!("Expenses: {} lamports", expenses);
+    // Sweep ticket revenue from the ticket escrow into the campaign escrow
+    // so the pools computed below are actually backed by escrowed lamports
+    let rent = Rent::get()?;
+    let rent_exempt_minimum = rent.minimum_balance(0);
     
-    if revenue > expenses {
-        // PROFIT scenario
-        let profit = revenue
-            .checked_sub(expenses)
-            .ok_or(EventError::ArithmeticOverflow)?;
-        
-        msg!("Profit: {} lamports", profit);
+    let sweep_amount = event.releasable_revenue(ctx.accounts.ticket_escrow.lamports(), rent_exempt_minimum);
+    
+    if sweep_amount > 0 {
+        let event_key = event.key();
+        let escrow_seeds = &[
+            b"ticket_escrow",
+            event_key.as_ref(),
+            &[event.ticket_escrow_bump],
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
         
-        // Split profit: 60% backers, 35% organizer, 5% platform
-        let backer_pool = profit
-            .checked_mul(60)
-            .ok_or(EventError::ArithmeticOverflow)?
-            .checked_div(100)
-            .ok_or(EventError::ArithmeticOverflow)?;
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.ticket_escrow.to_account_info(),
+                to: ctx.accounts.campaign_escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
         
-        let organizer_pool = profit
-            .checked_mul(35)
-            .ok_or(EventError::ArithmeticOverflow)?
-            .checked_div(100)
-            .ok_or(EventError::ArithmeticOverflow)?;
+        transfer(transfer_ctx, sweep_amount)?;
         
-        let platform_pool = profit
-            .checked_mul(5)
-            .ok_or(EventError::ArithmeticOverflow)?
-            .checked_div(100)
+        // The swept revenue left the ticket escrow like a withdrawal, but
+        // without a platform fee: the platform's share is its profit pool
+        event.record_withdrawal(sweep_amount, 0)
             .ok_or(EventError::ArithmeticOverflow)?;
-        
-        let distributed = backer_pool
-    .checked_add(organizer_pool)
-    .ok_or(EventError::ArithmeticOverflow)?
-    .checked_add(platform_pool)
-    .ok_or(EventError::ArithmeticOverflow)?;
-
-    let remainder = profit
-        .checked_sub(distributed)
-        .ok_or(EventError::ArithmeticOverflow)?;
-
-    // Give remainder to backers
-    let backer_pool = backer_pool
-        .checked_add(remainder)
-        .ok_or(EventError::ArithmeticOverflow)?;
-
-    campaign.backer_pool = backer_pool;
-    campaign.organizer_pool = organizer_pool;
-    campaign.platform_pool = platform_pool;
-
-    msg!("Backer pool (60% + remainder): {} lamports", backer_pool);
-    msg!("Organizer pool (35%): {} lamports", organizer_pool);
-    msg!("Platform pool (5%): {} lamports", platform_pool);
-    msg!("Remainder allocated to backers: {} lamports", remainder);
+    }
+    
+    msg!("Ticket revenue swept to campaign escrow: {} lamports", sweep_amount);
+    
+    // Revenue is what was actually moved into the campaign escrow; split
+    // profit by the campaign's shares or record the loss
+    campaign.record_distribution(sweep_amount)?;
+    
+    if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+        stats.record_distribution(sweep_amount, clock.unix_timestamp);
+    }
+    campaign.distributed_at = clock.unix_timestamp;
+    campaign.claim_deadline = clock.unix_timestamp
+        .saturating_add(PlatformConfig::backer_claim_window(platform_config.as_ref()));
+    
+    // Without profit, whatever is left in the campaign escrow goes back to
+    // backers through claim_loss_recovery
+    campaign.open_loss_recovery(Campaign::escrow_available(
+        ctx.accounts.campaign_escrow.lamports(),
+        rent_exempt_minimum,
+    ));
+    
+    msg!("Revenue: {} lamports", campaign.total_revenue);
+    msg!("Expenses: {} lamports", campaign.total_expenses);
+    
+    if campaign.recorded_loss > 0 {
+        msg!("Loss: {} lamports (no profit to distribute)", campaign.recorded_loss);
+        msg!("Recovery pool for backers: {} lamports", campaign.recovery_pool);
     } else {
-        // LOSS scenario - no profit to distribute
-        let loss = expenses
-            .checked_sub(revenue)
-            .ok_or(EventError::ArithmeticOverflow)?;
-        
-        msg!("Loss: {} lamports (no profit to distribute)", loss);
-        
-        campaign.backer_pool = 0;
-        campaign.organizer_pool = 0;
-        campaign.platform_pool = 0;
+        msg!("Backer pool ({} bps + remainder): {} lamports", campaign.backer_share_bps, campaign.backer_pool);
+        msg!("Organizer pool ({} bps): {} lamports", campaign.organizer_share_bps, campaign.organizer_pool);
+        msg!("Platform pool ({} bps): {} lamports", campaign.platform_share_bps, campaign.platform_pool);
     }
     
-    campaign.distribution_complete = true;
-    campaign.status = CampaignStatus::Completed;
+    emit!(DistributionCalculated {
+        campaign: campaign.key(),
+        event: campaign.event,
+        total_revenue: campaign.total_revenue,
+        total_expenses: campaign.total_expenses,
+        backer_pool: campaign.backer_pool,
+        organizer_pool: campaign.organizer_pool,
+        platform_pool: campaign.platform_pool,
+        recorded_loss: campaign.recorded_loss,
+        recovery_pool: campaign.recovery_pool,
+        claim_deadline: campaign.claim_deadline,
+        timestamp: clock.unix_timestamp,
+    });
     
+    msg!("Backers can claim until: {}", campaign.claim_deadline);
     msg!("Distribution calculated successfully");
     
     Ok(())
@@ -125,11 +167,64 @@ pub struct CalculateDistribution<'info> {
     
     /// Event account (to check end time and get ticket revenue)
     #[account(
+        mut,
         constraint = event.key() == campaign.event @ EventError::InvalidEvent,
         constraint = event.campaign == Some(campaign.key()) @ EventError::InvalidCampaign
     )]
     pub event: Account<'info, Event>,
     
-    /// Authority (organizer or platform admin can trigger this)
+    /// Ticket escrow holding ticket sale proceeds
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Campaign escrow that profit claims are paid from
+    /// PDA: ["campaign_escrow", campaign.key()]
+    #[account(
+        mut,
+        seeds = [b"campaign_escrow", campaign.key().as_ref()],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Authority (organizer or platform admin can trigger this; anyone once
+    /// the distribution timeout has passed)
     pub authority: Signer<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, only the organizer may trigger otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Platform stats PDA (optional; counters are skipped when omitted)
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Box<Account<'info, PlatformStats>>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct DistributionCalculated {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub total_revenue: u64,
+    pub total_expenses: u64,
+    pub backer_pool: u64,
+    pub organizer_pool: u64,
+    pub platform_pool: u64,
+    pub recorded_loss: u64,
+    pub recovery_pool: u64,
+    pub claim_deadline: i64,
+    pub timestamp: i64,
 }
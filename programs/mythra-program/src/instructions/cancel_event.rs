@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Campaign, CampaignStatus, Event, REFUND_FACTOR_SCALE};
+
+#[derive(Accounts)]
+pub struct CancelEvent<'info> {
+    /// Event account to be canceled
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Ticket escrow holding ticket sale proceeds
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Campaign crowdfunding the event, required when the event has one
+    pub campaign: Option<Box<Account<'info, Campaign>>>,
+    
+    /// Campaign escrow (validated against the campaign in the handler),
+    /// required with the campaign
+    pub campaign_escrow: Option<SystemAccount<'info>>,
+    
+    /// Event authority
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelEvent>) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    let clock = Clock::get()?;
+    
+    // Validation: Event must not already be canceled
+    require!(
+        !event.canceled,
+        EventError::EventAlreadyCanceled
+    );
+    
+    // Check whether escrow can cover every ticket refund
+    let rent = Rent::get()?;
+    let rent_exempt_minimum = rent.minimum_balance(0);
+    
+    let available_balance = ctx.accounts.ticket_escrow.lamports()
+        .saturating_sub(rent_exempt_minimum);
+    
+    event.canceled = true;
+    
+    if available_balance < event.ticket_revenue {
+        // Shortfall: open a claims-registration window so refunds are paid pro-rata
+        event.shortfall_mode = true;
+        event.refund_claim_deadline = clock.unix_timestamp
+            .checked_add(Event::REFUND_CLAIM_WINDOW_SECONDS)
+            .ok_or(EventError::ArithmeticOverflow)?;
+        
+        msg!("Escrow shortfall detected: {} available for {} of ticket revenue",
+            available_balance, event.ticket_revenue);
+        msg!("Refund claims open until: {}", event.refund_claim_deadline);
+    } else {
+        event.refund_factor = REFUND_FACTOR_SCALE;
+    }
+    
+    // Cascade to the campaign: a campaign still raising or spending
+    // switches to pro-rata refunds in the same instruction, so no refund
+    // can be claimed under a stale mode
+    if let Some(campaign_key) = event.campaign {
+        let campaign = ctx.accounts.campaign
+            .as_deref_mut()
+            .ok_or(EventError::InvalidCampaign)?;
+        require_keys_eq!(campaign.key(), campaign_key, EventError::InvalidCampaign);
+        
+        if campaign.status == CampaignStatus::Pending || campaign.status == CampaignStatus::Funded {
+            let campaign_escrow = ctx.accounts.campaign_escrow
+                .as_ref()
+                .ok_or(EventError::InvalidCampaign)?;
+            let expected_escrow = Pubkey::create_program_address(
+                &[b"campaign_escrow", campaign_key.as_ref(), &[campaign.escrow_bump]],
+                ctx.program_id,
+            ).map_err(|_| EventError::InvalidCampaign)?;
+            require_keys_eq!(campaign_escrow.key(), expected_escrow, EventError::InvalidCampaign);
+            
+            let escrow_available = Campaign::escrow_available(
+                campaign_escrow.lamports(),
+                rent_exempt_minimum,
+            );
+            campaign.cancel_for_event(escrow_available)?;
+            
+            msg!("Campaign refunds opened pro-rata: {} of {} lamports",
+                campaign.refund_pool, campaign.refund_base);
+        }
+    }
+    
+    // Emit EventCanceled event
+    emit!(EventCanceled {
+        event_pubkey: event.key(),
+        authority: event.authority,
+        ticket_revenue: event.ticket_revenue,
+        escrow_available: available_balance,
+        shortfall_mode: event.shortfall_mode,
+        refund_claim_deadline: event.refund_claim_deadline,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Event canceled: {}", event.key());
+    
+    Ok(())
+}
+
+#[event]
+pub struct EventCanceled {
+    pub event_pubkey: Pubkey,
+    pub authority: Pubkey,
+    pub ticket_revenue: u64,
+    pub escrow_available: u64,
+    pub shortfall_mode: bool,
+    pub refund_claim_deadline: i64,
+    pub timestamp: i64,
+}
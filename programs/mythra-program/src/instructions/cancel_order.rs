@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Order, BuyerRecord};
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    /// Unfulfilled order (closed to the buyer)
+    #[account(
+        mut,
+        seeds = [b"order", event.key().as_ref(), &Order::seed_hash(&order.order_id)],
+        bump = order.bump,
+        has_one = buyer @ EventError::InvalidOwner,
+        constraint = order.tier == tier.key() @ EventError::InvalidOrder,
+        close = buyer
+    )]
+    pub order: Account<'info, Order>,
+    
+    /// Event account (seat and revenue are released)
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+    
+    /// Tier account (reserved seat is returned to supply)
+    #[account(mut)]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Buyer's purchase counter for this tier
+    #[account(
+        mut,
+        seeds = [b"buyer", tier.key().as_ref(), buyer.key().as_ref()],
+        bump = buyer_record.bump
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+    
+    /// Escrow account holding the order payment
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Buyer receiving the refund and order rent
+    /// CHECK: Matched against order.buyer
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+    
+    /// Buyer, or the event authority once the fulfillment timeout has passed
+    pub canceler: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CancelOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    let event = &mut ctx.accounts.event;
+    let tier = &mut ctx.accounts.tier;
+    let clock = Clock::get()?;
+    
+    // Validation: only unfulfilled orders can be canceled
+    require!(
+        !order.is_fulfilled(),
+        EventError::OrderAlreadyFulfilled
+    );
+    
+    // Validation: buyer any time, authority only after the timeout
+    require!(
+        order.can_cancel(&ctx.accounts.canceler.key(), &event.authority, clock.unix_timestamp),
+        EventError::UnauthorizedOrderCancel
+    );
+    
+    // Refund the buyer from escrow
+    if order.amount_paid > 0 {
+        let event_key = event.key();
+        let escrow_seeds = &[
+            b"ticket_escrow",
+            event_key.as_ref(),
+            &[event.ticket_escrow_bump],
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.ticket_escrow.to_account_info(),
+                to: ctx.accounts.buyer.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        transfer(transfer_ctx, order.amount_paid)?;
+    }
+    
+    // Release the reserved seat
+    tier.release_seat()
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    event.tickets_sold = event.tickets_sold.saturating_sub(1);
+    event.ticket_revenue = event.ticket_revenue.saturating_sub(order.amount_paid);
+    event.pending_order_funds = event.pending_order_funds.saturating_sub(order.amount_paid);
+    
+    // Free up a slot in the buyer's per-wallet limit
+    if let Some(buyer_record) = ctx.accounts.buyer_record.as_mut() {
+        buyer_record.purchased_count = buyer_record.purchased_count.saturating_sub(1);
+    }
+    
+    // Emit OrderCanceled event
+    emit!(OrderCanceled {
+        order_pubkey: order.key(),
+        event_pubkey: event.key(),
+        tier_pubkey: tier.key(),
+        buyer: order.buyer,
+        order_id: order.order_id.clone(),
+        refund_amount: order.amount_paid,
+        canceled_by: ctx.accounts.canceler.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Order canceled: {}", order.order_id);
+    msg!("Refunded: {} lamports", order.amount_paid);
+    
+    Ok(())
+}
+
+#[event]
+pub struct OrderCanceled {
+    pub order_pubkey: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub buyer: Pubkey,
+    pub order_id: String,
+    pub refund_amount: u64,
+    pub canceled_by: Pubkey,
+    pub timestamp: i64,
+}
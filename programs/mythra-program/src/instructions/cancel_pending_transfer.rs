@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenInterface, TokenAccount, transfer_checked, TransferChecked, close_account, CloseAccount, Mint,
+};
+use crate::errors::EventError;
+use crate::state::{Ticket, PendingTransfer};
+
+#[derive(Accounts)]
+pub struct CancelPendingTransfer<'info> {
+    /// Ticket held in escrow
+    #[account(
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// NFT mint account
+    #[account(
+        constraint = mint.key() == ticket.mint @ EventError::InvalidMintOwner
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Pending transfer being canceled (closed to the sender)
+    #[account(
+        mut,
+        close = sender,
+        seeds = [b"pending_transfer", ticket.key().as_ref()],
+        bump = pending_transfer.bump,
+        has_one = sender @ EventError::InvalidOwner
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+    
+    /// Escrow token account holding the NFT (closed to the sender)
+    #[account(
+        mut,
+        seeds = [b"pending_transfer_escrow", ticket.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Sender's token account receiving the NFT back
+    #[account(
+        mut,
+        constraint = sender_token_account.mint == ticket.mint @ EventError::InvalidMintOwner,
+        constraint = sender_token_account.owner == sender.key() @ EventError::InvalidOwner
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Sender who created the pending transfer - must sign
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Take back a ticket nobody claimed in time
+///
+/// Only the sender can cancel, and only once the claim deadline has passed,
+/// so a shared claim link can't be pulled out from under the recipient.
+/// Works while the platform is paused since it only returns the sender's
+/// own ticket.
+pub fn handler(ctx: Context<CancelPendingTransfer>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Validation: claim window is over
+    ctx.accounts.pending_transfer.require_cancelable(now)?;
+    
+    // Return the NFT to the sender
+    let ticket_key = ctx.accounts.ticket.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"pending_transfer",
+        ticket_key.as_ref(),
+        &[ctx.accounts.pending_transfer.bump],
+    ]];
+    
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: ctx.accounts.sender_token_account.to_account_info(),
+                authority: ctx.accounts.pending_transfer.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.sender.to_account_info(),
+            authority: ctx.accounts.pending_transfer.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+    
+    emit!(PendingTransferCanceled {
+        pending_transfer: ctx.accounts.pending_transfer.key(),
+        ticket: ticket_key,
+        sender: ctx.accounts.sender.key(),
+        timestamp: now,
+    });
+    
+    msg!("Pending transfer canceled: {}", ticket_key);
+    
+    Ok(())
+}
+
+#[event]
+pub struct PendingTransferCanceled {
+    pub pending_transfer: Pubkey,
+    pub ticket: Pubkey,
+    pub sender: Pubkey,
+    pub timestamp: i64,
+}
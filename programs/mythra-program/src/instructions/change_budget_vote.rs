@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::{Budget, BudgetStatus, BudgetVote, VoteChoice};
+use crate::errors::EventError;
+
+/// Change an existing budget vote
+///
+/// Backers can switch between approve, reject and abstain while the budget is
+/// still Pending and the voting period hasn't ended. Their voting power
+/// is moved from the old side of the tally to the new one.
+pub fn handler(
+    ctx: Context<ChangeBudgetVote>,
+    choice: VoteChoice,
+) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let vote = &mut ctx.accounts.vote;
+    let clock = Clock::get()?;
+    
+    // Validate budget is in pending status
+    require!(
+        budget.status == BudgetStatus::Pending,
+        EventError::BudgetNotPending
+    );
+    
+    // Validate voting period hasn't ended
+    require!(
+        !budget.voting_ended(clock.unix_timestamp),
+        EventError::VotingPeriodEnded
+    );
+    
+    // Move voting power from the old side to the new side
+    let voting_power = vote.voting_power();
+    budget.remove_vote(voting_power, vote.contribution_amount, vote.choice)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    budget.add_vote(voting_power, vote.contribution_amount, choice)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    vote.choice = choice;
+    vote.voted_at = clock.unix_timestamp;
+    
+    msg!(
+        "Vote changed to {:?}: {} lamports voting power",
+        choice,
+        voting_power
+    );
+    msg!(
+        "Current tally: {} FOR, {} AGAINST, {} ABSTAIN",
+        budget.votes_for,
+        budget.votes_against,
+        budget.votes_abstain
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ChangeBudgetVote<'info> {
+    /// Budget being voted on
+    #[account(mut)]
+    pub budget: Account<'info, Budget>,
+    
+    /// Existing vote record
+    #[account(
+        mut,
+        seeds = [
+            b"budget_vote",
+            budget.key().as_ref(),
+            &[vote.revision],
+            voter.key().as_ref(),
+        ],
+        bump = vote.bump,
+        has_one = budget,
+        has_one = voter
+    )]
+    pub vote: Account<'info, BudgetVote>,
+    
+    /// Voter who cast the original vote
+    pub voter: Signer<'info>,
+}
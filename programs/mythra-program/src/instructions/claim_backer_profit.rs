@@ -6,15 +6,26 @@ use crate::errors::EventError;
 /// Allow backers to claim their proportional profit share
 /// 
 /// Each backer's share is calculated as:
-/// share = (backer_contribution / total_raised) * backer_pool
+/// share = (net_contribution / distribution_basis) * backer_pool
+///
+/// where `distribution_basis` is total_raised snapshotted at distribution
+/// and refunded amounts are excluded from the contribution.
 /// 
 /// This instruction:
 /// 1. Calculates the backer's proportional share
 /// 2. Transfers SOL from campaign escrow to backer
 /// 3. Marks the profit as claimed
+///
+/// Claims close at `campaign.claim_deadline`, after which unclaimed shares
+/// can be swept with `sweep_unclaimed_profits`.
+///
+/// When there is nothing to pay (e.g. the event ran at a loss) the claim is
+/// still recorded and `NoProfitToClaim` is emitted with the campaign's
+/// recorded loss instead of `BackerProfitClaimed`.
 pub fn handler(ctx: Context<ClaimBackerProfit>) -> Result<()> {
-    let campaign = &ctx.accounts.campaign;
+    let campaign = &mut ctx.accounts.campaign;
     let contribution = &mut ctx.accounts.contribution;
+    let clock = Clock::get()?;
     
     // Validation: Distribution must be complete
     require!(
@@ -28,43 +39,32 @@ pub fn handler(ctx: Context<ClaimBackerProfit>) -> Result<()> {
         EventError::InvalidCampaignStatus
     );
     
-    // Validation: Backer hasn't claimed yet
-    require!(
-        !contribution.profit_claimed,
-        EventError::ProfitAlreadyClaimed
-    );
-    
-    // Calculate backer's proportional share
-    let share = contribution.calculate_share(
-        campaign.backer_pool,
-        campaign.total_raised
-    );
+    // Validation: Claim deadline hasn't passed
+    campaign.require_claim_open(clock.unix_timestamp)?;
     
-    // Store the share in contribution for tracking
-    contribution.profit_share = share;
+    // Calculate backer's proportional share and mark it claimed (fails if
+    // the backer already claimed)
+    let share = campaign.settle_backer_claim(contribution)?;
     
-    msg!("Backer contribution: {} lamports", contribution.amount);
-    msg!("Total raised: {} lamports", campaign.total_raised);
+    msg!("Backer contribution: {} lamports", contribution.net_amount());
+    msg!("Distribution basis: {} lamports", campaign.distribution_basis);
     msg!("Backer pool: {} lamports", campaign.backer_pool);
     msg!("Backer share: {} lamports", share);
     
-    // Mark as claimed
-    contribution.profit_claimed = true;
-    
     // If there's profit to claim, transfer it
     if share > 0 {
-        // Validate escrow has sufficient balance
-        let escrow_balance = ctx.accounts.campaign_escrow.lamports();
-        require!(
-            escrow_balance >= share,
-            EventError::InsufficientBalance
-        );
+        // Validate escrow can pay without dipping into its rent reserve
+        Campaign::require_escrow_covers(
+            ctx.accounts.campaign_escrow.lamports(),
+            Rent::get()?.minimum_balance(0),
+            share,
+        )?;
 
         let campaign_key = campaign.key();
         let seeds = &[
             b"campaign_escrow",
             campaign_key.as_ref(),
-            &[ctx.bumps.campaign_escrow],
+            &[campaign.escrow_bump],
         ];
         let signer_seeds = &[&seeds[..]];
         
@@ -78,10 +78,33 @@ pub fn handler(ctx: Context<ClaimBackerProfit>) -> Result<()> {
         );
         
         transfer(transfer_ctx, share)?;
+        campaign.record_backer_payout(share)?;
         
         msg!("Transferred {} lamports to backer", share);
+        
+        emit!(BackerProfitClaimed {
+            campaign: campaign.key(),
+            event: campaign.event,
+            contribution: contribution.key(),
+            contributor: contribution.contributor,
+            contribution_amount: contribution.amount,
+            amount: share,
+            backer_pool: campaign.backer_pool,
+            timestamp: clock.unix_timestamp,
+        });
     } else {
-        msg!("No profit to claim (loss scenario)");
+        msg!("No profit to claim (recorded loss: {} lamports)", campaign.recorded_loss);
+        
+        emit!(NoProfitToClaim {
+            campaign: campaign.key(),
+            event: campaign.event,
+            contribution: contribution.key(),
+            contributor: contribution.contributor,
+            contribution_amount: contribution.amount,
+            backer_pool: campaign.backer_pool,
+            recorded_loss: campaign.recorded_loss,
+            timestamp: clock.unix_timestamp,
+        });
     }
     
     Ok(())
@@ -91,6 +114,7 @@ pub fn handler(ctx: Context<ClaimBackerProfit>) -> Result<()> {
 pub struct ClaimBackerProfit<'info> {
     /// Campaign account
     #[account(
+        mut,
         constraint = campaign.distribution_complete @ EventError::DistributionNotComplete,
         constraint = campaign.status == CampaignStatus::Completed @ EventError::InvalidCampaignStatus
     )]
@@ -109,7 +133,7 @@ pub struct ClaimBackerProfit<'info> {
     #[account(
         mut,
         seeds = [b"campaign_escrow", campaign.key().as_ref()],
-        bump
+        bump = campaign.escrow_bump
     )]
     pub campaign_escrow: SystemAccount<'info>,
     
@@ -119,3 +143,27 @@ pub struct ClaimBackerProfit<'info> {
     
     pub system_program: Program<'info, System>,
 }
+
+#[event]
+pub struct BackerProfitClaimed {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub contribution: Pubkey,
+    pub contributor: Pubkey,
+    pub contribution_amount: u64,
+    pub amount: u64,
+    pub backer_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NoProfitToClaim {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub contribution: Pubkey,
+    pub contributor: Pubkey,
+    pub contribution_amount: u64,
+    pub backer_pool: u64,
+    pub recorded_loss: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Campaign, CampaignStatus, Contribution};
+use crate::errors::EventError;
+
+/// Allow backers to recover their share of the escrow after a loss
+///
+/// When distribution recorded no profit, the escrow left at that point
+/// (unreleased milestones, unspent funds and the swept revenue) was
+/// snapshotted into `recovery_pool`. Each backer claims:
+/// share = (net_contribution / distribution_basis) * recovery_pool
+///
+/// Milestone releases are closed once distribution has run, so the pool
+/// can't be drained by the organizer before backers claim. Claims close at
+/// `campaign.claim_deadline`, like profit claims.
+pub fn handler(ctx: Context<ClaimLossRecovery>) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let contribution = &mut ctx.accounts.contribution;
+    let clock = Clock::get()?;
+    
+    // Validation: Distribution left something to recover
+    require!(
+        campaign.recovery_pool > 0,
+        EventError::NoLossRecovery
+    );
+    
+    // Validation: Claim deadline hasn't passed
+    campaign.require_claim_open(clock.unix_timestamp)?;
+    
+    // Validation: Backer hasn't recovered yet
+    require!(
+        !contribution.loss_recovered,
+        EventError::LossAlreadyRecovered
+    );
+    
+    let share = campaign.loss_recovery_share(contribution);
+    
+    msg!("Backer contribution: {} lamports", contribution.net_amount());
+    msg!("Distribution basis: {} lamports", campaign.distribution_basis);
+    msg!("Recovery pool: {} lamports", campaign.recovery_pool);
+    msg!("Recovery share: {} lamports", share);
+    
+    // Mark as recovered
+    contribution.loss_recovered = true;
+    
+    if share > 0 {
+        // Validate escrow can pay without dipping into its rent reserve
+        Campaign::require_escrow_covers(
+            ctx.accounts.campaign_escrow.lamports(),
+            Rent::get()?.minimum_balance(0),
+            share,
+        )?;
+        
+        let campaign_key = campaign.key();
+        let seeds = &[
+            b"campaign_escrow",
+            campaign_key.as_ref(),
+            &[campaign.escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.campaign_escrow.to_account_info(),
+                to: ctx.accounts.contributor.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        transfer(transfer_ctx, share)?;
+        campaign.record_backer_payout(share)?;
+        
+        msg!("Transferred {} lamports to backer", share);
+    }
+    
+    emit!(LossRecoveryClaimed {
+        campaign: campaign.key(),
+        event: campaign.event,
+        contribution: contribution.key(),
+        contributor: contribution.contributor,
+        contribution_amount: contribution.net_amount(),
+        amount: share,
+        recovery_pool: campaign.recovery_pool,
+        recorded_loss: campaign.recorded_loss,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimLossRecovery<'info> {
+    /// Campaign account
+    #[account(
+        mut,
+        constraint = campaign.distribution_complete @ EventError::DistributionNotComplete,
+        constraint = campaign.status == CampaignStatus::Completed @ EventError::InvalidCampaignStatus
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Contribution account for this backer
+    #[account(
+        mut,
+        constraint = contribution.campaign == campaign.key() @ EventError::InvalidContribution,
+        constraint = contribution.contributor == contributor.key() @ EventError::UnauthorizedClaim
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    /// Campaign escrow PDA (holds the funds)
+    #[account(
+        mut,
+        seeds = [b"campaign_escrow", campaign.key().as_ref()],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Contributor receiving the recovered funds
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct LossRecoveryClaimed {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub contribution: Pubkey,
+    pub contributor: Pubkey,
+    pub contribution_amount: u64,
+    pub amount: u64,
+    pub recovery_pool: u64,
+    pub recorded_loss: u64,
+    pub timestamp: i64,
+}
@@ -44,18 +44,18 @@ pub fn handler(ctx: Context<ClaimOrganizerProfit>) -> Result<()> {
     
     // If there's profit to claim, transfer it
     if organizer_share > 0 {
-        // Validate escrow has sufficient balance
-        let escrow_balance = ctx.accounts.campaign_escrow.lamports();
-        require!(
-            escrow_balance >= organizer_share,
-            EventError::InsufficientBalance
-        );
+        // Validate escrow can pay without dipping into its rent reserve
+        Campaign::require_escrow_covers(
+            ctx.accounts.campaign_escrow.lamports(),
+            Rent::get()?.minimum_balance(0),
+            organizer_share,
+        )?;
 
         let campaign_key = campaign.key();
         let seeds = &[
             b"campaign_escrow",
             campaign_key.as_ref(),
-            &[ctx.bumps.campaign_escrow],
+            &[campaign.escrow_bump],
         ];
         let signer_seeds = &[&seeds[..]];
         
@@ -75,6 +75,14 @@ pub fn handler(ctx: Context<ClaimOrganizerProfit>) -> Result<()> {
         msg!("No profit to claim (loss scenario)");
     }
     
+    emit!(OrganizerProfitClaimed {
+        campaign: campaign.key(),
+        event: campaign.event,
+        organizer: ctx.accounts.organizer.key(),
+        amount: organizer_share,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
 
@@ -94,7 +102,7 @@ pub struct ClaimOrganizerProfit<'info> {
     #[account(
         mut,
         seeds = [b"campaign_escrow", campaign.key().as_ref()],
-        bump
+        bump = campaign.escrow_bump
     )]
     pub campaign_escrow: SystemAccount<'info>,
     
@@ -104,3 +112,12 @@ pub struct ClaimOrganizerProfit<'info> {
     
     pub system_program: Program<'info, System>,
 }
+
+#[event]
+pub struct OrganizerProfitClaimed {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub organizer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
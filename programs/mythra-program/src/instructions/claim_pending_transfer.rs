@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenInterface, TokenAccount, transfer_checked, TransferChecked, close_account, CloseAccount, Mint,
+};
+use crate::errors::EventError;
+use crate::state::{Event, Ticket, TicketIndex, PendingTransfer, PlatformConfig, BlocklistEntry};
+
+#[derive(Accounts)]
+pub struct ClaimPendingTransfer<'info> {
+    /// Ticket being claimed
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event account (must match ticket.event)
+    #[account(
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// NFT mint account
+    #[account(
+        constraint = mint.key() == ticket.mint @ EventError::InvalidMintOwner
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Pending transfer being claimed (closed to the sender)
+    #[account(
+        mut,
+        close = sender,
+        seeds = [b"pending_transfer", ticket.key().as_ref()],
+        bump = pending_transfer.bump,
+        has_one = sender @ EventError::InvalidOwner
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+    
+    /// Escrow token account holding the NFT (closed to the sender)
+    #[account(
+        mut,
+        seeds = [b"pending_transfer_escrow", ticket.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Claimer's token account (new owner)
+    #[account(
+        mut,
+        constraint = claimer_token_account.mint == ticket.mint @ EventError::InvalidMintOwner,
+        constraint = claimer_token_account.owner == claimer.key() @ EventError::InvalidOwner
+    )]
+    pub claimer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Claimer (new ticket owner) - must sign
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+    
+    /// Sender who created the pending transfer (receives the escrow rent)
+    /// CHECK: Must match pending_transfer.sender
+    #[account(mut)]
+    pub sender: AccountInfo<'info>,
+    
+    /// Blocklist entry PDA for the claimer (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), claimer.key().as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
+    /// Sender's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        mut,
+        seeds = [b"ticket_index", event.key().as_ref(), sender.key().as_ref()],
+        bump = sender_ticket_index.bump
+    )]
+    pub sender_ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Claimer's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = TicketIndex::LEN,
+        seeds = [b"ticket_index", event.key().as_ref(), claimer.key().as_ref()],
+        bump
+    )]
+    pub claimer_ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Claim a pending ticket transfer with its secret
+///
+/// Anyone presenting a preimage of the stored claim hash before the
+/// deadline receives the NFT and becomes the ticket owner. The escrow
+/// accounts are closed and their rent returned to the sender.
+pub fn handler(ctx: Context<ClaimPendingTransfer>, preimage: Vec<u8>) -> Result<()> {
+    let event = &ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Validation: platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // Validation: right secret, before the deadline
+    ctx.accounts.pending_transfer.require_claimable(&preimage, now)?;
+    
+    // Validation: claimer must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &event.key(),
+        &ctx.accounts.claimer.key(),
+    )?;
+    
+    // Release the NFT from escrow to the claimer
+    let ticket_key = ctx.accounts.ticket.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"pending_transfer",
+        ticket_key.as_ref(),
+        &[ctx.accounts.pending_transfer.bump],
+    ]];
+    
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: ctx.accounts.claimer_token_account.to_account_info(),
+                authority: ctx.accounts.pending_transfer.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.sender.to_account_info(),
+            authority: ctx.accounts.pending_transfer.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+    
+    let ticket = &mut ctx.accounts.ticket;
+    let old_owner = ticket.owner;
+    ticket.owner = ctx.accounts.claimer.key();
+    ticket.last_sale_price = 0;
+    ticket.last_sale_royalty = 0;
+    ticket.checkin_delegate = Pubkey::default();
+    
+    // Move the ticket between wallet indexes if the event maintains them
+    if event.maintain_ticket_index {
+        let sender_ticket_index = ctx.accounts.sender_ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        sender_ticket_index.remove(&ticket.key());
+        
+        let claimer_ticket_index = ctx.accounts.claimer_ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        claimer_ticket_index.init_if_new(
+            event.key(),
+            ctx.accounts.claimer.key(),
+            ctx.bumps.claimer_ticket_index.ok_or(EventError::TicketIndexRequired)?,
+        );
+        claimer_ticket_index.add(ticket.key());
+    }
+    
+    emit!(PendingTransferClaimed {
+        pending_transfer: ctx.accounts.pending_transfer.key(),
+        ticket: ticket.key(),
+        event: event.key(),
+        from_owner: old_owner,
+        to_owner: ticket.owner,
+        timestamp: now,
+    });
+    
+    msg!("Pending transfer claimed: {}", ticket.key());
+    msg!("From: {}", old_owner);
+    msg!("To: {}", ticket.owner);
+    
+    Ok(())
+}
+
+#[event]
+pub struct PendingTransferClaimed {
+    pub pending_transfer: Pubkey,
+    pub ticket: Pubkey,
+    pub event: Pubkey,
+    pub from_owner: Pubkey,
+    pub to_owner: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount, burn, Burn, thaw_account, ThawAccount};
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Ticket, BuyerRecord};
+
+#[derive(Accounts)]
+pub struct ClaimPostponementRefund<'info> {
+    /// Ticket account to be refunded
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.owner == owner.key() @ EventError::InvalidOwner
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Postponed event (must match ticket.event)
+    #[account(
+        mut,
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Tier account (seat is returned to supply)
+    #[account(
+        mut,
+        constraint = tier.key() == ticket.tier @ EventError::UnauthorizedRefund
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Original buyer's purchase counter for this tier (absent for
+    /// tickets registered without a purchase)
+    #[account(
+        mut,
+        seeds = [b"buyer", tier.key().as_ref(), ticket.buyer.as_ref()],
+        bump = buyer_record.bump
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+    
+    /// Ticket escrow holding ticket sale proceeds
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// NFT mint to be burned
+    #[account(
+        mut,
+        constraint = mint.key() == ticket.mint @ EventError::InvalidMintOwner
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Owner's token account holding the NFT
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == ticket.mint @ EventError::InvalidMintOwner,
+        constraint = owner_token_account.owner == owner.key() @ EventError::InvalidMintOwner,
+        constraint = owner_token_account.amount == 1 @ EventError::TicketNotOwned
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Program PDA holding the mint's freeze authority (required to thaw a
+    /// soulbound ticket's NFT before burning it)
+    /// CHECK: Address checked by seeds; only signs the thaw CPI
+    #[account(
+        seeds = [b"ticket_freeze"],
+        bump
+    )]
+    pub freeze_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Ticket owner claiming the refund
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Refund a ticket bought before the event was postponed
+///
+/// Holders who bought before the start moved can refund themselves for
+/// `Event::POSTPONEMENT_REFUND_WINDOW_SECONDS` after the postponement
+/// (never past the new start), without the organizer's approval. The full
+/// price paid is returned from the ticket escrow and the NFT is burned.
+pub fn handler(ctx: Context<ClaimPostponementRefund>) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let event = &mut ctx.accounts.event;
+    let tier = &mut ctx.accounts.tier;
+    let clock = Clock::get()?;
+    
+    // Validation: Pre-registered stock was never paid for
+    ticket.require_active()?;
+    
+    // Validation: Ticket must not already be used or refunded
+    require!(
+        !ticket.used,
+        EventError::TicketUsedCannotRefund
+    );
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    // Validation: Bought before the postponement, within the refund window
+    require!(
+        event.postponement_refund_open(ticket.purchased_at, clock.unix_timestamp),
+        EventError::PostponementRefundNotAvailable
+    );
+    
+    let refund_amount = ticket.price_paid;
+    
+    // Validation: Escrow can pay without dipping into its rent reserve
+    let rent = Rent::get()?;
+    let available_balance = ctx.accounts.ticket_escrow.lamports()
+        .saturating_sub(rent.minimum_balance(0));
+    require!(
+        refund_amount <= available_balance,
+        EventError::InsufficientBalance
+    );
+    
+    // Soulbound tickets are frozen to their owner; thaw before burning
+    if ctx.accounts.owner_token_account.is_frozen() {
+        let freeze_authority = ctx.accounts.freeze_authority.as_ref()
+            .ok_or(EventError::SoulboundMintNotFreezable)?;
+        let bump = ctx.bumps.freeze_authority.ok_or(EventError::SoulboundMintNotFreezable)?;
+        let freeze_seeds: &[&[&[u8]]] = &[&[b"ticket_freeze", &[bump]]];
+        thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            ThawAccount {
+                account: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: freeze_authority.to_account_info(),
+            },
+            freeze_seeds,
+        ))?;
+    }
+    
+    // Burn the NFT (signed by the owner)
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    burn(burn_ctx, 1)?;
+    
+    // Transfer refund from ticket escrow to owner
+    if refund_amount > 0 {
+        let event_key = event.key();
+        let escrow_seeds = &[
+            b"ticket_escrow",
+            event_key.as_ref(),
+            &[event.ticket_escrow_bump],
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.ticket_escrow.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, refund_amount)?;
+    }
+    
+    // Mark ticket as refunded
+    ticket.refunded = true;
+    ticket.refund_ts = clock.unix_timestamp;
+    
+    // Return the seat to the tier so it can be resold
+    tier.release_seat()
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    event.record_ticket_refunded();
+    
+    // Refunded lamports no longer count as ticket revenue
+    event.ticket_revenue = event.ticket_revenue.saturating_sub(refund_amount);
+    
+    // Free up a slot in the owner's per-wallet limit
+    if let Some(buyer_record) = ctx.accounts.buyer_record.as_mut() {
+        buyer_record.purchased_count = buyer_record.purchased_count.saturating_sub(1);
+    }
+    
+    // Emit PostponementRefundClaimed event
+    emit!(PostponementRefundClaimed {
+        ticket_pubkey: ticket.key(),
+        event_pubkey: event.key(),
+        tier_pubkey: tier.key(),
+        mint_pubkey: ticket.mint,
+        owner: ticket.owner,
+        refund_amount,
+        purchased_at: ticket.purchased_at,
+        postponed_at: event.postponed_at,
+        tickets_sold: event.tickets_sold,
+        tickets_refunded: event.tickets_refunded,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Postponement refund claimed: {}", ticket.key());
+    msg!("Refund amount: {} lamports", refund_amount);
+    
+    Ok(())
+}
+
+#[event]
+pub struct PostponementRefundClaimed {
+    pub ticket_pubkey: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub mint_pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub refund_amount: u64,
+    pub purchased_at: i64,
+    pub postponed_at: i64,
+    pub tickets_sold: u32,
+    pub tickets_refunded: u32,
+    pub timestamp: i64,
+}
@@ -5,23 +5,17 @@ use crate::errors::EventError;
 /// Claim refund from a failed campaign
 /// 
 /// If a campaign fails to reach its funding goal by the deadline,
-/// backers can claim a full refund of their contribution.
+/// backers can claim a full refund of their contribution. If the event
+/// was canceled instead, they get a pro-rata share of what the escrow
+/// held at cancellation (see `RefundMode`).
 /// Each contributor must call this individually to receive their refund.
 pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
     let campaign = &mut ctx.accounts.campaign;
     let contribution = &mut ctx.accounts.contribution;
     
-    // Validate campaign has failed
-    require!(
-        campaign.refunds_available(),
-        EventError::CannotRefundFundedCampaign
-    );
-    
-    // Validate contribution hasn't been refunded
-    require!(
-        contribution.can_refund(),
-        EventError::ContributionAlreadyRefunded
-    );
+    // Refunds must be open and the contribution not yet refunded; the
+    // amount depends on how refunds were opened, not on the status
+    let refund_amount = campaign.refund_amount(contribution)?;
     
     // Validate contributor matches
     require!(
@@ -29,14 +23,19 @@ pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
         EventError::UnauthorizedCampaignAction
     );
     
-    let refund_amount = contribution.amount;
+    // Validate escrow can pay without dipping into its rent reserve
+    Campaign::require_escrow_covers(
+        ctx.accounts.campaign_escrow.lamports(),
+        Rent::get()?.minimum_balance(0),
+        refund_amount,
+    )?;
     
     // Transfer refund from escrow to contributor
     let campaign_key = campaign.key();
     let escrow_seeds = &[
         b"campaign_escrow",
         campaign_key.as_ref(),
-        &[ctx.bumps.campaign_escrow],
+        &[campaign.escrow_bump],
     ];
     let signer_seeds = &[&escrow_seeds[..]];
     
@@ -50,11 +49,14 @@ pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
     );
     anchor_lang::system_program::transfer(cpi_context, refund_amount)?;
     
-    // Mark contribution as refunded
+    // Mark contribution as refunded under the campaign's refund mode
     contribution.refunded = true;
+    contribution.refund_mode = campaign.refund_mode.clone();
+    contribution.refunded_amount = contribution.amount;
     
-    // Update campaign totals
-    campaign.total_raised = campaign.total_raised.saturating_sub(refund_amount);
+    // Update campaign totals (by the full contribution, so pro-rata
+    // campaigns still reach zero once everyone has claimed)
+    campaign.total_raised = campaign.total_raised.saturating_sub(contribution.amount);
     
     msg!(
         "Refund processed: {} lamports to {}",
@@ -65,6 +67,7 @@ pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
     // Emit refund event
     emit!(RefundClaimed {
         campaign: campaign.key(),
+        event: campaign.event,
         contributor: ctx.accounts.contributor.key(),
         amount: refund_amount,
         timestamp: Clock::get()?.unix_timestamp,
@@ -101,16 +104,15 @@ pub struct ClaimRefund<'info> {
     pub contribution: Account<'info, Contribution>,
     
     /// Campaign escrow PDA (holds contributions)
-    /// CHECK: PDA derived, sends refund
     #[account(
         mut,
         seeds = [
             b"campaign_escrow",
             campaign.key().as_ref(),
         ],
-        bump
+        bump = campaign.escrow_bump
     )]
-    pub campaign_escrow: AccountInfo<'info>,
+    pub campaign_escrow: SystemAccount<'info>,
     
     /// Contributor claiming refund
     #[account(mut)]
@@ -122,6 +124,7 @@ pub struct ClaimRefund<'info> {
 #[event]
 pub struct RefundClaimed {
     pub campaign: Pubkey,
+    pub event: Pubkey,
     pub contributor: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
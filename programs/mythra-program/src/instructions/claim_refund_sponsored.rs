@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use crate::ed25519::load_signed_message;
+use crate::state::{Campaign, Contribution, PlatformConfig};
+use crate::errors::EventError;
+
+/// Domain prefix for sponsored refund authorizations
+pub const SPONSORED_REFUND_DOMAIN: &[u8] = b"mythra:claim_refund_sponsored";
+
+/// Message the contributor signs to authorize a sponsored refund
+///
+/// Layout: SPONSORED_REFUND_DOMAIN || contribution (32) || relayer (32) ||
+/// relayer_fee (u64 LE). Binding the relayer and fee stops anyone else from
+/// submitting the authorization or raising the fee; the refund itself always
+/// goes to the contribution's contributor.
+pub fn sponsored_refund_message(contribution: &Pubkey, relayer: &Pubkey, relayer_fee: u64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(SPONSORED_REFUND_DOMAIN.len() + 32 + 32 + 8);
+    message.extend_from_slice(SPONSORED_REFUND_DOMAIN);
+    message.extend_from_slice(contribution.as_ref());
+    message.extend_from_slice(relayer.as_ref());
+    message.extend_from_slice(&relayer_fee.to_le_bytes());
+    message
+}
+
+/// Claim a refund from a failed campaign on the contributor's behalf
+///
+/// A relayer pays the transaction fees and is reimbursed `relayer_fee`
+/// (clamped to the platform maximum) out of the refund; the remainder goes
+/// to the contributor. The contributor authorizes this by signing
+/// `sponsored_refund_message` in an Ed25519Program instruction placed
+/// immediately before this one.
+pub fn handler(ctx: Context<ClaimRefundSponsored>, relayer_fee: u64) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let contribution = &mut ctx.accounts.contribution;
+    
+    // Refunds must be open and the contribution not yet refunded; the
+    // amount depends on how refunds were opened, not on the status
+    let refund_amount = campaign.refund_amount(contribution)?;
+    
+    // Verify the contributor authorized this relayer and fee
+    let message = load_signed_message(
+        &ctx.accounts.instructions,
+        &contribution.contributor,
+    )?;
+    require!(
+        message == sponsored_refund_message(
+            &contribution.key(),
+            &ctx.accounts.relayer.key(),
+            relayer_fee,
+        ),
+        EventError::InvalidSignature
+    );
+    
+    // Validate escrow can pay without dipping into its rent reserve
+    Campaign::require_escrow_covers(
+        ctx.accounts.campaign_escrow.lamports(),
+        Rent::get()?.minimum_balance(0),
+        refund_amount,
+    )?;
+    
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    let fee_paid = PlatformConfig::clamp_relayer_fee(
+        platform_config.as_ref(),
+        relayer_fee,
+        refund_amount,
+    );
+    let contributor_amount = refund_amount - fee_paid;
+    
+    // Transfer refund from escrow to relayer and contributor
+    let campaign_key = campaign.key();
+    let escrow_seeds = &[
+        b"campaign_escrow",
+        campaign_key.as_ref(),
+        &[campaign.escrow_bump],
+    ];
+    let signer_seeds = &[&escrow_seeds[..]];
+    
+    for (recipient, amount) in [
+        (ctx.accounts.relayer.to_account_info(), fee_paid),
+        (ctx.accounts.contributor.to_account_info(), contributor_amount),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.campaign_escrow.to_account_info(),
+                to: recipient,
+            },
+            signer_seeds,
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+    }
+    
+    // Mark contribution as refunded under the campaign's refund mode
+    contribution.refunded = true;
+    contribution.refund_mode = campaign.refund_mode.clone();
+    contribution.refunded_amount = contribution.amount;
+    
+    // Update campaign totals (by the full contribution, so pro-rata
+    // campaigns still reach zero once everyone has claimed)
+    campaign.total_raised = campaign.total_raised.saturating_sub(contribution.amount);
+    
+    msg!(
+        "Sponsored refund processed: {} lamports to {}, {} lamports to relayer {}",
+        contributor_amount,
+        contribution.contributor,
+        fee_paid,
+        ctx.accounts.relayer.key()
+    );
+    
+    // Emit refund event
+    emit!(SponsoredRefundClaimed {
+        campaign: campaign.key(),
+        event: campaign.event,
+        contributor: contribution.contributor,
+        relayer: ctx.accounts.relayer.key(),
+        amount: refund_amount,
+        relayer_fee: fee_paid,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefundSponsored<'info> {
+    /// Campaign that failed
+    #[account(
+        mut,
+        seeds = [
+            b"campaign",
+            campaign.event.as_ref(),
+        ],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Contribution record
+    #[account(
+        mut,
+        seeds = [
+            b"contribution",
+            campaign.key().as_ref(),
+            contributor.key().as_ref(),
+        ],
+        bump = contribution.bump,
+        has_one = campaign,
+        has_one = contributor
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    /// Campaign escrow PDA (holds contributions)
+    #[account(
+        mut,
+        seeds = [
+            b"campaign_escrow",
+            campaign.key().as_ref(),
+        ],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Contributor receiving the refund (does not sign the transaction)
+    #[account(mut)]
+    pub contributor: SystemAccount<'info>,
+    
+    /// Relayer paying transaction fees and receiving the relayer fee
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Sysvar for instruction introspection
+    #[account(address = INSTRUCTIONS_ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SponsoredRefundClaimed {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub contributor: Pubkey,
+    pub relayer: Pubkey,
+    pub amount: u64,
+    pub relayer_fee: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::EventError;
+use crate::state::{Event, Ticket, RefundClaim};
+
+#[derive(Accounts)]
+pub struct ClaimTicketRefund<'info> {
+    /// Registered refund claim
+    #[account(
+        mut,
+        seeds = [b"refund_claim", ticket.key().as_ref()],
+        bump = refund_claim.bump,
+        has_one = claimant @ EventError::UnauthorizedClaim
+    )]
+    pub refund_claim: Account<'info, RefundClaim>,
+    
+    /// Ticket being refunded
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.event == event.key() @ EventError::InvalidEvent
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Canceled event
+    #[account(
+        mut,
+        constraint = refund_claim.event == event.key() @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Ticket escrow paying the refund
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Claimant receiving the payout
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimTicketRefund>) -> Result<()> {
+    let refund_claim = &mut ctx.accounts.refund_claim;
+    let ticket = &mut ctx.accounts.ticket;
+    let event = &mut ctx.accounts.event;
+    let clock = Clock::get()?;
+    
+    // Validation: Claims must be settled
+    require!(
+        event.refund_claims_settled,
+        EventError::RefundClaimsNotSettled
+    );
+    
+    // Validation: Claim not already paid
+    require!(
+        !refund_claim.paid,
+        EventError::RefundClaimAlreadyPaid
+    );
+    
+    let payout = refund_claim.payout(event.refund_factor);
+    
+    if payout > 0 {
+        let event_key = event.key();
+        let escrow_seeds = &[
+            b"ticket_escrow",
+            event_key.as_ref(),
+            &[event.ticket_escrow_bump],
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.ticket_escrow.to_account_info(),
+                to: ctx.accounts.claimant.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        transfer(transfer_ctx, payout)?;
+    }
+    
+    // Mark claim paid and ticket refunded
+    refund_claim.paid = true;
+    ticket.refunded = true;
+    ticket.refund_ts = clock.unix_timestamp;
+    event.record_ticket_refunded();
+    
+    // Emit TicketRefundClaimed event
+    emit!(TicketRefundClaimed {
+        refund_claim: refund_claim.key(),
+        event_pubkey: event.key(),
+        ticket_pubkey: ticket.key(),
+        claimant: refund_claim.claimant,
+        entitlement: refund_claim.amount,
+        payout,
+        refund_factor: event.refund_factor,
+        tickets_sold: event.tickets_sold,
+        tickets_refunded: event.tickets_refunded,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Refund claim paid: {}", refund_claim.key());
+    msg!("Entitlement: {} lamports", refund_claim.amount);
+    msg!("Payout: {} lamports", payout);
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketRefundClaimed {
+    pub refund_claim: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub ticket_pubkey: Pubkey,
+    pub claimant: Pubkey,
+    pub entitlement: u64,
+    pub payout: u64,
+    pub refund_factor: u64,
+    pub tickets_sold: u32,
+    pub tickets_refunded: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::{Budget, BudgetStatus, Campaign};
+use crate::errors::EventError;
+
+/// Close a finished budget and return its rent to the organizer
+///
+/// Allowed once the budget is Executed, or for a Rejected budget once the
+/// revision superseding it has been approved. Pending and Approved budgets
+/// are refused since they are still needed for voting and releases.
+pub fn handler(ctx: Context<CloseBudget>) -> Result<()> {
+    let budget = &ctx.accounts.budget;
+    let superseded_by = ctx.accounts.superseding_budget.as_deref();
+    
+    require!(
+        budget.can_close(superseded_by.map(|revision| &**revision)),
+        EventError::BudgetNotCloseable
+    );
+    
+    msg!("Budget closed: {}", budget.key());
+    
+    emit!(BudgetClosed {
+        budget: budget.key(),
+        campaign: budget.campaign,
+        organizer: ctx.accounts.organizer.key(),
+        status: budget.status.clone(),
+        revision_count: budget.revision_count,
+        superseded_by: superseded_by.map(|revision| revision.key()),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseBudget<'info> {
+    /// Campaign the budget belongs to
+    #[account(
+        has_one = organizer @ EventError::UnauthorizedCampaignAction
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Budget (original or revision) being closed
+    #[account(
+        mut,
+        has_one = campaign,
+        close = organizer
+    )]
+    pub budget: Account<'info, Budget>,
+    
+    /// Revision that superseded a rejected budget
+    #[account(
+        seeds = [
+            b"budget_revision",
+            campaign.key().as_ref(),
+            &[budget.revision_count + 1],
+        ],
+        bump = superseding_budget.bump
+    )]
+    pub superseding_budget: Option<Box<Account<'info, Budget>>>,
+    
+    /// Organizer receiving the rent
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+}
+
+#[event]
+pub struct BudgetClosed {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub organizer: Pubkey,
+    pub status: BudgetStatus,
+    pub revision_count: u8,
+    pub superseded_by: Option<Pubkey>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{Budget, BudgetVote};
+use crate::errors::EventError;
+
+/// Close a vote record and return its rent to the voter
+///
+/// Allowed once voting on the budget has been finalized (or the budget
+/// account itself has been closed). Votes on a pending budget must use
+/// withdraw_budget_vote instead, which also removes them from the tally.
+pub fn handler(ctx: Context<CloseBudgetVote>) -> Result<()> {
+    let vote = &ctx.accounts.vote;
+    
+    if !ctx.accounts.budget.data_is_empty() {
+        let data = ctx.accounts.budget.try_borrow_data()?;
+        let budget = Budget::try_deserialize(&mut &data[..])?;
+        require!(
+            budget.is_finalized(),
+            EventError::BudgetNotFinalized
+        );
+    }
+    
+    msg!("Budget vote closed: {}", vote.key());
+    
+    emit!(BudgetVoteClosed {
+        vote: vote.key(),
+        budget: vote.budget,
+        voter: vote.voter,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseBudgetVote<'info> {
+    /// Budget the vote was cast on
+    /// CHECK: Deserialized in handler unless the budget has been closed
+    #[account(address = vote.budget)]
+    pub budget: UncheckedAccount<'info>,
+    
+    /// Vote record to close (rent returned to voter)
+    #[account(
+        mut,
+        close = voter,
+        seeds = [
+            b"budget_vote",
+            vote.budget.as_ref(),
+            &[vote.revision],
+            voter.key().as_ref(),
+        ],
+        bump = vote.bump,
+        has_one = voter
+    )]
+    pub vote: Account<'info, BudgetVote>,
+    
+    /// Voter who cast the vote
+    #[account(mut)]
+    pub voter: Signer<'info>,
+}
+
+#[event]
+pub struct BudgetVoteClosed {
+    pub vote: Pubkey,
+    pub budget: Pubkey,
+    pub voter: Pubkey,
+    pub timestamp: i64,
+}
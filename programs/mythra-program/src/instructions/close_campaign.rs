@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Budget, Campaign, CampaignStatus, PlatformConfig};
+use crate::errors::EventError;
+
+/// Close a settled campaign and reclaim its rent
+///
+/// Callable by the organizer once the campaign is Failed with every
+/// contribution refunded, or Completed with the organizer's claim made and
+/// every backer claimed (or unclaimed backer funds swept after the claim
+/// deadline).
+///
+/// The platform pool is paid to the platform treasury, any remaining escrow
+/// balance (rent reserve and rounding dust) goes to the organizer, and the
+/// Campaign (plus its Budget, if passed) is closed.
+pub fn handler(ctx: Context<CloseCampaign>) -> Result<()> {
+    let campaign = &ctx.accounts.campaign;
+    let clock = Clock::get()?;
+    
+    // Validate every refund and claim is settled
+    campaign.require_settled()?;
+    
+    let campaign_key = campaign.key();
+    let seeds = &[
+        b"campaign_escrow",
+        campaign_key.as_ref(),
+        &[campaign.escrow_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    // Pay the platform pool first
+    let platform_paid = campaign.platform_pool.min(ctx.accounts.campaign_escrow.lamports());
+    if platform_paid > 0 {
+        let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+        PlatformConfig::require_platform_treasury(
+            platform_config.as_ref(),
+            &ctx.accounts.platform_treasury.key(),
+        )?;
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.campaign_escrow.to_account_info(),
+                to: ctx.accounts.platform_treasury.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, platform_paid)?;
+    }
+    
+    // Drain whatever is left to the organizer
+    let organizer_drained = ctx.accounts.campaign_escrow.lamports();
+    if organizer_drained > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.campaign_escrow.to_account_info(),
+                to: ctx.accounts.organizer.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, organizer_drained)?;
+    }
+    
+    msg!("Campaign closed: {}", campaign.key());
+    msg!("Platform pool paid: {} lamports", platform_paid);
+    msg!("Escrow drained to organizer: {} lamports", organizer_drained);
+    
+    emit!(CampaignClosed {
+        campaign: campaign.key(),
+        event: campaign.event,
+        organizer: ctx.accounts.organizer.key(),
+        status: campaign.status.clone(),
+        platform_paid,
+        organizer_drained,
+        budget_closed: ctx.accounts.budget.is_some(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseCampaign<'info> {
+    /// Settled campaign being closed
+    #[account(
+        mut,
+        has_one = organizer @ EventError::UnauthorizedCampaignAction,
+        close = organizer
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Campaign escrow PDA, drained on close
+    #[account(
+        mut,
+        seeds = [b"campaign_escrow", campaign.key().as_ref()],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Campaign's active budget, closed alongside the campaign when one was
+    /// submitted (superseded budgets are closed with close_budget)
+    #[account(
+        mut,
+        has_one = campaign,
+        constraint = budget.key() == campaign.active_budget @ EventError::BudgetNotActive,
+        close = organizer
+    )]
+    pub budget: Option<Box<Account<'info, Budget>>>,
+    
+    /// Platform treasury receiving the platform pool
+    /// CHECK: Validated against platform_config.platform_treasury when a platform pool is paid
+    #[account(mut)]
+    pub platform_treasury: AccountInfo<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Organizer receiving the reclaimed rent
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct CampaignClosed {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub organizer: Pubkey,
+    pub status: CampaignStatus,
+    pub platform_paid: u64,
+    pub organizer_drained: u64,
+    pub budget_closed: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::Campaign;
+use crate::errors::EventError;
+
+/// Close the campaign escrow and return its balance to the organizer
+///
+/// Allowed once a failed campaign has refunded every contribution, or once a
+/// completed campaign has nothing left to pay out above the rent reserve.
+/// The reserve the organizer paid at creation (plus any rounding dust) is
+/// returned, leaving the escrow empty.
+pub fn handler(ctx: Context<CloseCampaignEscrow>) -> Result<()> {
+    let campaign = &ctx.accounts.campaign;
+    let escrow_balance = ctx.accounts.campaign_escrow.lamports();
+    
+    // Validate nothing is still owed out of the escrow
+    let escrow_available = Campaign::escrow_available(
+        escrow_balance,
+        Rent::get()?.minimum_balance(0),
+    );
+    require!(
+        campaign.escrow_closeable(escrow_available),
+        EventError::CampaignEscrowNotCloseable
+    );
+    
+    if escrow_balance > 0 {
+        let campaign_key = campaign.key();
+        let seeds = &[
+            b"campaign_escrow",
+            campaign_key.as_ref(),
+            &[campaign.escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.campaign_escrow.to_account_info(),
+                to: ctx.accounts.organizer.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        transfer(transfer_ctx, escrow_balance)?;
+    }
+    
+    msg!("Campaign escrow closed, {} lamports returned to organizer", escrow_balance);
+    
+    emit!(CampaignEscrowClosed {
+        campaign: campaign.key(),
+        event: campaign.event,
+        organizer: ctx.accounts.organizer.key(),
+        amount: escrow_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseCampaignEscrow<'info> {
+    /// Failed or completed campaign
+    #[account(
+        has_one = organizer @ EventError::UnauthorizedCampaignAction
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Campaign escrow PDA being closed
+    #[account(
+        mut,
+        seeds = [b"campaign_escrow", campaign.key().as_ref()],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Organizer receiving the rent reserve
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct CampaignEscrowClosed {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub organizer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
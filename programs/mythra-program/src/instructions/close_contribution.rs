@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::Contribution;
+use crate::errors::EventError;
+
+/// Close a settled contribution and return its rent to the backer
+///
+/// Allowed once the contribution has been refunded or its profit share
+/// claimed, or once the campaign itself has been closed. A contribution with
+/// an unclaimed profit share can't be closed while the campaign is open.
+pub fn handler(ctx: Context<CloseContribution>) -> Result<()> {
+    let contribution = &ctx.accounts.contribution;
+    let campaign_closed = ctx.accounts.campaign.data_is_empty();
+    
+    require!(
+        contribution.can_close(campaign_closed),
+        EventError::ContributionNotSettled
+    );
+    
+    msg!("Contribution closed: {}", contribution.key());
+    
+    emit!(ContributionClosed {
+        campaign: contribution.campaign,
+        contribution: contribution.key(),
+        contributor: contribution.contributor,
+        refunded: contribution.refunded,
+        profit_claimed: contribution.profit_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseContribution<'info> {
+    /// Contribution being closed
+    #[account(
+        mut,
+        has_one = contributor @ EventError::UnauthorizedClaim,
+        close = contributor
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    /// Campaign the contribution belongs to
+    /// CHECK: Only inspected to tell whether the campaign has been closed
+    #[account(address = contribution.campaign @ EventError::InvalidCampaign)]
+    pub campaign: UncheckedAccount<'info>,
+    
+    /// Backer receiving the rent
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+}
+
+#[event]
+pub struct ContributionClosed {
+    pub campaign: Pubkey,
+    pub contribution: Pubkey,
+    pub contributor: Pubkey,
+    pub refunded: bool,
+    pub profit_claimed: bool,
+    pub timestamp: i64,
+}
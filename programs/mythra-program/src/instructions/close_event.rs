@@ -12,13 +12,13 @@ pub struct CloseEvent<'info> {
     )]
     pub event: Account<'info, Event>,
     
-    /// Escrow account (must be empty or rent-exempt only)
+    /// Ticket escrow (must be empty or rent-exempt only)
     #[account(
         mut,
-        seeds = [b"escrow", event.key().as_ref()],
-        bump
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
     )]
-    pub escrow: SystemAccount<'info>,
+    pub ticket_escrow: SystemAccount<'info>,
     
     /// Event authority - receives reclaimed rent
     #[account(mut)]
@@ -41,7 +41,7 @@ pub fn handler(ctx: Context<CloseEvent>) -> Result<()> {
     let rent = Rent::get()?;
     let rent_exempt_minimum = rent.minimum_balance(0);
     
-    let escrow_balance = ctx.accounts.escrow.lamports();
+    let escrow_balance = ctx.accounts.ticket_escrow.lamports();
     
     // Allow small margin for rent-exempt minimum
     require!(
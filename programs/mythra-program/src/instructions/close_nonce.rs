@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::Nonce;
+
+#[derive(Accounts)]
+pub struct CloseNonce<'info> {
+    /// Expired nonce to close
+    #[account(
+        mut,
+        has_one = payer @ EventError::InvalidNonce,
+        close = payer
+    )]
+    pub nonce: Account<'info, Nonce>,
+    
+    /// Original rent payer - receives reclaimed rent
+    /// CHECK: Matched against nonce.payer
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+/// Close an expired check-in nonce and return its rent to the payer
+///
+/// Anyone may call this once the nonce has expired, used or not.
+pub fn handler(ctx: Context<CloseNonce>) -> Result<()> {
+    let nonce = &ctx.accounts.nonce;
+    let clock = Clock::get()?;
+    
+    // Validation: Only expired nonces can be closed
+    require!(
+        nonce.is_expired(clock.unix_timestamp),
+        EventError::NonceNotExpired
+    );
+    
+    emit!(NonceClosed {
+        nonce: nonce.key(),
+        ticket: nonce.ticket,
+        payer: nonce.payer,
+        used: nonce.used,
+        rent_returned: nonce.to_account_info().lamports(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Nonce closed: {}", nonce.key());
+    msg!("Rent returned to: {}", nonce.payer);
+    
+    Ok(())
+}
+
+#[event]
+pub struct NonceClosed {
+    pub nonce: Pubkey,
+    pub ticket: Pubkey,
+    pub payer: Pubkey,
+    pub used: bool,
+    pub rent_returned: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Campaign, Event, Ticket, TicketIndex};
+
+#[derive(Accounts)]
+pub struct CloseTicket<'info> {
+    /// Ticket to close; rent goes back to its owner
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.event == event.key() @ EventError::InvalidEvent,
+        close = owner
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event the ticket was sold for
+    pub event: Account<'info, Event>,
+    
+    /// Event's campaign (required when the event is crowdfunded)
+    pub campaign: Option<Account<'info, Campaign>>,
+    
+    /// Owner's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        mut,
+        seeds = [b"ticket_index", event.key().as_ref(), ticket.owner.as_ref()],
+        bump = ticket_index.bump
+    )]
+    pub ticket_index: Option<Account<'info, TicketIndex>>,
+    
+    /// Ticket owner receiving the reclaimed rent
+    /// CHECK: Matched against ticket.owner
+    #[account(
+        mut,
+        constraint = owner.key() == ticket.owner @ EventError::InvalidOwner
+    )]
+    pub owner: UncheckedAccount<'info>,
+    
+    /// Ticket owner, or the event authority once the close grace period
+    /// after the event has passed
+    pub closer: Signer<'info>,
+}
+
+/// Close a Ticket account after its event and return the rent to its owner
+///
+/// Nothing reads a ticket once its event has ended (and, for crowdfunded
+/// events, profits have been distributed), so used, refunded and no-show
+/// tickets alike can go. Owners close their own; the event authority can
+/// sweep the rest `TICKET_CLOSE_GRACE_SECONDS` after the end, except
+/// unrefunded tickets of a canceled event.
+pub fn handler(ctx: Context<CloseTicket>) -> Result<()> {
+    let ticket = &ctx.accounts.ticket;
+    let event = &ctx.accounts.event;
+    let closer = ctx.accounts.closer.key();
+    let clock = Clock::get()?;
+    
+    // Validation: unused tickets can still be checked in until the end
+    require!(
+        event.has_ended(clock.unix_timestamp),
+        EventError::EventNotEnded
+    );
+    
+    // Validation: owner, or the event authority after the grace period
+    let grace_passed = clock.unix_timestamp
+        > event.end_ts.saturating_add(Event::TICKET_CLOSE_GRACE_SECONDS);
+    require!(
+        closer == ticket.owner || (closer == event.authority && grace_passed),
+        EventError::UnauthorizedTicketClose
+    );
+    
+    // Validation: the authority can't sweep a canceled event's ticket
+    // before its holder got the refund, which still needs the account
+    require!(
+        closer == ticket.owner || !event.canceled || ticket.refunded,
+        EventError::TicketNotRefunded
+    );
+    
+    // Validation: a crowdfunded event's profits must be distributed first
+    // (canceled events never distribute)
+    if let Some(campaign_key) = event.campaign {
+        let campaign = ctx.accounts.campaign
+            .as_ref()
+            .ok_or(EventError::InvalidCampaign)?;
+        require_keys_eq!(campaign.key(), campaign_key, EventError::InvalidCampaign);
+        require!(
+            campaign.distribution_complete || event.canceled,
+            EventError::DistributionNotComplete
+        );
+    }
+    
+    // Drop the ticket from its owner's index
+    if event.maintain_ticket_index {
+        let ticket_index = ctx.accounts.ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        ticket_index.remove(&ticket.key());
+    }
+    
+    emit!(TicketClosed {
+        ticket: ticket.key(),
+        event: event.key(),
+        mint: ticket.mint,
+        owner: ticket.owner,
+        closed_by: closer,
+        rent_returned: ticket.to_account_info().lamports(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Ticket closed: {}", ticket.key());
+    msg!("Rent returned to: {}", ticket.owner);
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketClosed {
+    pub ticket: Pubkey,
+    pub event: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub closed_by: Pubkey,
+    pub rent_returned: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier};
+
+#[derive(Accounts)]
+pub struct CloseTicketTier<'info> {
+    /// Tier being closed (must belong to event)
+    #[account(
+        mut,
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Parent event whose allocated supply is released
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Event authority (receives the tier's rent when it is closed)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Close a tier to further sales
+///
+/// Unsold seats are released from `event.allocated_supply` so they can be
+/// given to another tier. A tier that never sold (or refunded everything)
+/// is released in full and its account closed to the authority; a tier with
+/// sold seats keeps them allocated and stays open, marked closed.
+pub fn handler(ctx: Context<CloseTicketTier>) -> Result<()> {
+    let tier = &mut ctx.accounts.tier;
+    let event = &mut ctx.accounts.event;
+    
+    let released = tier.close_sales()?;
+    event.release_allocation(released)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    let account_closed = tier.current_supply == 0;
+    
+    // Emit TicketTierClosed event
+    emit!(TicketTierClosed {
+        event_pubkey: event.key(),
+        tier_pubkey: tier.key(),
+        released_supply: released,
+        current_supply: tier.current_supply,
+        allocated_supply_after: event.allocated_supply,
+        account_closed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Ticket tier closed: {}", tier.key());
+    msg!("Released supply: {}", released);
+    msg!("Event allocated supply: {}/{}", event.allocated_supply, event.total_supply);
+    
+    // Nothing sold: return the rent and free the tier id
+    if account_closed {
+        tier.close(ctx.accounts.authority.to_account_info())?;
+    }
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketTierClosed {
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub released_supply: u32,
+    pub current_supply: u32,
+    pub allocated_supply_after: u32,
+    pub account_closed: bool,
+    pub timestamp: i64,
+}
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
-use crate::state::{Campaign, Contribution};
+use crate::state::{Campaign, Contribution, Denomination, PlatformConfig, RefundMode};
 use crate::errors::EventError;
 
 /// Contribute SOL to a crowdfunding campaign
 /// 
 /// Backers can contribute any amount of SOL to an active campaign.
 /// Contributions are held in the campaign escrow PDA until the campaign
-/// is finalized (either funded or failed).
+/// is finalized (either funded or failed). Contributing again tops up the
+/// backer's existing Contribution, recording the increase so voting power
+/// can be read as of a budget's snapshot.
 pub fn handler(
     ctx: Context<Contribute>,
     amount: u64,
@@ -16,6 +18,10 @@ pub fn handler(
     let contribution = &mut ctx.accounts.contribution;
     let clock = Clock::get()?;
     
+    // Validate platform is not paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
     // Validate campaign is active
     require!(
         campaign.is_active(),
@@ -44,19 +50,34 @@ pub fn handler(
     );
     transfer(cpi_context, amount)?;
     
-    // Initialize contribution record
-    contribution.campaign = campaign.key();
-    contribution.contributor = ctx.accounts.contributor.key();
-    contribution.amount = amount;
-    contribution.contributed_at = clock.unix_timestamp;
-    contribution.refunded = false;
-    contribution.profit_share = 0; // Will be calculated at distribution
-    contribution.profit_claimed = false;
-    contribution.bump = ctx.bumps.contribution;
+    let is_new = contribution.contributor == Pubkey::default();
+    if is_new {
+        // Initialize contribution record
+        contribution.campaign = campaign.key();
+        contribution.contributor = ctx.accounts.contributor.key();
+        contribution.amount = amount;
+        contribution.contributed_at = clock.unix_timestamp;
+        contribution.refunded = false;
+        contribution.profit_share = 0; // Will be calculated at distribution
+        contribution.profit_claimed = false;
+        contribution.refund_mode = RefundMode::None;
+        contribution.refunded_amount = 0;
+        contribution.loss_recovered = false;
+        contribution.previous_amount = 0;
+        contribution.last_increase_at = clock.unix_timestamp;
+        contribution.bump = ctx.bumps.contribution;
+    } else {
+        // Top up, keeping the amount from before for vote snapshots
+        contribution.record_increase(amount, clock.unix_timestamp)
+            .ok_or(EventError::ArithmeticOverflow)?;
+    }
     
     // Update campaign totals
+    let raised_before = campaign.total_raised;
     campaign.total_raised += amount;
-    campaign.total_contributors = campaign.total_contributors.checked_add(1).ok_or(EventError::ArithmeticOverflow)?;
+    if is_new {
+        campaign.total_contributors = campaign.total_contributors.checked_add(1).ok_or(EventError::ArithmeticOverflow)?;
+    }
     
     msg!(
         "Contribution received: {} lamports from {} (Total raised: {} / {})",
@@ -66,6 +87,31 @@ pub fn handler(
         campaign.funding_goal
     );
     
+    emit!(ContributionReceived {
+        campaign: campaign.key(),
+        event: campaign.event,
+        contribution: contribution.key(),
+        contributor: contribution.contributor,
+        amount,
+        denomination: campaign.denomination,
+        total_raised: campaign.total_raised,
+        total_contributors: campaign.total_contributors,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    // Emit an event for every funding milestone this contribution crossed
+    for threshold_bps in campaign.crossed_funding_milestones(raised_before, campaign.total_raised) {
+        emit!(FundingMilestoneReached {
+            campaign: campaign.key(),
+            event: campaign.event,
+            threshold_bps,
+            total_raised: campaign.total_raised,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Funding milestone reached: {}% of goal", threshold_bps / 100);
+    }
+    
     // Check if goal reached
     if campaign.goal_reached() {
         msg!("🎉 Funding goal reached! Campaign can be finalized.");
@@ -87,9 +133,9 @@ pub struct Contribute<'info> {
     )]
     pub campaign: Account<'info, Campaign>,
     
-    /// Contribution record to be created
+    /// Contribution record (created by the backer's first contribution)
     #[account(
-        init,
+        init_if_needed,
         payer = contributor,
         space = Contribution::LEN,
         seeds = [
@@ -102,16 +148,23 @@ pub struct Contribute<'info> {
     pub contribution: Account<'info, Contribution>,
     
     /// Campaign escrow PDA (holds all contributions)
-    /// CHECK: PDA derived from campaign, receives SOL transfers
     #[account(
         mut,
         seeds = [
             b"campaign_escrow",
             campaign.key().as_ref(),
         ],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
         bump
     )]
-    pub campaign_escrow: AccountInfo<'info>,
+    pub platform_config: UncheckedAccount<'info>,
     
     /// Contributor making the contribution
     #[account(mut)]
@@ -119,3 +172,25 @@ pub struct Contribute<'info> {
     
     pub system_program: Program<'info, System>,
 }
+
+#[event]
+pub struct ContributionReceived {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub contribution: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub denomination: Denomination,
+    pub total_raised: u64,
+    pub total_contributors: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FundingMilestoneReached {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub threshold_bps: u16,
+    pub total_raised: u64,
+    pub timestamp: i64,
+}
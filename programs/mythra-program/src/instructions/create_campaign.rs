@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{Campaign, CampaignStatus, Event};
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Campaign, CampaignStatus, Denomination, Event, PlatformConfig, RefundMode, validate_profit_split};
 use crate::errors::EventError;
 
 /// Create a crowdfunding campaign for an event
@@ -7,10 +8,24 @@ use crate::errors::EventError;
 /// This instruction allows event organizers to create a crowdfunding campaign
 /// to raise funds before the event. Backers can contribute SOL which is held
 /// in escrow until the funding goal is reached.
+///
+/// The profit split (backer/organizer/platform) is fixed at creation and must
+/// respect the platform minimums from the config (or program defaults).
+///
+/// The goal's denomination is stored on the campaign and reported in its
+/// events; only lamports are accepted until other units can be collected.
+///
+/// The campaign escrow is created here as a rent-exempt system account paid
+/// for by the organizer. That reserve is never paid out to contributors and
+/// is returned to the organizer by `close_campaign_escrow`.
 pub fn handler(
     ctx: Context<CreateCampaign>,
     funding_goal: u64,
     deadline: i64,
+    backer_share_bps: u16,
+    organizer_share_bps: u16,
+    platform_share_bps: u16,
+    denomination: Denomination,
 ) -> Result<()> {
     let campaign = &mut ctx.accounts.campaign;
     let event = &mut ctx.accounts.event;
@@ -28,12 +43,24 @@ pub fn handler(
         EventError::DeadlineAfterEventStart
     );
     
+    // Validate the goal is in a unit contributions can be collected in
+    denomination.require_supported()?;
+    
     // Validate funding goal is reasonable (at least 0.1 SOL)
     require!(
         funding_goal >= 100_000_000, // 0.1 SOL minimum
         EventError::InvalidContributionAmount
     );
     
+    // Validate profit split against platform minimums
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    validate_profit_split(
+        platform_config.as_ref(),
+        backer_share_bps,
+        organizer_share_bps,
+        platform_share_bps,
+    )?;
+    
     // Initialize campaign
     campaign.event = event.key();
     campaign.organizer = ctx.accounts.organizer.key();
@@ -50,12 +77,59 @@ pub fn handler(
     campaign.platform_pool = 0;
     campaign.distribution_complete = false;
     campaign.organizer_claimed = false;
+    campaign.backer_share_bps = backer_share_bps;
+    campaign.organizer_share_bps = organizer_share_bps;
+    campaign.platform_share_bps = platform_share_bps;
+    campaign.recorded_loss = 0;
+    campaign.escrow_bump = ctx.bumps.campaign_escrow;
+    campaign.backer_claims = 0;
+    campaign.distributed_at = 0;
+    campaign.deadline_extended = false;
+    campaign.refund_mode = RefundMode::None;
+    campaign.refund_pool = 0;
+    campaign.refund_base = 0;
+    campaign.distribution_basis = 0;
+    campaign.denomination = denomination;
+    campaign.recovery_pool = 0;
+    campaign.claim_deadline = 0;
+    campaign.backer_paid_out = 0;
+    campaign.backer_pool_swept = false;
+    campaign.active_budget = Pubkey::default();
     campaign.bump = ctx.bumps.campaign;
     
+    // Fund the escrow up to its rent-exempt minimum
+    let rent = Rent::get()?;
+    let rent_top_up = rent.minimum_balance(0)
+        .saturating_sub(ctx.accounts.campaign_escrow.lamports());
+    
+    if rent_top_up > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.organizer.to_account_info(),
+                to: ctx.accounts.campaign_escrow.to_account_info(),
+            },
+        );
+        transfer(cpi_context, rent_top_up)?;
+    }
+    
     // Mark event as crowdfunding enabled
     event.crowdfunding_enabled = true;
     event.campaign = Some(campaign.key());
     
+    emit!(CampaignCreated {
+        campaign: campaign.key(),
+        event: campaign.event,
+        organizer: campaign.organizer,
+        funding_goal,
+        denomination,
+        deadline,
+        backer_share_bps,
+        organizer_share_bps,
+        platform_share_bps,
+        timestamp: clock.unix_timestamp,
+    });
+    
     msg!(
         "Campaign created for event with goal {} lamports, deadline {}",
         funding_goal,
@@ -88,6 +162,15 @@ pub struct CreateCampaign<'info> {
     )]
     pub campaign: Account<'info, Campaign>,
     
+    /// Campaign escrow PDA, created rent-exempt with the campaign
+    /// PDA: ["campaign_escrow", campaign.key()]
+    #[account(
+        mut,
+        seeds = [b"campaign_escrow", campaign.key().as_ref()],
+        bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
     /// Event organizer (must match event.authority)
     #[account(mut)]
     pub organizer: Signer<'info>,
@@ -96,5 +179,27 @@ pub struct CreateCampaign<'info> {
     /// CHECK: Validated through has_one constraint on event
     pub authority: UncheckedAccount<'info>,
     
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
 }
+
+#[event]
+pub struct CampaignCreated {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub organizer: Pubkey,
+    pub funding_goal: u64,
+    pub denomination: Denomination,
+    pub deadline: i64,
+    pub backer_share_bps: u16,
+    pub organizer_share_bps: u16,
+    pub platform_share_bps: u16,
+    pub timestamp: i64,
+}
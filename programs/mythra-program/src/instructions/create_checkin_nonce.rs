@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, Ticket, Nonce};
+
+#[derive(Accounts)]
+#[instruction(nonce_hash: [u8; 32])]
+pub struct CreateCheckinNonce<'info> {
+    /// Ticket the nonce will check in
+    #[account(
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.event == event.key() @ EventError::InvalidEvent
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event the ticket belongs to
+    pub event: Account<'info, Event>,
+    
+    /// Nonce to be registered
+    /// PDA: ["nonce", ticket.key(), nonce_hash]
+    #[account(
+        init,
+        payer = payer,
+        space = Nonce::SPACE,
+        seeds = [b"nonce", ticket.key().as_ref(), nonce_hash.as_ref()],
+        bump
+    )]
+    pub nonce: Account<'info, Nonce>,
+    
+    /// Ticket owner or event authority (backend) registering the nonce
+    #[account(
+        constraint = authority.key() == ticket.owner ||
+            authority.key() == event.authority @ EventError::UnauthorizedNonceRegistration
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Pays rent for the nonce and receives it back on close
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateCheckinNonce>,
+    nonce_hash: [u8; 32],
+    expires_in: i64,
+) -> Result<()> {
+    let nonce = &mut ctx.accounts.nonce;
+    let clock = Clock::get()?;
+    
+    nonce.ticket = ctx.accounts.ticket.key();
+    nonce.nonce_hash = nonce_hash;
+    nonce.used = false;
+    nonce.created_at = clock.unix_timestamp;
+    nonce.expires_at = Nonce::expiry_from(clock.unix_timestamp, expires_in)?;
+    nonce.payer = ctx.accounts.payer.key();
+    nonce.bump = ctx.bumps.nonce;
+    
+    // Emit CheckinNonceCreated event
+    emit!(CheckinNonceCreated {
+        nonce: nonce.key(),
+        ticket: nonce.ticket,
+        nonce_hash,
+        expires_at: nonce.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Check-in nonce registered: {}", nonce.key());
+    msg!("Expires at: {}", nonce.expires_at);
+    
+    Ok(())
+}
+
+#[event]
+pub struct CheckinNonceCreated {
+    pub nonce: Pubkey,
+    pub ticket: Pubkey,
+    pub nonce_hash: [u8; 32],
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::ed25519::CHECKIN_MESSAGE_V1;
 use crate::errors::EventError;
-use crate::state::Event;
+use crate::state::{Event, PlatformConfig, PlatformStats, RefundPolicy, UNSET_TIMESTAMP};
 
 #[derive(Accounts)]
 #[instruction(event_id: String, metadata_uri: String)]
@@ -18,8 +19,33 @@ pub struct CreateEvent<'info> {
     pub organizer: Signer<'info>,
     
     /// CHECK: This is the treasury account that will receive funds
+    /// Must be a wallet, not an escrow PDA or a program
+    #[account(
+        constraint = Event::is_valid_treasury(
+            &event.key(),
+            &treasury.key(),
+            treasury.owner,
+            treasury.executable,
+        ) @ EventError::InvalidTreasuryAccount
+    )]
     pub treasury: AccountInfo<'info>,
     
+    /// Platform stats PDA (optional; counters are skipped when omitted)
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Box<Account<'info, PlatformStats>>>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -30,7 +56,7 @@ pub fn handler(
     start_ts: i64,
     end_ts: i64,
     total_supply: u32,
-    platform_split_bps: u16,
+    platform_split_bps: Option<u16>,
 ) -> Result<()> {
     // Validation: metadata URI length
     require!(
@@ -50,6 +76,15 @@ pub fn handler(
         EventError::ZeroSupply
     );
     
+    // Platform split defaults to the platform's configured fee
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    let platform_split_bps = platform_split_bps
+        .unwrap_or_else(|| PlatformConfig::default_platform_fee_bps(platform_config.as_ref()));
+    require!(
+        platform_split_bps <= 10_000,
+        EventError::InvalidPlatformSplit
+    );
+    
     let event = &mut ctx.accounts.event;
     
     // Store event data
@@ -65,8 +100,44 @@ pub fn handler(
     event.crowdfunding_enabled = false; // Will be set to true if campaign created
     event.campaign = None;
     event.ticket_revenue = 0;
+    event.shortfall_mode = false;
+    event.refund_claim_deadline = 0;
+    event.total_refund_claims = 0;
+    event.refund_factor = 0;
+    event.refund_claims_settled = false;
+    event.attendance_snapshot_count = 0;
+    event.maintain_ticket_index = false; // Opt in via update_event
+    event.allow_sales_during_event = false; // Opt in via update_event
+    event.checkin_message_version = CHECKIN_MESSAGE_V1; // Bump via update_event before start
+    event.sales_halted = false;
+    event.platform_fees_paid = 0;
+    event.total_withdrawn = 0;
+    event.backer_presale_seconds = 0;
+    event.backer_presale_min_contribution = 0;
+    event.locales = Vec::new();
+    event.compact_events = false;
+    event.postponed_count = 0;
+    event.postponed_at = UNSET_TIMESTAMP;
+    event.pending_authority = None;
+    event.checked_in_count = 0;
+    event.last_checkin_ts = UNSET_TIMESTAMP;
+    event.tickets_sold = 0;
+    event.tickets_checked_in = 0;
+    event.tickets_refunded = 0;
+    event.refund_policy = RefundPolicy::default(); // Set via update_event before the first sale
+    event.refund_fee_bps_override = None; // Platform config's refund fee applies
+    event.pending_order_funds = 0;
+    
+    // Derive the ticket escrow once so later instructions check and sign
+    // with the stored canonical bump
+    let event_key = event.key();
+    event.ticket_escrow_bump = Pubkey::find_program_address(&[b"ticket_escrow", event_key.as_ref()], ctx.program_id).1;
     event.bump = ctx.bumps.event;
     
+    if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+        stats.record_event_created(Clock::get()?.unix_timestamp);
+    }
+    
     // Emit EventCreated event
     emit!(EventCreated {
         event_pubkey: event.key(),
@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Order, BuyerRecord, PlatformConfig};
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct CreateOrder<'info> {
+    /// Order account to be created
+    #[account(
+        init,
+        payer = buyer,
+        space = Order::space(order_id.len()),
+        seeds = [b"order", event.key().as_ref(), &Order::seed_hash(&order_id)],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+    
+    /// Event account
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+    
+    /// Ticket tier account (seat is reserved at order time)
+    #[account(
+        mut,
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Per-wallet purchase counter for this tier
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = BuyerRecord::LEN,
+        seeds = [b"buyer", tier.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    /// Escrow account to receive ticket payment
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Buyer paying for the order
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateOrder>, order_id: String) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    let tier = &mut ctx.accounts.tier;
+    let event = &mut ctx.accounts.event;
+    let buyer_record = &mut ctx.accounts.buyer_record;
+    let clock = Clock::get()?;
+    
+    // VALIDATION: Order id length
+    require!(
+        order_id.len() <= Order::MAX_ORDER_ID_LENGTH,
+        EventError::OrderIdTooLong
+    );
+    
+    // VALIDATION: Platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // VALIDATION: Event must not be canceled, started or ended
+    event.require_sales_open(clock.unix_timestamp)?;
+    
+    // VALIDATION: Tier sale window must be open
+    tier.require_sale_open(clock.unix_timestamp)?;
+    
+    // VALIDATION: Soulbound tickets are only sold through purchase_ticket,
+    // which freezes the NFT
+    tier.require_transferable()?;
+    
+    // VALIDATION: Check tier has available supply
+    require!(tier.is_available(), EventError::ExceedsTotalSupply);
+    
+    // VALIDATION: Check event-wide capacity across all tiers
+    require!(event.has_capacity(), EventError::EventSoldOut);
+    
+    // VALIDATION: Check per-wallet limit
+    require!(
+        buyer_record.can_purchase(1, tier.max_per_wallet),
+        EventError::PurchaseLimitExceeded
+    );
+    
+    // STEP 1: Transfer payment from buyer to escrow
+    let payment_amount = tier.price_lamports;
+    
+    if payment_amount > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.ticket_escrow.to_account_info(),
+            },
+        );
+        
+        transfer(transfer_ctx, payment_amount)?;
+        
+        msg!("Payment transferred: {} lamports", payment_amount);
+    }
+    
+    // STEP 2: Reserve the seat until the order is fulfilled or canceled
+    tier.current_supply = tier.current_supply
+        .checked_add(1)
+        .ok_or(EventError::ExceedsTotalSupply)?;
+    
+    buyer_record.tier = tier.key();
+    buyer_record.buyer = ctx.accounts.buyer.key();
+    buyer_record.purchased_count = buyer_record.purchased_count
+        .checked_add(1)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    buyer_record.bump = ctx.bumps.buyer_record;
+    
+    event.tickets_sold = event.tickets_sold
+        .checked_add(1)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    event.ticket_revenue = event.ticket_revenue
+        .checked_add(payment_amount)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    // Held for cancel_order until the order is fulfilled
+    event.pending_order_funds = event.pending_order_funds
+        .checked_add(payment_amount)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    // STEP 3: Record the order (mint is set on fulfillment)
+    order.buyer = ctx.accounts.buyer.key();
+    order.event = event.key();
+    order.tier = tier.key();
+    order.mint = Pubkey::default();
+    order.order_id = order_id;
+    order.amount_paid = payment_amount;
+    order.timestamp = clock.unix_timestamp;
+    order.bump = ctx.bumps.order;
+    
+    // Emit OrderCreated event
+    emit!(OrderCreated {
+        order_pubkey: order.key(),
+        event_pubkey: order.event,
+        tier_pubkey: order.tier,
+        buyer: order.buyer,
+        order_id: order.order_id.clone(),
+        amount_paid: payment_amount,
+        timestamp: order.timestamp,
+    });
+    
+    msg!("Order created: {}", order.order_id);
+    msg!("Buyer: {}", order.buyer);
+    msg!("Tier supply: {}/{}", tier.current_supply, tier.max_supply);
+    
+    Ok(())
+}
+
+#[event]
+pub struct OrderCreated {
+    pub order_pubkey: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub buyer: Pubkey,
+    pub order_id: String,
+    pub amount_paid: u64,
+    pub timestamp: i64,
+}
@@ -1,14 +1,14 @@
 use anchor_lang::prelude::*;
 use crate::errors::EventError;
-use crate::state::{Event, TicketTier};
+use crate::state::{Event, EventDelegate, TicketTier, OptionalTimestamp};
 
 #[derive(Accounts)]
-#[instruction(tier_id: String, metadata_uri: String)]
+#[instruction(tier_id: String, params: CreateTicketTierParams)]
 pub struct CreateTicketTier<'info> {
     #[account(
         init,
         payer = authority,
-        space = TicketTier::space(metadata_uri.len()),
+        space = TicketTier::space(params.metadata_uri.len()),
         seeds = [b"tier", event.key().as_ref(), tier_id.as_bytes()],
         bump
     )]
@@ -16,26 +16,67 @@ pub struct CreateTicketTier<'info> {
     
     #[account(
         mut,
-        has_one = authority @ EventError::UnauthorizedTierCreation
+        constraint = EventDelegate::authorizes(
+            &event.authority,
+            &authority.key(),
+            event_delegate.as_deref(),
+            EventDelegate::PERMISSION_TIERS,
+        ) @ EventError::UnauthorizedTierCreation
     )]
     pub event: Account<'info, Event>,
     
+    /// Delegate entry when the signer is not the event authority
+    /// PDA: ["delegate", event.key(), authority.key()]
+    #[account(
+        seeds = [b"delegate", event.key().as_ref(), authority.key().as_ref()],
+        bump = event_delegate.bump
+    )]
+    pub event_delegate: Option<Account<'info, EventDelegate>>,
+    
+    /// Event authority, or a delegate with the tiers permission
     #[account(mut)]
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateTicketTierParams {
+    pub metadata_uri: String,
+    pub price_lamports: u64,
+    pub max_supply: u32,
+    pub royalty_bps: u16,
+    pub tier_index: u8,
+    pub resale_enabled: bool,
+    /// Tickets one wallet may buy from this tier (0 = unlimited)
+    pub max_per_wallet: u8,
+    /// Sale window bounds (0 = unbounded)
+    pub sale_start_ts: i64,
+    pub sale_end_ts: i64,
+    /// Resale price ceiling (0 = uncapped)
+    pub max_resale_price_lamports: u64,
+    /// Soulbound tiers (false) freeze ticket NFTs to their buyers
+    pub transferable: bool,
+}
+
 pub fn handler(
     ctx: Context<CreateTicketTier>,
     tier_id: String,
-    metadata_uri: String,
-    price_lamports: u64,
-    max_supply: u32,
-    royalty_bps: u16,
-    tier_index: u8,
-    resale_enabled: bool,
+    params: CreateTicketTierParams,
 ) -> Result<()> {
+    let CreateTicketTierParams {
+        metadata_uri,
+        price_lamports,
+        max_supply,
+        royalty_bps,
+        tier_index,
+        resale_enabled,
+        max_per_wallet,
+        sale_start_ts,
+        sale_end_ts,
+        max_resale_price_lamports,
+        transferable,
+    } = params;
     let event = &mut ctx.accounts.event;
     let tier = &mut ctx.accounts.tier;
     
@@ -45,34 +86,39 @@ pub fn handler(
         EventError::MetadataUriTooLong
     );
     
-    // Validation: price > 0
+    // Validation: free (RSVP) tiers need a per-wallet limit so one wallet
+    // can't claim the whole tier
     require!(
-        price_lamports > 0,
-        EventError::InvalidPrice
+        price_lamports > 0 || max_per_wallet > 0,
+        EventError::FreeTierRequiresWalletLimit
     );
     
-    // Validation: cumulative supply ≤ event.total_supply
-    let new_allocated = event.allocated_supply
-        .checked_add(max_supply)
-        .ok_or(EventError::ExceedsTotalSupply)?;
-    
-    require!(
-        new_allocated <= event.total_supply,
-        EventError::ExceedsTotalSupply
-    );
+    // Validation: sale window (0 = unbounded) closes by event start
+    TicketTier::validate_sale_window(sale_start_ts, sale_end_ts, event.start_ts)?;
     
-    // Update event allocated supply
-    event.allocated_supply = new_allocated;
+    // Validation: cumulative supply ≤ event.total_supply
+    event.allocate_supply(max_supply)?;
     
     // Store tier data
     tier.event = event.key();
     tier.price_lamports = price_lamports;
     tier.max_supply = max_supply;
     tier.current_supply = 0; // Initialize to 0, incremented on each purchase
-    tier.metadata_uri = metadata_uri.clone();
+    tier.metadata_uri = metadata_uri;
     tier.royalty_bps = royalty_bps;
     tier.resale_enabled = resale_enabled;
     tier.tier_index = tier_index;
+    tier.max_per_wallet = max_per_wallet;
+    tier.sale_start_ts = sale_start_ts;
+    tier.sale_end_ts = sale_end_ts;
+    tier.max_uses = 1; // Re-entry passes opt in via update_ticket_tier
+    tier.promo = None;
+    tier.closed = false;
+    tier.allowlist_root = None;
+    tier.max_resale_price_lamports = max_resale_price_lamports;
+    tier.transferable = transferable;
+    tier.seated = false; // Reserved seating opts in via update_ticket_tier
+    tier.next_seat = 0;
     tier.bump = ctx.bumps.tier;
     
     // Emit TicketTierCreated event
@@ -84,6 +130,8 @@ pub fn handler(
         max_supply,
         metadata_uri: tier.metadata_uri.clone(),
         tier_index,
+        max_resale_price_lamports,
+        transferable,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
@@ -91,6 +139,15 @@ pub fn handler(
     msg!("Tier ID: {}", tier_id);
     msg!("Price: {} lamports", price_lamports);
     msg!("Max supply: {}", max_supply);
+    if max_per_wallet > 0 {
+        msg!("Max per wallet: {}", max_per_wallet);
+    }
+    if sale_start_ts.is_set() || sale_end_ts.is_set() {
+        msg!("Sale window: {} - {}", sale_start_ts, sale_end_ts);
+    }
+    if !transferable {
+        msg!("Soulbound: ticket NFTs are frozen to their buyers");
+    }
     msg!("Event allocated supply: {}/{}", event.allocated_supply, event.total_supply);
     
     Ok(())
@@ -105,5 +162,7 @@ pub struct TicketTierCreated {
     pub max_supply: u32,
     pub metadata_uri: String,
     pub tier_index: u8,
+    pub max_resale_price_lamports: u64,
+    pub transferable: bool,
     pub timestamp: i64,
 }
@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Campaign, CampaignStatus, Contribution};
+use crate::errors::EventError;
+use crate::instructions::claim_backer_profit::BackerProfitClaimed;
+
+/// Maximum contributions per distribution batch (bounded by compute)
+pub const MAX_CONTRIBUTIONS_PER_BATCH: u8 = 10;
+
+/// Accounts passed per contribution via remaining_accounts:
+/// [contribution (writable), contributor (writable)]
+pub const DISTRIBUTION_ACCOUNT_STRIDE: usize = 2;
+
+/// Push backer profit shares to a batch of contributors
+///
+/// Permissionless crank so small shares get paid without each backer
+/// sending a claim. Each share is computed exactly as in
+/// `claim_backer_profit` and paid from the campaign escrow.
+///
+/// Contributions that were already claimed are skipped rather than failing
+/// the batch; a contribution from another campaign or a contributor account
+/// that doesn't match the contribution aborts it. The cranker earns
+/// `Campaign::CRANK_FEE_PER_CONTRIBUTION_LAMPORTS` per processed
+/// contribution out of the platform pool, while the pool lasts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeBackerProfits<'info>>,
+    count: u8,
+) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let clock = Clock::get()?;
+    
+    // Validation: Batch size and account layout
+    require!(
+        count > 0 && count <= MAX_CONTRIBUTIONS_PER_BATCH,
+        EventError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == count as usize * DISTRIBUTION_ACCOUNT_STRIDE,
+        EventError::InvalidBatchAccounts
+    );
+    
+    // Validation: Claim deadline hasn't passed
+    campaign.require_claim_open(clock.unix_timestamp)?;
+    
+    let campaign_key = campaign.key();
+    let seeds = &[
+        b"campaign_escrow",
+        campaign_key.as_ref(),
+        &[campaign.escrow_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    
+    let mut processed: u32 = 0;
+    let mut skipped: u32 = 0;
+    let mut total_paid: u64 = 0;
+    
+    for accounts in ctx.remaining_accounts.chunks(DISTRIBUTION_ACCOUNT_STRIDE) {
+        let contribution_info = &accounts[0];
+        let contributor_info = &accounts[1];
+        
+        let mut contribution = Account::<Contribution>::try_from(contribution_info)?;
+        
+        // Validate: Contribution belongs to this campaign and is paid to
+        // its own contributor
+        require!(
+            contribution.campaign == campaign_key,
+            EventError::InvalidContribution
+        );
+        require!(
+            contribution.contributor == contributor_info.key(),
+            EventError::UnauthorizedClaim
+        );
+        
+        if contribution.profit_claimed {
+            skipped += 1;
+            continue;
+        }
+        
+        let share = campaign.settle_backer_claim(&mut contribution)?;
+        contribution.exit(&crate::ID)?;
+        
+        if share > 0 {
+            // Validate escrow can pay without dipping into its rent reserve
+            Campaign::require_escrow_covers(
+                ctx.accounts.campaign_escrow.lamports(),
+                rent_exempt_minimum,
+                share,
+            )?;
+            
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.campaign_escrow.to_account_info(),
+                    to: contributor_info.clone(),
+                },
+                signer_seeds,
+            );
+            transfer(transfer_ctx, share)?;
+            campaign.record_backer_payout(share)?;
+            
+            total_paid = total_paid
+                .checked_add(share)
+                .ok_or(EventError::ArithmeticOverflow)?;
+            
+            emit!(BackerProfitClaimed {
+                campaign: campaign_key,
+                event: campaign.event,
+                contribution: contribution.key(),
+                contributor: contribution.contributor,
+                contribution_amount: contribution.amount,
+                amount: share,
+                backer_pool: campaign.backer_pool,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        
+        processed += 1;
+    }
+    
+    // Pay the cranker out of the platform pool
+    let crank_fee = campaign.take_crank_fee(processed);
+    if crank_fee > 0 {
+        Campaign::require_escrow_covers(
+            ctx.accounts.campaign_escrow.lamports(),
+            rent_exempt_minimum,
+            crank_fee,
+        )?;
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.campaign_escrow.to_account_info(),
+                to: ctx.accounts.cranker.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, crank_fee)?;
+    }
+    
+    emit!(BackerProfitsDistributed {
+        campaign: campaign_key,
+        event: campaign.event,
+        cranker: ctx.accounts.cranker.key(),
+        processed,
+        skipped,
+        total_paid,
+        crank_fee,
+        backer_paid_out: campaign.backer_paid_out,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Backer profits distributed: {} processed, {} skipped", processed, skipped);
+    msg!("Paid: {} lamports, crank fee: {} lamports", total_paid, crank_fee);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DistributeBackerProfits<'info> {
+    /// Campaign account
+    #[account(
+        mut,
+        constraint = campaign.distribution_complete @ EventError::DistributionNotComplete,
+        constraint = campaign.status == CampaignStatus::Completed @ EventError::InvalidCampaignStatus
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Campaign escrow PDA (holds the funds)
+    #[account(
+        mut,
+        seeds = [b"campaign_escrow", campaign.key().as_ref()],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Anyone cranking the distribution (receives the crank fee)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct BackerProfitsDistributed {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub cranker: Pubkey,
+    pub processed: u32,
+    pub skipped: u32,
+    pub total_paid: u64,
+    pub crank_fee: u64,
+    pub backer_paid_out: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::state::{Budget, Campaign};
+use crate::errors::EventError;
+
+/// Emit a voting-ends-soon reminder for a pending budget
+///
+/// Permissionless crank callable once when less than 24 hours remain
+/// before voting ends. The event carries the current tallies and turnout
+/// so notification services can nudge backers who haven't voted.
+pub fn handler(ctx: Context<EmitVotingReminder>) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let campaign = &ctx.accounts.campaign;
+    let clock = Clock::get()?;
+    
+    // Validate reminder hasn't been emitted yet
+    require!(
+        !budget.reminder_emitted,
+        EventError::ReminderAlreadyEmitted
+    );
+    
+    // Validate we're in the last 24 hours of voting
+    require!(
+        budget.in_reminder_window(clock.unix_timestamp),
+        EventError::ReminderWindowNotOpen
+    );
+    
+    budget.reminder_emitted = true;
+    
+    let turnout_bps = budget.turnout_bps(budget.total_raised_snapshot);
+    
+    emit!(VotingEndingSoon {
+        budget: budget.key(),
+        campaign: campaign.key(),
+        event: campaign.event,
+        voting_end: budget.voting_end,
+        votes_for: budget.votes_for,
+        votes_against: budget.votes_against,
+        votes_abstain: budget.votes_abstain,
+        turnout_bps,
+        quorum_bps: budget.quorum_bps,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!(
+        "Budget voting ends at {}: {} FOR vs {} AGAINST ({}% turnout)",
+        budget.voting_end,
+        budget.votes_for,
+        budget.votes_against,
+        turnout_bps / 100
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EmitVotingReminder<'info> {
+    /// Budget being voted on
+    #[account(
+        mut,
+        constraint = budget.campaign == campaign.key() @ EventError::InvalidCampaign
+    )]
+    pub budget: Account<'info, Budget>,
+    
+    /// Campaign the budget belongs to (for turnout against total raised)
+    pub campaign: Account<'info, Campaign>,
+}
+
+#[event]
+pub struct VotingEndingSoon {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub voting_end: i64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub turnout_bps: u64,
+    pub quorum_bps: u16,
+    pub timestamp: i64,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{Campaign, Event};
+use crate::errors::EventError;
+
+/// Extend the deadline of a pending campaign
+///
+/// Gives a campaign more runway instead of letting it fail and refund
+/// every backer. The organizer may extend once, to a later deadline that
+/// is still before the event starts.
+pub fn handler(ctx: Context<ExtendCampaignDeadline>, new_deadline: i64) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let clock = Clock::get()?;
+    
+    let old_deadline = campaign.extend_deadline(
+        new_deadline,
+        clock.unix_timestamp,
+        ctx.accounts.event.start_ts,
+    )?;
+    
+    emit!(CampaignDeadlineExtended {
+        campaign: campaign.key(),
+        event: campaign.event,
+        old_deadline,
+        new_deadline,
+        total_raised: campaign.total_raised,
+        funding_goal: campaign.funding_goal,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!(
+        "Campaign deadline extended from {} to {}",
+        old_deadline,
+        new_deadline
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExtendCampaignDeadline<'info> {
+    /// Pending campaign
+    #[account(
+        mut,
+        has_one = organizer @ EventError::UnauthorizedCampaignAction
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Event the campaign is funding (the deadline must stay before it starts)
+    #[account(
+        address = campaign.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Campaign organizer (signer)
+    pub organizer: Signer<'info>,
+}
+
+#[event]
+pub struct CampaignDeadlineExtended {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+    pub total_raised: u64,
+    pub funding_goal: u64,
+    pub timestamp: i64,
+}
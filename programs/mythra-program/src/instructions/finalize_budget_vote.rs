@@ -1,14 +1,20 @@
 use anchor_lang::prelude::*;
-use crate::state::{Budget, BudgetStatus};
+use crate::state::{Budget, BudgetStatus, Campaign};
 use crate::errors::EventError;
 
 /// Finalize budget voting
 /// 
 /// After voting period ends, anyone can call this to finalize
-/// the result. If votes_for > votes_against, budget is approved.
-/// Otherwise, it's rejected and can be revised.
+/// the result against the campaign's total raised when voting opened:
+/// - turnout (abstentions included) below the budget's `min_turnout_bps`
+///   expires the vote, and the organizer can resubmit without using a
+///   revision
+/// - otherwise the budget is approved if quorum is met and `votes_for` is
+///   strictly greater than `votes_against`, and rejected (and can be
+///   revised) if not
 pub fn handler(ctx: Context<FinalizeBudgetVote>) -> Result<()> {
     let budget = &mut ctx.accounts.budget;
+    let campaign = &ctx.accounts.campaign;
     let clock = Clock::get()?;
     
     // Validate budget is pending
@@ -23,57 +29,86 @@ pub fn handler(ctx: Context<FinalizeBudgetVote>) -> Result<()> {
         EventError::VotingPeriodNotEnded
     );
     
-    // Determine result
-    if budget.is_approved() {
-        budget.status = BudgetStatus::Approved;
-        
-        msg!(
+    // Check turnout against the total raised when voting opened
+    let total_raised = budget.total_raised_snapshot;
+    let quorum_met = budget.quorum_met(total_raised);
+    let turnout_bps = budget.turnout_bps(total_raised);
+    
+    budget.finalized_at = clock.unix_timestamp;
+    budget.status = budget.outcome();
+    
+    match budget.status {
+        BudgetStatus::Approved => msg!(
             "✅ Budget APPROVED! {} FOR vs {} AGAINST",
             budget.votes_for,
             budget.votes_against
-        );
-        
-        emit!(BudgetFinalized {
-            budget: budget.key(),
-            status: BudgetStatus::Approved,
-            votes_for: budget.votes_for,
-            votes_against: budget.votes_against,
-            timestamp: clock.unix_timestamp,
-        });
-    } else {
-        budget.status = BudgetStatus::Rejected;
-        
-        msg!(
-            "❌ Budget REJECTED. {} FOR vs {} AGAINST",
-            budget.votes_for,
-            budget.votes_against
-        );
-        msg!("Organizer can revise and resubmit (max 2 revisions)");
-        
-        emit!(BudgetFinalized {
-            budget: budget.key(),
-            status: BudgetStatus::Rejected,
-            votes_for: budget.votes_for,
-            votes_against: budget.votes_against,
-            timestamp: clock.unix_timestamp,
-        });
+        ),
+        BudgetStatus::Expired => {
+            msg!(
+                "⌛ Budget vote EXPIRED: turnout {} bps below the {} bps floor",
+                turnout_bps,
+                budget.min_turnout_bps
+            );
+            msg!("Organizer can resubmit without using a revision");
+        }
+        _ => {
+            msg!(
+                "❌ Budget REJECTED. {} FOR vs {} AGAINST",
+                budget.votes_for,
+                budget.votes_against
+            );
+            if !quorum_met {
+                msg!(
+                    "Quorum not met: {} bps of {} lamports raised required",
+                    budget.quorum_bps,
+                    total_raised
+                );
+            }
+            msg!("Organizer can revise and resubmit (max 2 revisions)");
+        }
     }
     
+    emit!(BudgetFinalized {
+        budget: budget.key(),
+        event: campaign.event,
+        status: budget.status.clone(),
+        votes_for: budget.votes_for,
+        votes_against: budget.votes_against,
+        votes_abstain: budget.votes_abstain,
+        votes_cast_count: budget.votes_cast_count,
+        total_voting_power_cast: budget.total_voting_power_cast,
+        quorum_met,
+        turnout_bps,
+        timestamp: clock.unix_timestamp,
+    });
+    
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct FinalizeBudgetVote<'info> {
     /// Budget to finalize
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = budget.campaign == campaign.key() @ EventError::InvalidCampaign
+    )]
     pub budget: Account<'info, Budget>,
+    
+    /// Campaign the budget belongs to (for turnout against total raised)
+    pub campaign: Account<'info, Campaign>,
 }
 
 #[event]
 pub struct BudgetFinalized {
     pub budget: Pubkey,
+    pub event: Pubkey,
     pub status: BudgetStatus,
     pub votes_for: u64,
     pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub votes_cast_count: u32,
+    pub total_voting_power_cast: u64,
+    pub quorum_met: bool,
+    pub turnout_bps: u64,
     pub timestamp: i64,
 }
@@ -1,6 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{Campaign, CampaignStatus};
-use crate::errors::EventError;
+use crate::state::{Campaign, CampaignStatus, Denomination, PlatformStats};
 
 /// Finalize a crowdfunding campaign
 /// 
@@ -14,17 +13,13 @@ pub fn handler(ctx: Context<FinalizeCampaign>) -> Result<()> {
     let campaign = &mut ctx.accounts.campaign;
     let clock = Clock::get()?;
     
-    // Validate campaign can be finalized
-    require!(
-        campaign.can_finalize(clock.unix_timestamp),
-        EventError::AlreadyFinalized
-    );
+    // Perform the status transition before anything else; a losing
+    // concurrent finalize fails here with AlreadyFinalized
+    let status = campaign.finalize(clock.unix_timestamp)?;
     
-    // Determine campaign outcome
-    if campaign.goal_reached() {
+    // Report campaign outcome
+    if status == CampaignStatus::Funded {
         // Success: Goal reached (even if before deadline)
-        campaign.status = CampaignStatus::Funded;
-        
         msg!(
             "✅ Campaign FUNDED! Raised {} / {} lamports from {} contributors",
             campaign.total_raised,
@@ -33,18 +28,24 @@ pub fn handler(ctx: Context<FinalizeCampaign>) -> Result<()> {
         );
         msg!("Event can now proceed with budget submission and ticket sales.");
         
+        if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+            stats.record_campaign_funded(campaign.total_raised, clock.unix_timestamp);
+        }
+        
         // Emit success event
         emit!(CampaignFinalized {
             campaign: campaign.key(),
+            event: campaign.event,
             status: CampaignStatus::Funded,
+            funding_goal: campaign.funding_goal,
+            denomination: campaign.denomination,
+            deadline: campaign.deadline,
             total_raised: campaign.total_raised,
             total_contributors: campaign.total_contributors,
             timestamp: clock.unix_timestamp,
         });
     } else {
         // Failure: Deadline passed without reaching goal
-        campaign.status = CampaignStatus::Failed;
-        
         msg!(
             "❌ Campaign FAILED. Only raised {} / {} lamports",
             campaign.total_raised,
@@ -55,7 +56,11 @@ pub fn handler(ctx: Context<FinalizeCampaign>) -> Result<()> {
         // Emit failure event
         emit!(CampaignFinalized {
             campaign: campaign.key(),
+            event: campaign.event,
             status: CampaignStatus::Failed,
+            funding_goal: campaign.funding_goal,
+            denomination: campaign.denomination,
+            deadline: campaign.deadline,
             total_raised: campaign.total_raised,
             total_contributors: campaign.total_contributors,
             timestamp: clock.unix_timestamp,
@@ -70,12 +75,24 @@ pub struct FinalizeCampaign<'info> {
     /// Campaign to finalize
     #[account(mut)]
     pub campaign: Account<'info, Campaign>,
+    
+    /// Platform stats PDA (optional; counters are skipped when omitted)
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Box<Account<'info, PlatformStats>>>,
 }
 
 #[event]
 pub struct CampaignFinalized {
     pub campaign: Pubkey,
+    pub event: Pubkey,
     pub status: CampaignStatus,
+    pub funding_goal: u64,
+    pub denomination: Denomination,
+    pub deadline: i64,
     pub total_raised: u64,
     pub total_contributors: u32,
     pub timestamp: i64,
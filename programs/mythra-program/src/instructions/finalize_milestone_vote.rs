@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::state::{Budget, Campaign, MilestoneApproval};
+use crate::errors::EventError;
+
+/// Finalize a milestone's release vote
+///
+/// After the vote ends anyone can call this. The milestone becomes
+/// releasable if the vote met the budget's quorum (measured against the
+/// budget's total raised snapshot) and more power approved than rejected,
+/// the same rules as the budget vote. A rejected milestone is void: its
+/// funds stay in escrow for distribution.
+pub fn handler(
+    ctx: Context<FinalizeMilestoneVote>,
+    milestone_index: u8,
+) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let clock = Clock::get()?;
+    
+    // Validate milestone index
+    require!(
+        (milestone_index as usize) < budget.milestones.len(),
+        EventError::MilestoneNotReady
+    );
+    
+    let total_raised_snapshot = budget.total_raised_snapshot;
+    let quorum_bps = budget.quorum_bps;
+    let milestone = &mut budget.milestones[milestone_index as usize];
+    
+    // Validate the vote is open and its period has ended
+    require!(
+        milestone.approval == MilestoneApproval::Voting,
+        EventError::MilestoneVoteNotOpen
+    );
+    require!(
+        clock.unix_timestamp >= milestone.vote_end,
+        EventError::VotingPeriodNotEnded
+    );
+    
+    let quorum_met = Budget::turnout_meets_quorum(
+        milestone.turnout_amount as u128,
+        total_raised_snapshot,
+        quorum_bps,
+    );
+    milestone.approval = if quorum_met && Budget::majority_approves(milestone.votes_for, milestone.votes_against) {
+        MilestoneApproval::Approved
+    } else {
+        MilestoneApproval::Rejected
+    };
+    
+    msg!(
+        "Milestone {} release {:?}: {} FOR vs {} AGAINST",
+        milestone_index,
+        milestone.approval,
+        milestone.votes_for,
+        milestone.votes_against
+    );
+    if !quorum_met {
+        msg!(
+            "Quorum not met: {} bps of {} lamports raised required",
+            quorum_bps,
+            total_raised_snapshot
+        );
+    }
+    
+    let (approval, votes_for, votes_against) =
+        (milestone.approval.clone(), milestone.votes_for, milestone.votes_against);
+    
+    emit!(MilestoneVoteFinalized {
+        budget: budget.key(),
+        campaign: ctx.accounts.campaign.key(),
+        milestone_index,
+        approval,
+        votes_for,
+        votes_against,
+        quorum_met,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeMilestoneVote<'info> {
+    /// Budget with the milestone
+    #[account(
+        mut,
+        has_one = campaign,
+        constraint = budget.key() == campaign.active_budget @ EventError::BudgetNotActive
+    )]
+    pub budget: Account<'info, Budget>,
+    
+    /// Campaign this budget belongs to
+    pub campaign: Account<'info, Campaign>,
+}
+
+#[event]
+pub struct MilestoneVoteFinalized {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub milestone_index: u8,
+    pub approval: MilestoneApproval,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub quorum_met: bool,
+    pub timestamp: i64,
+}
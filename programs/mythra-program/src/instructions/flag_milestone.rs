@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Campaign, Budget, BudgetStatus, Contribution, Dispute, DisputeStatus, UNSET_TIMESTAMP};
+use crate::errors::EventError;
+
+/// Flag an approved milestone as disputed
+///
+/// Any backer can flag a milestone that hasn't been released yet. The
+/// flagger posts a bond of 0.1% of the milestone amount (bounded by
+/// `Dispute::MIN_BOND_LAMPORTS` and `Dispute::MAX_BOND_LAMPORTS`) into the
+/// milestone's bond PDA, which keeps frivolous flags costly. The milestone
+/// can't be released until the platform admin resolves the dispute.
+pub fn handler(
+    ctx: Context<FlagMilestone>,
+    milestone_index: u8,
+) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let dispute = &mut ctx.accounts.dispute;
+    let clock = Clock::get()?;
+    
+    // Validate budget is approved
+    require!(
+        budget.status == BudgetStatus::Approved,
+        EventError::BudgetNotApproved
+    );
+    
+    // Validate milestone index
+    require!(
+        (milestone_index as usize) < budget.milestones.len(),
+        EventError::MilestoneNotReady
+    );
+    
+    let milestone = &budget.milestones[milestone_index as usize];
+    
+    // Validate not already released
+    require!(
+        !milestone.released,
+        EventError::MilestoneAlreadyReleased
+    );
+    
+    // Validate never disputed before (one dispute per milestone)
+    require!(
+        milestone.dispute_status == DisputeStatus::None,
+        EventError::MilestoneAlreadyDisputed
+    );
+    
+    let milestone_amount = milestone.amount(budget.total_amount);
+    let bond_amount = Dispute::bond_for(milestone_amount);
+    
+    // Transfer the bond from the flagger into the bond PDA
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.flagger.to_account_info(),
+            to: ctx.accounts.dispute_bond.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, bond_amount)?;
+    
+    // Record the dispute
+    dispute.budget = budget.key();
+    dispute.milestone_index = milestone_index;
+    dispute.flagger = ctx.accounts.flagger.key();
+    dispute.bond_amount = bond_amount;
+    dispute.status = DisputeStatus::Open;
+    dispute.flagged_at = clock.unix_timestamp;
+    dispute.resolved_at = UNSET_TIMESTAMP;
+    dispute.bump = ctx.bumps.dispute;
+    
+    budget.milestones[milestone_index as usize].dispute_status = DisputeStatus::Open;
+    
+    msg!("Milestone {} flagged by {}", milestone_index, dispute.flagger);
+    msg!("Bond: {} lamports ({} lamports milestone)", bond_amount, milestone_amount);
+    
+    emit!(MilestoneFlagged {
+        budget: budget.key(),
+        campaign: ctx.accounts.campaign.key(),
+        dispute: dispute.key(),
+        milestone_index,
+        flagger: dispute.flagger,
+        milestone_amount,
+        bond_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(milestone_index: u8)]
+pub struct FlagMilestone<'info> {
+    /// Campaign the budget belongs to
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Budget with the disputed milestone
+    #[account(
+        mut,
+        has_one = campaign,
+        constraint = budget.key() == campaign.active_budget @ EventError::BudgetNotActive
+    )]
+    pub budget: Account<'info, Budget>,
+    
+    /// Flagger's contribution (only backers can flag)
+    #[account(
+        seeds = [
+            b"contribution",
+            campaign.key().as_ref(),
+            flagger.key().as_ref(),
+        ],
+        bump = contribution.bump,
+        has_one = campaign @ EventError::InvalidContribution,
+        constraint = contribution.net_amount() > 0 @ EventError::NotAContributor
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    /// Dispute record for this milestone
+    #[account(
+        init,
+        payer = flagger,
+        space = Dispute::LEN,
+        seeds = [
+            b"milestone_dispute",
+            budget.key().as_ref(),
+            &[milestone_index],
+        ],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    
+    /// Bond PDA holding the flagger's bond until resolution
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond",
+            budget.key().as_ref(),
+            &[milestone_index],
+        ],
+        bump
+    )]
+    pub dispute_bond: SystemAccount<'info>,
+    
+    /// Backer flagging the milestone and posting the bond
+    #[account(mut)]
+    pub flagger: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct MilestoneFlagged {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub dispute: Pubkey,
+    pub milestone_index: u8,
+    pub flagger: Pubkey,
+    pub milestone_amount: u64,
+    pub bond_amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Ticket, TicketStatus, Order, TicketIndex, UNSET_TIMESTAMP};
+
+#[derive(Accounts)]
+pub struct FulfillOrder<'info> {
+    /// Paid order awaiting its NFT
+    #[account(
+        mut,
+        seeds = [b"order", event.key().as_ref(), &Order::seed_hash(&order.order_id)],
+        bump = order.bump,
+        constraint = order.tier == tier.key() @ EventError::InvalidOrder
+    )]
+    pub order: Account<'info, Order>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Ticket::SPACE,
+        seeds = [b"ticket", mint.key().as_ref()],
+        bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedTierCreation
+    )]
+    pub event: Account<'info, Event>,
+    
+    #[account(
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Freshly minted ticket NFT
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        constraint = buyer_token_account.mint == mint.key() @ EventError::InvalidMintOwner,
+        constraint = buyer_token_account.owner == order.buyer @ EventError::InvalidMintOwner,
+        constraint = buyer_token_account.amount == 1 @ EventError::InvalidSupply
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Buyer who placed the order
+    #[account(
+        constraint = buyer.key() == order.buyer @ EventError::InvalidOwner
+    )]
+    pub buyer: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Buyer's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TicketIndex::LEN,
+        seeds = [b"ticket_index", event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<FulfillOrder>) -> Result<()> {
+    let order = &mut ctx.accounts.order;
+    let ticket = &mut ctx.accounts.ticket;
+    let mint = &ctx.accounts.mint;
+    let event = &mut ctx.accounts.event;
+    let clock = Clock::get()?;
+    
+    // Validation: order must not already have a ticket
+    require!(
+        !order.is_fulfilled(),
+        EventError::OrderAlreadyFulfilled
+    );
+    
+    // Validation: mint supply must be exactly 1
+    require!(
+        mint.supply == 1,
+        EventError::InvalidSupply
+    );
+    
+    // Seat, wallet limit and revenue were already accounted for in
+    // create_order; the payment is now the organizer's to withdraw
+    event.pending_order_funds = event.pending_order_funds.saturating_sub(order.amount_paid);
+    
+    // Store ticket data
+    ticket.owner = order.buyer;
+    ticket.event = event.key();
+    ticket.tier = order.tier;
+    ticket.mint = mint.key();
+    ticket.used = false;
+    ticket.refunded = false;
+    ticket.checked_in_ts = UNSET_TIMESTAMP;
+    ticket.gate_operator = Pubkey::default();
+    ticket.refund_ts = UNSET_TIMESTAMP;
+    ticket.snapshotted = false;
+    ticket.price_paid = order.amount_paid;
+    ticket.purchased_at = order.timestamp;
+    ticket.use_count = 0;
+    ticket.status = TicketStatus::Active;
+    ticket.promo_applied = false;
+    ticket.last_sale_price = 0;
+    ticket.last_sale_royalty = 0;
+    ticket.seat_number = 0;
+    ticket.gift_count = 0;
+    ticket.checkin_delegate = Pubkey::default();
+    ticket.buyer = order.buyer;
+    ticket.bump = ctx.bumps.ticket;
+    
+    // Append to the buyer's ticket index if the event maintains one
+    if event.maintain_ticket_index {
+        let ticket_index = ctx.accounts.ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        ticket_index.init_if_new(
+            event.key(),
+            order.buyer,
+            ctx.bumps.ticket_index.ok_or(EventError::TicketIndexRequired)?,
+        );
+        ticket_index.add(ticket.key());
+    }
+    
+    // Write the mint back onto the order
+    order.mint = mint.key();
+    
+    // Emit OrderFulfilled event
+    emit!(OrderFulfilled {
+        order_pubkey: order.key(),
+        ticket_pubkey: ticket.key(),
+        event_pubkey: event.key(),
+        mint_pubkey: mint.key(),
+        buyer: order.buyer,
+        order_id: order.order_id.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Order fulfilled: {}", order.order_id);
+    msg!("Ticket: {}", ticket.key());
+    msg!("Mint: {}", mint.key());
+    
+    Ok(())
+}
+
+#[event]
+pub struct OrderFulfilled {
+    pub order_pubkey: Pubkey,
+    pub ticket_pubkey: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub mint_pubkey: Pubkey,
+    pub buyer: Pubkey,
+    pub order_id: String,
+    pub timestamp: i64,
+}
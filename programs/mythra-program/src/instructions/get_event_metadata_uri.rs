@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::state::Event;
+
+#[derive(Accounts)]
+pub struct GetEventMetadataUri<'info> {
+    /// Event whose metadata URI is resolved
+    pub event: Account<'info, Event>,
+}
+
+/// Resolve the metadata URI for `locale`
+///
+/// Read-only view: the URI is returned through return data, falling back
+/// to the default metadata URI when the event has no such locale.
+pub fn handler(ctx: Context<GetEventMetadataUri>, locale: [u8; 2]) -> Result<String> {
+    Ok(ctx.accounts.event.metadata_uri_for(locale))
+}
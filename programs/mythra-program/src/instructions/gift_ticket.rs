@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, transfer_checked, TransferChecked, Mint};
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Ticket, TicketIndex, PlatformConfig, BlocklistEntry};
+use crate::instructions::transfer_ticket::TicketTransferred;
+
+#[derive(Accounts)]
+pub struct GiftTicket<'info> {
+    /// Ticket being given away
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.owner == sender.key() @ EventError::InvalidOwner
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event account (must match ticket.event)
+    #[account(
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Tier account (gifts ignore resale_enabled but not soulbound tiers)
+    #[account(
+        constraint = tier.key() == ticket.tier @ EventError::InvalidTier,
+        constraint = tier.transferable @ EventError::TicketNotTransferable
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// NFT mint account
+    #[account(
+        constraint = mint.key() == ticket.mint @ EventError::InvalidMintOwner
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Sender's token account (current owner)
+    #[account(
+        mut,
+        constraint = sender_token_account.mint == ticket.mint @ EventError::InvalidMintOwner,
+        constraint = sender_token_account.owner == sender.key() @ EventError::InvalidOwner,
+        constraint = sender_token_account.amount == 1 @ EventError::TicketNotOwned
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Recipient's token account (new owner)
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == ticket.mint @ EventError::InvalidMintOwner,
+        constraint = recipient_token_account.owner == recipient.key() @ EventError::InvalidOwner
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Sender (current ticket owner) - must sign
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// Recipient (new ticket owner)
+    /// CHECK: This is the new owner
+    pub recipient: AccountInfo<'info>,
+    
+    /// Blocklist entry PDA for the recipient (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
+    /// Sender's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        mut,
+        seeds = [b"ticket_index", event.key().as_ref(), sender.key().as_ref()],
+        bump = sender_ticket_index.bump
+    )]
+    pub sender_ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Recipient's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = TicketIndex::LEN,
+        seeds = [b"ticket_index", event.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Give a ticket away without royalties
+///
+/// Unlike `transfer_ticket` this works on tiers with resale disabled and
+/// never charges royalties. To keep it from doubling as an off-book resale
+/// channel each ticket can only be gifted `Ticket::MAX_GIFTS` times.
+pub fn handler(ctx: Context<GiftTicket>) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let tier = &ctx.accounts.tier;
+    let event = &ctx.accounts.event;
+    
+    // Validation: platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // Validation: only sold, unused and unrefunded tickets change hands
+    ticket.require_active()?;
+    require!(
+        !ticket.used,
+        EventError::TicketAlreadyUsed
+    );
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    // Validation: recipient must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &event.key(),
+        &ctx.accounts.recipient.key(),
+    )?;
+    
+    // Validation: gift allowance not used up
+    ticket.record_gift()?;
+    
+    // Transfer the NFT from sender to recipient
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.sender_token_account.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.sender.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        1,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    let old_owner = ticket.owner;
+    ticket.owner = ctx.accounts.recipient.key();
+    ticket.last_sale_price = 0;
+    ticket.last_sale_royalty = 0;
+    ticket.checkin_delegate = Pubkey::default();
+    
+    // Move the ticket between wallet indexes if the event maintains them
+    if event.maintain_ticket_index {
+        let sender_ticket_index = ctx.accounts.sender_ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        sender_ticket_index.remove(&ticket.key());
+        
+        let recipient_ticket_index = ctx.accounts.recipient_ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        recipient_ticket_index.init_if_new(
+            event.key(),
+            ctx.accounts.recipient.key(),
+            ctx.bumps.recipient_ticket_index.ok_or(EventError::TicketIndexRequired)?,
+        );
+        recipient_ticket_index.add(ticket.key());
+    }
+    
+    emit!(TicketTransferred {
+        ticket_pubkey: ticket.key(),
+        event_pubkey: event.key(),
+        tier_pubkey: tier.key(),
+        mint_pubkey: ticket.mint,
+        from_owner: old_owner,
+        to_owner: ticket.owner,
+        sale_price: None,
+        royalty_bps: tier.royalty_bps,
+        platform_royalty: 0,
+        organizer_royalty: 0,
+        is_gift: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Ticket gifted: {}", ticket.key());
+    msg!("From: {}", old_owner);
+    msg!("To: {}", ticket.owner);
+    
+    Ok(())
+}
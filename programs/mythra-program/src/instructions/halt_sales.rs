@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::Event;
+
+#[derive(Accounts)]
+pub struct HaltSales<'info> {
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Event authority
+    pub authority: Signer<'info>,
+}
+
+/// Stop all ticket issuance for an event
+///
+/// Used to cut capacity early (e.g. for safety) while supply remains.
+/// Unlike cancel_event this does not open refunds, and unlike a tier's sale
+/// window it covers every tier and issuance path.
+pub fn handler(ctx: Context<HaltSales>) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    
+    require!(
+        !event.sales_halted,
+        EventError::SalesHalted
+    );
+    
+    event.sales_halted = true;
+    
+    // Emit SalesHalted event
+    emit!(SalesHalted {
+        event_pubkey: event.key(),
+        authority: event.authority,
+        tickets_sold: event.tickets_sold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Sales halted for event: {}", event.key());
+    
+    Ok(())
+}
+
+#[event]
+pub struct SalesHalted {
+    pub event_pubkey: Pubkey,
+    pub authority: Pubkey,
+    pub tickets_sold: u32,
+    pub timestamp: i64,
+}
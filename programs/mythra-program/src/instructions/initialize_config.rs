@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::PlatformConfig;
+
+/// Initialize the platform config singleton
+///
+/// Creates the ["config"] PDA and makes the signer the platform admin.
+/// Only the program's upgrade authority can run it, so the first caller
+/// after a deploy can't claim the platform.
+/// The platform starts unpaused.
+/// Until this runs, instructions that read the config use program defaults.
+pub fn handler(
+    ctx: Context<InitializeConfig>,
+    min_platform_share_bps: u16,
+    min_backer_share_bps: u16,
+    platform_treasury: Pubkey,
+    default_platform_fee_bps: u16,
+) -> Result<()> {
+    let config = &mut ctx.accounts.platform_config;
+    
+    // Validate minimums leave room for a valid split
+    require!(
+        min_platform_share_bps as u32 + min_backer_share_bps as u32 <= 10_000,
+        EventError::InvalidProfitSplit
+    );
+    
+    // Validate default fee
+    require!(
+        default_platform_fee_bps <= 10_000,
+        EventError::InvalidPlatformSplit
+    );
+    
+    config.admin = ctx.accounts.admin.key();
+    config.min_platform_share_bps = min_platform_share_bps;
+    config.min_backer_share_bps = min_backer_share_bps;
+    config.distribution_timeout_seconds = PlatformConfig::DEFAULT_DISTRIBUTION_TIMEOUT_SECONDS;
+    config.platform_treasury = platform_treasury;
+    config.default_platform_fee_bps = default_platform_fee_bps;
+    config.paused = false;
+    config.max_relayer_fee_lamports = PlatformConfig::DEFAULT_MAX_RELAYER_FEE_LAMPORTS;
+    config.distribution_grace_period_seconds = PlatformConfig::DEFAULT_DISTRIBUTION_GRACE_PERIOD_SECONDS;
+    config.backer_claim_window_seconds = PlatformConfig::DEFAULT_BACKER_CLAIM_WINDOW_SECONDS;
+    config.unclaimed_profit_recipient = PlatformConfig::DEFAULT_UNCLAIMED_PROFIT_RECIPIENT;
+    config.refund_fee_bps = PlatformConfig::DEFAULT_REFUND_FEE_BPS;
+    config.min_budget_turnout_bps = PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS;
+    config.bump = ctx.bumps.platform_config;
+    
+    emit!(PlatformConfigInitialized {
+        config: config.key(),
+        admin: config.admin,
+        min_platform_share_bps,
+        min_backer_share_bps,
+        platform_treasury,
+        default_platform_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Platform config initialized, admin: {}", config.admin);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    /// Platform config PDA to create
+    #[account(
+        init,
+        payer = admin,
+        space = PlatformConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    /// Platform admin (pays for the account)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// This program, used to locate its ProgramData account
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ EventError::UnauthorizedConfigInit
+    )]
+    pub program: Program<'info, crate::program::MythraProgram>,
+    
+    /// Program data holding the upgrade authority, which must be the admin
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ EventError::UnauthorizedConfigInit
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct PlatformConfigInitialized {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub min_platform_share_bps: u16,
+    pub min_backer_share_bps: u16,
+    pub platform_treasury: Pubkey,
+    pub default_platform_fee_bps: u16,
+    pub timestamp: i64,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{PlatformConfig, PlatformStats};
+
+/// Initialize the platform stats singleton
+///
+/// Creates the ["platform_stats"] PDA with every counter at zero. Only the
+/// platform admin can run it, once. Activity before this runs is not
+/// counted, and instructions skip the stats while it's not passed in.
+pub fn handler(ctx: Context<InitializeStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.platform_stats;
+    let clock = Clock::get()?;
+    
+    stats.events_created = 0;
+    stats.tickets_sold = 0;
+    stats.ticket_volume_lamports = 0;
+    stats.campaigns_funded = 0;
+    stats.campaign_volume_lamports = 0;
+    stats.distributions_calculated = 0;
+    stats.distributed_revenue_lamports = 0;
+    stats.last_updated = clock.unix_timestamp;
+    stats.bump = ctx.bumps.platform_stats;
+    
+    emit!(PlatformStatsInitialized {
+        stats: stats.key(),
+        admin: ctx.accounts.admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Platform stats initialized: {}", stats.key());
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeStats<'info> {
+    /// Platform stats PDA to create
+    #[account(
+        init,
+        payer = admin,
+        space = PlatformStats::LEN,
+        seeds = [b"platform_stats"],
+        bump
+    )]
+    pub platform_stats: Account<'info, PlatformStats>,
+    
+    /// Platform config (only its admin can create the stats)
+    #[account(
+        seeds = [b"config"],
+        bump = platform_config.bump,
+        has_one = admin @ EventError::UnauthorizedPlatformAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    /// Platform admin (pays for the account)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct PlatformStatsInitialized {
+    pub stats: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, transfer_checked, TransferChecked, Mint};
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Ticket, PendingTransfer, PlatformConfig};
+
+#[derive(Accounts)]
+pub struct InitiatePendingTransfer<'info> {
+    /// Ticket being sent
+    #[account(
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.owner == sender.key() @ EventError::InvalidOwner
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event account (must match ticket.event)
+    #[account(
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Tier account (claim links follow the resale rules)
+    #[account(
+        constraint = tier.key() == ticket.tier @ EventError::InvalidTier,
+        constraint = tier.transferable @ EventError::TicketNotTransferable,
+        constraint = tier.resale_enabled @ EventError::ResaleDisabled
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// NFT mint account
+    #[account(
+        constraint = mint.key() == ticket.mint @ EventError::InvalidMintOwner
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Sender's token account (current owner)
+    #[account(
+        mut,
+        constraint = sender_token_account.mint == ticket.mint @ EventError::InvalidMintOwner,
+        constraint = sender_token_account.owner == sender.key() @ EventError::InvalidOwner,
+        constraint = sender_token_account.amount == 1 @ EventError::TicketNotOwned
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Pending transfer (one per ticket)
+    /// PDA: ["pending_transfer", ticket]
+    #[account(
+        init,
+        payer = sender,
+        space = PendingTransfer::SPACE,
+        seeds = [b"pending_transfer", ticket.key().as_ref()],
+        bump
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+    
+    /// Escrow token account holding the NFT until it is claimed or canceled
+    /// PDA: ["pending_transfer_escrow", ticket]
+    #[account(
+        init,
+        payer = sender,
+        seeds = [b"pending_transfer_escrow", ticket.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pending_transfer,
+        token::token_program = token_program
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Sender (current ticket owner) - pays rent for the escrow accounts
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Put a ticket up for claiming by whoever knows a secret
+///
+/// The NFT moves into an escrow token account and `claim_hash`
+/// (sha256 of the secret) is recorded, so the sender can share a claim link
+/// without knowing the recipient's wallet. The ticket stays in the sender's
+/// name until it is claimed; past `expires_at` only the sender can recover it.
+pub fn handler(
+    ctx: Context<InitiatePendingTransfer>,
+    claim_hash: [u8; 32],
+    expires_at: i64,
+) -> Result<()> {
+    let ticket = &ctx.accounts.ticket;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Validation: platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // Validation: only sold, unused and unrefunded tickets change hands
+    ticket.require_active()?;
+    require!(
+        !ticket.used,
+        EventError::TicketAlreadyUsed
+    );
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    // Validation: claim window is open and bounded
+    PendingTransfer::validate_expiry(now, expires_at)?;
+    
+    // Move the NFT into escrow
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.sender_token_account.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.sender.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        1,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    let pending_transfer = &mut ctx.accounts.pending_transfer;
+    pending_transfer.ticket = ticket.key();
+    pending_transfer.sender = ctx.accounts.sender.key();
+    pending_transfer.claim_hash = claim_hash;
+    pending_transfer.created_at = now;
+    pending_transfer.expires_at = expires_at;
+    pending_transfer.bump = ctx.bumps.pending_transfer;
+    
+    emit!(PendingTransferInitiated {
+        pending_transfer: pending_transfer.key(),
+        ticket: ticket.key(),
+        event: ctx.accounts.event.key(),
+        sender: pending_transfer.sender,
+        expires_at,
+        timestamp: now,
+    });
+    
+    msg!("Pending transfer created: {}", pending_transfer.key());
+    msg!("Ticket: {}", ticket.key());
+    msg!("Claimable until: {}", expires_at);
+    
+    Ok(())
+}
+
+#[event]
+pub struct PendingTransferInitiated {
+    pub pending_transfer: Pubkey,
+    pub ticket: Pubkey,
+    pub event: Pubkey,
+    pub sender: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
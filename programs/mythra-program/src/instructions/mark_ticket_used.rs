@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{TokenInterface, TokenAccount};
 use crate::errors::EventError;
-use crate::state::Ticket;
+use crate::state::{Event, Ticket, TicketTier, BlocklistEntry};
 
 #[derive(Accounts)]
 pub struct MarkTicketUsed<'info> {
@@ -9,19 +9,44 @@ pub struct MarkTicketUsed<'info> {
         mut,
         seeds = [b"ticket", ticket.mint.as_ref()],
         bump = ticket.bump,
-        constraint = ticket.owner == owner.key() @ EventError::UnauthorizedTicketUse
+        constraint = ticket.can_check_in(&owner.key()) @ EventError::UnauthorizedTicketUse
     )]
     pub ticket: Account<'info, Ticket>,
     
+    /// Tier the ticket was sold from (sets how many check-ins it allows)
+    #[account(
+        constraint = tier.key() == ticket.tier @ EventError::InvalidTier
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Event the ticket belongs to (selects full or compact check-in events)
+    #[account(
+        mut,
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Owner's token account holding the NFT (not needed for soulbound
+    /// tiers, whose NFT is frozen to `ticket.owner` and checked through it).
+    /// A check-in delegate presents the owner's account; the NFT stays put.
     #[account(
         constraint = owner_token_account.mint == ticket.mint @ EventError::TicketNotOwned,
-        constraint = owner_token_account.owner == owner.key() @ EventError::TicketNotOwned,
+        constraint = owner_token_account.owner == ticket.owner @ EventError::TicketNotOwned,
         constraint = owner_token_account.amount == 1 @ EventError::TicketNotOwned
     )]
-    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     
+    /// Ticket owner, or the check-in delegate they set
     pub owner: Signer<'info>,
     
+    /// Blocklist entry PDA for the ticket owner (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), ticket.owner.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
     /// CHECK: Gate operator/scanner who is marking the ticket as used
     pub gate_operator: AccountInfo<'info>,
     
@@ -39,28 +64,54 @@ pub fn handler(
         EventError::TicketAlreadyUsed
     );
     
+    // Validation: transferable tickets must be held by the owner
+    require!(
+        !ctx.accounts.tier.transferable || ctx.accounts.owner_token_account.is_some(),
+        EventError::TicketNotOwned
+    );
+    
+    // Validation: owner must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &ctx.accounts.event.key(),
+        &ticket.owner,
+    )?;
+    
     let clock = Clock::get()?;
     
     // Mark ticket as used
-    ticket.used = true;
-    ticket.checked_in_ts = clock.unix_timestamp;
-    ticket.gate_operator = ctx.accounts.gate_operator.key();
+    ticket.check_in(clock.unix_timestamp, ctx.accounts.gate_operator.key(), ctx.accounts.tier.max_uses)?;
     
-    // Emit TicketUsed event
-    emit!(TicketUsed {
-        ticket_pubkey: ticket.key(),
-        owner: ticket.owner,
-        mint: ticket.mint,
-        event: ticket.event,
-        tier: ticket.tier,
-        gate_operator: ticket.gate_operator,
-        checked_in_ts: ticket.checked_in_ts,
-    });
+    // Order the check-in after every earlier one for the event
+    let ordered_ts = ctx.accounts.event.record_check_in(clock.unix_timestamp, ticket.use_count == 1)?;
     
-    msg!("Ticket marked as used: {}", ticket.key());
-    msg!("Owner: {}", ticket.owner);
-    msg!("Checked in at: {}", ticket.checked_in_ts);
-    msg!("Gate operator: {}", ticket.gate_operator);
+    if ctx.accounts.event.compact_events {
+        emit!(TicketUsedCompact {
+            ticket: ticket.key(),
+            seq: ticket.use_count as u32,
+            ts: ticket.checked_in_ts,
+        });
+    } else {
+        // Emit TicketUsed event
+        emit!(TicketUsed {
+            ticket_pubkey: ticket.key(),
+            owner: ticket.owner,
+            mint: ticket.mint,
+            event: ticket.event,
+            tier: ticket.tier,
+            gate_operator: ticket.gate_operator,
+            checked_in_ts: ticket.checked_in_ts,
+            ordered_ts,
+            use_count: ticket.use_count,
+            tickets_checked_in: ctx.accounts.event.tickets_checked_in,
+            seat_number: ticket.seat_number,
+        });
+        
+        msg!("Ticket marked as used: {}", ticket.key());
+        msg!("Owner: {}", ticket.owner);
+        msg!("Checked in at: {}", ticket.checked_in_ts);
+        msg!("Gate operator: {}", ticket.gate_operator);
+    }
     
     Ok(())
 }
@@ -74,4 +125,24 @@ pub struct TicketUsed {
     pub tier: Pubkey,
     pub gate_operator: Pubkey,
     pub checked_in_ts: i64,
+    pub ordered_ts: i64,
+    pub use_count: u16,
+    pub tickets_checked_in: u32,
+    pub seat_number: u32,
+}
+
+/// Minimal check-in event for events with `compact_events` set
+///
+/// Emitted by every check-in path in place of its full event. Indexers
+/// reconstruct the owner, event, tier and gate operator from the ticket
+/// account. `seq` is the ticket's use count after this check-in.
+///
+/// The full events are 224-259 bytes of log data plus several base58
+/// `msg!` lines; this one is 52 bytes and compact check-ins skip the
+/// `msg!` lines, which is where most of the logging compute goes.
+#[event]
+pub struct TicketUsedCompact {
+    pub ticket: Pubkey,
+    pub seq: u32,
+    pub ts: i64,
 }
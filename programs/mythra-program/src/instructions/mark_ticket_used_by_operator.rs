@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use anchor_spl::token_interface::TokenAccount;
+use crate::ed25519::load_signed_message;
+use crate::errors::EventError;
+use crate::state::{Event, Ticket, TicketTier, Nonce, GateOperator, BlocklistEntry};
+use crate::instructions::TicketUsedCompact;
+
+#[derive(Accounts)]
+#[instruction(nonce_hash: [u8; 32])]
+pub struct MarkTicketUsedByOperator<'info> {
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Tier the ticket was sold from (sets how many check-ins it allows)
+    #[account(
+        constraint = tier.key() == ticket.tier @ EventError::InvalidTier
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Event the ticket belongs to (selects full or compact check-in events)
+    #[account(
+        mut,
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Owner's token account holding the NFT (not needed for soulbound
+    /// tiers, whose NFT is frozen to `ticket.owner`)
+    #[account(
+        constraint = owner_token_account.mint == ticket.mint @ EventError::TicketNotOwned,
+        constraint = owner_token_account.owner == ticket.owner @ EventError::TicketNotOwned,
+        constraint = owner_token_account.amount == 1 @ EventError::TicketNotOwned
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Nonce registered ahead of time via create_checkin_nonce
+    #[account(
+        mut,
+        seeds = [b"nonce", ticket.key().as_ref(), nonce_hash.as_ref()],
+        bump = nonce.bump,
+        constraint = nonce.ticket == ticket.key() @ EventError::InvalidNonce
+    )]
+    pub nonce: Account<'info, Nonce>,
+    
+    /// Registry entry proving the operator may scan for the ticket's event
+    /// PDA: ["gate_operator", ticket.event, gate_operator.key()]
+    #[account(
+        seeds = [b"gate_operator", ticket.event.as_ref(), gate_operator.key().as_ref()],
+        bump = gate_operator_registry.bump,
+    )]
+    pub gate_operator_registry: Account<'info, GateOperator>,
+    
+    /// CHECK: Scanner key that signed the check-in challenge (verified via ed25519)
+    pub gate_operator: UncheckedAccount<'info>,
+    
+    /// Blocklist entry PDA for the ticket owner (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), ticket.owner.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: Sysvar for instruction introspection
+    #[account(address = INSTRUCTIONS_ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+/// Check a ticket in with a signature from a registered gate operator
+///
+/// The scanner signs `GateOperator::checkin_message` (ticket, nonce_hash,
+/// nonce_value) in an Ed25519Program instruction placed immediately before
+/// this one; the ticket owner only presents the ticket.
+pub fn handler(
+    ctx: Context<MarkTicketUsedByOperator>,
+    nonce_hash: [u8; 32],
+    nonce_value: u64,
+) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let nonce = &mut ctx.accounts.nonce;
+    let clock = Clock::get()?;
+    
+    // Validation: ticket must not already be used
+    require!(
+        !ticket.used,
+        EventError::TicketAlreadyUsed
+    );
+    
+    // Validation: transferable tickets must be held by the owner
+    require!(
+        !ctx.accounts.tier.transferable || ctx.accounts.owner_token_account.is_some(),
+        EventError::TicketNotOwned
+    );
+    
+    // Validation: owner must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &ctx.accounts.event.key(),
+        &ticket.owner,
+    )?;
+    
+    // Verify the gate operator signed the challenge for this ticket
+    let message = load_signed_message(
+        &ctx.accounts.instructions,
+        &ctx.accounts.gate_operator.key(),
+    )?;
+    GateOperator::verify_checkin_message(&message, &ticket.key(), &nonce_hash, nonce_value)?;
+    
+    // Check the pre-registered nonce is unexpired and unused
+    nonce.require_usable(clock.unix_timestamp)?;
+    
+    // Mark ticket and nonce as used
+    ticket.check_in(clock.unix_timestamp, ctx.accounts.gate_operator.key(), ctx.accounts.tier.max_uses)?;
+    nonce.used = true;
+    
+    // Order the check-in after every earlier one for the event
+    let ordered_ts = ctx.accounts.event.record_check_in(clock.unix_timestamp, ticket.use_count == 1)?;
+    
+    if ctx.accounts.event.compact_events {
+        emit!(TicketUsedCompact {
+            ticket: ticket.key(),
+            seq: ticket.use_count as u32,
+            ts: ticket.checked_in_ts,
+        });
+    } else {
+        // Emit TicketUsedByOperator event
+        emit!(TicketUsedByOperator {
+            ticket_pubkey: ticket.key(),
+            owner: ticket.owner,
+            mint: ticket.mint,
+            event: ticket.event,
+            tier: ticket.tier,
+            gate_operator: ticket.gate_operator,
+            checked_in_ts: ticket.checked_in_ts,
+            ordered_ts,
+            nonce_hash,
+            use_count: ticket.use_count,
+            tickets_checked_in: ctx.accounts.event.tickets_checked_in,
+            seat_number: ticket.seat_number,
+        });
+    
+        msg!("Ticket marked as used by gate operator {}", ticket.gate_operator);
+        msg!("Ticket: {}", ticket.key());
+        msg!("Checked in at: {}", ticket.checked_in_ts);
+    }
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketUsedByOperator {
+    pub ticket_pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub event: Pubkey,
+    pub tier: Pubkey,
+    pub gate_operator: Pubkey,
+    pub checked_in_ts: i64,
+    pub ordered_ts: i64,
+    pub nonce_hash: [u8; 32],
+    pub use_count: u16,
+    pub tickets_checked_in: u32,
+    pub seat_number: u32,
+}
@@ -1,10 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::sysvar::instructions::{
-    load_instruction_at_checked, ID as INSTRUCTIONS_ID,
-};
-use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use crate::ed25519::{load_signed_message, verify_checkin_message, CheckinContext};
 use crate::errors::EventError;
-use crate::state::{Ticket, Nonce};
+use crate::state::{Event, Ticket, TicketTier, Nonce, BlocklistEntry};
+use crate::instructions::TicketUsedCompact;
 
 #[derive(Accounts)]
 #[instruction(nonce_hash: [u8; 32])]
@@ -16,26 +15,43 @@ pub struct MarkTicketUsedEd25519<'info> {
     )]
     pub ticket: Account<'info, Ticket>,
     
+    /// Tier the ticket was sold from (sets how many check-ins it allows)
     #[account(
-        init,
-        payer = payer,
-        space = Nonce::SPACE,
+        constraint = tier.key() == ticket.tier @ EventError::InvalidTier
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Event the ticket belongs to (selects the check-in message format and
+    /// full or compact check-in events)
+    #[account(
+        mut,
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Nonce registered ahead of time via create_checkin_nonce
+    #[account(
+        mut,
         seeds = [b"nonce", ticket.key().as_ref(), nonce_hash.as_ref()],
-        bump
+        bump = nonce.bump,
+        constraint = nonce.ticket == ticket.key() @ EventError::InvalidNonce
     )]
     pub nonce: Account<'info, Nonce>,
     
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    
     /// CHECK: Gate operator/scanner who is marking the ticket as used
     pub gate_operator: AccountInfo<'info>,
     
+    /// Blocklist entry PDA for the ticket owner (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), ticket.owner.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
     /// CHECK: Sysvar for instruction introspection
     #[account(address = INSTRUCTIONS_ID)]
     pub instructions: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
@@ -53,156 +69,73 @@ pub fn handler(
         EventError::TicketAlreadyUsed
     );
     
-    // Verify ed25519 signature from pre-instruction
-    verify_ed25519_signature(
-        &ctx.accounts.instructions,
+    // Validation: owner must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &ctx.accounts.event.key(),
         &ticket.owner,
-        &nonce_hash,
-        nonce_value,
     )?;
     
-    // Initialize nonce
-    nonce.ticket = ticket.key();
-    nonce.nonce_hash = nonce_hash;
-    nonce.used = false;
-    nonce.created_at = clock.unix_timestamp;
-    nonce.expires_at = clock.unix_timestamp + Nonce::DEFAULT_EXPIRY_SECONDS;
-    nonce.bump = ctx.bumps.nonce;
-    
-    // Check nonce is not expired
-    require!(
-        !nonce.is_expired(clock.unix_timestamp),
-        EventError::NonceExpired
-    );
+    // Verify ed25519 signature from pre-instruction, in the message
+    // format required by the event's check-in message version
+    let checkin_message_version = ctx.accounts.event.checkin_message_version;
+    let message = load_signed_message(&ctx.accounts.instructions, &ticket.owner)?;
+    verify_checkin_message(
+        checkin_message_version,
+        &message,
+        &CheckinContext {
+            ticket: &ticket.key(),
+            event: &ctx.accounts.event.key(),
+            nonce_hash: &nonce_hash,
+            nonce_value,
+            current_slot: clock.slot,
+        },
+    )?;
     
-    // Check nonce is not used
-    require!(
-        !nonce.used,
-        EventError::NonceUsed
-    );
+    // Check the pre-registered nonce is unexpired and unused
+    nonce.require_usable(clock.unix_timestamp)?;
     
     // Mark ticket and nonce as used
-    ticket.used = true;
-    ticket.checked_in_ts = clock.unix_timestamp;
-    ticket.gate_operator = ctx.accounts.gate_operator.key();
+    ticket.check_in(clock.unix_timestamp, ctx.accounts.gate_operator.key(), ctx.accounts.tier.max_uses)?;
     nonce.used = true;
     
-    // Emit TicketUsed event
-    emit!(TicketUsedWithNonce {
-        ticket_pubkey: ticket.key(),
-        owner: ticket.owner,
-        mint: ticket.mint,
-        event: ticket.event,
-        tier: ticket.tier,
-        gate_operator: ticket.gate_operator,
-        checked_in_ts: ticket.checked_in_ts,
-        nonce_hash,
-    });
-    
-    msg!("Ticket marked as used with ed25519 verification");
-    msg!("Ticket: {}", ticket.key());
-    msg!("Owner: {}", ticket.owner);
-    msg!("Nonce: {:?}", nonce_hash);
-    msg!("Checked in at: {}", ticket.checked_in_ts);
-    
-    Ok(())
-}
-
-/// Verify ed25519 signature from the Ed25519Program instruction
-fn verify_ed25519_signature(
-    instructions_sysvar: &AccountInfo,
-    expected_signer: &Pubkey,
-    nonce_hash: &[u8; 32],
-    nonce_value: u64,
-) -> Result<()> {
-    // Load the current instruction index
-    let current_index = load_current_index_checked(instructions_sysvar)?;
-    
-    // Ed25519 instruction should be immediately before this instruction
-    if current_index == 0 {
-        return Err(EventError::Ed25519InstructionMissing.into());
+    // Order the check-in after every earlier one for the event
+    let ordered_ts = ctx.accounts.event.record_check_in(clock.unix_timestamp, ticket.use_count == 1)?;
+    
+    if ctx.accounts.event.compact_events {
+        emit!(TicketUsedCompact {
+            ticket: ticket.key(),
+            seq: ticket.use_count as u32,
+            ts: ticket.checked_in_ts,
+        });
+    } else {
+        // Emit TicketUsed event
+        emit!(TicketUsedWithNonce {
+            ticket_pubkey: ticket.key(),
+            owner: ticket.owner,
+            mint: ticket.mint,
+            event: ticket.event,
+            tier: ticket.tier,
+            gate_operator: ticket.gate_operator,
+            checked_in_ts: ticket.checked_in_ts,
+            ordered_ts,
+            nonce_hash,
+            use_count: ticket.use_count,
+            tickets_checked_in: ctx.accounts.event.tickets_checked_in,
+            seat_number: ticket.seat_number,
+            checkin_message_version,
+        });
+    
+        msg!("Ticket marked as used with ed25519 verification");
+        msg!("Ticket: {}", ticket.key());
+        msg!("Owner: {}", ticket.owner);
+        msg!("Nonce: {:?}", nonce_hash);
+        msg!("Checked in at: {}", ticket.checked_in_ts);
     }
     
-    let ed25519_ix_index = (current_index - 1) as u8;
-    let ed25519_ix = load_instruction_at_checked(ed25519_ix_index as usize, instructions_sysvar)?;
-    
-    // Verify it's the Ed25519Program
-    require!(
-        ed25519_ix.program_id == ed25519_program::ID,
-        EventError::Ed25519InstructionMissing
-    );
-    
-    // Parse Ed25519 instruction data
-    // Format: [num_signatures: u8, padding: u8, signature_offset: u16, signature_instruction_index: u16,
-    //          public_key_offset: u16, public_key_instruction_index: u16, message_data_offset: u16,
-    //          message_data_size: u16, message_instruction_index: u16]
-    require!(
-        ed25519_ix.data.len() >= 112, // Minimum size for ed25519 instruction
-        EventError::InvalidSignature
-    );
-    
-    // Extract public key (32 bytes at offset 16)
-    let pubkey_bytes = &ed25519_ix.data[16..48];
-    let pubkey = Pubkey::try_from(pubkey_bytes)
-        .map_err(|_| EventError::InvalidSignature)?;
-    
-    // Verify public key matches expected signer
-    require!(
-        pubkey == *expected_signer,
-        EventError::InvalidSignature
-    );
-    
-    // Extract signature (64 bytes at offset 48)
-    let signature = &ed25519_ix.data[48..112];
-    
-    // Extract message (should contain nonce_hash and nonce_value)
-    let message_data_offset = u16::from_le_bytes([ed25519_ix.data[12], ed25519_ix.data[13]]) as usize;
-    let message_data_size = u16::from_le_bytes([ed25519_ix.data[14], ed25519_ix.data[15]]) as usize;
-    
-    require!(
-        message_data_offset + message_data_size <= ed25519_ix.data.len(),
-        EventError::InvalidSignature
-    );
-    
-    let message = &ed25519_ix.data[message_data_offset..message_data_offset + message_data_size];
-    
-    // Verify message contains nonce_hash and nonce_value
-    require!(
-        message.len() >= 40, // 32 bytes nonce_hash + 8 bytes nonce_value
-        EventError::InvalidSignature
-    );
-    
-    let msg_nonce_hash = &message[0..32];
-    let msg_nonce_value = u64::from_le_bytes(message[32..40].try_into().unwrap());
-    
-    require!(
-        msg_nonce_hash == nonce_hash,
-        EventError::InvalidSignature
-    );
-    
-    require!(
-        msg_nonce_value == nonce_value,
-        EventError::InvalidSignature
-    );
-    
-    msg!("Ed25519 signature verified successfully");
-    msg!("Signer: {}", pubkey);
-    msg!("Signature: {:?}", &signature[0..8]); // Log first 8 bytes
-    
     Ok(())
 }
 
-/// Load current instruction index from instructions sysvar
-fn load_current_index_checked(instructions_sysvar: &AccountInfo) -> Result<usize> {
-    let data = instructions_sysvar.try_borrow_data()?;
-    let index_bytes: [u8; 2] = data.get(0..2)
-        .ok_or(EventError::Ed25519InstructionMissing)?
-        .try_into()
-        .map_err(|_| EventError::Ed25519InstructionMissing)?;
-    let current_index = u16::from_le_bytes(index_bytes) as usize;
-    Ok(current_index)
-}
-
 #[event]
 pub struct TicketUsedWithNonce {
     pub ticket_pubkey: Pubkey,
@@ -212,5 +145,10 @@ pub struct TicketUsedWithNonce {
     pub tier: Pubkey,
     pub gate_operator: Pubkey,
     pub checked_in_ts: i64,
+    pub ordered_ts: i64,
     pub nonce_hash: [u8; 32],
+    pub use_count: u16,
+    pub tickets_checked_in: u32,
+    pub seat_number: u32,
+    pub checkin_message_version: u8,
 }
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, Ticket, TicketTier, GateOperator, BlocklistEntry};
+use crate::instructions::{TicketUsed, TicketUsedCompact};
+
+/// Maximum tickets per batch check-in (bounded by compute and account limits)
+pub const MAX_CHECKINS_PER_BATCH: u8 = 8;
+
+/// Accounts passed per ticket via remaining_accounts:
+/// [ticket (writable), tier, blocklist_entry]
+pub const CHECKIN_BATCH_ACCOUNT_STRIDE: usize = 3;
+
+#[derive(Accounts)]
+pub struct MarkTicketsUsedBatch<'info> {
+    /// Event every ticket in the batch belongs to
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+    
+    /// Registry entry proving the operator may scan for the event
+    /// PDA: ["gate_operator", event, gate_operator.key()]
+    #[account(
+        seeds = [b"gate_operator", event.key().as_ref(), gate_operator.key().as_ref()],
+        bump = gate_operator_registry.bump,
+    )]
+    pub gate_operator_registry: Account<'info, GateOperator>,
+    
+    /// Registered scanner submitting the batch
+    pub gate_operator: Signer<'info>,
+}
+
+/// Check in several tickets for one event in a single transaction
+///
+/// Each ticket's Ticket PDA, tier and owner blocklist entry are passed via
+/// remaining_accounts in a fixed stride of three. Every ticket is checked
+/// in with the same timestamp and operator. Tickets that are already used
+/// (or appear twice) are skipped and reported in the summary event's
+/// `skipped_bitmap`, bit `i` for the ticket at position `i`; any other
+/// invalid ticket aborts the whole batch.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MarkTicketsUsedBatch<'info>>,
+    count: u8,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    let gate_operator = ctx.accounts.gate_operator.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Validation: batch size and account layout
+    require!(
+        count > 0 && count <= MAX_CHECKINS_PER_BATCH,
+        EventError::InvalidCheckinBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == count as usize * CHECKIN_BATCH_ACCOUNT_STRIDE,
+        EventError::InvalidBatchAccounts
+    );
+    
+    let event_key = event.key();
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(count as usize);
+    let mut skipped_bitmap: u8 = 0;
+    let mut checked_in: u8 = 0;
+    
+    for (i, accounts) in ctx.remaining_accounts.chunks(CHECKIN_BATCH_ACCOUNT_STRIDE).enumerate() {
+        let ticket_info = &accounts[0];
+        let tier_info = &accounts[1];
+        let blocklist_info = &accounts[2];
+        
+        // Validation: ticket must be a writable Ticket for this event
+        require!(
+            ticket_info.is_writable,
+            EventError::InvalidBatchAccounts
+        );
+        let mut ticket = Account::<Ticket>::try_from(ticket_info)?;
+        require!(
+            ticket.event == event_key,
+            EventError::InvalidEvent
+        );
+        
+        // Already used tickets and repeats are reported, not fatal
+        if ticket.used || seen.contains(&ticket_info.key()) {
+            skipped_bitmap |= 1 << i;
+            continue;
+        }
+        seen.push(ticket_info.key());
+        
+        // Validation: tier the ticket was sold from
+        let tier = Account::<TicketTier>::try_from(tier_info)?;
+        require!(
+            tier.key() == ticket.tier,
+            EventError::InvalidTier
+        );
+        
+        // Validation: owner must not be blocked from the event
+        let (blocklist_pda, _) = Pubkey::find_program_address(
+            &[b"blocklist", event_key.as_ref(), ticket.owner.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            blocklist_info.key() == blocklist_pda,
+            EventError::InvalidBatchAccounts
+        );
+        BlocklistEntry::require_not_blocked(blocklist_info, &event_key, &ticket.owner)?;
+        
+        // Mark ticket as used
+        ticket.check_in(now, gate_operator, tier.max_uses)?;
+        
+        // Order the check-in after every earlier one for the event
+        let ordered_ts = event.record_check_in(now, ticket.use_count == 1)?;
+        
+        if event.compact_events {
+            emit!(TicketUsedCompact {
+                ticket: ticket.key(),
+                seq: ticket.use_count as u32,
+                ts: ticket.checked_in_ts,
+            });
+        } else {
+            emit!(TicketUsed {
+                ticket_pubkey: ticket.key(),
+                owner: ticket.owner,
+                mint: ticket.mint,
+                event: ticket.event,
+                tier: ticket.tier,
+                gate_operator: ticket.gate_operator,
+                checked_in_ts: ticket.checked_in_ts,
+                ordered_ts,
+                use_count: ticket.use_count,
+                tickets_checked_in: event.tickets_checked_in,
+                seat_number: ticket.seat_number,
+            });
+        }
+        
+        ticket.exit(&crate::ID)?;
+        checked_in += 1;
+    }
+    
+    emit!(TicketsCheckedInBatch {
+        event: event_key,
+        gate_operator,
+        count,
+        checked_in,
+        skipped_bitmap,
+        timestamp: now,
+    });
+    
+    msg!("Batch check-in by gate operator {}", gate_operator);
+    msg!("Checked in: {}/{}", checked_in, count);
+    if skipped_bitmap != 0 {
+        msg!("Skipped bitmap: {:#010b}", skipped_bitmap);
+    }
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketsCheckedInBatch {
+    pub event: Pubkey,
+    pub gate_operator: Pubkey,
+    pub count: u8,
+    pub checked_in: u8,
+    pub skipped_bitmap: u8,
+    pub timestamp: i64,
+}
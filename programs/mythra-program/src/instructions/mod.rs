@@ -1,47 +1,183 @@
 pub mod create_event;
 pub mod update_event;
+pub mod get_event_metadata_uri;
 pub mod create_ticket_tier;
+pub mod update_ticket_tier;
+pub mod retire_tier_supply;
+pub mod set_tier_promo;
 pub mod purchase_ticket;
+pub mod purchase_tickets;
+pub mod preregister_mints_batch;
+pub mod activate_ticket;
 pub mod register_mint;
+pub mod create_order;
+pub mod fulfill_order;
+pub mod cancel_order;
 pub mod mark_ticket_used;
 pub mod mark_ticket_used_ed25519;
+pub mod create_checkin_nonce;
+pub mod close_nonce;
+pub mod add_gate_operator;
+pub mod remove_gate_operator;
+pub mod mark_ticket_used_by_operator;
+pub mod undo_check_in;
+pub mod halt_sales;
+pub mod resume_sales;
 pub mod withdraw_funds;
 pub mod refund_ticket;
 pub mod transfer_ticket;
 pub mod close_event;
+pub mod cancel_event;
+pub mod register_refund_claim;
+pub mod settle_refund_claims;
+pub mod claim_ticket_refund;
+pub mod snapshot_attendance;
+pub mod initialize_config;
+pub mod update_config;
+pub mod set_paused;
 pub mod create_campaign;
 pub mod contribute;
+pub mod withdraw_contribution;
 pub mod finalize_campaign;
+pub mod extend_campaign_deadline;
 pub mod claim_refund;
+pub mod claim_refund_sponsored;
+pub mod close_campaign_escrow;
+pub mod close_campaign;
+pub mod close_contribution;
 pub mod submit_budget;
 pub mod vote_on_budget;
+pub mod change_budget_vote;
+pub mod withdraw_budget_vote;
+pub mod close_budget_vote;
+pub mod close_budget;
 pub mod finalize_budget_vote;
+pub mod emit_voting_reminder;
 pub mod revise_budget;
 pub mod release_milestone;
 pub mod calculate_distribution;
 pub mod claim_backer_profit;
 pub mod claim_organizer_profit;
+pub mod claim_loss_recovery;
+pub mod sweep_unclaimed_profits;
+pub mod flag_milestone;
+pub mod resolve_dispute;
+pub mod initialize_stats;
+pub mod distribute_backer_profits;
+pub mod claim_postponement_refund;
+pub mod block_wallet;
+pub mod unblock_wallet;
+pub mod propose_authority_transfer;
+pub mod accept_event_authority;
+pub mod add_event_delegate;
+pub mod remove_event_delegate;
+pub mod settle_platform_fees;
+pub mod close_ticket_tier;
+pub mod upgrade_ticket;
+pub mod assign_seat;
+pub mod gift_ticket;
+pub mod initiate_pending_transfer;
+pub mod claim_pending_transfer;
+pub mod cancel_pending_transfer;
+pub mod attach_metadata;
+pub mod mark_tickets_used_batch;
+pub mod set_checkin_delegate;
+pub mod request_refund;
+pub mod set_event_refund_fee;
+pub mod process_cancellation_refunds;
+pub mod close_ticket;
+pub mod open_milestone_vote;
+pub mod vote_on_milestone;
+pub mod finalize_milestone_vote;
 
 pub use create_event::*;
 pub use update_event::*;
+pub use get_event_metadata_uri::*;
 pub use create_ticket_tier::*;
+pub use update_ticket_tier::*;
+pub use retire_tier_supply::*;
+pub use set_tier_promo::*;
 pub use purchase_ticket::*;
+pub use purchase_tickets::*;
+pub use preregister_mints_batch::*;
+pub use activate_ticket::*;
 pub use register_mint::*;
+pub use create_order::*;
+pub use fulfill_order::*;
+pub use cancel_order::*;
 pub use mark_ticket_used::*;
 pub use mark_ticket_used_ed25519::*;
+pub use create_checkin_nonce::*;
+pub use close_nonce::*;
+pub use add_gate_operator::*;
+pub use remove_gate_operator::*;
+pub use mark_ticket_used_by_operator::*;
+pub use undo_check_in::*;
+pub use halt_sales::*;
+pub use resume_sales::*;
 pub use withdraw_funds::*;
 pub use refund_ticket::*;
 pub use transfer_ticket::*;
 pub use close_event::*;
+pub use cancel_event::*;
+pub use register_refund_claim::*;
+pub use settle_refund_claims::*;
+pub use claim_ticket_refund::*;
+pub use snapshot_attendance::*;
+pub use initialize_config::*;
+pub use update_config::*;
+pub use set_paused::*;
 pub use create_campaign::*;
 pub use contribute::*;
+pub use withdraw_contribution::*;
 pub use finalize_campaign::*;
+pub use extend_campaign_deadline::*;
 pub use claim_refund::*;
+pub use claim_refund_sponsored::*;
+pub use close_campaign_escrow::*;
+pub use close_campaign::*;
+pub use close_contribution::*;
 pub use submit_budget::*;
 pub use vote_on_budget::*;
+pub use change_budget_vote::*;
+pub use withdraw_budget_vote::*;
+pub use close_budget_vote::*;
+pub use close_budget::*;
 pub use finalize_budget_vote::*;
+pub use emit_voting_reminder::*;
 pub use revise_budget::*;
 pub use release_milestone::*;
 pub use calculate_distribution::*;
 pub use claim_backer_profit::*;
-pub use claim_organizer_profit::*;
\ No newline at end of file
+pub use claim_organizer_profit::*;
+pub use claim_loss_recovery::*;
+pub use sweep_unclaimed_profits::*;
+pub use flag_milestone::*;
+pub use resolve_dispute::*;
+pub use initialize_stats::*;
+pub use distribute_backer_profits::*;
+pub use claim_postponement_refund::*;
+pub use block_wallet::*;
+pub use unblock_wallet::*;
+pub use propose_authority_transfer::*;
+pub use accept_event_authority::*;
+pub use add_event_delegate::*;
+pub use remove_event_delegate::*;
+pub use settle_platform_fees::*;
+pub use close_ticket_tier::*;
+pub use upgrade_ticket::*;
+pub use assign_seat::*;
+pub use gift_ticket::*;
+pub use initiate_pending_transfer::*;
+pub use claim_pending_transfer::*;
+pub use cancel_pending_transfer::*;
+pub use attach_metadata::*;
+pub use mark_tickets_used_batch::*;
+pub use set_checkin_delegate::*;
+pub use request_refund::*;
+pub use set_event_refund_fee::*;
+pub use process_cancellation_refunds::*;
+pub use close_ticket::*;
+pub use open_milestone_vote::*;
+pub use vote_on_milestone::*;
+pub use finalize_milestone_vote::*;
\ No newline at end of file
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::state::{Campaign, Budget, BudgetStatus, MilestoneApproval};
+use crate::errors::EventError;
+
+/// Open the release vote for one milestone
+///
+/// Only for budgets submitted with `per_milestone_approval`. The organizer
+/// opens the vote once they're ready to ask for the milestone's funds;
+/// backers then have `voting_period_seconds` to vote with
+/// `vote_on_milestone`. Each milestone gets a single vote.
+pub fn handler(
+    ctx: Context<OpenMilestoneVote>,
+    milestone_index: u8,
+    voting_period_seconds: i64,
+) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let clock = Clock::get()?;
+    
+    // Validate the budget releases milestone by milestone
+    require!(
+        budget.per_milestone_approval,
+        EventError::MilestoneVoteNotRequired
+    );
+    
+    // Validate budget is approved
+    require!(
+        budget.status == BudgetStatus::Approved,
+        EventError::BudgetNotApproved
+    );
+    
+    // Validate milestone index
+    require!(
+        (milestone_index as usize) < budget.milestones.len(),
+        EventError::MilestoneNotReady
+    );
+    
+    // Validate voting period
+    require!(
+        voting_period_seconds > 0,
+        EventError::InvalidVotingPeriod
+    );
+    
+    let milestone = &mut budget.milestones[milestone_index as usize];
+    
+    // Validate not already released, disputed or voted on
+    require!(
+        !milestone.released,
+        EventError::MilestoneAlreadyReleased
+    );
+    milestone.require_not_disputed()?;
+    require!(
+        milestone.approval == MilestoneApproval::None,
+        EventError::MilestoneVoteAlreadyOpened
+    );
+    
+    milestone.approval = MilestoneApproval::Voting;
+    milestone.vote_end = clock.unix_timestamp + voting_period_seconds;
+    milestone.votes_for = 0;
+    milestone.votes_against = 0;
+    let vote_end = milestone.vote_end;
+    
+    msg!("Milestone {} release vote open until {}", milestone_index, vote_end);
+    
+    emit!(MilestoneVoteOpened {
+        budget: budget.key(),
+        campaign: ctx.accounts.campaign.key(),
+        milestone_index,
+        vote_end,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenMilestoneVote<'info> {
+    /// Campaign the budget belongs to
+    #[account(
+        has_one = organizer @ EventError::UnauthorizedCampaignAction
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Budget with the milestone
+    #[account(
+        mut,
+        has_one = campaign,
+        constraint = budget.key() == campaign.active_budget @ EventError::BudgetNotActive
+    )]
+    pub budget: Account<'info, Budget>,
+    
+    /// Campaign organizer
+    pub organizer: Signer<'info>,
+}
+
+#[event]
+pub struct MilestoneVoteOpened {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub milestone_index: u8,
+    pub vote_end: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::errors::EventError;
+use crate::instructions::purchase_tickets::create_ticket_account;
+use crate::state::{Event, TicketTier, Ticket, TicketStatus, UNSET_TIMESTAMP};
+
+/// Maximum mints pre-registered per transaction (bounded by compute)
+pub const MAX_PREREGISTER_BATCH: u8 = 8;
+
+/// Accounts passed per mint via remaining_accounts:
+/// [mint, ticket (writable, uninitialized)]
+pub const PREREGISTER_ACCOUNT_STRIDE: usize = 2;
+
+#[derive(Accounts)]
+pub struct PreregisterMintsBatch<'info> {
+    #[account(
+        has_one = authority @ EventError::UnauthorizedTierCreation
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Tier the pre-registered tickets will be sold from
+    #[account(
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Event authority (pays rent for every Ticket PDA)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Pre-register box-office stock: create Inactive tickets for printed mints
+///
+/// Each mint and its Ticket PDA are passed via remaining_accounts in a
+/// fixed stride of two. The tickets have no owner and don't count against
+/// tier or event supply until activate_ticket sells them; check-in, refund
+/// and transfer all reject them until then.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PreregisterMintsBatch<'info>>,
+    count: u8,
+) -> Result<()> {
+    let authority = &ctx.accounts.authority;
+    let clock = Clock::get()?;
+    
+    // VALIDATION: Batch size and account layout
+    require!(
+        count > 0 && count <= MAX_PREREGISTER_BATCH,
+        EventError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == count as usize * PREREGISTER_ACCOUNT_STRIDE,
+        EventError::InvalidBatchAccounts
+    );
+    
+    // VALIDATION: Stock can't be added to a canceled event
+    require!(
+        !ctx.accounts.event.canceled,
+        EventError::EventCanceledNoSales
+    );
+    
+    // VALIDATION: Soulbound tickets are only sold through purchase_ticket,
+    // which freezes the NFT
+    ctx.accounts.tier.require_transferable()?;
+    
+    let event_key = ctx.accounts.event.key();
+    let tier_key = ctx.accounts.tier.key();
+    let rent = Rent::get()?;
+    
+    for accounts in ctx.remaining_accounts.chunks(PREREGISTER_ACCOUNT_STRIDE) {
+        let mint_info = &accounts[0];
+        let ticket_info = &accounts[1];
+        
+        // Validate: NFT supply must be exactly 1
+        let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+        require!(
+            mint.supply == 1,
+            EventError::InvalidSupply
+        );
+        
+        // Validate: Ticket account is the PDA for this mint
+        let mint_key = mint.key();
+        let (ticket_pda, ticket_bump) = Pubkey::find_program_address(
+            &[b"ticket", mint_key.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            ticket_info.key() == ticket_pda,
+            EventError::InvalidBatchAccounts
+        );
+        
+        // Create the Ticket PDA (fails if this mint already has a ticket)
+        let ticket_seeds = &[
+            b"ticket",
+            mint_key.as_ref(),
+            &[ticket_bump],
+        ];
+        let signer_seeds = &[&ticket_seeds[..]];
+        
+        create_ticket_account(
+            &authority.to_account_info(),
+            ticket_info,
+            &ctx.accounts.system_program.to_account_info(),
+            signer_seeds,
+            &rent,
+        )?;
+        
+        let ticket = Ticket {
+            owner: Pubkey::default(),
+            event: event_key,
+            tier: tier_key,
+            mint: mint_key,
+            used: false,
+            refunded: false,
+            checked_in_ts: UNSET_TIMESTAMP,
+            gate_operator: Pubkey::default(),
+            refund_ts: UNSET_TIMESTAMP,
+            snapshotted: false,
+            price_paid: 0,
+            purchased_at: UNSET_TIMESTAMP,
+            use_count: 0,
+            status: TicketStatus::Inactive,
+            promo_applied: false,
+            last_sale_price: 0,
+            last_sale_royalty: 0,
+            seat_number: 0,
+            gift_count: 0,
+            checkin_delegate: Pubkey::default(),
+            buyer: Pubkey::default(),
+            bump: ticket_bump,
+        };
+        ticket.try_serialize(&mut &mut ticket_info.try_borrow_mut_data()?[..])?;
+        
+        emit!(TicketPreregistered {
+            ticket_pubkey: ticket_info.key(),
+            event_pubkey: event_key,
+            tier_pubkey: tier_key,
+            mint_pubkey: mint_key,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    msg!("Pre-registered {} tickets for tier: {}", count, tier_key);
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketPreregistered {
+    pub ticket_pubkey: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub mint_pubkey: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,268 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, burn, Burn, thaw_account, ThawAccount};
+use crate::errors::EventError;
+use crate::state::{Event, Ticket, TicketStatus};
+use crate::instructions::TicketRefunded;
+
+/// Maximum tickets per cancellation refund batch (bounded by the
+/// transaction's account limit)
+pub const MAX_CANCELLATION_REFUNDS_PER_BATCH: u8 = 6;
+
+/// Accounts passed per ticket via remaining_accounts:
+/// [ticket (writable), mint (writable), buyer_token_account (writable), buyer (writable)]
+pub const CANCELLATION_REFUND_ACCOUNT_STRIDE: usize = 4;
+
+#[derive(Accounts)]
+pub struct ProcessCancellationRefunds<'info> {
+    /// Canceled event every ticket in the batch belongs to
+    #[account(
+        mut,
+        constraint = event.canceled @ EventError::EventNotCanceled
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Ticket escrow paying the refunds
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Program PDA holding the mint's freeze authority (required to thaw
+    /// soulbound tickets' NFTs before burning them)
+    /// CHECK: Address checked by seeds; only signs the thaw CPI
+    #[account(
+        seeds = [b"ticket_freeze"],
+        bump
+    )]
+    pub freeze_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Anyone can crank the refunds; they only pay the transaction fee
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Refund a batch of tickets for a canceled event
+///
+/// Each ticket's Ticket PDA, mint, holder token account and holder are
+/// passed via remaining_accounts in a fixed stride of four. The NFT is
+/// burned by the Ticket PDA, which `purchase_ticket` approves as delegate
+/// over the buyer's token account, and the full `price_paid` goes back to
+/// the holder from the ticket escrow. Tickets that can't be refunded here
+/// are skipped and reported in the summary event's `skipped_bitmap`, bit
+/// `i` for the ticket at position `i`: already refunded or used tickets,
+/// NFTs no longer delegated to the program (resold or revoked), and
+/// tickets the escrow can no longer cover. Anything else invalid aborts
+/// the whole batch.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProcessCancellationRefunds<'info>>,
+    count: u8,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    let cranker = ctx.accounts.cranker.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Validation: underfunded cancellations are paid pro-rata through claims
+    require!(
+        !event.shortfall_mode,
+        EventError::ShortfallRefundsByClaim
+    );
+    
+    // Validation: batch size and account layout
+    require!(
+        count > 0 && count <= MAX_CANCELLATION_REFUNDS_PER_BATCH,
+        EventError::InvalidRefundBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == count as usize * CANCELLATION_REFUND_ACCOUNT_STRIDE,
+        EventError::InvalidBatchAccounts
+    );
+    
+    let event_key = event.key();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let escrow_seeds: &[&[&[u8]]] = &[&[
+        b"ticket_escrow",
+        event_key.as_ref(),
+        &[event.ticket_escrow_bump],
+    ]];
+    
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(count as usize);
+    let mut skipped_bitmap: u8 = 0;
+    let mut refunded: u8 = 0;
+    let mut amount_refunded: u64 = 0;
+    
+    for (i, accounts) in ctx.remaining_accounts.chunks(CANCELLATION_REFUND_ACCOUNT_STRIDE).enumerate() {
+        let ticket_info = &accounts[0];
+        let mint_info = &accounts[1];
+        let token_account_info = &accounts[2];
+        let buyer_info = &accounts[3];
+        
+        // Validation: ticket must be a writable Ticket for this event
+        require!(
+            ticket_info.is_writable && buyer_info.is_writable,
+            EventError::InvalidBatchAccounts
+        );
+        let mut ticket = Account::<Ticket>::try_from(ticket_info)?;
+        require!(
+            ticket.event == event_key,
+            EventError::InvalidEvent
+        );
+        
+        // Validation: mint, holder and token account belong to the ticket
+        require!(
+            mint_info.key() == ticket.mint && buyer_info.key() == ticket.owner,
+            EventError::InvalidBatchAccounts
+        );
+        require!(
+            *token_account_info.owner == ctx.accounts.token_program.key(),
+            EventError::InvalidBatchAccounts
+        );
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(token_account_info)?;
+        require!(
+            token_account.mint == ticket.mint && token_account.owner == ticket.owner,
+            EventError::InvalidBatchAccounts
+        );
+        
+        // Refunded, used and unsold tickets and repeats are reported, not fatal
+        if ticket.refunded
+            || ticket.use_count > 0
+            || ticket.status != TicketStatus::Active
+            || seen.contains(&ticket_info.key())
+        {
+            skipped_bitmap |= 1 << i;
+            continue;
+        }
+        seen.push(ticket_info.key());
+        
+        // NFTs that moved wallets (or whose approval was revoked) can't be
+        // burned here; their holders go through refund_ticket instead
+        if token_account.amount != 1
+            || token_account.delegate != Some(ticket.key()).into()
+            || token_account.delegated_amount < 1
+        {
+            skipped_bitmap |= 1 << i;
+            continue;
+        }
+        
+        // Escrow exhausted: leave the ticket for a later batch
+        let refund_amount = ticket.price_paid;
+        let available_balance = ctx.accounts.ticket_escrow.lamports()
+            .saturating_sub(rent_exempt_minimum);
+        if refund_amount > available_balance {
+            skipped_bitmap |= 1 << i;
+            continue;
+        }
+        
+        // Soulbound tickets are frozen to their owner; thaw before burning
+        if token_account.is_frozen() {
+            let freeze_authority = ctx.accounts.freeze_authority.as_ref()
+                .ok_or(EventError::SoulboundMintNotFreezable)?;
+            let bump = ctx.bumps.freeze_authority.ok_or(EventError::SoulboundMintNotFreezable)?;
+            let freeze_seeds: &[&[&[u8]]] = &[&[b"ticket_freeze", &[bump]]];
+            thaw_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                ThawAccount {
+                    account: token_account_info.clone(),
+                    mint: mint_info.clone(),
+                    authority: freeze_authority.to_account_info(),
+                },
+                freeze_seeds,
+            ))?;
+        }
+        
+        // Burn the NFT with the Ticket PDA's delegated authority
+        let mint_key = ticket.mint;
+        let ticket_seeds: &[&[&[u8]]] = &[&[b"ticket", mint_key.as_ref(), &[ticket.bump]]];
+        burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: mint_info.clone(),
+                    from: token_account_info.clone(),
+                    authority: ticket_info.clone(),
+                },
+                ticket_seeds,
+            ),
+            1,
+        )?;
+        
+        // Pay the full price back from the escrow
+        if refund_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.ticket_escrow.to_account_info(),
+                        to: buyer_info.clone(),
+                    },
+                    escrow_seeds,
+                ),
+                refund_amount,
+            )?;
+        }
+        
+        // Mark ticket as refunded
+        ticket.refunded = true;
+        ticket.refund_ts = now;
+        event.record_ticket_refunded();
+        
+        // Refunded lamports no longer count as ticket revenue
+        event.ticket_revenue = event.ticket_revenue.saturating_sub(refund_amount);
+        
+        emit!(TicketRefunded {
+            ticket_pubkey: ticket.key(),
+            event_pubkey: event_key,
+            tier_pubkey: ticket.tier,
+            mint_pubkey: ticket.mint,
+            owner: ticket.owner,
+            refund_amount,
+            refund_fee: 0,
+            price_paid: ticket.price_paid,
+            platform_fee_clawback: 0,
+            refunded_by: cranker,
+            tickets_sold: event.tickets_sold,
+            tickets_refunded: event.tickets_refunded,
+            timestamp: now,
+        });
+        
+        ticket.exit(&crate::ID)?;
+        refunded += 1;
+        amount_refunded = amount_refunded
+            .checked_add(refund_amount)
+            .ok_or(EventError::ArithmeticOverflow)?;
+    }
+    
+    emit!(CancellationRefundsProcessed {
+        event: event_key,
+        cranker,
+        count,
+        refunded,
+        skipped_bitmap,
+        amount_refunded,
+        timestamp: now,
+    });
+    
+    msg!("Cancellation refunds for event {}", event_key);
+    msg!("Refunded: {}/{} ({} lamports)", refunded, count, amount_refunded);
+    if skipped_bitmap != 0 {
+        msg!("Skipped bitmap: {:#010b}", skipped_bitmap);
+    }
+    
+    Ok(())
+}
+
+#[event]
+pub struct CancellationRefundsProcessed {
+    pub event: Pubkey,
+    pub cranker: Pubkey,
+    pub count: u8,
+    pub refunded: u8,
+    pub skipped_bitmap: u8,
+    pub amount_refunded: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::Event;
+
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    /// Event being handed off
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Current event authority
+    pub authority: Signer<'info>,
+}
+
+/// Propose a new event authority
+///
+/// Step one of a two-step handoff: the proposed wallet becomes the
+/// authority only once it signs `accept_event_authority`. Proposing again
+/// replaces the pending proposal.
+pub fn handler(
+    ctx: Context<ProposeAuthorityTransfer>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    
+    event.propose_authority(new_authority);
+    
+    // Emit EventAuthorityProposed event
+    emit!(EventAuthorityProposed {
+        event_pubkey: event.key(),
+        authority: event.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Authority transfer proposed for event {}", event.key());
+    msg!("Pending authority: {}", new_authority);
+    
+    Ok(())
+}
+
+#[event]
+pub struct EventAuthorityProposed {
+    pub event_pubkey: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
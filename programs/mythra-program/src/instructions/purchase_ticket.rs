@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
-use anchor_spl::token::{Token, Mint, TokenAccount};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount, approve, Approve, freeze_account, FreezeAccount};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::errors::EventError;
-use crate::state::{Event, TicketTier, Ticket};
+use crate::metadata::{self, TokenMetadata, CreateTicketMetadata};
+use crate::state::{Event, TicketTier, Ticket, TicketStatus, BuyerRecord, TicketIndex, PlatformConfig, PlatformStats, BlocklistEntry, Campaign, Contribution, SeatAssignment, UNSET_TIMESTAMP};
 
 #[derive(Accounts)]
 pub struct PurchaseTicket<'info> {
@@ -28,45 +29,186 @@ pub struct PurchaseTicket<'info> {
     )]
     pub tier: Account<'info, TicketTier>,
     
-    /// NFT mint (must be created externally for now)
-    /// CHECK: Mint account will be validated in handler
-    #[account(mut)]
-    pub mint: AccountInfo<'info>,
+    /// Per-wallet purchase counter for this tier
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = BuyerRecord::LEN,
+        seeds = [b"buyer", tier.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    /// Buyer's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = TicketIndex::LEN,
+        seeds = [b"ticket_index", event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Seat handed out by this purchase (required for seated tiers)
+    /// PDA: ["seat", tier, next seat number]
+    #[account(
+        init,
+        payer = buyer,
+        space = SeatAssignment::SPACE,
+        seeds = [b"seat", tier.key().as_ref(), &tier.next_seat.saturating_add(1).to_le_bytes()],
+        bump
+    )]
+    pub seat_assignment: Option<Box<Account<'info, SeatAssignment>>>,
+    
+    /// Event's campaign (required to buy during the backer presale)
+    pub backer_campaign: Option<Box<Account<'info, Campaign>>>,
+    
+    /// Buyer's contribution to the event's campaign (required to buy during
+    /// the backer presale)
+    pub backer_contribution: Option<Box<Account<'info, Contribution>>>,
+    
+    /// NFT mint (must be created externally for now; SPL Token or Token-2022)
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
     
     /// Buyer's token account for the NFT
-    #[account(mut)]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::token_program = token_program
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Program PDA set as the mint's freeze authority (required for
+    /// soulbound tiers, whose NFTs are frozen in the buyer's token account)
+    /// CHECK: Address checked by seeds; only signs the freeze CPI
+    #[account(
+        seeds = [b"ticket_freeze"],
+        bump
+    )]
+    pub freeze_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Token Metadata account for the mint (required with_metadata)
+    /// CHECK: Address checked against the metadata PDA; created by the CPI
+    #[account(
+        mut,
+        address = metadata::metadata_address(&mint.key()) @ EventError::InvalidMetadataAccount
+    )]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    
+    /// Per-event PDA that owns ticket metadata (required with_metadata)
+    /// CHECK: Address checked by seeds; only signs the metadata CPI
+    #[account(
+        seeds = [metadata::METADATA_AUTHORITY_SEED, event.key().as_ref()],
+        bump
+    )]
+    pub metadata_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Token Metadata program (required with_metadata)
+    pub token_metadata_program: Option<Program<'info, TokenMetadata>>,
     
     /// Escrow account to receive ticket payment
     #[account(
         mut,
         seeds = [b"ticket_escrow", event.key().as_ref()],
-        bump
+        bump = event.ticket_escrow_bump
     )]
     pub ticket_escrow: SystemAccount<'info>,
     
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Platform stats PDA (optional; counters are skipped when omitted)
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Box<Account<'info, PlatformStats>>>,
+    
+    /// Blocklist entry PDA for the buyer (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
     /// Buyer (pays for ticket and receives NFT)
     #[account(mut)]
     pub buyer: Signer<'info>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
-pub fn handler(ctx: Context<PurchaseTicket>) -> Result<()> {
+pub fn handler(
+    ctx: Context<PurchaseTicket>,
+    allowlist_proof: Vec<[u8; 32]>,
+    with_metadata: bool,
+) -> Result<()> {
     let ticket = &mut ctx.accounts.ticket;
     let tier = &mut ctx.accounts.tier;
     let event = &mut ctx.accounts.event;
+    let buyer_record = &mut ctx.accounts.buyer_record;
+    
+    // VALIDATION: Platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // VALIDATION: Buyer must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &event.key(),
+        &ctx.accounts.buyer.key(),
+    )?;
+    
+    // VALIDATION: Event must not be canceled, started or ended
+    event.require_sales_open(Clock::get()?.unix_timestamp)?;
+    
+    // VALIDATION: Tier sale window must be open, except for allowlisted
+    // wallets or backers during their presales before it
+    let now = Clock::get()?.unix_timestamp;
+    if tier.in_allowlist_presale(now) {
+        tier.require_not_closed()?;
+        tier.require_allowlisted(&ctx.accounts.buyer.key(), &allowlist_proof)?;
+    } else if event.in_backer_presale(tier.sale_start_ts, now) {
+        tier.require_not_closed()?;
+        event.require_backer_presale_access(
+            ctx.accounts.backer_campaign.as_deref().map(|campaign| (campaign.key(), &**campaign)),
+            ctx.accounts.backer_contribution.as_deref().map(|contribution| &**contribution),
+            &ctx.accounts.buyer.key(),
+        )?;
+    } else {
+        tier.require_sale_open(now)?;
+    }
     
     // VALIDATION: Check tier has available supply
     require!(tier.is_available(), EventError::ExceedsTotalSupply);
     
+    // VALIDATION: Check event-wide capacity across all tiers
+    require!(event.has_capacity(), EventError::EventSoldOut);
+    
+    // VALIDATION: Check per-wallet limit
+    require!(
+        buyer_record.can_purchase(1, tier.max_per_wallet),
+        EventError::PurchaseLimitExceeded
+    );
+    
     // Note: For MVP, we skip crowdfunding validation
     // This can be added back when integrating with the campaign system
     
-    // STEP 1: Transfer payment from buyer to escrow
-    let payment_amount = tier.price_lamports;
+    // STEP 1: Transfer payment from buyer to escrow (promo price while a
+    // promo is live on the tier)
+    let (payment_amount, promo_applied) = tier.price_at(now);
     
     if payment_amount > 0 {
         let transfer_ctx = CpiContext::new(
@@ -86,15 +228,13 @@ pub fn handler(ctx: Context<PurchaseTicket>) -> Result<()> {
     // Note: For MVP, we simplified to require external mint creation
     // This matches the register_mint flow but with payment integrated
     
-    let mint_data = ctx.accounts.mint.try_borrow_data()?;
-    let mint_account = Mint::try_deserialize(&mut &mint_data[..])?;
+    let mint_account = &ctx.accounts.mint;
     
     // Validate: NFT supply must be exactly 1
     require!(
         mint_account.supply == 1,
         EventError::InvalidSupply
     );
-    drop(mint_data); // Release borrow
     
     // Validate: Buyer owns the NFT
     require!(
@@ -104,11 +244,72 @@ pub fn handler(ctx: Context<PurchaseTicket>) -> Result<()> {
     
     msg!("NFT validated for buyer: {}", ctx.accounts.buyer.key());
     
+    // Approve the Ticket PDA as delegate for the NFT, so cancellation
+    // refunds can burn it without the buyer signing again (done before a
+    // soulbound freeze, which would block the approval)
+    approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.buyer_token_account.to_account_info(),
+                delegate: ticket.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    
+    // Soulbound tiers: freeze the NFT in the buyer's token account so the
+    // raw SPL transfer path is closed too; only the program can thaw it
+    if !tier.transferable {
+        let freeze_authority = ctx.accounts.freeze_authority.as_ref()
+            .ok_or(EventError::SoulboundMintNotFreezable)?;
+        require!(
+            mint_account.freeze_authority == Some(freeze_authority.key()).into(),
+            EventError::SoulboundMintNotFreezable
+        );
+        require!(
+            ctx.accounts.buyer_token_account.mint == ctx.accounts.mint.key()
+                && ctx.accounts.buyer_token_account.owner == ctx.accounts.buyer.key(),
+            EventError::TicketNotOwned
+        );
+        
+        let bump = ctx.bumps.freeze_authority.ok_or(EventError::SoulboundMintNotFreezable)?;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"ticket_freeze", &[bump]]];
+        freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.buyer_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: freeze_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        
+        msg!("Soulbound ticket frozen to buyer");
+    }
+    
     // STEP 3: Increment tier supply
     tier.current_supply = tier.current_supply
         .checked_add(1)
         .ok_or(EventError::ExceedsTotalSupply)?;
     
+    // Count this purchase against the buyer's wallet limit
+    buyer_record.tier = tier.key();
+    buyer_record.buyer = ctx.accounts.buyer.key();
+    buyer_record.purchased_count = buyer_record.purchased_count
+        .checked_add(1)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    buyer_record.bump = ctx.bumps.buyer_record;
+    
+    event.tickets_sold = event.tickets_sold
+        .checked_add(1)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+        stats.record_ticket_sales(1, payment_amount, now);
+    }
+    
     // STEP 4: Create ticket record
     let event_key = event.key();
     ticket.owner = ctx.accounts.buyer.key();
@@ -117,14 +318,83 @@ pub fn handler(ctx: Context<PurchaseTicket>) -> Result<()> {
     ticket.mint = ctx.accounts.mint.key();
     ticket.used = false;
     ticket.refunded = false;
-    ticket.checked_in_ts = 0;
+    ticket.checked_in_ts = UNSET_TIMESTAMP;
     ticket.gate_operator = Pubkey::default();
-    ticket.refund_ts = 0;
+    ticket.refund_ts = UNSET_TIMESTAMP;
+    ticket.snapshotted = false;
+    ticket.price_paid = payment_amount;
+    ticket.purchased_at = Clock::get()?.unix_timestamp;
+    ticket.use_count = 0;
+    ticket.status = TicketStatus::Active;
+    ticket.promo_applied = promo_applied;
+    ticket.last_sale_price = 0;
+    ticket.last_sale_royalty = 0;
+    ticket.seat_number = 0;
+    ticket.gift_count = 0;
+    ticket.checkin_delegate = Pubkey::default();
+    ticket.buyer = ctx.accounts.buyer.key();
     ticket.bump = ctx.bumps.ticket;
     
+    // Seated tiers hand out the next seat in order
+    if tier.seated {
+        let seat_assignment = ctx.accounts.seat_assignment.as_mut()
+            .ok_or(EventError::SeatAssignmentRequired)?;
+        ticket.seat_number = tier.take_next_seat()?;
+        seat_assignment.tier = tier.key();
+        seat_assignment.seat_number = ticket.seat_number;
+        seat_assignment.ticket = ticket.key();
+        seat_assignment.bump = ctx.bumps.seat_assignment.ok_or(EventError::SeatAssignmentRequired)?;
+    } else {
+        require!(
+            ctx.accounts.seat_assignment.is_none(),
+            EventError::InvalidSeatNumber
+        );
+    }
+    
+    // Append to the buyer's ticket index if the event maintains one
+    if event.maintain_ticket_index {
+        let ticket_index = ctx.accounts.ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        ticket_index.init_if_new(
+            event_key,
+            ctx.accounts.buyer.key(),
+            ctx.bumps.ticket_index.ok_or(EventError::TicketIndexRequired)?,
+        );
+        ticket_index.add(ticket.key());
+    }
+    
+    // Give the NFT a name, symbol and URI so wallets can show it (the
+    // buyer brings the mint, so they sign as its mint authority)
+    if with_metadata {
+        let (Some(metadata_account), Some(metadata_authority), Some(token_metadata_program)) = (
+            ctx.accounts.metadata.as_ref(),
+            ctx.accounts.metadata_authority.as_ref(),
+            ctx.accounts.token_metadata_program.as_ref(),
+        ) else {
+            return err!(EventError::MetadataAccountsRequired);
+        };
+        
+        metadata::create_ticket_metadata(
+            CreateTicketMetadata {
+                metadata: metadata_account,
+                mint: &ctx.accounts.mint.to_account_info(),
+                mint_authority: &ctx.accounts.buyer,
+                payer: &ctx.accounts.buyer,
+                metadata_authority,
+                system_program: &ctx.accounts.system_program,
+                token_metadata_program,
+            },
+            metadata::ticket_data(&event_key, event, tier, ticket.seat_number, &metadata_authority.key()),
+            &event_key,
+            ctx.bumps.metadata_authority.ok_or(EventError::MetadataAccountsRequired)?,
+        )?;
+        
+        msg!("Ticket metadata created: {}", metadata_account.key());
+    }
+    
     // STEP 5: Track revenue
     event.ticket_revenue = event.ticket_revenue
-        .checked_add(tier.price_lamports)
+        .checked_add(payment_amount)
         .ok_or(EventError::ArithmeticOverflow)?;
     
     msg!("Ticket revenue updated: {} lamports", event.ticket_revenue);
@@ -137,6 +407,9 @@ pub fn handler(ctx: Context<PurchaseTicket>) -> Result<()> {
         mint_pubkey: ctx.accounts.mint.key(),
         buyer: ticket.owner,
         price_paid: payment_amount,
+        promo_applied,
+        tickets_sold: event.tickets_sold,
+        seat_number: ticket.seat_number,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
@@ -144,6 +417,9 @@ pub fn handler(ctx: Context<PurchaseTicket>) -> Result<()> {
     msg!("Ticket: {}", ticket.key());
     msg!("NFT Mint: {}", ctx.accounts.mint.key());
     msg!("Buyer: {}", ticket.owner);
+    if ticket.seat_number > 0 {
+        msg!("Seat: {}", ticket.seat_number);
+    }
     msg!("Tier supply: {}/{}", tier.current_supply, tier.max_supply);
     
     Ok(())
@@ -157,5 +433,8 @@ pub struct TicketPurchased {
     pub mint_pubkey: Pubkey,
     pub buyer: Pubkey,
     pub price_paid: u64,
+    pub promo_applied: bool,
+    pub tickets_sold: u32,
+    pub seat_number: u32,
     pub timestamp: i64,
 }
@@ -0,0 +1,370 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::errors::EventError;
+use crate::instructions::purchase_ticket::TicketPurchased;
+use crate::state::{Event, TicketTier, Ticket, TicketStatus, BuyerRecord, TicketIndex, PlatformConfig, PlatformStats, BlocklistEntry, UNSET_TIMESTAMP};
+
+/// Maximum tickets per batch purchase (bounded by compute)
+pub const MAX_TICKETS_PER_BATCH: u8 = 5;
+
+/// Accounts passed per ticket via remaining_accounts:
+/// [mint, buyer_token_account, ticket (writable, uninitialized)]
+pub const BATCH_ACCOUNT_STRIDE: usize = 3;
+
+#[derive(Accounts)]
+pub struct PurchaseTickets<'info> {
+    /// Event account
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+    
+    /// Ticket tier account
+    #[account(
+        mut,
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Per-wallet purchase counter for this tier
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = BuyerRecord::LEN,
+        seeds = [b"buyer", tier.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    /// Buyer's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = TicketIndex::LEN,
+        seeds = [b"ticket_index", event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Escrow account to receive ticket payment
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Platform stats PDA (optional; counters are skipped when omitted)
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Box<Account<'info, PlatformStats>>>,
+    
+    /// Blocklist entry PDA for the buyer (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
+    /// Buyer (pays for all tickets and holds every NFT)
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Purchase several tickets from one tier in a single transaction
+///
+/// Each ticket's mint, buyer token account and Ticket PDA are passed via
+/// remaining_accounts in a fixed stride of three. Payment is taken in one
+/// transfer and supply is reserved with a single check; any invalid ticket
+/// aborts the whole batch.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PurchaseTickets<'info>>,
+    count: u8,
+) -> Result<()> {
+    let tier = &mut ctx.accounts.tier;
+    let event = &mut ctx.accounts.event;
+    let buyer_record = &mut ctx.accounts.buyer_record;
+    let buyer = &ctx.accounts.buyer;
+    let clock = Clock::get()?;
+    
+    // VALIDATION: Batch size and account layout
+    require!(
+        count > 0 && count <= MAX_TICKETS_PER_BATCH,
+        EventError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == count as usize * BATCH_ACCOUNT_STRIDE,
+        EventError::InvalidBatchAccounts
+    );
+    
+    // VALIDATION: Platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // VALIDATION: Buyer must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &event.key(),
+        &buyer.key(),
+    )?;
+    
+    // VALIDATION: Event must not be canceled, started or ended
+    event.require_sales_open(clock.unix_timestamp)?;
+    
+    // VALIDATION: Tier sale window must be open
+    tier.require_sale_open(clock.unix_timestamp)?;
+    
+    // VALIDATION: Soulbound tickets are only sold through purchase_ticket,
+    // which freezes the NFT
+    tier.require_transferable()?;
+    
+    // VALIDATION: Check event-wide capacity across all tiers
+    require!(event.has_capacity_for(count as u32), EventError::EventSoldOut);
+    
+    // VALIDATION: Check per-wallet limit
+    require!(
+        buyer_record.can_purchase(count as u16, tier.max_per_wallet),
+        EventError::PurchaseLimitExceeded
+    );
+    
+    // STEP 1: Reserve every seat with a single supply check
+    tier.reserve_seats(count as u32)?;
+    
+    // STEP 2: Transfer payment for all tickets from buyer to escrow
+    let (unit_price, promo_applied) = tier.price_at(clock.unix_timestamp);
+    let payment_amount = unit_price
+        .checked_mul(count as u64)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    if payment_amount > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: buyer.to_account_info(),
+                to: ctx.accounts.ticket_escrow.to_account_info(),
+            },
+        );
+        
+        transfer(transfer_ctx, payment_amount)?;
+        
+        msg!("Payment transferred: {} lamports for {} tickets", payment_amount, count);
+    }
+    
+    // STEP 3: Validate each mint/ATA pair and create its Ticket PDA
+    let event_key = event.key();
+    let tier_key = tier.key();
+    let rent = Rent::get()?;
+    
+    for (i, accounts) in ctx.remaining_accounts.chunks(BATCH_ACCOUNT_STRIDE).enumerate() {
+        let mint_info = &accounts[0];
+        let token_account_info = &accounts[1];
+        let ticket_info = &accounts[2];
+        
+        // Validate: NFT supply must be exactly 1
+        let mint = Account::<Mint>::try_from(mint_info)?;
+        require!(
+            mint.supply == 1,
+            EventError::InvalidSupply
+        );
+        
+        // Validate: Buyer owns the NFT
+        let token_account = Account::<TokenAccount>::try_from(token_account_info)?;
+        require!(
+            token_account.mint == mint.key() && token_account.owner == buyer.key(),
+            EventError::InvalidMintOwner
+        );
+        require!(
+            token_account.amount == 1,
+            EventError::TicketNotOwned
+        );
+        
+        // Validate: Ticket account is the PDA for this mint
+        let mint_key = mint.key();
+        let (ticket_pda, ticket_bump) = Pubkey::find_program_address(
+            &[b"ticket", mint_key.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            ticket_info.key() == ticket_pda,
+            EventError::InvalidBatchAccounts
+        );
+        
+        // Create the Ticket PDA (fails if this mint already has a ticket)
+        let ticket_seeds = &[
+            b"ticket",
+            mint_key.as_ref(),
+            &[ticket_bump],
+        ];
+        let signer_seeds = &[&ticket_seeds[..]];
+        
+        create_ticket_account(
+            &buyer.to_account_info(),
+            ticket_info,
+            &ctx.accounts.system_program.to_account_info(),
+            signer_seeds,
+            &rent,
+        )?;
+        
+        let ticket = Ticket {
+            owner: buyer.key(),
+            event: event_key,
+            tier: tier_key,
+            mint: mint_key,
+            used: false,
+            refunded: false,
+            checked_in_ts: UNSET_TIMESTAMP,
+            gate_operator: Pubkey::default(),
+            refund_ts: UNSET_TIMESTAMP,
+            snapshotted: false,
+            price_paid: unit_price,
+            purchased_at: clock.unix_timestamp,
+            use_count: 0,
+            status: TicketStatus::Active,
+            promo_applied,
+            last_sale_price: 0,
+            last_sale_royalty: 0,
+            seat_number: 0,
+            gift_count: 0,
+            checkin_delegate: Pubkey::default(),
+            buyer: buyer.key(),
+            bump: ticket_bump,
+        };
+        ticket.try_serialize(&mut &mut ticket_info.try_borrow_mut_data()?[..])?;
+        
+        // Append to the buyer's ticket index if the event maintains one
+        if event.maintain_ticket_index {
+            let ticket_index = ctx.accounts.ticket_index.as_mut()
+                .ok_or(EventError::TicketIndexRequired)?;
+            ticket_index.init_if_new(
+                event_key,
+                buyer.key(),
+                ctx.bumps.ticket_index.ok_or(EventError::TicketIndexRequired)?,
+            );
+            ticket_index.add(ticket_info.key());
+        }
+        
+        // Emit TicketPurchased event per ticket
+        emit!(TicketPurchased {
+            ticket_pubkey: ticket_info.key(),
+            event_pubkey: event_key,
+            tier_pubkey: tier_key,
+            mint_pubkey: mint_key,
+            buyer: buyer.key(),
+            price_paid: unit_price,
+            promo_applied,
+            // Counted below for the whole batch; capacity was checked up front
+            tickets_sold: event.tickets_sold + i as u32 + 1,
+            // Batch tickets are seated afterwards through assign_seat
+            seat_number: 0,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    // STEP 4: Count purchases and track revenue
+    buyer_record.tier = tier_key;
+    buyer_record.buyer = buyer.key();
+    buyer_record.purchased_count = buyer_record.purchased_count
+        .checked_add(count as u16)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    buyer_record.bump = ctx.bumps.buyer_record;
+    
+    event.tickets_sold = event.tickets_sold
+        .checked_add(count as u32)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+        stats.record_ticket_sales(count as u64, payment_amount, clock.unix_timestamp);
+    }
+    
+    event.ticket_revenue = event.ticket_revenue
+        .checked_add(payment_amount)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    msg!("Batch purchase complete: {} tickets", count);
+    msg!("Buyer: {}", buyer.key());
+    msg!("Tier supply: {}/{}", tier.current_supply, tier.max_supply);
+    
+    Ok(())
+}
+/// Create a Ticket PDA the way Anchor's `init` does
+///
+/// `create_account` fails once the address holds any lamports, so anyone
+/// could block a mint's ticket by sending a few lamports to its PDA first.
+/// A pre-funded PDA is topped up to rent exemption, then allocated and
+/// assigned instead; an account that already has data (the mint already
+/// has a ticket) is still rejected.
+pub(crate) fn create_ticket_account<'info>(
+    payer: &AccountInfo<'info>,
+    ticket_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    rent: &Rent,
+) -> Result<()> {
+    let required_lamports = rent.minimum_balance(Ticket::SPACE);
+    let current_lamports = ticket_info.lamports();
+    
+    if current_lamports == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: ticket_info.clone(),
+                },
+                signer_seeds,
+            ),
+            required_lamports,
+            Ticket::SPACE as u64,
+            &crate::ID,
+        );
+    }
+    
+    let shortfall = required_lamports.saturating_sub(current_lamports);
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: ticket_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: ticket_info.clone(),
+            },
+            signer_seeds,
+        ),
+        Ticket::SPACE as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: ticket_info.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
+
@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
-use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount, burn, Burn};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount, burn, Burn, thaw_account, ThawAccount};
 use crate::errors::EventError;
-use crate::state::{Event, TicketTier, Ticket};
+use crate::state::{Event, EventDelegate, TicketTier, Ticket, BuyerRecord, PlatformConfig, PlatformFeeVault};
 
 #[derive(Accounts)]
 pub struct RefundTicket<'info> {
@@ -16,25 +16,52 @@ pub struct RefundTicket<'info> {
     
     /// Event account (must match ticket.event)
     #[account(
+        mut,
         constraint = event.key() == ticket.event @ EventError::UnauthorizedRefund,
-        has_one = authority @ EventError::UnauthorizedRefund
+        constraint = EventDelegate::authorizes(
+            &event.authority,
+            &authority.key(),
+            event_delegate.as_deref(),
+            EventDelegate::PERMISSION_REFUNDS,
+        ) @ EventError::UnauthorizedRefund
     )]
     pub event: Account<'info, Event>,
     
-    /// Tier account (for reference)
+    /// Tier account (seat is returned to supply)
     #[account(
+        mut,
         constraint = tier.key() == ticket.tier @ EventError::UnauthorizedRefund
     )]
     pub tier: Account<'info, TicketTier>,
     
-    /// Escrow account holding refund funds
-    /// PDA: ["escrow", event.key()]
+    /// Original buyer's purchase counter for this tier, which the refund
+    /// frees a slot in even after the ticket was resold (absent for tickets
+    /// registered through register_mint)
     #[account(
         mut,
-        seeds = [b"escrow", event.key().as_ref()],
-        bump
+        seeds = [b"buyer", tier.key().as_ref(), ticket.buyer.as_ref()],
+        bump = buyer_record.bump
     )]
-    pub escrow: SystemAccount<'info>,
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+    
+    /// Ticket escrow the purchase was paid into
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Platform fee vault (required once the refund cuts into withdrawn
+    /// revenue, so the platform's fee on it is clawed back)
+    /// PDA: ["platform_fees", event.key()]
+    #[account(
+        mut,
+        seeds = [b"platform_fees", event.key().as_ref()],
+        bump = platform_fees.bump
+    )]
+    pub platform_fees: Option<Account<'info, PlatformFeeVault>>,
     
     /// NFT mint to be burned
     #[account(
@@ -52,7 +79,17 @@ pub struct RefundTicket<'info> {
     )]
     pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// Buyer receiving the refund
+    /// Program PDA holding the mint's freeze authority (required to thaw a
+    /// soulbound ticket's NFT before burning it)
+    /// CHECK: Address checked by seeds; only signs the thaw CPI
+    #[account(
+        seeds = [b"ticket_freeze"],
+        bump
+    )]
+    pub freeze_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Buyer receiving the refund; only signs when the NFT is no longer in
+    /// the token account that approved the Ticket PDA at purchase
     /// CHECK: This is the ticket owner
     #[account(
         mut,
@@ -60,7 +97,28 @@ pub struct RefundTicket<'info> {
     )]
     pub buyer: AccountInfo<'info>,
     
-    /// Event authority that must approve refund
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Platform treasury receiving the refund fee (required when one applies)
+    /// CHECK: Checked against the platform config in the handler
+    #[account(mut)]
+    pub platform_treasury: Option<UncheckedAccount<'info>>,
+    
+    /// Delegate entry when the signer is not the event authority
+    /// PDA: ["delegate", event.key(), authority.key()]
+    #[account(
+        seeds = [b"delegate", event.key().as_ref(), authority.key().as_ref()],
+        bump = event_delegate.bump
+    )]
+    pub event_delegate: Option<Account<'info, EventDelegate>>,
+    
+    /// Event authority, or a delegate with the refunds permission, approving the refund
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
@@ -69,13 +127,16 @@ pub struct RefundTicket<'info> {
 
 pub fn handler(
     ctx: Context<RefundTicket>,
-    refund_amount: u64,
+    refund_amount: Option<u64>,
 ) -> Result<()> {
     let ticket = &mut ctx.accounts.ticket;
-    let event = &ctx.accounts.event;
-    let tier = &ctx.accounts.tier;
+    let event = &mut ctx.accounts.event;
+    let tier = &mut ctx.accounts.tier;
     let clock = Clock::get()?;
     
+    // Validation: Pre-registered stock was never paid for
+    ticket.require_active()?;
+    
     // Validation: Ticket must not already be used
     require!(
         !ticket.used,
@@ -95,57 +156,165 @@ pub fn handler(
         EventError::EventAlreadyStarted
     );
     
+    // Refund defaults to (and is capped at) what the buyer paid
+    let refund_amount = ticket.resolve_refund_amount(refund_amount)?;
+    
+    // Split off the platform's refund fee (never charged on cancellations)
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    let (refund_fee, buyer_amount) = PlatformConfig::split_refund_fee(
+        refund_amount,
+        event.refund_fee_bps(platform_config.as_ref()),
+    ).ok_or(EventError::ArithmeticOverflow)?;
+    if refund_fee > 0 {
+        let platform_treasury = ctx.accounts.platform_treasury.as_ref()
+            .ok_or(EventError::InvalidPlatformTreasury)?;
+        PlatformConfig::require_platform_treasury(platform_config.as_ref(), &platform_treasury.key())?;
+    }
+    
     // Validation: Check escrow has sufficient balance
     let rent = Rent::get()?;
     let rent_exempt_minimum = rent.minimum_balance(0);
     
-    let available_balance = ctx.accounts.escrow.lamports()
-        .checked_sub(rent_exempt_minimum)
-        .ok_or(EventError::InsufficientBalance)?;
+    let available_balance = ctx.accounts.ticket_escrow.lamports()
+        .saturating_sub(rent_exempt_minimum);
+    
+    // Refunding withdrawn revenue returns the platform's share of it from
+    // the fee vault while unsettled; the vault can't be left out then
+    let fee = event.platform_fee_clawback(refund_amount)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    let platform_fee_clawback = match ctx.accounts.platform_fees.as_mut() {
+        Some(platform_fees) => {
+            let clawback = platform_fees.claw_back(fee);
+            platform_fees.sub_lamports(clawback)?;
+            ctx.accounts.ticket_escrow.add_lamports(clawback)?;
+            clawback
+        }
+        None => {
+            require!(fee == 0, EventError::PlatformFeeVaultRequired);
+            0
+        }
+    };
+    event.record_platform_fee_clawback(platform_fee_clawback);
     
     require!(
-        refund_amount <= available_balance,
+        refund_amount <= available_balance.saturating_add(platform_fee_clawback),
         EventError::InsufficientBalance
     );
     
-    // Burn the NFT
-    let burn_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Burn {
-            mint: ctx.accounts.mint.to_account_info(),
-            from: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: ctx.accounts.buyer.to_account_info(),
-        },
-    );
+    // Soulbound tickets are frozen to their owner; thaw before burning
+    if ctx.accounts.buyer_token_account.is_frozen() {
+        let freeze_authority = ctx.accounts.freeze_authority.as_ref()
+            .ok_or(EventError::SoulboundMintNotFreezable)?;
+        let bump = ctx.bumps.freeze_authority.ok_or(EventError::SoulboundMintNotFreezable)?;
+        let freeze_seeds: &[&[&[u8]]] = &[&[b"ticket_freeze", &[bump]]];
+        thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            ThawAccount {
+                account: ctx.accounts.buyer_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: freeze_authority.to_account_info(),
+            },
+            freeze_seeds,
+        ))?;
+    }
     
-    // Note: In production, buyer should sign this transaction
-    // For now, we require buyer to be present but authority signs
-    burn(burn_ctx, 1)?;
-    
-    // Transfer refund from escrow to buyer
-    let event_key = event.key();
-    let escrow_seeds = &[
-        b"escrow",
-        event_key.as_ref(),
-        &[ctx.bumps.escrow],
-    ];
-    let signer_seeds = &[&escrow_seeds[..]];
-    
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.escrow.to_account_info(),
-            to: ctx.accounts.buyer.to_account_info(),
-        },
-        signer_seeds,
-    );
+    // Burn the NFT: purchase_ticket approved the Ticket PDA as delegate, so
+    // it signs the burn; an NFT that has since moved to another token
+    // account needs its holder's signature instead
+    let delegated = ctx.accounts.buyer_token_account.delegate == Some(ticket.key()).into()
+        && ctx.accounts.buyer_token_account.delegated_amount >= 1;
+    if delegated {
+        let mint_key = ticket.mint;
+        let ticket_seeds: &[&[&[u8]]] = &[&[b"ticket", mint_key.as_ref(), &[ticket.bump]]];
+        burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.buyer_token_account.to_account_info(),
+                    authority: ticket.to_account_info(),
+                },
+                ticket_seeds,
+            ),
+            1,
+        )?;
+    } else {
+        require!(
+            ctx.accounts.buyer.is_signer,
+            EventError::RefundNeedsBuyerSignature
+        );
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.buyer_token_account.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+    }
     
-    transfer(transfer_ctx, refund_amount)?;
+    // Pay the buyer's share and the platform's fee from the ticket escrow
+    if refund_amount > 0 {
+        let event_key = event.key();
+        let escrow_seeds = &[
+            b"ticket_escrow",
+            event_key.as_ref(),
+            &[event.ticket_escrow_bump],
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
+        
+        if buyer_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.ticket_escrow.to_account_info(),
+                        to: ctx.accounts.buyer.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                buyer_amount,
+            )?;
+        }
+        
+        if refund_fee > 0 {
+            let platform_treasury = ctx.accounts.platform_treasury.as_ref()
+                .ok_or(EventError::InvalidPlatformTreasury)?;
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.ticket_escrow.to_account_info(),
+                        to: platform_treasury.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                refund_fee,
+            )?;
+        }
+    }
     
     // Mark ticket as refunded
     ticket.refunded = true;
     ticket.refund_ts = clock.unix_timestamp;
     
+    // Return the seat to the tier so it can be resold
+    tier.release_seat()
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    event.record_ticket_refunded();
+    
+    // Refunded lamports no longer count as ticket revenue
+    event.ticket_revenue = event.ticket_revenue.saturating_sub(refund_amount);
+    
+    // Free up a slot in the buyer's per-wallet limit
+    if let Some(buyer_record) = ctx.accounts.buyer_record.as_mut() {
+        buyer_record.purchased_count = buyer_record.purchased_count.saturating_sub(1);
+    }
+    
     // Emit TicketRefunded event
     emit!(TicketRefunded {
         ticket_pubkey: ticket.key(),
@@ -154,14 +323,19 @@ pub fn handler(
         mint_pubkey: ticket.mint,
         owner: ticket.owner,
         refund_amount,
+        refund_fee,
+        price_paid: ticket.price_paid,
+        platform_fee_clawback,
         refunded_by: ctx.accounts.authority.key(),
+        tickets_sold: event.tickets_sold,
+        tickets_refunded: event.tickets_refunded,
         timestamp: ticket.refund_ts,
     });
     
     msg!("Ticket refunded successfully");
     msg!("Ticket: {}", ticket.key());
     msg!("Owner: {}", ticket.owner);
-    msg!("Refund amount: {} lamports", refund_amount);
+    msg!("Refund amount: {} lamports (paid {}, fee {})", refund_amount, ticket.price_paid, refund_fee);
     msg!("NFT burned: {}", ticket.mint);
     
     Ok(())
@@ -175,6 +349,11 @@ pub struct TicketRefunded {
     pub mint_pubkey: Pubkey,
     pub owner: Pubkey,
     pub refund_amount: u64,
+    pub refund_fee: u64,
+    pub price_paid: u64,
+    pub platform_fee_clawback: u64,
     pub refunded_by: Pubkey,
+    pub tickets_sold: u32,
+    pub tickets_refunded: u32,
     pub timestamp: i64,
 }
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
 use crate::errors::EventError;
-use crate::state::{Event, TicketTier, Ticket, Campaign, CampaignStatus};
+use crate::state::{Event, EventDelegate, TicketTier, Ticket, TicketStatus, Campaign, CampaignStatus, TicketIndex, PlatformStats, BlocklistEntry, UNSET_TIMESTAMP};
 
 #[derive(Accounts)]
 pub struct RegisterMint<'info> {
@@ -16,7 +16,12 @@ pub struct RegisterMint<'info> {
     
     #[account(
         mut,
-        has_one = authority @ EventError::UnauthorizedTierCreation
+        constraint = EventDelegate::authorizes(
+            &event.authority,
+            &authority.key(),
+            event_delegate.as_deref(),
+            EventDelegate::PERMISSION_MINTS,
+        ) @ EventError::UnauthorizedTierCreation
     )]
     pub event: Account<'info, Event>,
     
@@ -38,12 +43,47 @@ pub struct RegisterMint<'info> {
     /// CHECK: Buyer who owns the NFT
     pub buyer: AccountInfo<'info>,
     
+    /// Blocklist entry PDA for the buyer (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
+    /// Delegate entry when the signer is not the event authority
+    /// PDA: ["delegate", event.key(), authority.key()]
+    #[account(
+        seeds = [b"delegate", event.key().as_ref(), authority.key().as_ref()],
+        bump = event_delegate.bump
+    )]
+    pub event_delegate: Option<Account<'info, EventDelegate>>,
+    
+    /// Event authority, or a delegate with the mints permission
     #[account(mut)]
     pub authority: Signer<'info>,
     
     /// Optional campaign account (required if crowdfunding_enabled)
     pub campaign: Option<Account<'info, Campaign>>,
     
+    /// Buyer's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TicketIndex::LEN,
+        seeds = [b"ticket_index", event.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Platform stats PDA (optional; counters are skipped when omitted)
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Box<Account<'info, PlatformStats>>>,
+    
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
 }
@@ -79,23 +119,55 @@ pub fn handler(
     
     let event = &mut ctx.accounts.event;
     
+    // Validation: buyer must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &event.key(),
+        &ctx.accounts.buyer.key(),
+    )?;
+    
     // Validation: mint supply must be exactly 1
     require!(
         mint.supply == 1,
         EventError::InvalidSupply
     );
     
+    // Validation: event must not be canceled, started or ended
+    event.require_sales_open(Clock::get()?.unix_timestamp)?;
+    
+    // Validation: tier sale window must be open
+    tier.require_sale_open(Clock::get()?.unix_timestamp)?;
+    
+    // Validation: soulbound tickets are only sold through purchase_ticket,
+    // which freezes the NFT
+    tier.require_transferable()?;
+    
     // Validation: check tier has available supply
     require!(
         tier.is_available(),
         EventError::ExceedsTotalSupply
     );
     
+    // Validation: check event-wide capacity across all tiers
+    require!(
+        event.has_capacity(),
+        EventError::EventSoldOut
+    );
+    
     // Increment tier's current supply
     tier.current_supply = tier.current_supply
         .checked_add(1)
         .ok_or(EventError::ExceedsTotalSupply)?;
     
+    event.tickets_sold = event.tickets_sold
+        .checked_add(1)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    // Minted off-platform: counted as sold, no on-chain volume
+    if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+        stats.record_ticket_sales(1, 0, Clock::get()?.unix_timestamp);
+    }
+    
     // Store ticket data
     ticket.owner = ctx.accounts.buyer.key();
     ticket.event = event_key;
@@ -103,17 +175,37 @@ pub fn handler(
     ticket.mint = mint.key();
     ticket.used = false;
     ticket.refunded = false;
-    ticket.checked_in_ts = 0;
+    ticket.checked_in_ts = UNSET_TIMESTAMP;
     ticket.gate_operator = Pubkey::default();
-    ticket.refund_ts = 0;
+    ticket.refund_ts = UNSET_TIMESTAMP;
+    ticket.snapshotted = false;
+    ticket.price_paid = 0; // Minted off-platform, no payment collected on-chain
+    ticket.purchased_at = Clock::get()?.unix_timestamp;
+    ticket.use_count = 0;
+    ticket.status = TicketStatus::Active;
+    ticket.promo_applied = false;
+    ticket.last_sale_price = 0;
+    ticket.last_sale_royalty = 0;
+    ticket.seat_number = 0;
+    ticket.gift_count = 0;
+    ticket.checkin_delegate = Pubkey::default();
+    ticket.buyer = ctx.accounts.buyer.key();
     ticket.bump = ctx.bumps.ticket;
     
-    // Track ticket revenue (tier price)
-    event.ticket_revenue = event.ticket_revenue
-        .checked_add(tier.price_lamports)
-        .ok_or(EventError::ArithmeticOverflow)?;
+    // Append to the buyer's ticket index if the event maintains one
+    if event.maintain_ticket_index {
+        let ticket_index = ctx.accounts.ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        ticket_index.init_if_new(
+            event_key,
+            ctx.accounts.buyer.key(),
+            ctx.bumps.ticket_index.ok_or(EventError::TicketIndexRequired)?,
+        );
+        ticket_index.add(ticket.key());
+    }
     
-    msg!("Ticket revenue updated: {} lamports", event.ticket_revenue);
+    // No lamports are collected here, so ticket_revenue is left untouched
+    // to keep it in step with the escrow balance and price_paid
     
     // Emit TicketRegistered event
     emit!(TicketRegistered {
@@ -122,6 +214,7 @@ pub fn handler(
         tier_pubkey: tier.key(),
         mint_pubkey: mint.key(),
         owner: ticket.owner,
+        tickets_sold: event.tickets_sold,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
@@ -140,5 +233,6 @@ pub struct TicketRegistered {
     pub tier_pubkey: Pubkey,
     pub mint_pubkey: Pubkey,
     pub owner: Pubkey,
+    pub tickets_sold: u32,
     pub timestamp: i64,
 }
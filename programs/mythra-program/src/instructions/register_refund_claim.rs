@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, Ticket, RefundClaim};
+
+#[derive(Accounts)]
+pub struct RegisterRefundClaim<'info> {
+    /// Refund claim to be created (one per ticket)
+    #[account(
+        init,
+        payer = owner,
+        space = RefundClaim::LEN,
+        seeds = [b"refund_claim", ticket.key().as_ref()],
+        bump
+    )]
+    pub refund_claim: Account<'info, RefundClaim>,
+    
+    /// Ticket the claim is registered for
+    #[account(
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.owner == owner.key() @ EventError::InvalidOwner
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Canceled event (must match ticket.event)
+    #[account(
+        mut,
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Ticket owner registering the claim
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterRefundClaim>) -> Result<()> {
+    let refund_claim = &mut ctx.accounts.refund_claim;
+    let ticket = &ctx.accounts.ticket;
+    let event = &mut ctx.accounts.event;
+    let clock = Clock::get()?;
+    
+    // Validation: Event must be canceled in shortfall mode
+    require!(
+        event.canceled,
+        EventError::EventNotCanceled
+    );
+    require!(
+        event.shortfall_mode,
+        EventError::ShortfallModeNotActive
+    );
+    
+    // Validation: Registration window must still be open
+    require!(
+        event.refund_claim_window_open(clock.unix_timestamp),
+        EventError::RefundClaimWindowClosed
+    );
+    
+    // Validation: Ticket must have been sold, not used or already refunded
+    ticket.require_active()?;
+    require!(
+        !ticket.used,
+        EventError::TicketUsedCannotRefund
+    );
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    // Entitlement is what the buyer actually paid, not the tier's current price
+    let amount = ticket.price_paid;
+    
+    // Record the entitlement without moving any funds
+    refund_claim.event = event.key();
+    refund_claim.ticket = ticket.key();
+    refund_claim.claimant = ctx.accounts.owner.key();
+    refund_claim.amount = amount;
+    refund_claim.registered_at = clock.unix_timestamp;
+    refund_claim.paid = false;
+    refund_claim.bump = ctx.bumps.refund_claim;
+    
+    event.total_refund_claims = event.total_refund_claims
+        .checked_add(amount)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    // Emit RefundClaimRegistered event
+    emit!(RefundClaimRegistered {
+        refund_claim: refund_claim.key(),
+        event_pubkey: event.key(),
+        ticket_pubkey: ticket.key(),
+        claimant: refund_claim.claimant,
+        amount,
+        total_refund_claims: event.total_refund_claims,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Refund claim registered: {}", refund_claim.key());
+    msg!("Entitlement: {} lamports", amount);
+    msg!("Total registered claims: {} lamports", event.total_refund_claims);
+    
+    Ok(())
+}
+
+#[event]
+pub struct RefundClaimRegistered {
+    pub refund_claim: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub ticket_pubkey: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub total_refund_claims: u64,
+    pub timestamp: i64,
+}
@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::state::{Campaign, Budget, BudgetStatus, Event};
+use crate::state::{Campaign, Budget, BudgetStatus, Event, PlatformConfig};
 use crate::errors::EventError;
 
 /// Release funds for a milestone
 /// 
 /// Once a budget is approved and a milestone's unlock_date has passed,
 /// the organizer can claim funds for that milestone. Funds are transferred
-/// from campaign escrow to the organizer.
+/// from campaign escrow to the organizer. Budgets with
+/// `per_milestone_approval` also need the milestone's release vote to have
+/// passed.
 pub fn handler(
     ctx: Context<ReleaseMilestone>,
     milestone_index: u8,
@@ -15,6 +17,21 @@ pub fn handler(
     let campaign = &ctx.accounts.campaign;
     let clock = Clock::get()?;
     
+    // Validate releases are still open after the event (config timeout or default)
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_milestone_release_open(
+        platform_config.as_ref(),
+        ctx.accounts.event.end_ts,
+        clock.unix_timestamp,
+    )?;
+    
+    // Validate distribution hasn't run; what's left in escrow then belongs
+    // to the profit pools or the backers' loss recovery
+    require!(
+        !campaign.distribution_complete,
+        EventError::DistributionAlreadyComplete
+    );
+    
     // Validate budget is approved
     require!(
         budget.status == BudgetStatus::Approved,
@@ -42,15 +59,28 @@ pub fn handler(
         EventError::MilestoneAlreadyReleased
     );
     
+    // Validate no open dispute and not voided by an upheld one
+    milestone_data.require_not_disputed()?;
+    
+    // Validate the milestone's own release vote passed, if the budget needs one
+    milestone_data.require_release_approved(budget.per_milestone_approval)?;
+    
     // Calculate release amount from percentage
-    let release_amount = (budget.total_amount as u128 * milestone_data.release_percentage as u128 / 10_000) as u64;
+    let release_amount = milestone_data.amount(budget.total_amount);
+    
+    // Validate escrow can pay without dipping into its rent reserve
+    Campaign::require_escrow_covers(
+        ctx.accounts.campaign_escrow.lamports(),
+        Rent::get()?.minimum_balance(0),
+        release_amount,
+    )?;
     
     // Transfer funds from campaign escrow to organizer
     let campaign_key = campaign.key();
     let escrow_seeds = &[
         b"campaign_escrow",
         campaign_key.as_ref(),
-        &[ctx.bumps.campaign_escrow],
+        &[campaign.escrow_bump],
     ];
     let signer_seeds = &[&escrow_seeds[..]];
     
@@ -73,8 +103,8 @@ pub fn handler(
     let campaign = &mut ctx.accounts.campaign;
     campaign.total_expenses += release_amount;
     
-    // Check if all milestones released
-    let all_released = budget.milestones.iter().all(|m| m.released);
+    // Check if all milestones released (voided milestones never will be)
+    let all_released = budget.milestones.iter().all(|m| m.is_settled());
     if all_released {
         budget.status = BudgetStatus::Executed;
         msg!("🎉 All milestones released! Budget execution complete.");
@@ -88,6 +118,7 @@ pub fn handler(
     
     emit!(MilestoneReleased {
         budget: budget.key(),
+        event: campaign.event,
         milestone_index,
         amount: release_amount,
         organizer: ctx.accounts.organizer.key(),
@@ -114,26 +145,29 @@ pub struct ReleaseMilestone<'info> {
     /// Budget with milestones
     #[account(
         mut,
-        seeds = [
-            b"budget",
-            campaign.key().as_ref(),
-        ],
-        bump = budget.bump,
-        has_one = campaign
+        has_one = campaign,
+        constraint = budget.key() == campaign.active_budget @ EventError::BudgetNotActive
     )]
     pub budget: Account<'info, Budget>,
     
     /// Campaign escrow holding funds
-    /// CHECK: PDA, funds transferred out
     #[account(
         mut,
         seeds = [
             b"campaign_escrow",
             campaign.key().as_ref(),
         ],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
         bump
     )]
-    pub campaign_escrow: AccountInfo<'info>,
+    pub platform_config: UncheckedAccount<'info>,
     
     /// Organizer receiving milestone funds
     #[account(mut)]
@@ -145,6 +179,7 @@ pub struct ReleaseMilestone<'info> {
 #[event]
 pub struct MilestoneReleased {
     pub budget: Pubkey,
+    pub event: Pubkey,
     pub milestone_index: u8,
     pub amount: u64,
     pub organizer: Pubkey,
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, EventDelegate};
+
+#[derive(Accounts)]
+pub struct RemoveEventDelegate<'info> {
+    /// Event the delegate was registered for
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Delegate registry entry to be closed
+    #[account(
+        mut,
+        seeds = [b"delegate", event.key().as_ref(), event_delegate.delegate.as_ref()],
+        bump = event_delegate.bump,
+        close = authority
+    )]
+    pub event_delegate: Account<'info, EventDelegate>,
+    
+    /// Event authority - receives reclaimed rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveEventDelegate>) -> Result<()> {
+    let event_delegate = &ctx.accounts.event_delegate;
+    
+    // Emit EventDelegateRemoved event
+    emit!(EventDelegateRemoved {
+        event_pubkey: event_delegate.event,
+        delegate: event_delegate.delegate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Delegate {} removed", event_delegate.delegate);
+    
+    Ok(())
+}
+
+#[event]
+pub struct EventDelegateRemoved {
+    pub event_pubkey: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
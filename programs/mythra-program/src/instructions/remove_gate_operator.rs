@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, GateOperator};
+
+#[derive(Accounts)]
+pub struct RemoveGateOperator<'info> {
+    /// Event the operator was registered for
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Gate operator registry entry to be closed
+    #[account(
+        mut,
+        seeds = [b"gate_operator", event.key().as_ref(), gate_operator.operator.as_ref()],
+        bump = gate_operator.bump,
+        close = authority
+    )]
+    pub gate_operator: Account<'info, GateOperator>,
+    
+    /// Event authority - receives reclaimed rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveGateOperator>) -> Result<()> {
+    let gate_operator = &ctx.accounts.gate_operator;
+    
+    // Emit GateOperatorRemoved event
+    emit!(GateOperatorRemoved {
+        event_pubkey: gate_operator.event,
+        operator: gate_operator.operator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Gate operator {} removed", gate_operator.operator);
+    
+    Ok(())
+}
+
+#[event]
+pub struct GateOperatorRemoved {
+    pub event_pubkey: Pubkey,
+    pub operator: Pubkey,
+    pub timestamp: i64,
+}
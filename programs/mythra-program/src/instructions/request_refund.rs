@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount, burn, Burn, thaw_account, ThawAccount};
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Ticket, BuyerRecord, PlatformConfig, RefundPolicy};
+
+#[derive(Accounts)]
+pub struct RequestRefund<'info> {
+    /// Ticket account to be refunded
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.owner == owner.key() @ EventError::InvalidOwner
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event whose refund policy applies (must match ticket.event)
+    #[account(
+        mut,
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Tier account (seat is returned to supply)
+    #[account(
+        mut,
+        constraint = tier.key() == ticket.tier @ EventError::UnauthorizedRefund
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Original buyer's purchase counter for this tier (absent for
+    /// tickets registered without a purchase)
+    #[account(
+        mut,
+        seeds = [b"buyer", tier.key().as_ref(), ticket.buyer.as_ref()],
+        bump = buyer_record.bump
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+    
+    /// Ticket escrow holding ticket sale proceeds
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// NFT mint to be burned
+    #[account(
+        mut,
+        constraint = mint.key() == ticket.mint @ EventError::InvalidMintOwner
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Owner's token account holding the NFT
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == ticket.mint @ EventError::InvalidMintOwner,
+        constraint = owner_token_account.owner == owner.key() @ EventError::InvalidMintOwner,
+        constraint = owner_token_account.amount == 1 @ EventError::TicketNotOwned
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Program PDA holding the mint's freeze authority (required to thaw a
+    /// soulbound ticket's NFT before burning it)
+    /// CHECK: Address checked by seeds; only signs the thaw CPI
+    #[account(
+        seeds = [b"ticket_freeze"],
+        bump
+    )]
+    pub freeze_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Platform treasury receiving the refund fee (required when one applies)
+    /// CHECK: Checked against the platform config in the handler
+    #[account(mut)]
+    pub platform_treasury: Option<UncheckedAccount<'info>>,
+    
+    /// Ticket owner requesting the refund
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Refund a ticket under the event's refund policy
+///
+/// The owner refunds themselves without the organizer's approval. The
+/// refund is the stored `price_paid` times the policy's percentage for how
+/// far out from the start the request is, paid from the ticket escrow; the
+/// NFT is burned. Canceled events refund through the cancellation flow
+/// instead, and `refund_ticket` remains for manual overrides.
+pub fn handler(ctx: Context<RequestRefund>) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let event = &mut ctx.accounts.event;
+    let tier = &mut ctx.accounts.tier;
+    let clock = Clock::get()?;
+    
+    // Validation: Pre-registered stock was never paid for
+    ticket.require_active()?;
+    
+    // Validation: Ticket must not already be used or refunded
+    require!(
+        !ticket.used,
+        EventError::TicketUsedCannotRefund
+    );
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    // Validation: Re-entry passes that have been scanned once are spent
+    require!(
+        ticket.use_count == 0,
+        EventError::TicketUsedCannotRefund
+    );
+    
+    // Validation: Canceled events refund through the cancellation flow
+    require!(
+        !event.canceled,
+        EventError::EventAlreadyCanceled
+    );
+    
+    // Validation: The policy still refunds something at this point
+    let refund_bps = event.refund_policy.refund_bps_at(event.start_ts, clock.unix_timestamp);
+    require!(
+        refund_bps > 0,
+        EventError::RefundNotAvailable
+    );
+    
+    let refund_amount = RefundPolicy::refund_amount(ticket.price_paid, refund_bps)?;
+    
+    // Split off the platform's refund fee (never charged on cancellations)
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    let (refund_fee, buyer_amount) = PlatformConfig::split_refund_fee(
+        refund_amount,
+        event.refund_fee_bps(platform_config.as_ref()),
+    ).ok_or(EventError::ArithmeticOverflow)?;
+    if refund_fee > 0 {
+        let platform_treasury = ctx.accounts.platform_treasury.as_ref()
+            .ok_or(EventError::InvalidPlatformTreasury)?;
+        PlatformConfig::require_platform_treasury(platform_config.as_ref(), &platform_treasury.key())?;
+    }
+    
+    // Validation: Escrow can pay without dipping into its rent reserve
+    let rent = Rent::get()?;
+    let available_balance = ctx.accounts.ticket_escrow.lamports()
+        .saturating_sub(rent.minimum_balance(0));
+    require!(
+        refund_amount <= available_balance,
+        EventError::InsufficientBalance
+    );
+    
+    // Soulbound tickets are frozen to their owner; thaw before burning
+    if ctx.accounts.owner_token_account.is_frozen() {
+        let freeze_authority = ctx.accounts.freeze_authority.as_ref()
+            .ok_or(EventError::SoulboundMintNotFreezable)?;
+        let bump = ctx.bumps.freeze_authority.ok_or(EventError::SoulboundMintNotFreezable)?;
+        let freeze_seeds: &[&[&[u8]]] = &[&[b"ticket_freeze", &[bump]]];
+        thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            ThawAccount {
+                account: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: freeze_authority.to_account_info(),
+            },
+            freeze_seeds,
+        ))?;
+    }
+    
+    // Burn the NFT (signed by the owner)
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    burn(burn_ctx, 1)?;
+    
+    // Pay the buyer's share and the platform's fee from the escrow
+    if refund_amount > 0 {
+        let event_key = event.key();
+        let escrow_seeds = &[
+            b"ticket_escrow",
+            event_key.as_ref(),
+            &[event.ticket_escrow_bump],
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
+        
+        if buyer_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.ticket_escrow.to_account_info(),
+                        to: ctx.accounts.owner.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                buyer_amount,
+            )?;
+        }
+        
+        if refund_fee > 0 {
+            let platform_treasury = ctx.accounts.platform_treasury.as_ref()
+                .ok_or(EventError::InvalidPlatformTreasury)?;
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.ticket_escrow.to_account_info(),
+                        to: platform_treasury.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                refund_fee,
+            )?;
+        }
+    }
+    
+    // Mark ticket as refunded
+    ticket.refunded = true;
+    ticket.refund_ts = clock.unix_timestamp;
+    
+    // Return the seat to the tier so it can be resold
+    tier.release_seat()
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    event.record_ticket_refunded();
+    
+    // Refunded lamports no longer count as ticket revenue
+    event.ticket_revenue = event.ticket_revenue.saturating_sub(refund_amount);
+    
+    // Free up a slot in the owner's per-wallet limit
+    if let Some(buyer_record) = ctx.accounts.buyer_record.as_mut() {
+        buyer_record.purchased_count = buyer_record.purchased_count.saturating_sub(1);
+    }
+    
+    emit!(RefundRequested {
+        ticket_pubkey: ticket.key(),
+        event_pubkey: event.key(),
+        tier_pubkey: tier.key(),
+        mint_pubkey: ticket.mint,
+        owner: ticket.owner,
+        price_paid: ticket.price_paid,
+        refund_bps,
+        refund_amount,
+        refund_fee,
+        tickets_sold: event.tickets_sold,
+        tickets_refunded: event.tickets_refunded,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Refund requested: {}", ticket.key());
+    msg!("Refund amount: {} lamports ({} bps, fee {})", refund_amount, refund_bps, refund_fee);
+    
+    Ok(())
+}
+
+#[event]
+pub struct RefundRequested {
+    pub ticket_pubkey: Pubkey,
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub mint_pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub price_paid: u64,
+    pub refund_bps: u16,
+    pub refund_amount: u64,
+    pub refund_fee: u64,
+    pub tickets_sold: u32,
+    pub tickets_refunded: u32,
+    pub timestamp: i64,
+}
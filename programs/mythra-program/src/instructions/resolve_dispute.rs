@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Campaign, Budget, Dispute, DisputeStatus, PlatformConfig};
+use crate::errors::EventError;
+
+/// Resolve a milestone dispute
+///
+/// Only the platform admin can resolve. The bond moves according to the
+/// outcome:
+/// - Upheld: the milestone is voided (never released) and the bond is
+///   returned to the flagger
+/// - Dismissed: the milestone can be released again and the bond is
+///   forwarded to the organizer
+///
+/// The whole bond PDA balance is moved, which leaves it empty.
+pub fn handler(
+    ctx: Context<ResolveDispute>,
+    milestone_index: u8,
+    upheld: bool,
+) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let dispute = &mut ctx.accounts.dispute;
+    let clock = Clock::get()?;
+    
+    let status = dispute.resolve(upheld, clock.unix_timestamp)?;
+    budget.milestones[milestone_index as usize].dispute_status = status;
+    
+    let recipient = if status == DisputeStatus::Upheld {
+        ctx.accounts.flagger.to_account_info()
+    } else {
+        ctx.accounts.organizer.to_account_info()
+    };
+    let recipient_key = recipient.key();
+    let bond_balance = ctx.accounts.dispute_bond.lamports();
+    
+    if bond_balance > 0 {
+        let budget_key = budget.key();
+        let seeds = &[
+            b"dispute_bond",
+            budget_key.as_ref(),
+            &[milestone_index],
+            &[ctx.bumps.dispute_bond],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.dispute_bond.to_account_info(),
+                to: recipient,
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, bond_balance)?;
+    }
+    
+    msg!("Dispute on milestone {} {}", milestone_index, if upheld { "upheld" } else { "dismissed" });
+    msg!("Bond of {} lamports sent to {}", bond_balance, recipient_key);
+    
+    emit!(DisputeResolved {
+        budget: budget.key(),
+        campaign: ctx.accounts.campaign.key(),
+        dispute: dispute.key(),
+        milestone_index,
+        upheld,
+        bond_amount: bond_balance,
+        bond_recipient: recipient_key,
+        resolved_by: ctx.accounts.admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(milestone_index: u8)]
+pub struct ResolveDispute<'info> {
+    /// Platform config (the admin resolves disputes)
+    #[account(
+        seeds = [b"config"],
+        bump = platform_config.bump,
+        has_one = admin @ EventError::UnauthorizedPlatformAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    /// Platform admin
+    pub admin: Signer<'info>,
+    
+    /// Campaign the budget belongs to
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Budget with the disputed milestone
+    #[account(
+        mut,
+        has_one = campaign,
+        constraint = budget.key() == campaign.active_budget @ EventError::BudgetNotActive
+    )]
+    pub budget: Account<'info, Budget>,
+    
+    /// Dispute being resolved
+    #[account(
+        mut,
+        seeds = [
+            b"milestone_dispute",
+            budget.key().as_ref(),
+            &[milestone_index],
+        ],
+        bump = dispute.bump,
+        has_one = budget
+    )]
+    pub dispute: Account<'info, Dispute>,
+    
+    /// Bond PDA holding the flagger's bond
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond",
+            budget.key().as_ref(),
+            &[milestone_index],
+        ],
+        bump
+    )]
+    pub dispute_bond: SystemAccount<'info>,
+    
+    /// Flagger (receives the bond back if upheld)
+    /// CHECK: Must match the flagger recorded on the dispute
+    #[account(
+        mut,
+        address = dispute.flagger @ EventError::UnauthorizedClaim
+    )]
+    pub flagger: UncheckedAccount<'info>,
+    
+    /// Organizer (receives the bond if dismissed)
+    /// CHECK: Must match the campaign organizer
+    #[account(
+        mut,
+        address = campaign.organizer @ EventError::UnauthorizedCampaignAction
+    )]
+    pub organizer: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub dispute: Pubkey,
+    pub milestone_index: u8,
+    pub upheld: bool,
+    pub bond_amount: u64,
+    pub bond_recipient: Pubkey,
+    pub resolved_by: Pubkey,
+    pub timestamp: i64,
+}
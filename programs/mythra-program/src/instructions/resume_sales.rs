@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::Event;
+
+#[derive(Accounts)]
+pub struct ResumeSales<'info> {
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Event authority
+    pub authority: Signer<'info>,
+}
+
+/// Resume ticket issuance after halt_sales
+pub fn handler(ctx: Context<ResumeSales>) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    
+    require!(
+        event.sales_halted,
+        EventError::SalesNotHalted
+    );
+    
+    event.sales_halted = false;
+    
+    // Emit SalesResumed event
+    emit!(SalesResumed {
+        event_pubkey: event.key(),
+        authority: event.authority,
+        tickets_sold: event.tickets_sold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Sales resumed for event: {}", event.key());
+    
+    Ok(())
+}
+
+#[event]
+pub struct SalesResumed {
+    pub event_pubkey: Pubkey,
+    pub authority: Pubkey,
+    pub tickets_sold: u32,
+    pub timestamp: i64,
+}
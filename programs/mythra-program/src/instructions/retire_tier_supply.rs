@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier};
+
+#[derive(Accounts)]
+pub struct RetireTierSupply<'info> {
+    /// Tier giving up unsold seats (must belong to event)
+    #[account(
+        mut,
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Parent event whose allocated supply is released
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Event authority
+    pub authority: Signer<'info>,
+}
+
+/// Retire unsold seats from a tier
+///
+/// Reduces the tier's max_supply by `amount` (never below the seats already
+/// sold) and frees the same capacity in `event.allocated_supply`, so it can
+/// be given to a new tier.
+pub fn handler(ctx: Context<RetireTierSupply>, amount: u32) -> Result<()> {
+    let tier = &mut ctx.accounts.tier;
+    let event = &mut ctx.accounts.event;
+    
+    let max_supply_before = tier.max_supply;
+    let allocated_supply_before = event.allocated_supply;
+    
+    tier.retire_supply(amount)?;
+    event.release_allocation(amount)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    // Emit TierSupplyRetired event
+    emit!(TierSupplyRetired {
+        event_pubkey: event.key(),
+        tier_pubkey: tier.key(),
+        amount,
+        current_supply: tier.current_supply,
+        max_supply_before,
+        max_supply_after: tier.max_supply,
+        allocated_supply_before,
+        allocated_supply_after: event.allocated_supply,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Tier supply retired: {}", tier.key());
+    msg!("Max supply: {} -> {}", max_supply_before, tier.max_supply);
+    msg!("Event allocated supply: {}/{}", event.allocated_supply, event.total_supply);
+    
+    Ok(())
+}
+
+#[event]
+pub struct TierSupplyRetired {
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub amount: u32,
+    pub current_supply: u32,
+    pub max_supply_before: u32,
+    pub max_supply_after: u32,
+    pub allocated_supply_before: u32,
+    pub allocated_supply_after: u32,
+    pub timestamp: i64,
+}
@@ -1,12 +1,26 @@
 use anchor_lang::prelude::*;
-use crate::state::{Campaign, Budget, BudgetStatus, Milestone};
+use crate::state::{Campaign, Budget, Event, BudgetStatus, Milestone, MilestoneApproval, DisputeStatus, UNSET_TIMESTAMP};
 use crate::instructions::submit_budget::MilestoneInput;
 
-/// Revise a rejected budget
+/// Revise a rejected, expired or approved budget
 /// 
-/// If a budget is rejected, the organizer can revise and resubmit.
-/// Maximum 2 revisions allowed. Creates a new budget account with
-/// incremented revision count.
+/// If a budget is rejected, the organizer can revise and resubmit. An
+/// approved budget can be revised while it has unsettled milestones and no
+/// open dispute; it stops releasing funds as soon as the revision is
+/// submitted. Maximum 2 revisions allowed. Revisions chain: each one
+/// replaces the campaign's active budget and creates a new budget account
+/// with incremented revision count. Milestones that already released funds
+/// are carried forward and can't be removed or reduced. A revision can't be
+/// submitted within `Budget::REVISION_COOLDOWN_SECONDS` of a rejection or
+/// with the same total and milestones as the budget it revises. The
+/// revision keeps the old budget's `per_milestone_approval`, `voting_model`
+/// and `min_turnout_bps`.
+///
+/// Like submissions, revisions can't be made once the event has ended.
+///
+/// A budget whose vote expired below the turnout floor can be resubmitted
+/// immediately, with or without changes, and doesn't use up a revision;
+/// at most `Budget::MAX_EXPIRED_RESUBMISSIONS` times.
 pub fn handler(
     ctx: Context<ReviseBudget>,
     total_amount: u64,
@@ -16,18 +30,31 @@ pub fn handler(
 ) -> Result<()> {
     let old_budget = &ctx.accounts.old_budget;
     let new_budget = &mut ctx.accounts.new_budget;
-    let campaign = &ctx.accounts.campaign;
+    let campaign = &mut ctx.accounts.campaign;
     let clock = Clock::get()?;
     
+    // Validate event hasn't ended (distribution is the path after that)
+    require!(
+        !ctx.accounts.event.has_ended(clock.unix_timestamp),
+        ErrorCode::EventEnded
+    );
+    
     // Validate old budget can be revised
     require!(
         old_budget.can_revise(),
         ErrorCode::CannotReviseBudget
     );
     
-    // Validate hasn't exceeded revision limit
+    // Validate hasn't exceeded revision limit (expired votes resubmit for
+    // free, up to their own limit)
+    let expired = old_budget.status == BudgetStatus::Expired;
+    let within_limit = if expired {
+        old_budget.expired_resubmissions < Budget::MAX_EXPIRED_RESUBMISSIONS
+    } else {
+        old_budget.revisions_used() < 2
+    };
     require!(
-        old_budget.revision_count < 2,
+        within_limit,
         ErrorCode::MaxRevisionsReached
     );
     
@@ -43,14 +70,14 @@ pub fn handler(
         ErrorCode::BudgetDescriptionTooLong
     );
     
-    // Validate exactly 3 milestones
+    // Validate milestone count (1 to MAX_MILESTONES)
     require!(
-        milestones.len() == 3,
-        ErrorCode::InvalidMilestonePercentages
+        Budget::valid_milestone_count(milestones.len()),
+        ErrorCode::InvalidMilestoneCount
     );
     
     // Validate milestone percentages
-    let total_percentage: u16 = milestones.iter().map(|m| m.release_percentage).sum();
+    let total_percentage: u32 = milestones.iter().map(|m| m.release_percentage as u32).sum();
     require!(
         total_percentage == 10_000,
         ErrorCode::InvalidMilestonePercentages
@@ -70,24 +97,58 @@ pub fn handler(
     new_budget.description = description;
     
     // Set milestones
-    for (i, input) in milestones.iter().enumerate() {
-        new_budget.milestones[i] = Milestone {
+    let mut revised_milestones: Vec<Milestone> = milestones
+        .iter()
+        .map(|input| Milestone {
             description: input.description.clone(),
             release_percentage: input.release_percentage,
             unlock_date: input.unlock_date,
             released: false,
             released_amount: 0,
-        };
-    }
+            dispute_status: DisputeStatus::None,
+            approval: MilestoneApproval::None,
+            vote_end: UNSET_TIMESTAMP,
+            votes_for: 0,
+            votes_against: 0,
+            turnout_amount: 0,
+        })
+        .collect();
+    
+    // Milestones that already paid out can't be dropped or shrunk
+    old_budget.carry_forward_released(&mut revised_milestones, total_amount)?;
+    
+    // Revisions of rejected budgets must wait out the cooldown and change the terms
+    old_budget.require_revision_allowed(total_amount, &revised_milestones, clock.unix_timestamp)?;
+    new_budget.milestones = revised_milestones;
     
     new_budget.status = BudgetStatus::Pending;
     new_budget.voting_end = clock.unix_timestamp + voting_period_seconds;
     new_budget.votes_for = 0;
     new_budget.votes_against = 0;
-    new_budget.revision_count = old_budget.revision_count + 1;
+    new_budget.votes_abstain = 0;
+    new_budget.quorum_bps = Budget::DEFAULT_QUORUM_BPS;
+    new_budget.revision_count = old_budget.revision_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MaxRevisionsReached)?;
     new_budget.created_at = clock.unix_timestamp;
+    new_budget.reminder_emitted = false;
+    new_budget.votes_cast_count = 0;
+    new_budget.total_voting_power_cast = 0;
+    new_budget.finalized_at = UNSET_TIMESTAMP;
+    new_budget.total_raised_snapshot = campaign.total_raised;
+    new_budget.snapshot_ts = clock.unix_timestamp;
+    new_budget.per_milestone_approval = old_budget.per_milestone_approval;
+    new_budget.voting_model = old_budget.voting_model;
+    new_budget.turnout_amount = 0;
+    new_budget.min_turnout_bps = old_budget.min_turnout_bps;
+    new_budget.expired_resubmissions = old_budget.expired_resubmissions
+        .checked_add(expired as u8)
+        .ok_or(ErrorCode::MaxRevisionsReached)?;
     new_budget.bump = ctx.bumps.new_budget;
     
+    // Milestones are released from the revision from now on
+    campaign.active_budget = new_budget.key();
+    
     msg!(
         "Budget revised (revision #{}). New voting ends at {}",
         new_budget.revision_count,
@@ -102,17 +163,21 @@ pub fn handler(
 pub struct ReviseBudget<'info> {
     /// Campaign
     #[account(
+        mut,
         has_one = organizer @ ErrorCode::UnauthorizedCampaignAction
     )]
     pub campaign: Account<'info, Campaign>,
     
-    /// Old rejected budget
+    /// Event the campaign is funding
     #[account(
-        seeds = [
-            b"budget",
-            campaign.key().as_ref(),
-        ],
-        bump = old_budget.bump
+        address = campaign.event @ ErrorCode::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Budget being revised: the original or its latest revision
+    #[account(
+        has_one = campaign,
+        constraint = old_budget.key() == campaign.active_budget @ ErrorCode::BudgetNotActive
     )]
     pub old_budget: Account<'info, Budget>,
     
@@ -120,7 +185,10 @@ pub struct ReviseBudget<'info> {
     #[account(
         init,
         payer = organizer,
-        space = Budget::LEN,
+        space = Budget::space(
+            &description,
+            milestones.iter().map(|m| m.description.as_str()),
+        ),
         seeds = [
             b"budget_revision",
             campaign.key().as_ref(),
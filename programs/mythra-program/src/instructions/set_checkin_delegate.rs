@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::Ticket;
+
+#[derive(Accounts)]
+pub struct SetCheckinDelegate<'info> {
+    /// Ticket the delegate may check in
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.owner == owner.key() @ EventError::InvalidOwner
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Ticket owner - must sign
+    pub owner: Signer<'info>,
+}
+
+/// Let a companion wallet check the ticket in on the owner's behalf
+///
+/// The delegate can only sign `mark_ticket_used`; the NFT stays in the
+/// owner's wallet and transfers and refunds still need the owner. Passing
+/// the default pubkey clears the delegate, and any change of owner clears
+/// it too.
+pub fn handler(ctx: Context<SetCheckinDelegate>, delegate: Pubkey) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    
+    // Validation: only sold, unrefunded tickets can still be checked in
+    ticket.require_active()?;
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    // Validation: the owner checks in as themselves
+    require!(
+        delegate != ticket.owner,
+        EventError::InvalidCheckinDelegate
+    );
+    
+    let previous_delegate = ticket.checkin_delegate;
+    ticket.checkin_delegate = delegate;
+    
+    emit!(CheckinDelegateSet {
+        ticket: ticket.key(),
+        owner: ticket.owner,
+        previous_delegate,
+        delegate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Check-in delegate for {}: {}", ticket.key(), delegate);
+    
+    Ok(())
+}
+
+#[event]
+pub struct CheckinDelegateSet {
+    pub ticket: Pubkey,
+    pub owner: Pubkey,
+    pub previous_delegate: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
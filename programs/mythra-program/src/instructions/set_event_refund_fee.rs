@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, PlatformConfig};
+
+/// Override the platform refund fee for one event
+///
+/// `None` goes back to the config's `refund_fee_bps`. The fee belongs to
+/// the platform, so only the platform admin sets it; canceled events never
+/// pay it either way.
+pub fn handler(
+    ctx: Context<SetEventRefundFee>,
+    refund_fee_bps: Option<u16>,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    
+    // Validation: fee can't exceed the refund
+    require!(
+        refund_fee_bps.unwrap_or(0) <= 10_000,
+        EventError::InvalidRefundFee
+    );
+    
+    event.refund_fee_bps_override = refund_fee_bps;
+    
+    emit!(EventRefundFeeSet {
+        event: event.key(),
+        admin: ctx.accounts.admin.key(),
+        refund_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Refund fee override for {}: {:?}", event.key(), refund_fee_bps);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetEventRefundFee<'info> {
+    /// Event whose refunds the override applies to
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+    
+    /// Platform config PDA
+    #[account(
+        seeds = [b"config"],
+        bump = platform_config.bump,
+        has_one = admin @ EventError::UnauthorizedPlatformAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    /// Platform admin
+    pub admin: Signer<'info>,
+}
+
+#[event]
+pub struct EventRefundFeeSet {
+    pub event: Pubkey,
+    pub admin: Pubkey,
+    pub refund_fee_bps: Option<u16>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::PlatformConfig;
+
+/// Pause or unpause the platform
+///
+/// While paused, instructions that move user funds (purchase_ticket,
+/// contribute, transfer_ticket) fail with `ProgramPaused`.
+pub fn handler(
+    ctx: Context<SetPaused>,
+    paused: bool,
+) -> Result<()> {
+    let config = &mut ctx.accounts.platform_config;
+    
+    config.paused = paused;
+    
+    emit!(PauseStatusChanged {
+        config: config.key(),
+        paused,
+        admin: config.admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Platform {}", if paused { "paused" } else { "unpaused" });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    /// Platform config PDA
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = platform_config.bump,
+        has_one = admin @ EventError::UnauthorizedPlatformAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    /// Platform admin
+    pub admin: Signer<'info>,
+}
+
+#[event]
+pub struct PauseStatusChanged {
+    pub config: Pubkey,
+    pub paused: bool,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
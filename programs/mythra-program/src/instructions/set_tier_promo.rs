@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, TierPromo};
+
+#[derive(Accounts)]
+pub struct SetTierPromo<'info> {
+    /// Tier getting the promo (must belong to event)
+    #[account(
+        mut,
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Parent event (authority must match)
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Event authority
+    pub authority: Signer<'info>,
+}
+
+/// Schedule a time-boxed promotional price on a tier
+///
+/// Purchases between `start_ts` (inclusive) and `end_ts` (exclusive) pay
+/// `price_lamports` instead of the tier price; outside the window the
+/// regular price applies again without any further update. Overwrites a
+/// finished or upcoming promo, but not one that is currently running.
+pub fn handler(
+    ctx: Context<SetTierPromo>,
+    price_lamports: u64,
+    start_ts: i64,
+    end_ts: i64,
+) -> Result<()> {
+    let tier = &mut ctx.accounts.tier;
+    let clock = Clock::get()?;
+    
+    tier.set_promo(
+        TierPromo {
+            price_lamports,
+            start_ts,
+            end_ts,
+        },
+        clock.unix_timestamp,
+    )?;
+    
+    // Emit TierPromoSet event
+    emit!(TierPromoSet {
+        event_pubkey: ctx.accounts.event.key(),
+        tier_pubkey: tier.key(),
+        regular_price: tier.price_lamports,
+        promo_price: price_lamports,
+        start_ts,
+        end_ts,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Promo set on tier: {}", tier.key());
+    msg!("Price: {} lamports (regular {})", price_lamports, tier.price_lamports);
+    msg!("Window: {} to {}", start_ts, end_ts);
+    
+    Ok(())
+}
+
+#[event]
+pub struct TierPromoSet {
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub regular_price: u64,
+    pub promo_price: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub timestamp: i64,
+}
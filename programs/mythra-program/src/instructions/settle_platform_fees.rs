@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, PlatformConfig, PlatformFeeVault};
+
+#[derive(Accounts)]
+pub struct SettlePlatformFees<'info> {
+    /// Platform config (the admin settles fees to its treasury)
+    #[account(
+        seeds = [b"config"],
+        bump = platform_config.bump,
+        has_one = admin @ EventError::UnauthorizedPlatformAdmin,
+        has_one = platform_treasury @ EventError::InvalidPlatformTreasury
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    /// Platform admin
+    pub admin: Signer<'info>,
+    
+    /// Event the fees were taken from
+    pub event: Account<'info, Event>,
+    
+    /// Platform fee vault holding the accrued fees
+    /// PDA: ["platform_fees", event.key()]
+    #[account(
+        mut,
+        seeds = [b"platform_fees", event.key().as_ref()],
+        bump = platform_fees.bump
+    )]
+    pub platform_fees: Account<'info, PlatformFeeVault>,
+    
+    /// Platform treasury receiving the settled fees
+    /// CHECK: Must match platform_config.platform_treasury
+    #[account(mut)]
+    pub platform_treasury: UncheckedAccount<'info>,
+}
+
+/// Pay the event's accrued platform fees to the platform treasury
+///
+/// Fees stay in the vault, available for refund clawbacks, until the
+/// event's refund window has closed. Fees accrued by later withdrawals can
+/// be settled again.
+pub fn handler(ctx: Context<SettlePlatformFees>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // Validation: Refunds must no longer be able to claw fees back
+    require!(
+        ctx.accounts.event.platform_fees_settleable(clock.unix_timestamp),
+        EventError::PlatformFeesNotSettleable
+    );
+    
+    let platform_fees = &mut ctx.accounts.platform_fees;
+    let amount = platform_fees.settle(clock.unix_timestamp)?;
+    
+    // The vault is program-owned, so its lamports are moved directly
+    platform_fees.sub_lamports(amount)?;
+    ctx.accounts.platform_treasury.add_lamports(amount)?;
+    
+    // Emit PlatformFeesSettled event
+    emit!(PlatformFeesSettled {
+        event_pubkey: platform_fees.event,
+        platform_treasury: ctx.accounts.platform_treasury.key(),
+        amount,
+        total_settled: platform_fees.settled,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Platform fees settled: {} lamports", amount);
+    msg!("Event: {}", platform_fees.event);
+    msg!("Total settled: {} lamports", platform_fees.settled);
+    
+    Ok(())
+}
+
+#[event]
+pub struct PlatformFeesSettled {
+    pub event_pubkey: Pubkey,
+    pub platform_treasury: Pubkey,
+    pub amount: u64,
+    pub total_settled: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, compute_refund_factor};
+
+#[derive(Accounts)]
+pub struct SettleRefundClaims<'info> {
+    /// Canceled event in shortfall mode
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+    
+    /// Ticket escrow holding the funds available for refunds
+    /// PDA: ["ticket_escrow", event.key()]
+    #[account(
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+}
+
+/// Settle refund claims after the registration window closes
+///
+/// Computes the pro-rata haircut factor from the escrow balance and the
+/// total registered claims. Can be called by anyone once the window ends.
+pub fn handler(ctx: Context<SettleRefundClaims>) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    let clock = Clock::get()?;
+    
+    // Validation: Event must be canceled in shortfall mode
+    require!(
+        event.canceled && event.shortfall_mode,
+        EventError::ShortfallModeNotActive
+    );
+    
+    // Validation: Registration window must have closed
+    require!(
+        clock.unix_timestamp > event.refund_claim_deadline,
+        EventError::RefundClaimWindowOpen
+    );
+    
+    // Validation: Claims not already settled
+    require!(
+        !event.refund_claims_settled,
+        EventError::RefundClaimsAlreadySettled
+    );
+    
+    let rent = Rent::get()?;
+    let rent_exempt_minimum = rent.minimum_balance(0);
+    
+    let available_balance = ctx.accounts.ticket_escrow.lamports()
+        .saturating_sub(rent_exempt_minimum);
+    
+    event.refund_factor = compute_refund_factor(available_balance, event.total_refund_claims);
+    event.refund_claims_settled = true;
+    
+    // Emit RefundClaimsSettled event
+    emit!(RefundClaimsSettled {
+        event_pubkey: event.key(),
+        escrow_available: available_balance,
+        total_refund_claims: event.total_refund_claims,
+        refund_factor: event.refund_factor,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Refund claims settled for event: {}", event.key());
+    msg!("Available: {} lamports", available_balance);
+    msg!("Total claims: {} lamports", event.total_refund_claims);
+    msg!("Refund factor: {}", event.refund_factor);
+    
+    Ok(())
+}
+
+#[event]
+pub struct RefundClaimsSettled {
+    pub event_pubkey: Pubkey,
+    pub escrow_available: u64,
+    pub total_refund_claims: u64,
+    pub refund_factor: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, Ticket, AttendanceList};
+
+#[derive(Accounts)]
+#[instruction(page_index: u32)]
+pub struct SnapshotAttendance<'info> {
+    /// Event whose attendance is being snapshotted
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+    
+    /// Current attendance page (created on demand)
+    /// PDA: ["attendance", event.key(), page_index]
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AttendanceList::LEN,
+        seeds = [b"attendance", event.key().as_ref(), &page_index.to_le_bytes()],
+        bump
+    )]
+    pub attendance_list: Account<'info, AttendanceList>,
+    
+    /// Anyone can crank; pays rent for new pages
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Append owners of used tickets to the event's attendance list
+///
+/// Used Ticket accounts are passed (writable) via remaining_accounts.
+/// Tickets already snapshotted are skipped. Processing stops when the
+/// current page fills; remaining tickets are picked up by the next call
+/// against the following page.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SnapshotAttendance<'info>>,
+    page_index: u32,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    let attendance_list = &mut ctx.accounts.attendance_list;
+    let clock = Clock::get()?;
+    
+    // Validation: Event must have ended
+    require!(
+        clock.unix_timestamp > event.end_ts,
+        EventError::EventNotEnded
+    );
+    
+    // Validation: Pages are filled in order
+    require!(
+        page_index == AttendanceList::current_page(event.attendance_snapshot_count),
+        EventError::InvalidAttendancePage
+    );
+    
+    // Initialize page on first use
+    if attendance_list.event == Pubkey::default() {
+        attendance_list.event = event.key();
+        attendance_list.page_index = page_index;
+        attendance_list.attendees = Vec::new();
+        attendance_list.bump = ctx.bumps.attendance_list;
+    }
+    
+    let mut appended: u32 = 0;
+    let mut skipped: u32 = 0;
+    
+    for account_info in ctx.remaining_accounts.iter() {
+        if attendance_list.is_full() {
+            break;
+        }
+        
+        let mut ticket = Account::<Ticket>::try_from(account_info)?;
+        
+        require!(
+            ticket.event == event.key(),
+            EventError::InvalidEvent
+        );
+        require!(
+            ticket.used,
+            EventError::TicketNotCheckedIn
+        );
+        
+        // Deduplicate across cranks
+        if ticket.snapshotted {
+            skipped += 1;
+            continue;
+        }
+        
+        attendance_list.append(ticket.owner);
+        ticket.snapshotted = true;
+        ticket.exit(&crate::ID)?;
+        
+        appended += 1;
+    }
+    
+    event.attendance_snapshot_count = event.attendance_snapshot_count
+        .checked_add(appended)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    // Emit AttendanceSnapshotted event
+    emit!(AttendanceSnapshotted {
+        event_pubkey: event.key(),
+        page_index,
+        appended,
+        total_attendees: event.attendance_snapshot_count,
+        page_full: attendance_list.is_full(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Attendance snapshot page {}: {} appended, {} skipped", page_index, appended, skipped);
+    msg!("Total attendees snapshotted: {}", event.attendance_snapshot_count);
+    
+    Ok(())
+}
+
+#[event]
+pub struct AttendanceSnapshotted {
+    pub event_pubkey: Pubkey,
+    pub page_index: u32,
+    pub appended: u32,
+    pub total_attendees: u32,
+    pub page_full: bool,
+    pub timestamp: i64,
+}
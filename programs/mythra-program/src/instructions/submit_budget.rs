@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{Campaign, CampaignStatus, Budget, BudgetStatus, Milestone};
+use crate::state::{Campaign, CampaignStatus, Budget, BudgetStatus, Event, Milestone, MilestoneApproval, DisputeStatus, PlatformConfig, VotingModel, UNSET_TIMESTAMP};
 use crate::errors::EventError;
 
 /// Input for milestone creation
@@ -12,18 +12,35 @@ pub struct MilestoneInput {
 
 /// Submit budget for campaign
 /// 
-/// Organizer submits a budget with 3 fixed milestones showing how
+/// Organizer submits a budget with 1 to 8 milestones showing how
 /// they plan to spend crowdfunding money. Voting period is configurable in seconds.
+///
+/// Budgets can't be submitted once the event has ended; at that point the
+/// campaign should move on to calculate_distribution.
+///
+/// With `per_milestone_approval`, approving the budget isn't enough to
+/// release funds: each milestone also needs its own release vote (see
+/// `open_milestone_vote`).
+///
+/// `voting_model` fixes how contributions turn into voting power for the
+/// budget, its revisions and its milestone votes.
+///
+/// The vote's turnout floor comes from the platform config's
+/// `min_budget_turnout_bps` (or the program default) and carries over to
+/// revisions.
 pub fn handler(
     ctx: Context<SubmitBudget>,
     total_amount: u64,
     description: String,
     milestones: Vec<MilestoneInput>,
     voting_period_seconds: i64,
+    per_milestone_approval: bool,
+    voting_model: VotingModel,
 ) -> Result<()> {
-    let campaign = &ctx.accounts.campaign;
+    let campaign = &mut ctx.accounts.campaign;
     let budget = &mut ctx.accounts.budget;
     let clock = Clock::get()?;
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
     
     // Validate campaign is funded
     require!(
@@ -31,6 +48,12 @@ pub fn handler(
         EventError::CampaignNotFunded
     );
     
+    // Validate event hasn't ended (distribution is the path after that)
+    require!(
+        !ctx.accounts.event.has_ended(clock.unix_timestamp),
+        EventError::EventEnded
+    );
+    
     // Validate budget doesn't exceed raised funds
     require!(
         total_amount <= campaign.total_raised,
@@ -43,14 +66,14 @@ pub fn handler(
         EventError::BudgetDescriptionTooLong
     );
     
-    // Validate exactly 3 milestones
+    // Validate milestone count (1 to MAX_MILESTONES)
     require!(
-        milestones.len() == 3,
-        EventError::InvalidMilestonePercentages
+        Budget::valid_milestone_count(milestones.len()),
+        EventError::InvalidMilestoneCount
     );
     
     // Validate milestone percentages sum to 100%
-    let total_percentage: u16 = milestones.iter().map(|m| m.release_percentage).sum();
+    let total_percentage: u32 = milestones.iter().map(|m| m.release_percentage as u32).sum();
     require!(
         total_percentage == 10_000,
         EventError::InvalidMilestonePercentages
@@ -70,24 +93,60 @@ pub fn handler(
     budget.description = description;
     
     // Convert milestone inputs to full milestones
-    for (i, input) in milestones.iter().enumerate() {
-        budget.milestones[i] = Milestone {
+    budget.milestones = milestones
+        .iter()
+        .map(|input| Milestone {
             description: input.description.clone(),
             release_percentage: input.release_percentage,
             unlock_date: input.unlock_date,
             released: false,
             released_amount: 0,
-        };
-    }
+            dispute_status: DisputeStatus::None,
+            approval: MilestoneApproval::None,
+            vote_end: UNSET_TIMESTAMP,
+            votes_for: 0,
+            votes_against: 0,
+            turnout_amount: 0,
+        })
+        .collect();
     
     budget.status = BudgetStatus::Pending;
     budget.voting_end = clock.unix_timestamp + voting_period_seconds;
     budget.votes_for = 0;
     budget.votes_against = 0;
+    budget.votes_abstain = 0;
+    budget.quorum_bps = Budget::DEFAULT_QUORUM_BPS;
     budget.revision_count = 0;
     budget.created_at = clock.unix_timestamp;
+    budget.reminder_emitted = false;
+    budget.votes_cast_count = 0;
+    budget.total_voting_power_cast = 0;
+    budget.finalized_at = UNSET_TIMESTAMP;
+    budget.total_raised_snapshot = campaign.total_raised;
+    budget.snapshot_ts = clock.unix_timestamp;
+    budget.per_milestone_approval = per_milestone_approval;
+    budget.voting_model = voting_model;
+    budget.turnout_amount = 0;
+    budget.min_turnout_bps = PlatformConfig::min_budget_turnout_bps(platform_config.as_ref());
+    budget.expired_resubmissions = 0;
     budget.bump = ctx.bumps.budget;
     
+    // Milestones are released from this budget until a revision replaces it
+    campaign.active_budget = budget.key();
+    
+    emit!(BudgetSubmitted {
+        budget: budget.key(),
+        campaign: campaign.key(),
+        event: campaign.event,
+        total_amount,
+        milestone_count: budget.milestones.len() as u8,
+        revision_count: budget.revision_count,
+        voting_end: budget.voting_end,
+        quorum_bps: budget.quorum_bps,
+        min_turnout_bps: budget.min_turnout_bps,
+        timestamp: clock.unix_timestamp,
+    });
+    
     msg!(
         "Budget submitted: {} lamports, voting ends at {}",
         total_amount,
@@ -98,6 +157,7 @@ pub fn handler(
 }
 
 #[derive(Accounts)]
+#[instruction(total_amount: u64, description: String, milestones: Vec<MilestoneInput>)]
 pub struct SubmitBudget<'info> {
     /// Campaign that was funded
     #[account(
@@ -106,11 +166,20 @@ pub struct SubmitBudget<'info> {
     )]
     pub campaign: Account<'info, Campaign>,
     
+    /// Event the campaign is funding
+    #[account(
+        address = campaign.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
     /// Budget PDA to create
     #[account(
         init,
         payer = organizer,
-        space = Budget::LEN,
+        space = Budget::space(
+            &description,
+            milestones.iter().map(|m| m.description.as_str()),
+        ),
         seeds = [
             b"budget",
             campaign.key().as_ref(),
@@ -119,9 +188,31 @@ pub struct SubmitBudget<'info> {
     )]
     pub budget: Account<'info, Budget>,
     
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
     /// Campaign organizer (signer)
     #[account(mut)]
     pub organizer: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
+
+#[event]
+pub struct BudgetSubmitted {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub total_amount: u64,
+    pub milestone_count: u8,
+    pub revision_count: u8,
+    pub voting_end: i64,
+    pub quorum_bps: u16,
+    pub min_turnout_bps: u16,
+    pub timestamp: i64,
+}
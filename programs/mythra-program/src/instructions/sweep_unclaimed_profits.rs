@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{Campaign, CampaignStatus, PlatformConfig, UnclaimedProfitRecipient};
+use crate::errors::EventError;
+
+/// Sweep backer funds left unclaimed after the claim deadline
+///
+/// Callable once by the organizer or platform admin after
+/// `campaign.claim_deadline`. Whatever backers haven't claimed from the
+/// backer pool (and the loss recovery pool) is paid to the recipient set by
+/// platform policy: the organizer or the platform treasury. Afterwards the
+/// campaign counts as settled and can be closed.
+pub fn handler(ctx: Context<SweepUnclaimedProfits>) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let clock = Clock::get()?;
+    
+    // Validation: Only the organizer or platform admin may sweep
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    require!(
+        campaign.can_trigger_distribution(&ctx.accounts.authority.key(), platform_config.as_ref()),
+        EventError::UnauthorizedCampaignAction
+    );
+    
+    // Validation: Recipient matches the platform policy
+    let recipient_kind = PlatformConfig::unclaimed_profit_recipient(platform_config.as_ref());
+    match recipient_kind {
+        UnclaimedProfitRecipient::Organizer => require_keys_eq!(
+            ctx.accounts.recipient.key(),
+            campaign.organizer,
+            EventError::InvalidSweepRecipient
+        ),
+        UnclaimedProfitRecipient::PlatformTreasury => PlatformConfig::require_platform_treasury(
+            platform_config.as_ref(),
+            &ctx.accounts.recipient.key(),
+        )?,
+    }
+    
+    // Close the backer pools (fails before the deadline or on a second sweep)
+    let unclaimed = campaign.sweep_unclaimed(clock.unix_timestamp)?;
+    
+    // Never dip into the escrow's rent reserve
+    let amount = unclaimed.min(Campaign::escrow_available(
+        ctx.accounts.campaign_escrow.lamports(),
+        Rent::get()?.minimum_balance(0),
+    ));
+    
+    if amount > 0 {
+        let campaign_key = campaign.key();
+        let seeds = &[
+            b"campaign_escrow",
+            campaign_key.as_ref(),
+            &[campaign.escrow_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.campaign_escrow.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        transfer(transfer_ctx, amount)?;
+    }
+    
+    msg!("Unclaimed backer funds swept: {} lamports", amount);
+    msg!("Recipient: {}", ctx.accounts.recipient.key());
+    
+    emit!(UnclaimedProfitsSwept {
+        campaign: campaign.key(),
+        event: campaign.event,
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        recipient_kind,
+        amount,
+        backer_paid_out: campaign.backer_paid_out,
+        claim_deadline: campaign.claim_deadline,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedProfits<'info> {
+    /// Completed campaign whose claim deadline has passed
+    #[account(
+        mut,
+        constraint = campaign.status == CampaignStatus::Completed @ EventError::InvalidCampaignStatus
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Campaign escrow PDA (holds the unclaimed funds)
+    #[account(
+        mut,
+        seeds = [b"campaign_escrow", campaign.key().as_ref()],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Organizer or platform treasury, per platform policy
+    /// CHECK: Validated in handler against the policy in platform_config
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Organizer or platform admin
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct UnclaimedProfitsSwept {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub recipient_kind: UnclaimedProfitRecipient,
+    pub amount: u64,
+    pub backer_paid_out: u64,
+    pub claim_deadline: i64,
+    pub timestamp: i64,
+}
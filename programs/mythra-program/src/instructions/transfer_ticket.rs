@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::token_interface::{TokenInterface, TokenAccount, transfer_checked, TransferChecked, Mint};
 use crate::errors::EventError;
-use crate::state::{Event, TicketTier, Ticket};
+use crate::state::{Event, TicketTier, Ticket, TicketIndex, PlatformConfig, BlocklistEntry};
 
 #[derive(Accounts)]
 pub struct TransferTicket<'info> {
@@ -24,6 +24,7 @@ pub struct TransferTicket<'info> {
     /// Tier account (for resale validation)
     #[account(
         constraint = tier.key() == ticket.tier @ EventError::UnauthorizedRefund,
+        constraint = tier.transferable @ EventError::TicketNotTransferable,
         constraint = tier.resale_enabled @ EventError::ResaleDisabled
     )]
     pub tier: Account<'info, TicketTier>,
@@ -59,11 +60,54 @@ pub struct TransferTicket<'info> {
     /// CHECK: This is the new owner
     pub recipient: AccountInfo<'info>,
     
-    /// Platform treasury for royalty payment
-    /// CHECK: This is the platform treasury
+    /// Blocklist entry PDA for the recipient (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
+    /// Sender's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        mut,
+        seeds = [b"ticket_index", event.key().as_ref(), sender.key().as_ref()],
+        bump = sender_ticket_index.bump
+    )]
+    pub sender_ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Recipient's ticket index (required when event.maintain_ticket_index)
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = TicketIndex::LEN,
+        seeds = [b"ticket_index", event.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_ticket_index: Option<Box<Account<'info, TicketIndex>>>,
+    
+    /// Platform treasury for the platform share of royalties
+    /// CHECK: Validated against platform_config.platform_treasury when a platform royalty is paid
+    /// (never without a config)
     #[account(mut)]
     pub platform_treasury: AccountInfo<'info>,
     
+    /// Event organizer treasury for the organizer share of royalties
+    /// CHECK: Must be the event's treasury
+    #[account(
+        mut,
+        address = event.treasury @ EventError::InvalidTreasuryAccount
+    )]
+    pub organizer_treasury: AccountInfo<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
 }
@@ -77,6 +121,13 @@ pub fn handler(
     let event = &ctx.accounts.event;
     let clock = Clock::get()?;
     
+    // Validation: Platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // Validation: Pre-registered stock has no owner to transfer from
+    ticket.require_active()?;
+    
     // Validation: Ticket must not be used
     require!(
         !ticket.used,
@@ -89,7 +140,22 @@ pub fn handler(
         EventError::AlreadyRefunded
     );
     
+    // Validation: Recipient must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &event.key(),
+        &ctx.accounts.recipient.key(),
+    )?;
+    
+    // Validation: Reported sale price must be within the tier's ceiling
+    if let Some(price) = sale_price {
+        tier.require_resale_price_allowed(price)?;
+    }
+    
     // Calculate and transfer royalty if sale price is provided
+    let mut platform_royalty: u64 = 0;
+    let mut organizer_royalty: u64 = 0;
+    
     if let Some(price) = sale_price {
         if tier.royalty_bps > 0 && price > 0 {
             let royalty_amount = price
@@ -98,8 +164,24 @@ pub fn handler(
                 .checked_div(10000)
                 .ok_or(EventError::InvalidPrice)?;
             
-            if royalty_amount > 0 {
-                // Transfer royalty to platform treasury
+            // Split between platform and organizer (dust to organizer). With
+            // no platform config there is no treasury to pay, so the
+            // organizer takes the whole royalty
+            if platform_config.is_some() {
+                (platform_royalty, organizer_royalty) = event.split_platform_share(royalty_amount)
+                    .ok_or(EventError::ArithmeticOverflow)?;
+            } else {
+                organizer_royalty = royalty_amount;
+            }
+            
+            if platform_royalty > 0 {
+                // Only pay royalties to the configured platform treasury
+                PlatformConfig::require_platform_treasury(
+                    platform_config.as_ref(),
+                    &ctx.accounts.platform_treasury.key(),
+                )?;
+                
+                // Transfer platform share to platform treasury
                 let transfer_ctx = CpiContext::new(
                     ctx.accounts.system_program.to_account_info(),
                     Transfer {
@@ -108,10 +190,27 @@ pub fn handler(
                     },
                 );
                 
-                transfer(transfer_ctx, royalty_amount)?;
+                transfer(transfer_ctx, platform_royalty)?;
+            }
+            
+            if organizer_royalty > 0 {
+                // Transfer organizer share to event treasury
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.sender.to_account_info(),
+                        to: ctx.accounts.organizer_treasury.to_account_info(),
+                    },
+                );
                 
-                msg!("Royalty transferred: {} lamports", royalty_amount);
+                transfer(transfer_ctx, organizer_royalty)?;
             }
+            
+            msg!(
+                "Royalty transferred: {} lamports to platform, {} lamports to organizer",
+                platform_royalty,
+                organizer_royalty
+            );
         }
     }
     
@@ -128,9 +227,28 @@ pub fn handler(
     
     transfer_checked(transfer_ctx, 1, ctx.accounts.mint.decimals)?;
     
-    // Update ticket owner
+    // Update ticket owner and record the sale so repeat flips can be audited
     let old_owner = ticket.owner;
     ticket.owner = ctx.accounts.recipient.key();
+    ticket.last_sale_price = sale_price.unwrap_or(0);
+    ticket.last_sale_royalty = platform_royalty + organizer_royalty;
+    ticket.checkin_delegate = Pubkey::default();
+    
+    // Move the ticket between wallet indexes if the event maintains them
+    if event.maintain_ticket_index {
+        let sender_ticket_index = ctx.accounts.sender_ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        sender_ticket_index.remove(&ticket.key());
+        
+        let recipient_ticket_index = ctx.accounts.recipient_ticket_index.as_mut()
+            .ok_or(EventError::TicketIndexRequired)?;
+        recipient_ticket_index.init_if_new(
+            event.key(),
+            ctx.accounts.recipient.key(),
+            ctx.bumps.recipient_ticket_index.ok_or(EventError::TicketIndexRequired)?,
+        );
+        recipient_ticket_index.add(ticket.key());
+    }
     
     // Emit TicketTransferred event
     emit!(TicketTransferred {
@@ -142,6 +260,9 @@ pub fn handler(
         to_owner: ticket.owner,
         sale_price,
         royalty_bps: tier.royalty_bps,
+        platform_royalty,
+        organizer_royalty,
+        is_gift: false,
         timestamp: clock.unix_timestamp,
     });
     
@@ -166,5 +287,8 @@ pub struct TicketTransferred {
     pub to_owner: Pubkey,
     pub sale_price: Option<u64>,
     pub royalty_bps: u16,
+    pub platform_royalty: u64,
+    pub organizer_royalty: u64,
+    pub is_gift: bool,
     pub timestamp: i64,
 }
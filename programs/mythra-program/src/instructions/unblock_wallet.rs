@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, BlocklistEntry};
+
+#[derive(Accounts)]
+pub struct UnblockWallet<'info> {
+    /// Event the wallet was blocked from
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Blocklist entry to be closed
+    #[account(
+        mut,
+        seeds = [b"blocklist", event.key().as_ref(), blocklist_entry.wallet.as_ref()],
+        bump = blocklist_entry.bump,
+        close = authority
+    )]
+    pub blocklist_entry: Account<'info, BlocklistEntry>,
+    
+    /// Event authority - receives reclaimed rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UnblockWallet>) -> Result<()> {
+    let blocklist_entry = &ctx.accounts.blocklist_entry;
+    
+    // Emit WalletUnblocked event
+    emit!(WalletUnblocked {
+        event_pubkey: blocklist_entry.event,
+        wallet: blocklist_entry.wallet,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Wallet {} unblocked", blocklist_entry.wallet);
+    
+    Ok(())
+}
+
+#[event]
+pub struct WalletUnblocked {
+    pub event_pubkey: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
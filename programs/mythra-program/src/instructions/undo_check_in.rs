@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, Ticket};
+
+#[derive(Accounts)]
+pub struct UndoCheckIn<'info> {
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.event == event.key() @ EventError::InvalidEvent
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event the ticket belongs to
+    #[account(
+        mut,
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Event authority
+    pub authority: Signer<'info>,
+}
+
+/// Revert a mistaken check-in (wrong ticket scanned, double-fired scanner)
+///
+/// Only the event authority may revert, and only until the event ends.
+pub fn handler(ctx: Context<UndoCheckIn>) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let clock = Clock::get()?;
+    
+    // Validation: Event must not have ended
+    require!(
+        clock.unix_timestamp < ctx.accounts.event.end_ts,
+        EventError::EventEnded
+    );
+    
+    let checked_in_ts = ticket.checked_in_ts;
+    let gate_operator = ticket.undo_check_in()?;
+    ctx.accounts.event.record_check_in_reverted(ticket.use_count == 0);
+    
+    // Emit CheckInReverted event
+    emit!(CheckInReverted {
+        ticket_pubkey: ticket.key(),
+        owner: ticket.owner,
+        event: ticket.event,
+        gate_operator,
+        checked_in_ts,
+        reverted_by: ctx.accounts.authority.key(),
+        tickets_checked_in: ctx.accounts.event.tickets_checked_in,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Check-in reverted for ticket: {}", ticket.key());
+    msg!("Original gate operator: {}", gate_operator);
+    
+    Ok(())
+}
+
+#[event]
+pub struct CheckInReverted {
+    pub ticket_pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub event: Pubkey,
+    pub gate_operator: Pubkey,
+    pub checked_in_ts: i64,
+    pub reverted_by: Pubkey,
+    pub tickets_checked_in: u32,
+    pub timestamp: i64,
+}
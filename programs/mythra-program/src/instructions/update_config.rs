@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{PlatformConfig, UnclaimedProfitRecipient};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateConfigParams {
+    pub admin: Option<Pubkey>,
+    pub platform_treasury: Option<Pubkey>,
+    pub default_platform_fee_bps: Option<u16>,
+    pub min_platform_share_bps: Option<u16>,
+    pub min_backer_share_bps: Option<u16>,
+    pub distribution_timeout_seconds: Option<i64>,
+    pub max_relayer_fee_lamports: Option<u64>,
+    pub distribution_grace_period_seconds: Option<i64>,
+    pub backer_claim_window_seconds: Option<i64>,
+    pub unclaimed_profit_recipient: Option<UnclaimedProfitRecipient>,
+    pub refund_fee_bps: Option<u16>,
+    pub min_budget_turnout_bps: Option<u16>,
+}
+
+/// Update the platform config
+///
+/// Only the platform admin can update the config. Fields left as `None`
+/// keep their current value.
+pub fn handler(
+    ctx: Context<UpdateConfig>,
+    params: UpdateConfigParams,
+) -> Result<()> {
+    let config = &mut ctx.accounts.platform_config;
+    
+    let mut updated_fields = Vec::new();
+    
+    if let Some(admin) = params.admin {
+        config.admin = admin;
+        updated_fields.push("admin");
+    }
+    
+    if let Some(platform_treasury) = params.platform_treasury {
+        config.platform_treasury = platform_treasury;
+        updated_fields.push("platform_treasury");
+    }
+    
+    if let Some(default_platform_fee_bps) = params.default_platform_fee_bps {
+        require!(
+            default_platform_fee_bps <= 10_000,
+            EventError::InvalidPlatformSplit
+        );
+        config.default_platform_fee_bps = default_platform_fee_bps;
+        updated_fields.push("default_platform_fee_bps");
+    }
+    
+    if let Some(min_platform_share_bps) = params.min_platform_share_bps {
+        config.min_platform_share_bps = min_platform_share_bps;
+        updated_fields.push("min_platform_share_bps");
+    }
+    
+    if let Some(min_backer_share_bps) = params.min_backer_share_bps {
+        config.min_backer_share_bps = min_backer_share_bps;
+        updated_fields.push("min_backer_share_bps");
+    }
+    
+    // Validate minimums still leave room for a valid split
+    require!(
+        config.min_platform_share_bps as u32 + config.min_backer_share_bps as u32 <= 10_000,
+        EventError::InvalidProfitSplit
+    );
+    
+    if let Some(distribution_timeout_seconds) = params.distribution_timeout_seconds {
+        require!(
+            distribution_timeout_seconds >= 0,
+            EventError::InvalidTimestamps
+        );
+        config.distribution_timeout_seconds = distribution_timeout_seconds;
+        updated_fields.push("distribution_timeout_seconds");
+    }
+    
+    if let Some(distribution_grace_period_seconds) = params.distribution_grace_period_seconds {
+        require!(
+            distribution_grace_period_seconds >= 0,
+            EventError::InvalidTimestamps
+        );
+        config.distribution_grace_period_seconds = distribution_grace_period_seconds;
+        updated_fields.push("distribution_grace_period_seconds");
+    }
+    
+    // Validate the grace period ends before the permissionless fallback opens
+    require!(
+        config.distribution_grace_period_seconds <= config.distribution_timeout_seconds,
+        EventError::InvalidTimestamps
+    );
+    
+    if let Some(backer_claim_window_seconds) = params.backer_claim_window_seconds {
+        require!(
+            backer_claim_window_seconds >= 0,
+            EventError::InvalidTimestamps
+        );
+        config.backer_claim_window_seconds = backer_claim_window_seconds;
+        updated_fields.push("backer_claim_window_seconds");
+    }
+    
+    if let Some(unclaimed_profit_recipient) = params.unclaimed_profit_recipient {
+        config.unclaimed_profit_recipient = unclaimed_profit_recipient;
+        updated_fields.push("unclaimed_profit_recipient");
+    }
+    
+    if let Some(max_relayer_fee_lamports) = params.max_relayer_fee_lamports {
+        config.max_relayer_fee_lamports = max_relayer_fee_lamports;
+        updated_fields.push("max_relayer_fee_lamports");
+    }
+    
+    if let Some(refund_fee_bps) = params.refund_fee_bps {
+        require!(
+            refund_fee_bps <= 10_000,
+            EventError::InvalidRefundFee
+        );
+        config.refund_fee_bps = refund_fee_bps;
+        updated_fields.push("refund_fee_bps");
+    }
+    
+    if let Some(min_budget_turnout_bps) = params.min_budget_turnout_bps {
+        require!(
+            min_budget_turnout_bps <= 10_000,
+            EventError::InvalidMinTurnout
+        );
+        config.min_budget_turnout_bps = min_budget_turnout_bps;
+        updated_fields.push("min_budget_turnout_bps");
+    }
+    
+    emit!(PlatformConfigUpdated {
+        config: config.key(),
+        admin: config.admin,
+        updated_fields: updated_fields.join(", "),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Platform config updated: {}", updated_fields.join(", "));
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// Platform config PDA
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = platform_config.bump,
+        has_one = admin @ EventError::UnauthorizedPlatformAdmin
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    /// Current platform admin
+    pub admin: Signer<'info>,
+}
+
+#[event]
+pub struct PlatformConfigUpdated {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    pub updated_fields: String,
+    pub timestamp: i64,
+}
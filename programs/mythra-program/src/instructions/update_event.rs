@@ -1,14 +1,20 @@
 use anchor_lang::prelude::*;
 use crate::errors::EventError;
-use crate::state::Event;
+use crate::state::{Event, EventDelegate, RefundPolicy};
 
 #[derive(Accounts)]
 #[instruction(params: UpdateEventParams)]
 pub struct UpdateEvent<'info> {
     #[account(
         mut,
-        has_one = authority @ EventError::UnauthorizedUpdate,
-        realloc = Event::space(
+        constraint = EventDelegate::authorizes(
+            &event.authority,
+            &authority.key(),
+            event_delegate.as_deref(),
+            EventDelegate::PERMISSION_UPDATE,
+        ) @ EventError::UnauthorizedUpdate,
+        realloc = Event::realloc_space(
+            event.to_account_info().data_len(),
             params.metadata_uri.as_ref()
                 .map(|uri| uri.len())
                 .unwrap_or(event.metadata_uri.len())
@@ -18,9 +24,30 @@ pub struct UpdateEvent<'info> {
     )]
     pub event: Account<'info, Event>,
     
+    /// Delegate entry when the signer is not the event authority
+    /// PDA: ["delegate", event.key(), authority.key()]
+    #[account(
+        seeds = [b"delegate", event.key().as_ref(), authority.key().as_ref()],
+        bump = event_delegate.bump
+    )]
+    pub event_delegate: Option<Account<'info, EventDelegate>>,
+    
+    /// Event authority, or a delegate with the update permission
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    /// CHECK: New treasury account, required when `params.treasury` is set
+    /// Must be a wallet, not an escrow PDA or a program
+    #[account(
+        constraint = Event::is_valid_treasury(
+            &event.key(),
+            &new_treasury.key(),
+            new_treasury.owner,
+            new_treasury.executable,
+        ) @ EventError::InvalidTreasuryAccount
+    )]
+    pub new_treasury: Option<UncheckedAccount<'info>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -28,8 +55,17 @@ pub struct UpdateEvent<'info> {
 pub struct UpdateEventParams {
     pub metadata_uri: Option<String>,
     pub end_ts: Option<i64>,
+    pub start_ts: Option<i64>,
     pub platform_split_bps: Option<u16>,
     pub treasury: Option<Pubkey>,
+    pub maintain_ticket_index: Option<bool>,
+    pub allow_sales_during_event: Option<bool>,
+    pub checkin_message_version: Option<u8>,
+    pub backer_presale_seconds: Option<u32>,
+    pub backer_presale_min_contribution: Option<u64>,
+    pub locales: Option<Vec<[u8; 2]>>,
+    pub compact_events: Option<bool>,
+    pub refund_policy: Option<RefundPolicy>,
 }
 
 pub fn handler(
@@ -57,10 +93,33 @@ pub fn handler(
             end_ts > clock.unix_timestamp,
             EventError::EndTimestampInPast
         );
+        require!(
+            event.can_change_end_ts(end_ts),
+            EventError::CannotShortenLiveEvent
+        );
         event.end_ts = end_ts;
         updated_fields.push("end_ts");
     }
     
+    // Postpone start_ts if provided (checked against the updated end_ts)
+    if let Some(start_ts) = params.start_ts {
+        let old_start_ts = event.postpone_start(start_ts, clock.unix_timestamp)?;
+        updated_fields.push("start_ts");
+        
+        if start_ts != old_start_ts {
+            emit!(EventPostponed {
+                event_pubkey: event.key(),
+                authority: event.authority,
+                old_start_ts,
+                new_start_ts: start_ts,
+                postponed_count: event.postponed_count,
+                refund_window_end: clock.unix_timestamp
+                    .saturating_add(Event::POSTPONEMENT_REFUND_WINDOW_SECONDS),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+    }
+    
     // Update platform_split_bps if provided
     if let Some(platform_split_bps) = params.platform_split_bps {
         require!(
@@ -73,10 +132,88 @@ pub fn handler(
     
     // Update treasury if provided
     if let Some(treasury) = params.treasury {
+        // Redirecting withdrawals stays with the authority, not delegates
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            event.authority,
+            EventError::UnauthorizedUpdate
+        );
+        
+        // The validated treasury account must be supplied alongside the key
+        let new_treasury = ctx.accounts.new_treasury
+            .as_ref()
+            .ok_or(EventError::InvalidTreasuryAccount)?;
+        require_keys_eq!(
+            new_treasury.key(),
+            treasury,
+            EventError::InvalidTreasuryAccount
+        );
         event.treasury = treasury;
         updated_fields.push("treasury");
     }
     
+    // Update ticket index flag if provided
+    if let Some(maintain_ticket_index) = params.maintain_ticket_index {
+        event.maintain_ticket_index = maintain_ticket_index;
+        updated_fields.push("maintain_ticket_index");
+    }
+    
+    // Update door sales flag if provided
+    if let Some(allow_sales_during_event) = params.allow_sales_during_event {
+        event.allow_sales_during_event = allow_sales_during_event;
+        updated_fields.push("allow_sales_during_event");
+    }
+    
+    // Bump check-in message version if provided (before start only)
+    if let Some(checkin_message_version) = params.checkin_message_version {
+        event.validate_checkin_version_bump(checkin_message_version, clock.unix_timestamp)?;
+        event.checkin_message_version = checkin_message_version;
+        updated_fields.push("checkin_message_version");
+    }
+    
+    // Update backer presale window if provided
+    if let Some(backer_presale_seconds) = params.backer_presale_seconds {
+        event.backer_presale_seconds = backer_presale_seconds;
+        updated_fields.push("backer_presale_seconds");
+    }
+    
+    // Update backer presale contribution floor if provided
+    if let Some(backer_presale_min_contribution) = params.backer_presale_min_contribution {
+        event.backer_presale_min_contribution = backer_presale_min_contribution;
+        updated_fields.push("backer_presale_min_contribution");
+    }
+    
+    // Replace metadata locales if provided
+    if let Some(locales) = &params.locales {
+        Event::validate_locales(locales)?;
+        event.locales = locales.clone();
+        updated_fields.push("locales");
+    }
+    
+    // Update compact check-in events flag if provided
+    if let Some(compact_events) = params.compact_events {
+        event.compact_events = compact_events;
+        updated_fields.push("compact_events");
+    }
+    
+    // Replace the refund schedule if provided (only before the first sale,
+    // so buyers get the terms they bought under)
+    if let Some(refund_policy) = &params.refund_policy {
+        require!(
+            event.tickets_sold == 0 && event.tickets_refunded == 0,
+            EventError::RefundPolicyLocked
+        );
+        refund_policy.validate()?;
+        event.refund_policy = refund_policy.clone();
+        updated_fields.push("refund_policy");
+    }
+    
+    // Every field must still serialize within the (never shrinking) account
+    require!(
+        event.fits_in(event.to_account_info().data_len()),
+        EventError::EventAccountTooSmall
+    );
+    
     // Emit EventUpdated event
     emit!(EventUpdated {
         event_pubkey: event.key(),
@@ -106,3 +243,14 @@ pub struct EventUpdated {
     pub treasury: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct EventPostponed {
+    pub event_pubkey: Pubkey,
+    pub authority: Pubkey,
+    pub old_start_ts: i64,
+    pub new_start_ts: i64,
+    pub postponed_count: u8,
+    pub refund_window_end: i64,
+    pub timestamp: i64,
+}
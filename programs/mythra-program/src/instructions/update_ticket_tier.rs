@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier};
+
+#[derive(Accounts)]
+pub struct UpdateTicketTier<'info> {
+    /// Tier to update (must belong to event)
+    #[account(
+        mut,
+        constraint = tier.event == event.key() @ EventError::UnauthorizedTierCreation
+    )]
+    pub tier: Account<'info, TicketTier>,
+    
+    /// Parent event (authority must match)
+    #[account(
+        has_one = authority @ EventError::UnauthorizedUpdate
+    )]
+    pub event: Account<'info, Event>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateTicketTierParams {
+    pub sale_start_ts: Option<i64>,
+    pub sale_end_ts: Option<i64>,
+    pub max_uses: Option<u16>,
+    /// Set (`Some(Some(root))`) or clear (`Some(None)`) the presale allowlist
+    pub allowlist_root: Option<Option<[u8; 32]>>,
+    /// Resale price ceiling (0 = uncapped)
+    pub max_resale_price_lamports: Option<u64>,
+    /// Reserved seating: purchases are assigned seats in order
+    pub seated: Option<bool>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateTicketTier>,
+    params: UpdateTicketTierParams,
+) -> Result<()> {
+    let tier = &mut ctx.accounts.tier;
+    let event = &ctx.accounts.event;
+    
+    let mut updated_fields = Vec::new();
+    
+    // Update sale window if provided (only before any sales)
+    if params.sale_start_ts.is_some() || params.sale_end_ts.is_some() {
+        require!(
+            tier.current_supply == 0,
+            EventError::TierHasSales
+        );
+        
+        let sale_start_ts = params.sale_start_ts.unwrap_or(tier.sale_start_ts);
+        let sale_end_ts = params.sale_end_ts.unwrap_or(tier.sale_end_ts);
+        TicketTier::validate_sale_window(sale_start_ts, sale_end_ts, event.start_ts)?;
+        
+        tier.sale_start_ts = sale_start_ts;
+        tier.sale_end_ts = sale_end_ts;
+        updated_fields.push("sale_window");
+    }
+    
+    // Update check-ins per ticket if provided (only before any sales)
+    if let Some(max_uses) = params.max_uses {
+        require!(
+            tier.current_supply == 0,
+            EventError::TierHasSales
+        );
+        require!(
+            max_uses > 0,
+            EventError::InvalidMaxUses
+        );
+        
+        tier.max_uses = max_uses;
+        updated_fields.push("max_uses");
+    }
+    
+    // Update presale allowlist if provided (any time; only purchases before
+    // sale_start_ts check it)
+    if let Some(allowlist_root) = params.allowlist_root {
+        tier.allowlist_root = allowlist_root;
+        updated_fields.push("allowlist_root");
+    }
+    
+    // Update resale price ceiling if provided (applies to later resales)
+    if let Some(max_resale_price_lamports) = params.max_resale_price_lamports {
+        tier.max_resale_price_lamports = max_resale_price_lamports;
+        updated_fields.push("max_resale_price_lamports");
+    }
+    
+    // Update reserved seating if provided (only before any sales)
+    if let Some(seated) = params.seated {
+        require!(
+            tier.current_supply == 0,
+            EventError::TierHasSales
+        );
+        
+        tier.seated = seated;
+        updated_fields.push("seated");
+    }
+    
+    // Emit TicketTierUpdated event
+    emit!(TicketTierUpdated {
+        event_pubkey: event.key(),
+        tier_pubkey: tier.key(),
+        updated_fields: updated_fields.join(", "),
+        sale_start_ts: tier.sale_start_ts,
+        sale_end_ts: tier.sale_end_ts,
+        max_uses: tier.max_uses,
+        allowlist_root: tier.allowlist_root,
+        max_resale_price_lamports: tier.max_resale_price_lamports,
+        seated: tier.seated,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Tier updated: {}", tier.key());
+    msg!("Updated fields: {}", updated_fields.join(", "));
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketTierUpdated {
+    pub event_pubkey: Pubkey,
+    pub tier_pubkey: Pubkey,
+    pub updated_fields: String,
+    pub sale_start_ts: i64,
+    pub sale_end_ts: i64,
+    pub max_uses: u16,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub max_resale_price_lamports: u64,
+    pub seated: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::EventError;
+use crate::state::{Event, TicketTier, Ticket, PlatformConfig, BlocklistEntry};
+
+#[derive(Accounts)]
+pub struct UpgradeTicket<'info> {
+    /// Ticket being moved to the target tier
+    #[account(
+        mut,
+        seeds = [b"ticket", ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.owner == owner.key() @ EventError::InvalidOwner
+    )]
+    pub ticket: Account<'info, Ticket>,
+    
+    /// Event the ticket belongs to (revenue grows by the price difference)
+    #[account(
+        mut,
+        constraint = event.key() == ticket.event @ EventError::InvalidEvent
+    )]
+    pub event: Account<'info, Event>,
+    
+    /// Tier the ticket currently belongs to (seat is returned to supply)
+    #[account(
+        mut,
+        constraint = current_tier.key() == ticket.tier @ EventError::InvalidTier
+    )]
+    pub current_tier: Account<'info, TicketTier>,
+    
+    /// Higher-priced tier on the same event taking the ticket
+    #[account(
+        mut,
+        constraint = target_tier.event == event.key() @ EventError::InvalidTier,
+        constraint = target_tier.key() != current_tier.key() @ EventError::InvalidTierUpgrade
+    )]
+    pub target_tier: Account<'info, TicketTier>,
+    
+    /// Escrow receiving the price difference
+    #[account(
+        mut,
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
+    )]
+    pub ticket_escrow: SystemAccount<'info>,
+    
+    /// Platform config PDA (may not be initialized yet)
+    /// CHECK: Deserialized in handler when initialized, program defaults apply otherwise
+    #[account(
+        seeds = [b"config"],
+        bump
+    )]
+    pub platform_config: UncheckedAccount<'info>,
+    
+    /// Blocklist entry PDA for the owner (uninitialized unless blocked)
+    /// CHECK: Address checked by seeds, entry checked in the handler
+    #[account(
+        seeds = [b"blocklist", event.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+    
+    /// Ticket owner paying the difference
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Move a ticket to a higher-priced tier on the same event
+///
+/// The owner pays the difference between the two tier prices into the
+/// ticket escrow. The seat goes back to the current tier and one is taken
+/// from the target tier, whose sale window must be open. The ticket keeps
+/// its NFT; `price_paid` grows by the difference so refunds return
+/// everything paid.
+pub fn handler(ctx: Context<UpgradeTicket>) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let event = &mut ctx.accounts.event;
+    let current_tier = &mut ctx.accounts.current_tier;
+    let target_tier = &mut ctx.accounts.target_tier;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Validation: platform must not be paused
+    let platform_config = PlatformConfig::load_optional(&ctx.accounts.platform_config)?;
+    PlatformConfig::require_not_paused(platform_config.as_ref())?;
+    
+    // Validation: owner must not be blocked from the event
+    BlocklistEntry::require_not_blocked(
+        &ctx.accounts.blocklist_entry,
+        &event.key(),
+        &ctx.accounts.owner.key(),
+    )?;
+    
+    // Validation: only sold, unused and unrefunded tickets move
+    ticket.require_active()?;
+    require!(
+        !ticket.used,
+        EventError::TicketAlreadyUsed
+    );
+    require!(
+        !ticket.refunded,
+        EventError::AlreadyRefunded
+    );
+    
+    // Validation: sales must be open, and upgrades stop at the start even
+    // for events with door sales
+    event.require_sales_open(now)?;
+    require!(
+        now < event.start_ts,
+        EventError::EventAlreadyStarted
+    );
+    
+    // Validation: target tier is on sale, has a seat and costs more
+    target_tier.require_sale_open(now)?;
+    let price_difference = target_tier.upgrade_price_from(current_tier)?;
+    target_tier.reserve_seats(1)?;
+    
+    // Collect the difference into the ticket escrow
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.ticket_escrow.to_account_info(),
+            },
+        ),
+        price_difference,
+    )?;
+    
+    current_tier.release_seat()
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    ticket.tier = target_tier.key();
+    ticket.price_paid = ticket.price_paid
+        .checked_add(price_difference)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    event.ticket_revenue = event.ticket_revenue
+        .checked_add(price_difference)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    emit!(TicketUpgraded {
+        ticket: ticket.key(),
+        event: event.key(),
+        owner: ticket.owner,
+        from_tier: current_tier.key(),
+        to_tier: target_tier.key(),
+        price_difference,
+        price_paid: ticket.price_paid,
+        timestamp: now,
+    });
+    
+    msg!("Ticket upgraded: {}", ticket.key());
+    msg!("Tier: {} -> {}", current_tier.key(), target_tier.key());
+    msg!("Price difference: {} lamports", price_difference);
+    
+    Ok(())
+}
+
+#[event]
+pub struct TicketUpgraded {
+    pub ticket: Pubkey,
+    pub event: Pubkey,
+    pub owner: Pubkey,
+    pub from_tier: Pubkey,
+    pub to_tier: Pubkey,
+    pub price_difference: u64,
+    pub price_paid: u64,
+    pub timestamp: i64,
+}
@@ -1,15 +1,18 @@
 use anchor_lang::prelude::*;
-use crate::state::{Budget, BudgetStatus, BudgetVote, Contribution, Campaign};
+use crate::state::{Budget, BudgetStatus, BudgetVote, Contribution, Campaign, VoteChoice};
 use crate::errors::EventError;
 
 /// Vote on a budget proposal
 /// 
-/// Backers can vote to approve or reject a budget. Voting power
-/// is equal to contribution amount (linear voting for MVP).
-/// Voting period is time-limited (3 days).
+/// Backers can vote to approve or reject a budget, or abstain to count
+/// toward quorum without taking a side. Voting power comes from the
+/// contribution amount as of the budget's `snapshot_ts` (so money added once
+/// voting opened doesn't count) under the budget's voting model: the amount
+/// itself, or its square root for quadratic budgets. Voting period is
+/// time-limited (3 days).
 pub fn handler(
     ctx: Context<VoteOnBudget>,
-    approve: bool,
+    choice: VoteChoice,
 ) -> Result<()> {
     let budget = &mut ctx.accounts.budget;
     let contribution = &ctx.accounts.contribution;
@@ -34,30 +37,51 @@ pub fn handler(
         EventError::NotAContributor
     );
     
+    // Validate voter had backed the campaign when the budget was submitted
+    let snapshot_amount = contribution.amount_at(budget.snapshot_ts);
+    require!(
+        snapshot_amount > 0,
+        EventError::NoVotingPowerAtSnapshot
+    );
+    
     // Record vote
     vote.budget = budget.key();
+    vote.revision = budget.revision_count;
     vote.voter = ctx.accounts.voter.key();
-    vote.contribution_amount = contribution.amount;
-    vote.approve = approve;
+    vote.contribution_amount = snapshot_amount;
+    vote.voting_power = budget.voting_model.voting_power(snapshot_amount);
+    vote.choice = choice;
     vote.voted_at = clock.unix_timestamp;
     vote.bump = ctx.bumps.vote;
     
     // Update budget vote tallies
     let voting_power = vote.voting_power();
-    if approve {
-        budget.votes_for += voting_power;
-        msg!("Vote YES: {} lamports voting power", voting_power);
-    } else {
-        budget.votes_against += voting_power;
-        msg!("Vote NO: {} lamports voting power", voting_power);
-    }
+    budget.add_vote(voting_power, vote.contribution_amount, choice)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    msg!("Vote {:?}: {} lamports voting power", choice, voting_power);
     
     msg!(
-        "Current tally: {} FOR, {} AGAINST",
+        "Current tally: {} FOR, {} AGAINST, {} ABSTAIN",
         budget.votes_for,
-        budget.votes_against
+        budget.votes_against,
+        budget.votes_abstain
     );
     
+    emit!(BudgetVoteCast {
+        budget: budget.key(),
+        campaign: ctx.accounts.campaign.key(),
+        event: ctx.accounts.campaign.event,
+        voter: vote.voter,
+        choice,
+        voting_power,
+        votes_for: budget.votes_for,
+        votes_against: budget.votes_against,
+        votes_abstain: budget.votes_abstain,
+        votes_cast_count: budget.votes_cast_count,
+        total_voting_power_cast: budget.total_voting_power_cast,
+        timestamp: clock.unix_timestamp,
+    });
+    
     Ok(())
 }
 
@@ -90,6 +114,7 @@ pub struct VoteOnBudget<'info> {
         seeds = [
             b"budget_vote",
             budget.key().as_ref(),
+            &[budget.revision_count],
             voter.key().as_ref(),
         ],
         bump
@@ -102,3 +127,19 @@ pub struct VoteOnBudget<'info> {
     
     pub system_program: Program<'info, System>,
 }
+
+#[event]
+pub struct BudgetVoteCast {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub voter: Pubkey,
+    pub choice: VoteChoice,
+    pub voting_power: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub votes_cast_count: u32,
+    pub total_voting_power_cast: u64,
+    pub timestamp: i64,
+}
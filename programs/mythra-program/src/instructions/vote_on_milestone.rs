@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use crate::state::{Budget, Campaign, Contribution, MilestoneApproval, MilestoneVote};
+use crate::errors::EventError;
+
+/// Vote on a milestone's release
+///
+/// Voting power is the same as on the budget: the voter's contribution
+/// as of the budget's `snapshot_ts` under the budget's voting model. One
+/// vote per backer and milestone.
+pub fn handler(
+    ctx: Context<VoteOnMilestone>,
+    milestone_index: u8,
+    approve: bool,
+) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let contribution = &ctx.accounts.contribution;
+    let vote = &mut ctx.accounts.vote;
+    let clock = Clock::get()?;
+    
+    // Validate milestone index
+    require!(
+        (milestone_index as usize) < budget.milestones.len(),
+        EventError::MilestoneNotReady
+    );
+    
+    // Validate the milestone's release vote is open
+    let milestone = &budget.milestones[milestone_index as usize];
+    require!(
+        milestone.approval == MilestoneApproval::Voting,
+        EventError::MilestoneVoteNotOpen
+    );
+    require!(
+        clock.unix_timestamp < milestone.vote_end,
+        EventError::VotingPeriodEnded
+    );
+    
+    // Validate voter had backed the campaign when the budget was submitted
+    let snapshot_amount = contribution.amount_at(budget.snapshot_ts);
+    require!(
+        snapshot_amount > 0,
+        EventError::NoVotingPowerAtSnapshot
+    );
+    let voting_power = budget.voting_model.voting_power(snapshot_amount);
+    
+    // Record vote
+    vote.budget = budget.key();
+    vote.milestone_index = milestone_index;
+    vote.voter = ctx.accounts.voter.key();
+    vote.voting_power = voting_power;
+    vote.approve = approve;
+    vote.voted_at = clock.unix_timestamp;
+    vote.bump = ctx.bumps.vote;
+    
+    // Update the milestone's tally
+    let milestone = &mut budget.milestones[milestone_index as usize];
+    milestone.add_vote(voting_power, snapshot_amount, approve)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    let (votes_for, votes_against) = (milestone.votes_for, milestone.votes_against);
+    
+    msg!(
+        "Milestone {} tally: {} FOR, {} AGAINST",
+        milestone_index,
+        votes_for,
+        votes_against
+    );
+    
+    emit!(MilestoneVoteCast {
+        budget: budget.key(),
+        campaign: ctx.accounts.campaign.key(),
+        milestone_index,
+        voter: vote.voter,
+        approve,
+        voting_power,
+        votes_for,
+        votes_against,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(milestone_index: u8)]
+pub struct VoteOnMilestone<'info> {
+    /// Budget with the milestone
+    #[account(
+        mut,
+        has_one = campaign,
+        constraint = budget.key() == campaign.active_budget @ EventError::BudgetNotActive
+    )]
+    pub budget: Account<'info, Budget>,
+    
+    /// Campaign this budget belongs to
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Voter's contribution record (proves they're a backer)
+    #[account(
+        seeds = [
+            b"contribution",
+            campaign.key().as_ref(),
+            voter.key().as_ref(),
+        ],
+        bump = contribution.bump,
+        has_one = campaign
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    /// Vote record to create (prevents double voting)
+    #[account(
+        init,
+        payer = voter,
+        space = MilestoneVote::LEN,
+        seeds = [
+            b"milestone_vote",
+            budget.key().as_ref(),
+            &[milestone_index],
+            voter.key().as_ref(),
+        ],
+        bump
+    )]
+    pub vote: Account<'info, MilestoneVote>,
+    
+    /// Voter (must be a contributor)
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct MilestoneVoteCast {
+    pub budget: Pubkey,
+    pub campaign: Pubkey,
+    pub milestone_index: u8,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub voting_power: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub timestamp: i64,
+}
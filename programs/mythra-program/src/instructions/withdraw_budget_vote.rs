@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::{Budget, BudgetStatus, BudgetVote};
+use crate::errors::EventError;
+
+/// Withdraw a budget vote
+///
+/// Removes the voter's weight from the tally and closes the vote
+/// record, returning its rent to the voter. Only allowed while the
+/// budget is Pending and the voting period hasn't ended.
+pub fn handler(ctx: Context<WithdrawBudgetVote>) -> Result<()> {
+    let budget = &mut ctx.accounts.budget;
+    let vote = &ctx.accounts.vote;
+    let clock = Clock::get()?;
+    
+    // Validate budget is in pending status
+    require!(
+        budget.status == BudgetStatus::Pending,
+        EventError::BudgetNotPending
+    );
+    
+    // Validate voting period hasn't ended
+    require!(
+        !budget.voting_ended(clock.unix_timestamp),
+        EventError::VotingPeriodEnded
+    );
+    
+    // Remove voting power from the tally
+    let voting_power = vote.voting_power();
+    budget.remove_vote(voting_power, vote.contribution_amount, vote.choice)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
+    msg!("Vote withdrawn: {} lamports voting power", voting_power);
+    msg!(
+        "Current tally: {} FOR, {} AGAINST, {} ABSTAIN",
+        budget.votes_for,
+        budget.votes_against,
+        budget.votes_abstain
+    );
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBudgetVote<'info> {
+    /// Budget being voted on
+    #[account(mut)]
+    pub budget: Account<'info, Budget>,
+    
+    /// Vote record to close (rent returned to voter)
+    #[account(
+        mut,
+        close = voter,
+        seeds = [
+            b"budget_vote",
+            budget.key().as_ref(),
+            &[vote.revision],
+            voter.key().as_ref(),
+        ],
+        bump = vote.bump,
+        has_one = budget,
+        has_one = voter
+    )]
+    pub vote: Account<'info, BudgetVote>,
+    
+    /// Voter who cast the vote
+    #[account(mut)]
+    pub voter: Signer<'info>,
+}
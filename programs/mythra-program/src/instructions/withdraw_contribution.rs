@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use crate::state::{Campaign, Contribution};
+use crate::errors::EventError;
+
+/// Withdraw some or all of a contribution from a pending campaign
+///
+/// Backers can change their minds until the deadline, as long as the
+/// campaign hasn't been finalized. The lamports come back out of the
+/// campaign escrow; withdrawing everything closes the Contribution and
+/// returns its rent too.
+pub fn handler(ctx: Context<WithdrawContribution>, amount: u64) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let contribution = &mut ctx.accounts.contribution;
+    let clock = Clock::get()?;
+    
+    let fully_withdrawn = campaign.withdraw_contribution(contribution, amount, clock.unix_timestamp)?;
+    
+    // Validate escrow can pay without dipping into its rent reserve
+    Campaign::require_escrow_covers(
+        ctx.accounts.campaign_escrow.lamports(),
+        Rent::get()?.minimum_balance(0),
+        amount,
+    )?;
+    
+    // Transfer the withdrawn amount from escrow back to the contributor
+    let campaign_key = campaign.key();
+    let escrow_seeds = &[
+        b"campaign_escrow",
+        campaign_key.as_ref(),
+        &[campaign.escrow_bump],
+    ];
+    let signer_seeds = &[&escrow_seeds[..]];
+    
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.campaign_escrow.to_account_info(),
+            to: ctx.accounts.contributor.to_account_info(),
+        },
+        signer_seeds,
+    );
+    anchor_lang::system_program::transfer(cpi_context, amount)?;
+    
+    msg!(
+        "Contribution withdrawn: {} lamports to {} (Total raised: {} / {})",
+        amount,
+        ctx.accounts.contributor.key(),
+        campaign.total_raised,
+        campaign.funding_goal
+    );
+    
+    emit!(ContributionWithdrawn {
+        campaign: campaign.key(),
+        event: campaign.event,
+        contribution: contribution.key(),
+        contributor: contribution.contributor,
+        amount,
+        remaining: contribution.amount,
+        total_raised: campaign.total_raised,
+        total_contributors: campaign.total_contributors,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    // Nothing left to track: close the record and return its rent
+    if fully_withdrawn {
+        contribution.close(ctx.accounts.contributor.to_account_info())?;
+    }
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawContribution<'info> {
+    /// Pending campaign
+    #[account(
+        mut,
+        seeds = [
+            b"campaign",
+            campaign.event.as_ref(),
+        ],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    /// Contribution being reduced (closed when fully withdrawn)
+    #[account(
+        mut,
+        seeds = [
+            b"contribution",
+            campaign.key().as_ref(),
+            contributor.key().as_ref(),
+        ],
+        bump = contribution.bump,
+        has_one = campaign,
+        has_one = contributor @ EventError::UnauthorizedCampaignAction
+    )]
+    pub contribution: Account<'info, Contribution>,
+    
+    /// Campaign escrow PDA (holds contributions)
+    #[account(
+        mut,
+        seeds = [
+            b"campaign_escrow",
+            campaign.key().as_ref(),
+        ],
+        bump = campaign.escrow_bump
+    )]
+    pub campaign_escrow: SystemAccount<'info>,
+    
+    /// Contributor withdrawing
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct ContributionWithdrawn {
+    pub campaign: Pubkey,
+    pub event: Pubkey,
+    pub contribution: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub total_raised: u64,
+    pub total_contributors: u32,
+    pub timestamp: i64,
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
 use crate::errors::EventError;
-use crate::state::Event;
+use crate::state::{Campaign, Event, PlatformFeeVault, UNSET_TIMESTAMP};
 
 #[derive(Accounts)]
 pub struct WithdrawFunds<'info> {
@@ -13,66 +13,119 @@ pub struct WithdrawFunds<'info> {
     )]
     pub event: Account<'info, Event>,
     
-    /// Escrow account that holds event funds
-    /// PDA: ["escrow", event.key()]
+    /// Ticket escrow holding the event's ticket revenue
+    /// PDA: ["ticket_escrow", event.key()]
     #[account(
         mut,
-        seeds = [b"escrow", event.key().as_ref()],
-        bump
+        seeds = [b"ticket_escrow", event.key().as_ref()],
+        bump = event.ticket_escrow_bump
     )]
-    pub escrow: SystemAccount<'info>,
+    pub ticket_escrow: SystemAccount<'info>,
     
     /// Treasury account that receives the withdrawn funds
     /// CHECK: This is the treasury account specified in the event
     #[account(mut)]
     pub treasury: AccountInfo<'info>,
     
+    /// Platform fee vault holding the platform share until settlement
+    /// PDA: ["platform_fees", event.key()]
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PlatformFeeVault::LEN,
+        seeds = [b"platform_fees", event.key().as_ref()],
+        bump
+    )]
+    pub platform_fees: Account<'info, PlatformFeeVault>,
+    
+    /// The event's campaign (required when the event is crowdfunded)
+    pub campaign: Option<Account<'info, Campaign>>,
+    
     /// Event authority that must sign the withdrawal
+    #[account(mut)]
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
+/// Withdraw funds from the event's ticket escrow
+///
+/// `platform_split_bps` of every withdrawal goes to the event's platform fee
+/// vault, where it waits for `settle_platform_fees`, and the remainder to the
+/// event treasury, both paid by the escrow PDA.
+///
+/// Only ticket revenue that hasn't been withdrawn can leave the escrow, so
+/// payments of unfulfilled orders stay there for `cancel_order`. On a
+/// crowdfunded event the revenue belongs to the distribution first, so
+/// withdrawals wait until `calculate_distribution` has run.
 pub fn handler(
     ctx: Context<WithdrawFunds>,
     amount: u64,
 ) -> Result<()> {
-    let event = &ctx.accounts.event;
-    let escrow = &ctx.accounts.escrow;
+    require_distribution_complete(&ctx.accounts.event, ctx.accounts.campaign.as_ref())?;
     
-    // Validation: Check escrow has sufficient balance
-    // Account for rent-exempt minimum (leave at least rent-exempt balance)
-    let rent = Rent::get()?;
-    let rent_exempt_minimum = rent.minimum_balance(0); // Minimum for empty account
+    let available_balance = available_to_withdraw(&ctx.accounts.event, &ctx.accounts.ticket_escrow)?;
     
-    let available_balance = escrow.lamports()
-        .checked_sub(rent_exempt_minimum)
-        .ok_or(EventError::InsufficientBalance)?;
+    let event = &mut ctx.accounts.event;
+    let escrow = &ctx.accounts.ticket_escrow;
     
+    // Validation: amount is covered by unwithdrawn revenue and the escrow
     require!(
         amount <= available_balance,
         EventError::InsufficientBalance
     );
     
-    // Transfer lamports from escrow to treasury
+    // Split the withdrawal between the platform and the organizer
+    let (platform_fee, organizer_amount) = event.split_platform_share(amount)
+        .ok_or(EventError::ArithmeticOverflow)?;
+    
     let event_key = event.key();
     let escrow_seeds = &[
-        b"escrow",
+        b"ticket_escrow",
         event_key.as_ref(),
-        &[ctx.bumps.escrow],
+        &[event.ticket_escrow_bump],
     ];
     let signer_seeds = &[&escrow_seeds[..]];
     
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer {
-            from: escrow.to_account_info(),
-            to: ctx.accounts.treasury.to_account_info(),
-        },
-        signer_seeds,
-    );
+    let platform_fees = &mut ctx.accounts.platform_fees;
+    if platform_fees.event == Pubkey::default() {
+        platform_fees.event = event_key;
+        platform_fees.last_settled_at = UNSET_TIMESTAMP;
+        platform_fees.bump = ctx.bumps.platform_fees;
+    }
     
-    transfer(transfer_ctx, amount)?;
+    if platform_fee > 0 {
+        // Transfer platform share from escrow to the fee vault, where it
+        // stays refundable until settled
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: escrow.to_account_info(),
+                to: platform_fees.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        transfer(transfer_ctx, platform_fee)?;
+        platform_fees.accrue(platform_fee)?;
+    }
+    
+    if organizer_amount > 0 {
+        // Transfer the remainder from escrow to the event treasury
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: escrow.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        transfer(transfer_ctx, organizer_amount)?;
+    }
+    
+    event.record_withdrawal(amount, platform_fee)
+        .ok_or(EventError::ArithmeticOverflow)?;
     
     // Emit FundsWithdrawn event
     emit!(FundsWithdrawn {
@@ -80,7 +133,12 @@ pub fn handler(
         escrow_pubkey: escrow.key(),
         treasury: ctx.accounts.treasury.key(),
         amount,
-        remaining_balance: escrow.lamports().checked_sub(amount).unwrap_or(0),
+        platform_fee,
+        organizer_amount,
+        platform_fees_paid: event.platform_fees_paid,
+        platform_fees_accrued: ctx.accounts.platform_fees.accrued,
+        total_withdrawn: event.total_withdrawn,
+        remaining_balance: escrow.lamports(),
         withdrawn_by: ctx.accounts.authority.key(),
         timestamp: Clock::get()?.unix_timestamp,
     });
@@ -88,19 +146,57 @@ pub fn handler(
     msg!("Funds withdrawn from escrow");
     msg!("Event: {}", event.key());
     msg!("Amount: {} lamports", amount);
-    msg!("Treasury: {}", ctx.accounts.treasury.key());
-    msg!("Remaining escrow balance: {} lamports", 
-        escrow.lamports().checked_sub(amount).unwrap_or(0));
+    msg!("Platform fee: {} lamports", platform_fee);
+    msg!("Treasury: {} ({} lamports)", ctx.accounts.treasury.key(), organizer_amount);
+    msg!("Total withdrawn: {} lamports", event.total_withdrawn);
+    msg!("Remaining escrow balance: {} lamports", escrow.lamports());
     
     Ok(())
 }
 
+/// Withdraw all unwithdrawn ticket revenue
+///
+/// Computes the available balance on-chain so organizers don't have to
+/// race the client-side balance and rent calculation.
+pub fn withdraw_all_handler(ctx: Context<WithdrawFunds>) -> Result<()> {
+    let available_balance = available_to_withdraw(&ctx.accounts.event, &ctx.accounts.ticket_escrow)?;
+    
+    // Validation: Something must be left to withdraw
+    require!(
+        available_balance > 0,
+        EventError::InsufficientBalance
+    );
+    
+    handler(ctx, available_balance)
+}
+
+/// Reject withdrawals from a crowdfunded event before its distribution
+fn require_distribution_complete(event: &Event, campaign: Option<&Account<Campaign>>) -> Result<()> {
+    if let Some(campaign_key) = event.campaign {
+        let campaign = campaign.ok_or(EventError::InvalidCampaign)?;
+        require_keys_eq!(campaign.key(), campaign_key, EventError::InvalidCampaign);
+        require!(campaign.distribution_complete, EventError::DistributionNotComplete);
+    }
+    Ok(())
+}
+
+/// Unwithdrawn ticket revenue the escrow can pay out
+fn available_to_withdraw(event: &Event, escrow: &SystemAccount) -> Result<u64> {
+    let rent = Rent::get()?;
+    Ok(event.releasable_revenue(escrow.lamports(), rent.minimum_balance(0)))
+}
+
 #[event]
 pub struct FundsWithdrawn {
     pub event_pubkey: Pubkey,
     pub escrow_pubkey: Pubkey,
     pub treasury: Pubkey,
     pub amount: u64,
+    pub platform_fee: u64,
+    pub organizer_amount: u64,
+    pub platform_fees_paid: u64,
+    pub platform_fees_accrued: u64,
+    pub total_withdrawn: u64,
     pub remaining_balance: u64,
     pub withdrawn_by: Pubkey,
     pub timestamp: i64,
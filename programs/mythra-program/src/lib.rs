@@ -2,11 +2,15 @@ use anchor_lang::prelude::*;
 
 declare_id!("3STUXGoh2tGAcsLofsZM8seXdNH6K1AoijdNvxTCMULd");
 
+pub mod ed25519;
 pub mod errors;
 pub mod instructions;
+pub mod merkle;
+pub mod metadata;
 pub mod state;
 
 use instructions::*;
+use state::{Denomination, VoteChoice, VotingModel};
 
 #[program]
 pub mod mythra_program {
@@ -19,7 +23,7 @@ pub mod mythra_program {
         start_ts: i64,
         end_ts: i64,
         total_supply: u32,
-        platform_split_bps: u16,
+        platform_split_bps: Option<u16>,
     ) -> Result<()> {
         instructions::create_event::handler(
             ctx,
@@ -39,32 +43,104 @@ pub mod mythra_program {
         instructions::update_event::handler(ctx, params)
     }
     
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority_transfer::handler(ctx, new_authority)
+    }
+    
+    pub fn accept_event_authority(
+        ctx: Context<AcceptEventAuthority>,
+    ) -> Result<()> {
+        instructions::accept_event_authority::handler(ctx)
+    }
+    
+    pub fn add_event_delegate(
+        ctx: Context<AddEventDelegate>,
+        delegate: Pubkey,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::add_event_delegate::handler(ctx, delegate, permissions)
+    }
+    
+    pub fn remove_event_delegate(
+        ctx: Context<RemoveEventDelegate>,
+    ) -> Result<()> {
+        instructions::remove_event_delegate::handler(ctx)
+    }
+    
+    pub fn get_event_metadata_uri(
+        ctx: Context<GetEventMetadataUri>,
+        locale: [u8; 2],
+    ) -> Result<String> {
+        instructions::get_event_metadata_uri::handler(ctx, locale)
+    }
+    
     pub fn create_ticket_tier(
         ctx: Context<CreateTicketTier>,
         tier_id: String,
-        metadata_uri: String,
+        params: CreateTicketTierParams,
+    ) -> Result<()> {
+        instructions::create_ticket_tier::handler(ctx, tier_id, params)
+    }
+    
+    pub fn update_ticket_tier(
+        ctx: Context<UpdateTicketTier>,
+        params: UpdateTicketTierParams,
+    ) -> Result<()> {
+        instructions::update_ticket_tier::handler(ctx, params)
+    }
+    
+    pub fn retire_tier_supply(
+        ctx: Context<RetireTierSupply>,
+        amount: u32,
+    ) -> Result<()> {
+        instructions::retire_tier_supply::handler(ctx, amount)
+    }
+    
+    pub fn close_ticket_tier(
+        ctx: Context<CloseTicketTier>,
+    ) -> Result<()> {
+        instructions::close_ticket_tier::handler(ctx)
+    }
+    
+    pub fn set_tier_promo(
+        ctx: Context<SetTierPromo>,
         price_lamports: u64,
-        max_supply: u32,
-        royalty_bps: u16,
-        tier_index: u8,
-        resale_enabled: bool,
+        start_ts: i64,
+        end_ts: i64,
     ) -> Result<()> {
-        instructions::create_ticket_tier::handler(
-            ctx,
-            tier_id,
-            metadata_uri,
-            price_lamports,
-            max_supply,
-            royalty_bps,
-            tier_index,
-            resale_enabled,
-        )
+        instructions::set_tier_promo::handler(ctx, price_lamports, start_ts, end_ts)
     }
     
     pub fn purchase_ticket(
         ctx: Context<PurchaseTicket>,
+        allowlist_proof: Vec<[u8; 32]>,
+        with_metadata: bool,
+    ) -> Result<()> {
+        instructions::purchase_ticket::handler(ctx, allowlist_proof, with_metadata)
+    }
+    
+    pub fn purchase_tickets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PurchaseTickets<'info>>,
+        count: u8,
     ) -> Result<()> {
-        instructions::purchase_ticket::handler(ctx)
+        instructions::purchase_tickets::handler(ctx, count)
+    }
+    
+    pub fn preregister_mints_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PreregisterMintsBatch<'info>>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::preregister_mints_batch::handler(ctx, count)
+    }
+    
+    pub fn activate_ticket(
+        ctx: Context<ActivateTicket>,
+        buyer: Pubkey,
+    ) -> Result<()> {
+        instructions::activate_ticket::handler(ctx, buyer)
     }
     
     pub fn register_mint(
@@ -73,6 +149,25 @@ pub mod mythra_program {
         instructions::register_mint::handler(ctx)
     }
     
+    pub fn create_order(
+        ctx: Context<CreateOrder>,
+        order_id: String,
+    ) -> Result<()> {
+        instructions::create_order::handler(ctx, order_id)
+    }
+    
+    pub fn fulfill_order(
+        ctx: Context<FulfillOrder>,
+    ) -> Result<()> {
+        instructions::fulfill_order::handler(ctx)
+    }
+    
+    pub fn cancel_order(
+        ctx: Context<CancelOrder>,
+    ) -> Result<()> {
+        instructions::cancel_order::handler(ctx)
+    }
+    
     pub fn mark_ticket_used(
         ctx: Context<MarkTicketUsed>,
     ) -> Result<()> {
@@ -87,6 +182,72 @@ pub mod mythra_program {
         instructions::mark_ticket_used_ed25519::handler(ctx, nonce_hash, nonce_value)
     }
     
+    pub fn create_checkin_nonce(
+        ctx: Context<CreateCheckinNonce>,
+        nonce_hash: [u8; 32],
+        expires_in: i64,
+    ) -> Result<()> {
+        instructions::create_checkin_nonce::handler(ctx, nonce_hash, expires_in)
+    }
+    
+    pub fn close_nonce(
+        ctx: Context<CloseNonce>,
+    ) -> Result<()> {
+        instructions::close_nonce::handler(ctx)
+    }
+    
+    pub fn add_gate_operator(
+        ctx: Context<AddGateOperator>,
+        operator: Pubkey,
+    ) -> Result<()> {
+        instructions::add_gate_operator::handler(ctx, operator)
+    }
+    
+    pub fn remove_gate_operator(
+        ctx: Context<RemoveGateOperator>,
+    ) -> Result<()> {
+        instructions::remove_gate_operator::handler(ctx)
+    }
+    
+    pub fn block_wallet(
+        ctx: Context<BlockWallet>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        instructions::block_wallet::handler(ctx, wallet)
+    }
+    
+    pub fn unblock_wallet(
+        ctx: Context<UnblockWallet>,
+    ) -> Result<()> {
+        instructions::unblock_wallet::handler(ctx)
+    }
+    
+    pub fn mark_ticket_used_by_operator(
+        ctx: Context<MarkTicketUsedByOperator>,
+        nonce_hash: [u8; 32],
+        nonce_value: u64,
+    ) -> Result<()> {
+        instructions::mark_ticket_used_by_operator::handler(ctx, nonce_hash, nonce_value)
+    }
+    
+    pub fn undo_check_in(
+        ctx: Context<UndoCheckIn>,
+    ) -> Result<()> {
+        instructions::undo_check_in::handler(ctx)
+    }
+    
+    pub fn halt_sales(
+        ctx: Context<HaltSales>,
+    ) -> Result<()> {
+        instructions::halt_sales::handler(ctx)
+    }
+    
+    pub fn resume_sales(
+        ctx: Context<ResumeSales>,
+    ) -> Result<()> {
+        instructions::resume_sales::handler(ctx)
+    }
+    
     pub fn withdraw_funds(
         ctx: Context<WithdrawFunds>,
         amount: u64,
@@ -94,12 +255,28 @@ pub mod mythra_program {
         instructions::withdraw_funds::handler(ctx, amount)
     }
     
+    pub fn withdraw_all_funds(
+        ctx: Context<WithdrawFunds>,
+    ) -> Result<()> {
+        instructions::withdraw_funds::withdraw_all_handler(ctx)
+    }
+    
+    pub fn settle_platform_fees(
+        ctx: Context<SettlePlatformFees>,
+    ) -> Result<()> {
+        instructions::settle_platform_fees::handler(ctx)
+    }
+    
     pub fn refund_ticket(
         ctx: Context<RefundTicket>,
-        refund_amount: u64,
+        refund_amount: Option<u64>,
     ) -> Result<()> {
         instructions::refund_ticket::handler(ctx, refund_amount)
     }
+    
+    pub fn claim_postponement_refund(ctx: Context<ClaimPostponementRefund>) -> Result<()> {
+        instructions::claim_postponement_refund::handler(ctx)
+    }
 
     pub fn transfer_ticket(
         ctx: Context<TransferTicket>,
@@ -108,17 +285,196 @@ pub mod mythra_program {
         instructions::transfer_ticket::handler(ctx, sale_price)
     }
     
+    pub fn upgrade_ticket(
+        ctx: Context<UpgradeTicket>,
+    ) -> Result<()> {
+        instructions::upgrade_ticket::handler(ctx)
+    }
+    
+    pub fn assign_seat(
+        ctx: Context<AssignSeat>,
+        seat_number: u32,
+    ) -> Result<()> {
+        instructions::assign_seat::handler(ctx, seat_number)
+    }
+    
+    pub fn gift_ticket(
+        ctx: Context<GiftTicket>,
+    ) -> Result<()> {
+        instructions::gift_ticket::handler(ctx)
+    }
+    
+    pub fn initiate_pending_transfer(
+        ctx: Context<InitiatePendingTransfer>,
+        claim_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::initiate_pending_transfer::handler(ctx, claim_hash, expires_at)
+    }
+    
+    pub fn claim_pending_transfer(
+        ctx: Context<ClaimPendingTransfer>,
+        preimage: Vec<u8>,
+    ) -> Result<()> {
+        instructions::claim_pending_transfer::handler(ctx, preimage)
+    }
+    
+    pub fn cancel_pending_transfer(
+        ctx: Context<CancelPendingTransfer>,
+    ) -> Result<()> {
+        instructions::cancel_pending_transfer::handler(ctx)
+    }
+    
+    pub fn attach_metadata(ctx: Context<AttachMetadata>) -> Result<()> {
+        instructions::attach_metadata::handler(ctx)
+    }
+    
+    pub fn mark_tickets_used_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MarkTicketsUsedBatch<'info>>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::mark_tickets_used_batch::handler(ctx, count)
+    }
+    
+    pub fn set_checkin_delegate(
+        ctx: Context<SetCheckinDelegate>,
+        delegate: Pubkey,
+    ) -> Result<()> {
+        instructions::set_checkin_delegate::handler(ctx, delegate)
+    }
+    
+    pub fn request_refund(ctx: Context<RequestRefund>) -> Result<()> {
+        instructions::request_refund::handler(ctx)
+    }
+    
+    pub fn set_event_refund_fee(
+        ctx: Context<SetEventRefundFee>,
+        refund_fee_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::set_event_refund_fee::handler(ctx, refund_fee_bps)
+    }
+    
+    pub fn process_cancellation_refunds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessCancellationRefunds<'info>>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::process_cancellation_refunds::handler(ctx, count)
+    }
+    
+    pub fn close_ticket(ctx: Context<CloseTicket>) -> Result<()> {
+        instructions::close_ticket::handler(ctx)
+    }
+    
+    pub fn open_milestone_vote(
+        ctx: Context<OpenMilestoneVote>,
+        milestone_index: u8,
+        voting_period_seconds: i64,
+    ) -> Result<()> {
+        instructions::open_milestone_vote::handler(ctx, milestone_index, voting_period_seconds)
+    }
+    
+    pub fn vote_on_milestone(
+        ctx: Context<VoteOnMilestone>,
+        milestone_index: u8,
+        approve: bool,
+    ) -> Result<()> {
+        instructions::vote_on_milestone::handler(ctx, milestone_index, approve)
+    }
+    
+    pub fn finalize_milestone_vote(
+        ctx: Context<FinalizeMilestoneVote>,
+        milestone_index: u8,
+    ) -> Result<()> {
+        instructions::finalize_milestone_vote::handler(ctx, milestone_index)
+    }
+    
     pub fn close_event(ctx: Context<CloseEvent>) -> Result<()> {
         instructions::close_event::handler(ctx)
     }
     
+    pub fn cancel_event(ctx: Context<CancelEvent>) -> Result<()> {
+        instructions::cancel_event::handler(ctx)
+    }
+    
+    pub fn register_refund_claim(
+        ctx: Context<RegisterRefundClaim>,
+    ) -> Result<()> {
+        instructions::register_refund_claim::handler(ctx)
+    }
+    
+    pub fn settle_refund_claims(
+        ctx: Context<SettleRefundClaims>,
+    ) -> Result<()> {
+        instructions::settle_refund_claims::handler(ctx)
+    }
+    
+    pub fn claim_ticket_refund(
+        ctx: Context<ClaimTicketRefund>,
+    ) -> Result<()> {
+        instructions::claim_ticket_refund::handler(ctx)
+    }
+    
+    pub fn snapshot_attendance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SnapshotAttendance<'info>>,
+        page_index: u32,
+    ) -> Result<()> {
+        instructions::snapshot_attendance::handler(ctx, page_index)
+    }
+    
+    // Platform instructions
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        min_platform_share_bps: u16,
+        min_backer_share_bps: u16,
+        platform_treasury: Pubkey,
+        default_platform_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::initialize_config::handler(
+            ctx,
+            min_platform_share_bps,
+            min_backer_share_bps,
+            platform_treasury,
+            default_platform_fee_bps,
+        )
+    }
+    
+    pub fn initialize_stats(ctx: Context<InitializeStats>) -> Result<()> {
+        instructions::initialize_stats::handler(ctx)
+    }
+    
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        params: UpdateConfigParams,
+    ) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+    
+    pub fn set_paused(
+        ctx: Context<SetPaused>,
+        paused: bool,
+    ) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+    
     // Crowdfunding instructions
     pub fn create_campaign(
         ctx: Context<CreateCampaign>,
         funding_goal: u64,
         deadline: i64,
+        backer_share_bps: u16,
+        organizer_share_bps: u16,
+        platform_share_bps: u16,
+        denomination: Denomination,
     ) -> Result<()> {
-        instructions::create_campaign::handler(ctx, funding_goal, deadline)
+        instructions::create_campaign::handler(
+            ctx,
+            funding_goal,
+            deadline,
+            backer_share_bps,
+            organizer_share_bps,
+            platform_share_bps,
+            denomination,
+        )
     }
     
     pub fn contribute(
@@ -128,18 +484,51 @@ pub mod mythra_program {
         instructions::contribute::handler(ctx, amount)
     }
     
+    pub fn withdraw_contribution(
+        ctx: Context<WithdrawContribution>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_contribution::handler(ctx, amount)
+    }
+    
     pub fn finalize_campaign(
         ctx: Context<FinalizeCampaign>,
     ) -> Result<()> {
         instructions::finalize_campaign::handler(ctx)
     }
     
+    pub fn extend_campaign_deadline(
+        ctx: Context<ExtendCampaignDeadline>,
+        new_deadline: i64,
+    ) -> Result<()> {
+        instructions::extend_campaign_deadline::handler(ctx, new_deadline)
+    }
+    
     pub fn claim_refund(
         ctx: Context<ClaimRefund>,
     ) -> Result<()> {
         instructions::claim_refund::handler(ctx)
     }
     
+    pub fn claim_refund_sponsored(
+        ctx: Context<ClaimRefundSponsored>,
+        relayer_fee: u64,
+    ) -> Result<()> {
+        instructions::claim_refund_sponsored::handler(ctx, relayer_fee)
+    }
+    
+    pub fn close_campaign_escrow(ctx: Context<CloseCampaignEscrow>) -> Result<()> {
+        instructions::close_campaign_escrow::handler(ctx)
+    }
+    
+    pub fn close_campaign(ctx: Context<CloseCampaign>) -> Result<()> {
+        instructions::close_campaign::handler(ctx)
+    }
+    
+    pub fn close_contribution(ctx: Context<CloseContribution>) -> Result<()> {
+        instructions::close_contribution::handler(ctx)
+    }
+    
     // Budget & voting instructions
     pub fn submit_budget(
         ctx: Context<SubmitBudget>,
@@ -147,15 +536,46 @@ pub mod mythra_program {
         description: String,
         milestones: Vec<instructions::submit_budget::MilestoneInput>,
         voting_period_seconds: i64,
+        per_milestone_approval: bool,
+        voting_model: VotingModel,
     ) -> Result<()> {
-        instructions::submit_budget::handler(ctx, total_amount, description, milestones, voting_period_seconds)
+        instructions::submit_budget::handler(
+            ctx,
+            total_amount,
+            description,
+            milestones,
+            voting_period_seconds,
+            per_milestone_approval,
+            voting_model,
+        )
     }
     
     pub fn vote_on_budget(
         ctx: Context<VoteOnBudget>,
-        approve: bool,
+        choice: VoteChoice,
+    ) -> Result<()> {
+        instructions::vote_on_budget::handler(ctx, choice)
+    }
+    
+    pub fn change_budget_vote(
+        ctx: Context<ChangeBudgetVote>,
+        choice: VoteChoice,
     ) -> Result<()> {
-        instructions::vote_on_budget::handler(ctx, approve)
+        instructions::change_budget_vote::handler(ctx, choice)
+    }
+    
+    pub fn withdraw_budget_vote(
+        ctx: Context<WithdrawBudgetVote>,
+    ) -> Result<()> {
+        instructions::withdraw_budget_vote::handler(ctx)
+    }
+    
+    pub fn close_budget_vote(ctx: Context<CloseBudgetVote>) -> Result<()> {
+        instructions::close_budget_vote::handler(ctx)
+    }
+    
+    pub fn close_budget(ctx: Context<CloseBudget>) -> Result<()> {
+        instructions::close_budget::handler(ctx)
     }
     
     pub fn finalize_budget_vote(
@@ -164,6 +584,12 @@ pub mod mythra_program {
         instructions::finalize_budget_vote::handler(ctx)
     }
     
+    pub fn emit_voting_reminder(
+        ctx: Context<EmitVotingReminder>,
+    ) -> Result<()> {
+        instructions::emit_voting_reminder::handler(ctx)
+    }
+    
     pub fn revise_budget(
         ctx: Context<ReviseBudget>,
         total_amount: u64,
@@ -181,6 +607,21 @@ pub mod mythra_program {
         instructions::release_milestone::handler(ctx, milestone_index)
     }
     
+    pub fn flag_milestone(
+        ctx: Context<FlagMilestone>,
+        milestone_index: u8,
+    ) -> Result<()> {
+        instructions::flag_milestone::handler(ctx, milestone_index)
+    }
+    
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        milestone_index: u8,
+        upheld: bool,
+    ) -> Result<()> {
+        instructions::resolve_dispute::handler(ctx, milestone_index, upheld)
+    }
+    
     pub fn calculate_distribution(
         ctx: Context<CalculateDistribution>,
     ) -> Result<()> {
@@ -193,9 +634,28 @@ pub mod mythra_program {
         instructions::claim_backer_profit::handler(ctx)
     }
     
+    pub fn distribute_backer_profits<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeBackerProfits<'info>>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::distribute_backer_profits::handler(ctx, count)
+    }
+    
     pub fn claim_organizer_profit(
         ctx: Context<ClaimOrganizerProfit>,
     ) -> Result<()> {
         instructions::claim_organizer_profit::handler(ctx)
     }
+    
+    pub fn claim_loss_recovery(
+        ctx: Context<ClaimLossRecovery>,
+    ) -> Result<()> {
+        instructions::claim_loss_recovery::handler(ctx)
+    }
+    
+    pub fn sweep_unclaimed_profits(
+        ctx: Context<SweepUnclaimedProfits>,
+    ) -> Result<()> {
+        instructions::sweep_unclaimed_profits::handler(ctx)
+    }
 }
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::errors::EventError;
+
+/// Prefixes keeping leaf and node hashes apart, so an inner node can never
+/// be passed off as a leaf (second-preimage attack on the tree)
+const LEAF_PREFIX: &[u8] = &[0x00];
+const NODE_PREFIX: &[u8] = &[0x01];
+
+/// Longest proof accepted (a tree of up to 2^24 wallets)
+pub const MAX_PROOF_LEN: usize = 24;
+
+/// Leaf hash for a wallet: sha256(0x00 || pubkey)
+pub fn leaf_hash(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet.as_ref()]).to_bytes()
+}
+
+/// Parent hash of two nodes: sha256(0x01 || min || max)
+///
+/// Pairs are sorted before hashing, so proofs don't need to say which side
+/// each sibling is on and the tree builder only has to sort each pair the
+/// same way.
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Root reached by hashing `leaf` up through `proof`
+pub fn compute_root(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(leaf, |node, sibling| node_hash(&node, sibling))
+}
+
+/// Fail unless `proof` shows `wallet` is in the tree with `root`
+pub fn verify_wallet(root: &[u8; 32], wallet: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+    require!(
+        proof.len() <= MAX_PROOF_LEN && compute_root(leaf_hash(wallet), proof) == *root,
+        EventError::MerkleProofInvalid
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Build every level of a tree over `wallets`, carrying odd nodes up
+    fn levels(wallets: &[Pubkey]) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![wallets.iter().map(leaf_hash).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels.last().unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node_hash(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+    
+    fn proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        for level in &levels[..levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+    
+    #[test]
+    fn test_every_wallet_proves_membership() {
+        let wallets: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let levels = levels(&wallets);
+        let root = levels.last().unwrap()[0];
+        
+        for (i, wallet) in wallets.iter().enumerate() {
+            assert!(verify_wallet(&root, wallet, &proof(&levels, i)).is_ok());
+        }
+    }
+    
+    #[test]
+    fn test_invalid_proofs_rejected() {
+        let wallets: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let levels = levels(&wallets);
+        let root = levels.last().unwrap()[0];
+        let invalid = EventError::MerkleProofInvalid.into();
+        
+        // An outsider can't borrow a member's proof
+        let outsider = Pubkey::new_unique();
+        assert_eq!(verify_wallet(&root, &outsider, &proof(&levels, 0)).unwrap_err(), invalid);
+        
+        // A member's proof doesn't work for another member
+        assert_eq!(verify_wallet(&root, &wallets[1], &proof(&levels, 2)).unwrap_err(), invalid);
+        
+        // Tampered or truncated proofs fail
+        let mut tampered = proof(&levels, 0);
+        tampered[1][0] ^= 1;
+        assert_eq!(verify_wallet(&root, &wallets[0], &tampered).unwrap_err(), invalid);
+        assert_eq!(verify_wallet(&root, &wallets[0], &proof(&levels, 0)[..1]).unwrap_err(), invalid);
+        
+        // An inner node can't pose as a leaf
+        let inner = levels[1][0];
+        let forged = Pubkey::new_from_array(inner);
+        assert_eq!(verify_wallet(&root, &forged, &[levels[1][1]]).unwrap_err(), invalid);
+    }
+    
+    #[test]
+    fn test_sibling_order_does_not_matter() {
+        let a = leaf_hash(&Pubkey::new_unique());
+        let b = leaf_hash(&Pubkey::new_unique());
+        assert_eq!(node_hash(&a, &b), node_hash(&b, &a));
+        
+        // Equal siblings (a duplicated wallet) still hash consistently
+        assert_eq!(node_hash(&a, &a), hashv(&[NODE_PREFIX, &a, &a]).to_bytes());
+    }
+    
+    #[test]
+    fn test_single_wallet_tree() {
+        // A one-wallet allowlist's root is the leaf itself; the proof is empty
+        let wallet = Pubkey::new_unique();
+        let root = leaf_hash(&wallet);
+        assert!(verify_wallet(&root, &wallet, &[]).is_ok());
+        assert!(verify_wallet(&root, &Pubkey::new_unique(), &[]).is_err());
+    }
+    
+    #[test]
+    fn test_proof_length_capped() {
+        let wallet = Pubkey::new_unique();
+        let proof = vec![[7u8; 32]; MAX_PROOF_LEN + 1];
+        let root = compute_root(leaf_hash(&wallet), &proof);
+        assert_eq!(
+            verify_wallet(&root, &wallet, &proof).unwrap_err(),
+            EventError::MerkleProofInvalid.into()
+        );
+    }
+}
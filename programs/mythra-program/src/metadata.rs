@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::state::{Event, TicketTier};
+
+/// Metaplex Token Metadata program
+pub const ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Marker type so the program can be taken as `Program<'info, TokenMetadata>`
+#[derive(Clone)]
+pub struct TokenMetadata;
+
+impl Id for TokenMetadata {
+    fn id() -> Pubkey {
+        ID
+    }
+}
+
+/// Token Metadata field limits
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// Symbol shown by wallets for every ticket NFT
+pub const TICKET_SYMBOL: &str = "MYTHRA";
+
+/// Seed of the per-event PDA that owns ticket metadata and signs as its
+/// verified creator
+pub const METADATA_AUTHORITY_SEED: &[u8] = b"metadata_authority";
+
+/// `CreateMetadataAccountV3` instruction discriminator
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum CollectionDetails {
+    V1 { size: u64 },
+}
+
+/// Metadata fields, laid out as Token Metadata's `DataV2`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+/// Arguments of `CreateMetadataAccountV3`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreateMetadataAccountArgsV3 {
+    pub data: DataV2,
+    pub is_mutable: bool,
+    pub collection_details: Option<CollectionDetails>,
+}
+
+/// Metadata PDA for `mint`: ["metadata", program, mint] under Token Metadata
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", ID.as_ref(), mint.as_ref()], &ID).0
+}
+
+/// Wallet-facing ticket name: "Mythra <event> T<tier>" plus " #<seat>" for
+/// seated tickets
+///
+/// Events only keep their name off-chain, so the leading characters of the
+/// event address stand in for it. The longest name fits `MAX_NAME_LENGTH`.
+pub fn ticket_name(event: &Pubkey, tier_index: u8, seat_number: u32) -> String {
+    let event = event.to_string();
+    let mut name = format!("Mythra {} T{}", &event[..8], tier_index);
+    if seat_number > 0 {
+        name.push_str(&format!(" #{}", seat_number));
+    }
+    name
+}
+
+/// Metadata for a ticket of `tier`
+///
+/// The event's metadata authority PDA is the only verified creator (share 0),
+/// which lets marketplaces tell program-issued tickets from look-alikes;
+/// the event treasury is listed with the full share so royalties reach the
+/// organizer at `tier.royalty_bps`.
+pub fn ticket_data(
+    event_key: &Pubkey,
+    event: &Event,
+    tier: &TicketTier,
+    seat_number: u32,
+    metadata_authority: &Pubkey,
+) -> DataV2 {
+    DataV2 {
+        name: ticket_name(event_key, tier.tier_index, seat_number),
+        symbol: TICKET_SYMBOL.to_string(),
+        uri: tier.metadata_uri.clone(),
+        seller_fee_basis_points: tier.royalty_bps,
+        creators: Some(vec![
+            Creator {
+                address: *metadata_authority,
+                verified: true,
+                share: 0,
+            },
+            Creator {
+                address: event.treasury,
+                verified: false,
+                share: 100,
+            },
+        ]),
+        collection: None,
+        uses: None,
+    }
+}
+
+/// Accounts for creating a ticket's metadata account
+pub struct CreateTicketMetadata<'a, 'info> {
+    pub metadata: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub mint_authority: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub metadata_authority: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub token_metadata_program: &'a AccountInfo<'info>,
+}
+
+/// Create the metadata account through `CreateMetadataAccountV3`
+///
+/// The metadata authority PDA (["metadata_authority", event]) signs as
+/// update authority and verified creator; the metadata stays mutable so
+/// the program can refresh it later.
+pub fn create_ticket_metadata(
+    accounts: CreateTicketMetadata,
+    data: DataV2,
+    event: &Pubkey,
+    metadata_authority_bump: u8,
+) -> Result<()> {
+    let args = CreateMetadataAccountArgsV3 {
+        data,
+        is_mutable: true,
+        collection_details: None,
+    };
+    let mut instruction_data = vec![CREATE_METADATA_ACCOUNT_V3];
+    args.serialize(&mut instruction_data)?;
+    
+    let instruction = Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(accounts.metadata.key(), false),
+            AccountMeta::new_readonly(accounts.mint.key(), false),
+            AccountMeta::new_readonly(accounts.mint_authority.key(), true),
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new_readonly(accounts.metadata_authority.key(), true),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data: instruction_data,
+    };
+    
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.metadata.clone(),
+            accounts.mint.clone(),
+            accounts.mint_authority.clone(),
+            accounts.payer.clone(),
+            accounts.metadata_authority.clone(),
+            accounts.system_program.clone(),
+            accounts.token_metadata_program.clone(),
+        ],
+        &[&[METADATA_AUTHORITY_SEED, event.as_ref(), &[metadata_authority_bump]]],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_ticket_name_fits() {
+        let event = Pubkey::new_unique();
+        let prefix = &event.to_string()[..8];
+        assert_eq!(ticket_name(&event, 1, 0), format!("Mythra {} T1", prefix));
+        assert_eq!(ticket_name(&event, 2, 14), format!("Mythra {} T2 #14", prefix));
+        assert!(ticket_name(&event, u8::MAX, u32::MAX).len() <= MAX_NAME_LENGTH);
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+/// Page of attendee wallets for an event
+///
+/// Filled by the permissionless `snapshot_attendance` crank after the event
+/// ends. Pages are append-only and created on demand; page `n` holds
+/// attendees `n * MAX_ATTENDEES_PER_PAGE ..` in snapshot order. Airdrop
+/// tooling reads pages 0..=`event.attendance_snapshot_count / MAX_ATTENDEES_PER_PAGE`.
+#[account]
+pub struct AttendanceList {
+    /// The event these attendees checked in to
+    pub event: Pubkey,
+    
+    /// Page number of this list
+    pub page_index: u32,
+    
+    /// Owners of used tickets, in snapshot order
+    pub attendees: Vec<Pubkey>,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl AttendanceList {
+    /// Maximum attendees stored per page (keeps the account under 10KB)
+    pub const MAX_ATTENDEES_PER_PAGE: usize = 300;
+    
+    /// Calculate space needed for AttendanceList account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // event
+        4 +  // page_index
+        4 + (32 * Self::MAX_ATTENDEES_PER_PAGE) + // attendees (Vec)
+        1;   // bump
+    
+    /// Page that the next attendee should be appended to
+    pub fn current_page(snapshot_count: u32) -> u32 {
+        snapshot_count / Self::MAX_ATTENDEES_PER_PAGE as u32
+    }
+    
+    /// Check if this page has no room left
+    pub fn is_full(&self) -> bool {
+        self.attendees.len() >= Self::MAX_ATTENDEES_PER_PAGE
+    }
+    
+    /// Append an attendee, returning false if the page is full
+    pub fn append(&mut self, attendee: Pubkey) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        
+        self.attendees.push(attendee);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_attendance_list_len() {
+        // 8 + 32 + 4 + (4 + 32 * 300) + 1 = 9649, within the 10KB CPI limit
+        assert_eq!(AttendanceList::LEN, 9649);
+        
+        let full_page = AttendanceList {
+            event: Pubkey::new_unique(),
+            page_index: 0,
+            attendees: vec![Pubkey::new_unique(); AttendanceList::MAX_ATTENDEES_PER_PAGE],
+            bump: 0,
+        };
+        assert!(full_page.is_full());
+        assert_eq!(8 + full_page.try_to_vec().unwrap().len(), AttendanceList::LEN);
+    }
+    
+    #[test]
+    fn test_current_page() {
+        assert_eq!(AttendanceList::current_page(0), 0);
+        assert_eq!(AttendanceList::current_page(299), 0);
+        assert_eq!(AttendanceList::current_page(300), 1);
+        assert_eq!(AttendanceList::current_page(601), 2);
+    }
+}
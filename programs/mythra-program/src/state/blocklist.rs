@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+
+/// Wallet blocked by the organizer from an event
+///
+/// PDA: ["blocklist", event, wallet]. While the entry exists the wallet
+/// can't buy, register, receive or check in tickets for the event.
+/// Instructions take the PDA address for the relevant wallet unconditionally;
+/// an uninitialized account there means the wallet isn't blocked.
+#[account]
+pub struct BlocklistEntry {
+    pub event: Pubkey,          // 32 bytes - event the wallet is blocked from
+    pub wallet: Pubkey,         // 32 bytes - blocked wallet
+    pub blocked_at: i64,        // 8 bytes - when the wallet was blocked
+    pub bump: u8,               // 1 byte
+}
+
+impl BlocklistEntry {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1; // 81 bytes
+    
+    /// Whether the account at a wallet's blocklist PDA holds an entry
+    ///
+    /// The address is checked by seeds on the instruction. Anything not
+    /// owned by this program (an unused, system-owned address) is not an
+    /// entry; an owned one must be the entry for this event and wallet.
+    pub fn is_blocked(
+        entry_owner: &Pubkey,
+        entry_data: &[u8],
+        event: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<bool> {
+        if entry_owner != &crate::ID || entry_data.is_empty() {
+            return Ok(false);
+        }
+        
+        let entry = Self::try_deserialize(&mut &entry_data[..])?;
+        require!(
+            entry.event == *event && entry.wallet == *wallet,
+            EventError::InvalidBlocklistEntry
+        );
+        
+        Ok(true)
+    }
+    
+    /// Fail with `WalletBlocked` if `wallet` is blocked from `event`
+    pub fn require_not_blocked(
+        entry: &AccountInfo,
+        event: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<()> {
+        let blocked = Self::is_blocked(entry.owner, &entry.try_borrow_data()?, event, wallet)?;
+        require!(
+            !blocked,
+            EventError::WalletBlocked
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn entry_data(event: Pubkey, wallet: Pubkey) -> Vec<u8> {
+        let entry = BlocklistEntry {
+            event,
+            wallet,
+            blocked_at: 0,
+            bump: 0,
+        };
+        let mut data = Vec::new();
+        entry.try_serialize(&mut data).unwrap();
+        data
+    }
+    
+    #[test]
+    fn test_blocklist_entry_space() {
+        assert_eq!(entry_data(Pubkey::default(), Pubkey::default()).len(), BlocklistEntry::SPACE);
+    }
+    
+    #[test]
+    fn test_uninitialized_entry_not_blocked() {
+        let event = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        
+        // Never created, or only funded with lamports by someone else
+        assert!(!BlocklistEntry::is_blocked(&anchor_lang::system_program::ID, &[], &event, &wallet).unwrap());
+        
+        // Closed by unblock_wallet (no data left)
+        assert!(!BlocklistEntry::is_blocked(&crate::ID, &[], &event, &wallet).unwrap());
+    }
+    
+    #[test]
+    fn test_existing_entry_blocks() {
+        let event = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let data = entry_data(event, wallet);
+        
+        assert!(BlocklistEntry::is_blocked(&crate::ID, &data, &event, &wallet).unwrap());
+        
+        // Data that isn't a blocklist entry is rejected
+        assert!(BlocklistEntry::is_blocked(&crate::ID, &[0u8; BlocklistEntry::SPACE], &event, &wallet).is_err());
+    }
+    
+    #[test]
+    fn test_entry_for_other_wallet_rejected() {
+        let event = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let data = entry_data(event, Pubkey::new_unique());
+        
+        assert_eq!(
+            BlocklistEntry::is_blocked(&crate::ID, &data, &event, &wallet).unwrap_err(),
+            EventError::InvalidBlocklistEntry.into()
+        );
+    }
+}
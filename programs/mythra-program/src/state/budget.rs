@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::errors::EventError;
+use crate::state::{DisputeStatus, VoteChoice};
 
 /// Budget account for campaign expense management
 /// 
-/// Organizers submit a budget with 3 fixed milestones that shows
-/// how they plan to spend the crowdfunding money. Backers vote to
+/// Organizers submit a budget with 1 to `MAX_MILESTONES` milestones that
+/// shows how they plan to spend the crowdfunding money. Backers vote to
 /// approve or reject the budget.
 #[account]
 pub struct Budget {
@@ -16,8 +19,8 @@ pub struct Budget {
     /// Description of budget (max 200 chars)
     pub description: String,
     
-    /// Fixed 3 milestones for MVP
-    pub milestones: [Milestone; 3],
+    /// Milestones (1 to MAX_MILESTONES), release percentages sum to 10000
+    pub milestones: Vec<Milestone>,
     
     /// Current status
     pub status: BudgetStatus,
@@ -31,53 +34,394 @@ pub struct Budget {
     /// Sum of contribution amounts voting NO
     pub votes_against: u64,
     
+    /// Sum of contribution amounts abstaining
+    pub votes_abstain: u64,
+    
+    /// Minimum turnout (contributions behind every counted vote) as basis
+    /// points of total raised
+    pub quorum_bps: u16,
+    
     /// Number of revisions (max 2)
     pub revision_count: u8,
     
     /// When budget was created
     pub created_at: i64,
     
+    /// Whether the voting-ends-soon reminder has been emitted
+    pub reminder_emitted: bool,
+    
+    /// Number of vote records currently counted in the tally
+    pub votes_cast_count: u32,
+    
+    /// Total voting power currently counted in the tally
+    pub total_voting_power_cast: u64,
+    
+    /// When voting was finalized (UNSET_TIMESTAMP while pending)
+    pub finalized_at: i64,
+    
+    /// Campaign's total raised when the budget was submitted (quorum base)
+    pub total_raised_snapshot: u64,
+    
+    /// Contributions made after this timestamp carry no voting power
+    pub snapshot_ts: i64,
+    
+    /// Whether each milestone needs its own approval vote before release
+    pub per_milestone_approval: bool,
+    
+    /// How contributions translate into voting power
+    pub voting_model: VotingModel,
+    
+    /// Contribution amounts behind the votes currently counted (quorum turnout)
+    pub turnout_amount: u64,
+    
+    /// Turnout floor (basis points of total raised); below it the vote
+    /// expires instead of being decided
+    pub min_turnout_bps: u16,
+    
+    /// How many of `revision_count` were free resubmissions of expired votes
+    pub expired_resubmissions: u8,
+    
     /// PDA bump
     pub bump: u8,
 }
 
 impl Budget {
-    /// Calculate space needed for Budget account
-    /// 3 milestones with 100 char descriptions each
+    /// Worst-case space for a Budget account
+    /// Allocates for MAX_MILESTONES milestones with 100 char descriptions each.
+    /// Accounts are created with `space()` sized to the actual strings.
     pub const MAX_DESCRIPTION_LEN: usize = 200;
     pub const MAX_MILESTONE_DESC_LEN: usize = 100;
+    pub const MAX_MILESTONES: usize = 8;
+    
+    /// Default quorum: 20% of total raised must vote
+    pub const DEFAULT_QUORUM_BPS: u16 = 2000;
+    
+    /// Reminder can be emitted once less than this remains before voting_end
+    pub const REMINDER_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+    
+    /// A rejected budget can't be revised until this long after rejection
+    pub const REVISION_COOLDOWN_SECONDS: i64 = 24 * 60 * 60;
+    
+    /// Free resubmissions of expired votes allowed per campaign
+    pub const MAX_EXPIRED_RESUBMISSIONS: u8 = 5;
     
     pub const LEN: usize = 8 + // discriminator
         32 + // campaign
         8 +  // total_amount
         4 + Self::MAX_DESCRIPTION_LEN + // description (String)
-        (Milestone::LEN * 3) + // milestones array
+        4 + (Milestone::LEN * Self::MAX_MILESTONES) + // milestones (Vec)
         1 +  // status (enum)
         8 +  // voting_end
         8 +  // votes_for
         8 +  // votes_against
+        8 +  // votes_abstain
+        2 +  // quorum_bps
         1 +  // revision_count
         8 +  // created_at
+        1 +  // reminder_emitted
+        4 +  // votes_cast_count
+        8 +  // total_voting_power_cast
+        8 +  // finalized_at
+        8 +  // total_raised_snapshot
+        8 +  // snapshot_ts
+        1 +  // per_milestone_approval
+        1 +  // voting_model (enum)
+        8 +  // turnout_amount
+        2 +  // min_turnout_bps
+        1 +  // expired_resubmissions
         1;   // bump
     
+    /// Calculate space needed for a Budget with the given strings
+    ///
+    /// Descriptions are immutable after submission, so the account only
+    /// needs room for the strings it is created with.
+    pub fn space<'a>(
+        description: &str,
+        milestone_descriptions: impl IntoIterator<Item = &'a str>,
+    ) -> usize {
+        let milestones: usize = milestone_descriptions
+            .into_iter()
+            .map(Milestone::space)
+            .sum();
+        
+        Self::LEN
+            - Self::MAX_DESCRIPTION_LEN + description.len()
+            - Milestone::LEN * Self::MAX_MILESTONES + milestones
+    }
+    
     /// Check if voting period has ended
     pub fn voting_ended(&self, current_timestamp: i64) -> bool {
         current_timestamp >= self.voting_end
     }
     
-    /// Check if budget is approved based on votes
+    /// Check if budget is approved based on votes (abstentions don't count)
     pub fn is_approved(&self) -> bool {
-        self.votes_for > self.votes_against && self.votes_for > 0
+        Self::majority_approves(self.votes_for, self.votes_against)
+    }
+    
+    /// Contributions that took part: for, against and abstaining
+    ///
+    /// Measured in lamports rather than voting power, so quorum means the
+    /// same share of the money under either voting model.
+    pub fn turnout(&self) -> u128 {
+        self.turnout_amount as u128
+    }
+    
+    /// Check if enough contribution weight voted to meet quorum
+    pub fn quorum_met(&self, total_raised: u64) -> bool {
+        Self::turnout_meets_quorum(self.turnout(), total_raised, self.quorum_bps)
+    }
+    
+    /// Check if enough contribution weight voted for the result to count
+    pub fn min_turnout_met(&self, total_raised: u64) -> bool {
+        Self::turnout_meets_quorum(self.turnout(), total_raised, self.min_turnout_bps)
+    }
+    
+    /// Check if a tally approves: strictly more power for than against
+    ///
+    /// Ties reject, and so does a tally with nothing for.
+    pub fn majority_approves(votes_for: u64, votes_against: u64) -> bool {
+        votes_for > votes_against
+    }
+    
+    /// Result of the vote once voting has ended
+    ///
+    /// Below the turnout floor nobody really decided, so the vote expires.
+    /// Otherwise the budget needs quorum and a strict majority for.
+    pub fn outcome(&self) -> BudgetStatus {
+        let total_raised = self.total_raised_snapshot;
+        if !self.min_turnout_met(total_raised) {
+            BudgetStatus::Expired
+        } else if self.quorum_met(total_raised) && self.is_approved() {
+            BudgetStatus::Approved
+        } else {
+            BudgetStatus::Rejected
+        }
+    }
+    
+    /// Check if a tally's turnout meets quorum
+    ///
+    /// Requires `turnout >= total_raised * quorum_bps / 10000`, compared
+    /// without division so rounding can't let a vote slip through.
+    pub fn turnout_meets_quorum(turnout: u128, total_raised: u64, quorum_bps: u16) -> bool {
+        turnout * 10_000 >= total_raised as u128 * quorum_bps as u128
+    }
+    
+    /// Turnout (see `turnout`) as basis points of total raised
+    pub fn turnout_bps(&self, total_raised: u64) -> u64 {
+        if total_raised == 0 {
+            return 0;
+        }
+        
+        (self.turnout() * 10_000 / total_raised as u128) as u64
+    }
+    
+    /// Check if the voting reminder window is open
+    ///
+    /// Open while voting is pending and less than `REMINDER_WINDOW_SECONDS`
+    /// remain before `voting_end`.
+    pub fn in_reminder_window(&self, current_timestamp: i64) -> bool {
+        self.status == BudgetStatus::Pending
+            && !self.voting_ended(current_timestamp)
+            && self.voting_end - current_timestamp < Self::REMINDER_WINDOW_SECONDS
+    }
+    
+    /// Tally a vote choice is counted in
+    fn tally_mut(&mut self, choice: VoteChoice) -> &mut u64 {
+        match choice {
+            VoteChoice::Approve => &mut self.votes_for,
+            VoteChoice::Reject => &mut self.votes_against,
+            VoteChoice::Abstain => &mut self.votes_abstain,
+        }
+    }
+    
+    /// Add a vote's power to the approve, reject or abstain tally
+    ///
+    /// Also counts the vote in `votes_cast_count`, `total_voting_power_cast`
+    /// and, with the contribution behind it, `turnout_amount`. Nothing
+    /// changes if any counter overflows.
+    pub fn add_vote(
+        &mut self,
+        voting_power: u64,
+        contribution_amount: u64,
+        choice: VoteChoice,
+    ) -> Option<()> {
+        let votes_cast_count = self.votes_cast_count.checked_add(1)?;
+        let total_voting_power_cast = self.total_voting_power_cast.checked_add(voting_power)?;
+        let turnout_amount = self.turnout_amount.checked_add(contribution_amount)?;
+        
+        let tally = self.tally_mut(choice);
+        *tally = tally.checked_add(voting_power)?;
+        
+        self.turnout_amount = turnout_amount;
+        self.votes_cast_count = votes_cast_count;
+        self.total_voting_power_cast = total_voting_power_cast;
+        Some(())
+    }
+    
+    /// Remove a vote previously added with `add_vote`
+    ///
+    /// Changing a vote is a remove followed by an add, which leaves the
+    /// cast counters unchanged.
+    pub fn remove_vote(
+        &mut self,
+        voting_power: u64,
+        contribution_amount: u64,
+        choice: VoteChoice,
+    ) -> Option<()> {
+        let votes_cast_count = self.votes_cast_count.checked_sub(1)?;
+        let total_voting_power_cast = self.total_voting_power_cast.checked_sub(voting_power)?;
+        let turnout_amount = self.turnout_amount.checked_sub(contribution_amount)?;
+        
+        let tally = self.tally_mut(choice);
+        *tally = tally.checked_sub(voting_power)?;
+        
+        self.turnout_amount = turnout_amount;
+        self.votes_cast_count = votes_cast_count;
+        self.total_voting_power_cast = total_voting_power_cast;
+        Some(())
+    }
+    
+    /// Revisions counted against the limit (expired resubmissions are free)
+    pub fn revisions_used(&self) -> u8 {
+        self.revision_count - self.expired_resubmissions
     }
     
-    /// Check if can be revised (rejected and under revision limit)
+    /// Check if can be revised (rejected and under revision limit, or
+    /// expired and under the resubmission limit)
+    ///
+    /// An approved budget can be revised too while it still has milestones
+    /// to settle and none of them is disputed; that uses up a revision and
+    /// the released milestones are carried forward.
     pub fn can_revise(&self) -> bool {
-        self.status == BudgetStatus::Rejected && self.revision_count < 2
+        match self.status {
+            BudgetStatus::Rejected => self.revisions_used() < 2,
+            BudgetStatus::Approved => {
+                self.revisions_used() < 2 &&
+                    self.milestones.iter().any(|m| !m.is_settled()) &&
+                    self.milestones.iter().all(|m| m.dispute_status != DisputeStatus::Open)
+            }
+            BudgetStatus::Expired => self.expired_resubmissions < Self::MAX_EXPIRED_RESUBMISSIONS,
+            BudgetStatus::Pending | BudgetStatus::Executed => false,
+        }
+    }
+    
+    /// Fail unless a revision with these terms may replace this budget
+    ///
+    /// For a rejected budget the cooldown after rejection must have passed.
+    /// Revisions of rejected and approved budgets must change the total or
+    /// at least one milestone (compared by `terms_hash`, so a resubmission
+    /// that only rewords the budget description is rejected too). An
+    /// expired budget was never decided, so it can be resubmitted right
+    /// away and unchanged.
+    pub fn require_revision_allowed(
+        &self,
+        total_amount: u64,
+        revised: &[Milestone],
+        current_timestamp: i64,
+    ) -> Result<()> {
+        if self.status == BudgetStatus::Expired {
+            return Ok(());
+        }
+        
+        require!(
+            self.status != BudgetStatus::Rejected ||
+                current_timestamp >= self.finalized_at.saturating_add(Self::REVISION_COOLDOWN_SECONDS),
+            EventError::RevisionCooldownActive
+        );
+        require!(
+            Self::terms_hash(total_amount, revised) != Self::terms_hash(self.total_amount, &self.milestones),
+            EventError::IdenticalBudgetRevision
+        );
+        Ok(())
+    }
+    
+    /// Hash of the terms backers vote on: the total and each milestone's
+    /// description, percentage and unlock date (release state is ignored)
+    pub fn terms_hash(total_amount: u64, milestones: &[Milestone]) -> [u8; 32] {
+        let mut terms = total_amount.to_le_bytes().to_vec();
+        for milestone in milestones {
+            terms.extend_from_slice(&(milestone.description.len() as u32).to_le_bytes());
+            terms.extend_from_slice(milestone.description.as_bytes());
+            terms.extend_from_slice(&milestone.release_percentage.to_le_bytes());
+            terms.extend_from_slice(&milestone.unlock_date.to_le_bytes());
+        }
+        hashv(&[&terms]).to_bytes()
+    }
+    
+    /// Check whether voting on this budget has been finalized
+    pub fn is_finalized(&self) -> bool {
+        self.status != BudgetStatus::Pending
+    }
+    
+    /// Check whether this budget account can be closed
+    ///
+    /// Executed budgets are done. A rejected, expired or approved budget can
+    /// be closed once the revision that supersedes it has been approved (its
+    /// released milestones were carried forward). Pending budgets and
+    /// approved ones that weren't revised are still needed for voting and
+    /// milestone releases.
+    pub fn can_close(&self, superseded_by: Option<&Budget>) -> bool {
+        match self.status {
+            BudgetStatus::Executed => true,
+            BudgetStatus::Rejected | BudgetStatus::Expired | BudgetStatus::Approved => {
+                superseded_by.is_some_and(|revision| {
+                    revision.revision_count == self.revision_count + 1 &&
+                        matches!(revision.status, BudgetStatus::Approved | BudgetStatus::Executed)
+                })
+            }
+            BudgetStatus::Pending => false,
+        }
+    }
+    
+    /// Check that a milestone count is within 1..=MAX_MILESTONES
+    pub fn valid_milestone_count(count: usize) -> bool {
+        (1..=Self::MAX_MILESTONES).contains(&count)
+    }
+    
+    /// Carry already-released milestones forward into a revised milestone set
+    ///
+    /// Any milestone with `released_amount > 0` must appear at the same index
+    /// with the same description, and its percentage of `new_total_amount`
+    /// must still cover what was released. Those milestones are then copied
+    /// over verbatim (keeping their release state), taking only the revised
+    /// percentage from `revised`.
+    pub fn carry_forward_released(
+        &self,
+        revised: &mut [Milestone],
+        new_total_amount: u64,
+    ) -> Result<()> {
+        for (index, old) in self.milestones.iter().enumerate() {
+            if old.released_amount == 0 {
+                continue;
+            }
+            
+            let new = revised
+                .get_mut(index)
+                .ok_or(EventError::CannotReviseReleasedMilestone)?;
+            
+            require!(
+                new.description == old.description,
+                EventError::CannotReviseReleasedMilestone
+            );
+            require!(
+                new.release_percentage >= old.release_percentage &&
+                    new.amount(new_total_amount) >= old.released_amount,
+                EventError::CannotReviseReleasedMilestone
+            );
+            
+            let release_percentage = new.release_percentage;
+            *new = old.clone();
+            new.release_percentage = release_percentage;
+        }
+        
+        Ok(())
     }
     
     /// Calculate total milestone percentages (should equal 10000 = 100%)
     pub fn validate_milestone_percentages(&self) -> bool {
-        let total: u16 = self.milestones.iter().map(|m| m.release_percentage).sum();
+        // Sum as u32 so up to MAX_MILESTONES large percentages can't overflow
+        let total: u32 = self.milestones.iter().map(|m| m.release_percentage as u32).sum();
         total == 10_000
     }
 }
@@ -100,6 +444,24 @@ pub struct Milestone {
     
     /// Actual amount released (calculated from percentage)
     pub released_amount: u64,
+    
+    /// Dispute raised against this milestone, if any
+    pub dispute_status: DisputeStatus,
+    
+    /// Release vote state (budgets with `per_milestone_approval` only)
+    pub approval: MilestoneApproval,
+    
+    /// When the milestone's release vote ends
+    pub vote_end: i64,
+    
+    /// Voting power approving the release
+    pub votes_for: u64,
+    
+    /// Voting power rejecting the release
+    pub votes_against: u64,
+    
+    /// Contribution amounts behind the release votes (quorum turnout)
+    pub turnout_amount: u64,
 }
 
 impl Milestone {
@@ -108,12 +470,128 @@ impl Milestone {
         2 +  // release_percentage
         8 +  // unlock_date
         1 +  // released
-        8;   // released_amount
+        8 +  // released_amount
+        1 +  // dispute_status (enum)
+        1 +  // approval (enum)
+        8 +  // vote_end
+        8 +  // votes_for
+        8 +  // votes_against
+        8;   // turnout_amount
+    
+    /// Calculate space needed for a Milestone with the given description
+    pub fn space(description: &str) -> usize {
+        Self::LEN - Budget::MAX_MILESTONE_DESC_LEN + description.len()
+    }
+    
+    /// Amount this milestone releases out of a budget total
+    pub fn amount(&self, total_amount: u64) -> u64 {
+        (total_amount as u128 * self.release_percentage as u128 / 10_000) as u64
+    }
     
     /// Check if milestone is ready to be released
     pub fn is_unlocked(&self, current_timestamp: i64) -> bool {
         current_timestamp >= self.unlock_date && !self.released
     }
+    
+    /// Fail while a dispute is open or after an upheld dispute voided it
+    pub fn require_not_disputed(&self) -> Result<()> {
+        match self.dispute_status {
+            DisputeStatus::Open => err!(EventError::MilestoneDisputed),
+            DisputeStatus::Upheld => err!(EventError::MilestoneVoided),
+            DisputeStatus::None | DisputeStatus::Dismissed => Ok(()),
+        }
+    }
+    
+    /// Fail unless the milestone may be released under the budget's rules
+    ///
+    /// Budgets with `per_milestone_approval` need the milestone's own
+    /// release vote to have passed.
+    pub fn require_release_approved(&self, per_milestone_approval: bool) -> Result<()> {
+        require!(
+            !per_milestone_approval || self.approval == MilestoneApproval::Approved,
+            EventError::MilestoneNotApproved
+        );
+        Ok(())
+    }
+    
+    /// Add a vote's power to the milestone's approve or reject tally, and
+    /// the contribution behind it to `turnout_amount`
+    ///
+    /// Nothing changes on overflow.
+    pub fn add_vote(&mut self, voting_power: u64, contribution_amount: u64, approve: bool) -> Option<()> {
+        let turnout_amount = self.turnout_amount.checked_add(contribution_amount)?;
+        if approve {
+            self.votes_for = self.votes_for.checked_add(voting_power)?;
+        } else {
+            self.votes_against = self.votes_against.checked_add(voting_power)?;
+        }
+        self.turnout_amount = turnout_amount;
+        Some(())
+    }
+    
+    /// Whether the milestone is finished: released, or voided by a dispute
+    /// or a failed release vote
+    pub fn is_settled(&self) -> bool {
+        self.released
+            || self.dispute_status == DisputeStatus::Upheld
+            || self.approval == MilestoneApproval::Rejected
+    }
+}
+
+/// How budget voting power is derived from a backer's contribution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VotingModel {
+    /// One lamport, one vote
+    Linear,
+    
+    /// Square root of the contribution, so large backers weigh less per lamport
+    Quadratic,
+}
+
+impl VotingModel {
+    /// Voting power of a contribution under this model
+    pub fn voting_power(&self, contribution_amount: u64) -> u64 {
+        match self {
+            VotingModel::Linear => contribution_amount,
+            VotingModel::Quadratic => isqrt(contribution_amount),
+        }
+    }
+}
+
+/// Integer square root: the largest `r` with `r * r <= value`
+///
+/// Newton's method from a power of two at or above the root; every step
+/// stays below 2^33, so nothing can overflow.
+pub fn isqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    
+    let bits = 64 - value.leading_zeros();
+    let mut root = 1u64 << bits.div_ceil(2);
+    loop {
+        let next = (root + value / root) / 2;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// Release vote state of a milestone
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum MilestoneApproval {
+    /// No release vote opened
+    None,
+    
+    /// Release vote open until `vote_end`
+    Voting,
+    
+    /// Backers approved the release
+    Approved,
+    
+    /// Backers rejected the release; the milestone is void
+    Rejected,
 }
 
 /// Budget lifecycle status
@@ -130,6 +608,10 @@ pub enum BudgetStatus {
     
     /// All milestones released, budget complete
     Executed,
+    
+    /// Turnout stayed below the floor; can be resubmitted without using a
+    /// revision
+    Expired,
 }
 
 impl Default for BudgetStatus {
@@ -141,6 +623,7 @@ impl Default for BudgetStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::{PlatformConfig, UNSET_TIMESTAMP};
     
     #[test]
     fn test_milestone_percentages() {
@@ -148,13 +631,19 @@ mod tests {
             campaign: Pubkey::default(),
             total_amount: 100_000_000_000,
             description: "Test budget".to_string(),
-            milestones: [
+            milestones: vec![
                 Milestone {
                     description: "M1".to_string(),
                     release_percentage: 5000, // 50%
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
                 Milestone {
                     description: "M2".to_string(),
@@ -162,6 +651,12 @@ mod tests {
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
                 Milestone {
                     description: "M3".to_string(),
@@ -169,14 +664,33 @@ mod tests {
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
             ],
             status: BudgetStatus::Pending,
             voting_end: 0,
             votes_for: 0,
             votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
             revision_count: 0,
             created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
             bump: 0,
         };
         
@@ -189,13 +703,19 @@ mod tests {
             campaign: Pubkey::default(),
             total_amount: 0,
             description: String::new(),
-            milestones: [
+            milestones: vec![
                 Milestone {
                     description: String::new(),
                     release_percentage: 3333,
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
                 Milestone {
                     description: String::new(),
@@ -203,6 +723,12 @@ mod tests {
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
                 Milestone {
                     description: String::new(),
@@ -210,14 +736,33 @@ mod tests {
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
             ],
             status: BudgetStatus::Pending,
             voting_end: 1000,
             votes_for: 60_000_000_000,
             votes_against: 40_000_000_000,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
             revision_count: 0,
             created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
             bump: 0,
         };
         
@@ -226,6 +771,13 @@ mod tests {
         budget.votes_for = 40_000_000_000;
         budget.votes_against = 60_000_000_000;
         assert!(!budget.is_approved());
+        
+        // Ties and empty tallies reject
+        budget.votes_for = 60_000_000_000;
+        assert!(!budget.is_approved());
+        budget.votes_for = 0;
+        budget.votes_against = 0;
+        assert!(!budget.is_approved());
     }
     
     #[test]
@@ -234,13 +786,19 @@ mod tests {
             campaign: Pubkey::default(),
             total_amount: 0,
             description: String::new(),
-            milestones: [
+            milestones: vec![
                 Milestone {
                     description: String::new(),
                     release_percentage: 3333,
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
                 Milestone {
                     description: String::new(),
@@ -248,6 +806,12 @@ mod tests {
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
                 Milestone {
                     description: String::new(),
@@ -255,14 +819,33 @@ mod tests {
                     unlock_date: 0,
                     released: false,
                     released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
                 },
             ],
             status: BudgetStatus::Rejected,
             voting_end: 0,
             votes_for: 0,
             votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
             revision_count: 0,
             created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
             bump: 0,
         };
         
@@ -271,8 +854,795 @@ mod tests {
         budget.revision_count = 2;
         assert!(!budget.can_revise());
         
+        // Approved budgets while milestones are left to settle and none is disputed
         budget.revision_count = 0;
         budget.status = BudgetStatus::Approved;
+        assert!(budget.can_revise());
+        budget.milestones[1].dispute_status = DisputeStatus::Open;
+        assert!(!budget.can_revise());
+        budget.milestones[1].dispute_status = DisputeStatus::None;
+        for milestone in budget.milestones.iter_mut() {
+            milestone.released = true;
+        }
+        assert!(!budget.can_revise());
+        for milestone in budget.milestones.iter_mut() {
+            milestone.released = false;
+        }
+        budget.revision_count = 2;
+        assert!(!budget.can_revise());
+        
+        budget.status = BudgetStatus::Pending;
+        budget.revision_count = 0;
         assert!(!budget.can_revise());
+        
+        // Expired votes can be resubmitted regardless of revisions used,
+        // up to their own limit
+        budget.status = BudgetStatus::Expired;
+        budget.revision_count = 2;
+        assert!(budget.can_revise());
+        budget.expired_resubmissions = Budget::MAX_EXPIRED_RESUBMISSIONS;
+        assert!(!budget.can_revise());
+        budget.expired_resubmissions = 0;
+        
+        budget.status = BudgetStatus::Rejected;
+        budget.expired_resubmissions = 1;
+        assert_eq!(budget.revisions_used(), 1);
+        assert!(budget.can_revise());
+    }
+    
+    fn milestones(percentages: &[u16]) -> Vec<Milestone> {
+        percentages
+            .iter()
+            .map(|&release_percentage| Milestone {
+                description: String::new(),
+                release_percentage,
+                unlock_date: 0,
+                released: false,
+                released_amount: 0,
+                dispute_status: DisputeStatus::None,
+                approval: MilestoneApproval::None,
+                vote_end: 0,
+                votes_for: 0,
+                votes_against: 0,
+                turnout_amount: 0,
+            })
+            .collect()
+    }
+    
+    #[test]
+    fn test_variable_milestone_counts() {
+        let mut budget = Budget {
+            campaign: Pubkey::default(),
+            total_amount: 0,
+            description: String::new(),
+            milestones: milestones(&[10_000]),
+            status: BudgetStatus::Pending,
+            voting_end: 0,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
+            bump: 0,
+        };
+        
+        // 1 milestone releasing everything
+        assert!(Budget::valid_milestone_count(budget.milestones.len()));
+        assert!(budget.validate_milestone_percentages());
+        
+        // 3 milestones
+        budget.milestones = milestones(&[5000, 3000, 2000]);
+        assert!(Budget::valid_milestone_count(budget.milestones.len()));
+        assert!(budget.validate_milestone_percentages());
+        
+        // 8 milestones (the maximum)
+        budget.milestones = milestones(&[1250; 8]);
+        assert!(Budget::valid_milestone_count(budget.milestones.len()));
+        assert!(budget.validate_milestone_percentages());
+        
+        // Large percentages don't overflow the sum
+        budget.milestones = milestones(&[10_000; 8]);
+        assert!(!budget.validate_milestone_percentages());
+    }
+    
+    #[test]
+    fn test_invalid_milestone_counts() {
+        assert!(!Budget::valid_milestone_count(0));
+        assert!(!Budget::valid_milestone_count(Budget::MAX_MILESTONES + 1));
+        assert!(!Budget::valid_milestone_count(9));
+    }
+    
+    #[test]
+    fn test_budget_len_fits_max_milestones() {
+        let budget = Budget {
+            campaign: Pubkey::default(),
+            total_amount: 0,
+            description: "d".repeat(Budget::MAX_DESCRIPTION_LEN),
+            milestones: (0..Budget::MAX_MILESTONES)
+                .map(|_| Milestone {
+                    description: "m".repeat(Budget::MAX_MILESTONE_DESC_LEN),
+                    release_percentage: 1250,
+                    unlock_date: 0,
+                    released: false,
+                    released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
+                })
+                .collect(),
+            status: BudgetStatus::Pending,
+            voting_end: 0,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
+            bump: 0,
+        };
+        
+        let serialized = budget.try_to_vec().unwrap();
+        assert!(8 + serialized.len() <= Budget::LEN);
+    }
+    
+    #[test]
+    fn test_quorum() {
+        let mut budget = Budget {
+            campaign: Pubkey::default(),
+            total_amount: 0,
+            description: String::new(),
+            milestones: milestones(&[10_000]),
+            status: BudgetStatus::Pending,
+            voting_end: 0,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: 2000, // 20%
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
+            bump: 0,
+        };
+        
+        let total_raised = 100_000_000_000; // 100 SOL → quorum is 20 SOL
+        
+        // Barely met: exactly 20 SOL of turnout, including votes against
+        budget.add_vote(15_000_000_000, 15_000_000_000, VoteChoice::Approve).unwrap();
+        budget.add_vote(5_000_000_000, 5_000_000_000, VoteChoice::Reject).unwrap();
+        assert!(budget.quorum_met(total_raised));
+        
+        // Barely missed: one lamport short
+        budget.remove_vote(5_000_000_000, 5_000_000_000, VoteChoice::Reject).unwrap();
+        budget.add_vote(4_999_999_999, 4_999_999_999, VoteChoice::Reject).unwrap();
+        assert!(!budget.quorum_met(total_raised));
+        
+        // A single tiny yes vote is approved by majority but misses quorum
+        budget.remove_vote(15_000_000_000, 15_000_000_000, VoteChoice::Approve).unwrap();
+        budget.remove_vote(4_999_999_999, 4_999_999_999, VoteChoice::Reject).unwrap();
+        budget.add_vote(10_000_000, 10_000_000, VoteChoice::Approve).unwrap();
+        assert!(budget.is_approved());
+        assert!(!budget.quorum_met(total_raised));
+        
+        // Abstentions count toward quorum but never decide the outcome
+        budget.add_vote(20_000_000_000, 20_000_000_000, VoteChoice::Abstain).unwrap();
+        assert!(budget.quorum_met(total_raised));
+        assert_eq!(budget.turnout_bps(total_raised), 2001);
+        budget.votes_for = 0;
+        assert!(budget.quorum_met(total_raised));
+        assert!(!budget.is_approved());
+        
+        // Zero quorum is always met
+        budget.quorum_bps = 0;
+        assert!(budget.quorum_met(total_raised));
+    }
+    
+    #[test]
+    fn test_outcome_boundaries() {
+        let mut budget = Budget {
+            campaign: Pubkey::default(),
+            total_amount: 0,
+            description: String::new(),
+            milestones: milestones(&[10_000]),
+            status: BudgetStatus::Pending,
+            voting_end: 0,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: 2000, // 20%
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 100_000_000_000, // 100 SOL
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: 1000, // 10%
+            expired_resubmissions: 0,
+            bump: 0,
+        };
+        
+        // No votes at all expire rather than reject
+        assert_eq!(budget.outcome(), BudgetStatus::Expired);
+        
+        // One lamport under the floor: a lone yes vote still expires
+        budget.add_vote(9_999_999_999, 9_999_999_999, VoteChoice::Approve).unwrap();
+        assert_eq!(budget.outcome(), BudgetStatus::Expired);
+        
+        // Exactly on the floor the vote counts, but misses quorum
+        budget.add_vote(1, 1, VoteChoice::Approve).unwrap();
+        assert!(budget.min_turnout_met(budget.total_raised_snapshot));
+        assert_eq!(budget.outcome(), BudgetStatus::Rejected);
+        
+        // Quorum reached with a 50/50 tie rejects
+        budget.add_vote(10_000_000_000, 10_000_000_000, VoteChoice::Reject).unwrap();
+        assert!(budget.quorum_met(budget.total_raised_snapshot));
+        assert_eq!(budget.outcome(), BudgetStatus::Rejected);
+        
+        // One lamport of power more for than against approves
+        budget.add_vote(1, 1, VoteChoice::Approve).unwrap();
+        assert_eq!(budget.outcome(), BudgetStatus::Approved);
+        
+        // With a zero floor nothing expires; an empty vote is rejected
+        let empty = Budget {
+            min_turnout_bps: 0,
+            votes_for: 0,
+            votes_against: 0,
+            turnout_amount: 0,
+            ..budget
+        };
+        assert_eq!(empty.outcome(), BudgetStatus::Rejected);
+    }
+    
+    #[test]
+    fn test_vote_tally_flip_flops() {
+        let mut budget = Budget {
+            campaign: Pubkey::default(),
+            total_amount: 0,
+            description: String::new(),
+            milestones: milestones(&[10_000]),
+            status: BudgetStatus::Pending,
+            voting_end: 0,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
+            bump: 0,
+        };
+        
+        let alice = 30_000_000_000;
+        let bob = 20_000_000_000;
+        
+        budget.add_vote(alice, alice, VoteChoice::Reject).unwrap();
+        budget.add_vote(bob, bob, VoteChoice::Approve).unwrap();
+        assert_eq!((budget.votes_for, budget.votes_against), (bob, alice));
+        
+        // Alice flips to approve, back to reject, and to approve again
+        for (from, to) in [
+            (VoteChoice::Reject, VoteChoice::Approve),
+            (VoteChoice::Approve, VoteChoice::Reject),
+            (VoteChoice::Reject, VoteChoice::Approve),
+        ] {
+            budget.remove_vote(alice, alice, from).unwrap();
+            budget.add_vote(alice, alice, to).unwrap();
+            assert_eq!(budget.votes_for + budget.votes_against, alice + bob);
+        }
+        assert_eq!((budget.votes_for, budget.votes_against), (alice + bob, 0));
+        
+        // Bob withdraws entirely
+        budget.remove_vote(bob, bob, VoteChoice::Approve).unwrap();
+        assert_eq!((budget.votes_for, budget.votes_against), (alice, 0));
+        
+        // Removing weight that was never added fails instead of wrapping
+        assert!(budget.remove_vote(bob, bob, VoteChoice::Reject).is_none());
+        assert_eq!((budget.votes_for, budget.votes_against), (alice, 0));
+    }
+    
+    #[test]
+    fn test_vote_cast_counters() {
+        let mut budget = Budget {
+            campaign: Pubkey::default(),
+            total_amount: 0,
+            description: String::new(),
+            milestones: milestones(&[10_000]),
+            status: BudgetStatus::Pending,
+            voting_end: 0,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
+            bump: 0,
+        };
+        let counters = |budget: &Budget| (budget.votes_cast_count, budget.total_voting_power_cast);
+        
+        let alice = 30_000_000_000;
+        let bob = 20_000_000_000;
+        let carol = 10_000_000_000;
+        
+        // Vote: each vote is counted once with its power
+        budget.add_vote(alice, alice, VoteChoice::Approve).unwrap();
+        budget.add_vote(bob, bob, VoteChoice::Reject).unwrap();
+        assert_eq!(counters(&budget), (2, alice + bob));
+        
+        // Change: moving sides leaves the counters alone
+        budget.remove_vote(bob, bob, VoteChoice::Reject).unwrap();
+        budget.add_vote(bob, bob, VoteChoice::Approve).unwrap();
+        assert_eq!(counters(&budget), (2, alice + bob));
+        assert_eq!(budget.votes_for, alice + bob);
+        
+        // Vote then withdraw: counted and uncounted
+        budget.add_vote(carol, carol, VoteChoice::Reject).unwrap();
+        assert_eq!(counters(&budget), (3, alice + bob + carol));
+        budget.remove_vote(carol, carol, VoteChoice::Reject).unwrap();
+        assert_eq!(counters(&budget), (2, alice + bob));
+        
+        // Change then withdraw
+        budget.remove_vote(alice, alice, VoteChoice::Approve).unwrap();
+        budget.add_vote(alice, alice, VoteChoice::Reject).unwrap();
+        budget.remove_vote(alice, alice, VoteChoice::Reject).unwrap();
+        assert_eq!(counters(&budget), (1, bob));
+        
+        // Counters always match the tallies
+        assert_eq!(budget.total_voting_power_cast, budget.votes_for + budget.votes_against);
+        assert_eq!(budget.turnout_bps(100_000_000_000), 2000);
+        
+        // A failed remove leaves every counter untouched
+        assert!(budget.remove_vote(alice, alice, VoteChoice::Reject).is_none());
+        assert_eq!(counters(&budget), (1, bob));
+        assert_eq!((budget.votes_for, budget.votes_against), (bob, 0));
+        
+        // As does an overflowing add
+        assert!(budget.add_vote(u64::MAX, u64::MAX, VoteChoice::Reject).is_none());
+        assert_eq!(counters(&budget), (1, bob));
+    }
+    
+    #[test]
+    fn test_short_budget_space() {
+        let budget = Budget {
+            campaign: Pubkey::default(),
+            total_amount: 10_000_000_000,
+            description: "Venue and catering".to_string(),
+            milestones: vec![
+                Milestone {
+                    description: "Deposit".to_string(),
+                    release_percentage: 4000,
+                    unlock_date: -7,
+                    released: false,
+                    released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
+                },
+                Milestone {
+                    description: "Balance".to_string(),
+                    release_percentage: 6000,
+                    unlock_date: 1,
+                    released: false,
+                    released_amount: 0,
+                    dispute_status: DisputeStatus::None,
+                    approval: MilestoneApproval::None,
+                    vote_end: 0,
+                    votes_for: 0,
+                    votes_against: 0,
+                    turnout_amount: 0,
+                },
+            ],
+            status: BudgetStatus::Pending,
+            voting_end: 1000,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
+            bump: 255,
+        };
+        
+        let space = Budget::space(
+            &budget.description,
+            budget.milestones.iter().map(|m| m.description.as_str()),
+        );
+        
+        // Exactly the serialized size, far below the worst case
+        let mut data = Vec::new();
+        budget.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), space);
+        assert!(space < Budget::LEN / 2);
+        
+        // Round-trips from an account buffer of that size
+        let decoded = Budget::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(decoded.description, budget.description);
+        assert_eq!(decoded.milestones.len(), 2);
+        assert_eq!(decoded.milestones[0].description, "Deposit");
+        assert_eq!(decoded.milestones[1].release_percentage, 6000);
+        assert_eq!(decoded.milestones[1].unlock_date, 1);
+        assert_eq!(decoded.total_amount, budget.total_amount);
+        assert_eq!(decoded.voting_end, budget.voting_end);
+        assert_eq!(decoded.bump, 255);
+    }
+    
+    #[test]
+    fn test_max_budget_space_matches_len() {
+        let description = "d".repeat(Budget::MAX_DESCRIPTION_LEN);
+        let milestone = "m".repeat(Budget::MAX_MILESTONE_DESC_LEN);
+        let milestones = vec![milestone.as_str(); Budget::MAX_MILESTONES];
+        
+        assert_eq!(Budget::space(&description, milestones), Budget::LEN);
+    }
+    
+    #[test]
+    fn test_reminder_window() {
+        let mut budget = Budget {
+            campaign: Pubkey::default(),
+            total_amount: 0,
+            description: String::new(),
+            milestones: milestones(&[10_000]),
+            status: BudgetStatus::Pending,
+            voting_end: 100_000,
+            votes_for: 3_000_000_000,
+            votes_against: 1_000_000_000,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 4_000_000_000,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
+            bump: 0,
+        };
+        let window_start = budget.voting_end - Budget::REMINDER_WINDOW_SECONDS;
+        
+        // Exactly 24h left is not yet "less than 24h"
+        assert!(!budget.in_reminder_window(window_start));
+        
+        // One second later the window opens
+        assert!(budget.in_reminder_window(window_start + 1));
+        assert!(budget.in_reminder_window(budget.voting_end - 1));
+        
+        // Closes when voting ends
+        assert!(!budget.in_reminder_window(budget.voting_end));
+        
+        // Only while pending
+        budget.status = BudgetStatus::Approved;
+        assert!(!budget.in_reminder_window(window_start + 1));
+        
+        // 4 SOL of turnout on 10 SOL raised is 40%
+        assert_eq!(budget.turnout_bps(10_000_000_000), 4000);
+        assert_eq!(budget.turnout_bps(0), 0);
+    }
+    
+    fn partially_released_budget() -> Budget {
+        let mut released = milestones(&[4000, 6000]);
+        released[0].description = "Venue deposit".to_string();
+        released[0].unlock_date = 1_000;
+        released[0].released = true;
+        released[0].released_amount = 40_000_000_000; // 40% of 100 SOL
+        
+        Budget {
+            campaign: Pubkey::default(),
+            total_amount: 100_000_000_000,
+            description: String::new(),
+            milestones: released,
+            status: BudgetStatus::Approved,
+            voting_end: 0,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps: Budget::DEFAULT_QUORUM_BPS,
+            revision_count: 0,
+            created_at: 0,
+            reminder_emitted: false,
+            votes_cast_count: 0,
+            total_voting_power_cast: 0,
+            finalized_at: UNSET_TIMESTAMP,
+            total_raised_snapshot: 0,
+            snapshot_ts: 0,
+            per_milestone_approval: false,
+            voting_model: VotingModel::Linear,
+            turnout_amount: 0,
+            min_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            expired_resubmissions: 0,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_revise_carries_released_milestone_forward() {
+        let old = partially_released_budget();
+        
+        // Same milestone, higher percentage of a slightly smaller total
+        let mut revised = milestones(&[5000, 2500, 2500]);
+        revised[0].description = "Venue deposit".to_string();
+        old.carry_forward_released(&mut revised, 90_000_000_000).unwrap();
+        
+        assert!(revised[0].released);
+        assert_eq!(revised[0].released_amount, 40_000_000_000);
+        assert_eq!(revised[0].unlock_date, 1_000);
+        assert_eq!(revised[0].release_percentage, 5000);
+        
+        // Unreleased milestones are taken from the revision as-is
+        assert!(!revised[1].released);
+        assert_eq!(revised[1].release_percentage, 2500);
+    }
+    
+    #[test]
+    fn test_revise_rejects_stranding_released_milestone() {
+        let old = partially_released_budget();
+        let revision = |percentages: &[u16], description: &str| {
+            let mut revised = milestones(percentages);
+            revised[0].description = description.to_string();
+            revised
+        };
+        
+        // Percentage reduced
+        let mut revised = revision(&[3000, 7000], "Venue deposit");
+        assert_eq!(
+            old.carry_forward_released(&mut revised, 100_000_000_000).unwrap_err(),
+            EventError::CannotReviseReleasedMilestone.into()
+        );
+        
+        // Same percentage but the smaller total no longer covers the release
+        let mut revised = revision(&[4000, 6000], "Venue deposit");
+        assert_eq!(
+            old.carry_forward_released(&mut revised, 50_000_000_000).unwrap_err(),
+            EventError::CannotReviseReleasedMilestone.into()
+        );
+        
+        // Released milestone replaced by a different one
+        let mut revised = revision(&[4000, 6000], "Catering");
+        assert_eq!(
+            old.carry_forward_released(&mut revised, 100_000_000_000).unwrap_err(),
+            EventError::CannotReviseReleasedMilestone.into()
+        );
+        
+        // Released milestone dropped entirely
+        let mut revised: Vec<Milestone> = Vec::new();
+        assert_eq!(
+            old.carry_forward_released(&mut revised, 100_000_000_000).unwrap_err(),
+            EventError::CannotReviseReleasedMilestone.into()
+        );
+    }
+    
+    #[test]
+    fn test_revision_cooldown_and_identical_terms() {
+        let mut old = partially_released_budget();
+        old.status = BudgetStatus::Rejected;
+        old.finalized_at = 10_000;
+        let cooled_down = old.finalized_at + Budget::REVISION_COOLDOWN_SECONDS;
+        
+        // A changed split is still held back until the cooldown passes
+        let mut changed = milestones(&[5000, 5000]);
+        changed[0].description = "Venue deposit".to_string();
+        old.carry_forward_released(&mut changed, old.total_amount).unwrap();
+        assert_eq!(
+            old.require_revision_allowed(old.total_amount, &changed, cooled_down - 1).unwrap_err(),
+            EventError::RevisionCooldownActive.into()
+        );
+        old.require_revision_allowed(old.total_amount, &changed, cooled_down).unwrap();
+        
+        // Identical milestones and total are rejected even after the cooldown
+        let mut identical = old.milestones.clone();
+        assert_eq!(
+            old.require_revision_allowed(old.total_amount, &identical, cooled_down).unwrap_err(),
+            EventError::IdenticalBudgetRevision.into()
+        );
+        
+        // Release state alone doesn't make a revision different
+        identical[0].released = false;
+        identical[0].released_amount = 0;
+        assert!(old.require_revision_allowed(old.total_amount, &identical, cooled_down).is_err());
+        
+        // A new total or unlock date does
+        old.require_revision_allowed(old.total_amount - 1, &old.milestones, cooled_down).unwrap();
+        identical[1].unlock_date += 1;
+        old.require_revision_allowed(old.total_amount, &identical, cooled_down).unwrap();
+        
+        // Approved budgets have no cooldown but still need new terms
+        old.status = BudgetStatus::Approved;
+        old.require_revision_allowed(old.total_amount, &changed, old.finalized_at).unwrap();
+        assert_eq!(
+            old.require_revision_allowed(old.total_amount, &old.milestones, old.finalized_at).unwrap_err(),
+            EventError::IdenticalBudgetRevision.into()
+        );
+    }
+    
+    #[test]
+    fn test_can_close() {
+        let mut budget = partially_released_budget();
+        let mut revision = partially_released_budget();
+        revision.revision_count = 1;
+        revision.status = BudgetStatus::Pending;
+        
+        // Pending and unrevised approved budgets are still in use
+        budget.status = BudgetStatus::Pending;
+        assert!(!budget.can_close(None));
+        assert!(!budget.is_finalized());
+        budget.status = BudgetStatus::Approved;
+        assert!(!budget.can_close(None));
+        assert!(budget.is_finalized());
+        
+        // A revised approved budget goes once the revision is approved
+        assert!(!budget.can_close(Some(&revision)));
+        revision.status = BudgetStatus::Approved;
+        assert!(budget.can_close(Some(&revision)));
+        revision.status = BudgetStatus::Pending;
+        
+        // Rejected budgets wait for an approved revision
+        budget.status = BudgetStatus::Rejected;
+        assert!(!budget.can_close(None));
+        assert!(!budget.can_close(Some(&revision)));
+        revision.status = BudgetStatus::Rejected;
+        assert!(!budget.can_close(Some(&revision)));
+        revision.status = BudgetStatus::Approved;
+        assert!(budget.can_close(Some(&revision)));
+        
+        // Only the direct revision supersedes it
+        revision.revision_count = 2;
+        assert!(!budget.can_close(Some(&revision)));
+        
+        // Executed budgets can always be closed
+        budget.status = BudgetStatus::Executed;
+        assert!(budget.can_close(None));
+    }
+    
+    #[test]
+    fn test_milestone_dispute_status() {
+        let mut milestone = milestones(&[10000]).remove(0);
+        assert!(milestone.require_not_disputed().is_ok());
+        assert!(!milestone.is_settled());
+        
+        // An open dispute blocks release
+        milestone.dispute_status = DisputeStatus::Open;
+        assert_eq!(milestone.require_not_disputed().unwrap_err(), EventError::MilestoneDisputed.into());
+        
+        // A dismissed dispute lets it be released as normal
+        milestone.dispute_status = DisputeStatus::Dismissed;
+        assert!(milestone.require_not_disputed().is_ok());
+        assert!(!milestone.is_settled());
+        
+        // An upheld dispute voids it for good
+        milestone.dispute_status = DisputeStatus::Upheld;
+        assert_eq!(milestone.require_not_disputed().unwrap_err(), EventError::MilestoneVoided.into());
+        assert!(milestone.is_settled());
+    }
+    
+    #[test]
+    fn test_milestone_release_approval() {
+        let mut milestone = milestones(&[10000]).remove(0);
+        
+        // Budgets without per-milestone votes release as before
+        assert!(milestone.require_release_approved(false).is_ok());
+        let not_approved: Error = EventError::MilestoneNotApproved.into();
+        assert_eq!(milestone.require_release_approved(true).unwrap_err(), not_approved);
+        
+        milestone.approval = MilestoneApproval::Voting;
+        milestone.add_vote(6, 6, true).unwrap();
+        milestone.add_vote(4, 4, false).unwrap();
+        assert_eq!((milestone.votes_for, milestone.votes_against), (6, 4));
+        assert!(milestone.add_vote(u64::MAX, u64::MAX, true).is_none());
+        assert_eq!(milestone.votes_for, 6);
+        assert_eq!(milestone.require_release_approved(true).unwrap_err(), not_approved);
+        
+        milestone.approval = MilestoneApproval::Approved;
+        assert!(milestone.require_release_approved(true).is_ok());
+        assert!(!milestone.is_settled());
+        
+        // A rejected release voids the milestone
+        milestone.approval = MilestoneApproval::Rejected;
+        assert_eq!(milestone.require_release_approved(true).unwrap_err(), not_approved);
+        assert!(milestone.is_settled());
+    }
+    
+    #[test]
+    fn test_isqrt() {
+        for value in [0, 1, 2, 3, 4, 8, 9, 10, 99, 100, 1_000_000_000, 100_000_000_000] {
+            let root = isqrt(value) as u128;
+            assert!(root * root <= value as u128 && (root + 1) * (root + 1) > value as u128, "{value}");
+        }
+        assert_eq!(isqrt(1_000_000_000), 31_622);
+        assert_eq!(isqrt(100_000_000_000), 316_227);
+        
+        // Perfect squares and their neighbours around the top of the range
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt((u32::MAX as u64) * (u32::MAX as u64)), u32::MAX as u64);
+        assert_eq!(isqrt((u32::MAX as u64) * (u32::MAX as u64) - 1), u32::MAX as u64 - 1);
+        assert_eq!(isqrt(1 << 62), 1 << 31);
+        assert_eq!(isqrt((1 << 62) - 1), (1 << 31) - 1);
+    }
+    
+    #[test]
+    fn test_voting_model_power() {
+        assert_eq!(VotingModel::Linear.voting_power(100_000_000_000), 100_000_000_000);
+        assert_eq!(VotingModel::Quadratic.voting_power(100_000_000_000), 316_227);
+        assert_eq!(VotingModel::Quadratic.voting_power(1), 1);
+        assert_eq!(VotingModel::Quadratic.voting_power(0), 0);
     }
 }
+
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+/// Per-wallet purchase counter for a ticket tier
+///
+/// Created on a wallet's first purchase from a tier (PDA: ["buyer", tier, buyer])
+/// and used to enforce `TicketTier::max_per_wallet`.
+#[account]
+pub struct BuyerRecord {
+    /// The tier purchases are counted against
+    pub tier: Pubkey,
+    
+    /// The purchasing wallet
+    pub buyer: Pubkey,
+    
+    /// Tickets currently held from purchases (decremented on refund)
+    pub purchased_count: u16,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl BuyerRecord {
+    /// Calculate space needed for BuyerRecord account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // tier
+        32 + // buyer
+        2 +  // purchased_count
+        1;   // bump
+    
+    /// Check if this wallet can buy `quantity` more tickets under the tier limit
+    ///
+    /// A `max_per_wallet` of 0 means unlimited.
+    pub fn can_purchase(&self, quantity: u16, max_per_wallet: u8) -> bool {
+        if max_per_wallet == 0 {
+            return true;
+        }
+        
+        match self.purchased_count.checked_add(quantity) {
+            Some(total) => total <= max_per_wallet as u16,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn record(purchased_count: u16) -> BuyerRecord {
+        BuyerRecord {
+            tier: Pubkey::default(),
+            buyer: Pubkey::default(),
+            purchased_count,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_limit_boundary() {
+        // Limit of 4: the 4th ticket is allowed, the 5th is not
+        assert!(record(0).can_purchase(1, 4));
+        assert!(record(3).can_purchase(1, 4));
+        assert!(!record(4).can_purchase(1, 4));
+    }
+    
+    #[test]
+    fn test_unlimited() {
+        assert!(record(0).can_purchase(1, 0));
+        assert!(record(u16::MAX).can_purchase(1, 0));
+    }
+    
+    #[test]
+    fn test_second_wallet_unaffected() {
+        // Each wallet has its own record, so a maxed-out wallet doesn't block others
+        let maxed_out = record(2);
+        let second_wallet = record(0);
+        
+        assert!(!maxed_out.can_purchase(1, 2));
+        assert!(second_wallet.can_purchase(1, 2));
+        assert!(second_wallet.can_purchase(2, 2));
+    }
+    
+    #[test]
+    fn test_refund_frees_slot() {
+        let mut record = record(2);
+        assert!(!record.can_purchase(1, 2));
+        
+        // Refund decrements the counter
+        record.purchased_count = record.purchased_count.saturating_sub(1);
+        assert!(record.can_purchase(1, 2));
+    }
+}
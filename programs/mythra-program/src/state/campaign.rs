@@ -1,4 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::state::{Contribution, PlatformConfig};
+use crate::errors::EventError;
+
+/// Funding progress thresholds (in basis points of the goal) that emit a
+/// `FundingMilestoneReached` event when crossed
+pub const FUNDING_MILESTONE_THRESHOLDS_BPS: [u16; 4] = [2500, 5000, 7500, 10000];
 
 /// Campaign account for crowdfunding events
 /// 
@@ -13,7 +19,7 @@ pub struct Campaign {
     /// The organizer who created this campaign (must match event.authority)
     pub organizer: Pubkey,
     
-    /// Funding goal in lamports
+    /// Funding goal, in units of `denomination`
     pub funding_goal: u64,
     
     /// Total amount raised so far in lamports
@@ -38,13 +44,13 @@ pub struct Campaign {
     /// Total revenue from ticket sales
     pub total_revenue: u64,
     
-    /// Calculated profit pool for backers (60% of profit)
+    /// Calculated profit pool for backers (backer_share_bps of profit)
     pub backer_pool: u64,
     
-    /// Calculated profit pool for organizer (35-40% of profit)
+    /// Calculated profit pool for organizer (organizer_share_bps of profit)
     pub organizer_pool: u64,
     
-    /// Platform fee pool (5% of profit)
+    /// Platform fee pool (platform_share_bps of profit)
     pub platform_pool: u64,
     
     /// Whether profit distribution has been calculated
@@ -53,6 +59,62 @@ pub struct Campaign {
     /// Whether organizer has claimed their profit share
     pub organizer_claimed: bool,
     
+    /// Backers' share of profit in basis points (agreed at creation)
+    pub backer_share_bps: u16,
+    
+    /// Organizer's share of profit in basis points (agreed at creation)
+    pub organizer_share_bps: u16,
+    
+    /// Platform's share of profit in basis points (agreed at creation)
+    pub platform_share_bps: u16,
+    
+    /// Loss recorded at distribution (expenses not covered by revenue)
+    pub recorded_loss: u64,
+    
+    /// Bump of the campaign escrow PDA (created rent-exempt with the campaign)
+    pub escrow_bump: u8,
+    
+    /// Number of backers that have claimed their profit share
+    pub backer_claims: u32,
+    
+    /// When the distribution was calculated
+    pub distributed_at: i64,
+    
+    /// Whether the organizer has used the one-time deadline extension
+    pub deadline_extended: bool,
+    
+    /// How refunds are paid, set once by the instruction that opens them
+    pub refund_mode: RefundMode,
+    
+    /// Lamports available for pro-rata refunds when they were opened
+    pub refund_pool: u64,
+    
+    /// Total raised when refunds were opened (denominator for pro-rata shares)
+    pub refund_base: u64,
+    
+    /// total_raised snapshotted at distribution (denominator for backer shares)
+    pub distribution_basis: u64,
+    
+    /// Unit the funding goal and contributions are counted in
+    pub denomination: Denomination,
+    
+    /// Escrow left for backers when distribution recorded no profit
+    /// (snapshotted at distribution, shared pro-rata like the backer pool)
+    pub recovery_pool: u64,
+    
+    /// Last moment backers can claim profit or loss recovery (set at distribution)
+    pub claim_deadline: i64,
+    
+    /// Lamports paid to backers out of the backer and recovery pools
+    pub backer_paid_out: u64,
+    
+    /// Whether unclaimed backer funds were swept after the claim deadline
+    pub backer_pool_swept: bool,
+    
+    /// Budget milestones are released from: the original budget or its
+    /// latest revision (default until a budget is submitted)
+    pub active_budget: Pubkey,
+    
     /// PDA bump seed
     pub bump: u8,
 }
@@ -75,8 +137,30 @@ impl Campaign {
         8 +  // platform_pool
         1 +  // distribution_complete
         1 +  // organizer_claimed
+        2 +  // backer_share_bps
+        2 +  // organizer_share_bps
+        2 +  // platform_share_bps
+        8 +  // recorded_loss
+        1 +  // escrow_bump
+        4 +  // backer_claims
+        8 +  // distributed_at
+        1 +  // deadline_extended
+        1 +  // refund_mode (enum)
+        8 +  // refund_pool
+        8 +  // refund_base
+        8 +  // distribution_basis
+        Denomination::LEN + // denomination (enum)
+        8 +  // recovery_pool
+        8 +  // claim_deadline
+        8 +  // backer_paid_out
+        1 +  // backer_pool_swept
+        32 + // active_budget
         1;   // bump
     
+    /// Fee paid to whoever cranks `distribute_backer_profits`, per
+    /// contribution processed (taken from the platform pool while it lasts)
+    pub const CRANK_FEE_PER_CONTRIBUTION_LAMPORTS: u64 = 5_000;
+    
     /// Check if campaign is still accepting contributions
     pub fn is_active(&self) -> bool {
         self.status == CampaignStatus::Pending
@@ -98,9 +182,292 @@ impl Campaign {
         (self.goal_reached() || self.deadline_passed(current_timestamp))
     }
     
+    /// Transition a pending campaign to Funded or Failed
+    ///
+    /// Status is checked first so a second finalize in the same slot fails
+    /// with `AlreadyFinalized` instead of re-running the transition. Returns
+    /// the new status; only the caller that gets `Ok` performed it.
+    pub fn finalize(&mut self, current_timestamp: i64) -> Result<CampaignStatus> {
+        require!(
+            self.status == CampaignStatus::Pending,
+            EventError::AlreadyFinalized
+        );
+        require!(
+            self.goal_reached() || self.deadline_passed(current_timestamp),
+            EventError::CampaignNotReadyToFinalize
+        );
+        
+        if self.goal_reached() {
+            self.status = CampaignStatus::Funded;
+        } else {
+            self.open_refunds(RefundMode::FullFailed, self.total_raised)?;
+        }
+        
+        Ok(self.status.clone())
+    }
+    
+    /// Move the deadline of a pending campaign to `new_deadline`
+    ///
+    /// Allowed once per campaign. The new deadline must be later than both
+    /// the current deadline and `current_timestamp`, and still before the
+    /// event starts. Returns the previous deadline.
+    pub fn extend_deadline(
+        &mut self,
+        new_deadline: i64,
+        current_timestamp: i64,
+        event_start_ts: i64,
+    ) -> Result<i64> {
+        require!(
+            self.status == CampaignStatus::Pending,
+            EventError::CampaignNotActive
+        );
+        require!(
+            !self.deadline_extended,
+            EventError::DeadlineAlreadyExtended
+        );
+        require!(
+            new_deadline > self.deadline,
+            EventError::DeadlineNotExtended
+        );
+        require!(
+            new_deadline > current_timestamp,
+            EventError::DeadlineInPast
+        );
+        require!(
+            new_deadline < event_start_ts,
+            EventError::DeadlineAfterEventStart
+        );
+        
+        let old_deadline = self.deadline;
+        self.deadline = new_deadline;
+        self.deadline_extended = true;
+        
+        Ok(old_deadline)
+    }
+    
+    /// Take `amount` of a backer's contribution back out of a pending campaign
+    ///
+    /// Only before the deadline while the campaign is still Pending. Budgets
+    /// (and so budget votes) only exist once a campaign is Funded, so no vote
+    /// tally can depend on the withdrawn amount. Returns true when the whole
+    /// contribution has been withdrawn and the backer no longer counts as a
+    /// contributor.
+    pub fn withdraw_contribution(
+        &mut self,
+        contribution: &mut Contribution,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<bool> {
+        require!(
+            self.is_active(),
+            EventError::CampaignNotActive
+        );
+        require!(
+            !self.deadline_passed(current_timestamp),
+            EventError::CampaignDeadlinePassed
+        );
+        require!(
+            amount > 0 && amount <= contribution.amount,
+            EventError::InvalidContributionAmount
+        );
+        
+        contribution.amount -= amount;
+        self.total_raised = self.total_raised
+            .checked_sub(amount)
+            .ok_or(EventError::ArithmeticOverflow)?;
+        
+        let fully_withdrawn = contribution.amount == 0;
+        if fully_withdrawn {
+            self.total_contributors = self.total_contributors.saturating_sub(1);
+        }
+        
+        Ok(fully_withdrawn)
+    }
+    
+    /// Lamports in the campaign escrow above its rent-exempt reserve
+    pub fn escrow_available(escrow_lamports: u64, rent_exempt_minimum: u64) -> u64 {
+        escrow_lamports.saturating_sub(rent_exempt_minimum)
+    }
+    
+    /// Require the escrow to pay `amount` without dipping into its rent reserve
+    pub fn require_escrow_covers(
+        escrow_lamports: u64,
+        rent_exempt_minimum: u64,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            amount <= Campaign::escrow_available(escrow_lamports, rent_exempt_minimum),
+            EventError::InsufficientBalance
+        );
+        Ok(())
+    }
+    
+    /// Check whether the campaign escrow may be closed
+    ///
+    /// A failed campaign's escrow closes once every contribution has been
+    /// refunded; a completed campaign's once nothing above the rent reserve
+    /// is left to claim.
+    pub fn escrow_closeable(&self, escrow_available: u64) -> bool {
+        match self.status {
+            CampaignStatus::Failed => self.total_raised == 0,
+            CampaignStatus::Completed => escrow_available == 0,
+            _ => false,
+        }
+    }
+    
+    /// Fail once the backer claim deadline has passed
+    pub fn require_claim_open(&self, current_timestamp: i64) -> Result<()> {
+        require!(
+            current_timestamp <= self.claim_deadline,
+            EventError::ClaimDeadlinePassed
+        );
+        Ok(())
+    }
+    
+    /// Count a payment to a backer out of the backer or recovery pool
+    pub fn record_backer_payout(&mut self, amount: u64) -> Result<()> {
+        self.backer_paid_out = self.backer_paid_out
+            .checked_add(amount)
+            .ok_or(EventError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Mark a backer's profit as claimed and return their share
+    ///
+    /// Shared by `claim_backer_profit` and the `distribute_backer_profits`
+    /// crank so both pay exactly the same amount. The payout itself is
+    /// counted with `record_backer_payout` once transferred.
+    pub fn settle_backer_claim(&mut self, contribution: &mut Contribution) -> Result<u64> {
+        require!(
+            !contribution.profit_claimed,
+            EventError::ProfitAlreadyClaimed
+        );
+        
+        let share = self.backer_share(contribution);
+        contribution.profit_share = share;
+        contribution.profit_claimed = true;
+        self.backer_claims = self.backer_claims
+            .checked_add(1)
+            .ok_or(EventError::ArithmeticOverflow)?;
+        
+        Ok(share)
+    }
+    
+    /// Take the crank fee for `processed` contributions out of the platform
+    /// pool, returning the fee (capped at what the pool has left)
+    pub fn take_crank_fee(&mut self, processed: u32) -> u64 {
+        let fee = (processed as u64)
+            .saturating_mul(Self::CRANK_FEE_PER_CONTRIBUTION_LAMPORTS)
+            .min(self.platform_pool);
+        self.platform_pool -= fee;
+        fee
+    }
+    
+    /// Close the backer pools after the claim deadline, returning what is
+    /// left unclaimed
+    ///
+    /// Can happen exactly once. Whatever backers haven't claimed from the
+    /// backer pool and the loss recovery pool is released for the sweep.
+    pub fn sweep_unclaimed(&mut self, current_timestamp: i64) -> Result<u64> {
+        require!(
+            self.status == CampaignStatus::Completed,
+            EventError::InvalidCampaignStatus
+        );
+        require!(
+            current_timestamp > self.claim_deadline,
+            EventError::ClaimDeadlineNotPassed
+        );
+        require!(
+            !self.backer_pool_swept,
+            EventError::BackerPoolAlreadySwept
+        );
+        
+        self.backer_pool_swept = true;
+        Ok(self.backer_pool
+            .saturating_add(self.recovery_pool)
+            .saturating_sub(self.backer_paid_out))
+    }
+    
+    /// Fail unless every refund and claim against the campaign is settled
+    ///
+    /// A failed campaign is settled once every contribution has been
+    /// refunded. A completed campaign is settled once the organizer has
+    /// claimed and either every backer has claimed (with no loss recovery
+    /// outstanding) or the unclaimed backer funds have been swept.
+    pub fn require_settled(&self) -> Result<()> {
+        let settled = match self.status {
+            CampaignStatus::Failed => self.total_raised == 0,
+            CampaignStatus::Completed => {
+                self.organizer_claimed && (
+                    self.backer_pool_swept ||
+                    (self.backer_claims >= self.total_contributors && self.recovery_pool == 0)
+                )
+            }
+            _ => return err!(EventError::InvalidCampaignStatus),
+        };
+        
+        require!(settled, EventError::CampaignNotSettled);
+        Ok(())
+    }
+    
     /// Check if refunds are available
     pub fn refunds_available(&self) -> bool {
-        self.status == CampaignStatus::Failed
+        self.refund_mode != RefundMode::None
+    }
+    
+    /// Fail the campaign and open refunds under `mode`
+    ///
+    /// The mode is written exactly once, in the same instruction as the
+    /// status change, so claims pay by mode instead of inferring it from
+    /// `status`. `refund_pool` is capped at what was raised.
+    pub fn open_refunds(&mut self, mode: RefundMode, refund_pool: u64) -> Result<()> {
+        require!(
+            self.refund_mode == RefundMode::None,
+            EventError::RefundsAlreadyOpen
+        );
+        
+        self.status = CampaignStatus::Failed;
+        self.refund_mode = mode;
+        self.refund_pool = refund_pool.min(self.total_raised);
+        self.refund_base = self.total_raised;
+        
+        Ok(())
+    }
+    
+    /// Open pro-rata refunds because the funded event was canceled
+    ///
+    /// Milestone releases may already have left the escrow, so backers
+    /// share whatever it still holds in proportion to their contribution.
+    pub fn cancel_for_event(&mut self, escrow_available: u64) -> Result<()> {
+        require!(
+            self.status == CampaignStatus::Pending || self.status == CampaignStatus::Funded,
+            EventError::InvalidCampaignStatus
+        );
+        
+        self.open_refunds(RefundMode::ProRataCanceled, escrow_available)
+    }
+    
+    /// Amount to refund `contribution` under the campaign's refund mode
+    ///
+    /// A contribution is refunded at most once; a second claim under a
+    /// different mode (e.g. a full refund followed by a pro-rata share) is
+    /// rejected with `RefundModeMismatch`.
+    pub fn refund_amount(&self, contribution: &Contribution) -> Result<u64> {
+        if contribution.refunded {
+            return if contribution.refund_mode == self.refund_mode {
+                err!(EventError::ContributionAlreadyRefunded)
+            } else {
+                err!(EventError::RefundModeMismatch)
+            };
+        }
+        
+        match self.refund_mode {
+            RefundMode::None => err!(EventError::CannotRefundFundedCampaign),
+            RefundMode::FullFailed => Ok(contribution.amount),
+            RefundMode::ProRataCanceled => {
+                Ok(contribution.calculate_share(self.refund_pool, self.refund_base))
+            }
+        }
     }
     
     /// Check if profit distribution can be calculated
@@ -109,6 +476,122 @@ impl Campaign {
         event_ended && 
         !self.distribution_complete
     }
+    
+    /// Check if a signer may trigger profit distribution
+    ///
+    /// Only the campaign organizer or the platform admin (when the platform
+    /// config has been initialized) choose when revenue is snapshotted.
+    pub fn can_trigger_distribution(
+        &self,
+        authority: &Pubkey,
+        config: Option<&PlatformConfig>,
+    ) -> bool {
+        *authority == self.organizer || config.is_some_and(|config| config.admin == *authority)
+    }
+    
+    /// Funding milestone thresholds crossed when raised goes from `before` to `after`
+    ///
+    /// A threshold is crossed when `before` is below it and `after` is at or
+    /// above it, so a single large contribution can cross several at once and
+    /// landing exactly on a threshold counts as reaching it.
+    pub fn crossed_funding_milestones(&self, before: u64, after: u64) -> Vec<u16> {
+        if self.funding_goal == 0 {
+            return Vec::new();
+        }
+        
+        // Compare in basis-point space with u128 to avoid rounding and overflow
+        let goal = self.funding_goal as u128;
+        let before_scaled = before as u128 * 10_000;
+        let after_scaled = after as u128 * 10_000;
+        
+        FUNDING_MILESTONE_THRESHOLDS_BPS
+            .iter()
+            .copied()
+            .filter(|&threshold_bps| {
+                let target = threshold_bps as u128 * goal;
+                before_scaled < target && after_scaled >= target
+            })
+            .collect()
+    }
+    
+    /// Backer's share of the backer pool, by net contribution over the
+    /// total raised at distribution
+    pub fn backer_share(&self, contribution: &Contribution) -> u64 {
+        contribution.calculate_share(self.backer_pool, self.distribution_basis)
+    }
+    
+    /// Split profit into (backer, organizer, platform) pools
+    ///
+    /// Uses the shares agreed at creation (e.g. 60/35/5). The organizer and
+    /// platform pools are rounded down and the backer pool takes the rest,
+    /// so rounding dust goes to backers and the pools always sum to `profit`.
+    pub fn split_profit(&self, profit: u64) -> Option<(u64, u64, u64)> {
+        let share_of = |bps: u16| (profit as u128 * bps as u128 / 10_000) as u64;
+        let organizer_pool = share_of(self.organizer_share_bps);
+        let platform_pool = share_of(self.platform_share_bps);
+        
+        let backer_pool = profit
+            .checked_sub(organizer_pool)?
+            .checked_sub(platform_pool)?;
+        
+        Some((backer_pool, organizer_pool, platform_pool))
+    }
+    
+    /// Snapshot the escrow left for backers after a distribution without profit
+    ///
+    /// When revenue didn't exceed expenses every pool is zero, so whatever
+    /// the escrow still holds (unreleased milestones, unspent funds and the
+    /// swept revenue) is returned to backers pro-rata instead of being
+    /// stranded. Profitable distributions leave the recovery pool empty.
+    pub fn open_loss_recovery(&mut self, escrow_available: u64) {
+        self.recovery_pool = if self.total_revenue > self.total_expenses {
+            0
+        } else {
+            escrow_available
+        };
+    }
+    
+    /// Contribution's pro-rata share of the loss recovery pool
+    pub fn loss_recovery_share(&self, contribution: &Contribution) -> u64 {
+        contribution.calculate_share(self.recovery_pool, self.distribution_basis)
+    }
+    
+    /// Record the distribution outcome for the swept ticket revenue
+    ///
+    /// On profit the pools are filled via `split_profit`. On a loss (or
+    /// break-even) the pools are zeroed and the shortfall is kept in
+    /// `recorded_loss` so backers can tell a loss apart from unclaimed profit.
+    pub fn record_distribution(&mut self, revenue: u64) -> Result<()> {
+        self.total_revenue = revenue;
+        
+        if revenue > self.total_expenses {
+            let profit = revenue
+                .checked_sub(self.total_expenses)
+                .ok_or(EventError::ArithmeticOverflow)?;
+            let (backer_pool, organizer_pool, platform_pool) = self.split_profit(profit)
+                .ok_or(EventError::ArithmeticOverflow)?;
+            
+            self.backer_pool = backer_pool;
+            self.organizer_pool = organizer_pool;
+            self.platform_pool = platform_pool;
+            self.recorded_loss = 0;
+        } else {
+            self.backer_pool = 0;
+            self.organizer_pool = 0;
+            self.platform_pool = 0;
+            self.recorded_loss = self.total_expenses
+                .checked_sub(revenue)
+                .ok_or(EventError::ArithmeticOverflow)?;
+        }
+        
+        // Freeze the share denominator so later changes to total_raised
+        // can't skew backer claims
+        self.distribution_basis = self.total_raised;
+        self.distribution_complete = true;
+        self.status = CampaignStatus::Completed;
+        
+        Ok(())
+    }
 }
 
 /// Campaign status lifecycle
@@ -127,6 +610,49 @@ pub enum CampaignStatus {
     Completed,
 }
 
+/// How a failed or canceled campaign pays refunds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum RefundMode {
+    /// Refunds are not open
+    None,
+    
+    /// Campaign missed its goal, every contribution is refunded in full
+    FullFailed,
+    
+    /// Event was canceled, contributors share the remaining escrow pro-rata
+    ProRataCanceled,
+}
+
+/// Unit a campaign's funding goal is denominated in
+///
+/// Contributions are only accepted in lamports today; the other variants
+/// are reserved so indexers can already render progress by unit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Denomination {
+    /// Native SOL, counted in lamports
+    Lamports,
+    
+    /// US dollars, counted in cents (priced through an oracle)
+    UsdCents,
+    
+    /// An SPL token, counted in its base units
+    SplToken(Pubkey),
+}
+
+impl Denomination {
+    /// Space for the largest variant (tag + mint)
+    pub const LEN: usize = 1 + 32;
+    
+    /// Fail unless contributions can be collected in this denomination
+    pub fn require_supported(&self) -> Result<()> {
+        require!(
+            *self == Denomination::Lamports,
+            EventError::UnsupportedDenomination
+        );
+        Ok(())
+    }
+}
+
 impl Default for CampaignStatus {
     fn default() -> Self {
         CampaignStatus::Pending
@@ -136,15 +662,28 @@ impl Default for CampaignStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::validate_profit_split;
     
     #[test]
     fn test_campaign_len() {
         // Verify our LEN calculation is correct
-        // Campaign::LEN = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1 = 152
-        assert_eq!(Campaign::LEN, 152);
+        // Campaign::LEN = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 2 + 2 + 2 + 8 + 1 + 4 + 8 + 1 + 1 + 8 + 8 + 8 + 33 + 8 + 8 + 8 + 1 + 32 + 1 = 295
+        assert_eq!(Campaign::LEN, 295);
         assert!(Campaign::LEN >= 150); // Reasonable minimum
     }
     
+    #[test]
+    fn test_denomination() {
+        // Space covers the largest variant
+        let spl = Denomination::SplToken(Pubkey::new_unique());
+        assert_eq!(spl.try_to_vec().unwrap().len(), Denomination::LEN);
+        
+        // Only lamport goals can be collected today
+        assert!(Denomination::Lamports.require_supported().is_ok());
+        assert_eq!(Denomination::UsdCents.require_supported().unwrap_err(), EventError::UnsupportedDenomination.into());
+        assert_eq!(spl.require_supported().unwrap_err(), EventError::UnsupportedDenomination.into());
+    }
+    
     #[test]
     fn test_goal_reached() {
         let mut campaign = Campaign {
@@ -163,6 +702,24 @@ mod tests {
             platform_pool: 0,
             distribution_complete: false,
             organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
             bump: 0,
         };
         
@@ -190,6 +747,24 @@ mod tests {
             platform_pool: 0,
             distribution_complete: false,
             organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
             bump: 0,
         };
         
@@ -215,6 +790,24 @@ mod tests {
             platform_pool: 0,
             distribution_complete: false,
             organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
             bump: 0,
         };
         
@@ -229,4 +822,911 @@ mod tests {
         campaign.status = CampaignStatus::Funded;
         assert!(!campaign.can_finalize(500));
     }
+    
+    #[test]
+    fn test_double_finalize() {
+        let mut campaign = Campaign {
+            event: Pubkey::default(),
+            organizer: Pubkey::default(),
+            funding_goal: 100_000_000_000,
+            total_raised: 50_000_000_000,
+            deadline: 1000,
+            status: CampaignStatus::Pending,
+            total_contributors: 0,
+            created_at: 0,
+            total_expenses: 0,
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        };
+        
+        // Too early: neither goal nor deadline reached
+        assert_eq!(
+            campaign.finalize(500).unwrap_err(),
+            EventError::CampaignNotReadyToFinalize.into()
+        );
+        
+        // First finalize after the deadline wins the transition
+        assert_eq!(campaign.finalize(1001).unwrap(), CampaignStatus::Failed);
+        
+        // A contribution landing between the two can't flip the outcome
+        campaign.total_raised = 150_000_000_000;
+        
+        // Second finalize back-to-back loses with AlreadyFinalized
+        assert_eq!(
+            campaign.finalize(1001).unwrap_err(),
+            EventError::AlreadyFinalized.into()
+        );
+        assert_eq!(campaign.status, CampaignStatus::Failed);
+    }
+    
+    #[test]
+    fn test_split_profit() {
+        let campaign = refund_test_campaign(CampaignStatus::Funded);
+        
+        // 30 SOL profit → 18 / 10.5 / 1.5 SOL
+        let (backer, organizer, platform) = campaign.split_profit(30_000_000_000).unwrap();
+        assert_eq!(backer, 18_000_000_000);
+        assert_eq!(organizer, 10_500_000_000);
+        assert_eq!(platform, 1_500_000_000);
+        
+        // Rounding remainder goes to backers
+        let (backer, organizer, platform) = campaign.split_profit(101).unwrap();
+        assert_eq!((backer, organizer, platform), (61, 35, 5));
+        
+        // No intermediate overflow on huge profits
+        let (backer, organizer, platform) = campaign.split_profit(u64::MAX).unwrap();
+        assert_eq!(backer as u128 + organizer as u128 + platform as u128, u64::MAX as u128);
+    }
+    
+    #[test]
+    fn test_custom_profit_split() {
+        // High-risk show negotiated 70/25/5
+        let mut campaign = refund_test_campaign(CampaignStatus::Funded);
+        campaign.backer_share_bps = 7000;
+        campaign.organizer_share_bps = 2500;
+        campaign.platform_share_bps = 500;
+        
+        let (backer, organizer, platform) = campaign.split_profit(30_000_000_000).unwrap();
+        assert_eq!((backer, organizer, platform), (21_000_000_000, 7_500_000_000, 1_500_000_000));
+        
+        // Dust still lands in the backer pool
+        assert_eq!(campaign.split_profit(99).unwrap(), (71, 24, 4));
+        
+        // record_distribution fills the pools from the campaign's own split
+        campaign.total_raised = 10_000_000_000;
+        campaign.total_expenses = 10_000_000_000;
+        campaign.record_distribution(40_000_000_000).unwrap();
+        assert_eq!(campaign.backer_pool, 21_000_000_000);
+        assert_eq!(campaign.organizer_pool, 7_500_000_000);
+        assert_eq!(campaign.platform_pool, 1_500_000_000);
+        
+        // Splits that don't add up to 100% are rejected at creation
+        assert_eq!(
+            validate_profit_split(None, 7000, 2500, 600).unwrap_err(),
+            EventError::InvalidProfitSplit.into()
+        );
+        assert_eq!(
+            validate_profit_split(None, 7000, 2400, 500).unwrap_err(),
+            EventError::InvalidProfitSplit.into()
+        );
+    }
+    
+    #[test]
+    fn test_crossed_funding_milestones() {
+        let mut campaign = Campaign {
+            event: Pubkey::default(),
+            organizer: Pubkey::default(),
+            funding_goal: 100_000_000_000, // 100 SOL
+            total_raised: 0,
+            deadline: 0,
+            status: CampaignStatus::Pending,
+            total_contributors: 0,
+            created_at: 0,
+            total_expenses: 0,
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        };
+        
+        // Below the first threshold
+        assert!(campaign.crossed_funding_milestones(0, 24_999_999_999).is_empty());
+        
+        // Exactly at a threshold counts, and only once
+        assert_eq!(campaign.crossed_funding_milestones(0, 25_000_000_000), vec![2500]);
+        assert!(campaign.crossed_funding_milestones(25_000_000_000, 30_000_000_000).is_empty());
+        
+        // One large contribution crosses several thresholds
+        assert_eq!(
+            campaign.crossed_funding_milestones(10_000_000_000, 80_000_000_000),
+            vec![2500, 5000, 7500]
+        );
+        
+        // Overfunding crosses 100% once
+        assert_eq!(campaign.crossed_funding_milestones(99_000_000_000, 150_000_000_000), vec![10000]);
+        assert!(campaign.crossed_funding_milestones(150_000_000_000, 200_000_000_000).is_empty());
+        
+        // Goals that don't divide evenly (25% of 3 lamports is 0.75)
+        campaign.funding_goal = 3;
+        assert_eq!(campaign.crossed_funding_milestones(0, 1), vec![2500]);
+        assert_eq!(campaign.crossed_funding_milestones(1, 2), vec![5000]);
+        assert_eq!(campaign.crossed_funding_milestones(2, 3), vec![7500, 10000]);
+    }
+    
+    #[test]
+    fn test_can_trigger_distribution() {
+        let organizer = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let random_wallet = Pubkey::new_unique();
+        
+        let campaign = Campaign {
+            event: Pubkey::default(),
+            organizer,
+            funding_goal: 100_000_000_000,
+            total_raised: 100_000_000_000,
+            deadline: 0,
+            status: CampaignStatus::Funded,
+            total_contributors: 0,
+            created_at: 0,
+            total_expenses: 0,
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        };
+        let config = PlatformConfig {
+            admin,
+            min_platform_share_bps: PlatformConfig::DEFAULT_MIN_PLATFORM_SHARE_BPS,
+            min_backer_share_bps: PlatformConfig::DEFAULT_MIN_BACKER_SHARE_BPS,
+            distribution_timeout_seconds: PlatformConfig::DEFAULT_DISTRIBUTION_TIMEOUT_SECONDS,
+            platform_treasury: Pubkey::default(),
+            default_platform_fee_bps: 500,
+            paused: false,
+            max_relayer_fee_lamports: PlatformConfig::DEFAULT_MAX_RELAYER_FEE_LAMPORTS,
+            distribution_grace_period_seconds: PlatformConfig::DEFAULT_DISTRIBUTION_GRACE_PERIOD_SECONDS,
+            backer_claim_window_seconds: PlatformConfig::DEFAULT_BACKER_CLAIM_WINDOW_SECONDS,
+            unclaimed_profit_recipient: PlatformConfig::DEFAULT_UNCLAIMED_PROFIT_RECIPIENT,
+            refund_fee_bps: PlatformConfig::DEFAULT_REFUND_FEE_BPS,
+            min_budget_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            bump: 0,
+        };
+        
+        assert!(campaign.can_trigger_distribution(&organizer, Some(&config)));
+        assert!(campaign.can_trigger_distribution(&admin, Some(&config)));
+        assert!(!campaign.can_trigger_distribution(&random_wallet, Some(&config)));
+        
+        // Without a config only the organizer may trigger
+        assert!(campaign.can_trigger_distribution(&organizer, None));
+        assert!(!campaign.can_trigger_distribution(&admin, None));
+        assert!(!campaign.can_trigger_distribution(&random_wallet, None));
+        
+        // After the timeout an unrelated caller may trigger anyway
+        let end_ts = 1_000_000;
+        let warped_ts = end_ts + config.distribution_timeout_seconds + 1;
+        assert!(!PlatformConfig::distribution_timed_out(Some(&config), end_ts, end_ts + 1));
+        assert!(PlatformConfig::distribution_timed_out(Some(&config), end_ts, warped_ts));
+    }
+    
+    #[test]
+    fn test_loss_distribution_end_to_end() {
+        use crate::state::Contribution;
+        
+        let mut campaign = Campaign {
+            event: Pubkey::default(),
+            organizer: Pubkey::default(),
+            funding_goal: 60_000_000_000,
+            total_raised: 60_000_000_000,
+            deadline: 0,
+            status: CampaignStatus::Funded,
+            total_contributors: 2,
+            created_at: 0,
+            total_expenses: 50_000_000_000, // 50 SOL released to the organizer
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        };
+        
+        // Only 30 SOL of ticket revenue was swept → 20 SOL loss
+        campaign.record_distribution(30_000_000_000).unwrap();
+        assert_eq!(campaign.status, CampaignStatus::Completed);
+        assert!(campaign.distribution_complete);
+        assert_eq!(campaign.total_revenue, 30_000_000_000);
+        assert_eq!(campaign.recorded_loss, 20_000_000_000);
+        assert_eq!(
+            (campaign.backer_pool, campaign.organizer_pool, campaign.platform_pool),
+            (0, 0, 0)
+        );
+        
+        // Every backer claims zero, with the loss available to explain why
+        let backer = Contribution {
+            campaign: Pubkey::default(),
+            contributor: Pubkey::new_unique(),
+            amount: 40_000_000_000,
+            contributed_at: 0,
+            refunded: false,
+            profit_share: 0,
+            profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
+            bump: 0,
+        };
+        assert_eq!(backer.calculate_share(campaign.backer_pool, campaign.total_raised), 0);
+        
+        // Break-even records no loss and no profit
+        campaign.record_distribution(50_000_000_000).unwrap();
+        assert_eq!(campaign.recorded_loss, 0);
+        assert_eq!(campaign.backer_pool, 0);
+        
+        // Profit clears any loss and fills the pools
+        campaign.record_distribution(80_000_000_000).unwrap();
+        assert_eq!(campaign.recorded_loss, 0);
+        assert_eq!(
+            campaign.backer_pool + campaign.organizer_pool + campaign.platform_pool,
+            30_000_000_000
+        );
+    }
+    
+    #[test]
+    fn test_failed_campaign_escrow_survives_refunds() {
+        let rent_exempt_minimum = 890_880;
+        let contributions: [u64; 3] = [100_000_000, 250_000_001, 7];
+        
+        let mut campaign = Campaign {
+            event: Pubkey::default(),
+            organizer: Pubkey::default(),
+            funding_goal: 1_000_000_000,
+            total_raised: contributions.iter().sum(),
+            deadline: 0,
+            status: CampaignStatus::Failed,
+            total_contributors: 3,
+            created_at: 0,
+            total_expenses: 0,
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        };
+        
+        // Escrow created rent-exempt at campaign creation, then funded
+        let mut escrow = rent_exempt_minimum + campaign.total_raised;
+        
+        // Refunds can't dip into the rent reserve
+        assert_eq!(
+            Campaign::require_escrow_covers(escrow, rent_exempt_minimum, campaign.total_raised + 1).unwrap_err(),
+            EventError::InsufficientBalance.into()
+        );
+        
+        // Not closeable while contributions are still owed
+        assert!(!campaign.escrow_closeable(Campaign::escrow_available(escrow, rent_exempt_minimum)));
+        
+        // Refund every contributor
+        for amount in contributions {
+            Campaign::require_escrow_covers(escrow, rent_exempt_minimum, amount).unwrap();
+            escrow -= amount;
+            campaign.total_raised -= amount;
+        }
+        
+        // The escrow survives with exactly its rent reserve and can be closed
+        assert_eq!(escrow, rent_exempt_minimum);
+        assert!(campaign.escrow_closeable(Campaign::escrow_available(escrow, rent_exempt_minimum)));
+        
+        // Campaigns that are still running or funded never close their escrow
+        campaign.status = CampaignStatus::Funded;
+        assert!(!campaign.escrow_closeable(0));
+        campaign.status = CampaignStatus::Completed;
+        assert!(campaign.escrow_closeable(0));
+        assert!(!campaign.escrow_closeable(1));
+    }
+    
+    #[test]
+    fn test_require_settled() {
+        let mut campaign = Campaign {
+            event: Pubkey::default(),
+            organizer: Pubkey::default(),
+            funding_goal: 1_000_000_000,
+            total_raised: 1_000_000_000,
+            deadline: 0,
+            status: CampaignStatus::Funded,
+            total_contributors: 2,
+            created_at: 0,
+            total_expenses: 0,
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 1_000,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        };
+        // Running campaigns can't be closed
+        assert_eq!(campaign.require_settled().unwrap_err(), EventError::InvalidCampaignStatus.into());
+        
+        // Completed: organizer and every backer must have claimed
+        campaign.status = CampaignStatus::Completed;
+        assert_eq!(campaign.require_settled().unwrap_err(), EventError::CampaignNotSettled.into());
+        campaign.organizer_claimed = true;
+        campaign.backer_claims = 1;
+        assert!(campaign.require_settled().is_err());
+        
+        // ...or the unclaimed backer funds must have been swept
+        campaign.backer_pool_swept = true;
+        campaign.require_settled().unwrap();
+        campaign.backer_pool_swept = false;
+        campaign.backer_claims = 2;
+        campaign.require_settled().unwrap();
+        
+        // Outstanding loss recovery has to be swept even if every backer claimed profit
+        campaign.recovery_pool = 1_000;
+        assert!(campaign.require_settled().is_err());
+        campaign.backer_pool_swept = true;
+        campaign.require_settled().unwrap();
+        
+        // The organizer's claim is always required
+        campaign.organizer_claimed = false;
+        assert!(campaign.require_settled().is_err());
+        
+        // Failed: every contribution must have been refunded
+        campaign.status = CampaignStatus::Failed;
+        assert!(campaign.require_settled().is_err());
+        campaign.total_raised = 0;
+        campaign.require_settled().unwrap();
+    }
+    
+    #[test]
+    fn test_extend_deadline() {
+        let mut campaign = Campaign {
+            event: Pubkey::default(),
+            organizer: Pubkey::default(),
+            funding_goal: 1_000_000_000,
+            total_raised: 0,
+            deadline: 1_000,
+            status: CampaignStatus::Pending,
+            total_contributors: 0,
+            created_at: 0,
+            total_expenses: 0,
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        };
+        let event_start = 5_000;
+        
+        // Must move the deadline later, stay in the future and before the event
+        assert_eq!(campaign.extend_deadline(1_000, 500, event_start).unwrap_err(), EventError::DeadlineNotExtended.into());
+        assert_eq!(campaign.extend_deadline(1_500, 2_000, event_start).unwrap_err(), EventError::DeadlineInPast.into());
+        
+        // Event start is an exclusive ceiling
+        assert_eq!(campaign.extend_deadline(event_start, 500, event_start).unwrap_err(), EventError::DeadlineAfterEventStart.into());
+        
+        assert_eq!(campaign.extend_deadline(event_start - 1, 500, event_start).unwrap(), 1_000);
+        assert_eq!(campaign.deadline, event_start - 1);
+        assert!(campaign.deadline_extended);
+        
+        // Only one extension per campaign
+        campaign.deadline = 2_000;
+        assert_eq!(campaign.extend_deadline(3_000, 500, event_start).unwrap_err(), EventError::DeadlineAlreadyExtended.into());
+        assert_eq!(campaign.deadline, 2_000);
+        
+        // Finalized campaigns can't be extended
+        campaign.deadline_extended = false;
+        campaign.status = CampaignStatus::Funded;
+        assert_eq!(campaign.extend_deadline(3_000, 500, event_start).unwrap_err(), EventError::CampaignNotActive.into());
+    }
+    
+    fn refund_test_campaign(status: CampaignStatus) -> Campaign {
+        Campaign {
+            event: Pubkey::default(),
+            organizer: Pubkey::default(),
+            funding_goal: 10_000_000_000,
+            total_raised: 4_000_000_000,
+            deadline: 1_000,
+            status,
+            total_contributors: 2,
+            created_at: 0,
+            total_expenses: 0,
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        }
+    }
+    
+    fn refund_test_contribution(amount: u64) -> Contribution {
+        Contribution {
+            campaign: Pubkey::default(),
+            contributor: Pubkey::new_unique(),
+            amount,
+            contributed_at: 0,
+            refunded: false,
+            profit_share: 0,
+            profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_refund_modes() {
+        let contribution = refund_test_contribution(1_000_000_000);
+        
+        // No refunds before a transition opens them
+        let mut failed = refund_test_campaign(CampaignStatus::Pending);
+        assert_eq!(failed.refund_amount(&contribution).unwrap_err(), EventError::CannotRefundFundedCampaign.into());
+        
+        // Missing the goal opens full refunds
+        failed.finalize(2_000).unwrap();
+        assert_eq!(failed.status, CampaignStatus::Failed);
+        assert_eq!(failed.refund_mode, RefundMode::FullFailed);
+        assert_eq!(failed.refund_amount(&contribution).unwrap(), 1_000_000_000);
+        
+        // Canceling a funded event after 1 of 4 SOL was released pays 75%
+        let mut canceled = refund_test_campaign(CampaignStatus::Funded);
+        canceled.cancel_for_event(3_000_000_000).unwrap();
+        assert_eq!(canceled.status, CampaignStatus::Failed);
+        assert_eq!(canceled.refund_mode, RefundMode::ProRataCanceled);
+        assert_eq!(canceled.refund_amount(&contribution).unwrap(), 750_000_000);
+        
+        // The pool never exceeds what was raised
+        let mut overfunded = refund_test_campaign(CampaignStatus::Pending);
+        overfunded.cancel_for_event(9_000_000_000).unwrap();
+        assert_eq!(overfunded.refund_amount(&contribution).unwrap(), 1_000_000_000);
+    }
+    
+    #[test]
+    fn test_refund_mode_set_once() {
+        // A failed campaign can't be switched to pro-rata by a later cancellation
+        let mut campaign = refund_test_campaign(CampaignStatus::Pending);
+        campaign.finalize(2_000).unwrap();
+        assert_eq!(campaign.cancel_for_event(1).unwrap_err(), EventError::InvalidCampaignStatus.into());
+        assert_eq!(
+            campaign.open_refunds(RefundMode::ProRataCanceled, 1).unwrap_err(),
+            EventError::RefundsAlreadyOpen.into()
+        );
+        assert_eq!(campaign.refund_mode, RefundMode::FullFailed);
+        
+        // ...nor can a canceled campaign be finalized into full refunds
+        let mut campaign = refund_test_campaign(CampaignStatus::Pending);
+        campaign.cancel_for_event(2_000_000_000).unwrap();
+        assert_eq!(campaign.finalize(2_000).unwrap_err(), EventError::AlreadyFinalized.into());
+        assert_eq!(campaign.refund_mode, RefundMode::ProRataCanceled);
+        
+        // Completed campaigns have already paid out
+        let mut completed = refund_test_campaign(CampaignStatus::Completed);
+        assert_eq!(completed.cancel_for_event(1).unwrap_err(), EventError::InvalidCampaignStatus.into());
+    }
+    
+    #[test]
+    fn test_refund_claimed_once_across_modes() {
+        let mut campaign = refund_test_campaign(CampaignStatus::Funded);
+        campaign.cancel_for_event(2_000_000_000).unwrap();
+        
+        // Refunded in full before the mode flipped: the pro-rata claim is rejected
+        let mut early = refund_test_contribution(1_000_000_000);
+        early.refunded = true;
+        early.refund_mode = RefundMode::FullFailed;
+        assert_eq!(campaign.refund_amount(&early).unwrap_err(), EventError::RefundModeMismatch.into());
+        
+        // Claiming twice under the same mode
+        let mut claimed = refund_test_contribution(1_000_000_000);
+        claimed.refunded = true;
+        claimed.refund_mode = RefundMode::ProRataCanceled;
+        assert_eq!(campaign.refund_amount(&claimed).unwrap_err(), EventError::ContributionAlreadyRefunded.into());
+        
+        // Pro-rata payouts never exceed the pool
+        let contributions = [
+            refund_test_contribution(1_333_333_333),
+            refund_test_contribution(2_666_666_667),
+        ];
+        let paid: u64 = contributions.iter().map(|c| campaign.refund_amount(c).unwrap()).sum();
+        assert!(paid <= campaign.refund_pool);
+    }
+    
+    #[test]
+    fn test_withdraw_contribution() {
+        let mut campaign = refund_test_campaign(CampaignStatus::Pending);
+        campaign.funding_goal = 4_000_000_000;
+        let mut whale = refund_test_contribution(3_000_000_000);
+        let mut small = refund_test_contribution(1_000_000_000);
+        assert!(campaign.goal_reached());
+        
+        // A large withdrawal drops the campaign back below its goal
+        assert!(!campaign.withdraw_contribution(&mut whale, 2_000_000_000, 500).unwrap());
+        assert_eq!(whale.amount, 1_000_000_000);
+        assert_eq!(campaign.total_raised, 2_000_000_000);
+        assert_eq!(campaign.total_contributors, 2);
+        assert!(!campaign.goal_reached());
+        assert!(!campaign.can_finalize(500));
+        
+        // Withdrawing everything removes the contributor
+        assert!(campaign.withdraw_contribution(&mut small, 1_000_000_000, 500).unwrap());
+        assert_eq!(small.amount, 0);
+        assert_eq!(campaign.total_raised, 1_000_000_000);
+        assert_eq!(campaign.total_contributors, 1);
+        
+        // Can't take out more than was contributed, or nothing
+        let invalid: Error = EventError::InvalidContributionAmount.into();
+        assert_eq!(campaign.withdraw_contribution(&mut whale, 1_000_000_001, 500).unwrap_err(), invalid);
+        assert_eq!(campaign.withdraw_contribution(&mut whale, 0, 500).unwrap_err(), invalid);
+        
+        // Not after the deadline or once the campaign is finalized
+        assert_eq!(
+            campaign.withdraw_contribution(&mut whale, 1, 1_001).unwrap_err(),
+            EventError::CampaignDeadlinePassed.into()
+        );
+        campaign.status = CampaignStatus::Funded;
+        assert_eq!(
+            campaign.withdraw_contribution(&mut whale, 1, 500).unwrap_err(),
+            EventError::CampaignNotActive.into()
+        );
+        assert_eq!(whale.amount, 1_000_000_000);
+    }
+    
+    #[test]
+    fn test_backer_shares_never_exceed_pool() {
+        // Deterministic pseudo-random contributions, some partly refunded
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        
+        for _ in 0..200 {
+            let backers: Vec<Contribution> = (0..(next() % 20 + 1))
+                .map(|_| {
+                    let mut contribution = refund_test_contribution(next() % 50_000_000_000 + 1);
+                    if next() % 3 == 0 {
+                        contribution.refunded_amount = next() % (contribution.amount + 1);
+                    }
+                    contribution
+                })
+                .collect();
+            
+            let mut campaign = refund_test_campaign(CampaignStatus::Funded);
+            campaign.total_raised = backers.iter().map(Contribution::net_amount).sum();
+            campaign.total_expenses = 0;
+            campaign.record_distribution(next() % 500_000_000_000).unwrap();
+            
+            // Later changes to total_raised don't move the denominator
+            let basis = campaign.distribution_basis;
+            campaign.total_raised = next() % (basis + 1);
+            
+            let claimed: u64 = backers.iter().map(|backer| campaign.backer_share(backer)).sum();
+            assert!(claimed <= campaign.backer_pool);
+            assert_eq!(campaign.distribution_basis, basis);
+        }
+    }
+    
+    #[test]
+    fn test_backer_share_excludes_refunded_amount() {
+        let mut campaign = refund_test_campaign(CampaignStatus::Funded);
+        let full = refund_test_contribution(3_000_000_000);
+        let mut partly_refunded = refund_test_contribution(2_000_000_000);
+        partly_refunded.refunded_amount = 1_000_000_000;
+        
+        // 4 SOL still backs the campaign; 2 SOL profit → 1.2 SOL backer pool
+        campaign.total_raised = full.net_amount() + partly_refunded.net_amount();
+        campaign.record_distribution(2_000_000_000).unwrap();
+        assert_eq!(campaign.distribution_basis, 4_000_000_000);
+        
+        assert_eq!(campaign.backer_share(&full), 900_000_000);
+        assert_eq!(campaign.backer_share(&partly_refunded), 300_000_000);
+        assert_eq!(
+            campaign.backer_share(&full) + campaign.backer_share(&partly_refunded),
+            campaign.backer_pool
+        );
+    }
+    
+    #[test]
+    fn test_loss_recovery_after_one_of_three_milestones() {
+        use crate::state::{Milestone, MilestoneApproval, DisputeStatus};
+        
+        let milestone = |release_percentage: u16| Milestone {
+            description: String::new(),
+            release_percentage,
+            unlock_date: 0,
+            released: false,
+            released_amount: 0,
+            dispute_status: DisputeStatus::None,
+            approval: MilestoneApproval::None,
+            vote_end: 0,
+            votes_for: 0,
+            votes_against: 0,
+            turnout_amount: 0,
+        };
+        
+        // 4 SOL raised from three backers, 3 SOL budget over three milestones
+        let mut campaign = refund_test_campaign(CampaignStatus::Funded);
+        let backers = [
+            refund_test_contribution(1_000_000_000),
+            refund_test_contribution(1_000_000_000),
+            refund_test_contribution(2_000_000_000),
+        ];
+        let budget_total = 3_000_000_000;
+        let milestones = [milestone(3334), milestone(3333), milestone(3333)];
+        let mut campaign_escrow = campaign.total_raised;
+        
+        // Only the first milestone is released before the event
+        let released = milestones[0].amount(budget_total);
+        campaign_escrow -= released;
+        campaign.total_expenses = released;
+        
+        // Ticket sales don't cover it: 0.5 SOL swept in, recorded as a loss
+        let swept = 500_000_000;
+        campaign_escrow += swept;
+        campaign.record_distribution(swept).unwrap();
+        assert_eq!(campaign.recorded_loss, released - swept);
+        assert_eq!(campaign.backer_pool, 0);
+        
+        // Everything left in escrow is handed back to backers pro-rata
+        campaign.open_loss_recovery(campaign_escrow);
+        assert_eq!(campaign.recovery_pool, 3_499_800_000);
+        
+        let shares: Vec<u64> = backers.iter().map(|b| campaign.loss_recovery_share(b)).collect();
+        assert_eq!(shares, vec![874_950_000, 874_950_000, 1_749_900_000]);
+        assert_eq!(shares.iter().sum::<u64>(), campaign.recovery_pool);
+        
+        // A profitable distribution leaves nothing to recover
+        let mut profitable = refund_test_campaign(CampaignStatus::Funded);
+        profitable.total_expenses = released;
+        profitable.record_distribution(released + 1_000_000_000).unwrap();
+        profitable.open_loss_recovery(campaign_escrow);
+        assert_eq!(profitable.recovery_pool, 0);
+        assert_eq!(profitable.loss_recovery_share(&backers[0]), 0);
+    }
+    
+    #[test]
+    fn test_claim_deadline_and_sweep() {
+        let mut campaign = refund_test_campaign(CampaignStatus::Funded);
+        let backer = refund_test_contribution(1_000_000_000);
+        
+        // 2 SOL profit distributed at t=10_000 with the default 90 day window
+        campaign.record_distribution(2_000_000_000).unwrap();
+        campaign.claim_deadline = 10_000 + PlatformConfig::backer_claim_window(None);
+        let deadline = campaign.claim_deadline;
+        
+        // Claims are open up to and including the deadline
+        campaign.require_claim_open(10_000).unwrap();
+        campaign.require_claim_open(deadline).unwrap();
+        let share = campaign.backer_share(&backer);
+        campaign.record_backer_payout(share).unwrap();
+        
+        // ...and rejected after it
+        assert_eq!(campaign.require_claim_open(deadline + 1).unwrap_err(), EventError::ClaimDeadlinePassed.into());
+        
+        // Sweeping waits for the deadline
+        assert_eq!(campaign.sweep_unclaimed(deadline).unwrap_err(), EventError::ClaimDeadlineNotPassed.into());
+        assert!(!campaign.backer_pool_swept);
+        
+        // Then releases exactly what backers left unclaimed, once
+        assert_eq!(campaign.sweep_unclaimed(deadline + 1).unwrap(), campaign.backer_pool - share);
+        assert!(campaign.backer_pool_swept);
+        assert_eq!(campaign.sweep_unclaimed(deadline + 2).unwrap_err(), EventError::BackerPoolAlreadySwept.into());
+        
+        // Only completed campaigns have pools to sweep
+        let mut failed = refund_test_campaign(CampaignStatus::Failed);
+        assert_eq!(failed.sweep_unclaimed(i64::MAX).unwrap_err(), EventError::InvalidCampaignStatus.into());
+    }
+    
+    #[test]
+    fn test_push_distribution_mixed_batch() {
+        let mut campaign = refund_test_campaign(CampaignStatus::Funded);
+        campaign.record_distribution(6_000_000_000).unwrap();
+        
+        // 4 SOL raised: 1 SOL backer already claimed, 1 SOL and 2 SOL not yet
+        let mut claimed = refund_test_contribution(1_000_000_000);
+        let mut small = refund_test_contribution(1_000_000_000);
+        let mut large = refund_test_contribution(2_000_000_000);
+        let pulled = campaign.settle_backer_claim(&mut claimed).unwrap();
+        campaign.record_backer_payout(pulled).unwrap();
+        
+        // The crank skips the claimed contribution and settles the rest,
+        // paying exactly what a pull claim would
+        let expected: Vec<u64> = [&small, &large].iter().map(|c| campaign.backer_share(c)).collect();
+        let mut paid = Vec::new();
+        let mut skipped = 0;
+        for contribution in [&mut claimed, &mut small, &mut large] {
+            if contribution.profit_claimed {
+                skipped += 1;
+                continue;
+            }
+            let share = campaign.settle_backer_claim(contribution).unwrap();
+            campaign.record_backer_payout(share).unwrap();
+            paid.push(share);
+        }
+        
+        assert_eq!(skipped, 1);
+        assert_eq!(paid, expected);
+        assert_eq!(campaign.backer_claims, 3);
+        assert!(small.profit_claimed && large.profit_claimed);
+        assert_eq!(large.profit_share, 2 * small.profit_share);
+        assert!(campaign.backer_paid_out <= campaign.backer_pool);
+        
+        // A second pass finds nothing left to settle
+        assert_eq!(campaign.settle_backer_claim(&mut small).unwrap_err(), EventError::ProfitAlreadyClaimed.into());
+        
+        // The crank fee comes out of the platform pool and stops when it's empty
+        let platform_pool = campaign.platform_pool;
+        assert_eq!(campaign.take_crank_fee(2), 2 * Campaign::CRANK_FEE_PER_CONTRIBUTION_LAMPORTS);
+        assert_eq!(campaign.platform_pool, platform_pool - 2 * Campaign::CRANK_FEE_PER_CONTRIBUTION_LAMPORTS);
+        campaign.platform_pool = 1_000;
+        assert_eq!(campaign.take_crank_fee(10), 1_000);
+        assert_eq!(campaign.take_crank_fee(10), 0);
+    }
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::RefundMode;
 
 /// Contribution account tracks individual backer contributions to a campaign
 /// 
@@ -29,6 +30,21 @@ pub struct Contribution {
     /// Whether the contributor has claimed their profit share
     pub profit_claimed: bool,
     
+    /// Refund mode the contribution was refunded under (None until refunded)
+    pub refund_mode: RefundMode,
+    
+    /// Portion of `amount` that has been refunded and no longer backs the campaign
+    pub refunded_amount: u64,
+    
+    /// Whether the contributor has claimed their share of the loss recovery pool
+    pub loss_recovered: bool,
+    
+    /// Amount before the most recent increase
+    pub previous_amount: u64,
+    
+    /// When `amount` was last increased
+    pub last_increase_at: i64,
+    
     /// PDA bump seed
     pub bump: u8,
 }
@@ -43,6 +59,11 @@ impl Contribution {
         1 +  // refunded
         8 +  // profit_share
         1 +  // profit_claimed
+        1 +  // refund_mode (enum)
+        8 +  // refunded_amount
+        1 +  // loss_recovered
+        8 +  // previous_amount
+        8 +  // last_increase_at
         1;   // bump
     
     /// Calculate this contributor's voting power (equal to contribution amount for MVP)
@@ -50,6 +71,39 @@ impl Contribution {
         self.amount
     }
     
+    /// Amount contributed as of `timestamp`
+    ///
+    /// Only the latest increase is tracked: anything added after `timestamp`
+    /// by that increase doesn't count. Snapshots taken before an earlier
+    /// increase would read `previous_amount` too high, so callers only ask
+    /// about snapshots that precede at most one increase.
+    pub fn amount_at(&self, timestamp: i64) -> u64 {
+        if timestamp >= self.last_increase_at {
+            self.amount
+        } else {
+            self.previous_amount
+        }
+    }
+    
+    /// Add `amount` lamports to the contribution at `current_ts`
+    ///
+    /// Several increases within the same second keep the amount from before
+    /// the first of them. Returns None on overflow.
+    pub fn record_increase(&mut self, amount: u64, current_ts: i64) -> Option<()> {
+        let new_amount = self.amount.checked_add(amount)?;
+        if current_ts > self.last_increase_at {
+            self.previous_amount = self.amount;
+            self.last_increase_at = current_ts;
+        }
+        self.amount = new_amount;
+        Some(())
+    }
+    
+    /// Contribution still backing the campaign (refunded portion excluded)
+    pub fn net_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.refunded_amount)
+    }
+    
     /// Calculate proportional share of a pool
     /// 
     /// # Arguments
@@ -63,13 +117,13 @@ impl Contribution {
             return 0;
         }
         
-        // Calculate: (contribution / total_raised) * pool_amount
+        // Calculate: (net contribution / total_raised) * pool_amount
         // Use u128 to prevent overflow during calculation
-        let contribution = self.amount as u128;
+        let contribution = self.net_amount() as u128;
         let total = total_raised as u128;
         let pool = pool_amount as u128;
         
-        let share = (contribution * pool) / total;
+        let share = ((contribution * pool) / total).min(pool);
         
         // Safe to cast back to u64 since share <= pool_amount
         share as u64
@@ -80,6 +134,14 @@ impl Contribution {
         !self.refunded
     }
     
+    /// Check if this contribution's account can be closed
+    ///
+    /// Refunded and claimed contributions are settled. Once the campaign
+    /// account itself has been closed nothing more can be claimed.
+    pub fn can_close(&self, campaign_closed: bool) -> bool {
+        self.refunded || self.profit_claimed || campaign_closed
+    }
+    
     /// Check if profit can be claimed
     pub fn can_claim_profit(&self) -> bool {
         !self.profit_claimed && self.profit_share > 0
@@ -106,6 +168,11 @@ mod tests {
             refunded: false,
             profit_share: 0,
             profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
             bump: 0,
         };
         
@@ -123,6 +190,11 @@ mod tests {
             refunded: false,
             profit_share: 0,
             profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
             bump: 0,
         };
         
@@ -153,6 +225,11 @@ mod tests {
                 refunded: false,
                 profit_share: 0,
                 profit_claimed: false,
+                refund_mode: RefundMode::None,
+                refunded_amount: 0,
+                loss_recovered: false,
+                previous_amount: 0,
+                last_increase_at: 0,
                 bump: 0,
             };
             
@@ -172,6 +249,11 @@ mod tests {
             refunded: false,
             profit_share: 0,
             profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
             bump: 0,
         };
         
@@ -198,6 +280,11 @@ mod tests {
             refunded: false,
             profit_share: 0,
             profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
             bump: 0,
         };
         
@@ -217,6 +304,11 @@ mod tests {
             refunded: false,
             profit_share: 5_000_000_000,
             profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
             bump: 0,
         };
         
@@ -230,4 +322,67 @@ mod tests {
         contribution.profit_share = 0;
         assert!(!contribution.can_claim_profit());
     }
+    
+    #[test]
+    fn test_can_close() {
+        let mut contribution = Contribution {
+            campaign: Pubkey::default(),
+            contributor: Pubkey::default(),
+            amount: 10_000_000_000,
+            contributed_at: 0,
+            refunded: false,
+            profit_share: 0,
+            profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
+            bump: 0,
+        };
+        
+        // Unclaimed contributions stay open while the campaign exists
+        assert!(!contribution.can_close(false));
+        assert!(contribution.can_close(true));
+        
+        // Refunded or claimed contributions can be closed
+        contribution.refunded = true;
+        assert!(contribution.can_close(false));
+        contribution.refunded = false;
+        contribution.profit_claimed = true;
+        assert!(contribution.can_close(false));
+    }
+    
+    #[test]
+    fn test_amount_at_ignores_later_increases() {
+        let mut contribution = Contribution {
+            campaign: Pubkey::default(),
+            contributor: Pubkey::default(),
+            amount: 5_000_000_000,
+            contributed_at: 100,
+            refunded: false,
+            profit_share: 0,
+            profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 100,
+            bump: 0,
+        };
+        
+        // Nothing before the first contribution
+        assert_eq!(contribution.amount_at(99), 0);
+        assert_eq!(contribution.amount_at(100), 5_000_000_000);
+        
+        // Top-ups after a snapshot at 150 don't count toward it
+        contribution.record_increase(2_000_000_000, 200).unwrap();
+        contribution.record_increase(1_000_000_000, 200).unwrap();
+        assert_eq!(contribution.amount, 8_000_000_000);
+        assert_eq!(contribution.amount_at(150), 5_000_000_000);
+        assert_eq!(contribution.amount_at(200), 8_000_000_000);
+        
+        assert!(contribution.record_increase(u64::MAX, 300).is_none());
+        assert_eq!(contribution.amount, 8_000_000_000);
+    }
 }
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+
+/// Dispute raised by a backer against an approved budget milestone
+///
+/// PDA: ["milestone_dispute", budget, milestone_index]. The flagger's bond
+/// is held in a separate system-owned PDA (["dispute_bond", budget,
+/// milestone_index]) until the platform admin resolves the dispute: an
+/// upheld dispute voids the milestone and returns the bond, a dismissed one
+/// forwards the bond to the organizer.
+#[account]
+pub struct Dispute {
+    /// Budget the disputed milestone belongs to
+    pub budget: Pubkey,
+    
+    /// Index of the disputed milestone in `budget.milestones`
+    pub milestone_index: u8,
+    
+    /// Backer who flagged the milestone and posted the bond
+    pub flagger: Pubkey,
+    
+    /// Bond posted by the flagger in lamports
+    pub bond_amount: u64,
+    
+    /// Where the dispute stands
+    pub status: DisputeStatus,
+    
+    /// When the milestone was flagged
+    pub flagged_at: i64,
+    
+    /// When the dispute was resolved (UNSET_TIMESTAMP while open)
+    pub resolved_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Dispute state, also mirrored on the disputed milestone
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisputeStatus {
+    /// Never disputed
+    None,
+    
+    /// Flagged and waiting for resolution; the milestone can't be released
+    Open,
+    
+    /// Dispute upheld; the milestone is void and will never be released
+    Upheld,
+    
+    /// Dispute dismissed; the milestone can be released as normal
+    Dismissed,
+}
+
+impl Dispute {
+    /// Calculate space needed for Dispute account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // budget
+        1 +  // milestone_index
+        32 + // flagger
+        8 +  // bond_amount
+        1 +  // status (enum)
+        8 +  // flagged_at
+        8 +  // resolved_at
+        1;   // bump
+    
+    /// Bond as a share of the disputed milestone amount (0.1%)
+    pub const BOND_BPS: u64 = 10;
+    
+    /// Smallest bond (0.01 SOL), which also keeps the bond PDA rent-exempt
+    pub const MIN_BOND_LAMPORTS: u64 = 10_000_000;
+    
+    /// Largest bond (1 SOL)
+    pub const MAX_BOND_LAMPORTS: u64 = 1_000_000_000;
+    
+    /// Bond required to flag a milestone releasing `milestone_amount`
+    pub fn bond_for(milestone_amount: u64) -> u64 {
+        let bond = (milestone_amount as u128 * Self::BOND_BPS as u128 / 10_000) as u64;
+        bond.clamp(Self::MIN_BOND_LAMPORTS, Self::MAX_BOND_LAMPORTS)
+    }
+    
+    /// Resolve an open dispute, returning its final status
+    pub fn resolve(&mut self, upheld: bool, current_ts: i64) -> Result<DisputeStatus> {
+        require!(
+            self.status == DisputeStatus::Open,
+            EventError::DisputeNotOpen
+        );
+        
+        self.status = if upheld {
+            DisputeStatus::Upheld
+        } else {
+            DisputeStatus::Dismissed
+        };
+        self.resolved_at = current_ts;
+        
+        Ok(self.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::UNSET_TIMESTAMP;
+    
+    fn open_dispute() -> Dispute {
+        Dispute {
+            budget: Pubkey::default(),
+            milestone_index: 0,
+            flagger: Pubkey::default(),
+            bond_amount: Dispute::bond_for(30_000_000_000),
+            status: DisputeStatus::Open,
+            flagged_at: 1_000,
+            resolved_at: UNSET_TIMESTAMP,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_bond_bounds() {
+        // 0.1% of a 30 SOL milestone
+        assert_eq!(Dispute::bond_for(30_000_000_000), 30_000_000);
+        
+        // Small milestones pay the minimum, huge ones are capped
+        assert_eq!(Dispute::bond_for(1_000_000_000), Dispute::MIN_BOND_LAMPORTS);
+        assert_eq!(Dispute::bond_for(0), Dispute::MIN_BOND_LAMPORTS);
+        assert_eq!(Dispute::bond_for(5_000_000_000_000), Dispute::MAX_BOND_LAMPORTS);
+        assert_eq!(Dispute::bond_for(u64::MAX), Dispute::MAX_BOND_LAMPORTS);
+    }
+    
+    #[test]
+    fn test_dispute_upheld() {
+        let mut dispute = open_dispute();
+        
+        assert_eq!(dispute.resolve(true, 2_000).unwrap(), DisputeStatus::Upheld);
+        assert_eq!(dispute.resolved_at, 2_000);
+        
+        // Can't be resolved again either way
+        assert_eq!(dispute.resolve(false, 3_000).unwrap_err(), EventError::DisputeNotOpen.into());
+        assert_eq!(dispute.status, DisputeStatus::Upheld);
+    }
+    
+    #[test]
+    fn test_dispute_dismissed() {
+        let mut dispute = open_dispute();
+        
+        assert_eq!(dispute.resolve(false, 2_000).unwrap(), DisputeStatus::Dismissed);
+        assert!(dispute.resolve(true, 3_000).is_err());
+        assert_eq!(dispute.status, DisputeStatus::Dismissed);
+        assert_eq!(dispute.bond_amount, 30_000_000);
+    }
+}
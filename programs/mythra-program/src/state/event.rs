@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::ed25519::LATEST_CHECKIN_MESSAGE_VERSION;
+use crate::errors::EventError;
+use crate::state::{Campaign, CampaignStatus, Contribution, OptionalTimestamp, PlatformConfig, RefundPolicy, UNSET_TIMESTAMP};
 
 #[account]
 pub struct Event {
@@ -14,17 +17,1252 @@ pub struct Event {
     pub crowdfunding_enabled: bool, // 1 byte - whether this event has crowdfunding
     pub campaign: Option<Pubkey>, // 33 bytes - link to campaign account (1 + 32)
     pub ticket_revenue: u64,      // 8 bytes - track ticket sales separately
+    pub shortfall_mode: bool,     // 1 byte - escrow could not cover all refunds at cancellation
+    pub refund_claim_deadline: i64, // 8 bytes - end of the refund claim registration window
+    pub total_refund_claims: u64, // 8 bytes - sum of registered refund entitlements
+    pub refund_factor: u64,       // 8 bytes - pro-rata payout factor (REFUND_FACTOR_SCALE = 100%)
+    pub refund_claims_settled: bool, // 1 byte - whether refund_factor has been computed
+    pub attendance_snapshot_count: u32, // 4 bytes - attendees appended to AttendanceList pages
+    pub maintain_ticket_index: bool, // 1 byte - maintain per-wallet TicketIndex PDAs
+    pub tickets_sold: u32,        // 4 bytes - tickets issued across all tiers, net of refunds
+    pub allow_sales_during_event: bool, // 1 byte - keep selling (door sales) until end_ts
+    pub checkin_message_version: u8, // 1 byte - ed25519 check-in message format (see crate::ed25519)
+    pub sales_halted: bool,       // 1 byte - organizer stopped all issuance (not a cancellation)
+    pub platform_fees_paid: u64,  // 8 bytes - cumulative platform share of escrow withdrawals, net of refund clawbacks
+    pub total_withdrawn: u64,     // 8 bytes - cumulative escrow withdrawals (platform share included)
+    pub backer_presale_seconds: u32, // 4 bytes - backer-only window before each tier's sale start
+    pub backer_presale_min_contribution: u64, // 8 bytes - minimum contribution for presale access
+    pub locales: Vec<[u8; 2]>,    // 4 + 2 * MAX_LOCALES bytes - ISO 639-1 codes with localized metadata
+    pub compact_events: bool,     // 1 byte - check-ins emit TicketUsedCompact instead of the full event
+    pub postponed_count: u8,      // 1 byte - times start_ts was moved later
+    pub postponed_at: i64,        // 8 bytes - when start_ts last moved (UNSET_TIMESTAMP if never)
+    pub pending_authority: Option<Pubkey>, // 33 bytes - proposed new authority awaiting acceptance (1 + 32)
+    pub checked_in_count: u32,    // 4 bytes - check-ins recorded, net of reverts
+    pub last_checkin_ts: i64,     // 8 bytes - ordered timestamp of the latest check-in (never decreases)
+    pub tickets_checked_in: u32,  // 4 bytes - distinct tickets with at least one check-in, net of reverts
+    pub tickets_refunded: u32,    // 4 bytes - tickets refunded through any refund path (cumulative)
+    pub refund_policy: RefundPolicy, // 4 + 10 * MAX_REFUND_STEPS bytes - schedule for buyer-requested refunds
+    pub refund_fee_bps_override: Option<u16>, // 3 bytes - platform-set refund fee replacing the config's (1 + 2)
+    pub pending_order_funds: u64, // 8 bytes - escrowed payments of orders not yet fulfilled or canceled
+    pub ticket_escrow_bump: u8,   // 1 byte - canonical bump of the ["ticket_escrow", event] PDA
     pub bump: u8,                 // 1 byte
 }
 
 impl Event {
     pub const MAX_METADATA_URI_LENGTH: usize = 200;
     
+    /// Maximum number of metadata locales per event
+    pub const MAX_LOCALES: usize = 8;
+    
+    /// How long ticket holders have to register refund claims in shortfall mode
+    pub const REFUND_CLAIM_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60; // 7 days
+    
+    /// How long after the end the event authority waits before closing
+    /// tickets their owners left open
+    pub const TICKET_CLOSE_GRACE_SECONDS: i64 = 30 * 24 * 60 * 60; // 30 days
+    
+    /// How long holders who bought before a postponement can refund themselves
+    pub const POSTPONEMENT_REFUND_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60; // 7 days
+    
     /// Calculate space needed for an Event account
-    /// 8 (discriminator) + 32 (authority) + 4 + metadata_uri_len + 8 (start_ts) + 
-    /// 8 (end_ts) + 4 (total_supply) + 4 (allocated_supply) + 32 (treasury) + 2 (platform_split_bps) + 
-    /// 1 (canceled) + 1 (crowdfunding_enabled) + 33 (campaign) + 8 (ticket_revenue) + 1 (bump)
+    /// 8 (discriminator) + 32 (authority) + 4 + metadata_uri_len + 8 (start_ts) +
+    /// 8 (end_ts) + 4 (total_supply) + 4 (allocated_supply) + 32 (treasury) + 2 (platform_split_bps) +
+    /// 1 (canceled) + 1 (crowdfunding_enabled) + 33 (campaign) + 8 (ticket_revenue) +
+    /// 1 (shortfall_mode) + 8 (refund_claim_deadline) + 8 (total_refund_claims) +
+    /// 8 (refund_factor) + 1 (refund_claims_settled) + 4 (attendance_snapshot_count) +
+    /// 1 (maintain_ticket_index) + 4 (tickets_sold) + 1 (allow_sales_during_event) +
+    /// 1 (checkin_message_version) + 1 (sales_halted) + 8 (platform_fees_paid) + 8 (total_withdrawn) +
+    /// 4 (backer_presale_seconds) + 8 (backer_presale_min_contribution) +
+    /// 4 + 2 * MAX_LOCALES (locales) + 1 (compact_events) + 1 (postponed_count) +
+    /// 8 (postponed_at) + 33 (pending_authority) + 4 (checked_in_count) +
+    /// 8 (last_checkin_ts) + 4 (tickets_checked_in) + 4 (tickets_refunded) +
+    /// RefundPolicy::SPACE (refund_policy) + 3 (refund_fee_bps_override) +
+    /// 8 (pending_order_funds) + 1 (ticket_escrow_bump) + 1 (bump)
+    ///
+    /// The locale list and refund schedule are always reserved at full
+    /// capacity so they can be set later without resizing the account.
     pub fn space(metadata_uri_len: usize) -> usize {
-        8 + 32 + (4 + metadata_uri_len) + 8 + 8 + 4 + 4 + 32 + 2 + 1 + 1 + 33 + 8 + 1
+        8 + 32 + (4 + metadata_uri_len) + 8 + 8 + 4 + 4 + 32 + 2 + 1 + 1 + 33 + 8 + 1 + 8 + 8 + 8 + 1 + 4 + 1 + 4 + 1 + 1 + 1 + 1 + 8 + 8 + 4 + 8 +
+            (4 + 2 * Self::MAX_LOCALES) + 1 + 1 + 8 + 33 + 4 + 8 + 4 + 4 + RefundPolicy::SPACE + 3 + 8 + 1
+    }
+    
+    /// Account size to realloc to when the metadata URI changes
+    ///
+    /// Never shrinks: the account only grows to fit a longer URI, so a
+    /// shorter URI can't cut off later fields or leave stale bytes behind.
+    pub fn realloc_space(current_len: usize, metadata_uri_len: usize) -> usize {
+        current_len.max(Self::space(metadata_uri_len))
+    }
+    
+    /// Whether the serialized event fits in an account of `data_len` bytes
+    pub fn fits_in(&self, data_len: usize) -> bool {
+        self.try_to_vec()
+            .map(|data| 8 + data.len() <= data_len)
+            .unwrap_or(false)
+    }
+    
+    /// Validate a list of metadata locales
+    ///
+    /// At most `MAX_LOCALES` codes, each a lowercase two-letter ISO 639-1
+    /// code (e.g. `b"en"`), with no duplicates.
+    pub fn validate_locales(locales: &[[u8; 2]]) -> Result<()> {
+        require!(
+            locales.len() <= Self::MAX_LOCALES,
+            EventError::TooManyLocales
+        );
+        for (i, locale) in locales.iter().enumerate() {
+            require!(
+                locale.iter().all(u8::is_ascii_lowercase),
+                EventError::InvalidLocale
+            );
+            require!(
+                !locales[..i].contains(locale),
+                EventError::InvalidLocale
+            );
+        }
+        Ok(())
+    }
+    
+    /// Metadata URI for `locale`
+    ///
+    /// Localized metadata lives next to the default document and is
+    /// addressed as `{metadata_uri}?locale=xx` (or `&locale=xx` when the
+    /// base URI already has a query string). Locales the event doesn't
+    /// list fall back to the default `metadata_uri`.
+    pub fn metadata_uri_for(&self, locale: [u8; 2]) -> String {
+        if !self.locales.contains(&locale) {
+            return self.metadata_uri.clone();
+        }
+        let separator = if self.metadata_uri.contains('?') { '&' } else { '?' };
+        format!(
+            "{}{}locale={}{}",
+            self.metadata_uri,
+            separator,
+            locale[0] as char,
+            locale[1] as char,
+        )
+    }
+    
+    /// Check a new check-in message version can be set at `current_ts`
+    ///
+    /// Versions only move forward, up to the latest supported, and are
+    /// locked once the event starts so scanners in the field see a stable
+    /// format for the whole event.
+    pub fn validate_checkin_version_bump(&self, new_version: u8, current_ts: i64) -> Result<()> {
+        require!(
+            current_ts < self.start_ts,
+            EventError::EventAlreadyStarted
+        );
+        require!(
+            new_version > self.checkin_message_version &&
+                new_version <= LATEST_CHECKIN_MESSAGE_VERSION,
+            EventError::InvalidCheckinMessageVersion
+        );
+        
+        Ok(())
+    }
+    
+    /// Fail unless tickets can still be sold at `current_ts`
+    ///
+    /// Sales stop when the event is canceled or halted by the organizer, at
+    /// `start_ts` by default, and at `end_ts` when the organizer opted into
+    /// door sales.
+    pub fn require_sales_open(&self, current_ts: i64) -> Result<()> {
+        require!(
+            !self.canceled,
+            EventError::EventCanceledNoSales
+        );
+        require!(
+            !self.sales_halted,
+            EventError::SalesHalted
+        );
+        require!(
+            current_ts < self.end_ts,
+            EventError::EventEnded
+        );
+        require!(
+            self.allow_sales_during_event || current_ts < self.start_ts,
+            EventError::EventAlreadyStarted
+        );
+        Ok(())
+    }
+    
+    /// Check whether `current_ts` falls in a tier's backer presale window
+    ///
+    /// The window is `[sale_start_ts - backer_presale_seconds, sale_start_ts)`.
+    /// Tiers without a sale start have no presale.
+    pub fn in_backer_presale(&self, tier_sale_start_ts: i64, current_ts: i64) -> bool {
+        if self.backer_presale_seconds == 0 || !tier_sale_start_ts.is_set() {
+            return false;
+        }
+        let presale_start = tier_sale_start_ts.saturating_sub(self.backer_presale_seconds as i64);
+        current_ts >= presale_start && current_ts < tier_sale_start_ts
+    }
+    
+    /// Fail unless `buyer` backed this event's funded campaign with at least
+    /// `backer_presale_min_contribution`
+    pub fn require_backer_presale_access(
+        &self,
+        campaign: Option<(Pubkey, &Campaign)>,
+        contribution: Option<&Contribution>,
+        buyer: &Pubkey,
+    ) -> Result<()> {
+        let eligible = match (campaign, contribution) {
+            (Some((campaign_key, campaign)), Some(contribution)) => {
+                self.campaign == Some(campaign_key)
+                    && campaign.status == CampaignStatus::Funded
+                    && contribution.campaign == campaign_key
+                    && contribution.contributor == *buyer
+                    && !contribution.refunded
+                    && contribution.amount >= self.backer_presale_min_contribution
+            }
+            _ => false,
+        };
+        
+        require!(eligible, EventError::BackerPresaleOnly);
+        Ok(())
+    }
+    
+    /// Record an escrow withdrawal of `amount`, of which `platform_fee`
+    /// went to the platform fee vault
+    pub fn record_withdrawal(&mut self, amount: u64, platform_fee: u64) -> Option<()> {
+        let total_withdrawn = self.total_withdrawn.checked_add(amount)?;
+        let platform_fees_paid = self.platform_fees_paid.checked_add(platform_fee)?;
+        self.total_withdrawn = total_withdrawn;
+        self.platform_fees_paid = platform_fees_paid;
+        Some(())
+    }
+    
+    /// Ticket revenue still in the escrow that belongs to the organizer
+    ///
+    /// Revenue that was already withdrawn (or swept to the campaign) and
+    /// payments of orders that can still be canceled are excluded.
+    pub fn withdrawable_revenue(&self) -> u64 {
+        self.ticket_revenue
+            .saturating_sub(self.pending_order_funds)
+            .saturating_sub(self.total_withdrawn)
+    }
+    
+    /// Revenue that can leave a ticket escrow holding `escrow_lamports`
+    ///
+    /// `withdrawable_revenue`, capped at what the escrow holds above its
+    /// rent-exempt minimum once pending order payments are set aside.
+    pub fn releasable_revenue(&self, escrow_lamports: u64, rent_exempt_minimum: u64) -> u64 {
+        let escrow_available = escrow_lamports
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(self.pending_order_funds);
+        self.withdrawable_revenue().min(escrow_available)
+    }
+    
+    /// Platform fee to claw back for a refund of `refund_amount`
+    ///
+    /// The platform is owed its share of withdrawn ticket revenue that was
+    /// kept. Once the refund brings `ticket_revenue` below `total_withdrawn`,
+    /// the difference was withdrawn and is now being paid back, so its
+    /// share of `platform_fees_paid` comes back too. Measured against the
+    /// revenue counters rather than the ticket escrow's balance, which also
+    /// holds rent and unsettled order payments.
+    pub fn platform_fee_clawback(&self, refund_amount: u64) -> Option<u64> {
+        let kept_revenue = self.ticket_revenue.saturating_sub(refund_amount);
+        let (platform_fee, _) = self.split_platform_share(self.total_withdrawn.min(kept_revenue))?;
+        Some(self.platform_fees_paid.saturating_sub(platform_fee))
+    }
+    
+    /// Record a platform fee returned to the escrow for a refund
+    pub fn record_platform_fee_clawback(&mut self, amount: u64) {
+        self.platform_fees_paid = self.platform_fees_paid.saturating_sub(amount);
+    }
+    
+    /// Whether accrued platform fees may be settled to the platform treasury
+    ///
+    /// Organizer refunds and postponement refunds both close at the start;
+    /// canceled events keep their fees available for refunds.
+    pub fn platform_fees_settleable(&self, current_ts: i64) -> bool {
+        !self.canceled && current_ts >= self.start_ts
+    }
+    
+    /// Split an amount (resale royalty or escrow withdrawal) into
+    /// (platform, organizer) amounts
+    ///
+    /// The platform receives `platform_split_bps` of the amount, rounded
+    /// down; the organizer receives the remainder including any dust.
+    pub fn split_platform_share(&self, amount: u64) -> Option<(u64, u64)> {
+        let platform_amount = (amount as u128)
+            .checked_mul(self.platform_split_bps as u128)?
+            .checked_div(10_000)?;
+        let platform_amount = u64::try_from(platform_amount).ok()?;
+        let organizer_amount = amount.checked_sub(platform_amount)?;
+        Some((platform_amount, organizer_amount))
+    }
+    
+    /// Check whether the event has ended at `current_ts`
+    pub fn has_ended(&self, current_ts: i64) -> bool {
+        current_ts > self.end_ts
+    }
+    
+    /// Check if the event has capacity left across all tiers
+    pub fn has_capacity(&self) -> bool {
+        self.tickets_sold < self.total_supply
+    }
+    
+    /// Check if the event has room for `count` more tickets across all tiers
+    pub fn has_capacity_for(&self, count: u32) -> bool {
+        self.tickets_sold
+            .checked_add(count)
+            .is_some_and(|total| total <= self.total_supply)
+    }
+    
+    /// Allocate `count` seats of the event's total supply to a new tier
+    pub fn allocate_supply(&mut self, count: u32) -> Result<()> {
+        let new_allocated = self.allocated_supply
+            .checked_add(count)
+            .ok_or(EventError::ExceedsTotalSupply)?;
+        
+        require!(
+            new_allocated <= self.total_supply,
+            EventError::ExceedsTotalSupply
+        );
+        
+        self.allocated_supply = new_allocated;
+        Ok(())
+    }
+    
+    /// Return `count` retired tier seats to the unallocated supply
+    pub fn release_allocation(&mut self, count: u32) -> Option<()> {
+        self.allocated_supply = self.allocated_supply.checked_sub(count)?;
+        Some(())
+    }
+    
+    /// Check whether an account is acceptable as an event treasury
+    ///
+    /// The treasury must not be either of the event's escrow PDAs (which would
+    /// turn withdrawals into a lamport shuffle), must not be an executable
+    /// program, and must be a wallet: on the ed25519 curve or system-owned.
+    pub fn is_valid_treasury(
+        event_key: &Pubkey,
+        treasury: &Pubkey,
+        treasury_owner: &Pubkey,
+        treasury_executable: bool,
+    ) -> bool {
+        let (escrow, _) = Pubkey::find_program_address(
+            &[b"escrow", event_key.as_ref()],
+            &crate::ID,
+        );
+        let (ticket_escrow, _) = Pubkey::find_program_address(
+            &[b"ticket_escrow", event_key.as_ref()],
+            &crate::ID,
+        );
+        
+        if *treasury == escrow || *treasury == ticket_escrow {
+            return false;
+        }
+        
+        if treasury_executable || *treasury == crate::ID {
+            return false;
+        }
+        
+        treasury.is_on_curve() || *treasury_owner == anchor_lang::system_program::ID
+    }
+    
+    /// Check whether end_ts may move to `new_end_ts`
+    ///
+    /// Once tickets have been sold (free ones included) the event can only
+    /// be extended, so holders never get a shorter event than they signed
+    /// up for. Canceled events and events without sales can move end_ts
+    /// freely.
+    pub fn can_change_end_ts(&self, new_end_ts: i64) -> bool {
+        self.canceled || self.tickets_sold == 0 || new_end_ts >= self.end_ts
+    }
+    
+    /// Move start_ts later, returning the previous start
+    ///
+    /// The new start must be in the future, before end_ts, and not earlier
+    /// than the current start, so refund windows (open until start) only
+    /// grow. Only a start that actually moves counts as a postponement.
+    pub fn postpone_start(&mut self, new_start_ts: i64, current_ts: i64) -> Result<i64> {
+        require!(
+            new_start_ts > current_ts,
+            EventError::StartTimestampInPast
+        );
+        require!(
+            new_start_ts < self.end_ts,
+            EventError::InvalidTimestamps
+        );
+        require!(
+            new_start_ts >= self.start_ts,
+            EventError::CannotMoveStartEarlier
+        );
+        
+        let old_start_ts = self.start_ts;
+        if new_start_ts > old_start_ts {
+            self.start_ts = new_start_ts;
+            self.postponed_count = self.postponed_count
+                .checked_add(1)
+                .ok_or(EventError::ArithmeticOverflow)?;
+            self.postponed_at = current_ts;
+        }
+        
+        Ok(old_start_ts)
+    }
+    
+    /// Check whether a ticket bought at `purchased_at` can be refunded by
+    /// its holder because of a postponement
+    ///
+    /// Open for `POSTPONEMENT_REFUND_WINDOW_SECONDS` after the last
+    /// postponement, to tickets bought before it, and never past the start.
+    pub fn postponement_refund_open(&self, purchased_at: i64, current_ts: i64) -> bool {
+        self.postponed_at != UNSET_TIMESTAMP
+            && purchased_at < self.postponed_at
+            && current_ts <= self.postponed_at.saturating_add(Self::POSTPONEMENT_REFUND_WINDOW_SECONDS)
+            && current_ts < self.start_ts
+    }
+    
+    /// Propose handing the event to `new_authority`
+    ///
+    /// Nothing changes until the proposed wallet accepts, so a mistyped key
+    /// can't lock the organizer out. A new proposal replaces the previous one.
+    pub fn propose_authority(&mut self, new_authority: Pubkey) {
+        self.pending_authority = Some(new_authority);
+    }
+    
+    /// Complete a proposed handoff signed by `signer`, returning the
+    /// previous authority
+    pub fn accept_authority(&mut self, signer: &Pubkey) -> Result<Pubkey> {
+        let pending_authority = self.pending_authority
+            .ok_or(EventError::NoPendingAuthority)?;
+        require_keys_eq!(
+            pending_authority,
+            *signer,
+            EventError::NotPendingAuthority
+        );
+        
+        let previous_authority = self.authority;
+        self.authority = pending_authority;
+        self.pending_authority = None;
+        
+        Ok(previous_authority)
+    }
+    
+    /// Record a check-in scanned at `current_ts`, returning its ordered
+    /// timestamp
+    ///
+    /// The ordered timestamp is `max(current_ts, last_checkin_ts)`, so the
+    /// event's check-in sequence never goes backwards when the cluster clock
+    /// is skewed between scans. Tickets keep the true clock in
+    /// `checked_in_ts`; analytics should order by the ordered timestamp.
+    /// `first_use` marks the ticket's first check-in, which also counts it
+    /// towards `tickets_checked_in`.
+    pub fn record_check_in(&mut self, current_ts: i64, first_use: bool) -> Result<i64> {
+        let ordered_ts = current_ts.max(self.last_checkin_ts);
+        
+        self.checked_in_count = self.checked_in_count
+            .checked_add(1)
+            .ok_or(EventError::ArithmeticOverflow)?;
+        if first_use {
+            self.tickets_checked_in = self.tickets_checked_in
+                .checked_add(1)
+                .ok_or(EventError::ArithmeticOverflow)?;
+        }
+        self.last_checkin_ts = ordered_ts;
+        
+        Ok(ordered_ts)
+    }
+    
+    /// Record a reverted check-in
+    ///
+    /// Only the counts go back; `last_checkin_ts` stays so later check-ins
+    /// are still ordered after everything already reported. `last_use` marks
+    /// a revert that left the ticket with no uses.
+    pub fn record_check_in_reverted(&mut self, last_use: bool) {
+        self.checked_in_count = self.checked_in_count.saturating_sub(1);
+        if last_use {
+            self.tickets_checked_in = self.tickets_checked_in.saturating_sub(1);
+        }
+    }
+    
+    /// Record a refunded ticket
+    ///
+    /// The seat goes back to the event's capacity; `tickets_refunded` keeps
+    /// the cumulative count, so `tickets_sold + tickets_refunded` is every
+    /// ticket ever issued.
+    pub fn record_ticket_refunded(&mut self) {
+        self.tickets_sold = self.tickets_sold.saturating_sub(1);
+        self.tickets_refunded = self.tickets_refunded.saturating_add(1);
+    }
+    
+    /// Platform fee (bps) kept when one of this event's tickets is refunded
+    ///
+    /// Cancellations are always full refunds. Otherwise the event's
+    /// override applies, then the platform config.
+    pub fn refund_fee_bps(&self, config: Option<&PlatformConfig>) -> u16 {
+        if self.canceled {
+            return 0;
+        }
+        self.refund_fee_bps_override
+            .unwrap_or_else(|| PlatformConfig::refund_fee_bps(config))
+    }
+    
+    /// Check if refund claims can still be registered
+    pub fn refund_claim_window_open(&self, current_ts: i64) -> bool {
+        self.canceled && self.shortfall_mode && current_ts <= self.refund_claim_deadline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519::CHECKIN_MESSAGE_V1;
+    use crate::state::{Denomination, RefundMode, RefundStep};
+    
+    #[test]
+    fn test_wallet_treasury_accepted() {
+        let event_key = Pubkey::new_unique();
+        let wallet = find_on_curve_key();
+        
+        assert!(Event::is_valid_treasury(&event_key, &wallet, &anchor_lang::system_program::ID, false));
+    }
+    
+    #[test]
+    fn test_escrow_treasury_rejected() {
+        let event_key = Pubkey::new_unique();
+        let (escrow, _) = Pubkey::find_program_address(&[b"escrow", event_key.as_ref()], &crate::ID);
+        let (ticket_escrow, _) = Pubkey::find_program_address(&[b"ticket_escrow", event_key.as_ref()], &crate::ID);
+        
+        // Escrow PDAs are system-owned, but still rejected
+        assert!(!Event::is_valid_treasury(&event_key, &escrow, &anchor_lang::system_program::ID, false));
+        assert!(!Event::is_valid_treasury(&event_key, &ticket_escrow, &anchor_lang::system_program::ID, false));
+    }
+    
+    #[test]
+    fn test_program_treasury_rejected() {
+        let event_key = Pubkey::new_unique();
+        let loader = Pubkey::new_unique();
+        
+        // This program itself
+        assert!(!Event::is_valid_treasury(&event_key, &crate::ID, &loader, true));
+        
+        // Any executable account
+        let other_program = find_on_curve_key();
+        assert!(!Event::is_valid_treasury(&event_key, &other_program, &loader, true));
+    }
+    
+    #[test]
+    fn test_foreign_pda_treasury_rejected() {
+        let event_key = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let (pda, _) = Pubkey::find_program_address(&[b"vault"], &other_program);
+        
+        // Off-curve and owned by another program
+        assert!(!Event::is_valid_treasury(&event_key, &pda, &other_program, false));
+        
+        // Off-curve but system-owned is acceptable
+        assert!(Event::is_valid_treasury(&event_key, &pda, &anchor_lang::system_program::ID, false));
+    }
+    
+    #[test]
+    fn test_event_space() {
+        let mut event = event(3);
+        event.locales = vec![*b"en"; Event::MAX_LOCALES];
+        event.pending_authority = Some(Pubkey::default());
+        event.refund_fee_bps_override = Some(0);
+        event.refund_policy.steps = vec![
+            RefundStep { seconds_before_start: 0, refund_bps: 0 };
+            RefundPolicy::MAX_REFUND_STEPS
+        ];
+        assert_eq!(
+            8 + event.try_to_vec().unwrap().len(),
+            Event::space(event.metadata_uri.len())
+        );
+    }
+    
+    #[test]
+    fn test_validate_locales() {
+        Event::validate_locales(&[]).unwrap();
+        Event::validate_locales(&[*b"en", *b"fr", *b"ja"]).unwrap();
+        
+        let invalid: Error = EventError::InvalidLocale.into();
+        assert_eq!(Event::validate_locales(&[*b"EN"]).unwrap_err(), invalid);
+        assert_eq!(Event::validate_locales(&[*b"e1"]).unwrap_err(), invalid);
+        assert_eq!(Event::validate_locales(&[[0, 0]]).unwrap_err(), invalid);
+        assert_eq!(Event::validate_locales(&[*b"en", *b"de", *b"en"]).unwrap_err(), invalid);
+        
+        // Max list is accepted, one more is not
+        let codes: Vec<[u8; 2]> = (0..=Event::MAX_LOCALES as u8).map(|i| [b'a', b'a' + i]).collect();
+        Event::validate_locales(&codes[..Event::MAX_LOCALES]).unwrap();
+        let too_many: Error = EventError::TooManyLocales.into();
+        assert_eq!(Event::validate_locales(&codes).unwrap_err(), too_many);
+    }
+    
+    #[test]
+    fn test_metadata_uri_for() {
+        let mut event = event(3);
+        event.metadata_uri = "https://example.com/event.json".to_string();
+        event.locales = vec![*b"en", *b"es"];
+        
+        assert_eq!(event.metadata_uri_for(*b"es"), "https://example.com/event.json?locale=es");
+        
+        // Unknown locale falls back to the default URI
+        assert_eq!(event.metadata_uri_for(*b"fr"), "https://example.com/event.json");
+        
+        // Existing query string is extended rather than replaced
+        event.metadata_uri = "https://example.com/meta?id=7".to_string();
+        assert_eq!(event.metadata_uri_for(*b"en"), "https://example.com/meta?id=7&locale=en");
+        
+        // No locales configured: always the default
+        event.locales.clear();
+        assert_eq!(event.metadata_uri_for(*b"en"), "https://example.com/meta?id=7");
+    }
+    
+    #[test]
+    fn test_capacity_across_tiers() {
+        use crate::state::TicketTier;
+        
+        let tier = |max_supply: u32| TicketTier {
+            event: Pubkey::default(),
+            price_lamports: 0,
+            max_supply,
+            current_supply: 0,
+            metadata_uri: String::new(),
+            royalty_bps: 0,
+            resale_enabled: false,
+            tier_index: 0,
+            max_per_wallet: 0,
+            sale_start_ts: 0,
+            sale_end_ts: 0,
+            max_uses: 1,
+            promo: None,
+            closed: false,
+            allowlist_root: None,
+            max_resale_price_lamports: 0,
+            transferable: true,
+            seated: false,
+            next_seat: 0,
+            bump: 0,
+        };
+        
+        // Tiers over-allocated (e.g. mid-reallocation) against a 3-seat event
+        let mut event = event(3);
+        let mut vip = tier(2);
+        let mut general = tier(2);
+        
+        // Sell both VIP seats and one general seat
+        let sell = |tier: &mut TicketTier, event: &mut Event| {
+            assert!(tier.is_available() && event.has_capacity());
+            tier.current_supply += 1;
+            event.tickets_sold += 1;
+        };
+        sell(&mut vip, &mut event);
+        sell(&mut vip, &mut event);
+        sell(&mut general, &mut event);
+        
+        // General still has a seat, but the event is sold out
+        assert!(general.is_available());
+        assert!(!event.has_capacity());
+        
+        // A refund frees event capacity again
+        vip.release_seat().unwrap();
+        event.tickets_sold -= 1;
+        assert!(event.has_capacity());
+        
+        // A batch only fits if every seat does
+        assert!(event.has_capacity_for(1));
+        assert!(!event.has_capacity_for(2));
+    }
+    
+    #[test]
+    fn test_retired_supply_frees_allocation() {
+        use crate::state::TicketTier;
+        
+        // One 1000-seat tier uses the whole event; 500 seats already sold
+        let mut event = event(1_000);
+        let mut general = TicketTier {
+            event: Pubkey::default(),
+            price_lamports: 100_000_000,
+            max_supply: 1_000,
+            current_supply: 500,
+            metadata_uri: String::new(),
+            royalty_bps: 0,
+            resale_enabled: false,
+            tier_index: 0,
+            max_per_wallet: 0,
+            sale_start_ts: 0,
+            sale_end_ts: 0,
+            max_uses: 1,
+            promo: None,
+            closed: false,
+            allowlist_root: None,
+            max_resale_price_lamports: 0,
+            transferable: true,
+            seated: false,
+            next_seat: 0,
+            bump: 0,
+        };
+        event.allocate_supply(general.max_supply).unwrap();
+        assert_eq!(event.allocate_supply(1).unwrap_err(), EventError::ExceedsTotalSupply.into());
+        
+        // Retire the unsold seats
+        general.retire_supply(500).unwrap();
+        event.release_allocation(500).unwrap();
+        assert_eq!(event.allocated_supply, 500);
+        
+        // A new tier fits in the freed capacity, but no more
+        assert_eq!(event.allocate_supply(501).unwrap_err(), EventError::ExceedsTotalSupply.into());
+        event.allocate_supply(500).unwrap();
+        assert_eq!(event.allocated_supply, 1_000);
+        
+        // Releasing more than was allocated fails instead of wrapping
+        event.allocated_supply = 0;
+        assert!(event.release_allocation(1).is_none());
+    }
+    
+    #[test]
+    fn test_end_ts_before_and_after_first_sale() {
+        let mut event = event(100);
+        event.end_ts = 2_000;
+        
+        // Before any sale the event can be shortened
+        assert!(event.can_change_end_ts(1_500));
+        assert!(event.can_change_end_ts(2_500));
+        
+        // After the first sale it can only be extended, even a free one
+        event.tickets_sold = 1;
+        assert!(!event.can_change_end_ts(1_999));
+        assert!(event.can_change_end_ts(2_000));
+        assert!(event.can_change_end_ts(2_500));
+        
+        // Canceled events are unrestricted
+        event.canceled = true;
+        assert!(event.can_change_end_ts(1_500));
+    }
+    
+    #[test]
+    fn test_sales_around_event_boundaries() {
+        let mut event = event(100);
+        event.start_ts = 1_000;
+        event.end_ts = 2_000;
+        
+        // Before start sales are open
+        assert!(event.require_sales_open(999).is_ok());
+        
+        // Without door sales they stop at start
+        assert_eq!(event.require_sales_open(1_000).unwrap_err(), EventError::EventAlreadyStarted.into());
+        
+        // With door sales they run until just before end
+        event.allow_sales_during_event = true;
+        assert!(event.require_sales_open(1_000).is_ok());
+        assert!(event.require_sales_open(1_999).is_ok());
+        assert_eq!(event.require_sales_open(2_000).unwrap_err(), EventError::EventEnded.into());
+        
+        // Canceled events never sell
+        event.canceled = true;
+        assert_eq!(event.require_sales_open(999).unwrap_err(), EventError::EventCanceledNoSales.into());
+    }
+    
+    #[test]
+    fn test_has_ended() {
+        let mut event = event(100);
+        event.end_ts = 2_000;
+        
+        assert!(!event.has_ended(1_999));
+        assert!(!event.has_ended(2_000));
+        assert!(event.has_ended(2_001));
+    }
+    
+    #[test]
+    fn test_halted_sales() {
+        let mut event = event(100);
+        event.start_ts = 1_000;
+        event.end_ts = 2_000;
+        
+        // Halting stops sales even with supply left and before start
+        event.sales_halted = true;
+        assert!(event.has_capacity());
+        assert_eq!(event.require_sales_open(999).unwrap_err(), EventError::SalesHalted.into());
+        
+        // Halting is not a cancellation: refunds stay closed
+        assert!(!event.canceled);
+        
+        // Resuming reopens sales within the usual window
+        event.sales_halted = false;
+        assert!(event.require_sales_open(999).is_ok());
+        assert_eq!(event.require_sales_open(1_000).unwrap_err(), EventError::EventAlreadyStarted.into());
+    }
+    
+    #[test]
+    fn test_split_royalty() {
+        let mut event = event(100);
+        
+        // 0% platform split: everything to the organizer
+        event.platform_split_bps = 0;
+        assert_eq!(event.split_platform_share(1_000_000), Some((0, 1_000_000)));
+        
+        // 100% platform split: everything to the platform
+        event.platform_split_bps = 10_000;
+        assert_eq!(event.split_platform_share(1_000_000), Some((1_000_000, 0)));
+        
+        // Rounding dust goes to the organizer
+        event.platform_split_bps = 2_500;
+        assert_eq!(event.split_platform_share(1_001), Some((250, 751)));
+        
+        // Large royalties don't overflow
+        assert_eq!(event.split_platform_share(u64::MAX), Some((u64::MAX / 4, u64::MAX - u64::MAX / 4)));
+    }
+    
+    #[test]
+    fn test_split_withdrawal() {
+        let mut event = event(100);
+        
+        // 0 bps: the whole withdrawal goes to the organizer treasury
+        event.platform_split_bps = 0;
+        assert_eq!(event.split_platform_share(5_000_000_000), Some((0, 5_000_000_000)));
+        
+        // 250 bps: 2.5% to the platform
+        event.platform_split_bps = 250;
+        assert_eq!(event.split_platform_share(5_000_000_000), Some((125_000_000, 4_875_000_000)));
+        
+        // Odd lamport amounts round the platform share down, never losing a lamport
+        for amount in [1, 39, 41, 999_999_999, 1_000_000_001] {
+            let (platform, organizer) = event.split_platform_share(amount).unwrap();
+            assert_eq!(platform, amount * 250 / 10_000);
+            assert_eq!(platform + organizer, amount);
+        }
+        assert_eq!(event.split_platform_share(41), Some((1, 40)));
+        assert_eq!(event.split_platform_share(39), Some((0, 39)));
+    }
+    
+    #[test]
+    fn test_platform_fee_clawback() {
+        let mut event = event(100);
+        event.platform_split_bps = 250;
+        
+        // 3 SOL sold, 2 SOL withdrawn with 2.5% to the platform
+        event.ticket_revenue = 3_000_000_000;
+        event.record_withdrawal(2_000_000_000, 50_000_000).unwrap();
+        
+        // The kept revenue still covers everything withdrawn: no clawback
+        assert_eq!(event.platform_fee_clawback(1_000_000_000), Some(0));
+        
+        // Refunding into the withdrawn part returns its share
+        assert_eq!(event.platform_fee_clawback(1_600_000_000), Some(15_000_000));
+        assert_eq!(event.platform_fee_clawback(3_000_000_000), Some(50_000_000));
+        
+        // Fees already clawed back aren't returned twice
+        event.record_platform_fee_clawback(15_000_000);
+        event.ticket_revenue -= 1_600_000_000;
+        assert_eq!(event.platform_fee_clawback(0), Some(0));
+        assert_eq!(event.platform_fee_clawback(400_000_000), Some(10_000_000));
+        
+        // Nothing withdrawn, nothing to return
+        let mut unwithdrawn = event.clone();
+        unwithdrawn.total_withdrawn = 0;
+        unwithdrawn.platform_fees_paid = 0;
+        assert_eq!(unwithdrawn.platform_fee_clawback(1_000_000_000), Some(0));
+        
+        
+        event.platform_fees_paid = 25_000_000;
+        event.record_platform_fee_clawback(10_000_000);
+        assert_eq!(event.platform_fees_paid, 15_000_000);
+        event.record_platform_fee_clawback(20_000_000);
+        assert_eq!(event.platform_fees_paid, 0);
+    }
+    
+    #[test]
+    fn test_platform_fees_settleable() {
+        let mut event = event(100);
+        event.start_ts = 10_000;
+        
+        // Refunds are still open before the start
+        assert!(!event.platform_fees_settleable(9_999));
+        assert!(event.platform_fees_settleable(10_000));
+        
+        // Canceled events keep fees for ticket refunds
+        event.canceled = true;
+        assert!(!event.platform_fees_settleable(20_000));
+    }
+    
+    #[test]
+    fn test_backer_presale() {
+        let campaign_key = Pubkey::new_unique();
+        let backer = Pubkey::new_unique();
+        let outsider = Pubkey::new_unique();
+        
+        let mut event = event(100);
+        event.campaign = Some(campaign_key);
+        event.backer_presale_seconds = 48 * 3600;
+        event.backer_presale_min_contribution = 1_000_000_000;
+        
+        let mut campaign = Campaign {
+            event: Pubkey::default(),
+            organizer: Pubkey::default(),
+            funding_goal: 10_000_000_000,
+            total_raised: 10_000_000_000,
+            deadline: 0,
+            status: CampaignStatus::Funded,
+            total_contributors: 1,
+            created_at: 0,
+            total_expenses: 0,
+            total_revenue: 0,
+            backer_pool: 0,
+            organizer_pool: 0,
+            platform_pool: 0,
+            distribution_complete: false,
+            organizer_claimed: false,
+            backer_share_bps: 6000,
+            organizer_share_bps: 3500,
+            platform_share_bps: 500,
+            recorded_loss: 0,
+            escrow_bump: 0,
+            backer_claims: 0,
+            distributed_at: 0,
+            deadline_extended: false,
+            refund_mode: RefundMode::None,
+            refund_pool: 0,
+            refund_base: 0,
+            distribution_basis: 0,
+            denomination: Denomination::Lamports,
+            recovery_pool: 0,
+            claim_deadline: 0,
+            backer_paid_out: 0,
+            backer_pool_swept: false,
+            active_budget: Pubkey::default(),
+            bump: 0,
+        };
+        let mut contribution = Contribution {
+            campaign: campaign_key,
+            contributor: backer,
+            amount: 2_000_000_000,
+            contributed_at: 0,
+            refunded: false,
+            profit_share: 0,
+            profit_claimed: false,
+            refund_mode: RefundMode::None,
+            refunded_amount: 0,
+            loss_recovered: false,
+            previous_amount: 0,
+            last_increase_at: 0,
+            bump: 0,
+        };
+        
+        // Presale covers the 48h before the tier's sale start only
+        let sale_start = 1_000_000;
+        let presale_start = sale_start - 48 * 3600;
+        assert!(!event.in_backer_presale(sale_start, presale_start - 1));
+        assert!(event.in_backer_presale(sale_start, presale_start));
+        assert!(event.in_backer_presale(sale_start, sale_start - 1));
+        assert!(!event.in_backer_presale(sale_start, sale_start));
+        
+        // Unbounded tiers and events without a presale have no window
+        assert!(!event.in_backer_presale(UNSET_TIMESTAMP, 0));
+        let mut no_presale = event.clone();
+        no_presale.backer_presale_seconds = 0;
+        assert!(!no_presale.in_backer_presale(sale_start, sale_start - 1));
+        
+        // A backer above the floor gets in
+        event.require_backer_presale_access(Some((campaign_key, &campaign)), Some(&contribution), &backer).unwrap();
+        
+        // Non-backers are rejected, with or without someone else's contribution
+        let presale_only: Error = EventError::BackerPresaleOnly.into();
+        assert_eq!(event.require_backer_presale_access(None, None, &outsider).unwrap_err(), presale_only);
+        assert_eq!(
+            event.require_backer_presale_access(Some((campaign_key, &campaign)), Some(&contribution), &outsider).unwrap_err(),
+            presale_only
+        );
+        
+        // Contributions to another campaign don't count
+        assert!(event.require_backer_presale_access(Some((Pubkey::new_unique(), &campaign)), Some(&contribution), &backer).is_err());
+        
+        // Below the floor is rejected
+        contribution.amount = 999_999_999;
+        assert!(event.require_backer_presale_access(Some((campaign_key, &campaign)), Some(&contribution), &backer).is_err());
+        contribution.amount = 2_000_000_000;
+        
+        // Only funded campaigns grant access
+        campaign.status = CampaignStatus::Failed;
+        assert!(event.require_backer_presale_access(Some((campaign_key, &campaign)), Some(&contribution), &backer).is_err());
+    }
+    
+    #[test]
+    fn test_record_withdrawal() {
+        let mut event = event(100);
+        
+        event.record_withdrawal(1_000, 25).unwrap();
+        event.record_withdrawal(41, 1).unwrap();
+        assert_eq!(event.total_withdrawn, 1_041);
+        assert_eq!(event.platform_fees_paid, 26);
+        
+        // Overflow is reported instead of wrapping
+        assert!(event.record_withdrawal(u64::MAX, 0).is_none());
+    }
+    
+    fn event(total_supply: u32) -> Event {
+        Event {
+            authority: Pubkey::default(),
+            metadata_uri: "https://example.com/event.json".to_string(),
+            start_ts: 0,
+            end_ts: 0,
+            total_supply,
+            allocated_supply: 0,
+            treasury: Pubkey::default(),
+            platform_split_bps: 0,
+            canceled: false,
+            crowdfunding_enabled: false,
+            campaign: Some(Pubkey::default()),
+            ticket_revenue: 0,
+            shortfall_mode: false,
+            refund_claim_deadline: 0,
+            total_refund_claims: 0,
+            refund_factor: 0,
+            refund_claims_settled: false,
+            attendance_snapshot_count: 0,
+            maintain_ticket_index: false,
+            tickets_sold: 0,
+            allow_sales_during_event: false,
+            checkin_message_version: CHECKIN_MESSAGE_V1,
+            sales_halted: false,
+            platform_fees_paid: 0,
+            total_withdrawn: 0,
+            backer_presale_seconds: 0,
+            backer_presale_min_contribution: 0,
+            locales: Vec::new(),
+            compact_events: false,
+            postponed_count: 0,
+            postponed_at: UNSET_TIMESTAMP,
+            pending_authority: None,
+            checked_in_count: 0,
+            last_checkin_ts: UNSET_TIMESTAMP,
+            tickets_checked_in: 0,
+            tickets_refunded: 0,
+            refund_policy: RefundPolicy::default(),
+            refund_fee_bps_override: None,
+            pending_order_funds: 0,
+            ticket_escrow_bump: 0,
+            bump: 0,
+        }
+    }
+    
+    fn find_on_curve_key() -> Pubkey {
+        (0u8..=255)
+            .map(|i| Pubkey::new_from_array([i; 32]))
+            .find(|key| key.is_on_curve())
+            .unwrap()
+    }
+    
+    #[test]
+    fn test_checkin_version_bump() {
+        let mut event = event(100);
+        event.start_ts = 1_000;
+        
+        // New events use the legacy format until bumped
+        assert_eq!(event.checkin_message_version, CHECKIN_MESSAGE_V1);
+        assert!(event.validate_checkin_version_bump(LATEST_CHECKIN_MESSAGE_VERSION, 999).is_ok());
+        
+        // Locked once the event starts
+        assert_eq!(
+            event.validate_checkin_version_bump(LATEST_CHECKIN_MESSAGE_VERSION, 1_000).unwrap_err(),
+            EventError::EventAlreadyStarted.into()
+        );
+        
+        // No downgrades, no-op bumps or unknown versions
+        event.checkin_message_version = LATEST_CHECKIN_MESSAGE_VERSION;
+        for version in [CHECKIN_MESSAGE_V1, LATEST_CHECKIN_MESSAGE_VERSION, LATEST_CHECKIN_MESSAGE_VERSION + 1] {
+            assert_eq!(
+                event.validate_checkin_version_bump(version, 0).unwrap_err(),
+                EventError::InvalidCheckinMessageVersion.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn test_realloc_space_never_shrinks() {
+        let mut event = event(100);
+        let mut data_len = Event::space(event.metadata_uri.len());
+        
+        // Alternate long and short URIs across several updates
+        for uri_len in [200, 10, 150, 0, 200, 1, 50] {
+            let new_len = Event::realloc_space(data_len, uri_len);
+            assert!(new_len >= data_len);
+            data_len = new_len;
+            
+            event.metadata_uri = "u".repeat(uri_len);
+            assert!(event.fits_in(data_len));
+        }
+        
+        // Grew once to the longest URI and stayed there
+        assert_eq!(data_len, Event::space(Event::MAX_METADATA_URI_LENGTH));
+        
+        // An account sized for a shorter URI can't hold a longer one
+        event.metadata_uri = "u".repeat(20);
+        event.locales = vec![*b"en"; Event::MAX_LOCALES];
+        event.pending_authority = Some(Pubkey::default());
+        event.refund_fee_bps_override = Some(0);
+        event.refund_policy.steps = vec![
+            RefundStep { seconds_before_start: 0, refund_bps: 0 };
+            RefundPolicy::MAX_REFUND_STEPS
+        ];
+        assert!(!event.fits_in(Event::space(19)));
+        assert!(event.fits_in(Event::space(20)));
+    }
+    
+    #[test]
+    fn test_postpone_start() {
+        let mut event = event(100);
+        event.start_ts = 10_000;
+        event.end_ts = 20_000;
+        
+        // Moving the start later counts as a postponement
+        assert_eq!(event.postpone_start(12_000, 5_000).unwrap(), 10_000);
+        assert_eq!(event.start_ts, 12_000);
+        assert_eq!(event.postponed_count, 1);
+        assert_eq!(event.postponed_at, 5_000);
+        
+        // Earlier than the current start, in the past or past the end is rejected
+        assert_eq!(event.postpone_start(11_000, 6_000).unwrap_err(), EventError::CannotMoveStartEarlier.into());
+        assert_eq!(event.postpone_start(12_500, 13_000).unwrap_err(), EventError::StartTimestampInPast.into());
+        assert_eq!(event.postpone_start(20_000, 6_000).unwrap_err(), EventError::InvalidTimestamps.into());
+        assert_eq!(event.start_ts, 12_000);
+        
+        // Keeping the same start isn't a postponement
+        event.postpone_start(12_000, 7_000).unwrap();
+        assert_eq!(event.postponed_count, 1);
+        assert_eq!(event.postponed_at, 5_000);
+    }
+    
+    #[test]
+    fn test_postponement_refund_window() {
+        let mut event = event(100);
+        event.start_ts = 100_000_000;
+        event.end_ts = 200_000_000;
+        
+        // Never postponed: no holder refunds
+        assert!(!event.postponement_refund_open(1_000, 2_000));
+        
+        event.postpone_start(150_000_000, 10_000).unwrap();
+        let window_end = 10_000 + Event::POSTPONEMENT_REFUND_WINDOW_SECONDS;
+        
+        // Bought before the postponement: refundable until the window closes
+        assert!(event.postponement_refund_open(9_999, 10_000));
+        assert!(event.postponement_refund_open(9_999, window_end));
+        assert!(!event.postponement_refund_open(9_999, window_end + 1));
+        
+        // Bought after it: the buyer knew the new date
+        assert!(!event.postponement_refund_open(10_000, 10_001));
+        
+        // Never once the event has started
+        event.start_ts = 20_000;
+        assert!(!event.postponement_refund_open(9_999, 20_000));
+    }
+    
+    #[test]
+    fn test_authority_proposal_overwrite() {
+        let mut event = event(100);
+        let organizer = event.authority;
+        let typo = Pubkey::new_unique();
+        let intended = Pubkey::new_unique();
+        
+        // A mistyped proposal changes nothing and can be replaced
+        event.propose_authority(typo);
+        assert_eq!(event.authority, organizer);
+        event.propose_authority(intended);
+        assert_eq!(event.pending_authority, Some(intended));
+        
+        // The replaced proposal can no longer be accepted
+        assert_eq!(event.accept_authority(&typo).unwrap_err(), EventError::NotPendingAuthority.into());
+        
+        assert_eq!(event.accept_authority(&intended).unwrap(), organizer);
+        assert_eq!(event.authority, intended);
+        assert_eq!(event.pending_authority, None);
+    }
+    
+    #[test]
+    fn test_authority_accept_by_wrong_wallet() {
+        let mut event = event(100);
+        let organizer = event.authority;
+        let new_authority = Pubkey::new_unique();
+        
+        // Nothing to accept before a proposal
+        assert_eq!(event.accept_authority(&new_authority).unwrap_err(), EventError::NoPendingAuthority.into());
+        
+        event.propose_authority(new_authority);
+        
+        // Neither a stranger nor the current authority can accept
+        assert_eq!(event.accept_authority(&Pubkey::new_unique()).unwrap_err(), EventError::NotPendingAuthority.into());
+        assert_eq!(event.accept_authority(&organizer).unwrap_err(), EventError::NotPendingAuthority.into());
+        assert_eq!(event.authority, organizer);
+        assert_eq!(event.pending_authority, Some(new_authority));
+        
+        // Once accepted the proposal is used up
+        event.accept_authority(&new_authority).unwrap();
+        assert_eq!(event.accept_authority(&new_authority).unwrap_err(), EventError::NoPendingAuthority.into());
+    }
+    
+    #[test]
+    fn test_check_in_order_with_warped_clock() {
+        let mut event = event(100);
+        
+        // Clock jumps back twice between scans
+        let clock = [1_000, 1_005, 1_003, 1_010, 990, 1_011];
+        let ordered: Vec<i64> = clock.iter()
+            .map(|&ts| event.record_check_in(ts, true).unwrap())
+            .collect();
+        
+        assert_eq!(ordered, vec![1_000, 1_005, 1_005, 1_010, 1_010, 1_011]);
+        assert!(ordered.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(event.checked_in_count, 6);
+        assert_eq!(event.last_checkin_ts, 1_011);
+        
+        // A revert gives back the count but never moves the order back
+        event.record_check_in_reverted(true);
+        assert_eq!(event.checked_in_count, 5);
+        assert_eq!(event.record_check_in(1_000, true).unwrap(), 1_011);
+        
+        event.checked_in_count = 0;
+        event.record_check_in_reverted(true);
+        assert_eq!(event.checked_in_count, 0);
+    }
+    
+    #[test]
+    fn test_attendance_counters() {
+        let mut event = event(100);
+        event.tickets_sold = 3;
+        
+        // A three-use pass counts as one attendee, however often it's scanned
+        event.record_check_in(1_000, true).unwrap();
+        event.record_check_in(1_100, false).unwrap();
+        event.record_check_in(1_200, true).unwrap();
+        assert_eq!(event.checked_in_count, 3);
+        assert_eq!(event.tickets_checked_in, 2);
+        
+        // Reverting a repeat scan keeps the attendee; reverting the only one doesn't
+        event.record_check_in_reverted(false);
+        assert_eq!(event.tickets_checked_in, 2);
+        event.record_check_in_reverted(true);
+        assert_eq!(event.tickets_checked_in, 1);
+        
+        // Refunds move seats from sold to refunded
+        event.record_ticket_refunded();
+        assert_eq!(event.tickets_sold, 2);
+        assert_eq!(event.tickets_refunded, 1);
+        assert_eq!(event.tickets_sold + event.tickets_refunded, 3);
+    }
+    
+    #[test]
+    fn test_refund_fee_bps() {
+        let mut event = event(100);
+        assert_eq!(event.refund_fee_bps(None), PlatformConfig::DEFAULT_REFUND_FEE_BPS);
+        
+        let mut config = PlatformConfig::try_deserialize_unchecked(&mut &[0u8; PlatformConfig::LEN][..]).unwrap();
+        config.refund_fee_bps = 250;
+        assert_eq!(event.refund_fee_bps(Some(&config)), 250);
+        
+        // The event's override wins over the config
+        event.refund_fee_bps_override = Some(100);
+        assert_eq!(event.refund_fee_bps(Some(&config)), 100);
+        event.refund_fee_bps_override = Some(0);
+        assert_eq!(event.refund_fee_bps(Some(&config)), 0);
+        
+        // Cancellations are always full refunds
+        event.refund_fee_bps_override = Some(100);
+        event.canceled = true;
+        assert_eq!(event.refund_fee_bps(Some(&config)), 0);
     }
 }
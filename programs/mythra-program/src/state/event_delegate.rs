@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+
+/// Co-organizer allowed to run operational instructions for an event
+///
+/// PDA: ["delegate", event, delegate]. `permissions` is a bitmask of the
+/// `PERMISSION_*` flags; fund movements (`withdraw_funds`, `close_event`)
+/// and treasury changes stay with the event authority.
+#[account]
+pub struct EventDelegate {
+    pub event: Pubkey,          // 32 bytes - event the delegate acts for
+    pub delegate: Pubkey,       // 32 bytes - delegate signing key
+    pub permissions: u8,        // 1 byte - PERMISSION_* bitmask
+    pub added_at: i64,          // 8 bytes - registration timestamp
+    pub bump: u8,               // 1 byte
+}
+
+impl EventDelegate {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 1; // 82 bytes
+    
+    /// May create ticket tiers
+    pub const PERMISSION_TIERS: u8 = 1 << 0;
+    /// May register externally minted tickets
+    pub const PERMISSION_MINTS: u8 = 1 << 1;
+    /// May refund tickets
+    pub const PERMISSION_REFUNDS: u8 = 1 << 2;
+    /// May update event settings (except the treasury)
+    pub const PERMISSION_UPDATE: u8 = 1 << 3;
+    
+    pub const ALL_PERMISSIONS: u8 = Self::PERMISSION_TIERS
+        | Self::PERMISSION_MINTS
+        | Self::PERMISSION_REFUNDS
+        | Self::PERMISSION_UPDATE;
+    
+    /// Check a permission bitmask is non-empty and only uses known flags
+    pub fn validate_permissions(permissions: u8) -> Result<()> {
+        require!(
+            permissions != 0 && permissions & !Self::ALL_PERMISSIONS == 0,
+            EventError::InvalidDelegatePermissions
+        );
+        
+        Ok(())
+    }
+    
+    /// Whether this delegate entry lets `signer` act with `permission`
+    pub fn allows(&self, signer: &Pubkey, permission: u8) -> bool {
+        self.delegate == *signer && self.permissions & permission == permission
+    }
+    
+    /// Whether `signer` may run an operational instruction on an event
+    ///
+    /// The event authority always may; anyone else needs a delegate entry
+    /// (already checked against the event by its seeds) carrying `permission`.
+    pub fn authorizes(
+        authority: &Pubkey,
+        signer: &Pubkey,
+        delegate: Option<&EventDelegate>,
+        permission: u8,
+    ) -> bool {
+        *authority == *signer
+            || delegate.is_some_and(|delegate| delegate.allows(signer, permission))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn delegate(signer: Pubkey, permissions: u8) -> EventDelegate {
+        EventDelegate {
+            event: Pubkey::new_unique(),
+            delegate: signer,
+            permissions,
+            added_at: 0,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_event_delegate_space() {
+        let entry = delegate(Pubkey::default(), 0);
+        assert_eq!(8 + entry.try_to_vec().unwrap().len(), EventDelegate::SPACE);
+    }
+    
+    #[test]
+    fn test_validate_permissions() {
+        assert!(EventDelegate::validate_permissions(EventDelegate::PERMISSION_TIERS).is_ok());
+        assert!(EventDelegate::validate_permissions(EventDelegate::ALL_PERMISSIONS).is_ok());
+        
+        for permissions in [0, 1 << 4, EventDelegate::PERMISSION_TIERS | 1 << 7] {
+            assert_eq!(
+                EventDelegate::validate_permissions(permissions).unwrap_err(),
+                EventError::InvalidDelegatePermissions.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn test_authority_needs_no_delegate() {
+        let authority = Pubkey::new_unique();
+        
+        assert!(EventDelegate::authorizes(&authority, &authority, None, EventDelegate::PERMISSION_REFUNDS));
+        assert!(!EventDelegate::authorizes(&authority, &Pubkey::new_unique(), None, EventDelegate::PERMISSION_REFUNDS));
+    }
+    
+    #[test]
+    fn test_tier_only_delegate_rejected_from_refunds() {
+        let authority = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let entry = delegate(signer, EventDelegate::PERMISSION_TIERS);
+        
+        assert!(EventDelegate::authorizes(&authority, &signer, Some(&entry), EventDelegate::PERMISSION_TIERS));
+        assert!(!EventDelegate::authorizes(&authority, &signer, Some(&entry), EventDelegate::PERMISSION_REFUNDS));
+        assert!(!EventDelegate::authorizes(&authority, &signer, Some(&entry), EventDelegate::PERMISSION_MINTS));
+        assert!(!EventDelegate::authorizes(&authority, &signer, Some(&entry), EventDelegate::PERMISSION_UPDATE));
+    }
+    
+    #[test]
+    fn test_delegate_entry_bound_to_its_signer() {
+        let authority = Pubkey::new_unique();
+        let entry = delegate(Pubkey::new_unique(), EventDelegate::ALL_PERMISSIONS);
+        
+        assert!(!EventDelegate::authorizes(&authority, &Pubkey::new_unique(), Some(&entry), EventDelegate::PERMISSION_TIERS));
+    }
+}
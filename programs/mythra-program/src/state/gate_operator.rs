@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+
+/// Gate operator (venue scanner) registered for an event
+///
+/// PDA: ["gate_operator", event, operator]. Registered operators can check
+/// tickets in by signing a challenge with their scanner key, see
+/// `mark_ticket_used_by_operator`.
+#[account]
+pub struct GateOperator {
+    pub event: Pubkey,          // 32 bytes - event the operator may scan for
+    pub operator: Pubkey,       // 32 bytes - scanner signing key
+    pub added_at: i64,          // 8 bytes - registration timestamp
+    pub bump: u8,               // 1 byte
+}
+
+impl GateOperator {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1; // 81 bytes
+    
+    /// Length of the message a gate operator signs to check a ticket in
+    pub const CHECKIN_MESSAGE_LEN: usize = 32 + 32 + 8;
+    
+    /// Message a gate operator signs to check `ticket` in
+    ///
+    /// ticket (32 bytes) || nonce_hash (32 bytes) || nonce_value (u64 LE),
+    /// so a signature for one ticket can't be replayed for another.
+    pub fn checkin_message(
+        ticket: &Pubkey,
+        nonce_hash: &[u8; 32],
+        nonce_value: u64,
+    ) -> [u8; Self::CHECKIN_MESSAGE_LEN] {
+        let mut message = [0u8; Self::CHECKIN_MESSAGE_LEN];
+        message[0..32].copy_from_slice(ticket.as_ref());
+        message[32..64].copy_from_slice(nonce_hash);
+        message[64..72].copy_from_slice(&nonce_value.to_le_bytes());
+        message
+    }
+    
+    /// Check a signed message is exactly the check-in message for `ticket`
+    pub fn verify_checkin_message(
+        message: &[u8],
+        ticket: &Pubkey,
+        nonce_hash: &[u8; 32],
+        nonce_value: u64,
+    ) -> Result<()> {
+        require!(
+            message == Self::checkin_message(ticket, nonce_hash, nonce_value),
+            EventError::InvalidSignature
+        );
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_gate_operator_space() {
+        let operator = GateOperator {
+            event: Pubkey::default(),
+            operator: Pubkey::default(),
+            added_at: 0,
+            bump: 0,
+        };
+        assert_eq!(8 + operator.try_to_vec().unwrap().len(), GateOperator::SPACE);
+    }
+    
+    #[test]
+    fn test_checkin_message_accepted() {
+        let ticket = Pubkey::new_unique();
+        let message = GateOperator::checkin_message(&ticket, &[1; 32], 42);
+        
+        assert!(GateOperator::verify_checkin_message(&message, &ticket, &[1; 32], 42).is_ok());
+    }
+    
+    #[test]
+    fn test_wrong_ticket_message_rejected() {
+        let signed_for = Pubkey::new_unique();
+        let presented = Pubkey::new_unique();
+        let message = GateOperator::checkin_message(&signed_for, &[1; 32], 42);
+        
+        assert_eq!(
+            GateOperator::verify_checkin_message(&message, &presented, &[1; 32], 42).unwrap_err(),
+            EventError::InvalidSignature.into()
+        );
+    }
+    
+    #[test]
+    fn test_tampered_message_rejected() {
+        let ticket = Pubkey::new_unique();
+        let message = GateOperator::checkin_message(&ticket, &[1; 32], 42);
+        
+        // Different nonce hash or value
+        assert!(GateOperator::verify_checkin_message(&message, &ticket, &[2; 32], 42).is_err());
+        assert!(GateOperator::verify_checkin_message(&message, &ticket, &[1; 32], 43).is_err());
+        
+        // Old owner-style message without the ticket, and trailing bytes
+        assert!(GateOperator::verify_checkin_message(&message[32..], &ticket, &[1; 32], 42).is_err());
+        let mut padded = message.to_vec();
+        padded.push(0);
+        assert!(GateOperator::verify_checkin_message(&padded, &ticket, &[1; 32], 42).is_err());
+    }
+}
@@ -7,6 +7,21 @@ pub mod campaign;
 pub mod contribution;
 pub mod budget;
 pub mod vote;
+pub mod refund_claim;
+pub mod platform_config;
+pub mod buyer_record;
+pub mod attendance_list;
+pub mod ticket_index;
+pub mod timestamp;
+pub mod gate_operator;
+pub mod dispute;
+pub mod platform_stats;
+pub mod blocklist;
+pub mod event_delegate;
+pub mod platform_fee_vault;
+pub mod seat_assignment;
+pub mod pending_transfer;
+pub mod refund_policy;
 
 pub use event::*;
 pub use ticket_tier::*;
@@ -17,3 +32,18 @@ pub use campaign::*;
 pub use contribution::*;
 pub use budget::*;
 pub use vote::*;
+pub use refund_claim::*;
+pub use platform_config::*;
+pub use buyer_record::*;
+pub use attendance_list::*;
+pub use ticket_index::*;
+pub use timestamp::*;
+pub use gate_operator::*;
+pub use dispute::*;
+pub use platform_stats::*;
+pub use blocklist::*;
+pub use event_delegate::*;
+pub use platform_fee_vault::*;
+pub use seat_assignment::*;
+pub use pending_transfer::*;
+pub use refund_policy::*;
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::errors::EventError;
 
+/// Check-in nonce registered ahead of an ed25519 check-in
+///
+/// Created by `create_checkin_nonce` with a real expiry, consumed by
+/// `mark_ticket_used_ed25519`, and closed by `close_nonce` once expired.
 #[account]
 pub struct Nonce {
     pub ticket: Pubkey,         // 32 bytes - reference to ticket
@@ -7,15 +12,108 @@ pub struct Nonce {
     pub used: bool,             // 1 byte - whether nonce has been used
     pub created_at: i64,        // 8 bytes - creation timestamp
     pub expires_at: i64,        // 8 bytes - expiration timestamp
+    pub payer: Pubkey,          // 32 bytes - receives rent when closed
     pub bump: u8,               // 1 byte
 }
 
 impl Nonce {
-    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 1; // 90 bytes
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 32 + 1; // 122 bytes
     
     pub const DEFAULT_EXPIRY_SECONDS: i64 = 300; // 5 minutes
     
+    pub const MAX_EXPIRY_SECONDS: i64 = 86_400; // 24 hours
+    
     pub fn is_expired(&self, current_ts: i64) -> bool {
         current_ts > self.expires_at
     }
+    
+    /// Expiry timestamp for a nonce registered now that lives `expires_in` seconds
+    pub fn expiry_from(current_ts: i64, expires_in: i64) -> Result<i64> {
+        require!(
+            expires_in > 0 && expires_in <= Self::MAX_EXPIRY_SECONDS,
+            EventError::InvalidNonceExpiry
+        );
+        
+        current_ts
+            .checked_add(expires_in)
+            .ok_or(EventError::ArithmeticOverflow.into())
+    }
+    
+    /// Check the nonce may be consumed for a check-in right now
+    pub fn require_usable(&self, current_ts: i64) -> Result<()> {
+        require!(
+            !self.is_expired(current_ts),
+            EventError::NonceExpired
+        );
+        require!(
+            !self.used,
+            EventError::NonceUsed
+        );
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn registered(created_at: i64, expires_in: i64) -> Nonce {
+        Nonce {
+            ticket: Pubkey::default(),
+            nonce_hash: [7; 32],
+            used: false,
+            created_at,
+            expires_at: Nonce::expiry_from(created_at, expires_in).unwrap(),
+            payer: Pubkey::default(),
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_nonce_space() {
+        assert_eq!(8 + registered(0, 1).try_to_vec().unwrap().len(), Nonce::SPACE);
+    }
+    
+    #[test]
+    fn test_expiry_bounds() {
+        assert_eq!(Nonce::expiry_from(1_000, 300).unwrap(), 1_300);
+        assert_eq!(
+            Nonce::expiry_from(1_000, Nonce::MAX_EXPIRY_SECONDS).unwrap(),
+            1_000 + Nonce::MAX_EXPIRY_SECONDS
+        );
+        
+        for expires_in in [0, -1, Nonce::MAX_EXPIRY_SECONDS + 1] {
+            assert_eq!(
+                Nonce::expiry_from(1_000, expires_in).unwrap_err(),
+                EventError::InvalidNonceExpiry.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn test_expired_preregistered_nonce_rejected() {
+        let nonce = registered(1_000, 300);
+        
+        // Usable up to and including the expiry second
+        assert!(nonce.require_usable(1_000).is_ok());
+        assert!(nonce.require_usable(1_300).is_ok());
+        
+        // Rejected once the expiry has passed
+        assert_eq!(
+            nonce.require_usable(1_301).unwrap_err(),
+            EventError::NonceExpired.into()
+        );
+    }
+    
+    #[test]
+    fn test_used_nonce_rejected() {
+        let mut nonce = registered(1_000, 300);
+        nonce.used = true;
+        
+        assert_eq!(
+            nonce.require_usable(1_100).unwrap_err(),
+            EventError::NonceUsed.into()
+        );
+    }
 }
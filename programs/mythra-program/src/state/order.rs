@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
 
+/// Paid order awaiting (or holding) its ticket NFT
+///
+/// Created by `create_order` once payment reaches the ticket escrow, and
+/// completed by `fulfill_order` after the NFT is minted to the buyer.
+/// PDA: ["order", event, sha256(order_id)]
 #[account]
 pub struct Order {
     pub buyer: Pubkey,          // 32 bytes - buyer who created order
@@ -15,8 +21,96 @@ pub struct Order {
 impl Order {
     pub const MAX_ORDER_ID_LENGTH: usize = 64;
     
+    /// How long the event authority must wait before canceling an unfulfilled order
+    pub const FULFILLMENT_TIMEOUT_SECONDS: i64 = 24 * 60 * 60;
+    
     /// Calculate space needed for an Order account
     pub fn space(order_id_len: usize) -> usize {
         8 + 32 + 32 + 32 + 32 + (4 + order_id_len) + 8 + 8 + 1
     }
+    
+    /// PDA seed for an order id (ids can exceed the 32 byte seed limit)
+    pub fn seed_hash(order_id: &str) -> [u8; 32] {
+        hash(order_id.as_bytes()).to_bytes()
+    }
+    
+    /// Check if a ticket has been registered for this order
+    pub fn is_fulfilled(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+    
+    /// Check if `canceler` may cancel this order at `current_ts`
+    ///
+    /// The buyer can cancel any time before fulfillment; the event authority
+    /// only once the order has been waiting longer than the fulfillment timeout.
+    pub fn can_cancel(&self, canceler: &Pubkey, event_authority: &Pubkey, current_ts: i64) -> bool {
+        if *canceler == self.buyer {
+            return true;
+        }
+        
+        *canceler == *event_authority
+            && current_ts >= self.timestamp.saturating_add(Self::FULFILLMENT_TIMEOUT_SECONDS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn order(buyer: Pubkey) -> Order {
+        Order {
+            buyer,
+            event: Pubkey::default(),
+            tier: Pubkey::default(),
+            mint: Pubkey::default(),
+            order_id: "order-1".to_string(),
+            amount_paid: 100_000_000,
+            timestamp: 1_000,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_order_space_matches_serialized_size() {
+        let order = order(Pubkey::new_unique());
+        
+        let mut data = Vec::new();
+        order.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Order::space(order.order_id.len()));
+    }
+    
+    #[test]
+    fn test_fulfillment() {
+        let mut order = order(Pubkey::new_unique());
+        assert!(!order.is_fulfilled());
+        
+        order.mint = Pubkey::new_unique();
+        assert!(order.is_fulfilled());
+    }
+    
+    #[test]
+    fn test_cancel_permissions() {
+        let buyer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let order = order(buyer);
+        let timeout_ts = order.timestamp + Order::FULFILLMENT_TIMEOUT_SECONDS;
+        
+        // Buyer can cancel right away
+        assert!(order.can_cancel(&buyer, &authority, order.timestamp));
+        
+        // Authority must wait for the timeout
+        assert!(!order.can_cancel(&authority, &authority, timeout_ts - 1));
+        assert!(order.can_cancel(&authority, &authority, timeout_ts));
+        
+        // Anyone else never can
+        assert!(!order.can_cancel(&Pubkey::new_unique(), &authority, i64::MAX));
+    }
+    
+    #[test]
+    fn test_long_order_ids_hash_to_distinct_seeds() {
+        let a = "a".repeat(Order::MAX_ORDER_ID_LENGTH);
+        let b = format!("{}b", "a".repeat(Order::MAX_ORDER_ID_LENGTH - 1));
+        
+        assert_ne!(Order::seed_hash(&a), Order::seed_hash(&b));
+    }
 }
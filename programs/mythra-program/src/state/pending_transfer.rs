@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::errors::EventError;
+
+/// Ticket held in escrow until someone claims it with a secret
+///
+/// PDA: ["pending_transfer", ticket]. Created by `initiate_pending_transfer`,
+/// which moves the NFT into an escrow token account (PDA
+/// ["pending_transfer_escrow", ticket]) owned by this account. Whoever
+/// presents the preimage of `claim_hash` before `expires_at` receives the
+/// ticket through `claim_pending_transfer`; after that the sender can take
+/// it back with `cancel_pending_transfer`. Both close the escrow and this
+/// account, returning the rent to the sender.
+#[account]
+pub struct PendingTransfer {
+    pub ticket: Pubkey,         // 32 bytes - ticket held in escrow
+    pub sender: Pubkey,         // 32 bytes - owner who sent it (gets it back on cancel)
+    pub claim_hash: [u8; 32],   // 32 bytes - sha256 of the claim secret
+    pub created_at: i64,        // 8 bytes - initiation timestamp
+    pub expires_at: i64,        // 8 bytes - last moment the ticket can be claimed (exclusive)
+    pub bump: u8,               // 1 byte
+}
+
+impl PendingTransfer {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1; // 121 bytes
+    
+    pub const MAX_EXPIRY_SECONDS: i64 = 30 * 86_400; // 30 days
+    
+    /// Validate a claim deadline requested at `current_ts`
+    pub fn validate_expiry(current_ts: i64, expires_at: i64) -> Result<()> {
+        require!(
+            expires_at > current_ts && expires_at - current_ts <= Self::MAX_EXPIRY_SECONDS,
+            EventError::InvalidClaimExpiry
+        );
+        Ok(())
+    }
+    
+    /// Fail unless `preimage` unlocks the transfer at `current_ts`
+    pub fn require_claimable(&self, preimage: &[u8], current_ts: i64) -> Result<()> {
+        require!(
+            current_ts < self.expires_at,
+            EventError::ClaimExpired
+        );
+        require!(
+            hash(preimage).to_bytes() == self.claim_hash,
+            EventError::InvalidClaimPreimage
+        );
+        Ok(())
+    }
+    
+    /// Fail unless the claim window is over and the sender may take the
+    /// ticket back
+    pub fn require_cancelable(&self, current_ts: i64) -> Result<()> {
+        require!(
+            current_ts >= self.expires_at,
+            EventError::ClaimNotExpired
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn pending(secret: &[u8]) -> PendingTransfer {
+        PendingTransfer {
+            ticket: Pubkey::new_unique(),
+            sender: Pubkey::new_unique(),
+            claim_hash: hash(secret).to_bytes(),
+            created_at: 1_000,
+            expires_at: 2_000,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_pending_transfer_space() {
+        assert_eq!(8 + pending(b"").try_to_vec().unwrap().len(), PendingTransfer::SPACE);
+    }
+    
+    #[test]
+    fn test_expiry_bounds() {
+        assert!(PendingTransfer::validate_expiry(1_000, 1_001).is_ok());
+        assert!(PendingTransfer::validate_expiry(1_000, 1_000 + PendingTransfer::MAX_EXPIRY_SECONDS).is_ok());
+        
+        let invalid = EventError::InvalidClaimExpiry.into();
+        assert_eq!(PendingTransfer::validate_expiry(1_000, 1_000).unwrap_err(), invalid);
+        assert_eq!(
+            PendingTransfer::validate_expiry(1_000, 1_001 + PendingTransfer::MAX_EXPIRY_SECONDS).unwrap_err(),
+            invalid
+        );
+    }
+    
+    #[test]
+    fn test_claim_window() {
+        let pending = pending(b"open sesame");
+        assert!(pending.require_claimable(b"open sesame", 1_999).is_ok());
+        assert_eq!(
+            pending.require_claimable(b"open sesame!", 1_999).unwrap_err(),
+            EventError::InvalidClaimPreimage.into()
+        );
+        
+        // Claims and cancellation never overlap
+        assert_eq!(pending.require_cancelable(1_999).unwrap_err(), EventError::ClaimNotExpired.into());
+        assert_eq!(pending.require_claimable(b"open sesame", 2_000).unwrap_err(), EventError::ClaimExpired.into());
+        assert!(pending.require_cancelable(2_000).is_ok());
+    }
+}
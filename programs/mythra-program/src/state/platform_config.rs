@@ -0,0 +1,537 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+
+/// Platform-wide configuration (singleton PDA: ["config"])
+///
+/// Holds the platform admin, treasury, global pause switch and the policy
+/// limits that apply to every campaign. Instructions that read the config
+/// fall back to the program defaults below while it has not been
+/// initialized yet.
+#[account]
+pub struct PlatformConfig {
+    /// Platform admin allowed to manage the config
+    pub admin: Pubkey,
+    
+    /// Minimum share of campaign profit reserved for the platform (basis points)
+    pub min_platform_share_bps: u16,
+    
+    /// Minimum share of campaign profit reserved for backers (basis points)
+    pub min_backer_share_bps: u16,
+    
+    /// Seconds after an event ends before anyone may trigger distribution
+    pub distribution_timeout_seconds: i64,
+    
+    /// Wallet receiving platform fees and royalties
+    pub platform_treasury: Pubkey,
+    
+    /// Default platform fee for new events (basis points)
+    pub default_platform_fee_bps: u16,
+    
+    /// Global pause switch for instructions that move user funds
+    pub paused: bool,
+    
+    /// Maximum fee a relayer may take from a sponsored refund claim
+    pub max_relayer_fee_lamports: u64,
+    
+    /// Seconds after an event ends before distribution may be triggered at
+    /// all, leaving time to sweep revenue and finish milestone releases
+    pub distribution_grace_period_seconds: i64,
+    
+    /// Seconds after distribution that backers have to claim
+    pub backer_claim_window_seconds: i64,
+    
+    /// Who receives backer funds left unclaimed after the claim deadline
+    pub unclaimed_profit_recipient: UnclaimedProfitRecipient,
+    
+    /// Platform fee kept on ticket refunds (basis points), unless the event
+    /// overrides it
+    pub refund_fee_bps: u16,
+    
+    /// Share of the total raised that must take part in a budget vote for
+    /// it to count (basis points); below it the vote expires
+    pub min_budget_turnout_bps: u16,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PlatformConfig {
+    /// Default minimum platform share (5%)
+    pub const DEFAULT_MIN_PLATFORM_SHARE_BPS: u16 = 500;
+    
+    /// Default minimum backer share (50%)
+    pub const DEFAULT_MIN_BACKER_SHARE_BPS: u16 = 5_000;
+    
+    /// Default distribution timeout (30 days)
+    pub const DEFAULT_DISTRIBUTION_TIMEOUT_SECONDS: i64 = 30 * 24 * 60 * 60;
+    
+    /// Default distribution grace period (none: distribution opens at event end)
+    pub const DEFAULT_DISTRIBUTION_GRACE_PERIOD_SECONDS: i64 = 0;
+    
+    /// Default backer claim window (90 days)
+    pub const DEFAULT_BACKER_CLAIM_WINDOW_SECONDS: i64 = 90 * 86_400;
+    
+    /// Default recipient of unclaimed backer funds (the organizer, who could
+    /// always keep them when closing the campaign)
+    pub const DEFAULT_UNCLAIMED_PROFIT_RECIPIENT: UnclaimedProfitRecipient = UnclaimedProfitRecipient::Organizer;
+    
+    /// Default refund fee (none: refunds return everything)
+    pub const DEFAULT_REFUND_FEE_BPS: u16 = 0;
+    
+    /// Default maximum relayer fee (covers two signatures)
+    pub const DEFAULT_MAX_RELAYER_FEE_LAMPORTS: u64 = 10_000;
+    
+    /// Default budget vote turnout floor (10%)
+    pub const DEFAULT_MIN_BUDGET_TURNOUT_BPS: u16 = 1_000;
+    
+    /// Default platform fee for new events (none until a config sets one)
+    pub const DEFAULT_PLATFORM_FEE_BPS: u16 = 0;
+    
+    /// Calculate space needed for PlatformConfig account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        2 +  // min_platform_share_bps
+        2 +  // min_backer_share_bps
+        8 +  // distribution_timeout_seconds
+        32 + // platform_treasury
+        2 +  // default_platform_fee_bps
+        1 +  // paused
+        8 +  // max_relayer_fee_lamports
+        8 +  // distribution_grace_period_seconds
+        8 +  // backer_claim_window_seconds
+        1 +  // unclaimed_profit_recipient (enum)
+        2 +  // refund_fee_bps
+        2 +  // min_budget_turnout_bps
+        1;   // bump
+    
+    /// Load the config from its PDA if it has been initialized
+    ///
+    /// Returns `None` when the account is still owned by the system program
+    /// (config not created yet) so callers can apply program defaults.
+    pub fn load_optional(info: &AccountInfo) -> Result<Option<PlatformConfig>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        
+        let data = info.try_borrow_data()?;
+        let config = PlatformConfig::try_deserialize(&mut &data[..])?;
+        Ok(Some(config))
+    }
+    
+    /// Fail with `ProgramPaused` if the platform is paused
+    ///
+    /// An uninitialized config is never paused.
+    pub fn require_not_paused(config: Option<&PlatformConfig>) -> Result<()> {
+        require!(
+            !config.is_some_and(|config| config.paused),
+            EventError::ProgramPaused
+        );
+        Ok(())
+    }
+    
+    /// Validate a caller-provided platform treasury against the config
+    pub fn require_platform_treasury(config: Option<&PlatformConfig>, treasury: &Pubkey) -> Result<()> {
+        require!(
+            config.is_some_and(|config| config.platform_treasury == *treasury),
+            EventError::InvalidPlatformTreasury
+        );
+        Ok(())
+    }
+    
+    /// Minimum (platform, backer) shares from an optional config
+    pub fn min_shares(config: Option<&PlatformConfig>) -> (u16, u16) {
+        match config {
+            Some(config) => (config.min_platform_share_bps, config.min_backer_share_bps),
+            None => (
+                Self::DEFAULT_MIN_PLATFORM_SHARE_BPS,
+                Self::DEFAULT_MIN_BACKER_SHARE_BPS,
+            ),
+        }
+    }
+    
+    /// Relayer fee actually paid for a sponsored refund claim
+    ///
+    /// The contributor-signed fee is clamped to the platform maximum (from
+    /// the config, or the program default) and never exceeds the refund.
+    pub fn clamp_relayer_fee(
+        config: Option<&PlatformConfig>,
+        requested_fee: u64,
+        refund_amount: u64,
+    ) -> u64 {
+        let max_fee = config
+            .map(|config| config.max_relayer_fee_lamports)
+            .unwrap_or(Self::DEFAULT_MAX_RELAYER_FEE_LAMPORTS);
+        
+        requested_fee.min(max_fee).min(refund_amount)
+    }
+    
+    /// Check whether the permissionless distribution fallback has opened
+    ///
+    /// Once `distribution_timeout_seconds` (from the config, or the program
+    /// default) have passed since the event ended, anyone may trigger
+    /// distribution so backers are never stuck behind an absent organizer.
+    pub fn distribution_timed_out(
+        config: Option<&PlatformConfig>,
+        event_end_ts: i64,
+        current_ts: i64,
+    ) -> bool {
+        let timeout = config
+            .map(|config| config.distribution_timeout_seconds)
+            .unwrap_or(Self::DEFAULT_DISTRIBUTION_TIMEOUT_SECONDS);
+        
+        current_ts > event_end_ts.saturating_add(timeout)
+    }
+    
+    /// How long backers have to claim after distribution
+    pub fn backer_claim_window(config: Option<&PlatformConfig>) -> i64 {
+        config
+            .map(|config| config.backer_claim_window_seconds)
+            .unwrap_or(Self::DEFAULT_BACKER_CLAIM_WINDOW_SECONDS)
+    }
+    
+    /// Who receives backer funds left unclaimed after the claim deadline
+    pub fn unclaimed_profit_recipient(config: Option<&PlatformConfig>) -> UnclaimedProfitRecipient {
+        config
+            .map(|config| config.unclaimed_profit_recipient)
+            .unwrap_or(Self::DEFAULT_UNCLAIMED_PROFIT_RECIPIENT)
+    }
+    
+    /// Platform refund fee from an optional config
+    pub fn refund_fee_bps(config: Option<&PlatformConfig>) -> u16 {
+        config
+            .map(|config| config.refund_fee_bps)
+            .unwrap_or(Self::DEFAULT_REFUND_FEE_BPS)
+    }
+    
+    /// Platform fee for events created without an explicit split
+    pub fn default_platform_fee_bps(config: Option<&PlatformConfig>) -> u16 {
+        config
+            .map(|config| config.default_platform_fee_bps)
+            .unwrap_or(Self::DEFAULT_PLATFORM_FEE_BPS)
+    }
+    
+    /// Budget vote turnout floor from an optional config
+    pub fn min_budget_turnout_bps(config: Option<&PlatformConfig>) -> u16 {
+        config
+            .map(|config| config.min_budget_turnout_bps)
+            .unwrap_or(Self::DEFAULT_MIN_BUDGET_TURNOUT_BPS)
+    }
+    
+    /// Split a refund into (platform fee, buyer amount)
+    ///
+    /// The fee is `refund_fee_bps` of the refund, rounded down so any dust
+    /// goes to the buyer.
+    pub fn split_refund_fee(refund_amount: u64, refund_fee_bps: u16) -> Option<(u64, u64)> {
+        let fee = (refund_amount as u128)
+            .checked_mul(refund_fee_bps as u128)?
+            .checked_div(10_000)?;
+        let fee = u64::try_from(fee).ok()?;
+        Some((fee, refund_amount.checked_sub(fee)?))
+    }
+    
+    /// Fail until the distribution grace period after the event end has passed
+    ///
+    /// With no grace period (the program default) this only requires the
+    /// event to have ended.
+    pub fn require_distribution_grace_passed(
+        config: Option<&PlatformConfig>,
+        event_end_ts: i64,
+        current_ts: i64,
+    ) -> Result<()> {
+        let grace_period = config
+            .map(|config| config.distribution_grace_period_seconds)
+            .unwrap_or(Self::DEFAULT_DISTRIBUTION_GRACE_PERIOD_SECONDS);
+        
+        require!(
+            current_ts > event_end_ts.saturating_add(grace_period),
+            EventError::DistributionGracePeriodActive
+        );
+        Ok(())
+    }
+    
+    /// Fail once milestone releases are closed for an ended event
+    ///
+    /// Releases close when the distribution timeout passes, so late expenses
+    /// can't race a permissionless distribution snapshot.
+    pub fn require_milestone_release_open(
+        config: Option<&PlatformConfig>,
+        event_end_ts: i64,
+        current_ts: i64,
+    ) -> Result<()> {
+        require!(
+            !Self::distribution_timed_out(config, event_end_ts, current_ts),
+            EventError::MilestoneReleaseWindowClosed
+        );
+        Ok(())
+    }
+}
+
+/// Recipient of backer funds swept after the claim deadline
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnclaimedProfitRecipient {
+    /// The campaign organizer
+    Organizer,
+    
+    /// The platform treasury
+    PlatformTreasury,
+}
+
+/// Validate a backer/organizer/platform profit split against platform policy
+///
+/// Shares must sum to exactly 10_000 basis points and respect the minimum
+/// platform and backer shares (from the config, or program defaults).
+pub fn validate_profit_split(
+    config: Option<&PlatformConfig>,
+    backer_share_bps: u16,
+    organizer_share_bps: u16,
+    platform_share_bps: u16,
+) -> Result<()> {
+    let total = backer_share_bps as u32 + organizer_share_bps as u32 + platform_share_bps as u32;
+    require!(
+        total == 10_000,
+        EventError::InvalidProfitSplit
+    );
+    
+    let (min_platform_share_bps, min_backer_share_bps) = PlatformConfig::min_shares(config);
+    
+    require!(
+        platform_share_bps >= min_platform_share_bps,
+        EventError::PlatformShareTooLow
+    );
+    
+    require!(
+        backer_share_bps >= min_backer_share_bps,
+        EventError::BackerShareTooLow
+    );
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn config(min_platform_share_bps: u16, min_backer_share_bps: u16) -> PlatformConfig {
+        PlatformConfig {
+            admin: Pubkey::default(),
+            min_platform_share_bps,
+            min_backer_share_bps,
+            distribution_timeout_seconds: PlatformConfig::DEFAULT_DISTRIBUTION_TIMEOUT_SECONDS,
+            platform_treasury: Pubkey::default(),
+            default_platform_fee_bps: 500,
+            paused: false,
+            max_relayer_fee_lamports: PlatformConfig::DEFAULT_MAX_RELAYER_FEE_LAMPORTS,
+            distribution_grace_period_seconds: PlatformConfig::DEFAULT_DISTRIBUTION_GRACE_PERIOD_SECONDS,
+            backer_claim_window_seconds: PlatformConfig::DEFAULT_BACKER_CLAIM_WINDOW_SECONDS,
+            unclaimed_profit_recipient: PlatformConfig::DEFAULT_UNCLAIMED_PROFIT_RECIPIENT,
+            refund_fee_bps: PlatformConfig::DEFAULT_REFUND_FEE_BPS,
+            min_budget_turnout_bps: PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_split_must_sum_to_100_percent() {
+        let config = config(500, 5000);
+        
+        assert!(validate_profit_split(Some(&config), 6000, 3500, 500).is_ok());
+        assert_eq!(
+            validate_profit_split(Some(&config), 6000, 3500, 600).unwrap_err(),
+            EventError::InvalidProfitSplit.into()
+        );
+        assert_eq!(
+            validate_profit_split(Some(&config), 6000, 3000, 500).unwrap_err(),
+            EventError::InvalidProfitSplit.into()
+        );
+    }
+    
+    #[test]
+    fn test_minimum_shares_enforced() {
+        let config = config(500, 5000);
+        
+        // Organizer tries to keep everything
+        assert_eq!(
+            validate_profit_split(Some(&config), 0, 10_000, 0).unwrap_err(),
+            EventError::PlatformShareTooLow.into()
+        );
+        
+        // Platform minimum met but backers get less than 50%
+        assert_eq!(
+            validate_profit_split(Some(&config), 4999, 4501, 500).unwrap_err(),
+            EventError::BackerShareTooLow.into()
+        );
+        
+        // Exactly at both minimums
+        assert!(validate_profit_split(Some(&config), 5000, 4500, 500).is_ok());
+    }
+    
+    #[test]
+    fn test_defaults_without_config() {
+        assert_eq!(
+            PlatformConfig::min_shares(None),
+            (
+                PlatformConfig::DEFAULT_MIN_PLATFORM_SHARE_BPS,
+                PlatformConfig::DEFAULT_MIN_BACKER_SHARE_BPS
+            )
+        );
+        
+        // Standard 60/35/5 terms are valid under the defaults
+        assert!(validate_profit_split(None, 6000, 3500, 500).is_ok());
+        assert_eq!(
+            validate_profit_split(None, 100, 9400, 500).unwrap_err(),
+            EventError::BackerShareTooLow.into()
+        );
+    }
+    
+    #[test]
+    fn test_custom_config_minimums() {
+        // A config can relax the defaults
+        let config = config(200, 3000);
+        
+        assert!(validate_profit_split(Some(&config), 3000, 6800, 200).is_ok());
+        assert_eq!(
+            validate_profit_split(Some(&config), 3000, 6900, 100).unwrap_err(),
+            EventError::PlatformShareTooLow.into()
+        );
+    }
+    
+    #[test]
+    fn test_distribution_timeout() {
+        let end_ts = 1_000_000;
+        let timeout = PlatformConfig::DEFAULT_DISTRIBUTION_TIMEOUT_SECONDS;
+        
+        // Defaults apply without a config
+        assert!(!PlatformConfig::distribution_timed_out(None, end_ts, end_ts + timeout));
+        assert!(PlatformConfig::distribution_timed_out(None, end_ts, end_ts + timeout + 1));
+        
+        // A config can shorten the timeout
+        let mut config = config(500, 5000);
+        config.distribution_timeout_seconds = 60;
+        assert!(!PlatformConfig::distribution_timed_out(Some(&config), end_ts, end_ts + 60));
+        assert!(PlatformConfig::distribution_timed_out(Some(&config), end_ts, end_ts + 61));
+        
+        // Huge timeouts don't overflow
+        config.distribution_timeout_seconds = i64::MAX;
+        assert!(!PlatformConfig::distribution_timed_out(Some(&config), end_ts, i64::MAX));
+    }
+    
+    #[test]
+    fn test_distribution_grace_period() {
+        let end_ts = 1_000_000;
+        
+        // Without a grace period distribution opens right after the event ends
+        assert_eq!(
+            PlatformConfig::require_distribution_grace_passed(None, end_ts, end_ts).unwrap_err(),
+            EventError::DistributionGracePeriodActive.into()
+        );
+        PlatformConfig::require_distribution_grace_passed(None, end_ts, end_ts + 1).unwrap();
+        
+        // A configured grace period holds off every caller until it passes
+        let mut config = config(500, 5000);
+        config.distribution_grace_period_seconds = 3_600;
+        assert!(PlatformConfig::require_distribution_grace_passed(Some(&config), end_ts, end_ts + 1).is_err());
+        assert!(PlatformConfig::require_distribution_grace_passed(Some(&config), end_ts, end_ts + 3_600).is_err());
+        PlatformConfig::require_distribution_grace_passed(Some(&config), end_ts, end_ts + 3_601).unwrap();
+    }
+    
+    #[test]
+    fn test_milestone_release_cutoff() {
+        let end_ts = 1_000_000;
+        let timeout = PlatformConfig::DEFAULT_DISTRIBUTION_TIMEOUT_SECONDS;
+        
+        // Releases stay open through the event and the default 30 days after
+        PlatformConfig::require_milestone_release_open(None, end_ts, end_ts - 1).unwrap();
+        PlatformConfig::require_milestone_release_open(None, end_ts, end_ts + timeout).unwrap();
+        assert_eq!(
+            PlatformConfig::require_milestone_release_open(None, end_ts, end_ts + timeout + 1).unwrap_err(),
+            EventError::MilestoneReleaseWindowClosed.into()
+        );
+        
+        // The cutoff follows the configured distribution timeout
+        let mut config = config(500, 5000);
+        config.distribution_timeout_seconds = 60;
+        PlatformConfig::require_milestone_release_open(Some(&config), end_ts, end_ts + 60).unwrap();
+        assert!(PlatformConfig::require_milestone_release_open(Some(&config), end_ts, end_ts + 61).is_err());
+    }
+    
+    #[test]
+    fn test_pause_switch() {
+        let mut config = config(500, 5000);
+        
+        assert!(PlatformConfig::require_not_paused(None).is_ok());
+        assert!(PlatformConfig::require_not_paused(Some(&config)).is_ok());
+        
+        config.paused = true;
+        assert_eq!(
+            PlatformConfig::require_not_paused(Some(&config)).unwrap_err(),
+            EventError::ProgramPaused.into()
+        );
+    }
+    
+    #[test]
+    fn test_platform_treasury_validation() {
+        let mut config = config(500, 5000);
+        let treasury = Pubkey::new_unique();
+        config.platform_treasury = treasury;
+        
+        assert!(PlatformConfig::require_platform_treasury(Some(&config), &treasury).is_ok());
+        assert_eq!(
+            PlatformConfig::require_platform_treasury(Some(&config), &Pubkey::new_unique()).unwrap_err(),
+            EventError::InvalidPlatformTreasury.into()
+        );
+        
+        // Without a config there is no trusted treasury
+        assert_eq!(
+            PlatformConfig::require_platform_treasury(None, &treasury).unwrap_err(),
+            EventError::InvalidPlatformTreasury.into()
+        );
+    }
+    
+    #[test]
+    fn test_relayer_fee_clamping() {
+        let default_max = PlatformConfig::DEFAULT_MAX_RELAYER_FEE_LAMPORTS;
+        
+        // Requested fee under the cap is paid as signed
+        assert_eq!(PlatformConfig::clamp_relayer_fee(None, 5_000, 200_000_000), 5_000);
+        
+        // Over the default cap is clamped to it
+        assert_eq!(PlatformConfig::clamp_relayer_fee(None, 1_000_000, 200_000_000), default_max);
+        
+        // Config cap overrides the default
+        let mut config = config(500, 5000);
+        config.max_relayer_fee_lamports = 2_000;
+        assert_eq!(PlatformConfig::clamp_relayer_fee(Some(&config), 5_000, 200_000_000), 2_000);
+        
+        // Never more than the refund itself
+        assert_eq!(PlatformConfig::clamp_relayer_fee(None, 5_000, 3_000), 3_000);
+        
+        // A zero cap disables relayer fees
+        config.max_relayer_fee_lamports = 0;
+        assert_eq!(PlatformConfig::clamp_relayer_fee(Some(&config), 5_000, 200_000_000), 0);
+    }
+    
+    #[test]
+    fn test_min_budget_turnout_bps() {
+        assert_eq!(PlatformConfig::min_budget_turnout_bps(None), 1_000);
+        let mut custom = config(500, 5000);
+        custom.min_budget_turnout_bps = 2_500;
+        assert_eq!(PlatformConfig::min_budget_turnout_bps(Some(&custom)), 2_500);
+    }
+    
+    #[test]
+    fn test_refund_fee_split() {
+        assert_eq!(PlatformConfig::refund_fee_bps(None), 0);
+        let mut custom = config(500, 5000);
+        custom.refund_fee_bps = 250;
+        assert_eq!(PlatformConfig::refund_fee_bps(Some(&custom)), 250);
+        
+        assert_eq!(PlatformConfig::split_refund_fee(1_000_000_000, 250), Some((25_000_000, 975_000_000)));
+        assert_eq!(PlatformConfig::split_refund_fee(1_000_000_000, 0), Some((0, 1_000_000_000)));
+        assert_eq!(PlatformConfig::split_refund_fee(1_000_000_000, 10_000), Some((1_000_000_000, 0)));
+        
+        // Rounds down in the buyer's favour
+        assert_eq!(PlatformConfig::split_refund_fee(399, 250), Some((9, 390)));
+        assert_eq!(PlatformConfig::split_refund_fee(39, 250), Some((0, 39)));
+        assert_eq!(PlatformConfig::split_refund_fee(u64::MAX, 10_000), Some((u64::MAX, 0)));
+    }
+}
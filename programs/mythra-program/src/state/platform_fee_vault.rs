@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+
+/// Per-event holding account for the platform's share of withdrawals
+///
+/// PDA: ["platform_fees", event]. Fee slices accrue here instead of going
+/// straight to the platform treasury, so the fee on a ticket refunded after
+/// its revenue was withdrawn can be clawed back. The platform admin settles
+/// the accrued balance to the treasury once the event's refund window has
+/// closed. The account holds the fee lamports itself (on top of its rent).
+#[account]
+pub struct PlatformFeeVault {
+    /// Event the fees were taken from
+    pub event: Pubkey,
+    
+    /// Fees held and not yet settled to the platform treasury
+    pub accrued: u64,
+    
+    /// Cumulative fees returned to the event escrow for refunds
+    pub clawed_back: u64,
+    
+    /// Cumulative fees settled to the platform treasury
+    pub settled: u64,
+    
+    /// Last settlement time (UNSET_TIMESTAMP if never settled)
+    pub last_settled_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PlatformFeeVault {
+    /// Calculate space needed for PlatformFeeVault account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // event
+        8 +  // accrued
+        8 +  // clawed_back
+        8 +  // settled
+        8 +  // last_settled_at
+        1;   // bump
+    
+    /// Hold a fee slice taken from a withdrawal
+    pub fn accrue(&mut self, fee: u64) -> Result<()> {
+        self.accrued = self.accrued
+            .checked_add(fee)
+            .ok_or(EventError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Give back up to `fee` for a refund, returning the amount released
+    ///
+    /// Only unsettled fees can be clawed back; anything already paid to the
+    /// platform treasury stays there.
+    pub fn claw_back(&mut self, fee: u64) -> u64 {
+        let amount = fee.min(self.accrued);
+        self.accrued -= amount;
+        self.clawed_back = self.clawed_back.saturating_add(amount);
+        amount
+    }
+    
+    /// Settle everything accrued, returning the amount to pay out
+    pub fn settle(&mut self, current_ts: i64) -> Result<u64> {
+        require!(self.accrued > 0, EventError::NoPlatformFeesToSettle);
+        
+        let amount = self.accrued;
+        self.accrued = 0;
+        self.settled = self.settled.saturating_add(amount);
+        self.last_settled_at = current_ts;
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::UNSET_TIMESTAMP;
+    
+    fn vault() -> PlatformFeeVault {
+        PlatformFeeVault {
+            event: Pubkey::default(),
+            accrued: 0,
+            clawed_back: 0,
+            settled: 0,
+            last_settled_at: UNSET_TIMESTAMP,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_platform_fee_vault_space() {
+        assert_eq!(8 + vault().try_to_vec().unwrap().len(), PlatformFeeVault::LEN);
+    }
+    
+    #[test]
+    fn test_clawback_capped_at_accrued() {
+        let mut vault = vault();
+        vault.accrue(25_000).unwrap();
+        
+        assert_eq!(vault.claw_back(10_000), 10_000);
+        assert_eq!(vault.accrued, 15_000);
+        
+        // Only what is still held comes back
+        assert_eq!(vault.claw_back(20_000), 15_000);
+        assert_eq!(vault.accrued, 0);
+        assert_eq!(vault.clawed_back, 25_000);
+        assert_eq!(vault.claw_back(1), 0);
+    }
+    
+    #[test]
+    fn test_no_clawback_after_settlement() {
+        let mut vault = vault();
+        vault.accrue(25_000).unwrap();
+        
+        assert_eq!(vault.settle(1_000).unwrap(), 25_000);
+        assert_eq!(vault.settled, 25_000);
+        assert_eq!(vault.last_settled_at, 1_000);
+        
+        // Settled fees are out of reach for refunds
+        assert_eq!(vault.claw_back(10_000), 0);
+        assert_eq!(vault.clawed_back, 0);
+    }
+    
+    #[test]
+    fn test_settle_requires_accrued_fees() {
+        let mut vault = vault();
+        assert_eq!(vault.settle(1_000).unwrap_err(), EventError::NoPlatformFeesToSettle.into());
+        
+        // Fully clawed back: nothing left to settle either
+        vault.accrue(5_000).unwrap();
+        vault.claw_back(5_000);
+        assert_eq!(vault.settle(1_000).unwrap_err(), EventError::NoPlatformFeesToSettle.into());
+    }
+    
+    #[test]
+    fn test_accrue_overflow() {
+        let mut vault = vault();
+        vault.accrue(u64::MAX).unwrap();
+        assert_eq!(vault.accrue(1).unwrap_err(), EventError::ArithmeticOverflow.into());
+    }
+}
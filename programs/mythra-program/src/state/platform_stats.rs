@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+
+/// Platform-wide KPIs (singleton PDA: ["platform_stats"])
+///
+/// Updated incrementally by the instructions that create events, sell
+/// tickets, fund campaigns and distribute profit, so the platform can read
+/// aggregates on-chain without an indexer. The account is optional on
+/// those instructions and every counter saturates, so stats never block a
+/// user action.
+#[account]
+pub struct PlatformStats {
+    /// Events created
+    pub events_created: u64,
+    
+    /// Tickets sold or registered
+    pub tickets_sold: u64,
+    
+    /// Lamports paid for tickets on-chain
+    pub ticket_volume_lamports: u64,
+    
+    /// Campaigns that reached their funding goal
+    pub campaigns_funded: u64,
+    
+    /// Lamports raised by funded campaigns
+    pub campaign_volume_lamports: u64,
+    
+    /// Campaigns whose profit distribution was calculated
+    pub distributions_calculated: u64,
+    
+    /// Ticket revenue swept into campaign escrows at distribution
+    pub distributed_revenue_lamports: u64,
+    
+    /// Last time any counter changed
+    pub last_updated: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PlatformStats {
+    /// Calculate space needed for PlatformStats account
+    pub const LEN: usize = 8 + // discriminator
+        8 +  // events_created
+        8 +  // tickets_sold
+        8 +  // ticket_volume_lamports
+        8 +  // campaigns_funded
+        8 +  // campaign_volume_lamports
+        8 +  // distributions_calculated
+        8 +  // distributed_revenue_lamports
+        8 +  // last_updated
+        1;   // bump
+    
+    /// Record a new event
+    pub fn record_event_created(&mut self, current_ts: i64) {
+        self.events_created = self.events_created.saturating_add(1);
+        self.last_updated = current_ts;
+    }
+    
+    /// Record `count` tickets sold for `volume` lamports in total
+    pub fn record_ticket_sales(&mut self, count: u64, volume: u64, current_ts: i64) {
+        self.tickets_sold = self.tickets_sold.saturating_add(count);
+        self.ticket_volume_lamports = self.ticket_volume_lamports.saturating_add(volume);
+        self.last_updated = current_ts;
+    }
+    
+    /// Record a campaign that finalized as funded with `total_raised`
+    pub fn record_campaign_funded(&mut self, total_raised: u64, current_ts: i64) {
+        self.campaigns_funded = self.campaigns_funded.saturating_add(1);
+        self.campaign_volume_lamports = self.campaign_volume_lamports.saturating_add(total_raised);
+        self.last_updated = current_ts;
+    }
+    
+    /// Record a calculated distribution over `revenue` swept lamports
+    pub fn record_distribution(&mut self, revenue: u64, current_ts: i64) {
+        self.distributions_calculated = self.distributions_calculated.saturating_add(1);
+        self.distributed_revenue_lamports = self.distributed_revenue_lamports.saturating_add(revenue);
+        self.last_updated = current_ts;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn empty_stats() -> PlatformStats {
+        PlatformStats {
+            events_created: 0,
+            tickets_sold: 0,
+            ticket_volume_lamports: 0,
+            campaigns_funded: 0,
+            campaign_volume_lamports: 0,
+            distributions_calculated: 0,
+            distributed_revenue_lamports: 0,
+            last_updated: 0,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_platform_stats_space() {
+        assert_eq!(8 + empty_stats().try_to_vec().unwrap().len(), PlatformStats::LEN);
+    }
+    
+    #[test]
+    fn test_lifecycle_aggregates() {
+        let mut stats = empty_stats();
+        
+        // Two events, one crowdfunded
+        stats.record_event_created(100);
+        stats.record_event_created(110);
+        
+        // Campaign raises 50 SOL and is funded
+        stats.record_campaign_funded(50_000_000_000, 200);
+        
+        // Single purchase at 1 SOL, a batch of 3 at 0.5 SOL, and an
+        // off-platform mint registered without payment
+        stats.record_ticket_sales(1, 1_000_000_000, 300);
+        stats.record_ticket_sales(3, 1_500_000_000, 310);
+        stats.record_ticket_sales(1, 0, 320);
+        
+        // Distribution sweeps the ticket revenue
+        stats.record_distribution(2_500_000_000, 400);
+        
+        assert_eq!(stats.events_created, 2);
+        assert_eq!(stats.tickets_sold, 5);
+        assert_eq!(stats.ticket_volume_lamports, 2_500_000_000);
+        assert_eq!(stats.campaigns_funded, 1);
+        assert_eq!(stats.campaign_volume_lamports, 50_000_000_000);
+        assert_eq!(stats.distributions_calculated, 1);
+        assert_eq!(stats.distributed_revenue_lamports, 2_500_000_000);
+        assert_eq!(stats.last_updated, 400);
+    }
+    
+    #[test]
+    fn test_counters_saturate() {
+        let mut stats = empty_stats();
+        stats.ticket_volume_lamports = u64::MAX - 1;
+        stats.tickets_sold = u64::MAX;
+        
+        stats.record_ticket_sales(2, 10, 1);
+        
+        assert_eq!(stats.tickets_sold, u64::MAX);
+        assert_eq!(stats.ticket_volume_lamports, u64::MAX);
+    }
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+/// Fixed-point scale for refund factors (1_000_000_000 = 100%)
+pub const REFUND_FACTOR_SCALE: u64 = 1_000_000_000;
+
+/// Refund claim registered by a ticket holder for a canceled event
+///
+/// Only used when the event was canceled in shortfall mode (the ticket
+/// escrow could not cover every refund). Claims record each ticket's
+/// entitlement during the registration window; once the window closes
+/// every claim is paid out at the same pro-rata factor.
+#[account]
+pub struct RefundClaim {
+    /// The canceled event
+    pub event: Pubkey,
+    
+    /// The ticket this claim was registered for
+    pub ticket: Pubkey,
+    
+    /// Wallet that registered the claim and receives the payout
+    pub claimant: Pubkey,
+    
+    /// Full refund entitlement in lamports (price paid for the ticket)
+    pub amount: u64,
+    
+    /// When the claim was registered
+    pub registered_at: i64,
+    
+    /// Whether the payout has been made
+    pub paid: bool,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl RefundClaim {
+    /// Calculate space needed for RefundClaim account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // event
+        32 + // ticket
+        32 + // claimant
+        8 +  // amount
+        8 +  // registered_at
+        1 +  // paid
+        1;   // bump
+    
+    /// Amount paid out for this claim at the given refund factor
+    pub fn payout(&self, refund_factor: u64) -> u64 {
+        apply_refund_factor(self.amount, refund_factor)
+    }
+}
+
+/// Compute the pro-rata refund factor for a shortfall settlement
+///
+/// # Arguments
+/// * `available` - Lamports available in escrow for refunds
+/// * `total_claims` - Sum of all registered refund entitlements
+///
+/// # Returns
+/// `available / total_claims` scaled by `REFUND_FACTOR_SCALE`, capped at 100%
+pub fn compute_refund_factor(available: u64, total_claims: u64) -> u64 {
+    if total_claims == 0 || available >= total_claims {
+        return REFUND_FACTOR_SCALE;
+    }
+    
+    // Use u128 to prevent overflow during calculation
+    let factor = (available as u128 * REFUND_FACTOR_SCALE as u128) / total_claims as u128;
+    
+    // Safe to cast back to u64 since factor < REFUND_FACTOR_SCALE
+    factor as u64
+}
+
+/// Apply a refund factor to an entitlement (rounds down)
+pub fn apply_refund_factor(amount: u64, refund_factor: u64) -> u64 {
+    let payout = (amount as u128 * refund_factor as u128) / REFUND_FACTOR_SCALE as u128;
+    
+    // Safe to cast back to u64 since refund_factor <= REFUND_FACTOR_SCALE
+    payout as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_full_coverage_factor() {
+        // Enough funds (or no claims) means everyone is paid in full
+        assert_eq!(compute_refund_factor(10_000_000_000, 10_000_000_000), REFUND_FACTOR_SCALE);
+        assert_eq!(compute_refund_factor(20_000_000_000, 10_000_000_000), REFUND_FACTOR_SCALE);
+        assert_eq!(compute_refund_factor(0, 0), REFUND_FACTOR_SCALE);
+    }
+    
+    #[test]
+    fn test_partial_coverage_factor() {
+        // 6 SOL available for 10 SOL of claims → 60% haircut factor
+        let factor = compute_refund_factor(6_000_000_000, 10_000_000_000);
+        assert_eq!(factor, 600_000_000);
+        assert_eq!(apply_refund_factor(1_000_000_000, factor), 600_000_000);
+        
+        // Empty escrow pays nothing
+        assert_eq!(compute_refund_factor(0, 10_000_000_000), 0);
+    }
+    
+    #[test]
+    fn test_payouts_never_exceed_available() {
+        // Odd amounts that don't divide evenly
+        let claims: [u64; 3] = [333_333_333, 1_000_000_001, 7];
+        let total: u64 = claims.iter().sum();
+        let available = 999_999_999;
+        
+        let factor = compute_refund_factor(available, total);
+        let paid: u64 = claims.iter().map(|c| apply_refund_factor(*c, factor)).sum();
+        
+        assert!(paid <= available);
+    }
+    
+    #[test]
+    fn test_claim_payout() {
+        let claim = RefundClaim {
+            event: Pubkey::default(),
+            ticket: Pubkey::default(),
+            claimant: Pubkey::default(),
+            amount: 500_000_000, // 0.5 SOL ticket
+            registered_at: 0,
+            paid: false,
+            bump: 0,
+        };
+        
+        assert_eq!(claim.payout(REFUND_FACTOR_SCALE), 500_000_000);
+        assert_eq!(claim.payout(REFUND_FACTOR_SCALE / 4), 125_000_000);
+    }
+}
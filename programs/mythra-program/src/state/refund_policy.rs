@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use crate::errors::EventError;
+
+/// One step of a refund schedule
+///
+/// Refunds requested at least `seconds_before_start` before the event
+/// starts return `refund_bps` of the price paid.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RefundStep {
+    pub seconds_before_start: i64, // 8 bytes
+    pub refund_bps: u16,           // 2 bytes
+}
+
+/// Time-based refund schedule for buyer-requested refunds
+///
+/// Steps are ordered from furthest out to closest to the start; both the
+/// cutoffs and the percentages only go down. "100% until 30 days out, 50%
+/// until 7 days, nothing after" is `[(30 days, 10000), (7 days, 5000)]`.
+/// An empty schedule means buyers can't refund themselves.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq, Debug)]
+pub struct RefundPolicy {
+    pub steps: Vec<RefundStep>, // 4 + 10 * MAX_REFUND_STEPS bytes
+}
+
+impl RefundPolicy {
+    /// Maximum steps in a schedule
+    pub const MAX_REFUND_STEPS: usize = 4;
+    
+    /// Space reserved in the Event account (always at full capacity)
+    pub const SPACE: usize = 4 + (8 + 2) * Self::MAX_REFUND_STEPS; // 44 bytes
+    
+    /// Validate a schedule
+    ///
+    /// At most `MAX_REFUND_STEPS` steps with non-negative cutoffs that
+    /// strictly decrease, and percentages that never exceed 100% and never
+    /// increase closer to the start.
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.steps.len() <= Self::MAX_REFUND_STEPS,
+            EventError::InvalidRefundPolicy
+        );
+        for (i, step) in self.steps.iter().enumerate() {
+            require!(
+                step.seconds_before_start >= 0 && step.refund_bps <= 10000,
+                EventError::InvalidRefundPolicy
+            );
+            if let Some(previous) = i.checked_sub(1).map(|j| &self.steps[j]) {
+                require!(
+                    step.seconds_before_start < previous.seconds_before_start
+                        && step.refund_bps <= previous.refund_bps,
+                    EventError::InvalidRefundPolicy
+                );
+            }
+        }
+        Ok(())
+    }
+    
+    /// Refund percentage (bps) for a request at `current_ts`
+    ///
+    /// The first step whose cutoff hasn't passed applies; once every cutoff
+    /// has passed (or with no schedule) nothing is refunded.
+    pub fn refund_bps_at(&self, start_ts: i64, current_ts: i64) -> u16 {
+        let seconds_before_start = start_ts.saturating_sub(current_ts);
+        self.steps
+            .iter()
+            .find(|step| seconds_before_start >= step.seconds_before_start)
+            .map(|step| step.refund_bps)
+            .unwrap_or(0)
+    }
+    
+    /// Refund for a ticket bought for `price_paid`, in lamports
+    pub fn refund_amount(price_paid: u64, refund_bps: u16) -> Result<u64> {
+        let amount = (price_paid as u128)
+            .checked_mul(refund_bps as u128)
+            .ok_or(EventError::ArithmeticOverflow)?
+            / 10000;
+        u64::try_from(amount).map_err(|_| EventError::ArithmeticOverflow.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const DAY: i64 = 24 * 60 * 60;
+    
+    fn policy(steps: &[(i64, u16)]) -> RefundPolicy {
+        RefundPolicy {
+            steps: steps
+                .iter()
+                .map(|&(seconds_before_start, refund_bps)| RefundStep { seconds_before_start, refund_bps })
+                .collect(),
+        }
+    }
+    
+    #[test]
+    fn test_refund_policy_space() {
+        let full = policy(&[(4 * DAY, 10000), (3 * DAY, 7500), (2 * DAY, 5000), (DAY, 2500)]);
+        assert_eq!(full.try_to_vec().unwrap().len(), RefundPolicy::SPACE);
+    }
+    
+    #[test]
+    fn test_validate_refund_policy() {
+        policy(&[]).validate().unwrap();
+        policy(&[(30 * DAY, 10000), (7 * DAY, 5000)]).validate().unwrap();
+        policy(&[(7 * DAY, 5000), (0, 5000)]).validate().unwrap();
+        
+        let invalid: Error = EventError::InvalidRefundPolicy.into();
+        
+        // Too many steps
+        let five = policy(&[(5 * DAY, 10000), (4 * DAY, 8000), (3 * DAY, 6000), (2 * DAY, 4000), (DAY, 2000)]);
+        assert_eq!(five.validate().unwrap_err(), invalid);
+        
+        // Over 100%, or a cutoff after the start
+        assert_eq!(policy(&[(DAY, 10001)]).validate().unwrap_err(), invalid);
+        assert_eq!(policy(&[(-1, 5000)]).validate().unwrap_err(), invalid);
+        
+        // Cutoffs out of order or repeated
+        assert_eq!(policy(&[(7 * DAY, 5000), (30 * DAY, 5000)]).validate().unwrap_err(), invalid);
+        assert_eq!(policy(&[(7 * DAY, 10000), (7 * DAY, 5000)]).validate().unwrap_err(), invalid);
+        
+        // Refunds growing closer to the start
+        assert_eq!(policy(&[(30 * DAY, 5000), (7 * DAY, 10000)]).validate().unwrap_err(), invalid);
+    }
+    
+    #[test]
+    fn test_refund_bps_at_boundaries() {
+        let schedule = policy(&[(30 * DAY, 10000), (7 * DAY, 5000)]);
+        let start = 1_000 * DAY;
+        
+        assert_eq!(schedule.refund_bps_at(start, start - 60 * DAY), 10000);
+        assert_eq!(schedule.refund_bps_at(start, start - 30 * DAY), 10000);
+        assert_eq!(schedule.refund_bps_at(start, start - 30 * DAY + 1), 5000);
+        assert_eq!(schedule.refund_bps_at(start, start - 7 * DAY), 5000);
+        assert_eq!(schedule.refund_bps_at(start, start - 7 * DAY + 1), 0);
+        assert_eq!(schedule.refund_bps_at(start, start + DAY), 0);
+        
+        assert_eq!(policy(&[]).refund_bps_at(start, start - 60 * DAY), 0);
+    }
+    
+    #[test]
+    fn test_refund_amount_rounds_down() {
+        assert_eq!(RefundPolicy::refund_amount(1_000_000_000, 10000).unwrap(), 1_000_000_000);
+        assert_eq!(RefundPolicy::refund_amount(1_000_000_000, 5000).unwrap(), 500_000_000);
+        assert_eq!(RefundPolicy::refund_amount(3, 5000).unwrap(), 1);
+        assert_eq!(RefundPolicy::refund_amount(u64::MAX, 10000).unwrap(), u64::MAX);
+        assert_eq!(RefundPolicy::refund_amount(1_000, 0).unwrap(), 0);
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+/// Seat held by a ticket on a seated tier
+///
+/// PDA: ["seat", tier, seat_number (u32 LE)]. One account per seat, so a
+/// seat can't be given to two tickets. Created when `purchase_ticket`
+/// auto-assigns a seat or the organizer assigns one with `assign_seat`,
+/// and closed when the organizer moves the ticket to another seat.
+#[account]
+pub struct SeatAssignment {
+    pub tier: Pubkey,           // 32 bytes - seated tier
+    pub seat_number: u32,       // 4 bytes - seat within the tier (from 1)
+    pub ticket: Pubkey,         // 32 bytes - ticket holding the seat
+    pub bump: u8,               // 1 byte
+}
+
+impl SeatAssignment {
+    pub const SPACE: usize = 8 + 32 + 4 + 32 + 1; // 77 bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_seat_assignment_space() {
+        let seat = SeatAssignment {
+            tier: Pubkey::default(),
+            seat_number: 0,
+            ticket: Pubkey::default(),
+            bump: 0,
+        };
+        assert_eq!(8 + seat.try_to_vec().unwrap().len(), SeatAssignment::SPACE);
+    }
+}
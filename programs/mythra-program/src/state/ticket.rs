@@ -1,5 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::state::{OptionalTimestamp, UNSET_TIMESTAMP};
 
+/// Ticket record for a single NFT ticket
+///
+/// Migration note: `price_paid` and `purchased_at` were added after the
+/// initial deployment, then `use_count`, then `status`, then `promo_applied`,
+/// then `last_sale_price` and `last_sale_royalty`, then `seat_number`, then
+/// `gift_count`, then `checkin_delegate`, then `buyer`. Tickets created
+/// earlier are `SPACE - 105` (or `SPACE - 89`, `SPACE - 87`, `SPACE - 86`,
+/// `SPACE - 85`, `SPACE - 69`, `SPACE - 65`, `SPACE - 64`, `SPACE - 32`)
+/// bytes and will not deserialize until they are reallocated and backfilled.
 #[account]
 pub struct Ticket {
     pub owner: Pubkey,          // 32 bytes - ticket owner
@@ -8,12 +19,436 @@ pub struct Ticket {
     pub mint: Pubkey,           // 32 bytes - NFT mint
     pub used: bool,             // 1 byte - redemption status
     pub refunded: bool,         // 1 byte - refund status
-    pub checked_in_ts: i64,     // 8 bytes - check-in timestamp (0 if not checked in)
+    pub checked_in_ts: i64,     // 8 bytes - check-in timestamp (UNSET_TIMESTAMP if not checked in)
     pub gate_operator: Pubkey,  // 32 bytes - scanner/operator who checked in ticket
-    pub refund_ts: i64,         // 8 bytes - refund timestamp (0 if not refunded)
+    pub refund_ts: i64,         // 8 bytes - refund timestamp (UNSET_TIMESTAMP if not refunded)
+    pub snapshotted: bool,      // 1 byte - owner appended to the attendance list
+    pub price_paid: u64,        // 8 bytes - lamports paid at purchase (0 if minted off-platform)
+    pub purchased_at: i64,      // 8 bytes - purchase/registration timestamp
+    pub use_count: u16,         // 2 bytes - check-ins so far (used once it reaches the tier's max_uses)
+    pub status: TicketStatus,   // 1 byte - Inactive while pre-registered box-office stock
+    pub promo_applied: bool,    // 1 byte - bought at the tier's promo price
+    pub last_sale_price: u64,   // 8 bytes - reported price of the latest resale (0 if never resold or gifted)
+    pub last_sale_royalty: u64, // 8 bytes - royalty paid on the latest resale
+    pub seat_number: u32,       // 4 bytes - assigned seat on a seated tier (0 = general admission)
+    pub gift_count: u8,         // 1 byte - times given away with gift_ticket
+    pub checkin_delegate: Pubkey, // 32 bytes - companion wallet allowed to check in (default = none)
+    pub buyer: Pubkey,          // 32 bytes - wallet that bought it; keys its BuyerRecord (default = not sold)
     pub bump: u8,               // 1 byte
 }
 
+/// Whether a ticket has been sold
+///
+/// Use and refund are still tracked by `used`/`refunded`; this only
+/// separates pre-registered stock from tickets that have an owner.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum TicketStatus {
+    /// Sold or registered to its owner
+    Active,
+    
+    /// Pre-registered (e.g. printed box-office stock), not yet sold; doesn't
+    /// count against tier supply until activated
+    Inactive,
+}
+
 impl Ticket {
-    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 1 + 1 + 8 + 32 + 8 + 1; // 187 bytes
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 1 + 1 + 8 + 32 + 8 + 1 + 8 + 8 + 2 + 1 + 1 + 8 + 8 + 4 + 1 + 32 + 32 + 1; // 293 bytes
+    
+    /// Resolve the amount to refund for this ticket
+    ///
+    /// Defaults to the stored purchase price; an explicit amount may be
+    /// lower (partial refund) but never more than what was paid.
+    pub fn resolve_refund_amount(&self, requested: Option<u64>) -> Result<u64> {
+        match requested {
+            Some(amount) => {
+                require!(
+                    amount <= self.price_paid,
+                    EventError::RefundExceedsPricePaid
+                );
+                Ok(amount)
+            }
+            None => Ok(self.price_paid),
+        }
+    }
+    
+    /// Gifts allowed per ticket, so gifting can't stand in for royalty-free
+    /// resale
+    pub const MAX_GIFTS: u8 = 1;
+    
+    /// Count a gift, failing once the ticket has used its allowance
+    pub fn record_gift(&mut self) -> Result<()> {
+        require!(
+            self.gift_count < Self::MAX_GIFTS,
+            EventError::GiftLimitReached
+        );
+        self.gift_count += 1;
+        Ok(())
+    }
+    
+    /// Check whether `signer` may present the ticket at check-in
+    ///
+    /// The owner always can; a `checkin_delegate` set by the owner can too,
+    /// but only for check-in. Transfers and refunds still need the owner.
+    pub fn can_check_in(&self, signer: &Pubkey) -> bool {
+        *signer == self.owner
+            || (self.checkin_delegate != Pubkey::default() && *signer == self.checkin_delegate)
+    }
+    
+    /// Fail unless the ticket has been sold (not pre-registered stock)
+    pub fn require_active(&self) -> Result<()> {
+        require!(
+            self.status == TicketStatus::Active,
+            EventError::TicketInactive
+        );
+        Ok(())
+    }
+    
+    /// Sell a pre-registered ticket to `owner` for `price_paid`
+    pub fn activate(&mut self, owner: Pubkey, price_paid: u64, current_ts: i64) -> Result<()> {
+        require!(
+            self.status == TicketStatus::Inactive,
+            EventError::TicketAlreadyActive
+        );
+        
+        self.status = TicketStatus::Active;
+        self.owner = owner;
+        self.buyer = owner;
+        self.price_paid = price_paid;
+        self.purchased_at = current_ts;
+        
+        Ok(())
+    }
+    
+    /// Record a check-in by `gate_operator`
+    ///
+    /// Tickets from a tier with `max_uses > 1` can be checked in that many
+    /// times (re-entry passes). `used` is only set once every use has been
+    /// consumed, so partially used passes behave like unused tickets
+    /// elsewhere.
+    pub fn check_in(&mut self, current_ts: i64, gate_operator: Pubkey, max_uses: u16) -> Result<()> {
+        self.require_active()?;
+        require!(
+            !self.refunded,
+            EventError::AlreadyRefunded
+        );
+        require!(
+            !self.used && self.use_count < max_uses.max(1),
+            EventError::TicketAlreadyUsed
+        );
+        
+        self.use_count += 1;
+        self.used = self.use_count >= max_uses.max(1);
+        self.checked_in_ts = current_ts;
+        self.gate_operator = gate_operator;
+        
+        Ok(())
+    }
+    
+    /// Revert the latest check-in, returning the operator who performed it
+    ///
+    /// Gives back one use. The check-in timestamp and operator are cleared
+    /// once no uses remain.
+    pub fn undo_check_in(&mut self) -> Result<Pubkey> {
+        require!(
+            self.use_count > 0,
+            EventError::TicketNotUsed
+        );
+        
+        let gate_operator = self.gate_operator;
+        self.use_count -= 1;
+        self.used = false;
+        if self.use_count == 0 {
+            self.checked_in_ts = UNSET_TIMESTAMP;
+            self.gate_operator = Pubkey::default();
+        }
+        
+        Ok(gate_operator)
+    }
+    
+    /// When the ticket was checked in, if it has been
+    pub fn checked_in_at(&self) -> Option<i64> {
+        self.checked_in_ts.as_option()
+    }
+    
+    /// When the ticket was refunded, if it has been
+    pub fn refunded_at(&self) -> Option<i64> {
+        self.refund_ts.as_option()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn ticket(price_paid: u64) -> Ticket {
+        Ticket {
+            owner: Pubkey::default(),
+            event: Pubkey::default(),
+            tier: Pubkey::default(),
+            mint: Pubkey::default(),
+            used: false,
+            refunded: false,
+            checked_in_ts: 0,
+            gate_operator: Pubkey::default(),
+            refund_ts: 0,
+            snapshotted: false,
+            price_paid,
+            purchased_at: 0,
+            use_count: 0,
+            status: TicketStatus::Active,
+            promo_applied: false,
+            last_sale_price: 0,
+            last_sale_royalty: 0,
+            seat_number: 0,
+            gift_count: 0,
+            checkin_delegate: Pubkey::default(),
+            buyer: Pubkey::default(),
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_ticket_space() {
+        assert_eq!(8 + ticket(0).try_to_vec().unwrap().len(), Ticket::SPACE);
+    }
+    
+    #[test]
+    fn test_refund_defaults_to_price_paid() {
+        let ticket = ticket(500_000_000);
+        assert_eq!(ticket.resolve_refund_amount(None).unwrap(), 500_000_000);
+    }
+    
+    #[test]
+    fn test_refund_capped_at_price_paid() {
+        let paid = ticket(500_000_000);
+        
+        // Partial and exact refunds are allowed
+        assert_eq!(paid.resolve_refund_amount(Some(250_000_000)).unwrap(), 250_000_000);
+        assert_eq!(paid.resolve_refund_amount(Some(500_000_000)).unwrap(), 500_000_000);
+        
+        // More than was paid is rejected
+        assert_eq!(
+            paid.resolve_refund_amount(Some(500_000_001)).unwrap_err(),
+            EventError::RefundExceedsPricePaid.into()
+        );
+        
+        // Tickets registered without payment can't be refunded lamports
+        assert_eq!(
+            ticket(0).resolve_refund_amount(Some(1)).unwrap_err(),
+            EventError::RefundExceedsPricePaid.into()
+        );
+    }
+    
+    #[test]
+    fn test_undo_check_in() {
+        let mut ticket = ticket(500_000_000);
+        let scanner = Pubkey::new_unique();
+        
+        // Never used tickets can't be reverted
+        assert_eq!(
+            ticket.undo_check_in().unwrap_err(),
+            EventError::TicketNotUsed.into()
+        );
+        
+        // Mistaken scan is reverted, reporting the original operator
+        ticket.check_in(1_000, scanner, 1).unwrap();
+        assert_eq!(ticket.checked_in_at(), Some(1_000));
+        assert_eq!(ticket.undo_check_in().unwrap(), scanner);
+        assert!(!ticket.used);
+        assert_eq!(ticket.checked_in_at(), None);
+        assert_eq!(ticket.gate_operator, Pubkey::default());
+        
+        // The attendee can check in again afterwards
+        let other_scanner = Pubkey::new_unique();
+        ticket.check_in(1_060, other_scanner, 1).unwrap();
+        assert_eq!(ticket.checked_in_at(), Some(1_060));
+        assert_eq!(ticket.gate_operator, other_scanner);
+        
+        // But not twice
+        assert_eq!(
+            ticket.check_in(1_070, other_scanner, 1).unwrap_err(),
+            EventError::TicketAlreadyUsed.into()
+        );
+    }
+    
+    #[test]
+    fn test_refunded_ticket_cannot_check_in() {
+        let mut ticket = ticket(500_000_000);
+        ticket.refunded = true;
+        
+        assert_eq!(
+            ticket.check_in(1_000, Pubkey::new_unique(), 1).unwrap_err(),
+            EventError::AlreadyRefunded.into()
+        );
+        assert_eq!(ticket.use_count, 0);
+    }
+    
+    #[test]
+    fn test_multi_use_pass() {
+        let mut pass = ticket(500_000_000);
+        let scanner = Pubkey::new_unique();
+        
+        // Three-day festival pass: three check-ins succeed
+        for (day, ts) in [1_000, 87_400, 173_800].into_iter().enumerate() {
+            pass.check_in(ts, scanner, 3).unwrap();
+            assert_eq!(pass.use_count, day as u16 + 1);
+            assert_eq!(pass.checked_in_at(), Some(ts));
+        }
+        
+        // Only fully consumed once the last use is taken, so it stays
+        // refundable until then
+        assert!(pass.used);
+        
+        // The fourth is rejected
+        assert_eq!(
+            pass.check_in(260_200, scanner, 3).unwrap_err(),
+            EventError::TicketAlreadyUsed.into()
+        );
+        assert_eq!(pass.use_count, 3);
+        
+        // Undoing a mistaken scan gives one use back
+        pass.undo_check_in().unwrap();
+        assert_eq!(pass.use_count, 2);
+        assert!(!pass.used);
+        pass.check_in(260_200, scanner, 3).unwrap();
+        assert!(pass.used);
+    }
+    
+    #[test]
+    fn test_partially_used_pass_not_used() {
+        let mut pass = ticket(500_000_000);
+        
+        pass.check_in(1_000, Pubkey::new_unique(), 3).unwrap();
+        assert!(!pass.used);
+        
+        // Legacy tiers without max_uses behave as single-use
+        let mut single = ticket(500_000_000);
+        single.check_in(1_000, Pubkey::new_unique(), 0).unwrap();
+        assert!(single.used);
+    }
+    
+    #[test]
+    fn test_compact_checkin_event_size() {
+        use anchor_lang::Event as _;
+        use crate::instructions::{TicketUsed, TicketUsedByOperator, TicketUsedCompact, TicketUsedWithNonce};
+        
+        let mut ticket = ticket(500_000_000);
+        ticket.check_in(1_000, Pubkey::new_unique(), 2).unwrap();
+        
+        let compact = TicketUsedCompact {
+            ticket: Pubkey::new_unique(),
+            seq: ticket.use_count as u32,
+            ts: ticket.checked_in_ts,
+        };
+        let full = TicketUsed {
+            ticket_pubkey: compact.ticket,
+            owner: ticket.owner,
+            mint: ticket.mint,
+            event: ticket.event,
+            tier: ticket.tier,
+            gate_operator: ticket.gate_operator,
+            checked_in_ts: ticket.checked_in_ts,
+            ordered_ts: ticket.checked_in_ts,
+            use_count: ticket.use_count,
+            tickets_checked_in: 1,
+            seat_number: ticket.seat_number,
+        };
+        let by_operator = TicketUsedByOperator {
+            ticket_pubkey: compact.ticket,
+            owner: ticket.owner,
+            mint: ticket.mint,
+            event: ticket.event,
+            tier: ticket.tier,
+            gate_operator: ticket.gate_operator,
+            checked_in_ts: ticket.checked_in_ts,
+            ordered_ts: ticket.checked_in_ts,
+            nonce_hash: [0; 32],
+            use_count: ticket.use_count,
+            tickets_checked_in: 1,
+            seat_number: ticket.seat_number,
+        };
+        let with_nonce = TicketUsedWithNonce {
+            ticket_pubkey: compact.ticket,
+            owner: ticket.owner,
+            mint: ticket.mint,
+            event: ticket.event,
+            tier: ticket.tier,
+            gate_operator: ticket.gate_operator,
+            checked_in_ts: ticket.checked_in_ts,
+            ordered_ts: ticket.checked_in_ts,
+            nonce_hash: [0; 32],
+            use_count: ticket.use_count,
+            tickets_checked_in: 1,
+            seat_number: ticket.seat_number,
+            checkin_message_version: 1,
+        };
+        
+        // Log payload per check-in (discriminator included)
+        assert_eq!(compact.data().len(), 52);
+        assert_eq!(full.data().len(), 226);
+        assert_eq!(by_operator.data().len(), 258);
+        assert_eq!(with_nonce.data().len(), 259);
+        
+        // seq tracks the ticket's uses, so re-entry check-ins stay distinguishable
+        assert_eq!(compact.seq, 1);
+    }
+    
+    #[test]
+    fn test_preregistered_ticket_lifecycle() {
+        let mut stock = ticket(0);
+        stock.status = TicketStatus::Inactive;
+        stock.purchased_at = UNSET_TIMESTAMP;
+        let scanner = Pubkey::new_unique();
+        
+        // Unsold stock can't be scanned in
+        assert_eq!(stock.check_in(1_000, scanner, 1).unwrap_err(), EventError::TicketInactive.into());
+        assert_eq!(stock.require_active().unwrap_err(), EventError::TicketInactive.into());
+        assert_eq!(stock.use_count, 0);
+        
+        // Sold at the box office
+        let buyer = Pubkey::new_unique();
+        stock.activate(buyer, 250_000_000, 900).unwrap();
+        assert_eq!(stock.status, TicketStatus::Active);
+        assert_eq!(stock.owner, buyer);
+        assert_eq!(stock.buyer, buyer);
+        assert_eq!(stock.price_paid, 250_000_000);
+        assert_eq!(stock.purchased_at, 900);
+        assert_eq!(stock.resolve_refund_amount(None).unwrap(), 250_000_000);
+        
+        // Can't be sold twice
+        assert_eq!(
+            stock.activate(Pubkey::new_unique(), 0, 950).unwrap_err(),
+            EventError::TicketAlreadyActive.into()
+        );
+        assert_eq!(stock.owner, buyer);
+        
+        // Checks in like any other ticket
+        stock.check_in(1_000, scanner, 1).unwrap();
+        assert!(stock.used);
+    }
+    
+    #[test]
+    fn test_gift_allowance() {
+        let mut ticket = ticket(500_000_000);
+        ticket.record_gift().unwrap();
+        assert_eq!(ticket.gift_count, 1);
+        
+        assert_eq!(ticket.record_gift().unwrap_err(), EventError::GiftLimitReached.into());
+        assert_eq!(ticket.gift_count, Ticket::MAX_GIFTS);
+    }
+    
+    #[test]
+    fn test_checkin_delegate() {
+        let mut ticket = ticket(0);
+        ticket.owner = Pubkey::new_unique();
+        let phone = Pubkey::new_unique();
+        
+        assert!(ticket.can_check_in(&ticket.owner));
+        assert!(!ticket.can_check_in(&phone));
+        assert!(!ticket.can_check_in(&Pubkey::default()));
+        
+        ticket.checkin_delegate = phone;
+        assert!(ticket.can_check_in(&ticket.owner));
+        assert!(ticket.can_check_in(&phone));
+    }
 }
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+
+/// Wallet-scoped index of tickets owned for an event
+///
+/// Optional PDA (["ticket_index", event, owner]) maintained only when the
+/// event has `maintain_ticket_index` enabled. Lets wallets and support
+/// tooling list a wallet's tickets without getProgramAccounts filters.
+/// Tickets sold before the flag was enabled are not backfilled, and a
+/// wallet's tickets beyond `MAX_TICKETS` are left out rather than failing
+/// the sale or transfer, so the index is a convenience and not a complete
+/// record.
+#[account]
+pub struct TicketIndex {
+    /// The event these tickets belong to
+    pub event: Pubkey,
+    
+    /// Wallet that owns the indexed tickets
+    pub owner: Pubkey,
+    
+    /// Ticket account pubkeys currently owned (max MAX_TICKETS)
+    pub tickets: Vec<Pubkey>,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TicketIndex {
+    /// Maximum tickets tracked per wallet per event
+    pub const MAX_TICKETS: usize = 16;
+    
+    /// Calculate space needed for TicketIndex account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // event
+        32 + // owner
+        4 + (32 * Self::MAX_TICKETS) + // tickets (Vec)
+        1;   // bump
+    
+    /// Initialize a freshly created index (no-op if already initialized)
+    pub fn init_if_new(&mut self, event: Pubkey, owner: Pubkey, bump: u8) {
+        if self.owner == Pubkey::default() {
+            self.event = event;
+            self.owner = owner;
+            self.tickets = Vec::new();
+            self.bump = bump;
+        }
+    }
+    
+    /// Add a ticket to the index, returning whether it is now indexed
+    ///
+    /// Duplicates are ignored. A full index skips the ticket instead of
+    /// failing, so indexing never blocks a purchase or transfer.
+    pub fn add(&mut self, ticket: Pubkey) -> bool {
+        if self.tickets.contains(&ticket) {
+            return true;
+        }
+        if self.tickets.len() >= Self::MAX_TICKETS {
+            return false;
+        }
+        
+        self.tickets.push(ticket);
+        true
+    }
+    
+    /// Remove a ticket from the index, returning whether it was present
+    pub fn remove(&mut self, ticket: &Pubkey) -> bool {
+        match self.tickets.iter().position(|t| t == ticket) {
+            Some(position) => {
+                self.tickets.swap_remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn index(owner: Pubkey) -> TicketIndex {
+        TicketIndex {
+            event: Pubkey::default(),
+            owner,
+            tickets: Vec::new(),
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_ticket_index_len() {
+        let mut full = index(Pubkey::new_unique());
+        for _ in 0..TicketIndex::MAX_TICKETS {
+            assert!(full.add(Pubkey::new_unique()));
+        }
+        
+        assert_eq!(8 + full.try_to_vec().unwrap().len(), TicketIndex::LEN);
+    }
+    
+    #[test]
+    fn test_add_and_remove() {
+        let mut index = index(Pubkey::new_unique());
+        let ticket = Pubkey::new_unique();
+        
+        assert!(index.add(ticket));
+        assert!(index.add(ticket)); // duplicate ignored
+        assert_eq!(index.tickets, vec![ticket]);
+        
+        assert!(index.remove(&ticket));
+        assert!(!index.remove(&ticket));
+        assert!(index.tickets.is_empty());
+    }
+    
+    #[test]
+    fn test_index_full() {
+        let mut index = index(Pubkey::new_unique());
+        for _ in 0..TicketIndex::MAX_TICKETS {
+            assert!(index.add(Pubkey::new_unique()));
+        }
+        
+        // Further tickets are skipped, not rejected
+        let extra = Pubkey::new_unique();
+        assert!(!index.add(extra));
+        assert_eq!(index.tickets.len(), TicketIndex::MAX_TICKETS);
+        assert!(!index.tickets.contains(&extra));
+        
+        // Room frees up once a ticket leaves
+        let first = index.tickets[0];
+        assert!(index.remove(&first));
+        assert!(index.add(extra));
+    }
+    
+    #[test]
+    fn test_transfer_updates_both_indexes() {
+        let sender = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mut sender_index = index(sender);
+        let mut recipient_index = index(recipient);
+        
+        let ticket_a = Pubkey::new_unique();
+        let ticket_b = Pubkey::new_unique();
+        assert!(sender_index.add(ticket_a));
+        assert!(sender_index.add(ticket_b));
+        
+        // Transfer ticket_a the way transfer_ticket does
+        assert!(sender_index.remove(&ticket_a));
+        assert!(recipient_index.add(ticket_a));
+        
+        assert_eq!(sender_index.tickets, vec![ticket_b]);
+        assert_eq!(recipient_index.tickets, vec![ticket_a]);
+        
+        // And back again
+        assert!(recipient_index.remove(&ticket_a));
+        assert!(sender_index.add(ticket_a));
+        
+        assert!(recipient_index.tickets.is_empty());
+        assert_eq!(sender_index.tickets.len(), 2);
+        assert!(sender_index.tickets.contains(&ticket_a));
+    }
+}
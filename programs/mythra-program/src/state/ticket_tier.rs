@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::errors::EventError;
+use crate::merkle;
+use crate::state::OptionalTimestamp;
 
 #[account]
 pub struct TicketTier {
@@ -10,17 +13,159 @@ pub struct TicketTier {
     pub royalty_bps: u16,           // 2 bytes
     pub resale_enabled: bool,       // 1 byte - allow ticket transfers/resale
     pub tier_index: u8,             // 1 byte
+    pub max_per_wallet: u8,         // 1 byte - max tickets per buyer wallet (0 = unlimited)
+    pub sale_start_ts: i64,         // 8 bytes - when sales open (UNSET_TIMESTAMP = unbounded)
+    pub sale_end_ts: i64,           // 8 bytes - when sales close (UNSET_TIMESTAMP = unbounded)
+    pub max_uses: u16,              // 2 bytes - check-ins allowed per ticket (re-entry passes)
+    pub promo: Option<TierPromo>,   // 1 + 24 bytes - time-boxed promotional price
+    pub closed: bool,               // 1 byte - closed by the organizer; no further sales
+    pub allowlist_root: Option<[u8; 32]>, // 1 + 32 bytes - merkle root of wallets allowed to buy before sale_start_ts
+    pub max_resale_price_lamports: u64, // 8 bytes - resale price ceiling (0 = uncapped)
+    pub transferable: bool,         // 1 byte - false = soulbound; ticket NFTs are frozen to the buyer
+    pub seated: bool,               // 1 byte - reserved seating; purchases are assigned seats in order
+    pub next_seat: u32,             // 4 bytes - highest seat number handed out so far
     pub bump: u8,                   // 1 byte
 }
 
+/// Promotional price charged instead of the tier price inside a time window
+///
+/// The window is `[start_ts, end_ts)`, like the tier's sale window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TierPromo {
+    pub price_lamports: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+impl TierPromo {
+    pub const LEN: usize = 8 + 8 + 8;
+    
+    /// Whether the promo price applies at `current_ts`
+    pub fn is_live(&self, current_ts: i64) -> bool {
+        self.start_ts <= current_ts && current_ts < self.end_ts
+    }
+}
+
 impl TicketTier {
     pub const MAX_METADATA_URI_LENGTH: usize = 200;
     
     /// Calculate space needed for a TicketTier account
     /// 8 (discriminator) + 32 (event) + 8 (price_lamports) + 4 (max_supply) + 
-    /// 4 (current_supply) + 4 + metadata_uri_len + 2 (royalty_bps) + 1 (resale_enabled) + 1 (tier_index) + 1 (bump)
+    /// 4 (current_supply) + 4 + metadata_uri_len + 2 (royalty_bps) + 1 (resale_enabled) + 1 (tier_index) +
+    /// 1 (max_per_wallet) + 8 (sale_start_ts) + 8 (sale_end_ts) + 2 (max_uses) +
+    /// 1 + 24 (promo) + 1 (closed) + 1 + 32 (allowlist_root) + 8 (max_resale_price_lamports) + 1 (transferable) +
+    /// 1 (seated) + 4 (next_seat) + 1 (bump)
     pub fn space(metadata_uri_len: usize) -> usize {
-        8 + 32 + 8 + 4 + 4 + (4 + metadata_uri_len) + 2 + 1 + 1 + 1
+        8 + 32 + 8 + 4 + 4 + (4 + metadata_uri_len) + 2 + 1 + 1 + 1 + 8 + 8 + 2 + (1 + TierPromo::LEN) + 1 + (1 + 32) + 8 + 1 + 1 + 4 + 1
+    }
+    
+    /// Validate a sale window against the event start
+    ///
+    /// Either bound may be unset (unbounded). When both are set start must be
+    /// before end, and sales must close by the time the event starts.
+    pub fn validate_sale_window(sale_start_ts: i64, sale_end_ts: i64, event_start_ts: i64) -> Result<()> {
+        if let (Some(start), Some(end)) = (sale_start_ts.as_option(), sale_end_ts.as_option()) {
+            require!(
+                start < end,
+                EventError::InvalidSaleWindow
+            );
+        }
+        
+        if let Some(end) = sale_end_ts.as_option() {
+            require!(
+                end <= event_start_ts,
+                EventError::InvalidSaleWindow
+            );
+        }
+        
+        Ok(())
+    }
+    
+    /// Fail if the organizer closed the tier
+    pub fn require_not_closed(&self) -> Result<()> {
+        require!(
+            !self.closed,
+            EventError::TierClosed
+        );
+        Ok(())
+    }
+    
+    /// Fail unless the tier's sale window is open at `current_ts`
+    pub fn require_sale_open(&self, current_ts: i64) -> Result<()> {
+        self.require_not_closed()?;
+        require!(
+            self.sale_start_ts.reached_by(current_ts),
+            EventError::SaleNotStarted
+        );
+        require!(
+            self.sale_end_ts.not_reached_by(current_ts),
+            EventError::SaleEnded
+        );
+        Ok(())
+    }
+    
+    /// Whether only allowlisted wallets can buy at `current_ts`
+    ///
+    /// With an allowlist root set, the allowlist presale runs until the
+    /// public `sale_start_ts` and takes the place of the event's backer
+    /// presale for this tier. Tiers without a sale start have no presale.
+    pub fn in_allowlist_presale(&self, current_ts: i64) -> bool {
+        self.allowlist_root.is_some() && !self.sale_start_ts.reached_by(current_ts)
+    }
+    
+    /// Fail unless `proof` puts `buyer` on the tier's allowlist
+    pub fn require_allowlisted(&self, buyer: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        match self.allowlist_root {
+            Some(root) => merkle::verify_wallet(&root, buyer, proof),
+            None => err!(EventError::MerkleProofInvalid),
+        }
+    }
+    
+    /// Fail if a reported resale price is above the tier's ceiling
+    pub fn require_resale_price_allowed(&self, sale_price: u64) -> Result<()> {
+        require!(
+            self.max_resale_price_lamports == 0 || sale_price <= self.max_resale_price_lamports,
+            EventError::ResalePriceExceedsCap
+        );
+        Ok(())
+    }
+    
+    /// Fail if the tier's tickets are soulbound
+    pub fn require_transferable(&self) -> Result<()> {
+        require!(
+            self.transferable,
+            EventError::TicketNotTransferable
+        );
+        Ok(())
+    }
+    
+    /// Price charged at `current_ts` and whether the promo price applied
+    pub fn price_at(&self, current_ts: i64) -> (u64, bool) {
+        match self.promo {
+            Some(promo) if promo.is_live(current_ts) => (promo.price_lamports, true),
+            _ => (self.price_lamports, false),
+        }
+    }
+    
+    /// Schedule a promotional price, replacing any promo that isn't running
+    ///
+    /// The window must be non-empty and not already over. Only one promo is
+    /// stored: a finished or upcoming one is overwritten, but a promo that is
+    /// live at `current_ts` has to end first.
+    pub fn set_promo(&mut self, promo: TierPromo, current_ts: i64) -> Result<()> {
+        require!(
+            promo.start_ts < promo.end_ts && promo.end_ts > current_ts,
+            EventError::InvalidPromoWindow
+        );
+        if let Some(existing) = self.promo {
+            require!(
+                !existing.is_live(current_ts),
+                EventError::PromoAlreadyRunning
+            );
+        }
+        
+        self.promo = Some(promo);
+        Ok(())
     }
     
     /// Check if tier has available tickets
@@ -32,4 +177,350 @@ impl TicketTier {
     pub fn remaining(&self) -> u32 {
         self.max_supply.saturating_sub(self.current_supply)
     }
+    
+    /// Take `count` seats from the tier in one step
+    ///
+    /// All-or-nothing: fails without changing supply if fewer than `count`
+    /// seats remain.
+    pub fn reserve_seats(&mut self, count: u32) -> Result<()> {
+        require!(
+            count <= self.remaining(),
+            EventError::ExceedsTotalSupply
+        );
+        self.current_supply += count;
+        Ok(())
+    }
+    
+    /// Permanently remove `amount` unsold seats from the tier
+    ///
+    /// Sold seats are never touched: `max_supply` cannot drop below
+    /// `current_supply`.
+    pub fn retire_supply(&mut self, amount: u32) -> Result<()> {
+        require!(
+            amount > 0 && amount <= self.remaining(),
+            EventError::InvalidRetireAmount
+        );
+        self.max_supply -= amount;
+        Ok(())
+    }
+    
+    /// Close the tier to further sales, returning the unsold seats released
+    ///
+    /// Unsold seats are retired so the event can allocate them elsewhere;
+    /// sold seats stay allocated to the tier. When nothing was sold the
+    /// whole tier is released and the account can be closed.
+    pub fn close_sales(&mut self) -> Result<u32> {
+        self.require_not_closed()?;
+        
+        let released = self.remaining();
+        self.max_supply = self.current_supply;
+        self.closed = true;
+        Ok(released)
+    }
+    
+    /// Price difference owed to move a ticket from `current` up to this tier
+    ///
+    /// Only strictly more expensive tiers qualify, and a ticket can't move
+    /// between soulbound and transferable tiers (its NFT is frozen or not).
+    /// Seated tickets keep their seat; the organizer moves them instead.
+    pub fn upgrade_price_from(&self, current: &TicketTier) -> Result<u64> {
+        require!(
+            self.price_lamports > current.price_lamports && self.transferable == current.transferable,
+            EventError::InvalidTierUpgrade
+        );
+        require!(
+            !self.seated && !current.seated,
+            EventError::InvalidTierUpgrade
+        );
+        Ok(self.price_lamports - current.price_lamports)
+    }
+    
+    /// Hand out the next seat number on a seated tier
+    pub fn take_next_seat(&mut self) -> Result<u32> {
+        self.next_seat = self.next_seat
+            .checked_add(1)
+            .ok_or(EventError::ArithmeticOverflow)?;
+        Ok(self.next_seat)
+    }
+    
+    /// Validate a seat number the organizer assigns by hand
+    ///
+    /// A seat past the counter moves it forward, so purchases are never
+    /// handed a seat that was already assigned by hand.
+    pub fn claim_seat(&mut self, seat_number: u32) -> Result<()> {
+        require!(
+            self.seated && seat_number > 0,
+            EventError::InvalidSeatNumber
+        );
+        self.next_seat = self.next_seat.max(seat_number);
+        Ok(())
+    }
+    
+    /// Return a sold seat to the tier (e.g. after a refund)
+    pub fn release_seat(&mut self) -> Option<()> {
+        self.current_supply = self.current_supply.checked_sub(1)?;
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn fixture(max_supply: u32, current_supply: u32) -> TicketTier {
+        TicketTier {
+            event: Pubkey::default(),
+            price_lamports: 100_000_000,
+            max_supply,
+            current_supply,
+            metadata_uri: String::new(),
+            royalty_bps: 0,
+            resale_enabled: false,
+            tier_index: 0,
+            max_per_wallet: 0,
+            sale_start_ts: 0,
+            sale_end_ts: 0,
+            max_uses: 1,
+            promo: None,
+            closed: false,
+            allowlist_root: None,
+            max_resale_price_lamports: 0,
+            transferable: true,
+            seated: false,
+            next_seat: 0,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn test_sale_window_enforced() {
+        let tier = TicketTier {
+            sale_start_ts: 1_000,
+            sale_end_ts: 2_000,
+            ..fixture(100, 0)
+        };
+        
+        // Just before the window
+        assert_eq!(tier.require_sale_open(999).unwrap_err(), EventError::SaleNotStarted.into());
+        
+        // Inside the window
+        assert!(tier.require_sale_open(1_000).is_ok());
+        assert!(tier.require_sale_open(1_999).is_ok());
+        
+        // Just after the window
+        assert_eq!(tier.require_sale_open(2_000).unwrap_err(), EventError::SaleEnded.into());
+    }
+    
+    #[test]
+    fn test_unbounded_sale_window() {
+        let mut tier = fixture(100, 0);
+        
+        assert!(tier.require_sale_open(0).is_ok());
+        assert!(tier.require_sale_open(i64::MAX).is_ok());
+        
+        // Only an end bound (early-bird that closes)
+        tier.sale_end_ts = 2_000;
+        assert!(tier.require_sale_open(0).is_ok());
+        assert!(tier.require_sale_open(2_000).is_err());
+    }
+    
+    #[test]
+    fn test_validate_sale_window() {
+        let event_start = 10_000;
+        
+        assert!(TicketTier::validate_sale_window(0, 0, event_start).is_ok());
+        assert!(TicketTier::validate_sale_window(1_000, 2_000, event_start).is_ok());
+        assert!(TicketTier::validate_sale_window(1_000, event_start, event_start).is_ok());
+        
+        // Start must be before end
+        assert!(TicketTier::validate_sale_window(2_000, 2_000, event_start).is_err());
+        
+        // Sales must close by the event start
+        assert!(TicketTier::validate_sale_window(0, event_start + 1, event_start).is_err());
+    }
+    
+    #[test]
+    fn test_batch_hits_supply_ceiling() {
+        let mut tier = fixture(10, 7);
+        
+        // Batch of 5 with only 3 seats left fails as a whole
+        assert_eq!(tier.reserve_seats(5).unwrap_err(), EventError::ExceedsTotalSupply.into());
+        assert_eq!(tier.current_supply, 7);
+        
+        // Exactly the remaining seats succeeds and sells out the tier
+        tier.reserve_seats(3).unwrap();
+        assert_eq!(tier.current_supply, 10);
+        assert!(!tier.is_available());
+        
+        assert!(tier.reserve_seats(1).is_err());
+    }
+    
+    #[test]
+    fn test_retire_supply_floors_at_sold() {
+        let mut tier = fixture(1_000, 500);
+        
+        // Retiring more than the unsold seats fails without changing supply
+        assert_eq!(tier.retire_supply(501).unwrap_err(), EventError::InvalidRetireAmount.into());
+        assert_eq!(tier.retire_supply(0).unwrap_err(), EventError::InvalidRetireAmount.into());
+        assert_eq!(tier.max_supply, 1_000);
+        
+        // Retiring exactly the unsold seats closes the tier at its sold count
+        tier.retire_supply(500).unwrap();
+        assert_eq!(tier.max_supply, 500);
+        assert_eq!(tier.current_supply, 500);
+        assert!(!tier.is_available());
+        assert!(tier.retire_supply(1).is_err());
+    }
+    
+    #[test]
+    fn test_promo_price_window() {
+        let mut tier = fixture(100, 0);
+        
+        // No promo: regular price
+        assert_eq!(tier.price_at(1_500), (100_000_000, false));
+        
+        // 20% off between 1_000 and 2_000
+        tier.set_promo(TierPromo { price_lamports: 80_000_000, start_ts: 1_000, end_ts: 2_000 }, 500).unwrap();
+        assert_eq!(tier.price_at(999), (100_000_000, false));
+        assert_eq!(tier.price_at(1_000), (80_000_000, true));
+        assert_eq!(tier.price_at(1_999), (80_000_000, true));
+        assert_eq!(tier.price_at(2_000), (100_000_000, false));
+    }
+    
+    #[test]
+    fn test_set_promo_rules() {
+        let mut tier = fixture(100, 0);
+        let promo = |start_ts, end_ts| TierPromo { price_lamports: 50_000_000, start_ts, end_ts };
+        
+        // Empty or already-finished windows are rejected
+        assert_eq!(tier.set_promo(promo(2_000, 2_000), 0).unwrap_err(), EventError::InvalidPromoWindow.into());
+        assert_eq!(tier.set_promo(promo(1_000, 2_000), 2_000).unwrap_err(), EventError::InvalidPromoWindow.into());
+        
+        // An upcoming promo can be replaced before it starts
+        tier.set_promo(promo(1_000, 2_000), 0).unwrap();
+        tier.set_promo(promo(1_500, 2_500), 999).unwrap();
+        assert_eq!(tier.promo.unwrap().start_ts, 1_500);
+        
+        // A running promo can't be replaced, right up to its last second
+        assert_eq!(tier.set_promo(promo(3_000, 4_000), 1_500).unwrap_err(), EventError::PromoAlreadyRunning.into());
+        assert_eq!(tier.set_promo(promo(3_000, 4_000), 2_499).unwrap_err(), EventError::PromoAlreadyRunning.into());
+        
+        // Once it has finished it is overwritten
+        tier.set_promo(promo(3_000, 4_000), 2_500).unwrap();
+        assert_eq!(tier.promo, Some(promo(3_000, 4_000)));
+    }
+    
+    #[test]
+    fn test_close_releases_unsold_seats() {
+        let mut tier = fixture(100, 30);
+        
+        // The 70 unsold seats go back; the 30 sold stay with the tier
+        assert_eq!(tier.close_sales().unwrap(), 70);
+        assert_eq!(tier.max_supply, 30);
+        assert!(tier.closed);
+        
+        // No more sales, even once a refund frees a seat
+        tier.release_seat().unwrap();
+        assert_eq!(tier.require_sale_open(0).unwrap_err(), EventError::TierClosed.into());
+        assert_eq!(tier.close_sales().unwrap_err(), EventError::TierClosed.into());
+    }
+    
+    #[test]
+    fn test_allowlist_presale_window() {
+        use crate::state::UNSET_TIMESTAMP;
+        
+        let mut tier = TicketTier {
+            sale_start_ts: 1_000,
+            sale_end_ts: UNSET_TIMESTAMP,
+            ..fixture(10, 0)
+        };
+        
+        // No root: no presale, early buyers just wait for the sale
+        assert!(!tier.in_allowlist_presale(999));
+        
+        // With a root the presale runs until the public sale start
+        let fan = Pubkey::new_unique();
+        tier.allowlist_root = Some(merkle::leaf_hash(&fan));
+        assert!(tier.in_allowlist_presale(999));
+        assert!(!tier.in_allowlist_presale(1_000));
+        assert!(tier.require_allowlisted(&fan, &[]).is_ok());
+        assert_eq!(
+            tier.require_allowlisted(&Pubkey::new_unique(), &[]).unwrap_err(),
+            EventError::MerkleProofInvalid.into()
+        );
+        
+        // Without a sale start there is nothing to presell ahead of
+        tier.sale_start_ts = UNSET_TIMESTAMP;
+        assert!(!tier.in_allowlist_presale(0));
+    }
+    
+    #[test]
+    fn test_resale_price_cap_boundary() {
+        let mut tier = TicketTier {
+            resale_enabled: true,
+            ..fixture(10, 0)
+        };
+        
+        // Uncapped by default
+        assert!(tier.require_resale_price_allowed(u64::MAX).is_ok());
+        
+        // At the cap is fine, one lamport over is not
+        tier.max_resale_price_lamports = 150_000_000;
+        assert!(tier.require_resale_price_allowed(150_000_000).is_ok());
+        assert_eq!(
+            tier.require_resale_price_allowed(150_000_001).unwrap_err(),
+            EventError::ResalePriceExceedsCap.into()
+        );
+        assert!(tier.require_resale_price_allowed(0).is_ok());
+    }
+    
+    #[test]
+    fn test_upgrade_price_requires_higher_tier() {
+        let tier = |price_lamports, transferable| TicketTier {
+            price_lamports,
+            resale_enabled: true,
+            transferable,
+            ..fixture(10, 0)
+        };
+        let general = tier(100_000_000, true);
+        let invalid = EventError::InvalidTierUpgrade.into();
+        
+        assert_eq!(tier(250_000_000, true).upgrade_price_from(&general).unwrap(), 150_000_000);
+        
+        // Same price or cheaper is not an upgrade
+        assert_eq!(tier(100_000_000, true).upgrade_price_from(&general).unwrap_err(), invalid);
+        assert_eq!(general.upgrade_price_from(&tier(250_000_000, true)).unwrap_err(), invalid);
+        
+        // Soulbound and transferable tiers don't mix
+        assert_eq!(tier(250_000_000, false).upgrade_price_from(&general).unwrap_err(), invalid);
+        
+        // Nor do seated tiers
+        let mut seated = tier(250_000_000, true);
+        seated.seated = true;
+        assert_eq!(seated.upgrade_price_from(&general).unwrap_err(), invalid);
+    }
+    
+    #[test]
+    fn test_seat_numbers_never_collide() {
+        let mut tier = TicketTier {
+            resale_enabled: true,
+            ..fixture(10, 0)
+        };
+        
+        // Manual seats need a seated tier and start at 1
+        assert_eq!(tier.claim_seat(1).unwrap_err(), EventError::InvalidSeatNumber.into());
+        tier.seated = true;
+        assert_eq!(tier.claim_seat(0).unwrap_err(), EventError::InvalidSeatNumber.into());
+        
+        assert_eq!(tier.take_next_seat().unwrap(), 1);
+        assert_eq!(tier.take_next_seat().unwrap(), 2);
+        
+        // Assigning ahead of the counter skips those seats for purchases
+        tier.claim_seat(5).unwrap();
+        assert_eq!(tier.take_next_seat().unwrap(), 6);
+        
+        // Seats behind the counter (freed by a move) leave it alone
+        tier.claim_seat(2).unwrap();
+        assert_eq!(tier.next_seat, 6);
+    }
 }
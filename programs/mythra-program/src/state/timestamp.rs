@@ -0,0 +1,91 @@
+/// Value stored in an optional `i64` timestamp field that is not set
+///
+/// Accounts keep optional timestamps as plain `i64` with a zero sentinel
+/// (e.g. `Ticket::checked_in_ts`, `TicketTier::sale_start_ts`) so their
+/// layout and space stay fixed. Read and write them through
+/// `OptionalTimestamp` and `to_stored_timestamp` rather than comparing
+/// against 0 by hand.
+pub const UNSET_TIMESTAMP: i64 = 0;
+
+/// Typed access to sentinel-encoded optional timestamps
+pub trait OptionalTimestamp: Copy {
+    /// Whether a real timestamp is stored
+    fn is_set(self) -> bool;
+    
+    /// The stored timestamp, or `None` for the unset sentinel
+    fn as_option(self) -> Option<i64>;
+    
+    /// Whether `current_ts` is at or after this lower bound (unset = unbounded)
+    // `Option::is_none_or` is newer than the SBF toolchain's rustc
+    #[allow(clippy::unnecessary_map_or)]
+    fn reached_by(self, current_ts: i64) -> bool {
+        self.as_option().map_or(true, |ts| current_ts >= ts)
+    }
+    
+    /// Whether `current_ts` is strictly before this upper bound (unset = unbounded)
+    #[allow(clippy::unnecessary_map_or)]
+    fn not_reached_by(self, current_ts: i64) -> bool {
+        self.as_option().map_or(true, |ts| current_ts < ts)
+    }
+}
+
+impl OptionalTimestamp for i64 {
+    fn is_set(self) -> bool {
+        self != UNSET_TIMESTAMP
+    }
+    
+    fn as_option(self) -> Option<i64> {
+        if self.is_set() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+/// Encode an optional timestamp for storage
+pub fn to_stored_timestamp(ts: Option<i64>) -> i64 {
+    ts.unwrap_or(UNSET_TIMESTAMP)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sentinel_round_trip() {
+        assert!(!UNSET_TIMESTAMP.is_set());
+        assert_eq!(UNSET_TIMESTAMP.as_option(), None);
+        assert_eq!(to_stored_timestamp(None), UNSET_TIMESTAMP);
+        
+        let ts: i64 = 1_700_000_000;
+        assert!(ts.is_set());
+        assert_eq!(ts.as_option(), Some(ts));
+        assert_eq!(to_stored_timestamp(Some(ts)), ts);
+        
+        // Negative timestamps are real values, not the sentinel
+        assert_eq!((-1i64).as_option(), Some(-1));
+    }
+    
+    #[test]
+    fn test_lower_bound() {
+        // Unset lower bound is always reached
+        assert!(UNSET_TIMESTAMP.reached_by(i64::MIN));
+        
+        let start: i64 = 1_000;
+        assert!(!start.reached_by(999));
+        assert!(start.reached_by(1_000));
+        assert!(start.reached_by(1_001));
+    }
+    
+    #[test]
+    fn test_upper_bound() {
+        // Unset upper bound is never reached
+        assert!(UNSET_TIMESTAMP.not_reached_by(i64::MAX));
+        
+        let end: i64 = 2_000;
+        assert!(end.not_reached_by(1_999));
+        assert!(!end.not_reached_by(2_000));
+        assert!(!end.not_reached_by(2_001));
+    }
+}
@@ -2,21 +2,30 @@ use anchor_lang::prelude::*;
 
 /// Vote record for budget approval
 /// 
-/// Each backer can vote once on a budget. Their voting power
-/// is equal to their contribution amount (linear voting for MVP).
+/// Each backer can vote once on each revision of a budget. Their voting
+/// power is derived from their contribution amount by the budget's
+/// `VotingModel` and stored here, so changes and withdrawals move exactly
+/// what was counted.
+/// PDA: ["budget_vote", budget, revision, voter]
 #[account]
 pub struct BudgetVote {
     /// The budget being voted on
     pub budget: Pubkey,
     
+    /// Revision of the budget the vote was cast on (part of the PDA seeds)
+    pub revision: u8,
+    
     /// The voter (must be a contributor to the campaign)
     pub voter: Pubkey,
     
-    /// Voter's contribution amount (determines voting power)
+    /// Voter's contribution amount (counts toward quorum turnout)
     pub contribution_amount: u64,
     
-    /// Vote choice: true = approve, false = reject
-    pub approve: bool,
+    /// Voting power counted in the tally
+    pub voting_power: u64,
+    
+    /// Vote choice
+    pub choice: VoteChoice,
     
     /// When the vote was cast
     pub voted_at: i64,
@@ -25,22 +34,75 @@ pub struct BudgetVote {
     pub bump: u8,
 }
 
+/// Choice on a budget vote
+///
+/// Abstentions count toward quorum but not toward the approval majority.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VoteChoice {
+    Approve,
+    Reject,
+    Abstain,
+}
+
 impl BudgetVote {
     /// Calculate space needed for BudgetVote account
     pub const LEN: usize = 8 + // discriminator
         32 + // budget
+        1 +  // revision
         32 + // voter
         8 +  // contribution_amount
-        1 +  // approve
+        8 +  // voting_power
+        1 +  // choice (enum)
         8 +  // voted_at
         1;   // bump
     
-    /// Get voting power (equal to contribution amount for MVP)
+    /// Get the voting power counted for this vote
     pub fn voting_power(&self) -> u64 {
-        self.contribution_amount
+        self.voting_power
     }
 }
 
+/// Vote record for a single milestone's release
+///
+/// Used by budgets with `per_milestone_approval`. Each backer votes once
+/// per milestone with the same contribution-weighted power as on the
+/// budget itself.
+#[account]
+pub struct MilestoneVote {
+    /// The budget the milestone belongs to
+    pub budget: Pubkey,
+    
+    /// Index of the milestone in the budget
+    pub milestone_index: u8,
+    
+    /// The voter (must be a contributor to the campaign)
+    pub voter: Pubkey,
+    
+    /// Voting power counted for this vote
+    pub voting_power: u64,
+    
+    /// Vote choice: true = release, false = reject
+    pub approve: bool,
+    
+    /// When the vote was cast
+    pub voted_at: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl MilestoneVote {
+    /// Calculate space needed for MilestoneVote account
+    pub const LEN: usize = 8 + // discriminator
+        32 + // budget
+        1 +  // milestone_index
+        32 + // voter
+        8 +  // voting_power
+        1 +  // approve
+        8 +  // voted_at
+        1;   // bump
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,14 +111,16 @@ mod tests {
     fn test_voting_power() {
         let vote = BudgetVote {
             budget: Pubkey::default(),
+            revision: 0,
             voter: Pubkey::default(),
             contribution_amount: 10_000_000_000, // 10 SOL
-            approve: true,
+            voting_power: 10_000_000_000,
+            choice: VoteChoice::Approve,
             voted_at: 0,
             bump: 0,
         };
         
-        // Voting power equals contribution amount in MVP
+        // Voting power is the stored power, not recomputed
         assert_eq!(vote.voting_power(), 10_000_000_000);
     }
 }
@@ -0,0 +1,1622 @@
+//! In-process scenario harness for cross-instruction tests
+//!
+//! Runs the program's real Anchor entrypoint natively against an in-memory
+//! account store. Accounts are serialized in the runtime's input layout, so
+//! `init`, `realloc` and `close` behave as on-chain, and CPIs into the
+//! System and SPL Token programs are served by `SyscallStubs` that check
+//! signers (including PDA signer seeds). After every instruction the harness
+//! rejects non-rent-exempt accounts and asserts that lamports were conserved.
+//!
+//! `ScenarioBuilder` wraps the store with fixture helpers that derive every
+//! PDA and create token accounts, so a lifecycle reads as a list of steps.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::entrypoint::{deserialize, MAX_PERMITTED_DATA_INCREASE};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::{IsInitialized, Pack};
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::immutable_owner::ImmutableOwner;
+use anchor_spl::token_2022::spl_token_2022::extension::mint_close_authority::MintCloseAuthority;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut};
+use anchor_spl::token::spl_token::instruction::TokenInstruction;
+
+use mythra_program::instructions::{CreateTicketTierParams, MilestoneInput};
+use mythra_program::metadata;
+use mythra_program::state::{Budget, Campaign, Denomination, VoteChoice, VotingModel};
+
+/// Clock value handed to the program (unix seconds)
+static UNIX_TIMESTAMP: AtomicI64 = AtomicI64::new(0);
+
+/// The syscall stubs and clock are process-wide, so scenarios run one at a time
+static SCENARIO_LOCK: Mutex<()> = Mutex::new(());
+
+/// Timestamp every scenario starts at
+pub const GENESIS_TS: i64 = 1_750_000_000;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+pub const DAY: i64 = 24 * 60 * 60;
+
+/// Outcome of one instruction, as the runtime reports it
+pub type TxResult<T = ()> = std::result::Result<T, ProgramError>;
+
+/// Anchor error code as the runtime reports it
+pub fn program_error(error: mythra_program::errors::EventError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+pub fn rent_exempt(data_len: usize) -> u64 {
+    Rent::default().minimum_balance(data_len)
+}
+
+/// Milestone inputs from (release_percentage, unlock_date) pairs
+fn milestone_inputs(milestones: &[(u16, i64)]) -> Vec<MilestoneInput> {
+    milestones
+        .iter()
+        .enumerate()
+        .map(|(index, &(release_percentage, unlock_date))| MilestoneInput {
+            description: format!("Milestone {}", index + 1),
+            release_percentage,
+            unlock_date,
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TestAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+/// PDA derivations, mirroring the seeds in the account structs
+pub mod pda {
+    use super::*;
+
+    fn find(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &mythra_program::ID).0
+    }
+
+    pub fn event(organizer: &Pubkey, event_id: &str) -> Pubkey {
+        find(&[b"event", organizer.as_ref(), event_id.as_bytes()])
+    }
+
+    pub fn tier(event: &Pubkey, tier_id: &str) -> Pubkey {
+        find(&[b"tier", event.as_ref(), tier_id.as_bytes()])
+    }
+
+    pub fn ticket(mint: &Pubkey) -> Pubkey {
+        find(&[b"ticket", mint.as_ref()])
+    }
+
+    pub fn buyer_record(tier: &Pubkey, buyer: &Pubkey) -> Pubkey {
+        find(&[b"buyer", tier.as_ref(), buyer.as_ref()])
+    }
+
+    pub fn ticket_index(event: &Pubkey, owner: &Pubkey) -> Pubkey {
+        find(&[b"ticket_index", event.as_ref(), owner.as_ref()])
+    }
+
+    pub fn blocklist(event: &Pubkey, wallet: &Pubkey) -> Pubkey {
+        find(&[b"blocklist", event.as_ref(), wallet.as_ref()])
+    }
+
+    pub fn gate_operator(event: &Pubkey, operator: &Pubkey) -> Pubkey {
+        find(&[b"gate_operator", event.as_ref(), operator.as_ref()])
+    }
+
+    pub fn ticket_escrow(event: &Pubkey) -> Pubkey {
+        find(&[b"ticket_escrow", event.as_ref()])
+    }
+
+    pub fn platform_fees(event: &Pubkey) -> Pubkey {
+        find(&[b"platform_fees", event.as_ref()])
+    }
+
+    pub fn campaign(event: &Pubkey) -> Pubkey {
+        find(&[b"campaign", event.as_ref()])
+    }
+
+    pub fn campaign_escrow(campaign: &Pubkey) -> Pubkey {
+        find(&[b"campaign_escrow", campaign.as_ref()])
+    }
+
+    pub fn contribution(campaign: &Pubkey, contributor: &Pubkey) -> Pubkey {
+        find(&[b"contribution", campaign.as_ref(), contributor.as_ref()])
+    }
+
+    pub fn budget(campaign: &Pubkey) -> Pubkey {
+        find(&[b"budget", campaign.as_ref()])
+    }
+
+    pub fn budget_revision(campaign: &Pubkey, revision: u8) -> Pubkey {
+        find(&[b"budget_revision", campaign.as_ref(), &[revision]])
+    }
+
+    pub fn budget_vote(budget: &Pubkey, revision: u8, voter: &Pubkey) -> Pubkey {
+        find(&[b"budget_vote", budget.as_ref(), &[revision], voter.as_ref()])
+    }
+
+    pub fn milestone_vote(budget: &Pubkey, milestone_index: u8, voter: &Pubkey) -> Pubkey {
+        find(&[b"milestone_vote", budget.as_ref(), &[milestone_index], voter.as_ref()])
+    }
+
+    pub fn config() -> Pubkey {
+        find(&[b"config"])
+    }
+
+    pub fn order(event: &Pubkey, order_id: &str) -> Pubkey {
+        find(&[b"order", event.as_ref(), &mythra_program::state::Order::seed_hash(order_id)])
+    }
+
+    pub fn program_data() -> Pubkey {
+        Pubkey::find_program_address(&[mythra_program::ID.as_ref()], &anchor_lang::solana_program::bpf_loader_upgradeable::ID).0
+    }
+
+    pub fn seat(tier: &Pubkey, seat_number: u32) -> Pubkey {
+        find(&[b"seat", tier.as_ref(), &seat_number.to_le_bytes()])
+    }
+
+    pub fn ticket_freeze() -> Pubkey {
+        find(&[b"ticket_freeze"])
+    }
+
+    pub fn metadata_authority(event: &Pubkey) -> Pubkey {
+        find(&[metadata::METADATA_AUTHORITY_SEED, event.as_ref()])
+    }
+
+    pub fn pending_transfer(ticket: &Pubkey) -> Pubkey {
+        find(&[b"pending_transfer", ticket.as_ref()])
+    }
+
+    pub fn pending_transfer_escrow(ticket: &Pubkey) -> Pubkey {
+        find(&[b"pending_transfer_escrow", ticket.as_ref()])
+    }
+
+    pub fn refund_claim(ticket: &Pubkey) -> Pubkey {
+        find(&[b"refund_claim", ticket.as_ref()])
+    }
+
+    pub fn attendance(event: &Pubkey, page_index: u32) -> Pubkey {
+        find(&[b"attendance", event.as_ref(), &page_index.to_le_bytes()])
+    }
+}
+
+/// Native stand-ins for the runtime syscalls the program uses
+struct ScenarioStubs;
+
+impl SyscallStubs for ScenarioStubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            unix_timestamp: UNIX_TIMESTAMP.load(Ordering::SeqCst),
+            ..Clock::default()
+        };
+        unsafe { std::ptr::write_unaligned(var_addr as *mut Clock, clock) };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { std::ptr::write_unaligned(var_addr as *mut Rent, Rent::default()) };
+        0
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> TxResult {
+        let infos = instruction
+            .accounts
+            .iter()
+            .map(|meta| {
+                account_infos
+                    .iter()
+                    .find(|info| *info.key == meta.pubkey)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &mythra_program::ID))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+        let signed = |info: &AccountInfo| info.is_signer || signers.contains(info.key);
+
+        if instruction.program_id == system_program::ID {
+            invoke_system(&instruction.data, &infos, signed)
+        } else if instruction.program_id == spl_token::ID || instruction.program_id == spl_token_2022::ID {
+            invoke_token(&instruction.data, &infos, signed)
+        } else if instruction.program_id == metadata::ID {
+            invoke_metadata(&instruction.data, &infos, signed)
+        } else {
+            Err(ProgramError::IncorrectProgramId)
+        }
+    }
+}
+
+fn read_u64(data: &[u8], offset: usize) -> TxResult<u64> {
+    data.get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> TxResult<Pubkey> {
+    data.get(offset..offset + 32)
+        .map(|bytes| Pubkey::new_from_array(bytes.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> TxResult {
+    let from_balance = from.lamports().checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+/// System program: the instructions Anchor and the program issue
+fn invoke_system(
+    data: &[u8],
+    infos: &[&AccountInfo],
+    signed: impl Fn(&AccountInfo) -> bool,
+) -> TxResult {
+    let tag = data.get(0..4).ok_or(ProgramError::InvalidInstructionData)?;
+    match u32::from_le_bytes(tag.try_into().unwrap()) {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            let (from, to) = (infos[0], infos[1]);
+            if !signed(from) || !signed(to) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if to.lamports() > 0 || *to.owner != system_program::ID {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            move_lamports(from, to, read_u64(data, 4)?)?;
+            to.resize(read_u64(data, 12)? as usize)?;
+            to.assign(&read_pubkey(data, 20)?);
+            Ok(())
+        }
+        // Assign { owner }: only system-owned accounts change hands
+        1 => {
+            if !signed(infos[0]) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let owner = read_pubkey(data, 4)?;
+            if *infos[0].owner != system_program::ID && *infos[0].owner != owner {
+                return Err(ProgramError::InvalidArgument);
+            }
+            infos[0].assign(&owner);
+            Ok(())
+        }
+        // Transfer { lamports }: only from data-less system accounts
+        2 => {
+            let (from, to) = (infos[0], infos[1]);
+            if !signed(from) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *from.owner != system_program::ID || !from.data_is_empty() {
+                return Err(ProgramError::InvalidArgument);
+            }
+            move_lamports(from, to, read_u64(data, 4)?)
+        }
+        // Allocate { space }: accounts already in use are rejected
+        8 => {
+            if !signed(infos[0]) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *infos[0].owner != system_program::ID || !infos[0].data_is_empty() {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            infos[0].resize(read_u64(data, 4)? as usize)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Base token state, ignoring any Token-2022 extensions after it
+fn unpack_base<T: Pack + IsInitialized>(data: &[u8]) -> TxResult<T> {
+    T::unpack(data.get(..T::LEN).ok_or(ProgramError::InvalidAccountData)?)
+}
+
+fn pack_base<T: Pack + IsInitialized>(state: T, data: &mut [u8]) -> TxResult {
+    T::pack(state, data.get_mut(..T::LEN).ok_or(ProgramError::InvalidAccountData)?)
+}
+
+/// SPL Token and Token-2022: the instructions the program issues against
+/// ticket NFTs (extensions are carried along but not enforced)
+fn invoke_token(
+    data: &[u8],
+    infos: &[&AccountInfo],
+    signed: impl Fn(&AccountInfo) -> bool,
+) -> TxResult {
+    let unpack_account = |info: &AccountInfo| unpack_base::<spl_token::state::Account>(&info.try_borrow_data()?);
+    let pack_account = |info: &AccountInfo, account: spl_token::state::Account| {
+        pack_base(account, &mut info.try_borrow_mut_data()?)
+    };
+
+    let frozen = || ProgramError::Custom(spl_token::error::TokenError::AccountFrozen as u32);
+
+    match TokenInstruction::unpack(data)? {
+        TokenInstruction::Burn { amount } | TokenInstruction::BurnChecked { amount, .. } => {
+            let (source, mint, authority) = (infos[0], infos[1], infos[2]);
+            let mut account = unpack_account(source)?;
+            let delegated = account.delegate == COption::Some(*authority.key) && account.delegated_amount >= amount;
+            if (account.owner != *authority.key && !delegated) || !signed(authority) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if account.is_frozen() {
+                return Err(frozen());
+            }
+            account.amount = account.amount.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+            if account.owner != *authority.key {
+                account.delegated_amount -= amount;
+                if account.delegated_amount == 0 {
+                    account.delegate = COption::None;
+                }
+            }
+            pack_account(source, account)?;
+
+            let mut mint_state = unpack_base::<spl_token::state::Mint>(&mint.try_borrow_data()?)?;
+            mint_state.supply -= amount;
+            pack_base(mint_state, &mut mint.try_borrow_mut_data()?)
+        }
+        TokenInstruction::Transfer { amount } | TokenInstruction::TransferChecked { amount, .. } => {
+            let checked = !matches!(TokenInstruction::unpack(data)?, TokenInstruction::Transfer { .. });
+            let (source, destination, authority) = if checked {
+                (infos[0], infos[2], infos[3])
+            } else {
+                (infos[0], infos[1], infos[2])
+            };
+            let mut from = unpack_account(source)?;
+            if from.owner != *authority.key || !signed(authority) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if from.is_frozen() {
+                return Err(frozen());
+            }
+            from.amount = from.amount.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+            pack_account(source, from)?;
+
+            let mut to = unpack_account(destination)?;
+            to.amount += amount;
+            pack_account(destination, to)
+        }
+        TokenInstruction::Approve { amount } => {
+            let (source, delegate, owner) = (infos[0], infos[1], infos[2]);
+            let mut account = unpack_account(source)?;
+            if account.owner != *owner.key || !signed(owner) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if account.is_frozen() {
+                return Err(frozen());
+            }
+            account.delegate = COption::Some(*delegate.key);
+            account.delegated_amount = amount;
+            pack_account(source, account)
+        }
+        TokenInstruction::FreezeAccount | TokenInstruction::ThawAccount => {
+            let freeze = matches!(TokenInstruction::unpack(data)?, TokenInstruction::FreezeAccount);
+            let (target, mint, authority) = (infos[0], infos[1], infos[2]);
+            let mint_state = unpack_base::<spl_token::state::Mint>(&mint.try_borrow_data()?)?;
+            if mint_state.freeze_authority != COption::Some(*authority.key) || !signed(authority) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let mut account = unpack_account(target)?;
+            if account.mint != *mint.key || account.is_frozen() == freeze {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            account.state = if freeze {
+                spl_token::state::AccountState::Frozen
+            } else {
+                spl_token::state::AccountState::Initialized
+            };
+            pack_account(target, account)
+        }
+        TokenInstruction::InitializeAccount3 { owner } => {
+            let (target, mint) = (infos[0], infos[1]);
+            if *target.owner != *mint.owner
+                || spl_token::state::Account::unpack_unchecked(
+                    target.try_borrow_data()?.get(..spl_token::state::Account::LEN).ok_or(ProgramError::InvalidAccountData)?,
+                )?.is_initialized()
+            {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            unpack_base::<spl_token::state::Mint>(&mint.try_borrow_data()?)?;
+            pack_account(target, spl_token::state::Account {
+                mint: *mint.key,
+                owner,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            })
+        }
+        // CloseAccount: only empty accounts, rent goes to the destination
+        TokenInstruction::CloseAccount => {
+            let (target, destination, authority) = (infos[0], infos[1], infos[2]);
+            let account = unpack_account(target)?;
+            if account.owner != *authority.key || !signed(authority) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if account.amount != 0 {
+                return Err(ProgramError::Custom(spl_token::error::TokenError::NonNativeHasBalance as u32));
+            }
+            move_lamports(target, destination, target.lamports())?;
+            target.assign(&system_program::ID);
+            target.resize(0)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Token Metadata: `CreateMetadataAccountV3` with the program's validation
+///
+/// The account stores the update authority, mint and instruction arguments
+/// rather than Token Metadata's real layout; see `ScenarioBuilder::ticket_metadata`.
+fn invoke_metadata(
+    data: &[u8],
+    infos: &[&AccountInfo],
+    signed: impl Fn(&AccountInfo) -> bool,
+) -> TxResult {
+    let (account, mint, mint_authority, payer, update_authority) = (infos[0], infos[1], infos[2], infos[3], infos[4]);
+    let args = match data.split_first() {
+        Some((33, args)) => metadata::CreateMetadataAccountArgsV3::try_from_slice(args)
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+    if *account.key != metadata::metadata_address(mint.key) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if account.lamports() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    let mint_state = unpack_base::<spl_token::state::Mint>(&mint.try_borrow_data()?)?;
+    if mint_state.mint_authority != COption::Some(*mint_authority.key) || !signed(mint_authority) || !signed(payer) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let fields = &args.data;
+    if fields.name.len() > metadata::MAX_NAME_LENGTH
+        || fields.symbol.len() > metadata::MAX_SYMBOL_LENGTH
+        || fields.uri.len() > metadata::MAX_URI_LENGTH
+        || fields.seller_fee_basis_points > 10_000
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if let Some(creators) = &fields.creators {
+        if creators.iter().map(|creator| creator.share as u16).sum::<u16>() != 100 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let verified_by_signer = |creator: &metadata::Creator| {
+            creator.address == *update_authority.key && signed(update_authority)
+        };
+        if creators.iter().any(|creator| creator.verified && !verified_by_signer(creator)) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    let mut account_data = update_authority.key.to_bytes().to_vec();
+    account_data.extend_from_slice(mint.key.as_ref());
+    args.serialize(&mut account_data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    move_lamports(payer, account, rent_exempt(account_data.len()))?;
+    account.resize(account_data.len())?;
+    account.assign(&metadata::ID);
+    account.try_borrow_mut_data()?.copy_from_slice(&account_data);
+    Ok(())
+}
+
+/// Token-2022 state holding `base` (an SPL Token mint or account, which
+/// share the layout) followed by one extension: a mint close authority for
+/// mints, the immutable owner for token accounts
+fn token_2022_data<S: Token2022State>(base: S::Base) -> Vec<u8> {
+    let mut base_data = vec![0; S::Base::LEN];
+    pack_base(base, &mut base_data).unwrap();
+
+    let mut data = vec![0; ExtensionType::try_calculate_account_len::<S>(&[S::EXTENSION]).unwrap()];
+    let mut state = StateWithExtensionsMut::<S>::unpack_uninitialized(&mut data).unwrap();
+    state.base = S::unpack_from_slice(&base_data).unwrap();
+    state.pack_base();
+    S::init_extension(&mut state);
+    state.init_account_type().unwrap();
+    data
+}
+
+trait Token2022State: spl_token_2022::extension::BaseState + Pack {
+    type Base: Pack + IsInitialized;
+    const EXTENSION: ExtensionType;
+    fn init_extension(state: &mut StateWithExtensionsMut<Self>);
+}
+
+impl Token2022State for spl_token_2022::state::Mint {
+    type Base = spl_token::state::Mint;
+    const EXTENSION: ExtensionType = ExtensionType::MintCloseAuthority;
+    fn init_extension(state: &mut StateWithExtensionsMut<Self>) {
+        state.init_extension::<MintCloseAuthority>(true).unwrap();
+    }
+}
+
+impl Token2022State for spl_token_2022::state::Account {
+    type Base = spl_token::state::Account;
+    const EXTENSION: ExtensionType = ExtensionType::ImmutableOwner;
+    fn init_extension(state: &mut StateWithExtensionsMut<Self>) {
+        state.init_extension::<ImmutableOwner>(true).unwrap();
+    }
+}
+
+/// In-memory ledger the program runs against
+pub struct ScenarioBuilder {
+    accounts: HashMap<Pubkey, TestAccount>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl ScenarioBuilder {
+    pub fn new() -> Self {
+        let lock = SCENARIO_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        set_syscall_stubs(Box::new(ScenarioStubs));
+        UNIX_TIMESTAMP.store(GENESIS_TS, Ordering::SeqCst);
+
+        let mut scenario = Self {
+            accounts: HashMap::new(),
+            _lock: lock,
+        };
+        for program in [
+            mythra_program::ID,
+            system_program::ID,
+            spl_token::ID,
+            spl_token_2022::ID,
+            anchor_spl::associated_token::ID,
+            metadata::ID,
+        ] {
+            scenario.accounts.insert(program, TestAccount {
+                lamports: 1,
+                data: Vec::new(),
+                owner: anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+                executable: true,
+            });
+        }
+
+        // UpgradeableLoaderState::Program pointing at the ProgramData account
+        let program = scenario.accounts.get_mut(&mythra_program::ID).unwrap();
+        program.data = [&2u32.to_le_bytes()[..], pda::program_data().as_ref()].concat();
+        scenario.set_upgrade_authority(None);
+        scenario
+    }
+
+    /// Rewrite the program's ProgramData with `authority` as its upgrade authority
+    pub fn set_upgrade_authority(&mut self, authority: Option<&Pubkey>) {
+        // UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address }
+        let mut data = [&3u32.to_le_bytes()[..], &0u64.to_le_bytes()[..]].concat();
+        match authority {
+            Some(authority) => data.extend([&[1u8][..], authority.as_ref()].concat()),
+            None => data.push(0),
+        }
+        self.accounts.insert(pda::program_data(), TestAccount {
+            lamports: rent_exempt(data.len()),
+            data,
+            owner: anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+            executable: false,
+        });
+    }
+
+    // ---- Clock ----
+
+    pub fn now(&self) -> i64 {
+        UNIX_TIMESTAMP.load(Ordering::SeqCst)
+    }
+
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        UNIX_TIMESTAMP.store(unix_timestamp, Ordering::SeqCst);
+    }
+
+    pub fn warp_by(&mut self, seconds: i64) {
+        UNIX_TIMESTAMP.fetch_add(seconds, Ordering::SeqCst);
+    }
+
+    // ---- Accounts ----
+
+    /// New system wallet holding `lamports`
+    pub fn wallet(&mut self, lamports: u64) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.airdrop(&key, lamports);
+        key
+    }
+
+    pub fn airdrop(&mut self, key: &Pubkey, lamports: u64) {
+        let account = self.accounts.entry(*key).or_insert_with(|| TestAccount {
+            owner: system_program::ID,
+            ..TestAccount::default()
+        });
+        account.lamports += lamports;
+    }
+
+    /// Move `lamports` between wallets, like a plain system transfer
+    pub fn transfer_lamports(&mut self, from: &Pubkey, to: &Pubkey, lamports: u64) {
+        self.drain(from, lamports);
+        self.airdrop(to, lamports);
+    }
+
+    /// Take `lamports` out of an account, e.g. to leave an escrow short
+    pub fn drain(&mut self, key: &Pubkey, lamports: u64) {
+        let account = self.accounts.get_mut(key).expect("account to drain");
+        account.lamports -= lamports;
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |account| account.lamports)
+    }
+
+    pub fn exists(&self, key: &Pubkey) -> bool {
+        self.lamports(key) > 0
+    }
+
+    /// Deserialize a program account, panicking if it is missing
+    pub fn state<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self.accounts.get(key).unwrap_or_else(|| panic!("account {key} missing"));
+        assert_eq!(account.owner, mythra_program::ID, "account {key} not owned by the program");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Overwrite a program account's state in place, e.g. to stage changes
+    /// no instruction can make yet
+    pub fn set_state<T: AccountSerialize>(&mut self, key: &Pubkey, state: &T) {
+        let account = self.accounts.get_mut(key).unwrap_or_else(|| panic!("account {key} missing"));
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+    }
+
+    pub fn token_amount(&self, token_account: &Pubkey) -> u64 {
+        let account = &self.accounts[token_account];
+        unpack_base::<spl_token::state::Account>(&account.data).unwrap().amount
+    }
+
+    /// Update authority and fields of a mint's metadata, if it has any
+    pub fn ticket_metadata(&self, mint: &Pubkey) -> Option<(Pubkey, metadata::DataV2)> {
+        let account = self.accounts.get(&metadata::metadata_address(mint))?;
+        assert_eq!(account.owner, metadata::ID);
+        let update_authority = Pubkey::try_from(&account.data[..32]).unwrap();
+        let args = metadata::CreateMetadataAccountArgsV3::try_from_slice(&account.data[64..]).unwrap();
+        Some((update_authority, args.data))
+    }
+
+    pub fn token_frozen(&self, token_account: &Pubkey) -> bool {
+        let account = &self.accounts[token_account];
+        unpack_base::<spl_token::state::Account>(&account.data).unwrap().is_frozen()
+    }
+
+    /// Delegate approved over a token account and how many tokens it may move
+    pub fn token_delegate(&self, token_account: &Pubkey) -> (Option<Pubkey>, u64) {
+        let account = unpack_base::<spl_token::state::Account>(&self.accounts[token_account].data).unwrap();
+        (account.delegate.into(), account.delegated_amount)
+    }
+
+    /// Mint a ticket NFT (supply 1) into a fresh token account for `owner`
+    ///
+    /// Returns (mint, token account). Tickets are minted outside the program,
+    /// so the fixture writes the token state directly.
+    pub fn mint_nft(&mut self, owner: &Pubkey) -> (Pubkey, Pubkey) {
+        self.mint_nft_with_freeze_authority(owner, None)
+    }
+
+    /// Mint a ticket NFT whose mint has `freeze_authority` set
+    pub fn mint_nft_with_freeze_authority(
+        &mut self,
+        owner: &Pubkey,
+        freeze_authority: Option<Pubkey>,
+    ) -> (Pubkey, Pubkey) {
+        self.mint_nft_for_program(owner, freeze_authority, spl_token::ID)
+    }
+
+    /// Mint a ticket NFT under `token_program` (SPL Token or Token-2022)
+    ///
+    /// Token-2022 mints carry a mint close authority extension and their
+    /// token accounts the immutable owner extension, so both are larger than
+    /// the SPL Token layouts.
+    pub fn mint_nft_for_program(
+        &mut self,
+        owner: &Pubkey,
+        freeze_authority: Option<Pubkey>,
+        token_program: Pubkey,
+    ) -> (Pubkey, Pubkey) {
+        let (mint, token_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let base = spl_token::state::Mint {
+            mint_authority: COption::Some(*owner),
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: freeze_authority.into(),
+        };
+        let mint_data = if token_program == spl_token_2022::ID {
+            token_2022_data::<spl_token_2022::state::Mint>(base)
+        } else {
+            let mut mint_data = vec![0; spl_token::state::Mint::LEN];
+            spl_token::state::Mint::pack(base, &mut mint_data).unwrap();
+            mint_data
+        };
+        self.accounts.insert(mint, TestAccount {
+            lamports: rent_exempt(mint_data.len()),
+            data: mint_data,
+            owner: token_program,
+            executable: false,
+        });
+
+        self.insert_token_account(token_account, mint, owner, 1);
+
+        (mint, token_account)
+    }
+
+    pub fn account_len(&self, key: &Pubkey) -> usize {
+        self.accounts[key].data.len()
+    }
+
+    /// Token program owning `mint`
+    pub fn token_program(&self, mint: &Pubkey) -> Pubkey {
+        self.accounts[mint].owner
+    }
+
+    /// Create an empty token account for `owner` holding `mint`
+    pub fn token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let token_account = Pubkey::new_unique();
+        self.insert_token_account(token_account, *mint, owner, 0);
+        token_account
+    }
+
+    fn insert_token_account(&mut self, token_account: Pubkey, mint: Pubkey, owner: &Pubkey, amount: u64) {
+        let token_program = self.token_program(&mint);
+        let base = spl_token::state::Account {
+            mint,
+            owner: *owner,
+            amount,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+        let account_data = if token_program == spl_token_2022::ID {
+            token_2022_data::<spl_token_2022::state::Account>(base)
+        } else {
+            let mut account_data = vec![0; spl_token::state::Account::LEN];
+            spl_token::state::Account::pack(base, &mut account_data).unwrap();
+            account_data
+        };
+        self.accounts.insert(token_account, TestAccount {
+            lamports: rent_exempt(account_data.len()),
+            data: account_data,
+            owner: token_program,
+            executable: false,
+        });
+    }
+
+    // ---- Execution ----
+
+    /// Run one instruction atomically
+    ///
+    /// On error nothing is written back. On success every account must be
+    /// rent-exempt (or empty) and the total lamports must be unchanged.
+    pub fn process(
+        &mut self,
+        accounts: impl ToAccountMetas,
+        data: impl InstructionData,
+    ) -> TxResult {
+        let metas = accounts.to_account_metas(None);
+        let data = data.data();
+
+        // Merge duplicate keys the way the runtime does
+        let mut keys: Vec<(Pubkey, bool, bool)> = Vec::new();
+        let mut positions = Vec::with_capacity(metas.len());
+        for meta in &metas {
+            match keys.iter().position(|(key, _, _)| *key == meta.pubkey) {
+                Some(index) => {
+                    keys[index].1 |= meta.is_signer;
+                    keys[index].2 |= meta.is_writable;
+                    positions.push(index);
+                }
+                None => {
+                    positions.push(keys.len());
+                    keys.push((meta.pubkey, meta.is_signer, meta.is_writable));
+                }
+            }
+        }
+
+        let lamports_before: u128 = keys.iter().map(|(key, _, _)| self.lamports(key) as u128).sum();
+        let mut input = self.serialize(&keys, &positions, &data);
+
+        let result = {
+            let (program_id, infos, instruction_data) = unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+            let result = mythra_program::entry(program_id, &infos, instruction_data);
+
+            let mut written = Vec::new();
+            if result.is_ok() {
+                for (info, (_, _, is_writable)) in unique_infos(&infos, &positions).zip(&keys) {
+                    let account = TestAccount {
+                        lamports: info.lamports(),
+                        data: info.data.borrow().to_vec(),
+                        owner: *info.owner,
+                        executable: info.executable,
+                    };
+                    let before = self.accounts.get(info.key).cloned().unwrap_or_default();
+                    if !is_writable
+                        && (account.lamports != before.lamports || account.data != before.data)
+                    {
+                        panic!("read-only account {} was modified", info.key);
+                    }
+                    written.push((*info.key, before, account));
+                }
+            }
+            result.map(|_| written)
+        };
+
+        let written = result?;
+        for (key, before, account) in &written {
+            let changed = before.lamports != account.lamports || before.data.len() != account.data.len();
+            if changed && account.lamports > 0 && account.lamports < rent_exempt(account.data.len()) {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+            let _ = key;
+        }
+        let lamports_after: u128 = written.iter().map(|(_, _, account)| account.lamports as u128).sum();
+        assert_eq!(lamports_before, lamports_after, "instruction created or destroyed lamports");
+
+        for (key, _, account) in written {
+            if account.lamports == 0 {
+                self.accounts.remove(&key);
+            } else {
+                self.accounts.insert(key, account);
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the runtime input buffer (8-byte aligned, as the loader does)
+    fn serialize(&self, keys: &[(Pubkey, bool, bool)], positions: &[usize], data: &[u8]) -> Vec<u64> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(positions.len() as u64).to_le_bytes());
+
+        // Duplicates point back at the position of the key's first occurrence
+        let mut first_position: Vec<Option<usize>> = vec![None; keys.len()];
+        for (position, &index) in positions.iter().enumerate() {
+            if let Some(first) = first_position[index] {
+                bytes.push(first as u8);
+                bytes.extend_from_slice(&[0; 7]);
+                continue;
+            }
+            first_position[index] = Some(position);
+
+            let (key, is_signer, is_writable) = keys[index];
+            let account = self.accounts.get(&key).cloned().unwrap_or(TestAccount {
+                owner: system_program::ID,
+                ..TestAccount::default()
+            });
+            bytes.push(u8::MAX);
+            bytes.push(is_signer as u8);
+            bytes.push(is_writable as u8);
+            bytes.push(account.executable as u8);
+            bytes.extend_from_slice(&[0; 4]);
+            bytes.extend_from_slice(key.as_ref());
+            bytes.extend_from_slice(account.owner.as_ref());
+            bytes.extend_from_slice(&account.lamports.to_le_bytes());
+            bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&account.data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            bytes.resize(bytes.len().next_multiple_of(8), 0);
+            bytes.extend_from_slice(&u64::MAX.to_le_bytes()); // rent epoch
+        }
+
+        bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(mythra_program::ID.as_ref());
+
+        // Copy into u64 storage so the buffer is aligned for the deserializer
+        let mut input = vec![0u64; bytes.len().div_ceil(8)];
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), input.as_mut_ptr() as *mut u8, bytes.len());
+        }
+        input
+    }
+
+    // ---- Fixtures ----
+
+    /// Initialize the platform config with default shares and fees, with
+    /// `admin` as the program's upgrade authority
+    pub fn initialize_config(&mut self, admin: &Pubkey, platform_treasury: &Pubkey) {
+        self.set_upgrade_authority(Some(admin));
+        self.process(
+            mythra_program::accounts::InitializeConfig {
+                platform_config: pda::config(),
+                admin: *admin,
+                system_program: system_program::ID,
+                program: mythra_program::ID,
+                program_data: pda::program_data(),
+            },
+            mythra_program::instruction::InitializeConfig {
+                min_platform_share_bps: 500,
+                min_backer_share_bps: 5_000,
+                platform_treasury: *platform_treasury,
+                default_platform_fee_bps: 500,
+            },
+        ).expect("initialize_config");
+    }
+
+    /// Create an event starting in 30 days and ending a day later
+    pub fn create_event(&mut self, organizer: &Pubkey, event_id: &str, total_supply: u32) -> Pubkey {
+        let start_ts = self.now() + 30 * DAY;
+        self.create_event_at(organizer, event_id, start_ts, start_ts + DAY, total_supply, 0)
+    }
+
+    pub fn create_event_at(
+        &mut self,
+        organizer: &Pubkey,
+        event_id: &str,
+        start_ts: i64,
+        end_ts: i64,
+        total_supply: u32,
+        platform_split_bps: u16,
+    ) -> Pubkey {
+        let event = pda::event(organizer, event_id);
+        self.process(
+            mythra_program::accounts::CreateEvent {
+                event,
+                organizer: *organizer,
+                treasury: *organizer,
+                platform_stats: None,
+                platform_config: pda::config(),
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::CreateEvent {
+                event_id: event_id.to_string(),
+                metadata_uri: format!("https://example.com/{event_id}.json"),
+                start_ts,
+                end_ts,
+                total_supply,
+                platform_split_bps: Some(platform_split_bps),
+            },
+        ).expect("create_event");
+        event
+    }
+
+    /// Create an open tier (no sale window, no wallet limit)
+    pub fn create_tier(
+        &mut self,
+        organizer: &Pubkey,
+        event: &Pubkey,
+        tier_id: &str,
+        price_lamports: u64,
+        max_supply: u32,
+    ) -> Pubkey {
+        self.create_tier_with_limit(organizer, event, tier_id, price_lamports, max_supply, 0)
+            .expect("create_ticket_tier")
+    }
+
+    /// Create a tier with a per-wallet purchase limit (0 = unlimited)
+    pub fn create_tier_with_limit(
+        &mut self,
+        organizer: &Pubkey,
+        event: &Pubkey,
+        tier_id: &str,
+        price_lamports: u64,
+        max_supply: u32,
+        max_per_wallet: u8,
+    ) -> TxResult<Pubkey> {
+        let tier = pda::tier(event, tier_id);
+        self.process(
+            mythra_program::accounts::CreateTicketTier {
+                tier,
+                event: *event,
+                event_delegate: None,
+                authority: *organizer,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::CreateTicketTier {
+                tier_id: tier_id.to_string(),
+                params: CreateTicketTierParams {
+                    metadata_uri: format!("https://example.com/{tier_id}.json"),
+                    price_lamports,
+                    max_supply,
+                    royalty_bps: 0,
+                    tier_index: 0,
+                    resale_enabled: true,
+                    max_per_wallet,
+                    sale_start_ts: 0,
+                    sale_end_ts: 0,
+                    max_resale_price_lamports: 0,
+                    transferable: true,
+                },
+            },
+        )?;
+        Ok(tier)
+    }
+
+    /// Pay for a ticket order that the organizer fulfills later
+    pub fn create_order(&mut self, buyer: &Pubkey, event: &Pubkey, tier: &Pubkey, order_id: &str) -> TxResult<Pubkey> {
+        let order = pda::order(event, order_id);
+        self.process(
+            mythra_program::accounts::CreateOrder {
+                order,
+                event: *event,
+                tier: *tier,
+                buyer_record: pda::buyer_record(tier, buyer),
+                ticket_escrow: pda::ticket_escrow(event),
+                platform_config: pda::config(),
+                buyer: *buyer,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::CreateOrder { order_id: order_id.to_string() },
+        )?;
+        Ok(order)
+    }
+
+    /// Withdraw from the event's ticket escrow to the organizer's treasury
+    pub fn withdraw_funds(&mut self, organizer: &Pubkey, event: &Pubkey, data: impl InstructionData) -> TxResult {
+        let campaign = self.state::<mythra_program::state::Event>(event).campaign;
+        self.process(
+            mythra_program::accounts::WithdrawFunds {
+                event: *event,
+                ticket_escrow: pda::ticket_escrow(event),
+                treasury: *organizer,
+                platform_fees: pda::platform_fees(event),
+                campaign,
+                authority: *organizer,
+                system_program: system_program::ID,
+            },
+            data,
+        )
+    }
+
+    /// Buy a ticket with a freshly minted NFT, returning the ticket accounts
+    pub fn purchase_ticket(
+        &mut self,
+        buyer: &Pubkey,
+        event: &Pubkey,
+        tier: &Pubkey,
+    ) -> TxResult<TicketKeys> {
+        self.purchase_ticket_with_proof(buyer, event, tier, Vec::new())
+    }
+
+    /// Buy a ticket and create its Token Metadata in the same instruction
+    pub fn purchase_ticket_with_metadata(
+        &mut self,
+        buyer: &Pubkey,
+        event: &Pubkey,
+        tier: &Pubkey,
+    ) -> TxResult<TicketKeys> {
+        self.purchase(buyer, event, tier, Vec::new(), true, spl_token::ID)
+    }
+
+    /// Buy a ticket whose NFT is minted under `token_program`
+    pub fn purchase_ticket_with_token_program(
+        &mut self,
+        buyer: &Pubkey,
+        event: &Pubkey,
+        tier: &Pubkey,
+        token_program: Pubkey,
+    ) -> TxResult<TicketKeys> {
+        self.purchase(buyer, event, tier, Vec::new(), false, token_program)
+    }
+
+    /// Buy a ticket during a tier's allowlist presale
+    ///
+    /// Soulbound tiers get a mint whose freeze authority is the program's
+    /// ticket freeze PDA, as their buyers' wallets would create. Seated
+    /// tiers get the next seat's assignment account.
+    pub fn purchase_ticket_with_proof(
+        &mut self,
+        buyer: &Pubkey,
+        event: &Pubkey,
+        tier: &Pubkey,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> TxResult<TicketKeys> {
+        self.purchase(buyer, event, tier, allowlist_proof, false, spl_token::ID)
+    }
+
+    fn purchase(
+        &mut self,
+        buyer: &Pubkey,
+        event: &Pubkey,
+        tier: &Pubkey,
+        allowlist_proof: Vec<[u8; 32]>,
+        with_metadata: bool,
+        token_program: Pubkey,
+    ) -> TxResult<TicketKeys> {
+        let tier_state = self.state::<mythra_program::state::TicketTier>(tier);
+        let freeze_authority = (!tier_state.transferable).then(pda::ticket_freeze);
+        let seat_assignment = tier_state.seated.then(|| pda::seat(tier, tier_state.next_seat + 1));
+        let (mint, token_account) = self.mint_nft_for_program(buyer, freeze_authority, token_program);
+        let keys = TicketKeys {
+            ticket: pda::ticket(&mint),
+            mint,
+            token_account,
+        };
+        self.process(
+            mythra_program::accounts::PurchaseTicket {
+                ticket: keys.ticket,
+                event: *event,
+                tier: *tier,
+                buyer_record: pda::buyer_record(tier, buyer),
+                ticket_index: None,
+                seat_assignment,
+                backer_campaign: None,
+                backer_contribution: None,
+                mint,
+                buyer_token_account: token_account,
+                freeze_authority: Some(pda::ticket_freeze()),
+                metadata: with_metadata.then(|| metadata::metadata_address(&mint)),
+                metadata_authority: with_metadata.then(|| pda::metadata_authority(event)),
+                token_metadata_program: with_metadata.then_some(metadata::ID),
+                ticket_escrow: pda::ticket_escrow(event),
+                platform_config: pda::config(),
+                platform_stats: None,
+                blocklist_entry: pda::blocklist(event, buyer),
+                buyer: *buyer,
+                system_program: system_program::ID,
+                token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+            },
+            mythra_program::instruction::PurchaseTicket { allowlist_proof, with_metadata },
+        )?;
+        Ok(keys)
+    }
+
+    /// Transfer a ticket to `recipient`, returning the recipient's token account
+    ///
+    /// `sale_price` is the price the sender reports; royalties (if any) are
+    /// paid by the sender.
+    pub fn transfer_ticket(
+        &mut self,
+        sender: &Pubkey,
+        recipient: &Pubkey,
+        event: &Pubkey,
+        tier: &Pubkey,
+        keys: &TicketKeys,
+        sale_price: Option<u64>,
+    ) -> TxResult<Pubkey> {
+        let recipient_token_account = self.token_account(&keys.mint, recipient);
+        let treasury = self.state::<mythra_program::state::Event>(event).treasury;
+        self.process(
+            mythra_program::accounts::TransferTicket {
+                ticket: keys.ticket,
+                event: *event,
+                tier: *tier,
+                mint: keys.mint,
+                sender_token_account: keys.token_account,
+                recipient_token_account,
+                sender: *sender,
+                recipient: *recipient,
+                blocklist_entry: pda::blocklist(event, recipient),
+                sender_ticket_index: None,
+                recipient_ticket_index: None,
+                // Only checked when a platform royalty is paid; Anchor rejects
+                // the same key in two mutable slots
+                platform_treasury: Pubkey::new_unique(),
+                organizer_treasury: treasury,
+                platform_config: pda::config(),
+                system_program: system_program::ID,
+                token_program: self.token_program(&keys.mint),
+            },
+            mythra_program::instruction::TransferTicket { sale_price },
+        )?;
+        Ok(recipient_token_account)
+    }
+
+    /// Gift a ticket to `recipient`, returning the recipient's token account
+    pub fn gift_ticket(
+        &mut self,
+        sender: &Pubkey,
+        recipient: &Pubkey,
+        event: &Pubkey,
+        tier: &Pubkey,
+        keys: &TicketKeys,
+    ) -> TxResult<Pubkey> {
+        let recipient_token_account = self.token_account(&keys.mint, recipient);
+        self.process(
+            mythra_program::accounts::GiftTicket {
+                ticket: keys.ticket,
+                event: *event,
+                tier: *tier,
+                mint: keys.mint,
+                sender_token_account: keys.token_account,
+                recipient_token_account,
+                sender: *sender,
+                recipient: *recipient,
+                blocklist_entry: pda::blocklist(event, recipient),
+                sender_ticket_index: None,
+                recipient_ticket_index: None,
+                platform_config: pda::config(),
+                system_program: system_program::ID,
+                token_program: self.token_program(&keys.mint),
+            },
+            mythra_program::instruction::GiftTicket {},
+        )?;
+        Ok(recipient_token_account)
+    }
+
+    /// Move a ticket into escrow, claimable with the preimage of `claim_hash`
+    pub fn initiate_pending_transfer(
+        &mut self,
+        sender: &Pubkey,
+        event: &Pubkey,
+        tier: &Pubkey,
+        keys: &TicketKeys,
+        claim_hash: [u8; 32],
+        expires_at: i64,
+    ) -> TxResult {
+        self.process(
+            mythra_program::accounts::InitiatePendingTransfer {
+                ticket: keys.ticket,
+                event: *event,
+                tier: *tier,
+                mint: keys.mint,
+                sender_token_account: keys.token_account,
+                pending_transfer: pda::pending_transfer(&keys.ticket),
+                escrow_token_account: pda::pending_transfer_escrow(&keys.ticket),
+                sender: *sender,
+                platform_config: pda::config(),
+                system_program: system_program::ID,
+                token_program: self.token_program(&keys.mint),
+            },
+            mythra_program::instruction::InitiatePendingTransfer { claim_hash, expires_at },
+        )
+    }
+
+    /// Claim a pending transfer, returning the claimer's token account
+    pub fn claim_pending_transfer(
+        &mut self,
+        claimer: &Pubkey,
+        sender: &Pubkey,
+        event: &Pubkey,
+        keys: &TicketKeys,
+        preimage: &[u8],
+    ) -> TxResult<Pubkey> {
+        let claimer_token_account = self.token_account(&keys.mint, claimer);
+        self.process(
+            mythra_program::accounts::ClaimPendingTransfer {
+                ticket: keys.ticket,
+                event: *event,
+                mint: keys.mint,
+                pending_transfer: pda::pending_transfer(&keys.ticket),
+                escrow_token_account: pda::pending_transfer_escrow(&keys.ticket),
+                claimer_token_account,
+                claimer: *claimer,
+                sender: *sender,
+                blocklist_entry: pda::blocklist(event, claimer),
+                sender_ticket_index: None,
+                claimer_ticket_index: None,
+                platform_config: pda::config(),
+                system_program: system_program::ID,
+                token_program: self.token_program(&keys.mint),
+            },
+            mythra_program::instruction::ClaimPendingTransfer { preimage: preimage.to_vec() },
+        )?;
+        Ok(claimer_token_account)
+    }
+
+    /// Return an expired pending transfer's ticket to the sender
+    pub fn cancel_pending_transfer(&mut self, sender: &Pubkey, keys: &TicketKeys) -> TxResult {
+        self.process(
+            mythra_program::accounts::CancelPendingTransfer {
+                ticket: keys.ticket,
+                mint: keys.mint,
+                pending_transfer: pda::pending_transfer(&keys.ticket),
+                escrow_token_account: pda::pending_transfer_escrow(&keys.ticket),
+                sender_token_account: keys.token_account,
+                sender: *sender,
+                token_program: self.token_program(&keys.mint),
+            },
+            mythra_program::instruction::CancelPendingTransfer {},
+        )
+    }
+
+    /// Create a SOL campaign with the default 60/35/5 split
+    pub fn create_campaign(
+        &mut self,
+        organizer: &Pubkey,
+        event: &Pubkey,
+        funding_goal: u64,
+        deadline: i64,
+    ) -> Pubkey {
+        let campaign = pda::campaign(event);
+        self.process(
+            mythra_program::accounts::CreateCampaign {
+                event: *event,
+                campaign,
+                campaign_escrow: pda::campaign_escrow(&campaign),
+                organizer: *organizer,
+                authority: *organizer,
+                platform_config: pda::config(),
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::CreateCampaign {
+                funding_goal,
+                deadline,
+                backer_share_bps: 6_000,
+                organizer_share_bps: 3_500,
+                platform_share_bps: 500,
+                denomination: Denomination::Lamports,
+            },
+        ).expect("create_campaign");
+        campaign
+    }
+
+    pub fn contribute(
+        &mut self,
+        contributor: &Pubkey,
+        campaign: &Pubkey,
+        amount: u64,
+    ) -> TxResult<Pubkey> {
+        let contribution = pda::contribution(campaign, contributor);
+        self.process(
+            mythra_program::accounts::Contribute {
+                campaign: *campaign,
+                contribution,
+                campaign_escrow: pda::campaign_escrow(campaign),
+                platform_config: pda::config(),
+                contributor: *contributor,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::Contribute { amount },
+        )?;
+        Ok(contribution)
+    }
+
+    pub fn finalize_campaign(&mut self, campaign: &Pubkey) -> TxResult {
+        self.process(
+            mythra_program::accounts::FinalizeCampaign {
+                campaign: *campaign,
+                platform_stats: None,
+            },
+            mythra_program::instruction::FinalizeCampaign {},
+        )
+    }
+
+    /// Submit a budget whose milestones unlock at the given times
+    pub fn submit_budget(
+        &mut self,
+        organizer: &Pubkey,
+        event: &Pubkey,
+        campaign: &Pubkey,
+        total_amount: u64,
+        milestones: &[(u16, i64)],
+        voting_period_seconds: i64,
+    ) -> TxResult<Pubkey> {
+        self.submit_budget_with_options(
+            organizer,
+            event,
+            campaign,
+            total_amount,
+            milestones,
+            voting_period_seconds,
+            false,
+            VotingModel::Linear,
+        )
+    }
+
+    /// Submit a budget with per-milestone release votes and/or a voting model
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_budget_with_options(
+        &mut self,
+        organizer: &Pubkey,
+        event: &Pubkey,
+        campaign: &Pubkey,
+        total_amount: u64,
+        milestones: &[(u16, i64)],
+        voting_period_seconds: i64,
+        per_milestone_approval: bool,
+        voting_model: VotingModel,
+    ) -> TxResult<Pubkey> {
+        let budget = pda::budget(campaign);
+        self.process(
+            mythra_program::accounts::SubmitBudget {
+                campaign: *campaign,
+                event: *event,
+                budget,
+                platform_config: pda::config(),
+                organizer: *organizer,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::SubmitBudget {
+                total_amount,
+                description: "Production budget".to_string(),
+                milestones: milestone_inputs(milestones),
+                voting_period_seconds,
+                per_milestone_approval,
+                voting_model,
+            },
+        )?;
+        Ok(budget)
+    }
+
+    pub fn vote_on_budget(
+        &mut self,
+        voter: &Pubkey,
+        campaign: &Pubkey,
+        choice: VoteChoice,
+    ) -> TxResult<Pubkey> {
+        self.vote_on_budget_at(voter, campaign, &pda::budget(campaign), choice)
+    }
+
+    /// Vote on a specific budget account, e.g. a revision
+    pub fn vote_on_budget_at(
+        &mut self,
+        voter: &Pubkey,
+        campaign: &Pubkey,
+        budget: &Pubkey,
+        choice: VoteChoice,
+    ) -> TxResult<Pubkey> {
+        let budget = *budget;
+        let revision = self.state::<Budget>(&budget).revision_count;
+        let vote = pda::budget_vote(&budget, revision, voter);
+        self.process(
+            mythra_program::accounts::VoteOnBudget {
+                budget,
+                campaign: *campaign,
+                contribution: pda::contribution(campaign, voter),
+                vote,
+                voter: *voter,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::VoteOnBudget { choice },
+        )?;
+        Ok(vote)
+    }
+
+    /// Budget the campaign currently releases milestones from
+    pub fn active_budget(&self, campaign: &Pubkey) -> Pubkey {
+        self.state::<Campaign>(campaign).active_budget
+    }
+
+    pub fn open_milestone_vote(
+        &mut self,
+        organizer: &Pubkey,
+        campaign: &Pubkey,
+        milestone_index: u8,
+        voting_period_seconds: i64,
+    ) -> TxResult {
+        self.process(
+            mythra_program::accounts::OpenMilestoneVote {
+                campaign: *campaign,
+                budget: self.active_budget(campaign),
+                organizer: *organizer,
+            },
+            mythra_program::instruction::OpenMilestoneVote { milestone_index, voting_period_seconds },
+        )
+    }
+
+    pub fn vote_on_milestone(
+        &mut self,
+        voter: &Pubkey,
+        campaign: &Pubkey,
+        milestone_index: u8,
+        approve: bool,
+    ) -> TxResult<Pubkey> {
+        let budget = self.active_budget(campaign);
+        let vote = pda::milestone_vote(&budget, milestone_index, voter);
+        self.process(
+            mythra_program::accounts::VoteOnMilestone {
+                budget,
+                campaign: *campaign,
+                contribution: pda::contribution(campaign, voter),
+                vote,
+                voter: *voter,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::VoteOnMilestone { milestone_index, approve },
+        )?;
+        Ok(vote)
+    }
+
+    pub fn finalize_milestone_vote(&mut self, campaign: &Pubkey, milestone_index: u8) -> TxResult {
+        self.process(
+            mythra_program::accounts::FinalizeMilestoneVote {
+                budget: self.active_budget(campaign),
+                campaign: *campaign,
+            },
+            mythra_program::instruction::FinalizeMilestoneVote { milestone_index },
+        )
+    }
+
+    pub fn finalize_budget_vote(&mut self, campaign: &Pubkey) -> TxResult {
+        self.finalize_budget_vote_at(campaign, &pda::budget(campaign))
+    }
+
+    pub fn finalize_budget_vote_at(&mut self, campaign: &Pubkey, budget: &Pubkey) -> TxResult {
+        self.process(
+            mythra_program::accounts::FinalizeBudgetVote {
+                budget: *budget,
+                campaign: *campaign,
+            },
+            mythra_program::instruction::FinalizeBudgetVote {},
+        )
+    }
+
+    /// Revise the campaign's active budget; returns the revision's address
+    pub fn revise_budget(
+        &mut self,
+        organizer: &Pubkey,
+        campaign: &Pubkey,
+        total_amount: u64,
+        milestones: &[(u16, i64)],
+        voting_period_seconds: i64,
+    ) -> TxResult<Pubkey> {
+        let old_budget = self.active_budget(campaign);
+        let revision = self.state::<Budget>(&old_budget).revision_count + 1;
+        let new_budget = pda::budget_revision(campaign, revision);
+        self.process(
+            mythra_program::accounts::ReviseBudget {
+                campaign: *campaign,
+                event: self.state::<Campaign>(campaign).event,
+                old_budget,
+                new_budget,
+                organizer: *organizer,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::ReviseBudget {
+                total_amount,
+                description: "Revised budget".to_string(),
+                milestones: milestone_inputs(milestones),
+                voting_period_seconds,
+            },
+        )?;
+        Ok(new_budget)
+    }
+
+    pub fn release_milestone(
+        &mut self,
+        organizer: &Pubkey,
+        event: &Pubkey,
+        campaign: &Pubkey,
+        milestone_index: u8,
+    ) -> TxResult {
+        self.process(
+            mythra_program::accounts::ReleaseMilestone {
+                event: *event,
+                campaign: *campaign,
+                budget: self.active_budget(campaign),
+                campaign_escrow: pda::campaign_escrow(campaign),
+                platform_config: pda::config(),
+                organizer: *organizer,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::ReleaseMilestone { milestone_index },
+        )
+    }
+}
+
+/// Accounts backing one purchased ticket
+#[derive(Clone, Copy, Debug)]
+pub struct TicketKeys {
+    pub ticket: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+}
+
+/// First occurrence of each account, in the order `keys` was built
+fn unique_infos<'a, 'info>(
+    infos: &'a [AccountInfo<'info>],
+    positions: &'a [usize],
+) -> impl Iterator<Item = &'a AccountInfo<'info>> {
+    let mut seen = Vec::new();
+    infos.iter().zip(positions).filter_map(move |(info, &index)| {
+        if seen.contains(&index) {
+            None
+        } else {
+            seen.push(index);
+            Some(info)
+        }
+    })
+}
@@ -0,0 +1,984 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use common::*;
+use mythra_program::errors::EventError;
+use mythra_program::state::{
+    Budget, BudgetStatus, BudgetVote, Campaign, CampaignStatus, Contribution, MilestoneApproval, MilestoneVote,
+    PlatformConfig, VoteChoice, VotingModel,
+};
+
+const GOAL: u64 = 10 * LAMPORTS_PER_SOL;
+const BUDGET: u64 = 4 * LAMPORTS_PER_SOL;
+const TICKET_PRICE: u64 = 3 * LAMPORTS_PER_SOL;
+
+fn calculate_distribution(scenario: &mut ScenarioBuilder, authority: &Pubkey, event: &Pubkey, campaign: &Pubkey) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::CalculateDistribution {
+            campaign: *campaign,
+            event: *event,
+            ticket_escrow: pda::ticket_escrow(event),
+            campaign_escrow: pda::campaign_escrow(campaign),
+            authority: *authority,
+            platform_config: pda::config(),
+            platform_stats: None,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::CalculateDistribution {},
+    )
+}
+
+fn claim_refund(scenario: &mut ScenarioBuilder, contributor: &Pubkey, campaign: &Pubkey) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::ClaimRefund {
+            campaign: *campaign,
+            contribution: pda::contribution(campaign, contributor),
+            campaign_escrow: pda::campaign_escrow(campaign),
+            contributor: *contributor,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::ClaimRefund {},
+    )
+}
+
+fn claim_backer_profit(scenario: &mut ScenarioBuilder, contributor: &Pubkey, campaign: &Pubkey) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::ClaimBackerProfit {
+            campaign: *campaign,
+            contribution: pda::contribution(campaign, contributor),
+            campaign_escrow: pda::campaign_escrow(campaign),
+            contributor: *contributor,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::ClaimBackerProfit {},
+    )
+}
+
+fn claim_organizer_profit(scenario: &mut ScenarioBuilder, organizer: &Pubkey, campaign: &Pubkey) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::ClaimOrganizerProfit {
+            campaign: *campaign,
+            campaign_escrow: pda::campaign_escrow(campaign),
+            organizer: *organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::ClaimOrganizerProfit {},
+    )
+}
+
+#[test]
+fn test_full_crowdfunding_lifecycle() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(10 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let (start_ts, end_ts) = {
+        let event_state: mythra_program::state::Event = scenario.state(&event);
+        (event_state.start_ts, event_state.end_ts)
+    };
+
+    // Campaign: the organizer funds the escrow's rent reserve up front
+    let organizer_before = scenario.lamports(&organizer);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    let escrow = pda::campaign_escrow(&campaign);
+    let reserve = rent_exempt(0);
+    assert_eq!(scenario.lamports(&escrow), reserve);
+    assert_eq!(
+        scenario.lamports(&organizer),
+        organizer_before - rent_exempt(Campaign::LEN) - reserve
+    );
+
+    // Contributions land in the escrow; backers pay their own record's rent
+    let contribution_rent = rent_exempt(Contribution::LEN);
+    scenario.contribute(&alice, &campaign, 6 * LAMPORTS_PER_SOL).unwrap();
+    assert_eq!(scenario.lamports(&alice), 4 * LAMPORTS_PER_SOL - contribution_rent);
+    assert_eq!(scenario.lamports(&escrow), reserve + 6 * LAMPORTS_PER_SOL);
+
+    // The goal isn't reached yet and the deadline hasn't passed
+    assert_eq!(
+        scenario.finalize_campaign(&campaign).unwrap_err(),
+        program_error(EventError::CampaignNotReadyToFinalize)
+    );
+
+    scenario.contribute(&bob, &campaign, 4 * LAMPORTS_PER_SOL).unwrap();
+    assert_eq!(scenario.lamports(&bob), 6 * LAMPORTS_PER_SOL - contribution_rent);
+    assert_eq!(scenario.lamports(&escrow), reserve + GOAL);
+
+    scenario.finalize_campaign(&campaign).unwrap();
+    assert_eq!(scenario.state::<Campaign>(&campaign).status, CampaignStatus::Funded);
+    assert_eq!(scenario.lamports(&escrow), reserve + GOAL);
+
+    // Budget: half unlocks right away, half at the event start
+    let now = scenario.now();
+    let budget = scenario.submit_budget(
+        &organizer,
+        &event,
+        &campaign,
+        BUDGET,
+        &[(5_000, now), (5_000, start_ts)],
+        3 * DAY,
+    ).unwrap();
+
+    // Nothing is released before backers approve
+    assert_eq!(
+        scenario.release_milestone(&organizer, &event, &campaign, 0).unwrap_err(),
+        program_error(EventError::BudgetNotApproved)
+    );
+
+    // Alice (60%) approves, Bob (40%) rejects; each pays their vote's rent
+    let vote_rent = rent_exempt(BudgetVote::LEN);
+    let (alice_before, bob_before) = (scenario.lamports(&alice), scenario.lamports(&bob));
+    scenario.vote_on_budget(&alice, &campaign, VoteChoice::Approve).unwrap();
+    scenario.vote_on_budget(&bob, &campaign, VoteChoice::Reject).unwrap();
+    assert_eq!(scenario.lamports(&alice), alice_before - vote_rent);
+    assert_eq!(scenario.lamports(&bob), bob_before - vote_rent);
+
+    assert_eq!(
+        scenario.finalize_budget_vote(&campaign).unwrap_err(),
+        program_error(EventError::VotingPeriodNotEnded)
+    );
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+
+    let budget_state: Budget = scenario.state(&budget);
+    assert_eq!(budget_state.status, BudgetStatus::Approved);
+    assert_eq!(budget_state.votes_for, 6 * LAMPORTS_PER_SOL);
+    assert_eq!(budget_state.votes_against, 4 * LAMPORTS_PER_SOL);
+
+    // First milestone pays the organizer out of the escrow
+    let organizer_before = scenario.lamports(&organizer);
+    scenario.release_milestone(&organizer, &event, &campaign, 0).unwrap();
+    assert_eq!(scenario.lamports(&organizer), organizer_before + BUDGET / 2);
+    assert_eq!(scenario.lamports(&escrow), reserve + GOAL - BUDGET / 2);
+
+    // Neither a repeat release nor an early one moves anything
+    assert!(scenario.release_milestone(&organizer, &event, &campaign, 0).is_err());
+    assert_eq!(
+        scenario.release_milestone(&organizer, &event, &campaign, 1).unwrap_err(),
+        program_error(EventError::MilestoneNotReady)
+    );
+    assert_eq!(scenario.lamports(&escrow), reserve + GOAL - BUDGET / 2);
+
+    // Ticket sales go to the event's ticket escrow, not the campaign escrow
+    let tier = scenario.create_tier(&organizer, &event, "vip", TICKET_PRICE, 10);
+    scenario.purchase_ticket(&carol, &event, &tier).unwrap();
+    scenario.purchase_ticket(&carol, &event, &tier).unwrap();
+    let ticket_escrow = pda::ticket_escrow(&event);
+    assert_eq!(scenario.lamports(&ticket_escrow), 2 * TICKET_PRICE);
+    assert_eq!(scenario.lamports(&escrow), reserve + GOAL - BUDGET / 2);
+
+    // Second milestone unlocks at the start
+    scenario.warp_to(start_ts);
+    let organizer_before = scenario.lamports(&organizer);
+    scenario.release_milestone(&organizer, &event, &campaign, 1).unwrap();
+    assert_eq!(scenario.lamports(&organizer), organizer_before + BUDGET / 2);
+    assert_eq!(scenario.lamports(&escrow), reserve + GOAL - BUDGET);
+
+    // Distribution waits for the event to end
+    assert_eq!(
+        calculate_distribution(&mut scenario, &organizer, &event, &campaign).unwrap_err(),
+        program_error(EventError::EventNotEnded)
+    );
+
+    // After the end and grace period, revenue above the ticket escrow's rent
+    // is swept into the campaign escrow and split 60/35/5
+    scenario.warp_to(end_ts + 30 * DAY);
+    calculate_distribution(&mut scenario, &organizer, &event, &campaign).unwrap();
+
+    let swept = 2 * TICKET_PRICE - rent_exempt(0);
+    assert_eq!(scenario.lamports(&ticket_escrow), rent_exempt(0));
+    assert_eq!(scenario.lamports(&escrow), reserve + GOAL - BUDGET + swept);
+
+    let campaign_state: Campaign = scenario.state(&campaign);
+    assert_eq!(campaign_state.status, CampaignStatus::Completed);
+    assert_eq!(campaign_state.total_revenue, swept);
+    assert_eq!(campaign_state.total_expenses, BUDGET);
+    let profit = swept - BUDGET;
+    assert_eq!(campaign_state.organizer_pool, profit * 3_500 / 10_000);
+    assert_eq!(campaign_state.platform_pool, profit * 500 / 10_000);
+    assert_eq!(
+        campaign_state.backer_pool,
+        profit - campaign_state.organizer_pool - campaign_state.platform_pool
+    );
+
+    // Backers claim pro rata to their contribution
+    let backer_pool = campaign_state.backer_pool;
+    let alice_share = (backer_pool as u128 * 6 / 10) as u64;
+    let bob_share = (backer_pool as u128 * 4 / 10) as u64;
+
+    let alice_before = scenario.lamports(&alice);
+    claim_backer_profit(&mut scenario, &alice, &campaign).unwrap();
+    assert_eq!(scenario.lamports(&alice), alice_before + alice_share);
+
+    let bob_before = scenario.lamports(&bob);
+    claim_backer_profit(&mut scenario, &bob, &campaign).unwrap();
+    assert_eq!(scenario.lamports(&bob), bob_before + bob_share);
+
+    assert_eq!(
+        claim_backer_profit(&mut scenario, &alice, &campaign).unwrap_err(),
+        program_error(EventError::ProfitAlreadyClaimed)
+    );
+
+    let organizer_before = scenario.lamports(&organizer);
+    claim_organizer_profit(&mut scenario, &organizer, &campaign).unwrap();
+    assert_eq!(scenario.lamports(&organizer), organizer_before + campaign_state.organizer_pool);
+
+    // Only the claimed shares left the escrow; the platform pool, rounding
+    // dust and unbudgeted contributions stay behind
+    assert_eq!(
+        scenario.lamports(&escrow),
+        reserve + GOAL - BUDGET + swept - alice_share - bob_share - campaign_state.organizer_pool
+    );
+}
+
+#[test]
+fn test_distribution_pays_profit_from_ticket_revenue() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(40 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "sellout", 100);
+    let end_ts = scenario.state::<mythra_program::state::Event>(&event).end_ts;
+
+    // Contribute and finalize
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    let escrow = pda::campaign_escrow(&campaign);
+    scenario.contribute(&alice, &campaign, 6 * LAMPORTS_PER_SOL).unwrap();
+    scenario.contribute(&bob, &campaign, 4 * LAMPORTS_PER_SOL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    // Nearly the whole raise is spent up front
+    let expenses = 9 * LAMPORTS_PER_SOL;
+    let now = scenario.now();
+    scenario.submit_budget(&organizer, &event, &campaign, expenses, &[(10_000, now)], 3 * DAY).unwrap();
+    scenario.vote_on_budget(&alice, &campaign, VoteChoice::Approve).unwrap();
+    scenario.vote_on_budget(&bob, &campaign, VoteChoice::Approve).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+    scenario.release_milestone(&organizer, &event, &campaign, 0).unwrap();
+    let leftover = GOAL - expenses;
+
+    // A sellout leaves far more profit than the campaign escrow holds
+    let tier = scenario.create_tier(&organizer, &event, "ga", TICKET_PRICE, 10);
+    for _ in 0..8 {
+        scenario.purchase_ticket(&carol, &event, &tier).unwrap();
+    }
+    let swept = 8 * TICKET_PRICE - rent_exempt(0);
+    assert!(swept - expenses > leftover);
+
+    // Distribute: the ticket revenue is swept in before the pools are set
+    scenario.warp_to(end_ts + 30 * DAY);
+    calculate_distribution(&mut scenario, &organizer, &event, &campaign).unwrap();
+    assert_eq!(scenario.lamports(&pda::ticket_escrow(&event)), rent_exempt(0));
+    let reserve = rent_exempt(0);
+    assert_eq!(scenario.lamports(&escrow), reserve + leftover + swept);
+
+    // Every share is paid in full
+    let campaign_state: Campaign = scenario.state(&campaign);
+    let alice_share = (campaign_state.backer_pool as u128 * 6 / 10) as u64;
+    let bob_share = (campaign_state.backer_pool as u128 * 4 / 10) as u64;
+    for (backer, share) in [(alice, alice_share), (bob, bob_share)] {
+        let before = scenario.lamports(&backer);
+        claim_backer_profit(&mut scenario, &backer, &campaign).unwrap();
+        assert_eq!(scenario.lamports(&backer), before + share);
+    }
+    let organizer_before = scenario.lamports(&organizer);
+    claim_organizer_profit(&mut scenario, &organizer, &campaign).unwrap();
+    assert_eq!(scenario.lamports(&organizer), organizer_before + campaign_state.organizer_pool);
+
+    // The unspent contributions and the platform pool are still there
+    assert!(scenario.lamports(&escrow) >= reserve + leftover + campaign_state.platform_pool);
+}
+
+#[test]
+fn test_withdrawal_waits_for_distribution() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "backed", 100);
+    let end_ts = scenario.state::<mythra_program::state::Event>(&event).end_ts;
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, GOAL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    let tier = scenario.create_tier(&organizer, &event, "ga", TICKET_PRICE, 10);
+    scenario.purchase_ticket(&carol, &event, &tier).unwrap();
+    scenario.purchase_ticket(&carol, &event, &tier).unwrap();
+
+    // Ticket revenue is the backers' until the distribution has run
+    assert_eq!(
+        scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawAllFunds {}).unwrap_err(),
+        program_error(EventError::DistributionNotComplete)
+    );
+    let without_campaign = scenario.process(
+        mythra_program::accounts::WithdrawFunds {
+            event,
+            ticket_escrow: pda::ticket_escrow(&event),
+            treasury: organizer,
+            platform_fees: pda::platform_fees(&event),
+            campaign: None,
+            authority: organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::WithdrawFunds { amount: TICKET_PRICE },
+    );
+    assert_eq!(without_campaign.unwrap_err(), program_error(EventError::InvalidCampaign));
+
+    scenario.warp_to(end_ts + 30 * DAY);
+    calculate_distribution(&mut scenario, &organizer, &event, &campaign).unwrap();
+    let campaign_state: Campaign = scenario.state(&campaign);
+    assert_eq!(campaign_state.total_revenue, 2 * TICKET_PRICE - rent_exempt(0));
+
+    // Afterwards the gate is open, with nothing left behind to take
+    assert_eq!(
+        scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawAllFunds {}).unwrap_err(),
+        program_error(EventError::InsufficientBalance)
+    );
+}
+
+#[test]
+fn test_distribution_sweeps_only_unwithdrawn_revenue() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let dave = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "presold", 100);
+    let end_ts = scenario.state::<mythra_program::state::Event>(&event).end_ts;
+    let ticket_escrow = pda::ticket_escrow(&event);
+
+    // Tickets sell and the organizer withdraws before crowdfunding starts;
+    // Dave's order is still waiting to be fulfilled
+    let tier = scenario.create_tier(&organizer, &event, "ga", TICKET_PRICE, 10);
+    for _ in 0..3 {
+        scenario.purchase_ticket(&carol, &event, &tier).unwrap();
+    }
+    let order = scenario.create_order(&dave, &event, &tier, "dave-1").unwrap();
+    scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawFunds { amount: TICKET_PRICE }).unwrap();
+
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, GOAL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    // Only the revenue still owed to the event is swept
+    scenario.warp_to(end_ts + 30 * DAY);
+    calculate_distribution(&mut scenario, &organizer, &event, &campaign).unwrap();
+    let swept = 2 * TICKET_PRICE - rent_exempt(0);
+    assert_eq!(scenario.state::<Campaign>(&campaign).total_revenue, swept);
+    assert_eq!(scenario.lamports(&ticket_escrow), rent_exempt(0) + TICKET_PRICE);
+    let event_state: mythra_program::state::Event = scenario.state(&event);
+    assert_eq!(event_state.total_withdrawn, TICKET_PRICE + swept);
+
+    // Dave's payment was left behind for his cancellation
+    let dave_before = scenario.lamports(&dave);
+    let order_rent = rent_exempt(scenario.account_len(&order));
+    scenario.process(
+        mythra_program::accounts::CancelOrder {
+            order,
+            event,
+            tier,
+            buyer_record: Some(pda::buyer_record(&tier, &dave)),
+            ticket_escrow,
+            buyer: dave,
+            canceler: dave,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::CancelOrder {},
+    ).unwrap();
+    assert_eq!(scenario.lamports(&dave) - dave_before, TICKET_PRICE + order_rent);
+}
+
+#[test]
+fn test_failed_campaign_refunds_contributions() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(10 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "workshop", 50);
+    let deadline = scenario.now() + 10 * DAY;
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, deadline);
+    let escrow = pda::campaign_escrow(&campaign);
+
+    scenario.contribute(&alice, &campaign, 3 * LAMPORTS_PER_SOL).unwrap();
+    assert_eq!(scenario.lamports(&escrow), rent_exempt(0) + 3 * LAMPORTS_PER_SOL);
+
+    // Short of the goal at the deadline: the campaign fails
+    scenario.warp_to(deadline + 1);
+    scenario.finalize_campaign(&campaign).unwrap();
+    assert_eq!(scenario.state::<Campaign>(&campaign).status, CampaignStatus::Failed);
+
+    // A failed campaign can't take more money or a budget
+    let late = scenario.wallet(LAMPORTS_PER_SOL);
+    assert!(scenario.contribute(&late, &campaign, LAMPORTS_PER_SOL / 2).is_err());
+    assert_eq!(scenario.lamports(&late), LAMPORTS_PER_SOL);
+    assert_eq!(
+        scenario.submit_budget(&organizer, &event, &campaign, LAMPORTS_PER_SOL, &[(10_000, deadline)], 3 * DAY)
+            .unwrap_err(),
+        program_error(EventError::CampaignNotFunded)
+    );
+    assert_eq!(scenario.lamports(&escrow), rent_exempt(0) + 3 * LAMPORTS_PER_SOL);
+
+    // Alice gets her full contribution back, once; the escrow keeps its rent
+    let alice_before = scenario.lamports(&alice);
+    claim_refund(&mut scenario, &alice, &campaign).unwrap();
+    assert_eq!(scenario.lamports(&alice), alice_before + 3 * LAMPORTS_PER_SOL);
+    assert_eq!(scenario.lamports(&escrow), rent_exempt(0));
+    assert!(scenario.state::<Contribution>(&pda::contribution(&campaign, &alice)).refunded);
+    assert_eq!(scenario.state::<Campaign>(&campaign).total_raised, 0);
+
+    assert!(claim_refund(&mut scenario, &alice, &campaign).is_err());
+    assert_eq!(scenario.lamports(&alice), alice_before + 3 * LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn test_contribution_top_up() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    let first_at = scenario.now();
+    let contribution = scenario.contribute(&alice, &campaign, 4 * LAMPORTS_PER_SOL).unwrap();
+
+    // A second contribution tops up the same record
+    scenario.warp_by(DAY);
+    let alice_before = scenario.lamports(&alice);
+    assert_eq!(scenario.contribute(&alice, &campaign, 6 * LAMPORTS_PER_SOL).unwrap(), contribution);
+    assert_eq!(scenario.lamports(&alice), alice_before - 6 * LAMPORTS_PER_SOL);
+
+    let contribution_state: Contribution = scenario.state(&contribution);
+    assert_eq!(contribution_state.amount, GOAL);
+    assert_eq!(contribution_state.previous_amount, 4 * LAMPORTS_PER_SOL);
+    assert_eq!(contribution_state.last_increase_at, scenario.now());
+    assert_eq!(contribution_state.contributed_at, first_at);
+    assert_eq!(contribution_state.amount_at(first_at), 4 * LAMPORTS_PER_SOL);
+    assert_eq!(contribution_state.amount_at(scenario.now()), GOAL);
+
+    let campaign_state: Campaign = scenario.state(&campaign);
+    assert_eq!(campaign_state.total_raised, GOAL);
+    assert_eq!(campaign_state.total_contributors, 1);
+}
+
+#[test]
+fn test_budget_vote_uses_contribution_snapshot() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(10 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, 4 * LAMPORTS_PER_SOL).unwrap();
+    scenario.contribute(&bob, &campaign, 6 * LAMPORTS_PER_SOL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    let now = scenario.now();
+    let budget = scenario.submit_budget(&organizer, &event, &campaign, BUDGET, &[(10_000, now)], 3 * DAY)
+        .unwrap();
+    let budget_state: Budget = scenario.state(&budget);
+    assert_eq!(budget_state.total_raised_snapshot, GOAL);
+    assert_eq!(budget_state.snapshot_ts, now);
+
+    // Alice tops up 90 SOL once voting is open; the campaign's total grows with it
+    scenario.warp_by(DAY);
+    let alice_contribution = pda::contribution(&campaign, &alice);
+    let mut contribution: Contribution = scenario.state(&alice_contribution);
+    contribution.record_increase(90 * LAMPORTS_PER_SOL, scenario.now()).unwrap();
+    scenario.set_state(&alice_contribution, &contribution);
+    let mut campaign_state: Campaign = scenario.state(&campaign);
+    campaign_state.total_raised += 90 * LAMPORTS_PER_SOL;
+    scenario.set_state(&campaign, &campaign_state);
+
+    // Only what Alice had in at submission counts against Bob
+    scenario.vote_on_budget(&alice, &campaign, VoteChoice::Reject).unwrap();
+    scenario.vote_on_budget(&bob, &campaign, VoteChoice::Approve).unwrap();
+    let budget_state: Budget = scenario.state(&budget);
+    assert_eq!(budget_state.votes_against, 4 * LAMPORTS_PER_SOL);
+    assert_eq!(budget_state.votes_for, 6 * LAMPORTS_PER_SOL);
+
+    // Quorum is measured against the snapshot, not the inflated total
+    scenario.warp_by(2 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+    assert_eq!(scenario.state::<Budget>(&budget).status, BudgetStatus::Approved);
+}
+
+#[test]
+fn test_per_milestone_release_votes() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(10 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    let escrow = pda::campaign_escrow(&campaign);
+    scenario.contribute(&alice, &campaign, 6 * LAMPORTS_PER_SOL).unwrap();
+    scenario.contribute(&bob, &campaign, 4 * LAMPORTS_PER_SOL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    // Both milestones are unlocked from the start but each needs its own vote
+    let now = scenario.now();
+    let budget = scenario.submit_budget_with_options(
+        &organizer,
+        &event,
+        &campaign,
+        BUDGET,
+        &[(5_000, now), (5_000, now)],
+        3 * DAY,
+        true,
+        VotingModel::Linear,
+    ).unwrap();
+    assert!(scenario.state::<Budget>(&budget).per_milestone_approval);
+
+    // No release vote before the budget itself is approved
+    assert_eq!(
+        scenario.open_milestone_vote(&organizer, &campaign, 0, DAY).unwrap_err(),
+        program_error(EventError::BudgetNotApproved)
+    );
+    scenario.vote_on_budget(&alice, &campaign, VoteChoice::Approve).unwrap();
+    scenario.vote_on_budget(&bob, &campaign, VoteChoice::Approve).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+    assert_eq!(scenario.state::<Budget>(&budget).status, BudgetStatus::Approved);
+
+    // An approved budget alone no longer releases anything
+    assert_eq!(
+        scenario.release_milestone(&organizer, &event, &campaign, 0).unwrap_err(),
+        program_error(EventError::MilestoneNotApproved)
+    );
+    assert_eq!(
+        scenario.vote_on_milestone(&alice, &campaign, 0, true).unwrap_err(),
+        program_error(EventError::MilestoneVoteNotOpen)
+    );
+
+    // Milestone 0: only the organizer opens the vote, once
+    assert!(scenario.open_milestone_vote(&alice, &campaign, 0, DAY).is_err());
+    scenario.open_milestone_vote(&organizer, &campaign, 0, DAY).unwrap();
+    assert_eq!(
+        scenario.open_milestone_vote(&organizer, &campaign, 0, DAY).unwrap_err(),
+        program_error(EventError::MilestoneVoteAlreadyOpened)
+    );
+    assert_eq!(
+        scenario.release_milestone(&organizer, &event, &campaign, 0).unwrap_err(),
+        program_error(EventError::MilestoneNotApproved)
+    );
+
+    // Alice (60%) approves, Bob (40%) rejects; one vote each
+    let vote_rent = rent_exempt(MilestoneVote::LEN);
+    let alice_before = scenario.lamports(&alice);
+    let vote = scenario.vote_on_milestone(&alice, &campaign, 0, true).unwrap();
+    assert_eq!(scenario.lamports(&alice), alice_before - vote_rent);
+    assert_eq!(scenario.state::<MilestoneVote>(&vote).voting_power, 6 * LAMPORTS_PER_SOL);
+    scenario.vote_on_milestone(&bob, &campaign, 0, false).unwrap();
+    assert!(scenario.vote_on_milestone(&bob, &campaign, 0, true).is_err());
+
+    assert_eq!(
+        scenario.finalize_milestone_vote(&campaign, 0).unwrap_err(),
+        program_error(EventError::VotingPeriodNotEnded)
+    );
+    scenario.warp_by(DAY);
+    assert_eq!(
+        scenario.vote_on_milestone(&alice, &campaign, 1, true).unwrap_err(),
+        program_error(EventError::MilestoneVoteNotOpen)
+    );
+    scenario.finalize_milestone_vote(&campaign, 0).unwrap();
+    let milestone = scenario.state::<Budget>(&budget).milestones[0].clone();
+    assert_eq!(milestone.approval, MilestoneApproval::Approved);
+    assert_eq!(milestone.votes_for, 6 * LAMPORTS_PER_SOL);
+    assert_eq!(milestone.votes_against, 4 * LAMPORTS_PER_SOL);
+    assert!(scenario.finalize_milestone_vote(&campaign, 0).is_err());
+
+    let organizer_before = scenario.lamports(&organizer);
+    scenario.release_milestone(&organizer, &event, &campaign, 0).unwrap();
+    assert_eq!(scenario.lamports(&organizer), organizer_before + BUDGET / 2);
+
+    // Milestone 1: Alice rejects and Bob stays home, so it's void for good
+    scenario.open_milestone_vote(&organizer, &campaign, 1, DAY).unwrap();
+    scenario.vote_on_milestone(&alice, &campaign, 1, false).unwrap();
+    scenario.warp_by(DAY);
+    assert_eq!(
+        scenario.vote_on_milestone(&bob, &campaign, 1, true).unwrap_err(),
+        program_error(EventError::VotingPeriodEnded)
+    );
+    scenario.finalize_milestone_vote(&campaign, 1).unwrap();
+    assert_eq!(
+        scenario.state::<Budget>(&budget).milestones[1].approval,
+        MilestoneApproval::Rejected
+    );
+    assert_eq!(
+        scenario.release_milestone(&organizer, &event, &campaign, 1).unwrap_err(),
+        program_error(EventError::MilestoneNotApproved)
+    );
+    assert_eq!(
+        scenario.open_milestone_vote(&organizer, &campaign, 1, DAY).unwrap_err(),
+        program_error(EventError::MilestoneVoteAlreadyOpened)
+    );
+    assert_eq!(scenario.lamports(&escrow), rent_exempt(0) + GOAL - BUDGET / 2);
+}
+
+#[test]
+fn test_revote_on_revised_budget() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, GOAL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    // v0: Alice rejects it
+    let now = scenario.now();
+    let original = scenario.submit_budget(&organizer, &event, &campaign, BUDGET, &[(10_000, now)], 3 * DAY)
+        .unwrap();
+    let first_vote = scenario.vote_on_budget(&alice, &campaign, VoteChoice::Reject).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+    assert_eq!(scenario.state::<Budget>(&original).status, BudgetStatus::Rejected);
+
+    // v1 starts from an empty tally
+    scenario.warp_by(DAY);
+    let revision = scenario.revise_budget(&organizer, &campaign, BUDGET / 2, &[(10_000, now)], 3 * DAY)
+        .unwrap();
+    let revision_state: Budget = scenario.state(&revision);
+    assert_eq!(revision_state.revision_count, 1);
+    assert_eq!((revision_state.votes_for, revision_state.votes_against), (0, 0));
+    assert_eq!(revision_state.votes_cast_count, 0);
+
+    // The same wallet votes again, into a vote record of its own
+    let second_vote = scenario.vote_on_budget_at(&alice, &campaign, &revision, VoteChoice::Approve).unwrap();
+    assert_ne!(second_vote, first_vote);
+    assert_eq!(second_vote, pda::budget_vote(&revision, 1, &alice));
+    assert_eq!(scenario.state::<BudgetVote>(&second_vote).revision, 1);
+    assert_eq!(scenario.state::<BudgetVote>(&first_vote).choice, VoteChoice::Reject);
+
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote_at(&campaign, &revision).unwrap();
+    let revision_state: Budget = scenario.state(&revision);
+    assert_eq!(revision_state.status, BudgetStatus::Approved);
+    assert_eq!(revision_state.votes_for, GOAL);
+
+    // Milestones are released from the revision, never from the original
+    assert_eq!(scenario.active_budget(&campaign), revision);
+    let release_from_original = scenario.process(
+        mythra_program::accounts::ReleaseMilestone {
+            event,
+            campaign,
+            budget: original,
+            campaign_escrow: pda::campaign_escrow(&campaign),
+            platform_config: pda::config(),
+            organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::ReleaseMilestone { milestone_index: 0 },
+    );
+    assert_eq!(release_from_original.unwrap_err(), program_error(EventError::BudgetNotActive));
+
+    let organizer_before = scenario.lamports(&organizer);
+    scenario.release_milestone(&organizer, &event, &campaign, 0).unwrap();
+    assert_eq!(scenario.lamports(&organizer), organizer_before + BUDGET / 2);
+    assert_eq!(scenario.state::<Budget>(&revision).status, BudgetStatus::Executed);
+}
+
+#[test]
+fn test_revise_approved_budget_after_release() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, GOAL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    let now = scenario.now();
+    let original = scenario.submit_budget(&organizer, &event, &campaign, BUDGET, &[(5_000, now), (5_000, now)], 3 * DAY)
+        .unwrap();
+    scenario.vote_on_budget(&alice, &campaign, VoteChoice::Approve).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+    scenario.release_milestone(&organizer, &event, &campaign, 0).unwrap();
+
+    // The released half can't shrink, the rest can be rescheduled
+    let later = scenario.now() + DAY;
+    assert_eq!(
+        scenario.revise_budget(&organizer, &campaign, BUDGET, &[(4_000, now), (6_000, later)], 3 * DAY)
+            .unwrap_err(),
+        program_error(EventError::CannotReviseReleasedMilestone)
+    );
+    let revision = scenario.revise_budget(&organizer, &campaign, BUDGET, &[(5_000, now), (5_000, later)], 3 * DAY)
+        .unwrap();
+    let revision_state: Budget = scenario.state(&revision);
+    assert_eq!(revision_state.revisions_used(), 1);
+    assert!(revision_state.milestones[0].released);
+    assert_eq!(revision_state.milestones[0].released_amount, BUDGET / 2);
+
+    // The original stops releasing once revised, the revision waits for its vote
+    assert_eq!(scenario.state::<Budget>(&original).status, BudgetStatus::Approved);
+    assert_eq!(
+        scenario.release_milestone(&organizer, &event, &campaign, 1).unwrap_err(),
+        program_error(EventError::BudgetNotApproved)
+    );
+    scenario.vote_on_budget_at(&alice, &campaign, &revision, VoteChoice::Approve).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote_at(&campaign, &revision).unwrap();
+
+    assert!(scenario.release_milestone(&organizer, &event, &campaign, 0).is_err());
+    let organizer_before = scenario.lamports(&organizer);
+    scenario.release_milestone(&organizer, &event, &campaign, 1).unwrap();
+    assert_eq!(scenario.lamports(&organizer), organizer_before + BUDGET / 2);
+    assert_eq!(scenario.state::<Budget>(&revision).status, BudgetStatus::Executed);
+    assert_eq!(scenario.state::<Campaign>(&campaign).total_expenses, BUDGET);
+}
+
+#[test]
+fn test_no_revision_after_event_end() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, GOAL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    let now = scenario.now();
+    scenario.submit_budget(&organizer, &event, &campaign, BUDGET, &[(10_000, now)], 3 * DAY).unwrap();
+    scenario.vote_on_budget(&alice, &campaign, VoteChoice::Reject).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+
+    // Past the event end a rejected budget stays rejected
+    let end_ts = scenario.state::<mythra_program::state::Event>(&event).end_ts;
+    scenario.warp_to(end_ts + 1);
+    assert_eq!(
+        scenario.revise_budget(&organizer, &campaign, BUDGET / 2, &[(10_000, now)], 3 * DAY).unwrap_err(),
+        program_error(EventError::EventEnded)
+    );
+}
+
+#[test]
+fn test_revisions_chain_up_to_limit() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, GOAL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    // v0, v1 and v2 are each rejected; every revision builds on the last
+    let now = scenario.now();
+    let mut latest = scenario.submit_budget(&organizer, &event, &campaign, BUDGET, &[(10_000, now)], 3 * DAY)
+        .unwrap();
+    for revision in 1..=2 {
+        scenario.vote_on_budget_at(&alice, &campaign, &latest, VoteChoice::Reject).unwrap();
+        scenario.warp_by(3 * DAY + 1);
+        scenario.finalize_budget_vote_at(&campaign, &latest).unwrap();
+        scenario.warp_by(DAY);
+
+        let amount = BUDGET - revision as u64 * LAMPORTS_PER_SOL;
+        latest = scenario.revise_budget(&organizer, &campaign, amount, &[(10_000, now)], 3 * DAY).unwrap();
+        assert_eq!(latest, pda::budget_revision(&campaign, revision));
+        assert_eq!(scenario.active_budget(&campaign), latest);
+        assert_eq!(scenario.state::<Budget>(&latest).revisions_used(), revision);
+    }
+
+    // The second revision is rejected too, and there is no third
+    scenario.vote_on_budget_at(&alice, &campaign, &latest, VoteChoice::Reject).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote_at(&campaign, &latest).unwrap();
+    scenario.warp_by(DAY);
+    assert_eq!(
+        scenario.revise_budget(&organizer, &campaign, LAMPORTS_PER_SOL, &[(10_000, now)], 3 * DAY).unwrap_err(),
+        program_error(EventError::CannotReviseBudget)
+    );
+}
+
+#[test]
+fn test_expired_resubmissions_chain_up_to_limit() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, GOAL).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    // Nobody votes, so every resubmission expires in turn
+    let now = scenario.now();
+    let mut latest = scenario.submit_budget(&organizer, &event, &campaign, BUDGET, &[(10_000, now)], DAY)
+        .unwrap();
+    for _ in 0..Budget::MAX_EXPIRED_RESUBMISSIONS {
+        scenario.warp_by(DAY + 1);
+        scenario.finalize_budget_vote_at(&campaign, &latest).unwrap();
+        assert_eq!(scenario.state::<Budget>(&latest).status, BudgetStatus::Expired);
+        latest = scenario.revise_budget(&organizer, &campaign, BUDGET, &[(10_000, now)], DAY).unwrap();
+    }
+    let latest_state: Budget = scenario.state(&latest);
+    assert_eq!(latest_state.expired_resubmissions, Budget::MAX_EXPIRED_RESUBMISSIONS);
+    assert_eq!(latest_state.revisions_used(), 0);
+
+    scenario.warp_by(DAY + 1);
+    scenario.finalize_budget_vote_at(&campaign, &latest).unwrap();
+    assert_eq!(
+        scenario.revise_budget(&organizer, &campaign, BUDGET, &[(10_000, now)], DAY).unwrap_err(),
+        program_error(EventError::CannotReviseBudget)
+    );
+}
+
+#[test]
+fn test_abstentions_count_toward_quorum_only() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, 9 * LAMPORTS_PER_SOL).unwrap();
+    scenario.contribute(&bob, &campaign, 6 * LAMPORTS_PER_SOL / 10).unwrap();
+    scenario.contribute(&carol, &campaign, 4 * LAMPORTS_PER_SOL / 10).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    let now = scenario.now();
+    let budget = scenario.submit_budget(&organizer, &event, &campaign, BUDGET, &[(10_000, now)], 3 * DAY)
+        .unwrap();
+
+    // Bob and Carol alone (10%) would miss the 20% quorum; Alice's abstention carries it
+    let alice_vote = scenario.vote_on_budget(&alice, &campaign, VoteChoice::Abstain).unwrap();
+    scenario.vote_on_budget(&bob, &campaign, VoteChoice::Approve).unwrap();
+    scenario.vote_on_budget(&carol, &campaign, VoteChoice::Reject).unwrap();
+    assert_eq!(scenario.state::<BudgetVote>(&alice_vote).choice, VoteChoice::Abstain);
+
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+
+    // The outcome is Bob's 0.6 SOL against Carol's 0.4 SOL
+    let budget_state: Budget = scenario.state(&budget);
+    assert_eq!(budget_state.status, BudgetStatus::Approved);
+    assert_eq!(budget_state.votes_for, 6 * LAMPORTS_PER_SOL / 10);
+    assert_eq!(budget_state.votes_against, 4 * LAMPORTS_PER_SOL / 10);
+    assert_eq!(budget_state.votes_abstain, 9 * LAMPORTS_PER_SOL);
+    assert_eq!(budget_state.votes_cast_count, 3);
+    assert_eq!(budget_state.total_voting_power_cast, GOAL);
+}
+
+#[test]
+fn test_low_turnout_budget_vote_expires() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(2 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, GOAL, scenario.now() + 10 * DAY);
+    scenario.contribute(&alice, &campaign, GOAL / 10 - 1).unwrap();
+    scenario.contribute(&bob, &campaign, GOAL - GOAL / 10 + 1).unwrap();
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    let now = scenario.now();
+    let original = scenario.submit_budget(&organizer, &event, &campaign, BUDGET, &[(10_000, now)], 3 * DAY)
+        .unwrap();
+    let original_state: Budget = scenario.state(&original);
+    assert_eq!(original_state.min_turnout_bps, PlatformConfig::DEFAULT_MIN_BUDGET_TURNOUT_BPS);
+
+    // Alice's lone yes vote is one lamport short of the 10% floor
+    scenario.vote_on_budget(&alice, &campaign, VoteChoice::Approve).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+    let original_state: Budget = scenario.state(&original);
+    assert_eq!(original_state.status, BudgetStatus::Expired);
+    assert_eq!(original_state.turnout_bps(original_state.total_raised_snapshot), 999);
+
+    // Resubmitted right away with the same terms, without using a revision
+    let resubmission = scenario.revise_budget(&organizer, &campaign, BUDGET, &[(10_000, now)], 3 * DAY)
+        .unwrap();
+    let resubmission_state: Budget = scenario.state(&resubmission);
+    assert_eq!(resubmission_state.revision_count, 1);
+    assert_eq!(resubmission_state.expired_resubmissions, 1);
+    assert_eq!(resubmission_state.revisions_used(), 0);
+    assert_eq!(resubmission_state.min_turnout_bps, original_state.min_turnout_bps);
+
+    // Bob turns out for the resubmission and carries it
+    scenario.vote_on_budget_at(&bob, &campaign, &resubmission, VoteChoice::Approve).unwrap();
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote_at(&campaign, &resubmission).unwrap();
+    assert_eq!(scenario.state::<Budget>(&resubmission).status, BudgetStatus::Approved);
+}
+
+/// A 100 SOL whale approves, ten 1 SOL backers reject; returns the final budget
+fn whale_vote(voting_model: VotingModel) -> Budget {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let whale = scenario.wallet(101 * LAMPORTS_PER_SOL);
+    let backers: Vec<Pubkey> = (0..10).map(|_| scenario.wallet(2 * LAMPORTS_PER_SOL)).collect();
+
+    let event = scenario.create_event(&organizer, "festival", 100);
+    let campaign = scenario.create_campaign(&organizer, &event, 110 * LAMPORTS_PER_SOL, scenario.now() + 10 * DAY);
+    scenario.contribute(&whale, &campaign, 100 * LAMPORTS_PER_SOL).unwrap();
+    for backer in &backers {
+        scenario.contribute(backer, &campaign, LAMPORTS_PER_SOL).unwrap();
+    }
+    scenario.finalize_campaign(&campaign).unwrap();
+
+    let now = scenario.now();
+    let budget = scenario.submit_budget_with_options(
+        &organizer,
+        &event,
+        &campaign,
+        BUDGET,
+        &[(10_000, now)],
+        3 * DAY,
+        false,
+        voting_model,
+    ).unwrap();
+    assert_eq!(scenario.state::<Budget>(&budget).voting_model, voting_model);
+
+    let whale_vote = scenario.vote_on_budget(&whale, &campaign, VoteChoice::Approve).unwrap();
+    let whale_power = voting_model.voting_power(100 * LAMPORTS_PER_SOL);
+    let vote_state: BudgetVote = scenario.state(&whale_vote);
+    assert_eq!((vote_state.contribution_amount, vote_state.voting_power), (100 * LAMPORTS_PER_SOL, whale_power));
+    for backer in &backers {
+        scenario.vote_on_budget(backer, &campaign, VoteChoice::Reject).unwrap();
+    }
+
+    scenario.warp_by(3 * DAY + 1);
+    scenario.finalize_budget_vote(&campaign).unwrap();
+    scenario.state(&budget)
+}
+
+#[test]
+fn test_quadratic_voting_power() {
+    // Linear: the whale outweighs all ten backers ten to one
+    let linear = whale_vote(VotingModel::Linear);
+    assert_eq!(linear.votes_for, 100 * LAMPORTS_PER_SOL);
+    assert_eq!(linear.votes_against, 10 * LAMPORTS_PER_SOL);
+    assert_eq!(linear.status, BudgetStatus::Approved);
+
+    // Quadratic: sqrt(100 SOL) is ten times sqrt(1 SOL), so the whale only
+    // matches the ten backers together (flooring leaves it 7 ahead)
+    let quadratic = whale_vote(VotingModel::Quadratic);
+    assert_eq!(quadratic.votes_for, 316_227);
+    assert_eq!(quadratic.votes_against, 10 * 31_622);
+    assert_eq!(quadratic.total_voting_power_cast, 316_227 + 10 * 31_622);
+
+    // Quorum counts the lamports behind the votes under either model
+    assert_eq!(linear.turnout_amount, 110 * LAMPORTS_PER_SOL);
+    assert_eq!(quadratic.turnout_amount, 110 * LAMPORTS_PER_SOL);
+    assert!(quadratic.quorum_met(quadratic.total_raised_snapshot));
+}
+
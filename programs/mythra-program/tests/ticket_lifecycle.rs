@@ -0,0 +1,2464 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use mythra_program::errors::EventError;
+use mythra_program::instructions::{CreateTicketTierParams, UpdateConfigParams, UpdateEventParams, UpdateTicketTierParams};
+use mythra_program::merkle;
+use mythra_program::metadata;
+use mythra_program::state::{AttendanceList, BuyerRecord, Event, PlatformConfig, RefundClaim, RefundPolicy, RefundStep, SeatAssignment, Ticket, TicketTier};
+
+const PRICE: u64 = LAMPORTS_PER_SOL;
+
+fn postpone(scenario: &mut ScenarioBuilder, organizer: &Pubkey, event: &Pubkey, start_ts: i64, end_ts: i64) {
+    scenario.process(
+        mythra_program::accounts::UpdateEvent {
+            event: *event,
+            event_delegate: None,
+            authority: *organizer,
+            new_treasury: None,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::UpdateEvent {
+            params: UpdateEventParams {
+                metadata_uri: None,
+                end_ts: Some(end_ts),
+                start_ts: Some(start_ts),
+                platform_split_bps: None,
+                treasury: None,
+                maintain_ticket_index: None,
+                allow_sales_during_event: None,
+                checkin_message_version: None,
+                backer_presale_seconds: None,
+                backer_presale_min_contribution: None,
+                locales: None,
+                compact_events: None,
+                refund_policy: None,
+            },
+        },
+    ).expect("update_event");
+}
+
+/// Platform treasury from the config, once it has been initialized
+fn platform_treasury(scenario: &ScenarioBuilder) -> Option<Pubkey> {
+    scenario
+        .exists(&pda::config())
+        .then(|| scenario.state::<PlatformConfig>(&pda::config()).platform_treasury)
+}
+
+fn set_refund_fee(scenario: &mut ScenarioBuilder, admin: &Pubkey, refund_fee_bps: u16) {
+    scenario.process(
+        mythra_program::accounts::UpdateConfig {
+            platform_config: pda::config(),
+            admin: *admin,
+        },
+        mythra_program::instruction::UpdateConfig {
+            params: UpdateConfigParams {
+                admin: None,
+                platform_treasury: None,
+                default_platform_fee_bps: None,
+                min_platform_share_bps: None,
+                min_backer_share_bps: None,
+                distribution_timeout_seconds: None,
+                max_relayer_fee_lamports: None,
+                distribution_grace_period_seconds: None,
+                backer_claim_window_seconds: None,
+                unclaimed_profit_recipient: None,
+                refund_fee_bps: Some(refund_fee_bps),
+                min_budget_turnout_bps: None,
+            },
+        },
+    ).expect("update_config");
+}
+
+fn set_refund_policy(scenario: &mut ScenarioBuilder, organizer: &Pubkey, event: &Pubkey, steps: &[(i64, u16)]) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::UpdateEvent {
+            event: *event,
+            event_delegate: None,
+            authority: *organizer,
+            new_treasury: None,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::UpdateEvent {
+            params: UpdateEventParams {
+                metadata_uri: None,
+                end_ts: None,
+                start_ts: None,
+                platform_split_bps: None,
+                treasury: None,
+                maintain_ticket_index: None,
+                allow_sales_during_event: None,
+                checkin_message_version: None,
+                backer_presale_seconds: None,
+                backer_presale_min_contribution: None,
+                locales: None,
+                compact_events: None,
+                refund_policy: Some(RefundPolicy {
+                    steps: steps
+                        .iter()
+                        .map(|&(seconds_before_start, refund_bps)| RefundStep { seconds_before_start, refund_bps })
+                        .collect(),
+                }),
+            },
+        },
+    )
+}
+
+fn request_refund(
+    scenario: &mut ScenarioBuilder,
+    owner: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    keys: &TicketKeys,
+) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::RequestRefund {
+            ticket: keys.ticket,
+            event: *event,
+            tier: *tier,
+            buyer_record: Some(pda::buyer_record(tier, &scenario.state::<Ticket>(&keys.ticket).buyer)),
+            ticket_escrow: pda::ticket_escrow(event),
+            mint: keys.mint,
+            owner_token_account: keys.token_account,
+            freeze_authority: Some(pda::ticket_freeze()),
+            platform_config: pda::config(),
+            platform_treasury: platform_treasury(scenario),
+            owner: *owner,
+            system_program: system_program::ID,
+            token_program: scenario.token_program(&keys.mint),
+        },
+        mythra_program::instruction::RequestRefund {},
+    )
+}
+
+fn claim_postponement_refund(
+    scenario: &mut ScenarioBuilder,
+    owner: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    keys: &TicketKeys,
+) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::ClaimPostponementRefund {
+            ticket: keys.ticket,
+            event: *event,
+            tier: *tier,
+            buyer_record: Some(pda::buyer_record(tier, &scenario.state::<Ticket>(&keys.ticket).buyer)),
+            ticket_escrow: pda::ticket_escrow(event),
+            mint: keys.mint,
+            owner_token_account: keys.token_account,
+            freeze_authority: Some(pda::ticket_freeze()),
+            owner: *owner,
+            system_program: system_program::ID,
+            token_program: scenario.token_program(&keys.mint),
+        },
+        mythra_program::instruction::ClaimPostponementRefund {},
+    )
+}
+
+fn mark_ticket_used(
+    scenario: &mut ScenarioBuilder,
+    owner: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    keys: &TicketKeys,
+) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::MarkTicketUsed {
+            ticket: keys.ticket,
+            tier: *tier,
+            event: *event,
+            owner_token_account: Some(keys.token_account),
+            owner: *owner,
+            blocklist_entry: pda::blocklist(event, owner),
+            gate_operator: Pubkey::new_unique(),
+            token_program: scenario.token_program(&keys.mint),
+        },
+        mythra_program::instruction::MarkTicketUsed {},
+    )
+}
+
+fn snapshot_attendance(scenario: &mut ScenarioBuilder, event: &Pubkey, page_index: u32, tickets: &[&TicketKeys]) -> TxResult {
+    let payer = scenario.wallet(LAMPORTS_PER_SOL);
+    let mut metas = mythra_program::accounts::SnapshotAttendance {
+        event: *event,
+        attendance_list: pda::attendance(event, page_index),
+        payer,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    metas.extend(tickets.iter().map(|keys| AccountMeta::new(keys.ticket, false)));
+    scenario.process(metas, mythra_program::instruction::SnapshotAttendance { page_index })
+}
+
+fn set_checkin_delegate(scenario: &mut ScenarioBuilder, owner: &Pubkey, keys: &TicketKeys, delegate: &Pubkey) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::SetCheckinDelegate {
+            ticket: keys.ticket,
+            owner: *owner,
+        },
+        mythra_program::instruction::SetCheckinDelegate { delegate: *delegate },
+    )
+}
+
+fn add_gate_operator(scenario: &mut ScenarioBuilder, organizer: &Pubkey, event: &Pubkey, operator: &Pubkey) {
+    scenario.process(
+        mythra_program::accounts::AddGateOperator {
+            event: *event,
+            gate_operator: pda::gate_operator(event, operator),
+            authority: *organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::AddGateOperator { operator: *operator },
+    ).expect("add_gate_operator");
+}
+
+/// Accounts for a batch check-in, with each ticket's
+/// [ticket, tier, blocklist_entry] appended as remaining accounts
+fn batch_checkin_accounts(
+    scenario: &ScenarioBuilder,
+    operator: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    tickets: &[&TicketKeys],
+) -> Vec<AccountMeta> {
+    let mut metas = mythra_program::accounts::MarkTicketsUsedBatch {
+        event: *event,
+        gate_operator_registry: pda::gate_operator(event, operator),
+        gate_operator: *operator,
+    }
+    .to_account_metas(None);
+    for keys in tickets {
+        let owner = scenario.state::<Ticket>(&keys.ticket).owner;
+        metas.push(AccountMeta::new(keys.ticket, false));
+        metas.push(AccountMeta::new_readonly(*tier, false));
+        metas.push(AccountMeta::new_readonly(pda::blocklist(event, &owner), false));
+    }
+    metas
+}
+
+fn mark_tickets_used_batch(
+    scenario: &mut ScenarioBuilder,
+    operator: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    tickets: &[&TicketKeys],
+) -> TxResult {
+    let metas = batch_checkin_accounts(scenario, operator, event, tier, tickets);
+    scenario.process(
+        metas,
+        mythra_program::instruction::MarkTicketsUsedBatch { count: tickets.len() as u8 },
+    )
+}
+
+/// Cancel an event whose escrow covers every ticket
+///
+/// The first sale pays the escrow's rent exemption out of the ticket
+/// price, so the organizer tops that back up first; otherwise the event
+/// would go into shortfall mode.
+fn cancel_event(scenario: &mut ScenarioBuilder, organizer: &Pubkey, event: &Pubkey) {
+    scenario.airdrop(&pda::ticket_escrow(event), rent_exempt(0));
+    scenario.process(
+        mythra_program::accounts::CancelEvent {
+            event: *event,
+            ticket_escrow: pda::ticket_escrow(event),
+            campaign: None,
+            campaign_escrow: None,
+            authority: *organizer,
+        },
+        mythra_program::instruction::CancelEvent {},
+    ).expect("cancel_event");
+}
+
+/// Accounts for a cancellation refund batch, with each ticket's
+/// [ticket, mint, holder token account, holder] appended as remaining accounts
+fn process_cancellation_refunds(
+    scenario: &mut ScenarioBuilder,
+    cranker: &Pubkey,
+    event: &Pubkey,
+    tickets: &[(&TicketKeys, Pubkey)],
+) -> TxResult {
+    let mut metas = mythra_program::accounts::ProcessCancellationRefunds {
+        event: *event,
+        ticket_escrow: pda::ticket_escrow(event),
+        freeze_authority: Some(pda::ticket_freeze()),
+        cranker: *cranker,
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+    }
+    .to_account_metas(None);
+    for (keys, token_account) in tickets {
+        let holder = scenario.state::<Ticket>(&keys.ticket).owner;
+        metas.push(AccountMeta::new(keys.ticket, false));
+        metas.push(AccountMeta::new(keys.mint, false));
+        metas.push(AccountMeta::new(*token_account, false));
+        metas.push(AccountMeta::new(holder, false));
+    }
+    scenario.process(
+        metas,
+        mythra_program::instruction::ProcessCancellationRefunds { count: tickets.len() as u8 },
+    )
+}
+
+fn refund_ticket_accounts(
+    scenario: &ScenarioBuilder,
+    organizer: &Pubkey,
+    owner: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    keys: &TicketKeys,
+) -> Vec<AccountMeta> {
+    let platform_fees = pda::platform_fees(event);
+    let buyer_record = pda::buyer_record(tier, &scenario.state::<Ticket>(&keys.ticket).buyer);
+    mythra_program::accounts::RefundTicket {
+        ticket: keys.ticket,
+        event: *event,
+        tier: *tier,
+        buyer_record: scenario.exists(&buyer_record).then_some(buyer_record),
+        ticket_escrow: pda::ticket_escrow(event),
+        platform_fees: scenario.exists(&platform_fees).then_some(platform_fees),
+        mint: keys.mint,
+        buyer_token_account: keys.token_account,
+        freeze_authority: Some(pda::ticket_freeze()),
+        buyer: *owner,
+        platform_config: pda::config(),
+        platform_treasury: platform_treasury(scenario),
+        event_delegate: None,
+        authority: *organizer,
+        system_program: system_program::ID,
+        token_program: scenario.token_program(&keys.mint),
+    }
+    .to_account_metas(None)
+}
+
+fn refund_ticket(
+    scenario: &mut ScenarioBuilder,
+    organizer: &Pubkey,
+    owner: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    keys: &TicketKeys,
+) -> TxResult {
+    let metas = refund_ticket_accounts(scenario, organizer, owner, event, tier, keys);
+    scenario.process(metas, mythra_program::instruction::RefundTicket { refund_amount: None })
+}
+
+fn close_ticket_tier(scenario: &mut ScenarioBuilder, organizer: &Pubkey, event: &Pubkey, tier: &Pubkey) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::CloseTicketTier {
+            tier: *tier,
+            event: *event,
+            authority: *organizer,
+        },
+        mythra_program::instruction::CloseTicketTier {},
+    )
+}
+
+fn update_ticket_tier(
+    scenario: &mut ScenarioBuilder,
+    organizer: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    params: UpdateTicketTierParams,
+) {
+    scenario.process(
+        mythra_program::accounts::UpdateTicketTier {
+            tier: *tier,
+            event: *event,
+            authority: *organizer,
+        },
+        mythra_program::instruction::UpdateTicketTier { params },
+    ).expect("update_ticket_tier");
+}
+
+fn upgrade_ticket(
+    scenario: &mut ScenarioBuilder,
+    owner: &Pubkey,
+    event: &Pubkey,
+    current_tier: &Pubkey,
+    target_tier: &Pubkey,
+    keys: &TicketKeys,
+) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::UpgradeTicket {
+            ticket: keys.ticket,
+            event: *event,
+            current_tier: *current_tier,
+            target_tier: *target_tier,
+            ticket_escrow: pda::ticket_escrow(event),
+            platform_config: pda::config(),
+            blocklist_entry: pda::blocklist(event, owner),
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::UpgradeTicket {},
+    )
+}
+
+fn assign_seat(
+    scenario: &mut ScenarioBuilder,
+    organizer: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    keys: &TicketKeys,
+    seat_number: u32,
+) -> TxResult {
+    let current_seat = scenario.state::<Ticket>(&keys.ticket).seat_number;
+    scenario.process(
+        mythra_program::accounts::AssignSeat {
+            ticket: keys.ticket,
+            tier: *tier,
+            event: *event,
+            seat_assignment: pda::seat(tier, seat_number),
+            previous_seat: (current_seat > 0).then(|| pda::seat(tier, current_seat)),
+            authority: *organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::AssignSeat { seat_number },
+    )
+}
+
+fn attach_metadata(
+    scenario: &mut ScenarioBuilder,
+    mint_authority: &Pubkey,
+    event: &Pubkey,
+    tier: &Pubkey,
+    keys: &TicketKeys,
+) -> TxResult {
+    scenario.process(
+        mythra_program::accounts::AttachMetadata {
+            ticket: keys.ticket,
+            event: *event,
+            tier: *tier,
+            mint: keys.mint,
+            metadata: metadata::metadata_address(&keys.mint),
+            metadata_authority: pda::metadata_authority(event),
+            mint_authority: *mint_authority,
+            token_metadata_program: metadata::ID,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::AttachMetadata {},
+    )
+}
+
+fn create_tier_with_transfer_rules(
+    scenario: &mut ScenarioBuilder,
+    organizer: &Pubkey,
+    event: &Pubkey,
+    tier_id: &str,
+    resale_enabled: bool,
+    transferable: bool,
+) -> Pubkey {
+    let tier = pda::tier(event, tier_id);
+    scenario.process(
+        mythra_program::accounts::CreateTicketTier {
+            tier,
+            event: *event,
+            event_delegate: None,
+            authority: *organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::CreateTicketTier {
+            tier_id: tier_id.to_string(),
+            params: CreateTicketTierParams {
+                metadata_uri: format!("https://example.com/{tier_id}.json"),
+                price_lamports: PRICE,
+                max_supply: 50,
+                royalty_bps: 0,
+                tier_index: 0,
+                resale_enabled,
+                max_per_wallet: 0,
+                sale_start_ts: 0,
+                sale_end_ts: 0,
+                max_resale_price_lamports: 0,
+                transferable,
+            },
+        },
+    ).expect("create_ticket_tier");
+    tier
+}
+
+#[test]
+fn test_full_ticket_lifecycle() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    // Organizer pays rent for the event and tier accounts only
+    let event = scenario.create_event(&organizer, "concert", 100);
+    let event_rent = scenario.lamports(&event);
+    assert_eq!(event_rent, rent_exempt(Event::space("https://example.com/concert.json".len())));
+    assert_eq!(scenario.lamports(&organizer), 10 * LAMPORTS_PER_SOL - event_rent);
+
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 50);
+    let tier_rent = scenario.lamports(&tier);
+    assert_eq!(tier_rent, rent_exempt(TicketTier::space("https://example.com/ga.json".len())));
+    assert_eq!(scenario.lamports(&organizer), 10 * LAMPORTS_PER_SOL - event_rent - tier_rent);
+
+    // Each buyer pays the price into the ticket escrow plus their own rent
+    let escrow = pda::ticket_escrow(&event);
+    let per_buyer_rent = rent_exempt(Ticket::SPACE) + rent_exempt(BuyerRecord::LEN);
+
+    let alice_ticket = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    assert_eq!(scenario.lamports(&alice), 5 * LAMPORTS_PER_SOL - PRICE - per_buyer_rent);
+    assert_eq!(scenario.lamports(&escrow), PRICE);
+
+    let bob_ticket = scenario.purchase_ticket(&bob, &event, &tier).unwrap();
+    assert_eq!(scenario.lamports(&bob), 5 * LAMPORTS_PER_SOL - PRICE - per_buyer_rent);
+    assert_eq!(scenario.lamports(&escrow), 2 * PRICE);
+
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.tickets_sold, 2);
+    assert_eq!(event_state.ticket_revenue, 2 * PRICE);
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 2);
+
+    // Nobody can refund themselves before a postponement
+    assert_eq!(
+        claim_postponement_refund(&mut scenario, &bob, &event, &tier, &bob_ticket).unwrap_err(),
+        program_error(EventError::PostponementRefundNotAvailable)
+    );
+
+    // Postponing by a week opens self-service refunds for existing holders
+    let old_start = event_state.start_ts;
+    scenario.warp_by(DAY);
+    postpone(&mut scenario, &organizer, &event, old_start + 7 * DAY, event_state.end_ts + 7 * DAY);
+    assert_eq!(scenario.lamports(&event), event_rent);
+
+    claim_postponement_refund(&mut scenario, &bob, &event, &tier, &bob_ticket).unwrap();
+    assert_eq!(scenario.lamports(&bob), 5 * LAMPORTS_PER_SOL - per_buyer_rent);
+    assert_eq!(scenario.lamports(&escrow), PRICE);
+    assert_eq!(scenario.token_amount(&bob_ticket.token_account), 0);
+
+    let refunded: Ticket = scenario.state(&bob_ticket.ticket);
+    assert!(refunded.refunded);
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.tickets_sold, 1);
+    assert_eq!(event_state.ticket_revenue, PRICE);
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 1);
+
+    // A second refund is rejected and moves nothing
+    assert!(claim_postponement_refund(&mut scenario, &bob, &event, &tier, &bob_ticket).is_err());
+    assert_eq!(scenario.lamports(&escrow), PRICE);
+
+    // At the new start Alice checks in; no lamports move
+    scenario.warp_to(old_start + 7 * DAY);
+    let alice_before = scenario.lamports(&alice);
+    mark_ticket_used(&mut scenario, &alice, &event, &tier, &alice_ticket).unwrap();
+    assert_eq!(scenario.lamports(&alice), alice_before);
+    assert_eq!(scenario.lamports(&escrow), PRICE);
+
+    let used: Ticket = scenario.state(&alice_ticket.ticket);
+    assert!(used.used);
+    assert_eq!(scenario.state::<Event>(&event).checked_in_count, 1);
+
+    assert_eq!(
+        mark_ticket_used(&mut scenario, &alice, &event, &tier, &alice_ticket).unwrap_err(),
+        program_error(EventError::TicketAlreadyUsed)
+    );
+
+    // Sales are closed once the event has started
+    let carol = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    assert!(scenario.purchase_ticket(&carol, &event, &tier).is_err());
+    assert_eq!(scenario.lamports(&carol), 5 * LAMPORTS_PER_SOL);
+    assert_eq!(scenario.lamports(&escrow), PRICE);
+}
+
+#[test]
+fn test_first_purchase_below_rent_exemption_rejected() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let buyer = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    // An empty ticket escrow can't be funded with less than its rent
+    let event = scenario.create_event(&organizer, "meetup", 10);
+    let tier = scenario.create_tier(&organizer, &event, "cheap", rent_exempt(0) - 1, 10);
+
+    assert_eq!(
+        scenario.purchase_ticket(&buyer, &event, &tier).unwrap_err(),
+        ProgramError::AccountNotRentExempt
+    );
+    assert_eq!(scenario.lamports(&buyer), 5 * LAMPORTS_PER_SOL);
+    assert!(!scenario.exists(&pda::ticket_escrow(&event)));
+}
+
+#[test]
+fn test_attendance_counters_reconcile() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "gala", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 50);
+    let alice_ticket = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    let bob_ticket = scenario.purchase_ticket(&bob, &event, &tier).unwrap();
+
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(
+        (event_state.tickets_sold, event_state.tickets_checked_in, event_state.tickets_refunded),
+        (2, 0, 0)
+    );
+
+    // Bob refunds after a postponement
+    scenario.warp_by(DAY);
+    let new_start = event_state.start_ts + 7 * DAY;
+    postpone(&mut scenario, &organizer, &event, new_start, event_state.end_ts + 7 * DAY);
+    claim_postponement_refund(&mut scenario, &bob, &event, &tier, &bob_ticket).unwrap();
+
+    // Alice checks in at the new start
+    scenario.warp_to(new_start);
+    mark_ticket_used(&mut scenario, &alice, &event, &tier, &alice_ticket).unwrap();
+
+    // A checked-in ticket can't be refunded, and the attempt counts nothing
+    let escrow_before = scenario.lamports(&pda::ticket_escrow(&event));
+    assert_eq!(
+        refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &alice_ticket).unwrap_err(),
+        program_error(EventError::TicketUsedCannotRefund)
+    );
+    assert_eq!(scenario.lamports(&pda::ticket_escrow(&event)), escrow_before);
+
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(
+        (event_state.tickets_sold, event_state.tickets_checked_in, event_state.tickets_refunded),
+        (1, 1, 1)
+    );
+    assert_eq!(event_state.checked_in_count, 1);
+    assert_eq!(event_state.tickets_sold + event_state.tickets_refunded, 2);
+}
+
+#[test]
+fn test_attendance_snapshot_pages() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let [alice, bob, carol, dave, erin] = [(); 5].map(|_| scenario.wallet(5 * LAMPORTS_PER_SOL));
+
+    let event = scenario.create_event(&organizer, "snapshot", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let [alice_ticket, bob_ticket, carol_ticket, dave_ticket, erin_ticket] =
+        [alice, bob, carol, dave, erin].map(|buyer| scenario.purchase_ticket(&buyer, &event, &tier).unwrap());
+
+    // Everyone but Carol shows up
+    let event_state: Event = scenario.state(&event);
+    scenario.warp_to(event_state.start_ts);
+    for (owner, keys) in [(alice, &alice_ticket), (bob, &bob_ticket), (dave, &dave_ticket), (erin, &erin_ticket)] {
+        mark_ticket_used(&mut scenario, &owner, &event, &tier, keys).unwrap();
+    }
+    assert_eq!(
+        snapshot_attendance(&mut scenario, &event, 0, &[&alice_ticket]).unwrap_err(),
+        program_error(EventError::EventNotEnded)
+    );
+
+    scenario.warp_to(event_state.end_ts + 1);
+    assert_eq!(
+        snapshot_attendance(&mut scenario, &event, 0, &[&carol_ticket]).unwrap_err(),
+        program_error(EventError::TicketNotCheckedIn)
+    );
+    assert_eq!(
+        snapshot_attendance(&mut scenario, &event, 1, &[&alice_ticket]).unwrap_err(),
+        program_error(EventError::InvalidAttendancePage)
+    );
+
+    // Tickets already snapshotted are skipped on later cranks
+    snapshot_attendance(&mut scenario, &event, 0, &[&alice_ticket]).unwrap();
+    snapshot_attendance(&mut scenario, &event, 0, &[&alice_ticket, &bob_ticket]).unwrap();
+    let page: AttendanceList = scenario.state(&pda::attendance(&event, 0));
+    assert_eq!(page.attendees, vec![alice, bob]);
+    assert_eq!(scenario.state::<Event>(&event).attendance_snapshot_count, 2);
+
+    // Leave one slot on page 0: Dave takes it and Erin waits for page 1
+    let mut page = page;
+    page.attendees.resize_with(AttendanceList::MAX_ATTENDEES_PER_PAGE - 1, Pubkey::new_unique);
+    scenario.set_state(&pda::attendance(&event, 0), &page);
+    let mut event_state: Event = scenario.state(&event);
+    event_state.attendance_snapshot_count = page.attendees.len() as u32;
+    scenario.set_state(&event, &event_state);
+
+    snapshot_attendance(&mut scenario, &event, 0, &[&dave_ticket, &erin_ticket]).unwrap();
+    let page: AttendanceList = scenario.state(&pda::attendance(&event, 0));
+    assert_eq!(page.attendees.last(), Some(&dave));
+    assert!(!scenario.state::<Ticket>(&erin_ticket.ticket).snapshotted);
+    assert_eq!(
+        snapshot_attendance(&mut scenario, &event, 0, &[&erin_ticket]).unwrap_err(),
+        program_error(EventError::InvalidAttendancePage)
+    );
+
+    snapshot_attendance(&mut scenario, &event, 1, &[&erin_ticket]).unwrap();
+    let page: AttendanceList = scenario.state(&pda::attendance(&event, 1));
+    assert_eq!((page.page_index, page.attendees), (1, vec![erin]));
+    assert_eq!(
+        scenario.state::<Event>(&event).attendance_snapshot_count,
+        AttendanceList::MAX_ATTENDEES_PER_PAGE as u32 + 1
+    );
+}
+
+#[test]
+fn test_close_ticket_tier_reallocates_supply() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    // Two tiers use up the whole event
+    let event = scenario.create_event(&organizer, "expo", 100);
+    let vip = scenario.create_tier(&organizer, &event, "vip", 2 * PRICE, 40);
+    let general = scenario.create_tier(&organizer, &event, "general", PRICE, 60);
+    assert_eq!(scenario.state::<Event>(&event).allocated_supply, 100);
+
+    // Closing the unsold tier frees all 40 seats and refunds its rent
+    let organizer_before = scenario.lamports(&organizer);
+    let vip_rent = scenario.lamports(&vip);
+    close_ticket_tier(&mut scenario, &organizer, &event, &vip).unwrap();
+    assert!(!scenario.exists(&vip));
+    assert_eq!(scenario.lamports(&organizer), organizer_before + vip_rent);
+    assert_eq!(scenario.state::<Event>(&event).allocated_supply, 60);
+
+    // The capacity goes to a new, cheaper tier
+    let early = scenario.create_tier(&organizer, &event, "early", PRICE / 2, 40);
+    assert_eq!(scenario.state::<Event>(&event).allocated_supply, 100);
+    scenario.purchase_ticket(&alice, &event, &early).unwrap();
+
+    // A tier with sales only gives back its unsold seats and stays open
+    scenario.purchase_ticket(&alice, &event, &general).unwrap();
+    close_ticket_tier(&mut scenario, &organizer, &event, &general).unwrap();
+    assert!(scenario.exists(&general));
+    let general_state: TicketTier = scenario.state(&general);
+    assert!(general_state.closed);
+    assert_eq!((general_state.current_supply, general_state.max_supply), (1, 1));
+    assert_eq!(scenario.state::<Event>(&event).allocated_supply, 41);
+
+    // Closed tiers don't sell and can't be closed twice
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    assert_eq!(
+        scenario.purchase_ticket(&bob, &event, &general).unwrap_err(),
+        program_error(EventError::TierClosed)
+    );
+    assert_eq!(scenario.lamports(&bob), 5 * LAMPORTS_PER_SOL);
+    assert_eq!(
+        close_ticket_tier(&mut scenario, &organizer, &event, &general).unwrap_err(),
+        program_error(EventError::TierClosed)
+    );
+}
+
+#[test]
+fn test_free_tier_rsvp() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "meetup", 100);
+
+    // Free tiers must cap claims per wallet
+    assert_eq!(
+        scenario.create_tier_with_limit(&organizer, &event, "rsvp", 0, 50, 0).unwrap_err(),
+        program_error(EventError::FreeTierRequiresWalletLimit)
+    );
+    let tier = scenario.create_tier_with_limit(&organizer, &event, "rsvp", 0, 50, 2).unwrap();
+
+    // Claiming costs only the ticket and buyer record rent; no escrow is funded
+    let per_buyer_rent = rent_exempt(Ticket::SPACE) + rent_exempt(BuyerRecord::LEN);
+    let first = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    let second = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    assert_eq!(
+        scenario.lamports(&alice),
+        LAMPORTS_PER_SOL - per_buyer_rent - rent_exempt(Ticket::SPACE)
+    );
+    assert!(!scenario.exists(&pda::ticket_escrow(&event)));
+    assert_eq!(scenario.state::<Ticket>(&first.ticket).price_paid, 0);
+
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.tickets_sold, 2);
+    assert_eq!(event_state.ticket_revenue, 0);
+
+    // The wallet limit stops hoarding
+    assert_eq!(
+        scenario.purchase_ticket(&alice, &event, &tier).unwrap_err(),
+        program_error(EventError::PurchaseLimitExceeded)
+    );
+
+    // A postponement refund of a free ticket pays nothing but frees the seat
+    let event_state: Event = scenario.state(&event);
+    scenario.warp_by(DAY);
+    let new_start = event_state.start_ts + 7 * DAY;
+    postpone(&mut scenario, &organizer, &event, new_start, event_state.end_ts + 7 * DAY);
+    let alice_before = scenario.lamports(&alice);
+    claim_postponement_refund(&mut scenario, &alice, &event, &tier, &second).unwrap();
+    assert_eq!(scenario.lamports(&alice), alice_before);
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 1);
+    assert_eq!(scenario.state::<Event>(&event).ticket_revenue, 0);
+
+    // The remaining ticket checks in like a paid one
+    scenario.warp_to(new_start);
+    mark_ticket_used(&mut scenario, &alice, &event, &tier, &first).unwrap();
+    assert!(scenario.state::<Ticket>(&first.ticket).used);
+    assert_eq!(scenario.state::<Event>(&event).tickets_checked_in, 1);
+}
+
+#[test]
+fn test_allowlist_presale() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    // Fan-club allowlist of Alice and Bob; the public sale opens in 10 days
+    let root = merkle::node_hash(&merkle::leaf_hash(&alice), &merkle::leaf_hash(&bob));
+    let event = scenario.create_event(&organizer, "tour", 100);
+    let tier = scenario.create_tier(&organizer, &event, "fanclub", PRICE, 50);
+    let public_sale = scenario.now() + 10 * DAY;
+    update_ticket_tier(&mut scenario, &organizer, &event, &tier, UpdateTicketTierParams {
+        sale_start_ts: Some(public_sale),
+        sale_end_ts: None,
+        max_uses: None,
+        allowlist_root: Some(Some(root)),
+        max_resale_price_lamports: None,
+        seated: None,
+    });
+
+    // Members buy early with their sibling's leaf as proof
+    scenario.purchase_ticket_with_proof(&alice, &event, &tier, vec![merkle::leaf_hash(&bob)]).unwrap();
+    assert_eq!(scenario.lamports(&pda::ticket_escrow(&event)), PRICE);
+
+    // Outsiders and bad proofs are turned away without paying
+    assert_eq!(
+        scenario.purchase_ticket_with_proof(&carol, &event, &tier, vec![merkle::leaf_hash(&bob)]).unwrap_err(),
+        program_error(EventError::MerkleProofInvalid)
+    );
+    assert_eq!(
+        scenario.purchase_ticket(&bob, &event, &tier).unwrap_err(),
+        program_error(EventError::MerkleProofInvalid)
+    );
+    assert_eq!(scenario.lamports(&carol), 5 * LAMPORTS_PER_SOL);
+
+    // Once the public sale opens anyone can buy without a proof
+    scenario.warp_to(public_sale);
+    scenario.purchase_ticket(&carol, &event, &tier).unwrap();
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 2);
+}
+
+#[test]
+fn test_resale_price_cap() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "final", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 50);
+    let cap = PRICE + PRICE / 2;
+    update_ticket_tier(&mut scenario, &organizer, &event, &tier, UpdateTicketTierParams {
+        sale_start_ts: None,
+        sale_end_ts: None,
+        max_uses: None,
+        allowlist_root: None,
+        max_resale_price_lamports: Some(cap),
+        seated: None,
+    });
+    let ticket = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+
+    // One lamport over the cap is rejected and the ticket stays put
+    assert_eq!(
+        scenario.transfer_ticket(&alice, &bob, &event, &tier, &ticket, Some(cap + 1)).unwrap_err(),
+        program_error(EventError::ResalePriceExceedsCap)
+    );
+    assert_eq!(scenario.token_amount(&ticket.token_account), 1);
+
+    // Exactly at the cap goes through and is recorded on the ticket
+    let bob_account = scenario.transfer_ticket(&alice, &bob, &event, &tier, &ticket, Some(cap)).unwrap();
+    assert_eq!(scenario.token_amount(&bob_account), 1);
+    let resold: Ticket = scenario.state(&ticket.ticket);
+    assert_eq!(resold.owner, bob);
+    assert_eq!(resold.last_sale_price, cap);
+    assert_eq!(resold.last_sale_royalty, 0);
+
+    // A gift resets the recorded price
+    let bob_keys = TicketKeys { token_account: bob_account, ..ticket };
+    scenario.transfer_ticket(&bob, &carol, &event, &tier, &bob_keys, None).unwrap();
+    assert_eq!(scenario.state::<Ticket>(&ticket.ticket).last_sale_price, 0);
+}
+
+#[test]
+fn test_soulbound_ticket() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "summit", 100);
+    let tier = create_tier_with_transfer_rules(&mut scenario, &organizer, &event, "named", true, false);
+    assert!(!scenario.state::<TicketTier>(&tier).transferable);
+
+    // The NFT is frozen in the buyer's token account on purchase
+    let alice_ticket = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    let bob_ticket = scenario.purchase_ticket(&bob, &event, &tier).unwrap();
+    assert!(scenario.token_frozen(&alice_ticket.token_account));
+
+    // transfer_ticket refuses, gifts and sales alike, and so does gift_ticket
+    for sale_price in [None, Some(PRICE)] {
+        assert_eq!(
+            scenario.transfer_ticket(&alice, &bob, &event, &tier, &alice_ticket, sale_price).unwrap_err(),
+            program_error(EventError::TicketNotTransferable)
+        );
+    }
+    assert_eq!(
+        scenario.gift_ticket(&alice, &bob, &event, &tier, &alice_ticket).unwrap_err(),
+        program_error(EventError::TicketNotTransferable)
+    );
+    assert_eq!(scenario.token_amount(&alice_ticket.token_account), 1);
+    assert_eq!(scenario.state::<Ticket>(&alice_ticket.ticket).owner, alice);
+
+    // A mint the program can't freeze is rejected
+    let (mint, token_account) = scenario.mint_nft(&bob);
+    let unfreezable = TicketKeys { ticket: pda::ticket(&mint), mint, token_account };
+    let result = scenario.process(
+        mythra_program::accounts::PurchaseTicket {
+            ticket: unfreezable.ticket,
+            event,
+            tier,
+            buyer_record: pda::buyer_record(&tier, &bob),
+            ticket_index: None,
+            seat_assignment: None,
+            backer_campaign: None,
+            backer_contribution: None,
+            mint,
+            buyer_token_account: token_account,
+            freeze_authority: Some(pda::ticket_freeze()),
+            metadata: None,
+            metadata_authority: None,
+            token_metadata_program: None,
+            ticket_escrow: pda::ticket_escrow(&event),
+            platform_config: pda::config(),
+            platform_stats: None,
+            blocklist_entry: pda::blocklist(&event, &bob),
+            buyer: bob,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        mythra_program::instruction::PurchaseTicket { allowlist_proof: Vec::new(), with_metadata: false },
+    );
+    assert_eq!(result.unwrap_err(), program_error(EventError::SoulboundMintNotFreezable));
+
+    // Refunds thaw the NFT before burning it
+    let event_state: Event = scenario.state(&event);
+    scenario.warp_by(DAY);
+    postpone(&mut scenario, &organizer, &event, event_state.start_ts + 7 * DAY, event_state.end_ts + 7 * DAY);
+    claim_postponement_refund(&mut scenario, &bob, &event, &tier, &bob_ticket).unwrap();
+    assert_eq!(scenario.token_amount(&bob_ticket.token_account), 0);
+
+    // Check-in goes by ticket.owner; the frozen token account isn't needed
+    scenario.warp_to(event_state.start_ts + 7 * DAY);
+    scenario.process(
+        mythra_program::accounts::MarkTicketUsed {
+            ticket: alice_ticket.ticket,
+            tier,
+            event,
+            owner_token_account: None,
+            owner: alice,
+            blocklist_entry: pda::blocklist(&event, &alice),
+            gate_operator: Pubkey::new_unique(),
+            token_program: spl_token::ID,
+        },
+        mythra_program::instruction::MarkTicketUsed {},
+    ).unwrap();
+    assert!(scenario.state::<Ticket>(&alice_ticket.ticket).used);
+    assert!(scenario.token_frozen(&alice_ticket.token_account));
+}
+
+#[test]
+fn test_upgrade_ticket() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(10 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "gala", 100);
+    let general = scenario.create_tier(&organizer, &event, "ga", PRICE, 50);
+    let vip = scenario.create_tier(&organizer, &event, "vip", 3 * PRICE, 2);
+    let escrow = pda::ticket_escrow(&event);
+
+    let alice_ticket = scenario.purchase_ticket(&alice, &event, &general).unwrap();
+    let bob_ticket = scenario.purchase_ticket(&bob, &event, &general).unwrap();
+
+    // Alice pays only the difference and the seat moves tiers
+    let alice_before = scenario.lamports(&alice);
+    upgrade_ticket(&mut scenario, &alice, &event, &general, &vip, &alice_ticket).unwrap();
+    assert_eq!(scenario.lamports(&alice), alice_before - 2 * PRICE);
+    assert_eq!(scenario.lamports(&escrow), 4 * PRICE);
+
+    let upgraded: Ticket = scenario.state(&alice_ticket.ticket);
+    assert_eq!(upgraded.tier, vip);
+    assert_eq!(upgraded.price_paid, 3 * PRICE);
+    assert_eq!(scenario.state::<TicketTier>(&general).current_supply, 1);
+    assert_eq!(scenario.state::<TicketTier>(&vip).current_supply, 1);
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.tickets_sold, 2);
+    assert_eq!(event_state.ticket_revenue, 4 * PRICE);
+
+    // No way back down, and the old tier no longer matches the ticket
+    assert_eq!(
+        upgrade_ticket(&mut scenario, &alice, &event, &vip, &general, &alice_ticket).unwrap_err(),
+        program_error(EventError::InvalidTierUpgrade)
+    );
+    assert_eq!(
+        upgrade_ticket(&mut scenario, &alice, &event, &general, &vip, &alice_ticket).unwrap_err(),
+        program_error(EventError::InvalidTier)
+    );
+
+    // Once VIP sells out, Bob's upgrade fails and costs nothing
+    scenario.purchase_ticket(&carol, &event, &vip).unwrap();
+    let bob_before = scenario.lamports(&bob);
+    assert_eq!(
+        upgrade_ticket(&mut scenario, &bob, &event, &general, &vip, &bob_ticket).unwrap_err(),
+        program_error(EventError::ExceedsTotalSupply)
+    );
+    assert_eq!(scenario.lamports(&bob), bob_before);
+    assert_eq!(scenario.state::<Ticket>(&bob_ticket.ticket).tier, general);
+
+    // Upgrades stop when the event starts
+    let event_state: Event = scenario.state(&event);
+    scenario.warp_to(event_state.start_ts);
+    let vip_two = scenario.create_tier(&organizer, &event, "vip2", 3 * PRICE, 5);
+    assert_eq!(
+        upgrade_ticket(&mut scenario, &bob, &event, &general, &vip_two, &bob_ticket).unwrap_err(),
+        program_error(EventError::EventAlreadyStarted)
+    );
+}
+
+#[test]
+fn test_seat_assignment() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "opera", 100);
+    let stalls = scenario.create_tier(&organizer, &event, "stalls", PRICE, 50);
+    let standing = scenario.create_tier(&organizer, &event, "standing", PRICE, 50);
+    update_ticket_tier(&mut scenario, &organizer, &event, &stalls, UpdateTicketTierParams {
+        sale_start_ts: None,
+        sale_end_ts: None,
+        max_uses: None,
+        allowlist_root: None,
+        max_resale_price_lamports: None,
+        seated: Some(true),
+    });
+
+    // Purchases take seats in order, each backed by its own assignment
+    let alice_ticket = scenario.purchase_ticket(&alice, &event, &stalls).unwrap();
+    let bob_ticket = scenario.purchase_ticket(&bob, &event, &stalls).unwrap();
+    assert_eq!(scenario.state::<Ticket>(&alice_ticket.ticket).seat_number, 1);
+    assert_eq!(scenario.state::<Ticket>(&bob_ticket.ticket).seat_number, 2);
+    let seat: SeatAssignment = scenario.state(&pda::seat(&stalls, 2));
+    assert_eq!(seat.ticket, bob_ticket.ticket);
+
+    // Moving Alice ahead frees seat 1 and pushes later purchases past her
+    assign_seat(&mut scenario, &organizer, &event, &stalls, &alice_ticket, 5).unwrap();
+    assert_eq!(scenario.state::<Ticket>(&alice_ticket.ticket).seat_number, 5);
+    assert!(!scenario.exists(&pda::seat(&stalls, 1)));
+    let carol_ticket = scenario.purchase_ticket(&carol, &event, &stalls).unwrap();
+    assert_eq!(scenario.state::<Ticket>(&carol_ticket.ticket).seat_number, 6);
+
+    // A taken seat can't be given out again; a freed one can
+    assert!(assign_seat(&mut scenario, &organizer, &event, &stalls, &bob_ticket, 5).is_err());
+    assert_eq!(scenario.state::<Ticket>(&bob_ticket.ticket).seat_number, 2);
+    assign_seat(&mut scenario, &organizer, &event, &stalls, &bob_ticket, 1).unwrap();
+    assert_eq!(scenario.state::<SeatAssignment>(&pda::seat(&stalls, 1)).ticket, bob_ticket.ticket);
+    assert!(!scenario.exists(&pda::seat(&stalls, 2)));
+
+    // General admission tickets have no seat and can't be given one
+    let standing_ticket = scenario.purchase_ticket(&alice, &event, &standing).unwrap();
+    assert_eq!(scenario.state::<Ticket>(&standing_ticket.ticket).seat_number, 0);
+    assert_eq!(
+        assign_seat(&mut scenario, &organizer, &event, &standing, &standing_ticket, 3).unwrap_err(),
+        program_error(EventError::InvalidSeatNumber)
+    );
+}
+
+#[test]
+fn test_gift_ticket() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "matinee", 100);
+    let tier = create_tier_with_transfer_rules(&mut scenario, &organizer, &event, "family", false, true);
+    let ticket = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+
+    // Resale is off for the tier, but a gift goes through without royalties
+    assert_eq!(
+        scenario.transfer_ticket(&alice, &bob, &event, &tier, &ticket, None).unwrap_err(),
+        program_error(EventError::ResaleDisabled)
+    );
+    let alice_before = scenario.lamports(&alice);
+    let bob_account = scenario.gift_ticket(&alice, &bob, &event, &tier, &ticket).unwrap();
+    assert_eq!(scenario.lamports(&alice), alice_before);
+    assert_eq!(scenario.token_amount(&bob_account), 1);
+    assert_eq!(scenario.token_amount(&ticket.token_account), 0);
+
+    let gifted: Ticket = scenario.state(&ticket.ticket);
+    assert_eq!(gifted.owner, bob);
+    assert_eq!(gifted.gift_count, 1);
+
+    // The ticket's one gift is spent, so Bob can't pass it on again
+    let bob_keys = TicketKeys { token_account: bob_account, ..ticket };
+    assert_eq!(
+        scenario.gift_ticket(&bob, &carol, &event, &tier, &bob_keys).unwrap_err(),
+        program_error(EventError::GiftLimitReached)
+    );
+    assert_eq!(scenario.token_amount(&bob_account), 1);
+    assert_eq!(scenario.state::<Ticket>(&ticket.ticket).owner, bob);
+}
+
+#[test]
+fn test_pending_transfer_claim_link() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "claim-link", 100);
+    let tier = create_tier_with_transfer_rules(&mut scenario, &organizer, &event, "ga", true, true);
+    let locked = create_tier_with_transfer_rules(&mut scenario, &organizer, &event, "locked", false, true);
+    let ticket = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    let locked_ticket = scenario.purchase_ticket(&alice, &event, &locked).unwrap();
+
+    let secret = b"see you at the show";
+    let claim_hash = hash(secret).to_bytes();
+    let expires_at = scenario.now() + 86_400;
+
+    // Claim links follow the resale rules
+    assert_eq!(
+        scenario
+            .initiate_pending_transfer(&alice, &event, &locked, &locked_ticket, claim_hash, expires_at)
+            .unwrap_err(),
+        program_error(EventError::ResaleDisabled)
+    );
+
+    // The NFT moves into escrow while the ticket stays in Alice's name
+    let alice_before = scenario.lamports(&alice);
+    scenario.initiate_pending_transfer(&alice, &event, &tier, &ticket, claim_hash, expires_at).unwrap();
+    let escrow = pda::pending_transfer_escrow(&ticket.ticket);
+    assert_eq!(scenario.token_amount(&ticket.token_account), 0);
+    assert_eq!(scenario.token_amount(&escrow), 1);
+    assert_eq!(scenario.state::<Ticket>(&ticket.ticket).owner, alice);
+
+    // A wrong secret doesn't unlock it
+    assert_eq!(
+        scenario.claim_pending_transfer(&bob, &alice, &event, &ticket, b"see you at the gig").unwrap_err(),
+        program_error(EventError::InvalidClaimPreimage)
+    );
+
+    // Alice can't pull the ticket back while the link is live
+    assert_eq!(
+        scenario.cancel_pending_transfer(&alice, &ticket).unwrap_err(),
+        program_error(EventError::ClaimNotExpired)
+    );
+
+    // Bob claims it and Alice gets her rent back
+    let bob_account = scenario.claim_pending_transfer(&bob, &alice, &event, &ticket, secret).unwrap();
+    assert_eq!(scenario.token_amount(&bob_account), 1);
+    assert_eq!(scenario.state::<Ticket>(&ticket.ticket).owner, bob);
+    assert!(!scenario.exists(&escrow));
+    assert!(!scenario.exists(&pda::pending_transfer(&ticket.ticket)));
+    assert_eq!(scenario.lamports(&alice), alice_before);
+
+    // Bob sends it on, but nobody claims it in time
+    let bob_keys = TicketKeys { token_account: bob_account, ..ticket };
+    let expires_at = scenario.now() + 3_600;
+    scenario.initiate_pending_transfer(&bob, &event, &tier, &bob_keys, claim_hash, expires_at).unwrap();
+    scenario.warp_to(expires_at);
+    assert_eq!(
+        scenario.claim_pending_transfer(&alice, &bob, &event, &bob_keys, secret).unwrap_err(),
+        program_error(EventError::ClaimExpired)
+    );
+
+    scenario.cancel_pending_transfer(&bob, &bob_keys).unwrap();
+    assert_eq!(scenario.token_amount(&bob_account), 1);
+    assert_eq!(scenario.state::<Ticket>(&ticket.ticket).owner, bob);
+    assert!(!scenario.exists(&escrow));
+}
+
+#[test]
+fn test_initialize_config_requires_upgrade_authority() {
+    let mut scenario = ScenarioBuilder::new();
+    let deployer = scenario.wallet(LAMPORTS_PER_SOL);
+    let mallory = scenario.wallet(LAMPORTS_PER_SOL);
+
+    let initialize_config = |scenario: &mut ScenarioBuilder, admin: Pubkey| {
+        scenario.process(
+            mythra_program::accounts::InitializeConfig {
+                platform_config: pda::config(),
+                admin,
+                system_program: system_program::ID,
+                program: mythra_program::ID,
+                program_data: pda::program_data(),
+            },
+            mythra_program::instruction::InitializeConfig {
+                min_platform_share_bps: 500,
+                min_backer_share_bps: 5_000,
+                platform_treasury: admin,
+                default_platform_fee_bps: 500,
+            },
+        )
+    };
+
+    // Nobody can claim the platform of an immutable program
+    assert_eq!(
+        initialize_config(&mut scenario, deployer).unwrap_err(),
+        program_error(EventError::UnauthorizedConfigInit)
+    );
+
+    // Nor can anyone but the upgrade authority front-run it
+    scenario.set_upgrade_authority(Some(&deployer));
+    assert_eq!(
+        initialize_config(&mut scenario, mallory).unwrap_err(),
+        program_error(EventError::UnauthorizedConfigInit)
+    );
+
+    initialize_config(&mut scenario, deployer).unwrap();
+    assert_eq!(scenario.state::<PlatformConfig>(&pda::config()).admin, deployer);
+}
+
+#[test]
+fn test_platform_fee_defaults() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let create_event = |scenario: &mut ScenarioBuilder, event_id: &str| {
+        let event = pda::event(&organizer, event_id);
+        let start_ts = scenario.now() + 30 * DAY;
+        scenario.process(
+            mythra_program::accounts::CreateEvent {
+                event,
+                organizer,
+                treasury: organizer,
+                platform_stats: None,
+                platform_config: pda::config(),
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::CreateEvent {
+                event_id: event_id.to_string(),
+                metadata_uri: format!("https://example.com/{event_id}.json"),
+                start_ts,
+                end_ts: start_ts + DAY,
+                total_supply: 100,
+                platform_split_bps: None,
+            },
+        ).unwrap();
+        event
+    };
+
+    // Without a config, events default to no platform split, and an
+    // explicit split has no treasury to pay royalties into, so the
+    // organizer keeps the whole royalty
+    let unconfigured = create_event(&mut scenario, "unconfigured");
+    assert_eq!(scenario.state::<Event>(&unconfigured).platform_split_bps, 0);
+
+    let start_ts = scenario.now() + 30 * DAY;
+    let event = scenario.create_event_at(&organizer, "royalties", start_ts, start_ts + DAY, 100, 1000);
+    let tier = pda::tier(&event, "resale");
+    scenario.process(
+        mythra_program::accounts::CreateTicketTier {
+            tier,
+            event,
+            event_delegate: None,
+            authority: organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::CreateTicketTier {
+            tier_id: "resale".to_string(),
+            params: CreateTicketTierParams {
+                metadata_uri: "https://example.com/resale.json".to_string(),
+                price_lamports: PRICE,
+                max_supply: 50,
+                royalty_bps: 1000,
+                tier_index: 0,
+                resale_enabled: true,
+                max_per_wallet: 0,
+                sale_start_ts: 0,
+                sale_end_ts: 0,
+                max_resale_price_lamports: 0,
+                transferable: true,
+            },
+        },
+    ).unwrap();
+    let ticket = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+
+    let organizer_before = scenario.lamports(&organizer);
+    let bob_account = scenario.transfer_ticket(&alice, &bob, &event, &tier, &ticket, Some(PRICE)).unwrap();
+    assert_eq!(scenario.lamports(&organizer) - organizer_before, PRICE / 10);
+
+    // Once configured, new events take the platform's default fee and the
+    // platform's royalty share must go to its treasury
+    let admin = scenario.wallet(LAMPORTS_PER_SOL);
+    let platform_treasury = scenario.wallet(LAMPORTS_PER_SOL);
+    scenario.initialize_config(&admin, &platform_treasury);
+    let configured = create_event(&mut scenario, "configured");
+    assert_eq!(scenario.state::<Event>(&configured).platform_split_bps, 500);
+
+    let bob_keys = TicketKeys { token_account: bob_account, ..ticket };
+    assert_eq!(
+        scenario.transfer_ticket(&bob, &carol, &event, &tier, &bob_keys, Some(PRICE)).unwrap_err(),
+        program_error(EventError::InvalidPlatformTreasury)
+    );
+}
+
+#[test]
+fn test_ticket_metadata() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "gallery", 100);
+    let tier = pda::tier(&event, "vip");
+    scenario.process(
+        mythra_program::accounts::CreateTicketTier {
+            tier,
+            event,
+            event_delegate: None,
+            authority: organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::CreateTicketTier {
+            tier_id: "vip".to_string(),
+            params: CreateTicketTierParams {
+                metadata_uri: "https://example.com/vip.json".to_string(),
+                price_lamports: PRICE,
+                max_supply: 50,
+                royalty_bps: 500,
+                tier_index: 2,
+                resale_enabled: true,
+                max_per_wallet: 0,
+                sale_start_ts: 0,
+                sale_end_ts: 0,
+                max_resale_price_lamports: 0,
+                transferable: true,
+            },
+        },
+    ).unwrap();
+
+    // Metadata is created with the purchase, owned by the event's PDA
+    let alice_ticket = scenario.purchase_ticket_with_metadata(&alice, &event, &tier).unwrap();
+    let metadata_authority = pda::metadata_authority(&event);
+    let (update_authority, data) = scenario.ticket_metadata(&alice_ticket.mint).unwrap();
+    assert_eq!(update_authority, metadata_authority);
+    assert_eq!(data.name, metadata::ticket_name(&event, 2, 0));
+    assert_eq!(data.symbol, "MYTHRA");
+    assert_eq!(data.uri, "https://example.com/vip.json");
+    assert_eq!(data.seller_fee_basis_points, 500);
+
+    // The PDA is the verified creator; royalties go to the event treasury
+    let treasury = scenario.state::<Event>(&event).treasury;
+    assert_eq!(data.creators, Some(vec![
+        metadata::Creator { address: metadata_authority, verified: true, share: 0 },
+        metadata::Creator { address: treasury, verified: false, share: 100 },
+    ]));
+
+    // Plain purchases skip it, and the mint authority can attach it later
+    let bob_ticket = scenario.purchase_ticket(&bob, &event, &tier).unwrap();
+    assert!(scenario.ticket_metadata(&bob_ticket.mint).is_none());
+    assert_eq!(
+        attach_metadata(&mut scenario, &alice, &event, &tier, &bob_ticket).unwrap_err(),
+        ProgramError::MissingRequiredSignature
+    );
+    attach_metadata(&mut scenario, &bob, &event, &tier, &bob_ticket).unwrap();
+    let (_, bob_data) = scenario.ticket_metadata(&bob_ticket.mint).unwrap();
+    assert_eq!(bob_data, data);
+
+    // Metadata can only be created once
+    assert_eq!(
+        attach_metadata(&mut scenario, &alice, &event, &tier, &alice_ticket).unwrap_err(),
+        ProgramError::AccountAlreadyInitialized
+    );
+}
+
+#[test]
+fn test_purchase_with_either_token_program() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "token-programs", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let soulbound = create_tier_with_transfer_rules(&mut scenario, &organizer, &event, "named", false, false);
+
+    for token_program in [spl_token::ID, spl_token_2022::ID] {
+        let ticket = scenario.purchase_ticket_with_token_program(&alice, &event, &tier, token_program).unwrap();
+        assert_eq!(scenario.token_program(&ticket.mint), token_program);
+        assert_eq!(scenario.state::<Ticket>(&ticket.ticket).owner, alice);
+
+        // The NFT moves through the same token program it was minted under
+        let bob_account = scenario.transfer_ticket(&alice, &bob, &event, &tier, &ticket, None).unwrap();
+        assert_eq!(scenario.token_amount(&bob_account), 1);
+        assert_eq!(scenario.state::<Ticket>(&ticket.ticket).owner, bob);
+
+        // Soulbound tickets are frozen by either program
+        let named = scenario.purchase_ticket_with_token_program(&alice, &event, &soulbound, token_program).unwrap();
+        assert!(scenario.token_frozen(&named.token_account));
+    }
+
+    // Token-2022 extensions make the mint and token account larger than the
+    // SPL Token layouts; purchase and transfer read them all the same
+    let ticket = scenario.purchase_ticket_with_token_program(&bob, &event, &tier, spl_token_2022::ID).unwrap();
+    let state: Ticket = scenario.state(&ticket.ticket);
+    assert_eq!(state.mint, ticket.mint);
+    assert!(scenario.account_len(&ticket.mint) > spl_token::state::Mint::LEN);
+    assert!(scenario.account_len(&ticket.token_account) > spl_token::state::Account::LEN);
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 3);
+}
+
+#[test]
+fn test_batch_purchase_into_prefunded_ticket() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "batch-prefunded", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let nfts = [scenario.mint_nft(&alice), scenario.mint_nft(&alice)];
+
+    // Someone sends dust to one of the ticket PDAs ahead of the sale
+    let griefed = pda::ticket(&nfts[1].0);
+    scenario.airdrop(&griefed, 1);
+
+    let purchase_tickets = |scenario: &mut ScenarioBuilder| {
+        let mut metas = mythra_program::accounts::PurchaseTickets {
+            event,
+            tier,
+            buyer_record: pda::buyer_record(&tier, &alice),
+            ticket_index: None,
+            ticket_escrow: pda::ticket_escrow(&event),
+            platform_config: pda::config(),
+            platform_stats: None,
+            blocklist_entry: pda::blocklist(&event, &alice),
+            buyer: alice,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        for (mint, token_account) in &nfts {
+            metas.push(AccountMeta::new_readonly(*mint, false));
+            metas.push(AccountMeta::new_readonly(*token_account, false));
+            metas.push(AccountMeta::new(pda::ticket(mint), false));
+        }
+        scenario.process(metas, mythra_program::instruction::PurchaseTickets { count: 2 })
+    };
+
+    // The sale still goes through, topping the PDA up to rent exemption
+    purchase_tickets(&mut scenario).unwrap();
+    for (mint, _) in &nfts {
+        let ticket: Ticket = scenario.state(&pda::ticket(mint));
+        assert_eq!(ticket.owner, alice);
+        assert_eq!(ticket.price_paid, PRICE);
+    }
+    assert_eq!(scenario.lamports(&griefed), rent_exempt(Ticket::SPACE));
+    assert_eq!(scenario.state::<Event>(&event).tickets_sold, 2);
+
+    // A mint that already has a ticket can't be sold again
+    assert!(purchase_tickets(&mut scenario).is_err());
+    assert_eq!(scenario.state::<Event>(&event).tickets_sold, 2);
+}
+
+#[test]
+fn test_box_office_sale_matches_purchase_rules() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let mallory = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "box-office", 100);
+    let tier = scenario.create_tier(&organizer, &event, "door", PRICE, 10);
+    let stock = [scenario.mint_nft(&alice), scenario.mint_nft(&mallory)];
+
+    let mut metas = mythra_program::accounts::PreregisterMintsBatch {
+        event,
+        tier,
+        authority: organizer,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    for (mint, _) in &stock {
+        metas.push(AccountMeta::new_readonly(*mint, false));
+        metas.push(AccountMeta::new(pda::ticket(mint), false));
+    }
+    scenario.process(metas, mythra_program::instruction::PreregisterMintsBatch { count: 2 }).unwrap();
+
+    let activate_ticket = |scenario: &mut ScenarioBuilder, buyer: Pubkey, (mint, token_account): (Pubkey, Pubkey)| {
+        scenario.process(
+            mythra_program::accounts::ActivateTicket {
+                ticket: pda::ticket(&mint),
+                event,
+                tier,
+                buyer_token_account: token_account,
+                buyer_record: pda::buyer_record(&tier, &buyer),
+                ticket_index: None,
+                ticket_escrow: pda::ticket_escrow(&event),
+                platform_config: pda::config(),
+                blocklist_entry: pda::blocklist(&event, &buyer),
+                authority: organizer,
+                payer: buyer,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::ActivateTicket { buyer },
+        )
+    };
+
+    // Blocked wallets can't buy at the door either
+    scenario.process(
+        mythra_program::accounts::BlockWallet {
+            event,
+            blocklist_entry: pda::blocklist(&event, &mallory),
+            authority: organizer,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::BlockWallet { wallet: mallory },
+    ).unwrap();
+    assert_eq!(
+        activate_ticket(&mut scenario, mallory, stock[1]).unwrap_err(),
+        program_error(EventError::WalletBlocked)
+    );
+
+    // A live promo applies at the door too
+    let now = scenario.now();
+    scenario.process(
+        mythra_program::accounts::SetTierPromo { tier, event, authority: organizer },
+        mythra_program::instruction::SetTierPromo { price_lamports: PRICE / 2, start_ts: now, end_ts: now + DAY },
+    ).unwrap();
+    let alice_before = scenario.lamports(&alice);
+    activate_ticket(&mut scenario, alice, stock[0]).unwrap();
+
+    let ticket: Ticket = scenario.state(&pda::ticket(&stock[0].0));
+    assert_eq!(ticket.owner, alice);
+    assert_eq!(ticket.price_paid, PRICE / 2);
+    assert!(ticket.promo_applied);
+    assert_eq!(
+        alice_before - scenario.lamports(&alice),
+        PRICE / 2 + rent_exempt(BuyerRecord::LEN)
+    );
+    assert_eq!(scenario.state::<Event>(&event).ticket_revenue, PRICE / 2);
+}
+
+#[test]
+fn test_registered_mint_records_no_revenue_and_refunds() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "airdrop", 100);
+    let tier = scenario.create_tier(&organizer, &event, "vip", PRICE, 10);
+    let (mint, token_account) = scenario.mint_nft(&alice);
+
+    scenario.process(
+        mythra_program::accounts::RegisterMint {
+            ticket: pda::ticket(&mint),
+            event,
+            tier,
+            mint,
+            buyer_token_account: token_account,
+            buyer: alice,
+            blocklist_entry: pda::blocklist(&event, &alice),
+            event_delegate: None,
+            authority: organizer,
+            campaign: None,
+            ticket_index: None,
+            platform_stats: None,
+            system_program: system_program::ID,
+            token_program: scenario.token_program(&mint),
+        },
+        mythra_program::instruction::RegisterMint {},
+    ).unwrap();
+
+    // Nothing was paid on-chain, so revenue and price_paid agree on zero
+    let ticket: Ticket = scenario.state(&pda::ticket(&mint));
+    assert_eq!(ticket.owner, alice);
+    assert_eq!(ticket.price_paid, 0);
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.tickets_sold, 1);
+    assert_eq!(event_state.ticket_revenue, 0);
+
+    // There's no buyer record, but the ticket can still be refunded; the
+    // holder co-signs the burn since the NFT was never approved to the PDA
+    let keys = TicketKeys { ticket: pda::ticket(&mint), mint, token_account };
+    assert!(!scenario.exists(&pda::buyer_record(&tier, &alice)));
+    let mut metas = refund_ticket_accounts(&scenario, &organizer, &alice, &event, &tier, &keys);
+    for meta in metas.iter_mut().filter(|meta| meta.pubkey == alice) {
+        meta.is_signer = true;
+    }
+    scenario.process(metas, mythra_program::instruction::RefundTicket { refund_amount: None }).unwrap();
+    assert!(scenario.state::<Ticket>(&keys.ticket).refunded);
+    assert_eq!(scenario.token_amount(&token_account), 0);
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 0);
+}
+
+#[test]
+fn test_batch_checkin_at_max_size() {
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::solana_program::message::Message;
+    use anchor_lang::InstructionData;
+    use mythra_program::instructions::MAX_CHECKINS_PER_BATCH;
+
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let operator = scenario.wallet(LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "batch-checkin", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 20);
+    add_gate_operator(&mut scenario, &organizer, &event, &operator);
+
+    let tickets: Vec<TicketKeys> = (0..MAX_CHECKINS_PER_BATCH + 1)
+        .map(|_| scenario.purchase_ticket(&alice, &event, &tier).unwrap())
+        .collect();
+    let batch: Vec<&TicketKeys> = tickets.iter().take(MAX_CHECKINS_PER_BATCH as usize).collect();
+
+    // A full batch still fits in one legacy transaction packet
+    let metas = batch_checkin_accounts(&scenario, &operator, &event, &tier, &batch);
+    let instruction = Instruction {
+        program_id: mythra_program::ID,
+        accounts: metas,
+        data: mythra_program::instruction::MarkTicketsUsedBatch { count: MAX_CHECKINS_PER_BATCH }.data(),
+    };
+    let message = Message::new(&[instruction], Some(&operator));
+    let signatures = message.header.num_required_signatures as usize;
+    assert!(1 + 64 * signatures + message.serialize().len() <= 1232);
+
+    // One more ticket than the limit is rejected outright
+    let oversized: Vec<&TicketKeys> = tickets.iter().collect();
+    assert_eq!(
+        mark_tickets_used_batch(&mut scenario, &operator, &event, &tier, &oversized).unwrap_err(),
+        program_error(EventError::InvalidCheckinBatchSize)
+    );
+
+    // Only registered operators can scan a batch
+    let stranger = scenario.wallet(LAMPORTS_PER_SOL);
+    assert!(mark_tickets_used_batch(&mut scenario, &stranger, &event, &tier, &batch).is_err());
+
+    // A ticket scanned at another gate is skipped, not fatal
+    mark_ticket_used(&mut scenario, &alice, &event, &tier, batch[2]).unwrap();
+    let used_at = scenario.state::<Ticket>(&batch[2].ticket).checked_in_ts;
+    scenario.warp_by(60);
+
+    mark_tickets_used_batch(&mut scenario, &operator, &event, &tier, &batch).unwrap();
+    for (i, keys) in batch.iter().enumerate() {
+        let ticket: Ticket = scenario.state(&keys.ticket);
+        assert!(ticket.used);
+        if i == 2 {
+            assert_eq!(ticket.checked_in_ts, used_at);
+            assert_ne!(ticket.gate_operator, operator);
+        } else {
+            assert_eq!(ticket.checked_in_ts, scenario.now());
+            assert_eq!(ticket.gate_operator, operator);
+        }
+    }
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.tickets_checked_in, MAX_CHECKINS_PER_BATCH as u32);
+    assert_eq!(event_state.checked_in_count, MAX_CHECKINS_PER_BATCH as u32);
+
+    // Tickets for another event abort the whole batch
+    let other_event = scenario.create_event(&organizer, "other-event", 100);
+    let other_tier = scenario.create_tier(&organizer, &other_event, "ga", PRICE, 10);
+    let foreign = scenario.purchase_ticket(&alice, &other_event, &other_tier).unwrap();
+    let last = &tickets[MAX_CHECKINS_PER_BATCH as usize];
+    assert_eq!(
+        mark_tickets_used_batch(&mut scenario, &operator, &event, &tier, &[last, &foreign]).unwrap_err(),
+        program_error(EventError::InvalidEvent)
+    );
+    assert!(!scenario.state::<Ticket>(&last.ticket).used);
+}
+
+#[test]
+fn test_batch_checkin_rejects_refunded_ticket() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let operator = scenario.wallet(LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "batch-refunded", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    add_gate_operator(&mut scenario, &organizer, &event, &operator);
+
+    let tickets: Vec<TicketKeys> = (0..3)
+        .map(|_| scenario.purchase_ticket(&alice, &event, &tier).unwrap())
+        .collect();
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &tickets[1]).unwrap();
+
+    // A refunded ticket aborts the batch instead of being admitted
+    let batch: Vec<&TicketKeys> = tickets.iter().collect();
+    assert_eq!(
+        mark_tickets_used_batch(&mut scenario, &operator, &event, &tier, &batch).unwrap_err(),
+        program_error(EventError::AlreadyRefunded)
+    );
+    for keys in &tickets {
+        assert_eq!(scenario.state::<Ticket>(&keys.ticket).use_count, 0);
+    }
+    assert_eq!(scenario.state::<Event>(&event).tickets_checked_in, 0);
+
+    // The remaining tickets check in once it is left out
+    mark_tickets_used_batch(&mut scenario, &operator, &event, &tier, &[&tickets[0], &tickets[2]]).unwrap();
+    assert!(scenario.state::<Ticket>(&tickets[0].ticket).used);
+    assert!(!scenario.state::<Ticket>(&tickets[1].ticket).used);
+    assert!(scenario.state::<Ticket>(&tickets[2].ticket).used);
+    assert_eq!(scenario.state::<Event>(&event).tickets_checked_in, 2);
+}
+
+#[test]
+fn test_checkin_delegate() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let phone = scenario.wallet(LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "checkin-delegate", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let ticket = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+
+    // Only the owner sets a delegate, and not to themselves
+    assert_eq!(
+        set_checkin_delegate(&mut scenario, &phone, &ticket, &phone).unwrap_err(),
+        program_error(EventError::InvalidOwner)
+    );
+    assert_eq!(
+        set_checkin_delegate(&mut scenario, &alice, &ticket, &alice).unwrap_err(),
+        program_error(EventError::InvalidCheckinDelegate)
+    );
+    set_checkin_delegate(&mut scenario, &alice, &ticket, &phone).unwrap();
+    assert_eq!(scenario.state::<Ticket>(&ticket.ticket).checkin_delegate, phone);
+
+    // The delegate can't move the ticket out of the owner's wallet
+    assert_eq!(
+        scenario.transfer_ticket(&phone, &bob, &event, &tier, &ticket, None).unwrap_err(),
+        program_error(EventError::InvalidOwner)
+    );
+
+    // The delegate checks in with the NFT still in the owner's wallet
+    scenario.process(
+        mythra_program::accounts::MarkTicketUsed {
+            ticket: ticket.ticket,
+            tier,
+            event,
+            owner_token_account: Some(ticket.token_account),
+            owner: phone,
+            blocklist_entry: pda::blocklist(&event, &alice),
+            gate_operator: Pubkey::new_unique(),
+            token_program: scenario.token_program(&ticket.mint),
+        },
+        mythra_program::instruction::MarkTicketUsed {},
+    ).unwrap();
+    let used: Ticket = scenario.state(&ticket.ticket);
+    assert!(used.used);
+    assert_eq!(used.owner, alice);
+    assert_eq!(scenario.token_amount(&ticket.token_account), 1);
+
+    // A transfer clears the delegate
+    let second = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    set_checkin_delegate(&mut scenario, &alice, &second, &phone).unwrap();
+    scenario.transfer_ticket(&alice, &bob, &event, &tier, &second, None).unwrap();
+    let transferred: Ticket = scenario.state(&second.ticket);
+    assert_eq!(transferred.owner, bob);
+    assert_eq!(transferred.checkin_delegate, Pubkey::default());
+}
+
+#[test]
+fn test_refund_policy_schedule() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let start_ts = scenario.now() + 60 * DAY;
+    let event = scenario.create_event_at(&organizer, "refund-policy", start_ts, start_ts + DAY, 100, 0);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+
+    // 100% until 30 days out, 50% until 7 days, nothing after
+    assert_eq!(
+        set_refund_policy(&mut scenario, &organizer, &event, &[(7 * DAY, 5000), (30 * DAY, 10000)]).unwrap_err(),
+        program_error(EventError::InvalidRefundPolicy)
+    );
+    set_refund_policy(&mut scenario, &organizer, &event, &[(30 * DAY, 10000), (7 * DAY, 5000)]).unwrap();
+
+    let tickets: Vec<TicketKeys> = (0..4)
+        .map(|_| scenario.purchase_ticket(&alice, &event, &tier).unwrap())
+        .collect();
+
+    // Buyers keep the terms they bought under
+    assert_eq!(
+        set_refund_policy(&mut scenario, &organizer, &event, &[]).unwrap_err(),
+        program_error(EventError::RefundPolicyLocked)
+    );
+
+    // Each step applies up to and including its cutoff
+    let boundaries = [
+        (start_ts - 30 * DAY, PRICE),
+        (start_ts - 30 * DAY + 1, PRICE / 2),
+        (start_ts - 7 * DAY, PRICE / 2),
+    ];
+    for ((now, refund), keys) in boundaries.into_iter().zip(&tickets) {
+        scenario.warp_to(now);
+        let balance = scenario.lamports(&alice);
+        request_refund(&mut scenario, &alice, &event, &tier, keys).unwrap();
+        assert_eq!(scenario.lamports(&alice) - balance, refund);
+
+        let ticket: Ticket = scenario.state(&keys.ticket);
+        assert!(ticket.refunded);
+        assert_eq!(ticket.refund_ts, now);
+        assert_eq!(scenario.token_amount(&keys.token_account), 0);
+    }
+
+    // Past the last cutoff nothing is refunded and the ticket stays put
+    scenario.warp_to(start_ts - 7 * DAY + 1);
+    assert_eq!(
+        request_refund(&mut scenario, &alice, &event, &tier, &tickets[3]).unwrap_err(),
+        program_error(EventError::RefundNotAvailable)
+    );
+    assert_eq!(scenario.token_amount(&tickets[3].token_account), 1);
+
+    // The retained half stays in escrow as ticket revenue
+    let event_state: Event = scenario.state(&event);
+    assert_eq!((event_state.tickets_sold, event_state.tickets_refunded), (1, 3));
+    assert_eq!(event_state.ticket_revenue, 4 * PRICE - 2 * PRICE);
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 1);
+}
+
+#[test]
+fn test_refund_fee() {
+    let mut scenario = ScenarioBuilder::new();
+    let admin = scenario.wallet(LAMPORTS_PER_SOL);
+    let treasury = scenario.wallet(LAMPORTS_PER_SOL);
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    scenario.initialize_config(&admin, &treasury);
+    set_refund_fee(&mut scenario, &admin, 250);
+
+    // An odd price so the 2.5% fee doesn't divide evenly
+    let price = PRICE + 39;
+    let start_ts = scenario.now() + 60 * DAY;
+    let event = scenario.create_event_at(&organizer, "refund-fee", start_ts, start_ts + DAY, 100, 0);
+    let tier = scenario.create_tier(&organizer, &event, "ga", price, 10);
+    set_refund_policy(&mut scenario, &organizer, &event, &[(30 * DAY, 10000), (7 * DAY, 5000)]).unwrap();
+    let tickets: Vec<TicketKeys> = (0..4)
+        .map(|_| scenario.purchase_ticket(&alice, &event, &tier).unwrap())
+        .collect();
+
+    let refund = |scenario: &mut ScenarioBuilder, keys: &TicketKeys| {
+        let (alice_before, treasury_before) = (scenario.lamports(&alice), scenario.lamports(&treasury));
+        request_refund(scenario, &alice, &event, &tier, keys).unwrap();
+        (scenario.lamports(&alice) - alice_before, scenario.lamports(&treasury) - treasury_before)
+    };
+
+    // The fee rounds down, leaving the dust with the buyer
+    assert_eq!(refund(&mut scenario, &tickets[0]), (price - 25_000_000, 25_000_000));
+
+    // It's taken from what is refunded, not the full price
+    scenario.warp_to(start_ts - 10 * DAY);
+    let half = price / 2;
+    assert_eq!(refund(&mut scenario, &tickets[1]), (half - 12_500_000, 12_500_000));
+
+    // Only the platform admin can waive the fee for an event
+    let set_event_fee = |scenario: &mut ScenarioBuilder, signer: &Pubkey, refund_fee_bps: Option<u16>| {
+        scenario.process(
+            mythra_program::accounts::SetEventRefundFee {
+                event,
+                platform_config: pda::config(),
+                admin: *signer,
+            },
+            mythra_program::instruction::SetEventRefundFee { refund_fee_bps },
+        )
+    };
+    assert_eq!(
+        set_event_fee(&mut scenario, &organizer, Some(0)).unwrap_err(),
+        program_error(EventError::UnauthorizedPlatformAdmin)
+    );
+    set_event_fee(&mut scenario, &admin, Some(0)).unwrap();
+    assert_eq!(refund(&mut scenario, &tickets[2]), (half, 0));
+
+    // Cancellations are full refunds even with a fee in force
+    set_event_fee(&mut scenario, &admin, Some(1000)).unwrap();
+    scenario.process(
+        mythra_program::accounts::CancelEvent {
+            event,
+            ticket_escrow: pda::ticket_escrow(&event),
+            campaign: None,
+            campaign_escrow: None,
+            authority: organizer,
+        },
+        mythra_program::instruction::CancelEvent {},
+    ).unwrap();
+
+    // Paid out of what the purchases left in the ticket escrow
+    let escrow = pda::ticket_escrow(&event);
+    let (alice_before, treasury_before, escrow_before) =
+        (scenario.lamports(&alice), scenario.lamports(&treasury), scenario.lamports(&escrow));
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &tickets[3]).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, price);
+    assert_eq!(scenario.lamports(&treasury), treasury_before);
+    assert_eq!(escrow_before - scenario.lamports(&escrow), price);
+}
+
+#[test]
+fn test_withdrawal_leaves_pending_order_payments() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "orders", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let escrow = pda::ticket_escrow(&event);
+    scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+
+    // Bob pays for an order that is never fulfilled
+    let order = scenario.create_order(&bob, &event, &tier, "bob-1").unwrap();
+    let event_state: Event = scenario.state(&event);
+    assert_eq!((event_state.ticket_revenue, event_state.pending_order_funds), (2 * PRICE, PRICE));
+
+    // Only Alice's sale can be withdrawn; Bob's payment stays in escrow
+    assert_eq!(
+        scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawFunds { amount: 2 * PRICE })
+            .unwrap_err(),
+        program_error(EventError::InsufficientBalance)
+    );
+    scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawAllFunds {}).unwrap();
+    assert!(scenario.lamports(&escrow) >= PRICE);
+
+    // So the order can still be canceled and refunded in full
+    let bob_before = scenario.lamports(&bob);
+    let order_rent = rent_exempt(scenario.account_len(&order));
+    scenario.process(
+        mythra_program::accounts::CancelOrder {
+            order,
+            event,
+            tier,
+            buyer_record: Some(pda::buyer_record(&tier, &bob)),
+            ticket_escrow: escrow,
+            buyer: bob,
+            canceler: bob,
+            system_program: system_program::ID,
+        },
+        mythra_program::instruction::CancelOrder {},
+    ).unwrap();
+    assert_eq!(scenario.lamports(&bob) - bob_before, PRICE + order_rent);
+    let event_state: Event = scenario.state(&event);
+    assert_eq!((event_state.ticket_revenue, event_state.pending_order_funds), (PRICE, 0));
+}
+
+#[test]
+fn test_ticket_escrow_holds_sales() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "one-escrow", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let escrow = pda::ticket_escrow(&event);
+
+    // The escrow always holds exactly the sales not yet withdrawn
+    let assert_escrow_holds_sales = |scenario: &ScenarioBuilder| {
+        let event_state: Event = scenario.state(&event);
+        assert_eq!(
+            scenario.lamports(&escrow),
+            event_state.ticket_revenue - event_state.total_withdrawn
+        );
+    };
+
+    let tickets: Vec<TicketKeys> = (0..3)
+        .map(|_| scenario.purchase_ticket(&alice, &event, &tier).unwrap())
+        .collect();
+    assert_eq!(scenario.state::<Event>(&event).ticket_revenue, 3 * PRICE);
+    assert_escrow_holds_sales(&scenario);
+
+    scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawFunds { amount: PRICE }).unwrap();
+    assert_eq!(scenario.state::<Event>(&event).total_withdrawn, PRICE);
+    assert_escrow_holds_sales(&scenario);
+
+    let alice_before = scenario.lamports(&alice);
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &tickets[0]).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, PRICE);
+    assert_escrow_holds_sales(&scenario);
+
+    // Nothing beyond the rent reserve can be withdrawn
+    assert_eq!(
+        scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawFunds { amount: PRICE })
+            .unwrap_err(),
+        program_error(EventError::InsufficientBalance)
+    );
+}
+
+#[test]
+fn test_refund_claws_back_withdrawn_platform_fee() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let start_ts = scenario.now() + 30 * DAY;
+    let event = scenario.create_event_at(&organizer, "clawback", start_ts, start_ts + DAY, 100, 1000);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let refunded = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+
+    // The organizer withdraws every sale, 10% of it into the fee vault
+    let escrow = pda::ticket_escrow(&event);
+    scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawAllFunds {}).unwrap();
+    let withdrawn = 3 * PRICE - rent_exempt(0);
+    assert_eq!(scenario.state::<Event>(&event).platform_fees_paid, withdrawn / 10);
+    assert_eq!(scenario.lamports(&escrow), rent_exempt(0));
+
+    // Refunding a withdrawn sale takes the platform's share back from the
+    // vault; the organizer covers the rest
+    let clawback = withdrawn / 10 - 2 * PRICE / 10;
+    assert_eq!(
+        refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &refunded).unwrap_err(),
+        program_error(EventError::InsufficientBalance)
+    );
+    scenario.transfer_lamports(&organizer, &escrow, PRICE - clawback);
+
+    // Leaving the vault out doesn't skip the clawback
+    let vault = pda::platform_fees(&event);
+    let mut metas = refund_ticket_accounts(&scenario, &organizer, &alice, &event, &tier, &refunded);
+    for meta in metas.iter_mut().filter(|meta| meta.pubkey == vault) {
+        *meta = AccountMeta::new_readonly(mythra_program::ID, false);
+    }
+    assert_eq!(
+        scenario.process(metas, mythra_program::instruction::RefundTicket { refund_amount: None }).unwrap_err(),
+        program_error(EventError::PlatformFeeVaultRequired)
+    );
+
+    let vault_before = scenario.lamports(&vault);
+    let alice_before = scenario.lamports(&alice);
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &refunded).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, PRICE);
+    assert_eq!(vault_before - scenario.lamports(&vault), clawback);
+    assert_eq!(scenario.lamports(&escrow), rent_exempt(0));
+
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.platform_fees_paid, 2 * PRICE / 10);
+    assert_eq!(event_state.ticket_revenue, 2 * PRICE);
+}
+
+#[test]
+fn test_refund_claim_registers_price_paid() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "claim-price", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let now = scenario.now();
+    scenario.process(
+        mythra_program::accounts::SetTierPromo { tier, event, authority: organizer },
+        mythra_program::instruction::SetTierPromo { price_lamports: PRICE / 2, start_ts: now, end_ts: now + DAY },
+    ).unwrap();
+    let promo = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    scenario.warp_by(DAY);
+    let regular = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+
+    // Withdrawn sales leave the escrow short, so cancellation opens claims
+    scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawAllFunds {}).unwrap();
+    cancel_event(&mut scenario, &organizer, &event);
+    assert!(scenario.state::<Event>(&event).shortfall_mode);
+
+    for keys in [&promo, &regular] {
+        scenario.process(
+            mythra_program::accounts::RegisterRefundClaim {
+                refund_claim: pda::refund_claim(&keys.ticket),
+                ticket: keys.ticket,
+                event,
+                owner: alice,
+                system_program: system_program::ID,
+            },
+            mythra_program::instruction::RegisterRefundClaim {},
+        ).unwrap();
+    }
+
+    // Each claim is worth what its buyer paid, not the tier's price
+    assert_eq!(scenario.state::<RefundClaim>(&pda::refund_claim(&promo.ticket)).amount, PRICE / 2);
+    assert_eq!(scenario.state::<RefundClaim>(&pda::refund_claim(&regular.ticket)).amount, PRICE);
+    assert_eq!(scenario.state::<Event>(&event).total_refund_claims, PRICE + PRICE / 2);
+}
+
+#[test]
+fn test_cancellation_refund_crank() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let cranker = scenario.wallet(LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "canceled", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 20);
+    let soulbound = create_tier_with_transfer_rules(&mut scenario, &organizer, &event, "named", false, false);
+    let tickets: Vec<TicketKeys> = (0..4)
+        .map(|_| scenario.purchase_ticket(&alice, &event, &tier).unwrap())
+        .collect();
+    let named = scenario.purchase_ticket(&bob, &event, &soulbound).unwrap();
+    fn with_holder(keys: &TicketKeys) -> (&TicketKeys, Pubkey) {
+        (keys, keys.token_account)
+    }
+
+    // Purchases approve the Ticket PDA to burn the NFT
+    assert_eq!(scenario.token_delegate(&tickets[0].token_account), (Some(tickets[0].ticket), 1));
+
+    // Nothing is refunded until the event is canceled
+    assert_eq!(
+        process_cancellation_refunds(&mut scenario, &cranker, &event, &[with_holder(&tickets[0])]).unwrap_err(),
+        program_error(EventError::EventNotCanceled)
+    );
+
+    // A resold NFT left the delegated token account
+    let resold = scenario.transfer_ticket(&alice, &bob, &event, &tier, &tickets[3], None).unwrap();
+    mark_ticket_used(&mut scenario, &alice, &event, &tier, &tickets[2]).unwrap();
+    cancel_event(&mut scenario, &organizer, &event);
+
+    // A partial batch refunds only the tickets it names, without the owner
+    // or the organizer signing
+    let alice_before = scenario.lamports(&alice);
+    process_cancellation_refunds(&mut scenario, &cranker, &event, &[with_holder(&tickets[0])]).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, PRICE);
+    assert!(scenario.state::<Ticket>(&tickets[0].ticket).refunded);
+    assert_eq!(scenario.token_amount(&tickets[0].token_account), 0);
+    assert!(!scenario.state::<Ticket>(&tickets[1].ticket).refunded);
+
+    // Refunded, used and resold tickets are skipped; the rest of the batch
+    // (including the frozen soulbound ticket) still goes through
+    let (alice_before, bob_before) = (scenario.lamports(&alice), scenario.lamports(&bob));
+    process_cancellation_refunds(
+        &mut scenario,
+        &cranker,
+        &event,
+        &[
+            with_holder(&tickets[0]),
+            with_holder(&tickets[1]),
+            with_holder(&tickets[2]),
+            (&tickets[3], resold),
+            with_holder(&named),
+        ],
+    ).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, PRICE);
+    assert_eq!(scenario.lamports(&bob) - bob_before, PRICE);
+    assert!(scenario.state::<Ticket>(&tickets[1].ticket).refunded);
+    assert!(scenario.state::<Ticket>(&named.ticket).refunded);
+    assert_eq!(scenario.token_amount(&named.token_account), 0);
+    for keys in &tickets[2..] {
+        assert!(!scenario.state::<Ticket>(&keys.ticket).refunded);
+    }
+    assert_eq!(scenario.token_amount(&resold), 1);
+
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.tickets_refunded, 3);
+    assert_eq!(event_state.tickets_sold, 2);
+    assert_eq!(event_state.ticket_revenue, 2 * PRICE);
+
+    // A batch naming another ticket's holder aborts
+    let foreign = (&tickets[1], tickets[2].token_account);
+    assert_eq!(
+        process_cancellation_refunds(&mut scenario, &cranker, &event, &[foreign]).unwrap_err(),
+        program_error(EventError::InvalidBatchAccounts)
+    );
+}
+
+#[test]
+fn test_cancellation_refund_crank_escrow_exhausted() {
+    use mythra_program::instructions::MAX_CANCELLATION_REFUNDS_PER_BATCH;
+
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let cranker = scenario.wallet(LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "short", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 20);
+    let tickets: Vec<TicketKeys> = (0..3)
+        .map(|_| scenario.purchase_ticket(&alice, &event, &tier).unwrap())
+        .collect();
+    let batch: Vec<(&TicketKeys, Pubkey)> = tickets.iter().map(|keys| (keys, keys.token_account)).collect();
+    cancel_event(&mut scenario, &organizer, &event);
+
+    // The escrow loses more than a ticket's worth after the cancellation
+    let escrow = pda::ticket_escrow(&event);
+    scenario.drain(&escrow, PRICE + PRICE / 2);
+
+    // Tickets past what the escrow covers are skipped, not failed
+    let alice_before = scenario.lamports(&alice);
+    process_cancellation_refunds(&mut scenario, &cranker, &event, &batch).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, PRICE);
+    assert_eq!(scenario.lamports(&escrow), rent_exempt(0) + PRICE / 2);
+    assert!(scenario.state::<Ticket>(&tickets[0].ticket).refunded);
+    for keys in &tickets[1..] {
+        assert!(!scenario.state::<Ticket>(&keys.ticket).refunded);
+        assert_eq!(scenario.token_amount(&keys.token_account), 1);
+    }
+
+    // Topping the escrow back up lets a later crank finish the job
+    scenario.airdrop(&escrow, PRICE + PRICE / 2);
+    process_cancellation_refunds(&mut scenario, &cranker, &event, &batch).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, 3 * PRICE);
+    assert_eq!(scenario.lamports(&escrow), rent_exempt(0));
+    assert_eq!(scenario.state::<Event>(&event).tickets_refunded, 3);
+
+    // Oversized batches are rejected outright
+    let oversized: Vec<(&TicketKeys, Pubkey)> = vec![batch[0]; MAX_CANCELLATION_REFUNDS_PER_BATCH as usize + 1];
+    assert_eq!(
+        process_cancellation_refunds(&mut scenario, &cranker, &event, &oversized).unwrap_err(),
+        program_error(EventError::InvalidRefundBatchSize)
+    );
+}
+
+#[test]
+fn test_refund_frees_sold_out_seat() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(5 * LAMPORTS_PER_SOL);
+    let carol = scenario.wallet(5 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "resell-seat", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 2);
+    let refunded = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    scenario.purchase_ticket(&bob, &event, &tier).unwrap();
+    assert_eq!(
+        scenario.purchase_ticket(&carol, &event, &tier).unwrap_err(),
+        program_error(EventError::ExceedsTotalSupply)
+    );
+
+    // The refund gives the seat and its revenue back
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &refunded).unwrap();
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 1);
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(event_state.tickets_sold, 1);
+    assert_eq!(event_state.ticket_revenue, PRICE);
+
+    // So the sold-out tier can sell it again, and only once
+    scenario.purchase_ticket(&carol, &event, &tier).unwrap();
+    assert_eq!(scenario.state::<TicketTier>(&tier).current_supply, 2);
+    assert_eq!(scenario.state::<Event>(&event).ticket_revenue, 2 * PRICE);
+    assert_eq!(
+        scenario.purchase_ticket(&alice, &event, &tier).unwrap_err(),
+        program_error(EventError::ExceedsTotalSupply)
+    );
+}
+
+#[test]
+fn test_refund_ticket_signers() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+    let bob = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let event = scenario.create_event(&organizer, "refund-signers", 100);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let soulbound = create_tier_with_transfer_rules(&mut scenario, &organizer, &event, "named", false, false);
+    let kept = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    let named = scenario.purchase_ticket(&alice, &event, &soulbound).unwrap();
+    let resold = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    // Bob's own ticket keeps the escrow above its rent reserve
+    scenario.purchase_ticket(&bob, &event, &tier).unwrap();
+    let escrow = pda::ticket_escrow(&event);
+    assert_eq!(scenario.lamports(&escrow), 4 * PRICE);
+
+    // The buyer alone can't approve a refund
+    assert_eq!(
+        refund_ticket(&mut scenario, &alice, &alice, &event, &tier, &kept).unwrap_err(),
+        program_error(EventError::UnauthorizedRefund)
+    );
+
+    // The organizer alone can: the Ticket PDA burns the NFT it was approved for
+    let alice_before = scenario.lamports(&alice);
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &kept).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, PRICE);
+    assert_eq!(scenario.token_amount(&kept.token_account), 0);
+    assert!(scenario.state::<Ticket>(&kept.ticket).refunded);
+
+    // Frozen soulbound NFTs are thawed and burned the same way
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &soulbound, &named).unwrap();
+    assert_eq!(scenario.token_amount(&named.token_account), 0);
+
+    // A resold NFT sits in a token account nobody approved, so its new
+    // holder has to co-sign
+    let bob_account = scenario.transfer_ticket(&alice, &bob, &event, &tier, &resold, None).unwrap();
+    let bob_keys = TicketKeys { token_account: bob_account, ..resold };
+    assert_eq!(
+        refund_ticket(&mut scenario, &organizer, &bob, &event, &tier, &bob_keys).unwrap_err(),
+        program_error(EventError::RefundNeedsBuyerSignature)
+    );
+
+    let mut metas = refund_ticket_accounts(&scenario, &organizer, &bob, &event, &tier, &bob_keys);
+    for meta in metas.iter_mut().filter(|meta| meta.pubkey == bob) {
+        meta.is_signer = true;
+    }
+    let bob_before = scenario.lamports(&bob);
+    scenario.process(metas, mythra_program::instruction::RefundTicket { refund_amount: None }).unwrap();
+    assert_eq!(scenario.lamports(&bob) - bob_before, PRICE);
+    assert_eq!(scenario.token_amount(&bob_account), 0);
+
+    // The slot freed is Alice's, who bought it, not Bob's
+    assert_eq!(scenario.state::<BuyerRecord>(&pda::buyer_record(&tier, &alice)).purchased_count, 0);
+    assert_eq!(scenario.state::<BuyerRecord>(&pda::buyer_record(&tier, &bob)).purchased_count, 1);
+
+    // All three refunds came out of the purchases
+    assert_eq!(scenario.lamports(&escrow), PRICE);
+}
+
+#[test]
+fn test_close_ticket() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let start_ts = scenario.now() + DAY;
+    let end_ts = start_ts + DAY;
+    let event = scenario.create_event_at(&organizer, "close-ticket", start_ts, end_ts, 100, 0);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let used = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    let refunded = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    let no_show = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &refunded).unwrap();
+
+    let close_ticket = |scenario: &mut ScenarioBuilder, closer: &Pubkey, keys: &TicketKeys| {
+        scenario.process(
+            mythra_program::accounts::CloseTicket {
+                ticket: keys.ticket,
+                event,
+                campaign: None,
+                ticket_index: None,
+                owner: alice,
+                closer: *closer,
+            },
+            mythra_program::instruction::CloseTicket {},
+        )
+    };
+
+    // Nothing closes before the event ends, used or not
+    scenario.warp_to(start_ts);
+    mark_ticket_used(&mut scenario, &alice, &event, &tier, &used).unwrap();
+    for keys in [&used, &refunded, &no_show] {
+        assert_eq!(
+            close_ticket(&mut scenario, &alice, keys).unwrap_err(),
+            program_error(EventError::EventNotEnded)
+        );
+    }
+
+    // Afterwards the owner gets the rent back
+    scenario.warp_to(end_ts + 1);
+    let rent = rent_exempt(Ticket::SPACE);
+    for keys in [&used, &refunded] {
+        let alice_before = scenario.lamports(&alice);
+        close_ticket(&mut scenario, &alice, keys).unwrap();
+        assert_eq!(scenario.lamports(&alice) - alice_before, rent);
+        assert!(!scenario.exists(&keys.ticket));
+    }
+
+    // The organizer can only sweep what's left after the grace period,
+    // and the rent still goes to the owner
+    assert_eq!(
+        close_ticket(&mut scenario, &organizer, &no_show).unwrap_err(),
+        program_error(EventError::UnauthorizedTicketClose)
+    );
+    scenario.warp_to(end_ts + Event::TICKET_CLOSE_GRACE_SECONDS + 1);
+    let alice_before = scenario.lamports(&alice);
+    close_ticket(&mut scenario, &organizer, &no_show).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, rent);
+    assert!(!scenario.exists(&no_show.ticket));
+}
+
+#[test]
+fn test_close_ticket_of_canceled_event() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let start_ts = scenario.now() + DAY;
+    let end_ts = start_ts + DAY;
+    let event = scenario.create_event_at(&organizer, "close-canceled", start_ts, end_ts, 100, 0);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    let keys = scenario.purchase_ticket(&alice, &event, &tier).unwrap();
+    cancel_event(&mut scenario, &organizer, &event);
+
+    let close_ticket = |scenario: &mut ScenarioBuilder| {
+        scenario.process(
+            mythra_program::accounts::CloseTicket {
+                ticket: keys.ticket,
+                event,
+                campaign: None,
+                ticket_index: None,
+                owner: alice,
+                closer: organizer,
+            },
+            mythra_program::instruction::CloseTicket {},
+        )
+    };
+
+    // Past the grace period the authority still can't sweep a ticket
+    // whose holder hasn't been refunded
+    scenario.warp_to(end_ts + Event::TICKET_CLOSE_GRACE_SECONDS + 1);
+    assert_eq!(
+        close_ticket(&mut scenario).unwrap_err(),
+        program_error(EventError::TicketNotRefunded)
+    );
+
+    process_cancellation_refunds(&mut scenario, &organizer, &event, &[(&keys, keys.token_account)]).unwrap();
+    close_ticket(&mut scenario).unwrap();
+    assert!(!scenario.exists(&keys.ticket));
+}
+
+#[test]
+fn test_escrow_bumps_stored() {
+    let mut scenario = ScenarioBuilder::new();
+    let organizer = scenario.wallet(10 * LAMPORTS_PER_SOL);
+    let alice = scenario.wallet(20 * LAMPORTS_PER_SOL);
+
+    let start_ts = scenario.now() + 60 * DAY;
+    let event = scenario.create_event_at(&organizer, "escrow-bumps", start_ts, start_ts + DAY, 100, 1000);
+    let tier = scenario.create_tier(&organizer, &event, "ga", PRICE, 10);
+    set_refund_policy(&mut scenario, &organizer, &event, &[(30 * DAY, 10000)]).unwrap();
+
+    // create_event records the ticket escrow's canonical bump
+    let escrow = pda::ticket_escrow(&event);
+    let event_state: Event = scenario.state(&event);
+    assert_eq!(
+        event_state.ticket_escrow_bump,
+        Pubkey::find_program_address(&[b"ticket_escrow", event.as_ref()], &mythra_program::ID).1
+    );
+
+    // Purchases pay into the ticket escrow
+    let tickets: Vec<TicketKeys> = (0..4)
+        .map(|_| scenario.purchase_ticket(&alice, &event, &tier).unwrap())
+        .collect();
+    assert_eq!(scenario.lamports(&escrow), 4 * PRICE);
+
+    // It signs a buyer refund with its stored bump
+    let alice_before = scenario.lamports(&alice);
+    request_refund(&mut scenario, &alice, &event, &tier, &tickets[0]).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, PRICE);
+
+    // A withdrawal from the same escrow
+    let organizer_before = scenario.lamports(&organizer);
+    scenario.withdraw_funds(&organizer, &event, mythra_program::instruction::WithdrawFunds { amount: PRICE }).unwrap();
+    assert_eq!(scenario.lamports(&escrow), 2 * PRICE);
+    assert_eq!(
+        scenario.lamports(&organizer) - organizer_before,
+        PRICE - PRICE / 10 - rent_exempt(mythra_program::state::PlatformFeeVault::LEN)
+    );
+
+    // And an organizer refund, still from the same escrow
+    let alice_before = scenario.lamports(&alice);
+    refund_ticket(&mut scenario, &organizer, &alice, &event, &tier, &tickets[1]).unwrap();
+    assert_eq!(scenario.lamports(&alice) - alice_before, PRICE);
+    assert_eq!(scenario.lamports(&escrow), PRICE);
+}
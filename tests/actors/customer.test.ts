@@ -117,12 +117,19 @@ describe("🎫 Customer (Ticket Buyer) Actions on Devnet", () => {
     await program.methods
       .createTicketTier(
         tierId,
-        "https://mythra.com/tiers/general.json",
-        new BN(0.01 * anchor.web3.LAMPORTS_PER_SOL), // 0.01 SOL (cheap for testing)
-        50,
-        250,
-        0,
-        true
+        {
+          metadataUri: "https://mythra.com/tiers/general.json",
+          priceLamports: new BN(0.01 * anchor.web3.LAMPORTS_PER_SOL), // 0.01 SOL (cheap for testing)
+          maxSupply: 50,
+          royaltyBps: 250,
+          tierIndex: 0,
+          resaleEnabled: true,
+          maxPerWallet: 0, // No per-wallet limit
+          saleStartTs: new BN(0), // Sale opens immediately
+          saleEndTs: new BN(0), // Sale runs until sold out
+          maxResalePriceLamports: new BN(0), // Resale uncapped
+          transferable: true,
+        }
       )
       .accountsPartial({
         tier: tierPda,
@@ -218,7 +225,7 @@ describe("🎫 Customer (Ticket Buyer) Actions on Devnet", () => {
       const escrowBefore = await provider.connection.getBalance(escrowPda);
       
       const tx = await program.methods
-        .purchaseTicket()
+        .purchaseTicket([], false)
         .accountsPartial({
           ticket: customer1TicketPda,
           event: eventPda,
@@ -287,7 +294,7 @@ describe("🎫 Customer (Ticket Buyer) Actions on Devnet", () => {
       );
       
       const tx = await program.methods
-        .purchaseTicket()
+        .purchaseTicket([], false)
         .accountsPartial({
           ticket: ticketPda,
           event: eventPda,
@@ -106,12 +106,19 @@ describe("🎭 Event Organizer Actions on Devnet", () => {
       const tx = await program.methods
         .createTicketTier(
           tierId,
-          "https://mythra.com/tiers/vip.json",
-          new BN(10_000_000), // 0.01 SOL (very cheap for testing)
-          100,  // Max supply
-          500,  // 5% royalty
-          0,    // No specific event tier index
-          true  // Resale enabled
+          {
+            metadataUri: "https://mythra.com/tiers/vip.json",
+            priceLamports: new BN(10_000_000), // 0.01 SOL (very cheap for testing)
+            maxSupply: 100,
+            royaltyBps: 500, // 5% royalty
+            tierIndex: 0, // No specific event tier index
+            resaleEnabled: true,
+            maxPerWallet: 0, // No per-wallet limit
+            saleStartTs: new BN(0), // Sale opens immediately
+            saleEndTs: new BN(0), // Sale runs until sold out
+            maxResalePriceLamports: new BN(0), // Resale uncapped
+            transferable: true,
+          }
         )
         .accountsPartial({
           tier: tierPda,
@@ -147,12 +154,19 @@ describe("🎭 Event Organizer Actions on Devnet", () => {
       const tx = await program.methods
         .createTicketTier(
           tierId,
-          "https://mythra.com/tiers/general.json",
-          new BN(5_000_000), // 0.005 SOL (very cheap for testing)
-          500,  // Max supply
-          250,  // 2.5% royalty
-          1,
-          true
+          {
+            metadataUri: "https://mythra.com/tiers/general.json",
+            priceLamports: new BN(5_000_000), // 0.005 SOL (very cheap for testing)
+            maxSupply: 500,
+            royaltyBps: 250, // 2.5% royalty
+            tierIndex: 1,
+            resaleEnabled: true,
+            maxPerWallet: 0, // No per-wallet limit
+            saleStartTs: new BN(0), // Sale opens immediately
+            saleEndTs: new BN(0), // Sale runs until sold out
+            maxResalePriceLamports: new BN(0), // Resale uncapped
+            transferable: true,
+          }
         )
         .accountsPartial({
           tier: generalTierPda,
@@ -179,12 +193,22 @@ describe("🎭 Event Organizer Actions on Devnet", () => {
         .updateEvent({
           metadataUri: "https://mythra.com/events/updated-metadata.json",
           endTs: eventAccount.endTs,
+          startTs: null,
           platformSplitBps: eventAccount.platformSplitBps,
           treasury: eventAccount.treasury,
+          maintainTicketIndex: null,
+          allowSalesDuringEvent: null,
+          checkinMessageVersion: null,
+          backerPresaleSeconds: null,
+          backerPresaleMinContribution: null,
+          locales: null,
+          compactEvents: null,
+          refundPolicy: null,
         })
         .accountsPartial({
           event: eventPda,
           authority: organizer.publicKey,
+          newTreasury: eventAccount.treasury,
         })
         .rpc();
       
@@ -102,11 +102,17 @@ describe("💰 Investor/DAO Actions on Devnet", () => {
       const fundingGoal = new BN(0.1 * anchor.web3.LAMPORTS_PER_SOL); // 0.1 SOL goal (minimum for campaign validation)
       const deadline = new BN(Math.floor(Date.now() / 1000) + 86400 * 30); // 30 days
       
+      const [campaignEscrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign_escrow"), campaignPda.toBuffer()],
+        program.programId
+      );
+      
       const tx = await program.methods
-        .createCampaign(fundingGoal, deadline)
+        .createCampaign(fundingGoal, deadline, 6000, 3500, 500, { lamports: {} })
         .accountsPartial({
           event: eventPda,
           campaign: campaignPda,
+          campaignEscrow: campaignEscrowPda,
           organizer: organizer.publicKey,
           authority: organizer.publicKey,
           systemProgram: SystemProgram.programId,
@@ -323,10 +329,13 @@ describe("💰 Investor/DAO Actions on Devnet", () => {
           new BN(0.08 * anchor.web3.LAMPORTS_PER_SOL), // 0.08 SOL (80% of raised funds)
           "Event production budget breakdown",
           milestones,
-          new BN(20) // 20 seconds voting period (for testing)
+          new BN(20), // 20 seconds voting period (for testing)
+          false, // No per-milestone release votes
+          { linear: {} } // One lamport, one vote
         )
         .accountsPartial({
           campaign: campaignPda,
+          event: eventPda,
           budget: budgetPda,
           organizer: organizer.publicKey,
           systemProgram: SystemProgram.programId,
@@ -349,12 +358,12 @@ describe("💰 Investor/DAO Actions on Devnet", () => {
       );
       
       const [votePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("budget_vote"), budgetPda.toBuffer(), investor1.publicKey.toBuffer()],
+        [Buffer.from("budget_vote"), budgetPda.toBuffer(), Buffer.from([0]), investor1.publicKey.toBuffer()],
         program.programId
       );
       
       const tx = await program.methods
-        .voteOnBudget(true) // Approve
+        .voteOnBudget({ approve: {} })
         .accountsPartial({
           budget: budgetPda,
           campaign: campaignPda,
@@ -380,12 +389,12 @@ describe("💰 Investor/DAO Actions on Devnet", () => {
       );
       
       const [votePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("budget_vote"), budgetPda.toBuffer(), investor2.publicKey.toBuffer()],
+        [Buffer.from("budget_vote"), budgetPda.toBuffer(), Buffer.from([0]), investor2.publicKey.toBuffer()],
         program.programId
       );
       
       const tx = await program.methods
-        .voteOnBudget(true)
+        .voteOnBudget({ approve: {} })
         .accountsPartial({
           budget: budgetPda,
           campaign: campaignPda,
@@ -415,6 +424,7 @@ describe("💰 Investor/DAO Actions on Devnet", () => {
         .finalizeBudgetVote()
         .accountsPartial({
           budget: budgetPda,
+          campaign: campaignPda,
         })
         .rpc();
       
@@ -98,12 +98,19 @@ describe("🏛️ Mythra Platform Operations on Devnet", () => {
     await program.methods
       .createTicketTier(
         tierId,
-        "https://mythra.com/tiers/general.json",
-        new BN(0.01 * anchor.web3.LAMPORTS_PER_SOL), // 0.01 SOL (minimal for testing)
-        50,
-        250,
-        0,
-        true
+        {
+          metadataUri: "https://mythra.com/tiers/general.json",
+          priceLamports: new BN(0.01 * anchor.web3.LAMPORTS_PER_SOL), // 0.01 SOL (minimal for testing)
+          maxSupply: 50,
+          royaltyBps: 250,
+          tierIndex: 0,
+          resaleEnabled: true,
+          maxPerWallet: 0, // No per-wallet limit
+          saleStartTs: new BN(0), // Sale opens immediately
+          saleEndTs: new BN(0), // Sale runs until sold out
+          maxResalePriceLamports: new BN(0), // Resale uncapped
+          transferable: true,
+        }
       )
       .accountsPartial({
         tier: tierPda,
@@ -156,7 +163,7 @@ describe("🏛️ Mythra Platform Operations on Devnet", () => {
       );
       
       await program.methods
-        .purchaseTicket()
+        .purchaseTicket([], false)
         .accountsPartial({
           ticket: ticketPda,
           event: eventPda,
@@ -231,6 +238,8 @@ describe("🏛️ Mythra Platform Operations on Devnet", () => {
         .markTicketUsed()
         .accountsPartial({
           ticket: ticketPda,
+          tier: tierPda,
+          event: eventPda,
           ownerTokenAccount: customerTokenAccount,
           owner: customer.publicKey,
           gateOperator: organizer.publicKey,
@@ -242,6 +251,13 @@ describe("🏛️ Mythra Platform Operations on Devnet", () => {
       console.log(`✅ Ticket marked as used`);
       console.log(`   Transaction: ${tx}`);
       
+      // Full TicketUsed event (compact_events is off by default)
+      const txDetails = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      console.log(`   Compute units: ${txDetails?.meta?.computeUnitsConsumed}`);
+      
       const ticketAccount = await program.account.ticket.fetch(ticketPda);
       assert.equal(ticketAccount.used, true);
       